@@ -0,0 +1,93 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::Assertion;
+use math::StarkField;
+use utils::collections::{vec, Vec};
+
+// LAGRANGE BOUNDARY CONSTRAINTS
+// ================================================================================================
+/// A set of assertions embedding values at arbitrary steps of a single register, where values
+/// at consecutive steps are batched together.
+///
+/// Asserting $k$ values at $k$ steps via [single](Assertion::single) assertions results in $k$
+/// boundary constraints, each with its own divisor $(x - g^{s_i})$. When values are asserted at
+/// consecutive steps $a, a + 1, ..., a + k - 1$, all of them can instead be described by a single
+/// [consecutive](Assertion::consecutive) assertion. The resulting boundary constraint has divisor
+/// $\prod_{i=0}^{k-1}(x - g^{a + i})$, and its value polynomial is the Lagrange interpolant of
+/// the asserted values over points $g^{a}, ..., g^{a + k - 1}$.
+///
+/// This struct splits the provided values into runs of consecutive steps and creates a single
+/// consecutive assertion for each run (and a single-value assertion for each isolated step). The
+/// resulting assertions can be returned from [Air::get_assertions()](crate::Air::get_assertions)
+/// as any other assertions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LagrangeBoundaryConstraints<B: StarkField> {
+    assertions: Vec<Assertion<B>>,
+}
+
+impl<B: StarkField> LagrangeBoundaryConstraints<B> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns assertions requiring that values in the specified `register` are equal to the
+    /// provided values at the steps with which they are paired in `values`.
+    ///
+    /// The values can be specified in any order.
+    ///
+    /// # Panics
+    /// Panics if `values` is empty or contains more than one value for the same step.
+    pub fn new(register: usize, values: &[(usize, B)]) -> Self {
+        assert!(
+            !values.is_empty(),
+            "at least one value must be provided for register {}",
+            register
+        );
+        let mut values = values.to_vec();
+        values.sort_by_key(|&(step, _)| step);
+
+        let mut assertions = Vec::new();
+        let mut first_step = values[0].0;
+        let mut run = vec![values[0].1];
+        for &(step, value) in values.iter().skip(1) {
+            let last_step = first_step + run.len() - 1;
+            assert!(
+                step != last_step,
+                "more than one value provided for step {} of register {}",
+                step,
+                register
+            );
+            if step == last_step + 1 {
+                run.push(value);
+            } else {
+                assertions.push(Assertion::consecutive(register, first_step, run));
+                first_step = step;
+                run = vec![value];
+            }
+        }
+        assertions.push(Assertion::consecutive(register, first_step, run));
+
+        LagrangeBoundaryConstraints { assertions }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the assertions which describe the values.
+    ///
+    /// There is one assertion per run of consecutive steps, and thus, the assertions will result
+    /// in as many boundary constraint divisors.
+    pub fn assertions(&self) -> &[Assertion<B>] {
+        &self.assertions
+    }
+}
+
+impl<B: StarkField> IntoIterator for LagrangeBoundaryConstraints<B> {
+    type Item = Assertion<B>;
+    type IntoIter = vec::IntoIter<Assertion<B>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.assertions.into_iter()
+    }
+}
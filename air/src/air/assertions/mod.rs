@@ -8,9 +8,12 @@ use core::{
     cmp::{Ord, Ordering, PartialOrd},
     fmt::{Display, Formatter},
 };
-use math::StarkField;
+use math::FieldElement;
 use utils::collections::Vec;
 
+mod lagrange;
+pub use lagrange::LagrangeBoundaryConstraints;
+
 #[cfg(test)]
 mod tests;
 
@@ -19,6 +22,7 @@ mod tests;
 
 const MIN_STRIDE_LENGTH: usize = 2;
 const NO_STRIDE: usize = 0;
+const CONSECUTIVE_STRIDE: usize = 1;
 
 // ASSERTION
 // ================================================================================================
@@ -26,7 +30,7 @@ const NO_STRIDE: usize = 0;
 /// An assertion made against an execution trace.
 ///
 /// An assertion is always placed against a single register of an execution trace, but can cover
-/// multiple steps and multiple values. Specifically, there are three kinds of assertions:
+/// multiple steps and multiple values. Specifically, there are four kinds of assertions:
 ///
 /// 1. **Single** assertion - which requires that a value in a single cell of an execution trace
 ///    is equal to the specified value.
@@ -40,27 +44,31 @@ const NO_STRIDE: usize = 0;
 ///   lengths equal to powers of two. For example, we can specify that values in a register must
 ///   be equal to a sequence 1, 2, 3, 4 at steps 0, 8, 16, 24. That is, value at step 0 should be
 ///   equal to 1, value at step 8 should be equal to 2 etc.
+/// 4. **Consecutive** assertion - which requires that cells at consecutive steps of a single
+///    register are equal to the values from the provided list. The number of values does not
+///    need to be a power of two. For example, we can specify that values in a register must be
+///    equal to a sequence 1, 2, 3 at steps 5, 6, 7.
 ///
 /// Note that single and periodic assertions are succinct. That is, a verifier can evaluate them
-/// very efficiently. However, sequence assertions have liner complexity in the number of
-/// asserted values. Though, unless many thousands of values are asserted, practical impact of
-/// this linear complexity should be negligible.
+/// very efficiently. However, sequence and consecutive assertions have liner complexity in the
+/// number of asserted values. Though, unless many thousands of values are asserted, practical
+/// impact of this linear complexity should be negligible.
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct Assertion<B: StarkField> {
+pub struct Assertion<E: FieldElement> {
     pub(super) register: usize,
     pub(super) first_step: usize,
     pub(super) stride: usize,
-    pub(super) values: Vec<B>,
+    pub(super) values: Vec<E>,
 }
 
-impl<B: StarkField> Assertion<B> {
+impl<E: FieldElement> Assertion<E> {
     // CONSTRUCTORS
     // --------------------------------------------------------------------------------------------
     /// Returns an assertion against a single cell of an execution trace.
     ///
     /// The returned assertion requires that the value in the specified `register` at the specified
     /// `step` is equal to the provided `value`.
-    pub fn single(register: usize, step: usize, value: B) -> Self {
+    pub fn single(register: usize, step: usize, value: E) -> Self {
         Assertion {
             register,
             first_step: step,
@@ -79,7 +87,7 @@ impl<B: StarkField> Assertion<B> {
     /// Panics if:
     /// * `stride` is not a power of two, or is smaller than 2.
     /// * `first_step` is greater than `stride`.
-    pub fn periodic(register: usize, first_step: usize, stride: usize, value: B) -> Self {
+    pub fn periodic(register: usize, first_step: usize, stride: usize, value: E) -> Self {
         validate_stride(stride, first_step, register);
         Assertion {
             register,
@@ -100,7 +108,7 @@ impl<B: StarkField> Assertion<B> {
     /// * `stride` is not a power of two, or is smaller than 2.
     /// * `first_step` is greater than `stride`.
     /// * `values` is empty or number of values in not a power of two.
-    pub fn sequence(register: usize, first_step: usize, stride: usize, values: Vec<B>) -> Self {
+    pub fn sequence(register: usize, first_step: usize, stride: usize, values: Vec<E>) -> Self {
         validate_stride(stride, first_step, register);
         assert!(
             !values.is_empty(),
@@ -121,6 +129,36 @@ impl<B: StarkField> Assertion<B> {
         }
     }
 
+    /// Returns a multi-value assertion against consecutive cells of a single register.
+    ///
+    /// The returned assertion requires that values in the specified `register` must be equal to
+    /// the provided `values` at steps `first_step`, `first_step + 1`, `first_step + 2` etc. until
+    /// all values have been consumed.
+    ///
+    /// All constraints resulting from a consecutive assertion share a single divisor, and thus,
+    /// asserting many values at consecutive steps in this way is cheaper than making a separate
+    /// single-value assertion for each of the steps.
+    ///
+    /// # Panics
+    /// Panics if `values` is empty.
+    pub fn consecutive(register: usize, first_step: usize, values: Vec<E>) -> Self {
+        assert!(
+            !values.is_empty(),
+            "invalid assertion for register {}: number of asserted values must be greater than zero",
+            register
+        );
+        Assertion {
+            register,
+            first_step,
+            stride: if values.len() == 1 {
+                NO_STRIDE
+            } else {
+                CONSECUTIVE_STRIDE
+            },
+            values,
+        }
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -138,7 +176,7 @@ impl<B: StarkField> Assertion<B> {
 
     /// Returns the interval at which the assertion repeats in the execution trace.
     ///
-    /// For single value assertions, this will be 0.
+    /// For single value assertions, this will be 0; for consecutive assertions, this will be 1.
     pub fn stride(&self) -> usize {
         self.stride
     }
@@ -146,7 +184,7 @@ impl<B: StarkField> Assertion<B> {
     /// Returns asserted values.
     ///
     /// For single value and periodic assertions this will be a slice containing one value.
-    pub fn values(&self) -> &[B] {
+    pub fn values(&self) -> &[E] {
         &self.values
     }
 
@@ -160,9 +198,14 @@ impl<B: StarkField> Assertion<B> {
         self.stride != NO_STRIDE && self.values.len() == 1
     }
 
-    /// Returns true if this is a sequence assertion (many values, many steps).
+    /// Returns true if this is a sequence assertion (many values, many evenly spaced steps).
     pub fn is_sequence(&self) -> bool {
-        self.values.len() > 1
+        self.values.len() > 1 && self.stride != CONSECUTIVE_STRIDE
+    }
+
+    /// Returns true if this is a consecutive assertion (many values, many consecutive steps).
+    pub fn is_consecutive(&self) -> bool {
+        self.stride == CONSECUTIVE_STRIDE
     }
 
     // PUBLIC METHODS
@@ -171,13 +214,23 @@ impl<B: StarkField> Assertion<B> {
     /// Checks if this assertion overlaps with the provided assertion.
     ///
     /// Overlap is defined as asserting a value for the same step in the same register.
-    pub fn overlaps_with(&self, other: &Assertion<B>) -> bool {
+    pub fn overlaps_with(&self, other: &Assertion<E>) -> bool {
         if self.register != other.register {
             return false;
         }
         if self.first_step == other.first_step {
             return true;
         }
+
+        // consecutive assertions cover a contiguous range of steps; so, we check whether any
+        // step in this range is covered by the other assertion
+        if self.is_consecutive() {
+            return self.steps_range().any(|step| other.covers_step(step));
+        }
+        if other.is_consecutive() {
+            return other.steps_range().any(|step| self.covers_step(step));
+        }
+
         if self.stride == other.stride {
             return false;
         }
@@ -225,6 +278,7 @@ impl<B: StarkField> Assertion<B> {
     /// * For single assertion, `first_step` >= `trace_length`.
     /// * For periodic assertion, `stride` > `trace_length`.
     /// * For sequence assertion, `num_values` * `stride` != `trace_length`;
+    /// * For consecutive assertion, `first_step` + `num_values` > `trace_length`.
     pub fn validate_trace_length(&self, trace_length: usize) -> Result<(), AssertionError> {
         if !trace_length.is_power_of_two() {
             return Err(AssertionError::TraceLengthNotPowerOfTwo(trace_length));
@@ -243,6 +297,14 @@ impl<B: StarkField> Assertion<B> {
                     trace_length,
                 ));
             }
+        } else if self.is_consecutive() {
+            let last_step = self.first_step + self.values.len() - 1;
+            if last_step >= trace_length {
+                return Err(AssertionError::TraceLengthTooShort(
+                    (last_step + 1).next_power_of_two(),
+                    trace_length,
+                ));
+            }
         } else {
             let expected_length = self.values.len() * self.stride;
             if expected_length != trace_length {
@@ -262,7 +324,7 @@ impl<B: StarkField> Assertion<B> {
     /// Panics if the specified trace length is not valid for this assertion.
     pub fn apply<F>(&self, trace_length: usize, mut f: F)
     where
-        F: FnMut(usize, B),
+        F: FnMut(usize, E),
     {
         self.validate_trace_length(trace_length)
             .unwrap_or_else(|err| {
@@ -286,7 +348,8 @@ impl<B: StarkField> Assertion<B> {
     ///
     /// * For single-value assertions, this will always be one.
     /// * For periodic assertions this will be equal to `trace_length` / `stride`.
-    /// * For sequence assertions this will be equal to the number of asserted values.
+    /// * For sequence and consecutive assertions this will be equal to the number of asserted
+    ///   values.
     ///
     /// # Panics
     /// Panics if the specified trace length is not valid for this assertion.
@@ -303,6 +366,28 @@ impl<B: StarkField> Assertion<B> {
             self.values.len()
         }
     }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a range of steps covered by a consecutive assertion.
+    fn steps_range(&self) -> core::ops::Range<usize> {
+        debug_assert!(self.is_consecutive(), "assertion is not consecutive");
+        self.first_step..(self.first_step + self.values.len())
+    }
+
+    /// Returns true if this assertion asserts a value at the specified step; periodic and
+    /// sequence assertions are assumed to be valid for the trace containing the step.
+    fn covers_step(&self, step: usize) -> bool {
+        if self.is_single() {
+            step == self.first_step
+        } else if self.is_consecutive() {
+            self.steps_range().contains(&step)
+        } else {
+            // stride is always a power of two here
+            step >= self.first_step && (step - self.first_step) & (self.stride - 1) == 0
+        }
+    }
 }
 
 // OTHER TRAIT IMPLEMENTATIONS
@@ -310,7 +395,7 @@ impl<B: StarkField> Assertion<B> {
 
 /// We define ordering of assertions to be first by stride, then by first_step, and finally by
 /// register in ascending order.
-impl<B: StarkField> Ord for Assertion<B> {
+impl<E: FieldElement> Ord for Assertion<E> {
     fn cmp(&self, other: &Self) -> Ordering {
         if self.stride == other.stride {
             if self.first_step == other.first_step {
@@ -324,13 +409,13 @@ impl<B: StarkField> Ord for Assertion<B> {
     }
 }
 
-impl<B: StarkField> PartialOrd for Assertion<B> {
+impl<E: FieldElement> PartialOrd for Assertion<E> {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         Some(self.cmp(other))
     }
 }
 
-impl<B: StarkField> Display for Assertion<B> {
+impl<E: FieldElement> Display for Assertion<E> {
     fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
         write!(f, "(register={}, ", self.register)?;
         match self.stride {
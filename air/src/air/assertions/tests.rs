@@ -3,7 +3,7 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::{Assertion, AssertionError};
+use super::{Assertion, AssertionError, LagrangeBoundaryConstraints};
 use math::{fields::f128::BaseElement, FieldElement};
 use rand_utils::{rand_value, rand_vector};
 use utils::collections::Vec;
@@ -199,6 +199,80 @@ fn sequence_assertion_num_values_not_power_of_two() {
     );
 }
 
+// CONSECUTIVE ASSERTIONS
+// ================================================================================================
+
+#[test]
+fn consecutive_assertion() {
+    let values = rand_vector::<BaseElement>(3);
+    let a = Assertion::consecutive(2, 5, values.clone());
+    assert_eq!(2, a.register);
+    assert_eq!(5, a.first_step);
+    assert_eq!(values, a.values);
+    assert_eq!(1, a.stride);
+    assert!(a.is_consecutive());
+    assert!(!a.is_sequence());
+    assert_eq!(3, a.get_num_steps(8));
+    assert_eq!(3, a.get_num_steps(16));
+    a.apply(8, |step, value| {
+        assert_eq!(values[step - 5], value);
+    });
+
+    assert_eq!(Ok(()), a.validate_trace_length(8));
+    assert_eq!(
+        Err(AssertionError::TraceLengthTooShort(8, 4)),
+        a.validate_trace_length(4)
+    );
+
+    // a consecutive assertion with a single value is a single assertion
+    let value = rand_value::<BaseElement>();
+    let a = Assertion::consecutive(2, 5, vec![value]);
+    assert_eq!(Assertion::single(2, 5, value), a);
+}
+
+#[test]
+#[should_panic(
+    expected = "invalid assertion for register 3: number of asserted values must be greater than zero"
+)]
+fn consecutive_assertion_empty_values() {
+    let _ = Assertion::<BaseElement>::consecutive(3, 2, Vec::new());
+}
+
+#[test]
+fn lagrange_boundary_constraints() {
+    let values = rand_vector::<BaseElement>(6);
+    let steps = [9, 1, 2, 3, 7, 8];
+    let pairs = steps
+        .iter()
+        .zip(values.iter())
+        .map(|(&step, &value)| (step, value))
+        .collect::<Vec<_>>();
+
+    // steps 1, 2, 3 and 7, 8, 9 form two runs of consecutive steps
+    let constraints = LagrangeBoundaryConstraints::new(4, &pairs);
+    let expected = vec![
+        Assertion::consecutive(4, 1, vec![values[1], values[2], values[3]]),
+        Assertion::consecutive(4, 7, vec![values[4], values[5], values[0]]),
+    ];
+    assert_eq!(expected, constraints.assertions());
+    assert_eq!(expected, constraints.into_iter().collect::<Vec<_>>());
+
+    // isolated steps result in single assertions
+    let constraints = LagrangeBoundaryConstraints::new(4, &[(3, values[0]), (0, values[1])]);
+    let expected = vec![
+        Assertion::single(4, 0, values[1]),
+        Assertion::single(4, 3, values[0]),
+    ];
+    assert_eq!(expected, constraints.assertions());
+}
+
+#[test]
+#[should_panic(expected = "more than one value provided for step 2 of register 1")]
+fn lagrange_boundary_constraints_duplicate_steps() {
+    let values = rand_vector::<BaseElement>(3);
+    let _ = LagrangeBoundaryConstraints::new(1, &[(1, values[0]), (2, values[1]), (2, values[2])]);
+}
+
 // OVERLAPPING ASSERTIONS
 // ================================================================================================
 
@@ -362,4 +436,39 @@ fn assertion_overlap() {
     let b = Assertion::periodic(0, 0, 16, BaseElement::ONE);
     assert!(!a.overlaps_with(&b));
     assert!(!b.overlaps_with(&a));
+
+    // ----- consecutive overlap ------------------------------------------------------------------
+
+    let values = vec![BaseElement::ONE, BaseElement::ZERO, BaseElement::ONE];
+    let a = Assertion::consecutive(3, 5, values.clone());
+
+    let b = Assertion::single(3, 6, BaseElement::ONE);
+    assert!(a.overlaps_with(&b));
+    assert!(b.overlaps_with(&a));
+
+    let b = Assertion::periodic(3, 3, 4, BaseElement::ONE);
+    assert!(a.overlaps_with(&b));
+    assert!(b.overlaps_with(&a));
+
+    let b = Assertion::consecutive(3, 7, values.clone());
+    assert!(a.overlaps_with(&b));
+    assert!(b.overlaps_with(&a));
+
+    // different registers: no overlap
+    let b = Assertion::single(1, 6, BaseElement::ONE);
+    assert!(!a.overlaps_with(&b));
+    assert!(!b.overlaps_with(&a));
+
+    // steps outside of the consecutive range: no overlap
+    let b = Assertion::single(3, 8, BaseElement::ONE);
+    assert!(!a.overlaps_with(&b));
+    assert!(!b.overlaps_with(&a));
+
+    let b = Assertion::periodic(3, 0, 4, BaseElement::ONE);
+    assert!(!a.overlaps_with(&b));
+    assert!(!b.overlaps_with(&a));
+
+    let b = Assertion::consecutive(3, 8, values);
+    assert!(!a.overlaps_with(&b));
+    assert!(!b.overlaps_with(&a));
 }
@@ -4,7 +4,7 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::{Assertion, ConstraintDivisor};
-use math::{fft, polynom, FieldElement, StarkField};
+use math::{fft, polynom, FieldElement};
 use utils::collections::{BTreeMap, Vec};
 
 #[cfg(test)]
@@ -24,19 +24,28 @@ mod tests;
 /// the same. The constraints stored in the group describe polynomials $b$. At the time of
 /// constraint evaluation, a prover or a verifier provides evaluations of the relevant polynomial
 /// $f$ so that the value of the constraint can be computed.
+///
+/// Values asserted by the constraints are elements of field `F`: this is the base field for
+/// constraints against the main segment of the trace, and the extension field `E` for constraints
+/// against the auxiliary segment.
 #[derive(Debug, Clone)]
-pub struct BoundaryConstraintGroup<B: StarkField, E: FieldElement<BaseField = B>> {
-    constraints: Vec<BoundaryConstraint<B, E>>,
-    divisor: ConstraintDivisor<B>,
+pub struct BoundaryConstraintGroup<
+    F: FieldElement,
+    E: FieldElement<BaseField = F::BaseField> + From<F>,
+> {
+    constraints: Vec<BoundaryConstraint<F, E>>,
+    divisor: ConstraintDivisor<F::BaseField>,
     degree_adjustment: u32,
 }
 
-impl<B: StarkField, E: FieldElement<BaseField = B>> BoundaryConstraintGroup<B, E> {
+impl<F: FieldElement, E: FieldElement<BaseField = F::BaseField> + From<F>>
+    BoundaryConstraintGroup<F, E>
+{
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
     /// Returns a new  boundary constraint group to hold constraints with the specified divisor.
     pub(super) fn new(
-        divisor: ConstraintDivisor<B>,
+        divisor: ConstraintDivisor<F::BaseField>,
         trace_poly_degree: usize,
         composition_degree: usize,
     ) -> Self {
@@ -58,12 +67,12 @@ impl<B: StarkField, E: FieldElement<BaseField = B>> BoundaryConstraintGroup<B, E
     // --------------------------------------------------------------------------------------------
 
     /// Returns a list of boundary constraints in this group.
-    pub fn constraints(&self) -> &[BoundaryConstraint<B, E>] {
+    pub fn constraints(&self) -> &[BoundaryConstraint<F, E>] {
         &self.constraints
     }
 
     /// Returns a divisor applicable to all boundary constraints in this group.
-    pub fn divisor(&self) -> &ConstraintDivisor<B> {
+    pub fn divisor(&self) -> &ConstraintDivisor<F::BaseField> {
         &self.divisor
     }
 
@@ -78,9 +87,9 @@ impl<B: StarkField, E: FieldElement<BaseField = B>> BoundaryConstraintGroup<B, E
     /// Creates a new boundary constraint from the specified assertion and adds it to the group.
     pub(super) fn add(
         &mut self,
-        assertion: Assertion<B>,
-        inv_g: B,
-        twiddle_map: &mut BTreeMap<usize, Vec<B>>,
+        assertion: Assertion<F>,
+        inv_g: F::BaseField,
+        twiddle_map: &mut BTreeMap<usize, Vec<F::BaseField>>,
         coefficients: (E, E),
     ) {
         self.constraints.push(BoundaryConstraint::new(
@@ -91,6 +100,17 @@ impl<B: StarkField, E: FieldElement<BaseField = B>> BoundaryConstraintGroup<B, E
         ));
     }
 
+    /// Adds the specified domain point constraint to the group, and sets its composition
+    /// coefficients to `coefficients`.
+    pub(super) fn add_domain_point_constraint(
+        &mut self,
+        mut constraint: BoundaryConstraint<F, E>,
+        coefficients: (E, E),
+    ) {
+        constraint.cc = coefficients;
+        self.constraints.push(constraint);
+    }
+
     /// Evaluates all constraints in this group at the specified point `x`.
     ///
     /// `xp` is a degree adjustment multiplier which must be computed as `x^degree_adjustment`.
@@ -119,7 +139,7 @@ impl<B: StarkField, E: FieldElement<BaseField = B>> BoundaryConstraintGroup<B, E
         );
         let mut numerator = E::ZERO;
         for constraint in self.constraints().iter() {
-            let trace_value = state[constraint.register()];
+            let trace_value = constraint.get_trace_value(state);
             let evaluation = constraint.evaluate_at(x, trace_value);
             numerator += evaluation * (constraint.cc().0 + constraint.cc().1 * xp);
         }
@@ -144,33 +164,55 @@ impl<B: StarkField, E: FieldElement<BaseField = B>> BoundaryConstraintGroup<B, E
 /// evaluate the constraint and to compose constraint evaluations with other constraints (i.e.,
 /// constraint composition coefficients).
 ///
-/// `BoundaryConstraint`s cannot be instantiated directly, they are created internally from
-/// [Assertions](Assertion).
+/// Most `BoundaryConstraint`s are created internally from [Assertions](Assertion). The only
+/// constraints which can be instantiated directly are constraints placed at arbitrary points of
+/// the domain of trace polynomials (see [at_domain_point()](BoundaryConstraint::at_domain_point)).
+/// Such constraints can also be placed against a linear combination of trace polynomials (see
+/// [linear_combination()](BoundaryConstraint::linear_combination)), in which case $f(x)$ is the
+/// linear combination.
 #[derive(Debug, Clone, Eq, PartialEq)]
-pub struct BoundaryConstraint<B: StarkField, E: FieldElement<BaseField = B>> {
+pub struct BoundaryConstraint<F: FieldElement, E: FieldElement<BaseField = F::BaseField> + From<F>>
+{
     register: usize,
-    poly: Vec<B>,
-    poly_offset: (usize, B),
+    poly: Vec<F>,
+    poly_offset: (usize, F::BaseField),
     cc: (E, E),
+    domain_point: Option<F::BaseField>,
+    terms: Vec<(usize, F::BaseField)>,
 }
 
-impl<B: StarkField, E: FieldElement<BaseField = B>> BoundaryConstraint<B, E> {
+impl<F: FieldElement, E: FieldElement<BaseField = F::BaseField> + From<F>>
+    BoundaryConstraint<F, E>
+{
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
     /// Creates a new boundary constraint from the specified assertion.
     pub(super) fn new(
-        assertion: Assertion<B>,
-        inv_g: B,
-        twiddle_map: &mut BTreeMap<usize, Vec<B>>,
+        assertion: Assertion<F>,
+        inv_g: F::BaseField,
+        twiddle_map: &mut BTreeMap<usize, Vec<F::BaseField>>,
         cc: (E, E),
     ) -> Self {
         // build a polynomial which evaluates to constraint values at asserted steps; for
         // single-value assertions we use the value as constant coefficient of degree 0
         // polynomial; but for multi-value assertions, we need to interpolate the values
         // into a polynomial using inverse FFT
-        let mut poly_offset = (0, B::ONE);
+        let is_consecutive = assertion.is_consecutive();
+        let mut poly_offset = (0, F::BaseField::ONE);
         let mut poly = assertion.values;
-        if poly.len() > 1 {
+        if is_consecutive {
+            // consecutive steps do not form a subgroup of the trace domain; so, we use Lagrange
+            // interpolation to build the polynomial. The polynomial is padded with zeros to the
+            // next power of two so that it could be evaluated using FFT when needed.
+            let g = inv_g.inv();
+            let first_step = assertion.first_step;
+            let xs = (0..poly.len())
+                .map(|i| F::from(g.exp(((first_step + i) as u64).into())))
+                .collect::<Vec<_>>();
+            let num_coefficients = poly.len().next_power_of_two();
+            poly = polynom::interpolate(&xs, &poly, false);
+            poly.resize(num_coefficients, F::ZERO);
+        } else if poly.len() > 1 {
             // get the twiddles from the map; if twiddles for this domain haven't been built
             // yet, build them and add them to the map
             let inv_twiddles = twiddle_map
@@ -193,6 +235,68 @@ impl<B: StarkField, E: FieldElement<BaseField = B>> BoundaryConstraint<B, E> {
             poly,
             poly_offset,
             cc,
+            domain_point: None,
+            terms: Vec::new(),
+        }
+    }
+
+    /// Returns a constraint requiring that the trace polynomial of the specified `register`
+    /// evaluates to `value` at the specified `point`.
+    ///
+    /// Unlike constraints created from assertions, the point does not need to be a power of the
+    /// trace domain generator: it could be any element of the base field (e.g., a root of unity
+    /// of a larger domain, or a point of a coset). The divisor of the resulting constraint is
+    /// $(x - point)$.
+    ///
+    /// Composition coefficients of the constraint are assigned when boundary constraints are
+    /// built by [Air::get_boundary_constraints()](crate::Air::get_boundary_constraints).
+    pub fn at_domain_point(register: usize, point: F::BaseField, value: F) -> Self {
+        BoundaryConstraint {
+            register,
+            poly: vec![value],
+            poly_offset: (0, F::BaseField::ONE),
+            cc: (E::ZERO, E::ZERO),
+            domain_point: Some(point),
+            terms: Vec::new(),
+        }
+    }
+
+    /// Returns a constraint requiring that a linear combination of trace polynomials evaluates
+    /// to `value` at the specified `point`.
+    ///
+    /// Each of the `terms` is a tuple `(register, coefficient)`; e.g., terms `[(0, 2), (3, 1)]`
+    /// with value 5 describe the constraint $2 \cdot f_0(point) + f_3(point) = 5$. To place the
+    /// constraint at step $i$ of the execution trace, set `point` to $g^i$, where $g$ is the
+    /// generator of the trace domain (see
+    /// [Air::trace_domain_generator()](crate::Air::trace_domain_generator)). In all other
+    /// respects, the constraint is the same as the constraints created via
+    /// [at_domain_point()](BoundaryConstraint::at_domain_point).
+    ///
+    /// # Panics
+    /// Panics if `terms` is empty or if any register appears in `terms` more than once.
+    pub fn linear_combination(
+        terms: Vec<(usize, F::BaseField)>,
+        point: F::BaseField,
+        value: F,
+    ) -> Self {
+        assert!(
+            !terms.is_empty(),
+            "a linear combination must contain at least one term"
+        );
+        for (i, &(register, _)) in terms.iter().enumerate() {
+            assert!(
+                terms[..i].iter().all(|&(r, _)| r != register),
+                "register {} appears in the linear combination more than once",
+                register
+            );
+        }
+        BoundaryConstraint {
+            register: terms[0].0,
+            poly: vec![value],
+            poly_offset: (0, F::BaseField::ONE),
+            cc: (E::ZERO, E::ZERO),
+            domain_point: Some(point),
+            terms,
         }
     }
 
@@ -200,12 +304,15 @@ impl<B: StarkField, E: FieldElement<BaseField = B>> BoundaryConstraint<B, E> {
     // --------------------------------------------------------------------------------------------
 
     /// Returns index of the register against which this constraint applies.
+    ///
+    /// For constraints placed against a linear combination of registers, this is the first
+    /// register of the combination.
     pub fn register(&self) -> usize {
         self.register
     }
 
     /// Returns a value polynomial for this constraint.
-    pub fn poly(&self) -> &[B] {
+    pub fn poly(&self) -> &[F] {
         &self.poly
     }
 
@@ -214,7 +321,7 @@ impl<B: StarkField, E: FieldElement<BaseField = B>> BoundaryConstraint<B, E> {
     /// The offset is returned as a tuple describing both, the number of steps by which the
     /// domain needs to be shifted, and field element by which a domain element needs to be
     /// multiplied to achieve the desired shift.
-    pub fn poly_offset(&self) -> (usize, B) {
+    pub fn poly_offset(&self) -> (usize, F::BaseField) {
         self.poly_offset
     }
 
@@ -223,6 +330,34 @@ impl<B: StarkField, E: FieldElement<BaseField = B>> BoundaryConstraint<B, E> {
         &self.cc
     }
 
+    /// Returns the domain point at which this constraint is placed, or None if this constraint
+    /// was created from an assertion.
+    pub fn domain_point(&self) -> Option<F::BaseField> {
+        self.domain_point
+    }
+
+    /// Returns `(register, coefficient)` terms of the linear combination against which this
+    /// constraint is placed, or an empty slice if the constraint is placed against a single
+    /// register.
+    pub fn terms(&self) -> &[(usize, F::BaseField)] {
+        &self.terms
+    }
+
+    /// Returns the value of the trace polynomial (or of the linear combination of trace
+    /// polynomials) against which this constraint is placed, given the `state` of the trace
+    /// at some point.
+    pub fn get_trace_value<T: FieldElement<BaseField = F::BaseField>>(&self, state: &[T]) -> T {
+        if self.terms.is_empty() {
+            state[self.register]
+        } else {
+            self.terms
+                .iter()
+                .fold(T::ZERO, |acc, &(register, coefficient)| {
+                    acc + state[register] * T::from(coefficient)
+                })
+        }
+    }
+
     // CONSTRAINT EVALUATOR
     // --------------------------------------------------------------------------------------------
     /// Evaluates this constraint at the specified point `x`.
@@ -242,7 +377,7 @@ impl<B: StarkField, E: FieldElement<BaseField = B>> BoundaryConstraint<B, E> {
             // fall on steps that are powers of two, we need to evaluate the value polynomial
             // at x * offset (instead of just x).
             //
-            // note that while the coefficients of the value polynomial are in the base field,
+            // note that while the coefficients of the value polynomial may be in the base field,
             // if we are working in an extension field, the result of the evaluation will be a
             // value in the extension field.
             let x = x * E::from(self.poly_offset.1);
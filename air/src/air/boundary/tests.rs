@@ -173,6 +173,81 @@ fn boundary_constraint_from_sequence_assertion() {
     );
 }
 
+#[test]
+fn boundary_constraint_from_consecutive_assertion() {
+    let mut test_prng = build_prng();
+    let (inv_g, mut twiddle_map, mut prng) = build_constraint_params(16);
+    let g = inv_g.inv();
+
+    // constraint should be built correctly for register 2, steps 5, 6, 7
+    let values = rand_vector::<BaseElement>(3);
+    let assertion = Assertion::consecutive(2, 5, values.clone());
+    let constraint = BoundaryConstraint::<BaseElement, BaseElement>::new(
+        assertion,
+        inv_g,
+        &mut twiddle_map,
+        prng.draw_pair().unwrap(),
+    );
+    assert_eq!(2, constraint.register());
+    assert_eq!(4, constraint.poly().len());
+    assert_eq!((0, BaseElement::ONE), constraint.poly_offset());
+    assert_eq!(test_prng.draw_pair::<BaseElement>().unwrap(), constraint.cc);
+
+    // the constraint should evaluate to zero at asserted steps when trace values are equal to
+    // the asserted values
+    for (i, &value) in values.iter().enumerate() {
+        let x = g.exp((5 + i as u64).into());
+        assert_eq!(BaseElement::ZERO, constraint.evaluate_at(x, value));
+    }
+
+    // consecutive value constraints should evaluate to trace_value - constraint_poly(x)
+    let x = rand_value::<BaseElement>();
+    let trace_value = rand_value::<BaseElement>();
+    assert_eq!(
+        trace_value - polynom::eval(constraint.poly(), x),
+        constraint.evaluate_at(x, trace_value)
+    );
+
+    // twiddle map was not touched
+    assert!(twiddle_map.is_empty());
+}
+
+#[test]
+fn boundary_constraint_from_linear_combination() {
+    let point = rand_value::<BaseElement>();
+    let value = rand_value::<BaseElement>();
+    let terms = vec![(1, BaseElement::new(2)), (3, BaseElement::new(5))];
+    let constraint = BoundaryConstraint::<BaseElement, BaseElement>::linear_combination(
+        terms.clone(),
+        point,
+        value,
+    );
+    assert_eq!(1, constraint.register());
+    assert_eq!(&terms[..], constraint.terms());
+    assert_eq!(Some(point), constraint.domain_point());
+    assert_eq!(vec![value], constraint.poly());
+
+    // the trace value of the constraint is the linear combination of register values
+    let state = rand_vector::<BaseElement>(4);
+    let expected = BaseElement::new(2) * state[1] + BaseElement::new(5) * state[3];
+    assert_eq!(expected, constraint.get_trace_value(&state));
+    assert_eq!(
+        expected - value,
+        constraint.evaluate_at(point, constraint.get_trace_value(&state))
+    );
+}
+
+#[test]
+#[should_panic(expected = "register 1 appears in the linear combination more than once")]
+fn boundary_constraint_from_linear_combination_duplicate_register() {
+    let terms = vec![(1, BaseElement::new(2)), (1, BaseElement::new(5))];
+    let _ = BoundaryConstraint::<BaseElement, BaseElement>::linear_combination(
+        terms,
+        BaseElement::ONE,
+        BaseElement::ONE,
+    );
+}
+
 // HELPER FUNCTIONS
 // ================================================================================================
 fn build_constraint_params(
@@ -6,6 +6,22 @@
 use math::FieldElement;
 use utils::collections::Vec;
 
+// COMBINATION MODE
+// ================================================================================================
+/// Defines how coefficients for the random linear combination of constraints are drawn from the
+/// public coin during construction of constraint composition polynomial.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CombinationMode {
+    /// A pair of independent random coefficients is drawn for each constraint (or for each
+    /// constraint group with a shared coefficient).
+    Linear,
+    /// A single random element $r$ is drawn, and the coefficients are consecutive powers of $r$:
+    /// the $i$th pair of coefficients is set to $(r^{2i + 1}, r^{2i + 2})$. This reduces the
+    /// number of draws from the public coin to one, at the expense of a soundness error which
+    /// grows linearly with the number of constraints.
+    PowerSeries,
+}
+
 // CONSTRAINT COMPOSITION COEFFICIENTS
 // ================================================================================================
 /// Coefficients used in construction of constraint composition polynomial.
@@ -25,12 +41,15 @@ use utils::collections::Vec;
 /// * $C_i(x)$ is an evaluation of the $i$th constraint at $x$.
 /// * $d_i$ is the degree adjustment factor needed to normalize all constraints to the same degree.
 ///
-/// The coefficients are separated into two lists: one for transition constraints and another one
-/// for boundary constraints. This separation is done for convenience only.
+/// The coefficients are separated into three lists: one for transition constraints, one for
+/// boundary constraints, and one for padding constraints. This separation is done for
+/// convenience only. The list of padding constraint coefficients is empty for execution traces
+/// without padding rows.
 #[derive(Debug, Clone)]
 pub struct ConstraintCompositionCoefficients<E: FieldElement> {
     pub transition: Vec<(E, E)>,
     pub boundary: Vec<(E, E)>,
+    pub padding: Vec<(E, E)>,
 }
 
 // DEEP COMPOSITION COEFFICIENTS
@@ -66,6 +85,10 @@ pub struct ConstraintCompositionCoefficients<E: FieldElement> {
 /// * $\alpha_i, \beta_i, \gamma_i$ are composition coefficients for the $i$th trace polynomial.
 /// * $\delta_j$ is a composition coefficient for $j$th constraint column polynomial.
 ///
+/// For evaluation frames with $r > 2$ rows, $Y(x)$ also includes the terms
+/// $\epsilon_{i,l} \cdot \frac{T_i(x) - T_i(z \cdot g^l)}{x - z \cdot g^l}$ for all trace
+/// polynomials and $2 \le l < r$.
+///
 /// $T(x)$ and $H(x)$ are polynomials of degree $n - 1$, where $n$ is the length of the execution
 /// trace. Thus, the degree of $Y(x)$ polynomial is $n - 2$. To bring the degree back up to
 /// $n - 1$, we compute the DEEP composition polynomial as:
@@ -81,4 +104,8 @@ pub struct DeepCompositionCoefficients<E: FieldElement> {
     pub constraints: Vec<E>,
     /// Degree adjustment composition coefficients $\lambda$ and $\mu$.
     pub degree: (E, E),
+    /// Composition coefficients for trace polynomial evaluations at rows of the evaluation frame
+    /// beyond the first two; `additional_trace[k][i]` is the coefficient of the $i$th trace
+    /// polynomial at $z \cdot g^{k + 2}$. This is empty for frames of the default size.
+    pub additional_trace: Vec<Vec<E>>,
 }
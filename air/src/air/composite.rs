@@ -0,0 +1,301 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    Air, AirContext, Assertion, BoundaryConstraint, CombinationMode, ConstraintGroup,
+    EvaluationFrame, ProofOptions, TraceInfo,
+};
+use crypto::{hashers::Blake3_256, Digest, Hasher};
+use math::FieldElement;
+use utils::{collections::Vec, ByteWriter, Serializable};
+
+// COMPOSITE PUBLIC INPUTS
+// ================================================================================================
+/// Public inputs for a [CompositeAir] instance.
+///
+/// In addition to public inputs of both underlying computations, this struct specifies the
+/// number of registers in the execution trace of the first computation. Registers of the second
+/// computation are assumed to immediately follow the registers of the first computation.
+pub struct CompositePublicInputs<P1: Serializable, P2: Serializable> {
+    first_trace_width: usize,
+    first: P1,
+    second: P2,
+}
+
+impl<P1: Serializable, P2: Serializable> CompositePublicInputs<P1, P2> {
+    /// Returns new public inputs for a composite computation instantiated from the provided
+    /// parameters.
+    ///
+    /// # Panics
+    /// Panics if `first_trace_width` is zero or greater than 255.
+    pub fn new(first_trace_width: usize, first: P1, second: P2) -> Self {
+        assert!(
+            first_trace_width > 0,
+            "trace width of the first computation must be greater than 0"
+        );
+        assert!(
+            first_trace_width <= TraceInfo::MAX_TRACE_WIDTH,
+            "trace width of the first computation cannot be greater than {}, but was {}",
+            TraceInfo::MAX_TRACE_WIDTH,
+            first_trace_width
+        );
+        CompositePublicInputs {
+            first_trace_width,
+            first,
+            second,
+        }
+    }
+}
+
+impl<P1: Serializable, P2: Serializable> Serializable for CompositePublicInputs<P1, P2> {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u8(self.first_trace_width as u8);
+        self.first.write_into(target);
+        self.second.write_into(target);
+    }
+}
+
+// COMPOSITE AIR
+// ================================================================================================
+/// An AIR describing two independent computations executed side-by-side over the same trace
+/// domain.
+///
+/// The execution trace of a composite computation consists of the registers of the first
+/// computation followed by the registers of the second computation. Both computations must have
+/// execution traces of the same length.
+///
+/// Transition constraints of the composite computation are the transition constraints of the
+/// first computation followed by the transition constraints of the second computation; register
+/// indexes of assertions against the second computation are shifted by the trace width of the
+/// first computation. Periodic columns of both computations are concatenated in the same order.
+/// Constraint groups of the second computation are shifted by the number of transition
+/// constraints of the first computation. Domain point constraints are handled the same way as
+/// assertions, and linearity of transition constraints is reported by the underlying AIRs. The
+/// channel seed of the composite computation is a hash of the channel seeds of both computations,
+/// unless neither of them defines a seed.
+///
+/// Neither of the underlying computations may have an auxiliary trace segment.
+pub struct CompositeAir<A1, A2>
+where
+    A1: Air,
+    A2: Air<BaseElement = A1::BaseElement>,
+{
+    context: AirContext<A1::BaseElement>,
+    first: A1,
+    second: A2,
+    num_first_periodic_columns: usize,
+}
+
+impl<A1, A2> CompositeAir<A1, A2>
+where
+    A1: Air,
+    A2: Air<BaseElement = A1::BaseElement>,
+{
+    /// Returns a reference to the AIR of the first computation.
+    pub fn first(&self) -> &A1 {
+        &self.first
+    }
+
+    /// Returns a reference to the AIR of the second computation.
+    pub fn second(&self) -> &A2 {
+        &self.second
+    }
+}
+
+impl<A1, A2> Air for CompositeAir<A1, A2>
+where
+    A1: Air,
+    A2: Air<BaseElement = A1::BaseElement>,
+{
+    type BaseElement = A1::BaseElement;
+    type PublicInputs = CompositePublicInputs<A1::PublicInputs, A2::PublicInputs>;
+
+    const MAX_CONSTRAINT_DEGREE: usize = if A1::MAX_CONSTRAINT_DEGREE > A2::MAX_CONSTRAINT_DEGREE {
+        A1::MAX_CONSTRAINT_DEGREE
+    } else {
+        A2::MAX_CONSTRAINT_DEGREE
+    };
+
+    /// Instantiates AIRs for both computations and composes their contexts.
+    ///
+    /// The same trace length, number of padding rows, trace metadata, and proof options are
+    /// passed to both underlying AIRs.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * Trace width specified by `trace_info` is not greater than the trace width of the first
+    ///   computation.
+    /// * Any of the underlying AIRs cannot be instantiated from the provided parameters.
+    /// * Any of the underlying computations has an auxiliary trace segment.
+    /// * Contexts of the underlying AIRs cannot be composed.
+    fn new(trace_info: TraceInfo, pub_inputs: Self::PublicInputs, options: ProofOptions) -> Self {
+        let first_width = pub_inputs.first_trace_width;
+        assert!(
+            trace_info.width() > first_width,
+            "trace width must be greater than {}, but was {}",
+            first_width,
+            trace_info.width()
+        );
+        let second_width = trace_info.width() - first_width;
+
+        let num_padding_rows = trace_info.num_padding_rows();
+        let first_info =
+            TraceInfo::with_meta(first_width, trace_info.length(), trace_info.meta().to_vec())
+                .with_num_padding_rows(num_padding_rows);
+        let second_info = TraceInfo::with_meta(
+            second_width,
+            trace_info.length(),
+            trace_info.meta().to_vec(),
+        )
+        .with_num_padding_rows(num_padding_rows);
+
+        let first = A1::new(first_info, pub_inputs.first, options.clone());
+        let second = A2::new(second_info, pub_inputs.second, options);
+        assert!(
+            !has_aux_segment(&first) && !has_aux_segment(&second),
+            "computations with auxiliary trace segments cannot be composed"
+        );
+
+        let context = first.context().clone().compose(second.context().clone());
+        let num_first_periodic_columns = first.get_periodic_column_values().len();
+
+        CompositeAir {
+            context,
+            first,
+            second,
+            num_first_periodic_columns,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseElement> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement<BaseField = Self::BaseElement>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let frames = frame.split_registers(self.first.trace_width());
+
+        let (first_periodic, second_periodic) =
+            periodic_values.split_at(self.num_first_periodic_columns);
+        let (first_result, second_result) =
+            result.split_at_mut(self.first.num_transition_constraints());
+
+        self.first
+            .evaluate_transition(&frames[0], first_periodic, first_result);
+        self.second
+            .evaluate_transition(&frames[1], second_periodic, second_result);
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseElement>> {
+        let first_width = self.first.trace_width();
+        let mut result = self.first.get_assertions();
+        for mut assertion in self.second.get_assertions() {
+            assertion.register += first_width;
+            result.push(assertion);
+        }
+        result
+    }
+
+    fn get_domain_point_constraints<E: FieldElement<BaseField = Self::BaseElement>>(
+        &self,
+    ) -> Vec<BoundaryConstraint<Self::BaseElement, E>> {
+        let first_width = self.first.trace_width();
+        let mut result = self.first.get_domain_point_constraints();
+        for constraint in self.second.get_domain_point_constraints::<E>() {
+            let point = constraint
+                .domain_point()
+                .expect("domain point constraint must have a domain point");
+            let value = constraint.poly()[0];
+            let constraint = if constraint.terms().is_empty() {
+                BoundaryConstraint::at_domain_point(
+                    constraint.register() + first_width,
+                    point,
+                    value,
+                )
+            } else {
+                let terms = constraint
+                    .terms()
+                    .iter()
+                    .map(|&(register, coefficient)| (register + first_width, coefficient))
+                    .collect();
+                BoundaryConstraint::linear_combination(terms, point, value)
+            };
+            result.push(constraint);
+        }
+        result
+    }
+
+    fn is_transition_constraint_linear(&self, index: usize) -> bool {
+        let num_first_constraints = self.first.num_transition_constraints();
+        if index < num_first_constraints {
+            self.first.is_transition_constraint_linear(index)
+        } else {
+            self.second
+                .is_transition_constraint_linear(index - num_first_constraints)
+        }
+    }
+
+    fn get_periodic_column_values(&self) -> Vec<Vec<Self::BaseElement>> {
+        let mut result = self.first.get_periodic_column_values();
+        result.append(&mut self.second.get_periodic_column_values());
+        result
+    }
+
+    fn get_constraint_groups(&self) -> Vec<ConstraintGroup> {
+        let num_first_constraints = self.first.num_transition_constraints();
+        let mut result = self.first.get_constraint_groups();
+        for group in self.second.get_constraint_groups() {
+            let constraints = group
+                .constraints()
+                .iter()
+                .map(|&index| index + num_first_constraints)
+                .collect();
+            result.push(ConstraintGroup::new(
+                constraints,
+                group.shared_coefficient(),
+            ));
+        }
+        result
+    }
+
+    fn constraint_combination_mode(&self) -> CombinationMode {
+        // coefficients are derived from a power series only if both computations request it
+        match (
+            self.first.constraint_combination_mode(),
+            self.second.constraint_combination_mode(),
+        ) {
+            (CombinationMode::PowerSeries, CombinationMode::PowerSeries) => {
+                CombinationMode::PowerSeries
+            }
+            _ => CombinationMode::Linear,
+        }
+    }
+
+    fn channel_seed(&self) -> [u8; 32] {
+        let first = self.first.channel_seed();
+        let second = self.second.channel_seed();
+        if first == [0; 32] && second == [0; 32] {
+            return [0; 32];
+        }
+
+        // the seeds are hashed in order, and thus, swapping the computations changes the seed
+        let mut seeds = [0; 64];
+        seeds[..32].copy_from_slice(&first);
+        seeds[32..].copy_from_slice(&second);
+        Blake3_256::<Self::BaseElement>::hash(&seeds).as_bytes()
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns true if the `air` has an auxiliary trace segment or declares constraints against it.
+fn has_aux_segment<A: Air>(air: &A) -> bool {
+    air.auxiliary_trace_width() > 0 || air.context().num_aux_transition_constraints() > 0
+}
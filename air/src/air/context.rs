@@ -4,6 +4,7 @@
 // LICENSE file in the root directory of this source tree.
 
 use crate::{air::TransitionConstraintDegree, ProofOptions, TraceInfo};
+use core::cmp;
 use math::{log2, StarkField};
 use utils::collections::Vec;
 
@@ -15,12 +16,19 @@ pub struct AirContext<B: StarkField> {
     pub(super) options: ProofOptions,
     pub(super) trace_info: TraceInfo,
     pub(super) transition_constraint_degrees: Vec<TransitionConstraintDegree>,
+    pub(super) transition_constraint_exemptions: Vec<usize>,
+    pub(super) num_main_transition_constraints: usize,
+    pub(super) frame_size: usize,
+    pub(super) num_padding_rows: usize,
     pub(super) ce_blowup_factor: usize,
     pub(super) trace_domain_generator: B,
     pub(super) lde_domain_generator: B,
 }
 
 impl<B: StarkField> AirContext<B> {
+    /// Maximum number of consecutive trace rows in an evaluation frame.
+    pub const MAX_FRAME_SIZE: usize = 255;
+
     // CONSTRUCTORS
     // --------------------------------------------------------------------------------------------
     /// Returns a new instance of [AirContext] instantiated from the specified parameters.
@@ -31,7 +39,12 @@ impl<B: StarkField> AirContext<B> {
     /// in the order defined by this list.
     ///
     /// # Panics
-    /// Panics if `transition_constraint_degrees` is an empty vector.
+    /// Panics if:
+    /// * `transition_constraint_degrees` is an empty vector.
+    /// * Blowup factor specified by `options` is smaller than the blowup factor required to
+    ///   evaluate transition constraints.
+    /// * Domain offset specified by `options` is not valid in field `B`, as defined by
+    ///   [ProofOptions::validate_domain_offset()].
     pub fn new(
         trace_info: TraceInfo,
         transition_constraint_degrees: Vec<TransitionConstraintDegree>,
@@ -61,13 +74,315 @@ impl<B: StarkField> AirContext<B> {
         let trace_length = trace_info.length();
         let lde_domain_size = trace_length * options.blowup_factor();
 
+        if let Err(err) = options.validate_domain_offset::<B>() {
+            panic!("invalid domain offset: {}", err);
+        }
+
+        let transition_constraint_exemptions = vec![1; transition_constraint_degrees.len()];
         AirContext {
             options,
             trace_info,
+            num_main_transition_constraints: transition_constraint_degrees.len(),
             transition_constraint_degrees,
+            transition_constraint_exemptions,
+            frame_size: 2,
+            num_padding_rows: 0,
             ce_blowup_factor,
             trace_domain_generator: B::get_root_of_unity(log2(trace_length)),
             lde_domain_generator: B::get_root_of_unity(log2(lde_domain_size)),
         }
     }
+
+    /// Sets the number of steps at the end of the execution trace on which each of the transition
+    /// constraints is not enforced, and returns the updated context.
+    ///
+    /// The exemptions are specified in the order of transition constraint degrees passed to
+    /// [AirContext::new()]. By default, a transition constraint is not enforced on the last step
+    /// of the execution trace only. The number of exemptions for a constraint is never smaller
+    /// than the number of [transition exemptions](AirContext::num_transition_exemptions) of this
+    /// context; so, for padded execution traces, constraints are still not enforced on padding
+    /// rows.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * The number of values in `exemptions` is not equal to the number of transition
+    ///   constraints.
+    /// * Any of the values is zero or is not smaller than the length of the execution trace.
+    pub fn with_transition_constraint_exemptions(mut self, exemptions: Vec<usize>) -> Self {
+        assert_eq!(
+            self.transition_constraint_degrees.len(),
+            exemptions.len(),
+            "number of exemptions must be equal to the number of transition constraints"
+        );
+        for (i, &num_exemptions) in exemptions.iter().enumerate() {
+            assert!(
+                num_exemptions > 0 && num_exemptions < self.trace_info.length(),
+                "number of exemptions must be between 1 and {}, but was {} for constraint {}",
+                self.trace_info.length() - 1,
+                num_exemptions,
+                i
+            );
+        }
+        self.transition_constraint_exemptions = exemptions;
+        self
+    }
+
+    /// Appends transition constraints against the auxiliary segment of the execution trace with
+    /// the specified degrees, and returns the updated context.
+    ///
+    /// Auxiliary transition constraints are evaluated by
+    /// [Air::evaluate_aux_transition()](crate::Air::evaluate_aux_transition), and their indexes
+    /// follow the indexes of the transition constraints passed to [AirContext::new()]; thus,
+    /// exemptions, constraint groups etc. for these constraints should be specified using
+    /// these indexes. Each of the constraints is exempted from the last step of the execution
+    /// trace only; to change this, call
+    /// [with_transition_constraint_exemptions()](AirContext::with_transition_constraint_exemptions)
+    /// after this method.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `degrees` is an empty vector.
+    /// * Blowup factor specified by the proof options of this context is smaller than the blowup
+    ///   factor required to evaluate the auxiliary transition constraints.
+    /// * Padding rows have been set for this context.
+    pub fn with_aux_transition_constraint_degrees(
+        mut self,
+        degrees: Vec<TransitionConstraintDegree>,
+    ) -> Self {
+        assert!(
+            !degrees.is_empty(),
+            "at least one auxiliary transition constraint degree must be specified"
+        );
+        assert_eq!(
+            self.num_padding_rows, 0,
+            "auxiliary transition constraints cannot be set for padded execution traces"
+        );
+        for degree in degrees.iter() {
+            self.ce_blowup_factor = cmp::max(self.ce_blowup_factor, degree.min_blowup_factor());
+        }
+        assert!(
+            self.options.blowup_factor() >= self.ce_blowup_factor,
+            "blowup factor too small; expected at least {}, but was {}",
+            self.ce_blowup_factor,
+            self.options.blowup_factor()
+        );
+
+        self.transition_constraint_exemptions.resize(
+            self.transition_constraint_exemptions.len() + degrees.len(),
+            1,
+        );
+        self.transition_constraint_degrees.extend(degrees);
+        self
+    }
+
+    /// Sets the number of consecutive trace rows in the evaluation frames passed to
+    /// [Air::evaluate_transition()](crate::Air::evaluate_transition), and returns the updated
+    /// context.
+    ///
+    /// By default, an evaluation frame consists of two rows: the current row and the next row.
+    /// Setting the frame size to $k$ allows transition constraints to relate values in rows at
+    /// steps $s, s + 1, ..., s + k - 1$. Since frames starting at the last $k - 1$ steps of the
+    /// trace wrap around, transition constraints are not enforced on these steps (see
+    /// [num_transition_exemptions()](AirContext::num_transition_exemptions)).
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `frame_size` is smaller than 2 or greater than 255.
+    /// * The resulting number of transition exemptions is not smaller than the length of the
+    ///   execution trace.
+    pub fn with_frame_size(mut self, frame_size: usize) -> Self {
+        assert!(
+            (2..=Self::MAX_FRAME_SIZE).contains(&frame_size),
+            "frame size must be between 2 and {}, but was {}",
+            Self::MAX_FRAME_SIZE,
+            frame_size
+        );
+        self.frame_size = frame_size;
+        assert!(
+            self.num_transition_exemptions() < self.trace_info.length(),
+            "number of transition exemptions must be smaller than trace length {}, but was {}",
+            self.trace_info.length(),
+            self.num_transition_exemptions()
+        );
+        self
+    }
+
+    /// Sets the number of padding rows at the end of the execution trace, and returns the
+    /// updated context.
+    ///
+    /// The number of padding rows must be derived from the public inputs of the computation
+    /// (e.g., from the number of steps executed by the computation) rather than from the trace
+    /// info passed to [Air::new()](crate::Air::new): the prover and the verifier reject execution
+    /// traces and proofs which contain a different number of padding rows. Transition
+    /// constraints are not enforced on the steps at which the evaluation frame includes any of
+    /// the padding rows; instead, each padding row is required to be equal to the row preceding
+    /// it (see [Air::get_padding_constraints()](crate::Air::get_padding_constraints)). Thus,
+    /// values in the padding rows are the values of the last row of the computation.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * The resulting number of transition exemptions is not smaller than the length of the
+    ///   execution trace.
+    /// * Auxiliary transition constraints have been set for this context.
+    pub fn with_num_padding_rows(mut self, num_padding_rows: usize) -> Self {
+        assert_eq!(
+            self.num_aux_transition_constraints(),
+            0,
+            "padding rows cannot be set for computations with auxiliary transition constraints"
+        );
+        self.num_padding_rows = num_padding_rows;
+        assert!(
+            self.num_transition_exemptions() < self.trace_info.length(),
+            "number of transition exemptions must be smaller than trace length {}, but was {}",
+            self.trace_info.length(),
+            self.num_transition_exemptions()
+        );
+        self
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+    /// Returns the largest base degree of transition constraints described by this context.
+    pub fn max_constraint_degree(&self) -> usize {
+        self.transition_constraint_degrees
+            .iter()
+            .map(|degree| degree.base())
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Returns the number of transition constraints against the main segment of the execution
+    /// trace; these are the constraints evaluated by
+    /// [Air::evaluate_transition()](crate::Air::evaluate_transition).
+    pub fn num_main_transition_constraints(&self) -> usize {
+        self.num_main_transition_constraints
+    }
+
+    /// Returns the number of transition constraints against the auxiliary segment of the
+    /// execution trace set via
+    /// [with_aux_transition_constraint_degrees()](AirContext::with_aux_transition_constraint_degrees).
+    pub fn num_aux_transition_constraints(&self) -> usize {
+        self.transition_constraint_degrees.len() - self.num_main_transition_constraints
+    }
+
+    /// Returns the number of consecutive trace rows in an evaluation frame.
+    ///
+    /// This is 2 unless a different frame size was set via
+    /// [with_frame_size()](AirContext::with_frame_size).
+    pub fn frame_size(&self) -> usize {
+        self.frame_size
+    }
+
+    /// Returns the number of padding rows at the end of the execution trace.
+    ///
+    /// This is 0 unless a different number was set via
+    /// [with_num_padding_rows()](AirContext::with_num_padding_rows).
+    pub fn num_padding_rows(&self) -> usize {
+        self.num_padding_rows
+    }
+
+    /// Returns the number of steps at the end of the execution trace on which transition
+    /// constraints are not enforced.
+    ///
+    /// Transition constraints are never enforced on the last `frame_size - 1` steps of the trace
+    /// (for the default frame size, this is just the last step); for padded execution traces,
+    /// they are also not enforced on the steps at which the frame would include any of the
+    /// padding rows. Thus, the number of exemptions is the number of padding rows plus the
+    /// frame size minus one.
+    pub fn num_transition_exemptions(&self) -> usize {
+        self.num_padding_rows + self.frame_size - 1
+    }
+
+    /// Returns the number of steps at the end of the execution trace on which the transition
+    /// constraint at the specified index is not enforced.
+    ///
+    /// This is the larger of the number of exemptions set for the constraint via
+    /// [with_transition_constraint_exemptions()](AirContext::with_transition_constraint_exemptions)
+    /// and the number of [transition exemptions](AirContext::num_transition_exemptions).
+    ///
+    /// # Panics
+    /// Panics if `constraint_idx` is not smaller than the number of transition constraints.
+    pub fn num_constraint_exemptions(&self, constraint_idx: usize) -> usize {
+        cmp::max(
+            self.transition_constraint_exemptions[constraint_idx],
+            self.num_transition_exemptions(),
+        )
+    }
+
+    // COMPOSITION
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new [AirContext] which describes the computations of this context and the
+    /// `other` context executed side-by-side.
+    ///
+    /// The resulting context describes an execution trace in which the registers of the `other`
+    /// trace are placed immediately after the registers of this trace. Transition constraints of
+    /// the `other` context are appended to the transition constraints of this context, and the
+    /// constraint evaluation blowup factor is set to the larger of the two. Trace metadata of
+    /// this context is retained, while metadata of the `other` context is discarded.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * Trace lengths, numbers of padding rows, or frame sizes of the two contexts are not the
+    ///   same.
+    /// * Proof options of the two contexts are not the same.
+    /// * Combined trace width of the two contexts is greater than 255.
+    /// * Any of the contexts has auxiliary transition constraints.
+    pub fn compose(self, other: AirContext<B>) -> Self {
+        assert!(
+            self.num_aux_transition_constraints() == 0
+                && other.num_aux_transition_constraints() == 0,
+            "contexts with auxiliary transition constraints cannot be composed"
+        );
+        assert_eq!(
+            self.trace_info.length(),
+            other.trace_info.length(),
+            "trace lengths of composed contexts must be the same"
+        );
+        assert_eq!(
+            self.num_padding_rows, other.num_padding_rows,
+            "numbers of padding rows of composed contexts must be the same"
+        );
+        assert_eq!(
+            self.frame_size, other.frame_size,
+            "frame sizes of composed contexts must be the same"
+        );
+        assert!(
+            self.options == other.options,
+            "proof options of composed contexts must be the same"
+        );
+
+        let trace_width = self.trace_info.width() + other.trace_info.width();
+        assert!(
+            trace_width <= TraceInfo::MAX_TRACE_WIDTH,
+            "combined trace width cannot be greater than {}, but was {}",
+            TraceInfo::MAX_TRACE_WIDTH,
+            trace_width
+        );
+
+        let trace_info = TraceInfo::with_meta(
+            trace_width,
+            self.trace_info.length(),
+            self.trace_info.meta().to_vec(),
+        )
+        .with_num_padding_rows(self.trace_info.num_padding_rows());
+
+        let mut transition_constraint_degrees = self.transition_constraint_degrees;
+        transition_constraint_degrees.extend(other.transition_constraint_degrees);
+        let num_main_transition_constraints = transition_constraint_degrees.len();
+        let mut transition_constraint_exemptions = self.transition_constraint_exemptions;
+        transition_constraint_exemptions.extend(other.transition_constraint_exemptions);
+
+        AirContext {
+            options: self.options,
+            trace_info,
+            transition_constraint_degrees,
+            transition_constraint_exemptions,
+            num_main_transition_constraints,
+            frame_size: self.frame_size,
+            num_padding_rows: self.num_padding_rows,
+            ce_blowup_factor: cmp::max(self.ce_blowup_factor, other.ce_blowup_factor),
+            trace_domain_generator: self.trace_domain_generator,
+            lde_domain_generator: self.lde_domain_generator,
+        }
+    }
 }
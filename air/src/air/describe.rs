@@ -0,0 +1,239 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{Air, CombinationMode, ConstraintDivisor};
+use crate::FieldExtension;
+use math::{FieldElement, StarkField};
+use serde_json::{Map, Value};
+use utils::{
+    collections::Vec,
+    string::{String, ToString},
+};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Version of the constraint description format produced by [describe()].
+const DESCRIPTION_VERSION: u8 = 1;
+
+// AIR DESCRIPTION
+// ================================================================================================
+
+/// Returns a JSON description of the constraints of the specified `air`; see
+/// [Air::describe()] for the description of the format.
+pub fn describe<A: Air + ?Sized>(air: &A) -> String {
+    let context = air.context();
+    let num_main_constraints = context.num_main_transition_constraints();
+
+    let mut field = Map::new();
+    field.insert("modulus_bits".into(), A::BaseElement::MODULUS_BITS.into());
+    field.insert(
+        "max_element".into(),
+        encode_element(A::BaseElement::ZERO - A::BaseElement::ONE),
+    );
+    field.insert(
+        "extension".into(),
+        match air.options().field_extension() {
+            FieldExtension::None => "none",
+            FieldExtension::Quadratic => "quadratic",
+            FieldExtension::Cubic => "cubic",
+        }
+        .into(),
+    );
+
+    let trace_info = air.trace_info();
+    let mut trace = Map::new();
+    trace.insert("width".into(), air.trace_width().into());
+    trace.insert("length".into(), air.trace_length().into());
+    trace.insert("num_padding_rows".into(), context.num_padding_rows().into());
+    trace.insert("aux_width".into(), air.auxiliary_trace_width().into());
+    trace.insert(
+        "num_aux_rand_elements".into(),
+        air.num_aux_rand_elements().into(),
+    );
+    trace.insert("frame_size".into(), air.frame_size().into());
+    trace.insert("meta".into(), encode_bytes(trace_info.meta()));
+
+    let transition_constraints = air
+        .transition_constraint_degrees()
+        .iter()
+        .enumerate()
+        .map(|(i, degree)| {
+            let is_main = i < num_main_constraints;
+            let mut constraint = Map::new();
+            constraint.insert("index".into(), i.into());
+            constraint.insert(
+                "segment".into(),
+                if is_main { "main" } else { "aux" }.into(),
+            );
+            constraint.insert("base_degree".into(), degree.base().into());
+            constraint.insert("cycles".into(), degree.cycles().to_vec().into());
+            constraint.insert(
+                "evaluation_degree".into(),
+                degree.get_evaluation_degree(air.trace_length()).into(),
+            );
+            constraint.insert(
+                "num_exemptions".into(),
+                context.num_constraint_exemptions(i).into(),
+            );
+            constraint.insert(
+                "linear".into(),
+                (is_main && air.is_transition_constraint_linear(i)).into(),
+            );
+            Value::Object(constraint)
+        })
+        .collect::<Vec<_>>();
+
+    let constraint_groups = air
+        .get_constraint_groups()
+        .iter()
+        .map(|group| {
+            let mut result = Map::new();
+            result.insert("constraints".into(), group.constraints().to_vec().into());
+            result.insert(
+                "shared_coefficient".into(),
+                group.shared_coefficient().into(),
+            );
+            Value::Object(result)
+        })
+        .collect::<Vec<_>>();
+
+    let periodic_columns = air
+        .get_periodic_column_values()
+        .into_iter()
+        .map(|column| column.into_iter().map(encode_element).collect())
+        .collect::<Vec<Value>>();
+
+    let assertions = air
+        .get_assertions()
+        .iter()
+        .map(|assertion| {
+            let mut result = Map::new();
+            result.insert("register".into(), assertion.register().into());
+            result.insert("first_step".into(), assertion.first_step().into());
+            result.insert("stride".into(), assertion.stride().into());
+            result.insert(
+                "values".into(),
+                assertion
+                    .values()
+                    .iter()
+                    .copied()
+                    .map(encode_element)
+                    .collect(),
+            );
+            result.insert(
+                "divisor".into(),
+                encode_divisor(&ConstraintDivisor::from_assertion(
+                    assertion,
+                    air.trace_length(),
+                )),
+            );
+            Value::Object(result)
+        })
+        .collect::<Vec<_>>();
+
+    let domain_point_constraints = air
+        .get_domain_point_constraints::<A::BaseElement>()
+        .iter()
+        .map(|constraint| {
+            let terms = if constraint.terms().is_empty() {
+                vec![(constraint.register(), A::BaseElement::ONE)]
+            } else {
+                constraint.terms().to_vec()
+            };
+            let point = constraint
+                .domain_point()
+                .expect("domain point constraint must specify a domain point");
+            let mut result = Map::new();
+            result.insert(
+                "terms".into(),
+                terms
+                    .into_iter()
+                    .map(|(register, coefficient)| {
+                        Value::Array(vec![register.into(), encode_element(coefficient)])
+                    })
+                    .collect(),
+            );
+            result.insert("point".into(), encode_element(point));
+            result.insert("value".into(), encode_element(constraint.poly()[0]));
+            result.insert(
+                "divisor".into(),
+                encode_divisor(&ConstraintDivisor::from_domain_point(point)),
+            );
+            Value::Object(result)
+        })
+        .collect::<Vec<_>>();
+
+    let mut result = Map::new();
+    result.insert("version".into(), DESCRIPTION_VERSION.into());
+    result.insert("field".into(), Value::Object(field));
+    result.insert("trace".into(), Value::Object(trace));
+    result.insert("ce_blowup_factor".into(), air.ce_blowup_factor().into());
+    result.insert(
+        "transition_constraints".into(),
+        Value::Array(transition_constraints),
+    );
+    result.insert(
+        "transition_divisor".into(),
+        encode_divisor(&air.transition_constraint_divisor()),
+    );
+    result.insert("constraint_groups".into(), Value::Array(constraint_groups));
+    result.insert(
+        "combination_mode".into(),
+        match air.constraint_combination_mode() {
+            CombinationMode::Linear => "linear",
+            CombinationMode::PowerSeries => "power_series",
+        }
+        .into(),
+    );
+    result.insert("periodic_columns".into(), Value::Array(periodic_columns));
+    result.insert("assertions".into(), Value::Array(assertions));
+    result.insert(
+        "domain_point_constraints".into(),
+        Value::Array(domain_point_constraints),
+    );
+    Value::Object(result).to_string()
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn encode_element<B: StarkField>(element: B) -> Value {
+    Value::String(element.to_hex_string())
+}
+
+fn encode_bytes(bytes: &[u8]) -> Value {
+    let mut result = String::with_capacity(2 + bytes.len() * 2);
+    result.push_str("0x");
+    for byte in bytes {
+        result.push_str(&format!("{:02x}", byte));
+    }
+    Value::String(result)
+}
+
+/// Encodes the divisor as an object with `numerator` field containing `[degree, constant]`
+/// pairs of the $(x^{degree} - constant)$ factors, and `exclude` field containing the points
+/// $a$ of the $(x - a)$ factors of the denominator.
+fn encode_divisor<B: StarkField>(divisor: &ConstraintDivisor<B>) -> Value {
+    let mut result = Map::new();
+    result.insert(
+        "numerator".into(),
+        divisor
+            .numerator()
+            .iter()
+            .map(|&(degree, constant)| Value::Array(vec![degree.into(), encode_element(constant)]))
+            .collect(),
+    );
+    result.insert(
+        "exclude".into(),
+        divisor
+            .exclude()
+            .iter()
+            .copied()
+            .map(encode_element)
+            .collect(),
+    );
+    Value::Object(result)
+}
@@ -51,8 +51,70 @@ impl<B: StarkField> ConstraintDivisor<B> {
     /// The above divisor specifies that transition constraints must hold on all steps of the
     /// execution trace except for the last one.
     pub fn from_transition(trace_length: usize) -> Self {
-        let x_at_last_step = get_trace_domain_value_at::<B>(trace_length, trace_length - 1);
-        Self::new(vec![(trace_length, B::ONE)], vec![x_at_last_step])
+        Self::from_transition_with_exemptions(trace_length, 1)
+    }
+
+    /// Builds a divisor for transition constraints which are not enforced on the last
+    /// `num_exemptions` steps of the execution trace.
+    ///
+    /// The divisor polynomial $z(x)$ is defined as:
+    ///
+    /// $$
+    /// z(x) = \frac{x^n - 1}{\prod_{i=1}^{k} (x - g^{n - i})}
+    /// $$
+    ///
+    /// where, $n$ is the length of the execution trace, $k$ is the number of exemptions, and $g$
+    /// is the generator of the trace domain.
+    ///
+    /// # Panics
+    /// Panics if `num_exemptions` is zero or is not smaller than `trace_length`.
+    pub fn from_transition_with_exemptions(trace_length: usize, num_exemptions: usize) -> Self {
+        assert!(
+            num_exemptions > 0,
+            "number of transition exemptions must be greater than zero"
+        );
+        assert!(
+            num_exemptions < trace_length,
+            "number of transition exemptions must be smaller than trace length {}, but was {}",
+            trace_length,
+            num_exemptions
+        );
+        let exclude = (trace_length - num_exemptions..trace_length)
+            .map(|step| get_trace_domain_value_at::<B>(trace_length, step))
+            .collect();
+        Self::new(vec![(trace_length, B::ONE)], exclude)
+    }
+
+    /// Builds a divisor for padding constraints of an execution trace with `num_padding_rows`
+    /// padding rows.
+    ///
+    /// Padding constraints must hold on the steps at which the next row of the evaluation frame
+    /// is a padding row; thus, the divisor polynomial $z(x)$ is defined as:
+    ///
+    /// $$
+    /// z(x) = \prod_{i=2}^{k + 1} (x - g^{n - i})
+    /// $$
+    ///
+    /// where, $n$ is the length of the execution trace, $k$ is the number of padding rows, and
+    /// $g$ is the generator of the trace domain.
+    ///
+    /// # Panics
+    /// Panics if `num_padding_rows` is zero or is not smaller than `trace_length`.
+    pub fn from_padding(trace_length: usize, num_padding_rows: usize) -> Self {
+        assert!(
+            num_padding_rows > 0,
+            "number of padding rows must be greater than zero"
+        );
+        assert!(
+            num_padding_rows < trace_length,
+            "number of padding rows must be smaller than trace length {}, but was {}",
+            trace_length,
+            num_padding_rows
+        );
+        let numerator = (trace_length - num_padding_rows - 1..trace_length - 1)
+            .map(|step| (1, get_trace_domain_value_at::<B>(trace_length, step)))
+            .collect();
+        Self::new(numerator, vec![])
     }
 
     /// Builds a divisor for a boundary constraint described by the assertion.
@@ -76,11 +138,29 @@ impl<B: StarkField> ConstraintDivisor<B> {
     ///   $(x - g^a) \cdot (x - g^{a + j}) \cdot (x - g^{a + 2 \cdot j}) ... (x - g^{a + (k  - 1) \cdot j})$,
     ///   where $j$ is the length of interval between asserted steps (e.g. 8).
     ///
+    /// For assertions against $k$ consecutive steps starting at step $a$, the divisor polynomial
+    /// is defined as a product of $k$ terms:
+    ///
+    /// $$
+    /// z(x) = \prod_{i=0}^{k-1} (x - g^{a + i})
+    /// $$
+    ///
     /// # Panics
     /// Panics of the specified `trace_length` is inconsistent with the specified `assertion`.
-    pub fn from_assertion(assertion: &Assertion<B>, trace_length: usize) -> Self {
+    pub fn from_assertion<E>(assertion: &Assertion<E>, trace_length: usize) -> Self
+    where
+        E: FieldElement<BaseField = B>,
+    {
         let num_steps = assertion.get_num_steps(trace_length);
-        if assertion.first_step == 0 {
+        if assertion.is_consecutive() {
+            let numerator = (0..num_steps)
+                .map(|i| {
+                    let x = get_trace_domain_value_at::<B>(trace_length, assertion.first_step + i);
+                    (1, x)
+                })
+                .collect();
+            Self::new(numerator, vec![])
+        } else if assertion.first_step == 0 {
             Self::new(vec![(num_steps, B::ONE)], vec![])
         } else {
             let trace_offset = num_steps * assertion.first_step;
@@ -89,6 +169,13 @@ impl<B: StarkField> ConstraintDivisor<B> {
         }
     }
 
+    /// Builds a divisor for a boundary constraint placed at the specified domain `point`.
+    ///
+    /// The divisor polynomial is defined as $z(x) = (x - p)$, where $p$ is the domain point.
+    pub fn from_domain_point(point: B) -> Self {
+        Self::new(vec![(1, point)], vec![])
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -326,4 +413,57 @@ mod tests {
             }
         }
     }
+    #[test]
+    fn constraint_divisor_from_consecutive_assertion() {
+        let n = 16_usize;
+        let g = BaseElement::get_root_of_unity(n.trailing_zeros());
+
+        // create a divisor for assertion against steps 5, 6, 7
+        let values = vec![BaseElement::ONE, BaseElement::ZERO, BaseElement::ONE];
+        let assertion = Assertion::consecutive(0, 5, values);
+        let divisor = ConstraintDivisor::from_assertion(&assertion, n);
+        assert_eq!(3, divisor.degree());
+
+        // z(x) = (x - g^5) * (x - g^6) * (x - g^7)
+        let poly = polynom::mul(
+            &polynom::mul(
+                &[-g.exp(5), BaseElement::ONE],
+                &[-g.exp(6), BaseElement::ONE],
+            ),
+            &[-g.exp(7), BaseElement::ONE],
+        );
+
+        for i in 0..n {
+            let expected = polynom::eval(&poly, g.exp(i as u128));
+            let actual = divisor.evaluate_at(g.exp(i as u128));
+            assert_eq!(expected, actual);
+            assert_eq!((5..8).contains(&i), actual == BaseElement::ZERO);
+        }
+    }
+
+    #[test]
+    fn constraint_divisor_from_transition_with_exemptions() {
+        let n = 16_usize;
+        let g = BaseElement::get_root_of_unity(n.trailing_zeros());
+
+        // a single exemption is the same as the regular transition divisor
+        assert_eq!(
+            ConstraintDivisor::<BaseElement>::from_transition(n),
+            ConstraintDivisor::from_transition_with_exemptions(n, 1)
+        );
+
+        // divisor with 3 exemptions: z(x) = (x^16 - 1) / ((x - g^13) * (x - g^14) * (x - g^15))
+        let divisor = ConstraintDivisor::from_transition_with_exemptions(n, 3);
+        assert_eq!(n - 3, divisor.degree());
+
+        // the divisor must evaluate to ZERO on all non-exempted steps
+        for i in 0..n - 3 {
+            assert_eq!(BaseElement::ZERO, divisor.evaluate_at(g.exp(i as u128)));
+        }
+
+        let x = BaseElement::new(7);
+        let expected = (x.exp(n as u128) - BaseElement::ONE)
+            / ((x - g.exp(13)) * (x - g.exp(14)) * (x - g.exp(15)));
+        assert_eq!(expected, divisor.evaluate_at(x));
+    }
 }
@@ -3,9 +3,9 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use crate::ProofOptions;
-use crypto::{Hasher, RandomCoin, RandomCoinError};
-use math::{fft, ExtensibleField, FieldElement, StarkField};
+use crate::{EvaluationError, ProofOptions};
+use crypto::{PublicCoin, RandomCoinError};
+use math::{fft, ExtensibleField, FieldElement};
 use utils::{
     collections::{BTreeMap, BTreeSet, Vec},
     Serializable,
@@ -18,20 +18,40 @@ mod context;
 pub use context::AirContext;
 
 mod assertions;
-pub use assertions::Assertion;
+pub use assertions::{Assertion, LagrangeBoundaryConstraints};
 
 mod boundary;
 pub use boundary::{BoundaryConstraint, BoundaryConstraintGroup};
 
 mod transition;
-pub use transition::{EvaluationFrame, TransitionConstraintDegree, TransitionConstraintGroup};
+pub use transition::{
+    ConstraintGroup, EvaluationFrame, PaddingConstraintGroup, TransitionConstraintDegree,
+    TransitionConstraintGroup,
+};
 
 mod coefficients;
-pub use coefficients::{ConstraintCompositionCoefficients, DeepCompositionCoefficients};
+pub use coefficients::{
+    CombinationMode, ConstraintCompositionCoefficients, DeepCompositionCoefficients,
+};
 
 mod divisor;
 pub use divisor::ConstraintDivisor;
 
+mod composite;
+pub use composite::{CompositeAir, CompositePublicInputs};
+
+mod stacked;
+pub use stacked::{StackedAir, StackedPublicInputs};
+
+mod tagged;
+pub use tagged::TaggedAir;
+
+mod verification_key;
+pub use verification_key::VerificationKey;
+
+#[cfg(feature = "serde_json")]
+mod describe;
+
 #[cfg(test)]
 mod tests;
 
@@ -51,16 +71,18 @@ const MIN_CYCLE_LENGTH: usize = 2;
 ///    [math::fields] for available field options).
 /// 2. Define a set of public inputs which are required for your computation via the
 ///    [Air::PublicInputs] associated type.
-/// 3. Implement [Air::new()] function. As a part of this function you should create a
+/// 3. Specify the maximum degree of transition constraints via the
+///    [Air::MAX_CONSTRAINT_DEGREE] associated constant.
+/// 4. Implement [Air::new()] function. As a part of this function you should create a
 ///    [AirContext] struct which takes degrees for all transition constraints as one of
 ///    the constructor parameters.
-/// 4. Implement [Air::context()] method which should return a reference to the
+/// 5. Implement [Air::context()] method which should return a reference to the
 ///    [AirContext] struct created in [Air::new()] function.
-/// 5. Implement [Air::evaluate_transition()] method which should evaluate
+/// 6. Implement [Air::evaluate_transition()] method which should evaluate
 ///    [transition constraints](#transition-constraints) over a given evaluation frame.
-/// 6. Implement [Air::get_assertions()] method which should return a vector of
+/// 7. Implement [Air::get_assertions()] method which should return a vector of
 ///    [assertions](#trace-assertions) for a given instance of your computation.
-/// 7. If your computation requires [periodic values](#periodic-values), you can also override
+/// 8. If your computation requires [periodic values](#periodic-values), you can also override
 ///    the default [Air::get_periodic_column_values()] method.
 ///
 /// ### Transition constraints
@@ -142,6 +164,18 @@ const MIN_CYCLE_LENGTH: usize = 2;
 /// [Air::get_periodic_column_values()] method. The values of the periodic columns at a given
 /// step of the computation will be supplied to the [Air::evaluate_transition()] method via the
 /// `periodic_values` parameter.
+///
+/// ### Auxiliary trace segment
+/// Some constraints (e.g., permutation checks) require trace columns which depend on random
+/// values chosen by the verifier after the prover has committed to the execution trace. Such
+/// columns form an auxiliary segment of the trace: its width and the number of random elements
+/// needed to build it are specified via [Air::auxiliary_trace_width()] and
+/// [Air::num_aux_rand_elements()] methods, transition constraints against it are declared via
+/// [AirContext::with_aux_transition_constraint_degrees()] and evaluated in
+/// [Air::evaluate_aux_transition()], and assertions against it are returned from
+/// [Air::get_aux_assertions()] method.
+/// The random elements are drawn from the field in which the protocol is executed, and thus,
+/// values in the auxiliary segment are elements of this field rather than of the base field.
 pub trait Air: Send + Sync {
     /// Base field for the computation described by this AIR. STARK protocol for this computation
     /// may be executed in the base field, or in an extension of the base fields as specified
@@ -150,8 +184,31 @@ pub trait Air: Send + Sync {
 
     /// A type defining shape of public inputs for the computation described by this protocol.
     /// This could be any type as long as it can be serialized into a sequence of bytes.
+    ///
+    /// Public inputs of variable length (e.g., inputs and outputs of a program) can be stored in
+    /// vectors of integers or field elements. Vectors are serialized without a length prefix,
+    /// and thus, the [Serializable] implementation of public inputs should write the length of
+    /// each vector before its elements. Serialized public inputs are used to seed the public coin,
+    /// and this makes sure that different public inputs always result in different seeds.
     type PublicInputs: Serializable;
 
+    /// Maximum degree of transition constraints of this computation, not counting multiplications
+    /// by periodic columns.
+    ///
+    /// This must be equal to the largest base degree of the transition constraint degree
+    /// descriptors used to instantiate [AirContext] for this AIR. The prover and the verifier
+    /// check this when an instance of this AIR is created. In debug builds, the prover also
+    /// checks that the declared degrees are the actual degrees of the transition constraints by
+    /// evaluating the constraints at random points.
+    const MAX_CONSTRAINT_DEGREE: usize;
+
+    /// Minimum conjectured security level (in bits) of proofs generated for this computation.
+    ///
+    /// The prover refuses to generate a proof if the security level provided by the proof
+    /// options for the trace of the computation (as computed by
+    /// [ProofOptions::security_bits()]) is lower than this value.
+    const REQUIRED_SECURITY_BITS: usize = 96;
+
     // REQUIRED METHODS
     // --------------------------------------------------------------------------------------------
 
@@ -170,6 +227,9 @@ pub trait Air: Send + Sync {
 
     /// Evaluates transition constraints over the specified evaluation frame.
     ///
+    /// The frame contains [frame_size()](Air::frame_size) consecutive rows of the execution
+    /// trace starting with the row at the current step.
+    ///
     /// The evaluations should be written into the `results` slice in the same order as the
     /// the order of transition constraint degree descriptors used to instantiate [AirContext]
     /// for this AIR. Thus, the length of the `result` slice will equal to the number of
@@ -182,6 +242,12 @@ pub trait Air: Send + Sync {
     );
 
     /// Returns a set of assertions against a concrete execution trace of this computation.
+    ///
+    /// The prover and the verifier call this method exactly once for every proof (right after
+    /// the AIR is instantiated), and pass the returned assertions by reference to the places
+    /// where they are needed. Thus, it is fine for this method to be expensive (e.g., when the
+    /// assertions embed large public inputs), but the returned assertions must not change over
+    /// the lifetime of the AIR.
     fn get_assertions(&self) -> Vec<Assertion<Self::BaseElement>>;
 
     // PROVIDED METHODS
@@ -200,6 +266,201 @@ pub trait Air: Send + Sync {
         Vec::new()
     }
 
+    /// Returns the number of auxiliary columns expected in the execution trace of the
+    /// computation.
+    ///
+    /// Auxiliary columns (e.g., for permutation or LogUp arguments) are built by the prover only
+    /// after the main segment of the trace has been committed to, and their values depend on
+    /// [random elements](Air::num_aux_rand_elements) drawn from the public coin. The auxiliary
+    /// segment is committed to separately from the main segment; in evaluation frames of the
+    /// auxiliary segment and in assertions against it, registers are indexed from 0.
+    ///
+    /// The default implementation of this method returns 0. Proofs for computations which
+    /// declare a non-zero number of auxiliary columns must be generated via
+    /// `prove_with_aux_segment()` function of the prover crate; trying to generate such proofs
+    /// without an auxiliary segment fails with `ProverError::MismatchedAuxiliaryTraceWidth`.
+    fn auxiliary_trace_width(&self) -> usize {
+        0
+    }
+
+    /// Returns the number of random elements needed to build the auxiliary segment of the
+    /// execution trace.
+    ///
+    /// The elements are drawn from the public coin right after the commitment to the main
+    /// segment of the trace, and are passed to the builder of the auxiliary segment, to
+    /// [evaluate_aux_transition()](Air::evaluate_aux_transition), and to
+    /// [get_aux_assertions()](Air::get_aux_assertions). The elements are drawn from the field in
+    /// which the protocol is executed (i.e., from the extension field when a field extension is
+    /// used), and thus, values of the auxiliary segment are elements of this field as well.
+    ///
+    /// The default implementation of this method returns 0.
+    fn num_aux_rand_elements(&self) -> usize {
+        0
+    }
+
+    /// Evaluates transition constraints against the auxiliary segment of the execution trace
+    /// over the specified evaluation frames.
+    ///
+    /// `main_frame` and `aux_frame` contain the same rows of the main and the auxiliary segments
+    /// of the trace respectively, and `aux_rand_elements` are the random elements used to build
+    /// the auxiliary segment. The evaluations should be written into the `result` slice in the
+    /// order of the auxiliary transition constraint degrees set via
+    /// [AirContext::with_aux_transition_constraint_degrees()].
+    ///
+    /// This method is invoked only for computations which declare auxiliary transition
+    /// constraints, and thus, must be implemented by all such computations. The default
+    /// implementation of this method returns
+    /// [EvaluationError::MissingAuxTransitionEvaluator]; the prover and the verifier reject
+    /// proofs for computations for which this error is returned.
+    fn evaluate_aux_transition<E: FieldElement<BaseField = Self::BaseElement>>(
+        &self,
+        _main_frame: &EvaluationFrame<E>,
+        _aux_frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        _aux_rand_elements: &[E],
+        _result: &mut [E],
+    ) -> Result<(), EvaluationError> {
+        Err(EvaluationError::MissingAuxTransitionEvaluator)
+    }
+
+    /// Returns a set of assertions against the auxiliary segment of the execution trace built
+    /// using the specified random elements.
+    ///
+    /// Registers of these assertions refer to the registers of the auxiliary segment (i.e., the
+    /// first auxiliary register has index 0). The prover
+    /// and the verifier call this method once for every proof, right after the random elements
+    /// are drawn.
+    ///
+    /// The default implementation of this method returns an empty vector.
+    fn get_aux_assertions<E: FieldElement<BaseField = Self::BaseElement>>(
+        &self,
+        _aux_rand_elements: &[E],
+    ) -> Vec<Assertion<E>> {
+        Vec::new()
+    }
+
+    /// Returns groups of transition constraints which should be combined using the same random
+    /// coefficients during construction of the constraint composition polynomial.
+    ///
+    /// For each group with `shared_coefficient` set, a single pair of composition coefficients is
+    /// drawn from the public coin and is used for all constraints in the group. Constraints which
+    /// do not belong to any such group are assigned their own coefficients.
+    ///
+    /// The default implementation of this method returns an empty vector, and thus, every
+    /// transition constraint is combined using its own coefficients.
+    fn get_constraint_groups(&self) -> Vec<ConstraintGroup> {
+        Vec::new()
+    }
+
+    /// Returns the mode in which random coefficients for combining constraints into the
+    /// constraint composition polynomial are drawn from the public coin.
+    ///
+    /// The default implementation of this method returns [CombinationMode::Linear], and thus,
+    /// every pair of coefficients is drawn independently.
+    fn constraint_combination_mode(&self) -> CombinationMode {
+        CombinationMode::Linear
+    }
+
+    /// Returns a seed which is absorbed into the public coin before any commitments are made.
+    ///
+    /// This can be used for domain separation between different types of proofs in the same
+    /// protocol: computations with different seeds produce different transcripts even for the
+    /// same execution trace. The seed is appended to the public inputs and the proof context when
+    /// the public coin is instantiated, both by the prover and by the verifier.
+    ///
+    /// The default implementation of this method returns all zeros; in this case, nothing is
+    /// absorbed into the public coin, and thus, transcripts are the same as for computations
+    /// which do not define a seed.
+    fn channel_seed(&self) -> [u8; 32] {
+        [0; 32]
+    }
+
+    /// Returns boundary constraints placed at arbitrary points of the domain of trace
+    /// polynomials.
+    ///
+    /// Assertions returned from [get_assertions()](Air::get_assertions) always refer to steps
+    /// of the execution trace, which correspond to points $g^i$ of the trace domain. Constraints
+    /// returned from this method are created via
+    /// [BoundaryConstraint::at_domain_point()](BoundaryConstraint::at_domain_point) and specify
+    /// the domain point directly; e.g., a root of unity of a larger domain, or a point of a
+    /// coset. Such points must not belong to the constraint evaluation domain.
+    ///
+    /// Constraints created via
+    /// [BoundaryConstraint::linear_combination()](BoundaryConstraint::linear_combination) are
+    /// placed against a linear combination of registers; placing such a constraint at $g^i$
+    /// asserts a linear relation between values of the registers at step $i$.
+    ///
+    /// Composition coefficients for these constraints are drawn after the coefficients for
+    /// assertions, in the order in which the constraints are returned.
+    ///
+    /// The default implementation of this method returns an empty vector.
+    fn get_domain_point_constraints<E: FieldElement<BaseField = Self::BaseElement>>(
+        &self,
+    ) -> Vec<BoundaryConstraint<Self::BaseElement, E>> {
+        Vec::new()
+    }
+
+    /// Returns true if the transition constraint at the specified index is linear.
+    ///
+    /// A linear constraint is an affine function of the current and the next rows of the
+    /// execution trace which does not depend on values of periodic columns; the degree of such
+    /// a constraint must be 1. The prover computes a random linear combination of all linear
+    /// constraints directly from the columns of the extended execution trace, and thus,
+    /// evaluations of linear constraints written by
+    /// [evaluate_transition()](Air::evaluate_transition) are ignored by the prover. When all
+    /// transition constraints of the computation are linear, the prover does not invoke
+    /// [evaluate_transition()](Air::evaluate_transition) at all (in release builds).
+    ///
+    /// This does not affect the proof or the verifier; constraint indexes are assumed to be
+    /// consistent with the order in which constraint evaluations are written into the `result`
+    /// slice by the [evaluate_transition()](Air::evaluate_transition) method. This method is not
+    /// invoked for auxiliary transition constraints.
+    ///
+    /// The default implementation of this method returns false for all constraints.
+    fn is_transition_constraint_linear(&self, _index: usize) -> bool {
+        false
+    }
+
+    /// Returns a machine-readable description of the constraints of this AIR as a JSON string.
+    ///
+    /// The description is meant for auditing constraints and for cross-checking them against
+    /// other implementations of the verifier. It is a JSON object with the following fields:
+    /// * `version` - version of the description format; currently, this is always 1.
+    /// * `field` - `modulus_bits` and `max_element` (i.e., the modulus minus one) of the base
+    ///   field, and the `extension` (`none`, `quadratic`, or `cubic`) in which the composition
+    ///   polynomial is constructed.
+    /// * `trace` - `width`, `length`, `num_padding_rows`, `aux_width`, `num_aux_rand_elements`,
+    ///   `frame_size`, and `meta` of the execution trace.
+    /// * `ce_blowup_factor` - blowup factor of the constraint evaluation domain.
+    /// * `transition_constraints` - for each transition constraint (main constraints first): its
+    ///   `index`, `segment` (`main` or `aux`), `base_degree`, periodic column `cycles`,
+    ///   `evaluation_degree`, `num_exemptions`, and whether it is declared `linear`.
+    /// * `transition_divisor` - the divisor shared by all transition constraints; constraints
+    ///   with more exemptions than the divisor excludes are multiplied by the factors of the
+    ///   additionally exempted steps.
+    /// * `constraint_groups` and `combination_mode` - as returned from
+    ///   [get_constraint_groups()](Air::get_constraint_groups) and
+    ///   [constraint_combination_mode()](Air::constraint_combination_mode).
+    /// * `periodic_columns` - values of each periodic column over one cycle.
+    /// * `assertions` - `register`, `first_step`, `stride`, `values`, and `divisor` of each
+    ///   assertion returned from [get_assertions()](Air::get_assertions).
+    /// * `domain_point_constraints` - `terms` (as `[register, coefficient]` pairs), `point`,
+    ///   `value`, and `divisor` of each constraint returned from
+    ///   [get_domain_point_constraints()](Air::get_domain_point_constraints).
+    ///
+    /// Divisors are objects with a `numerator` field listing `[degree, constant]` pairs of the
+    /// $(x^{degree} - constant)$ factors, and an `exclude` field listing the points $a$ of the
+    /// $(x - a)$ factors of the denominator. Field elements and bytes are encoded as hex strings
+    /// in the format of [FieldElement::to_hex_string()]. Assertions against the auxiliary trace
+    /// segment depend on random elements drawn during proof generation, and thus, are not
+    /// included.
+    ///
+    /// This method is available only when the `serde_json` feature is enabled.
+    #[cfg(feature = "serde_json")]
+    fn describe(&self) -> utils::string::String {
+        describe::describe(self)
+    }
+
     /// Returns polynomial for all periodic columns.
     ///
     /// These polynomials are interpolated from the values returned from the
@@ -240,7 +501,8 @@ pub trait Air: Send + Sync {
             .collect()
     }
 
-    /// Groups transition constraints together by their degree.
+    /// Groups transition constraints together by their degree and the number of steps from which
+    /// they are exempted.
     ///
     /// This function also assigns coefficients to each constraint. These coefficients will be
     /// used to compute a random linear combination of transition constraints evaluations during
@@ -256,16 +518,28 @@ pub trait Air: Send + Sync {
         );
 
         // iterate over all transition constraint degrees, and assign each constraint to the
-        // appropriate group based on degree
+        // appropriate group based on degree and the number of exemptions; steps exempted by the
+        // transition constraint divisor are the last `num_transition_exemptions` steps, and any
+        // additional exemptions are the steps immediately preceding them
         let context = self.context();
+        let divisor_degree = self.transition_constraint_divisor().degree();
+        let num_divisor_exemptions = context.num_transition_exemptions();
+        let g = self.trace_domain_generator();
         let mut groups = BTreeMap::new();
         for (i, degree) in context.transition_constraint_degrees.iter().enumerate() {
             let evaluation_degree = degree.get_evaluation_degree(self.trace_length());
-            let group = groups.entry(evaluation_degree).or_insert_with(|| {
+            let num_exemptions = context.num_constraint_exemptions(i);
+            let key = (evaluation_degree, num_exemptions);
+            let group = groups.entry(key).or_insert_with(|| {
+                let exemption_points = (num_divisor_exemptions + 1..=num_exemptions)
+                    .map(|j| E::from(g.exp(((self.trace_length() - j) as u64).into())))
+                    .collect();
                 TransitionConstraintGroup::new(
                     degree.clone(),
                     self.trace_poly_degree(),
+                    divisor_degree,
                     self.composition_degree(),
+                    exemption_points,
                 )
             });
             group.add(i, coefficients[i]);
@@ -275,14 +549,24 @@ pub trait Air: Send + Sync {
         groups.into_iter().map(|e| e.1).collect()
     }
 
-    /// Convert assertions returned from [get_assertions()](Air::get_assertions) method into
+    /// Convert `assertions` returned from [get_assertions()](Air::get_assertions) method into
     /// boundary constraints.
     ///
     /// This function also assign coefficients to each constraint, and group the constraints by
     /// denominator. The coefficients will be used to compute random linear combination of boundary
-    /// constraints during constraint merging.
+    /// constraints during constraint merging. Constraints returned from
+    /// [get_domain_point_constraints()](Air::get_domain_point_constraints) are added to the
+    /// result as well, and are assigned coefficients following the coefficients of assertions.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * The number of coefficients is different from the number of assertions plus the number
+    ///   of domain point constraints.
+    /// * Any of the domain point constraints is placed against a register which does not exist,
+    ///   or at a point of the constraint evaluation domain.
     fn get_boundary_constraints<E: FieldElement<BaseField = Self::BaseElement>>(
         &self,
+        assertions: &[Assertion<Self::BaseElement>],
         coefficients: &[(E, E)],
     ) -> Vec<BoundaryConstraintGroup<Self::BaseElement, E>> {
         // compute inverse of the trace domain generator; this will be used for offset
@@ -297,37 +581,145 @@ pub trait Air: Send + Sync {
         // the context of this computation; also, sort the assertions in the deterministic order
         // so that changing the order of assertions does not change random coefficients that
         // get assigned to them
-        let assertions = prepare_assertions(self.get_assertions(), self.context());
+        let assertions =
+            prepare_assertions(assertions.to_vec(), self.trace_width(), self.trace_length());
+        let domain_point_constraints = self.get_domain_point_constraints::<E>();
+        let num_assertions = assertions.len();
         assert_eq!(
-            assertions.len(),
+            num_assertions + domain_point_constraints.len(),
             coefficients.len(),
-            "number of assertions must match the number of coefficient tuples"
+            "number of assertions and domain point constraints must match the number of coefficient tuples"
+        );
+        let mut groups = group_assertions(
+            self,
+            assertions,
+            inv_g,
+            &mut twiddle_map,
+            &coefficients[..num_assertions],
         );
 
-        // iterate over all assertions, which are sorted first by stride and then by first_step
-        // in ascending order
-        let mut groups = BTreeMap::new();
-        for (i, assertion) in assertions.into_iter().enumerate() {
-            let key = (assertion.stride(), assertion.first_step());
-            let group = groups.entry(key).or_insert_with(|| {
-                BoundaryConstraintGroup::new(
-                    ConstraintDivisor::from_assertion(&assertion, self.trace_length()),
-                    self.trace_poly_degree(),
-                    self.composition_degree(),
-                )
-            });
+        // domain point constraints have divisors of the form (x - point); constraints placed at
+        // the same point are put into the same group
+        let ce_domain_size = (self.ce_domain_size() as u64).into();
+        let mut point_groups: Vec<(Self::BaseElement, BoundaryConstraintGroup<_, E>)> = Vec::new();
+        for (i, constraint) in domain_point_constraints.into_iter().enumerate() {
+            let point = constraint
+                .domain_point()
+                .expect("domain point constraint must have a domain point");
+            let max_register = constraint
+                .terms()
+                .iter()
+                .map(|&(register, _)| register)
+                .fold(constraint.register(), usize::max);
+            assert!(
+                max_register < self.trace_width(),
+                "domain point constraint is placed against register {}, but the trace has only {} registers",
+                max_register,
+                self.trace_width()
+            );
+            assert!(
+                (point / self.domain_offset()).exp(ce_domain_size) != Self::BaseElement::ONE,
+                "domain point {} belongs to the constraint evaluation domain",
+                point
+            );
 
-            // add a new assertion constraint to the current group (last group in the list)
-            group.add(assertion, inv_g, &mut twiddle_map, coefficients[i]);
+            let coefficients = coefficients[num_assertions + i];
+            match point_groups.iter_mut().find(|(p, _)| *p == point) {
+                Some((_, group)) => group.add_domain_point_constraint(constraint, coefficients),
+                None => {
+                    let mut group = BoundaryConstraintGroup::new(
+                        ConstraintDivisor::from_domain_point(point),
+                        self.trace_poly_degree(),
+                        self.composition_degree(),
+                    );
+                    group.add_domain_point_constraint(constraint, coefficients);
+                    point_groups.push((point, group));
+                }
+            }
         }
+        groups.extend(point_groups.into_iter().map(|(_, group)| group));
+
+        // make sure groups are sorted by adjustment degree
+        groups.sort_by_key(|c| c.degree_adjustment());
+
+        groups
+    }
+
+    /// Convert `assertions` returned from [get_aux_assertions()](Air::get_aux_assertions) method
+    /// into boundary constraints against the auxiliary segment of the execution trace.
+    ///
+    /// Registers of the assertions refer to the registers of the auxiliary segment, and values
+    /// of the resulting constraints are elements of the extension field `E`. Otherwise, the
+    /// constraints are built, grouped, and assigned `coefficients` in the same way as by
+    /// [get_boundary_constraints()](Air::get_boundary_constraints).
+    ///
+    /// # Panics
+    /// Panics if the number of coefficients is different from the number of assertions, or if
+    /// any of the assertions is not valid against the auxiliary segment of the execution trace.
+    fn get_aux_boundary_constraints<E: FieldElement<BaseField = Self::BaseElement>>(
+        &self,
+        assertions: &[Assertion<E>],
+        coefficients: &[(E, E)],
+    ) -> Vec<BoundaryConstraintGroup<E, E>> {
+        let inv_g = self.trace_domain_generator().inv();
+        let mut twiddle_map = BTreeMap::new();
+
+        let assertions = prepare_assertions(
+            assertions.to_vec(),
+            self.auxiliary_trace_width(),
+            self.trace_length(),
+        );
+        assert_eq!(
+            assertions.len(),
+            coefficients.len(),
+            "number of auxiliary assertions must match the number of coefficient tuples"
+        );
+        let mut groups = group_assertions(self, assertions, inv_g, &mut twiddle_map, coefficients);
 
         // make sure groups are sorted by adjustment degree
-        let mut groups = groups.into_iter().map(|e| e.1).collect::<Vec<_>>();
         groups.sort_by_key(|c| c.degree_adjustment());
 
         groups
     }
 
+    /// Returns padding constraints of the computation merged using the specified composition
+    /// coefficients, or None if the execution trace of the computation does not contain
+    /// padding rows.
+    ///
+    /// Padding constraints require each padding row of the execution trace to be equal to the
+    /// row preceding it; thus, all padding rows must repeat the last row of the computation,
+    /// and assertions against the last step of the padded trace apply to the last step of the
+    /// computation as well. The constraints are defined for the registers of the main trace
+    /// segment only, and use [padding_constraint_divisor()](Air::padding_constraint_divisor) as
+    /// their divisor.
+    ///
+    /// # Panics
+    /// Panics if the number of coefficients is not equal to the width of the main trace segment
+    /// for a padded execution trace, or is not zero otherwise.
+    fn get_padding_constraints<E: FieldElement<BaseField = Self::BaseElement>>(
+        &self,
+        coefficients: &[(E, E)],
+    ) -> Option<PaddingConstraintGroup<E>> {
+        if self.context().num_padding_rows() == 0 {
+            assert!(
+                coefficients.is_empty(),
+                "padding constraint coefficients must be empty for traces without padding rows"
+            );
+            return None;
+        }
+        assert_eq!(
+            self.trace_width(),
+            coefficients.len(),
+            "number of padding constraint coefficients must match trace width"
+        );
+        Some(PaddingConstraintGroup::new(
+            self.trace_poly_degree(),
+            self.padding_constraint_divisor().degree(),
+            self.composition_degree(),
+            coefficients.to_vec(),
+        ))
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -359,6 +751,14 @@ pub trait Air: Send + Sync {
         self.context().trace_info.width()
     }
 
+    /// Returns the number of consecutive trace rows in the evaluation frames of the computation
+    /// described by this AIR.
+    ///
+    /// This is 2 unless a different frame size was set via [AirContext::with_frame_size()].
+    fn frame_size(&self) -> usize {
+        self.context().frame_size()
+    }
+
     /// Returns degree of trace polynomials for an instance of the computation described by
     /// this AIR.
     ///
@@ -435,7 +835,8 @@ pub trait Air: Send + Sync {
     /// computation described by this AIR.
     ///
     /// This list will be identical to the list passed into the [AirContext::new()] method as
-    /// the `transition_constraint_degrees` parameter.
+    /// the `transition_constraint_degrees` parameter, followed by the degrees of auxiliary
+    /// transition constraints (if any).
     fn transition_constraint_degrees(&self) -> &[TransitionConstraintDegree] {
         &self.context().transition_constraint_degrees
     }
@@ -444,7 +845,7 @@ pub trait Air: Send + Sync {
     /// by this AIR.
     ///
     /// The number of transition constraints is defined by the number of transition constraint
-    /// degree descriptors.
+    /// degree descriptors, and includes auxiliary transition constraints.
     fn num_transition_constraints(&self) -> usize {
         self.context().transition_constraint_degrees.len()
     }
@@ -453,15 +854,42 @@ pub trait Air: Send + Sync {
     ///
     /// All transition constraints have the same divisor which has the form:
     /// $$
-    /// z(x) = \frac{x^n - 1}{x - g^{n - 1}}
+    /// z(x) = \frac{x^n - 1}{\prod_{i=1}^{k} (x - g^{n - i})}
     /// $$
-    /// where: $n$ is the length of the execution trace and $g$ is the generator of the trace
-    /// domain.
+    /// where: $n$ is the length of the execution trace, $k$ is the number of
+    /// [transition exemptions](AirContext::num_transition_exemptions), and $g$ is the generator
+    /// of the trace domain.
     ///
     /// This divisor specifies that transition constraints must hold on all steps of the
-    /// execution trace except for the last one.
+    /// execution trace except for the last one; for padded execution traces, transition
+    /// constraints also do not need to hold on the last step of the computation and on the
+    /// padding rows. Constraints exempted from more steps (see
+    /// [AirContext::with_transition_constraint_exemptions()]) use the same divisor, but their
+    /// evaluations are multiplied by the divisor factors of the additionally exempted steps
+    /// when they are merged.
     fn transition_constraint_divisor(&self) -> ConstraintDivisor<Self::BaseElement> {
-        ConstraintDivisor::from_transition(self.trace_length())
+        ConstraintDivisor::from_transition_with_exemptions(
+            self.trace_length(),
+            self.context().num_transition_exemptions(),
+        )
+    }
+
+    /// Returns the divisor of [padding constraints](Air::get_padding_constraints) for an
+    /// instance of the computation described by this AIR.
+    ///
+    /// The divisor polynomial is defined as:
+    /// $$
+    /// z(x) = \prod_{i=2}^{k + 1} (x - g^{n - i})
+    /// $$
+    /// where: $n$ is the length of the execution trace, $k$ is the number of
+    /// [padding rows](AirContext::num_padding_rows), and $g$ is the generator of the trace
+    /// domain. That is, padding constraints must hold on all steps at which the next row of the
+    /// evaluation frame is a padding row.
+    ///
+    /// # Panics
+    /// Panics if the execution trace of the computation does not contain padding rows.
+    fn padding_constraint_divisor(&self) -> ConstraintDivisor<Self::BaseElement> {
+        ConstraintDivisor::from_padding(self.trace_length(), self.context().num_padding_rows())
     }
 
     // LINEAR COMBINATION COEFFICIENTS
@@ -469,44 +897,89 @@ pub trait Air: Send + Sync {
 
     /// Returns coefficients needed for random linear combination during construction of constraint
     /// composition polynomial.
-    fn get_constraint_composition_coefficients<E, H>(
+    ///
+    /// `num_assertions` must be the number of assertions returned from
+    /// [get_assertions()](Air::get_assertions) method plus the number of constraints returned
+    /// from [get_domain_point_constraints()](Air::get_domain_point_constraints) method plus the
+    /// number of assertions returned from [get_aux_assertions()](Air::get_aux_assertions) method.
+    /// Boundary coefficients are assigned in the same order: coefficients for the assertions
+    /// against the auxiliary trace segment follow coefficients for all other boundary constraints.
+    ///
+    /// Coefficients for transition constraints are drawn in the order of constraint indexes; for
+    /// a [constraint group](Air::get_constraint_groups) with a shared coefficient, a single pair
+    /// of coefficients is drawn when the first constraint of the group is reached. In
+    /// [CombinationMode::PowerSeries] mode, a single element is drawn from the public coin instead,
+    /// and the pairs are derived from its powers in the same order. For padded execution traces,
+    /// coefficients for [padding constraints](Air::get_padding_constraints) are drawn last, one
+    /// pair per register of the main trace segment.
+    ///
+    /// # Panics
+    /// Panics if any of the constraint groups references a transition constraint which does not
+    /// exist, or if a transition constraint belongs to more than one group.
+    fn get_constraint_composition_coefficients<E, C>(
         &self,
-        public_coin: &mut RandomCoin<Self::BaseElement, H>,
+        public_coin: &mut C,
+        num_assertions: usize,
     ) -> Result<ConstraintCompositionCoefficients<E>, RandomCoinError>
     where
         E: FieldElement<BaseField = Self::BaseElement>,
-        H: Hasher,
+        C: PublicCoin<BaseField = Self::BaseElement>,
     {
-        let mut t_coefficients = Vec::new();
-        for _ in 0..self.num_transition_constraints() {
-            t_coefficients.push(public_coin.draw_pair()?);
+        let num_constraints = self.num_transition_constraints();
+        let shared_groups =
+            build_shared_constraint_groups(&self.get_constraint_groups(), num_constraints);
+
+        // in power series mode, the last power of the drawn element is tracked together with the
+        // element itself
+        let mut powers = match self.constraint_combination_mode() {
+            CombinationMode::Linear => None,
+            CombinationMode::PowerSeries => Some((public_coin.draw::<E>()?, E::ONE)),
+        };
+
+        let mut t_coefficients: Vec<(E, E)> = Vec::with_capacity(num_constraints);
+        for (i, &group_start) in shared_groups.iter().enumerate() {
+            let coefficients = match group_start {
+                Some(first) if first < i => t_coefficients[first],
+                _ => draw_coefficient_pair(public_coin, &mut powers)?,
+            };
+            t_coefficients.push(coefficients);
         }
 
-        // TODO: calling self.get_assertions() is heavy; find a better way to specify the number
-        // assertions
         let mut b_coefficients = Vec::new();
-        for _ in 0..self.get_assertions().len() {
-            b_coefficients.push(public_coin.draw_pair()?);
+        for _ in 0..num_assertions {
+            b_coefficients.push(draw_coefficient_pair(public_coin, &mut powers)?);
+        }
+
+        // padding constraints are defined only for padded execution traces, and thus,
+        // coefficients for them are not drawn otherwise
+        let mut p_coefficients = Vec::new();
+        if self.context().num_padding_rows() > 0 {
+            for _ in 0..self.trace_width() {
+                p_coefficients.push(draw_coefficient_pair(public_coin, &mut powers)?);
+            }
         }
 
         Ok(ConstraintCompositionCoefficients {
             transition: t_coefficients,
             boundary: b_coefficients,
+            padding: p_coefficients,
         })
     }
 
     /// Returns coefficients needed for random linear combinations during construction of DEEP
     /// composition polynomial.
-    fn get_deep_composition_coefficients<E, H>(
+    fn get_deep_composition_coefficients<E, C>(
         &self,
-        public_coin: &mut RandomCoin<Self::BaseElement, H>,
+        public_coin: &mut C,
     ) -> Result<DeepCompositionCoefficients<E>, RandomCoinError>
     where
         E: FieldElement<BaseField = Self::BaseElement>,
-        H: Hasher,
+        C: PublicCoin<BaseField = Self::BaseElement>,
     {
+        // registers of the auxiliary trace segment follow the registers of the main segment
+        let trace_width = self.trace_width() + self.auxiliary_trace_width();
         let mut t_coefficients = Vec::new();
-        for _ in 0..self.trace_width() {
+        for _ in 0..trace_width {
             t_coefficients.push(public_coin.draw_triple()?);
         }
 
@@ -516,10 +989,24 @@ pub trait Air: Send + Sync {
             c_coefficients.push(public_coin.draw()?);
         }
 
+        let degree = public_coin.draw_pair()?;
+
+        // coefficients for additional rows of the evaluation frame are drawn last so that
+        // coefficients for frames of the default size do not depend on this feature
+        let mut additional_coefficients = Vec::new();
+        for _ in 2..self.frame_size() {
+            let mut row_coefficients = Vec::with_capacity(trace_width);
+            for _ in 0..trace_width {
+                row_coefficients.push(public_coin.draw()?);
+            }
+            additional_coefficients.push(row_coefficients);
+        }
+
         Ok(DeepCompositionCoefficients {
             trace: t_coefficients,
             constraints: c_coefficients,
-            degree: public_coin.draw_pair()?,
+            degree,
+            additional_trace: additional_coefficients,
         })
     }
 }
@@ -527,25 +1014,27 @@ pub trait Air: Send + Sync {
 // HELPER FUNCTIONS
 // ================================================================================================
 
-/// Makes sure the assertions are valid in the context of this computation and don't overlap with
-/// each other - i.e. no two assertions are placed against the same register and step combination.
-fn prepare_assertions<B: StarkField>(
-    assertions: Vec<Assertion<B>>,
-    context: &AirContext<B>,
-) -> Vec<Assertion<B>> {
+/// Makes sure the assertions are valid against a trace segment of the specified width and length
+/// and don't overlap with each other - i.e. no two assertions are placed against the same register
+/// and step combination.
+fn prepare_assertions<F: FieldElement>(
+    assertions: Vec<Assertion<F>>,
+    trace_width: usize,
+    trace_length: usize,
+) -> Vec<Assertion<F>> {
     // we use a sorted set to help us sort the assertions by their 'natural' order. The natural
     // order is defined as sorting first by stride, then by first step, and finally by register,
     // all in ascending order.
-    let mut result = BTreeSet::<Assertion<B>>::new();
+    let mut result = BTreeSet::<Assertion<F>>::new();
 
     for assertion in assertions.into_iter() {
         assertion
-            .validate_trace_width(context.trace_info.width())
+            .validate_trace_width(trace_width)
             .unwrap_or_else(|err| {
                 panic!("assertion {} is invalid: {}", assertion, err);
             });
         assertion
-            .validate_trace_length(context.trace_info.length())
+            .validate_trace_length(trace_length)
             .unwrap_or_else(|err| {
                 panic!("assertion {} is invalid: {}", assertion, err);
             });
@@ -563,3 +1052,97 @@ fn prepare_assertions<B: StarkField>(
 
     result.into_iter().collect()
 }
+
+/// Converts prepared `assertions` into boundary constraints grouped by divisor; the constraint
+/// built from the ith assertion is assigned the ith tuple of `coefficients`.
+fn group_assertions<A, F, E>(
+    air: &A,
+    assertions: Vec<Assertion<F>>,
+    inv_g: A::BaseElement,
+    twiddle_map: &mut BTreeMap<usize, Vec<A::BaseElement>>,
+    coefficients: &[(E, E)],
+) -> Vec<BoundaryConstraintGroup<F, E>>
+where
+    A: Air + ?Sized,
+    F: FieldElement<BaseField = A::BaseElement>,
+    E: FieldElement<BaseField = A::BaseElement> + From<F>,
+{
+    // iterate over all assertions, which are sorted first by stride and then by first_step
+    // in ascending order; for consecutive assertions, the divisor depends also on the number
+    // of asserted steps, and thus, the number of steps is included into the group key
+    let mut groups = BTreeMap::new();
+    for (assertion, &coefficients) in assertions.into_iter().zip(coefficients) {
+        let num_steps = assertion.get_num_steps(air.trace_length());
+        let key = (assertion.stride(), assertion.first_step(), num_steps);
+        let group = groups.entry(key).or_insert_with(|| {
+            BoundaryConstraintGroup::new(
+                ConstraintDivisor::from_assertion(&assertion, air.trace_length()),
+                air.trace_poly_degree(),
+                air.composition_degree(),
+            )
+        });
+
+        // add a new assertion constraint to the current group (last group in the list)
+        group.add(assertion, inv_g, twiddle_map, coefficients);
+    }
+    groups.into_iter().map(|e| e.1).collect()
+}
+
+/// Returns the next pair of constraint composition coefficients; if `powers` is set, the pair
+/// consists of the next two powers of the element drawn for [CombinationMode::PowerSeries] mode,
+/// otherwise, the pair is drawn from the public coin.
+fn draw_coefficient_pair<E, C>(
+    public_coin: &mut C,
+    powers: &mut Option<(E, E)>,
+) -> Result<(E, E), RandomCoinError>
+where
+    E: FieldElement,
+    C: PublicCoin<BaseField = E::BaseField>,
+{
+    match powers {
+        Some((r, power)) => {
+            let alpha = *power * *r;
+            let beta = alpha * *r;
+            *power = beta;
+            Ok((alpha, beta))
+        }
+        None => public_coin.draw_pair(),
+    }
+}
+
+/// Makes sure the constraint groups are valid for a computation with the specified number of
+/// transition constraints, and returns a vector which maps each transition constraint to the
+/// smallest constraint index of its group if the group shares coefficients.
+fn build_shared_constraint_groups(
+    groups: &[ConstraintGroup],
+    num_constraints: usize,
+) -> Vec<Option<usize>> {
+    let mut is_grouped = vec![false; num_constraints];
+    let mut result = vec![None; num_constraints];
+
+    for group in groups.iter() {
+        for &index in group.constraints() {
+            assert!(
+                index < num_constraints,
+                "constraint group references constraint {}, but the computation has only {} transition constraints",
+                index,
+                num_constraints
+            );
+            assert!(
+                !is_grouped[index],
+                "constraint {} belongs to more than one constraint group",
+                index
+            );
+            is_grouped[index] = true;
+        }
+
+        if group.shared_coefficient() {
+            let first = group.constraints().iter().copied().min();
+            for &index in group.constraints() {
+                result[index] = first;
+            }
+        }
+    }
+
+    result
+}
@@ -0,0 +1,318 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    Air, AirContext, Assertion, CombinationMode, ConstraintGroup, EvaluationFrame, ProofOptions,
+    TraceInfo, TransitionConstraintDegree,
+};
+use math::FieldElement;
+use utils::{collections::Vec, ByteWriter, Serializable};
+
+// STACKED PUBLIC INPUTS
+// ================================================================================================
+/// Public inputs for a [StackedAir] instance.
+///
+/// Contains public inputs of each of the stacked instances of the underlying computation, in the
+/// order in which execution traces of these instances are stacked.
+pub struct StackedPublicInputs<P: Serializable> {
+    instances: Vec<P>,
+}
+
+impl<P: Serializable> StackedPublicInputs<P> {
+    /// Returns new public inputs for a stacked computation instantiated from public inputs of
+    /// each of the stacked instances.
+    ///
+    /// # Panics
+    /// Panics if `instances` is empty or the number of instances is not a power of two.
+    pub fn new(instances: Vec<P>) -> Self {
+        assert!(
+            instances.len().is_power_of_two(),
+            "number of stacked instances must be a power of two, but was {}",
+            instances.len()
+        );
+        StackedPublicInputs { instances }
+    }
+
+    /// Returns the number of stacked instances.
+    pub fn num_instances(&self) -> usize {
+        self.instances.len()
+    }
+}
+
+impl<P: Serializable> Serializable for StackedPublicInputs<P> {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u32(self.instances.len() as u32);
+        for instance in self.instances.iter() {
+            instance.write_into(target);
+        }
+    }
+}
+
+// STACKED AIR
+// ================================================================================================
+/// An AIR describing several instances of the same computation executed one after another.
+///
+/// The execution trace of a stacked computation consists of execution traces of all instances
+/// placed one below another: for $N$ instances with traces of $n$ steps, steps $i \cdot n$
+/// through $(i + 1) \cdot n - 1$ of the stacked trace hold the trace of instance $i$. Thus, a
+/// single proof attests to all $N$ executions, and the size of this proof grows only
+/// logarithmically with $N$.
+///
+/// Transition constraints of the stacked computation are transition constraints of the
+/// underlying computation multiplied by segment selectors: periodic columns with a period of $n$
+/// steps which are set to zero on the steps at the end of each instance at which the constraint
+/// is exempted. This way, transition constraints are not enforced between the last rows of one
+/// instance and the first rows of the next one. Selector columns are placed after the periodic
+/// columns of the underlying computation.
+///
+/// Assertions of all instances are merged into assertions against the stacked trace: an
+/// assertion against a single step becomes a sequence assertion with a stride of $n$ steps,
+/// periodic and sequence assertions become sequence assertions with the same stride, and
+/// consecutive assertions are placed against each of the instances separately.
+///
+/// Transition constraints are evaluated using the AIR of the first instance, and thus, they must
+/// not depend on public inputs. The underlying computation must not have auxiliary trace
+/// segments or domain point constraints, and all of its instances must make assertions of the
+/// same kinds against the same steps.
+pub struct StackedAir<A: Air> {
+    context: AirContext<A::BaseElement>,
+    instances: Vec<A>,
+    num_inner_periodic_columns: usize,
+    selector_exemptions: Vec<usize>,
+    selector_indexes: Vec<usize>,
+}
+
+impl<A: Air> StackedAir<A> {
+    /// Returns AIRs of all stacked instances.
+    pub fn instances(&self) -> &[A] {
+        &self.instances
+    }
+
+    /// Returns the number of steps in the execution trace of each of the stacked instances.
+    pub fn segment_length(&self) -> usize {
+        self.trace_length() / self.instances.len()
+    }
+}
+
+impl<A: Air> Air for StackedAir<A> {
+    type BaseElement = A::BaseElement;
+    type PublicInputs = StackedPublicInputs<A::PublicInputs>;
+
+    const MAX_CONSTRAINT_DEGREE: usize = A::MAX_CONSTRAINT_DEGREE;
+
+    /// Instantiates AIRs for all stacked instances and builds a context for the stacked trace.
+    ///
+    /// Each of the underlying AIRs is instantiated with the trace width and metadata specified
+    /// by `trace_info`, a trace length equal to the trace length specified by `trace_info`
+    /// divided by the number of instances, and the same proof options.
+    ///
+    /// Stacked execution traces cannot contain padding rows, and thus, no padding rows are
+    /// declared in the context of the stacked AIR.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * The number of instances is greater than the trace length divided by
+    ///   [TraceInfo::MIN_TRACE_LENGTH].
+    /// * Any of the underlying AIRs cannot be instantiated from the provided parameters.
+    /// * The underlying computation has an auxiliary trace segment, padding rows, or domain point
+    ///   constraints.
+    /// * The blowup factor specified by `options` is too small to evaluate transition constraints
+    ///   multiplied by segment selectors.
+    fn new(trace_info: TraceInfo, pub_inputs: Self::PublicInputs, options: ProofOptions) -> Self {
+        let num_instances = pub_inputs.instances.len();
+        let segment_length = trace_info.length() / num_instances;
+        assert!(
+            segment_length >= TraceInfo::MIN_TRACE_LENGTH,
+            "trace length of each instance must be at least {}, but was {}",
+            TraceInfo::MIN_TRACE_LENGTH,
+            segment_length
+        );
+
+        let instances = pub_inputs
+            .instances
+            .into_iter()
+            .map(|inputs| {
+                let info = TraceInfo::with_meta(
+                    trace_info.width(),
+                    segment_length,
+                    trace_info.meta().to_vec(),
+                );
+                A::new(info, inputs, options.clone())
+            })
+            .collect::<Vec<_>>();
+
+        let first = &instances[0];
+        assert_eq!(
+            first.context().num_aux_transition_constraints(),
+            0,
+            "computations with auxiliary trace segments cannot be stacked"
+        );
+        assert_eq!(
+            first.context().num_padding_rows(),
+            0,
+            "computations with padded execution traces cannot be stacked"
+        );
+        assert!(
+            first
+                .get_domain_point_constraints::<A::BaseElement>()
+                .is_empty(),
+            "computations with domain point constraints cannot be stacked"
+        );
+
+        // a separate selector column is needed for each distinct number of exempted steps
+        let exemptions = (0..first.num_transition_constraints())
+            .map(|i| first.context().num_constraint_exemptions(i))
+            .collect::<Vec<_>>();
+        let mut selector_exemptions = exemptions.clone();
+        selector_exemptions.sort_unstable();
+        selector_exemptions.dedup();
+        let selector_indexes = exemptions
+            .iter()
+            .map(|e| selector_exemptions.binary_search(e).unwrap())
+            .collect();
+
+        let degrees = first
+            .transition_constraint_degrees()
+            .iter()
+            .map(|degree| {
+                let mut cycles = degree.cycles().to_vec();
+                cycles.push(segment_length);
+                TransitionConstraintDegree::with_cycles(degree.base(), cycles)
+            })
+            .collect();
+        let context = AirContext::new(trace_info, degrees, options)
+            .with_frame_size(first.frame_size())
+            .with_transition_constraint_exemptions(exemptions);
+        let num_inner_periodic_columns = first.get_periodic_column_values().len();
+
+        StackedAir {
+            context,
+            instances,
+            num_inner_periodic_columns,
+            selector_exemptions,
+            selector_indexes,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseElement> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement<BaseField = Self::BaseElement>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let (inner_periodic, selectors) = periodic_values.split_at(self.num_inner_periodic_columns);
+        self.instances[0].evaluate_transition(frame, inner_periodic, result);
+        for (result, &selector_idx) in result.iter_mut().zip(self.selector_indexes.iter()) {
+            *result *= selectors[selector_idx];
+        }
+    }
+
+    /// # Panics
+    /// Panics if the instances return different numbers of assertions, or if assertions at the
+    /// same position in the lists returned by the instances are placed against different
+    /// registers, start at different steps, or have different strides or numbers of values.
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseElement>> {
+        let segment_length = self.segment_length();
+        let instance_assertions = self
+            .instances
+            .iter()
+            .map(|instance| instance.get_assertions())
+            .collect::<Vec<_>>();
+        let first = &instance_assertions[0];
+
+        let mut result = Vec::with_capacity(first.len());
+        for (i, assertion) in first.iter().enumerate() {
+            let group = instance_assertions
+                .iter()
+                .map(|assertions| {
+                    assert_eq!(
+                        assertions.len(),
+                        first.len(),
+                        "all stacked instances must make the same number of assertions"
+                    );
+                    let other = &assertions[i];
+                    assert!(
+                        other.register == assertion.register
+                            && other.first_step == assertion.first_step
+                            && other.stride == assertion.stride
+                            && other.values.len() == assertion.values.len(),
+                        "assertion {} must have the same shape for all stacked instances",
+                        i
+                    );
+                    other
+                })
+                .collect::<Vec<_>>();
+
+            let (register, first_step) = (assertion.register, assertion.first_step);
+            if assertion.is_single() {
+                let values = group.iter().map(|a| a.values[0]).collect();
+                result.push(Assertion::sequence(
+                    register,
+                    first_step,
+                    segment_length,
+                    values,
+                ));
+            } else if assertion.is_periodic() {
+                let num_repeats = segment_length / assertion.stride;
+                let values = group
+                    .iter()
+                    .flat_map(|a| (0..num_repeats).map(move |_| a.values[0]))
+                    .collect();
+                result.push(Assertion::sequence(
+                    register,
+                    first_step,
+                    assertion.stride,
+                    values,
+                ));
+            } else if assertion.is_consecutive() {
+                for (j, a) in group.iter().enumerate() {
+                    let step = j * segment_length + first_step;
+                    result.push(Assertion::consecutive(register, step, a.values.clone()));
+                }
+            } else {
+                let values = group
+                    .iter()
+                    .flat_map(|a| a.values.iter().copied())
+                    .collect();
+                result.push(Assertion::sequence(
+                    register,
+                    first_step,
+                    assertion.stride,
+                    values,
+                ));
+            }
+        }
+        result
+    }
+
+    fn get_periodic_column_values(&self) -> Vec<Vec<Self::BaseElement>> {
+        let segment_length = self.segment_length();
+        let mut result = self.instances[0].get_periodic_column_values();
+        for &num_exemptions in self.selector_exemptions.iter() {
+            let mut selector = vec![Self::BaseElement::ONE; segment_length];
+            for value in selector.iter_mut().skip(segment_length - num_exemptions) {
+                *value = Self::BaseElement::ZERO;
+            }
+            result.push(selector);
+        }
+        result
+    }
+
+    fn get_constraint_groups(&self) -> Vec<ConstraintGroup> {
+        self.instances[0].get_constraint_groups()
+    }
+
+    fn constraint_combination_mode(&self) -> CombinationMode {
+        self.instances[0].constraint_combination_mode()
+    }
+
+    fn channel_seed(&self) -> [u8; 32] {
+        self.instances[0].channel_seed()
+    }
+}
@@ -0,0 +1,34 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::Air;
+
+// TAGGED AIR
+// ================================================================================================
+/// An [Air] which is identified by a unique string tag.
+///
+/// Tags are used to determine which AIR a [TaggedProof](crate::proof::TaggedProof) should be
+/// verified against without relying on any out-of-band information. A tag should change whenever
+/// the constraints described by the AIR change; e.g., "my-air-v1", "my-air-v2" etc.
+///
+/// This trait is usually implemented via the [register_air!](crate::register_air) macro.
+pub trait TaggedAir: Air {
+    /// A string which uniquely identifies this AIR.
+    const AIR_TAG: &'static str;
+}
+
+/// Implements [TaggedAir] trait for the specified [Air] type using the specified tag.
+///
+/// For example, `register_air!(MyAir, "my-air-v1")` assigns tag "my-air-v1" to `MyAir`. Once
+/// tagged, the AIR can be added to an `AirRegistry` of the verifier via
+/// `AirRegistry::register::<MyAir>()`.
+#[macro_export]
+macro_rules! register_air {
+    ($air:ty, $tag:expr) => {
+        impl $crate::TaggedAir for $air {
+            const AIR_TAG: &'static str = $tag;
+        }
+    };
+}
@@ -4,14 +4,20 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::{
-    Air, AirContext, Assertion, EvaluationFrame, ProofOptions, TraceInfo,
-    TransitionConstraintDegree,
+    Air, AirContext, Assertion, BoundaryConstraint, CombinationMode, CompositeAir,
+    CompositePublicInputs, ConstraintGroup, EvaluationFrame, LagrangeBoundaryConstraints,
+    ProofOptions, StackedAir, StackedPublicInputs, TraceInfo, TransitionConstraintDegree,
 };
 use crate::{FieldExtension, HashFunction};
+use core::convert::TryInto;
 use crypto::{hashers::Blake3_256, RandomCoin};
 use math::{fields::f128::BaseElement, get_power_series, log2, polynom, FieldElement, StarkField};
 use rand_utils::shuffle;
-use utils::collections::{BTreeMap, Vec};
+use utils::{
+    collections::{BTreeMap, Vec},
+    string::ToString,
+    Deserializable, Serializable, SliceReader,
+};
 
 // PERIODIC COLUMNS
 // ================================================================================================
@@ -60,7 +66,195 @@ fn get_periodic_column_polys_num_values_not_power_of_two() {
 // TRANSITION CONSTRAINTS
 // ================================================================================================
 
-// TODO
+#[test]
+fn get_transition_constraints_with_exemptions() {
+    let trace_length = 16;
+    let mut air = MockAir::with_constraint_groups(Vec::new(), 3, trace_length);
+    air.context = air
+        .context
+        .with_transition_constraint_exemptions(vec![1, 3, 1]);
+    assert_eq!(1, air.context().num_constraint_exemptions(0));
+    assert_eq!(3, air.context().num_constraint_exemptions(1));
+
+    let coefficients = vec![(BaseElement::ONE, BaseElement::ZERO); 3];
+    let groups = air.get_transition_constraints(&coefficients);
+    assert_eq!(2, groups.len());
+    assert_eq!(&[0, 2], groups[0].indexes());
+    assert_eq!(0, groups[0].num_extra_exemptions());
+    assert_eq!(&[1], groups[1].indexes());
+    assert_eq!(2, groups[1].num_extra_exemptions());
+
+    // merged evaluations of the exempted constraint vanish at the additionally exempted steps
+    let g = air.trace_domain_generator();
+    let evaluations = vec![BaseElement::ZERO, BaseElement::new(5), BaseElement::ZERO];
+    for &step in [trace_length - 2, trace_length - 3].iter() {
+        let x = g.exp((step as u64).into());
+        assert_eq!(
+            BaseElement::ZERO,
+            groups[1].merge_evaluations(&evaluations, x)
+        );
+    }
+    let x = g.exp(((trace_length - 4) as u64).into());
+    assert_ne!(
+        BaseElement::ZERO,
+        groups[1].merge_evaluations(&evaluations, x)
+    );
+}
+
+#[test]
+fn trace_info_with_padding_rows() {
+    let info = TraceInfo::new(4, 16);
+    assert_eq!(0, info.num_padding_rows());
+    assert_eq!(16, info.unpadded_length());
+
+    // padding rows are a part of the trace length
+    let info = info.with_num_padding_rows(5);
+    assert_eq!(16, info.length());
+    assert_eq!(5, info.num_padding_rows());
+    assert_eq!(11, info.unpadded_length());
+}
+
+#[test]
+#[should_panic(
+    expected = "number of padding rows must be smaller than trace length 16, but was 16"
+)]
+fn trace_info_with_too_many_padding_rows() {
+    let _ = TraceInfo::new(4, 16).with_num_padding_rows(16);
+}
+
+#[test]
+fn get_padding_constraints() {
+    let trace_length = 16;
+    let mut air = MockAir::with_constraint_groups(Vec::new(), 1, trace_length);
+    assert!(air.get_padding_constraints::<BaseElement>(&[]).is_none());
+
+    air.context = air.context.with_num_padding_rows(3);
+    assert_eq!(3, air.context().num_padding_rows());
+    assert_eq!(4, air.context().num_transition_exemptions());
+
+    // the padding divisor vanishes exactly at the steps followed by a padding row
+    let divisor = air.padding_constraint_divisor();
+    assert_eq!(3, divisor.degree());
+    let g = air.trace_domain_generator();
+    for step in 0..trace_length {
+        let x = g.exp((step as u64).into());
+        let is_root = (trace_length - 4..trace_length - 1).contains(&step);
+        assert_eq!(is_root, divisor.evaluate_at(x) == BaseElement::ZERO);
+    }
+
+    // the merged evaluation is zero when the next row repeats the current row
+    let coefficients = vec![(BaseElement::new(3), BaseElement::new(5)); 4];
+    let constraints = air
+        .get_padding_constraints(&coefficients)
+        .expect("padding constraints should be defined");
+    let x = BaseElement::new(7);
+    let row = vec![BaseElement::new(11); 4];
+    let frame = EvaluationFrame::from_rows(row.clone(), row.clone());
+    assert_eq!(BaseElement::ZERO, constraints.merge_evaluations(&frame, x));
+
+    let mut next = row.clone();
+    next[2] = BaseElement::new(12);
+    let frame = EvaluationFrame::from_rows(row, next);
+    assert_ne!(BaseElement::ZERO, constraints.merge_evaluations(&frame, x));
+}
+
+#[test]
+#[should_panic(
+    expected = "number of exemptions must be between 1 and 15, but was 0 for constraint 0"
+)]
+fn context_transition_constraint_exemptions_zero() {
+    build_context::<BaseElement>(16, 4).with_transition_constraint_exemptions(vec![0]);
+}
+
+// CONSTRAINT GROUPS
+// ================================================================================================
+
+#[test]
+fn get_constraint_composition_coefficients_with_groups() {
+    let groups = vec![
+        ConstraintGroup::new(vec![3, 1], true),
+        ConstraintGroup::new(vec![2, 4], false),
+    ];
+    let air = MockAir::with_constraint_groups(groups, 5, 16);
+
+    let mut prng = build_prng();
+    let coefficients = air
+        .get_constraint_composition_coefficients::<BaseElement, _>(&mut prng, 2)
+        .unwrap();
+
+    // a single pair of coefficients is drawn for the shared group when its first constraint is
+    // reached; all other constraints get their own coefficients
+    let mut prng = build_prng();
+    let draws: Vec<(BaseElement, BaseElement)> =
+        (0..6).map(|_| prng.draw_pair().unwrap()).collect();
+    let expected_transition = vec![draws[0], draws[1], draws[2], draws[1], draws[3]];
+    assert_eq!(expected_transition, coefficients.transition);
+    assert_eq!(draws[4..].to_vec(), coefficients.boundary);
+}
+
+#[test]
+fn get_constraint_composition_coefficients_without_groups() {
+    let air = MockAir::with_constraint_groups(Vec::new(), 3, 16);
+
+    let mut prng = build_prng();
+    let coefficients = air
+        .get_constraint_composition_coefficients::<BaseElement, _>(&mut prng, 1)
+        .unwrap();
+
+    let mut prng = build_prng();
+    let draws: Vec<(BaseElement, BaseElement)> =
+        (0..4).map(|_| prng.draw_pair().unwrap()).collect();
+    assert_eq!(draws[..3].to_vec(), coefficients.transition);
+    assert_eq!(draws[3..].to_vec(), coefficients.boundary);
+}
+
+#[test]
+fn get_constraint_composition_coefficients_with_power_series() {
+    let groups = vec![ConstraintGroup::new(vec![2, 0], true)];
+    let mut air = MockAir::with_constraint_groups(groups, 3, 16);
+    air.combination_mode = CombinationMode::PowerSeries;
+
+    let mut public_coin = build_prng();
+    let coefficients = air
+        .get_constraint_composition_coefficients::<BaseElement, _>(&mut public_coin, 2)
+        .unwrap();
+
+    // a single element is drawn, and pairs of coefficients are consecutive powers of it; the
+    // shared group reuses the pair of its first constraint
+    let mut prng = build_prng();
+    let r: BaseElement = prng.draw().unwrap();
+    let powers = get_power_series(r, 9);
+    let pairs: Vec<(BaseElement, BaseElement)> = (0..4)
+        .map(|i| (powers[2 * i + 1], powers[2 * i + 2]))
+        .collect();
+    assert_eq!(vec![pairs[0], pairs[1], pairs[0]], coefficients.transition);
+    assert_eq!(pairs[2..].to_vec(), coefficients.boundary);
+
+    // no other elements have been drawn from the public coin
+    assert_eq!(
+        prng.draw::<BaseElement>().unwrap(),
+        public_coin.draw::<BaseElement>().unwrap()
+    );
+}
+
+#[test]
+#[should_panic(
+    expected = "constraint group references constraint 3, but the computation has only 3 transition constraints"
+)]
+fn build_shared_constraint_groups_with_invalid_index() {
+    let groups = vec![ConstraintGroup::new(vec![0, 3], true)];
+    let _ = super::build_shared_constraint_groups(&groups, 3);
+}
+
+#[test]
+#[should_panic(expected = "constraint 1 belongs to more than one constraint group")]
+fn build_shared_constraint_groups_with_overlap() {
+    let groups = vec![
+        ConstraintGroup::new(vec![0, 1], true),
+        ConstraintGroup::new(vec![1, 2], false),
+    ];
+    let _ = super::build_shared_constraint_groups(&groups, 3);
+}
 
 // BOUNDARY CONSTRAINTS
 // ================================================================================================
@@ -111,7 +305,7 @@ fn get_boundary_constraints() {
     let coefficients = (0..8)
         .map(|_| prng.draw_pair().unwrap())
         .collect::<Vec<(BaseElement, BaseElement)>>();
-    let mut groups = air.get_boundary_constraints(&coefficients);
+    let mut groups = air.get_boundary_constraints(&air.get_assertions(), &coefficients);
     groups.sort_by(|g1, g2| {
         if g1.degree_adjustment() == g2.degree_adjustment() {
             let n1 = &g1.divisor().numerator()[0].1;
@@ -216,6 +410,127 @@ fn get_boundary_constraints() {
     assert_eq!(expected_cc[&7], constraint.cc().clone());
 }
 
+#[test]
+fn get_boundary_constraints_with_domain_points() {
+    // points which are roots of unity of a domain twice as large as the trace domain
+    let trace_length = 16;
+    let h = BaseElement::get_root_of_unity(log2(trace_length * 2));
+    let (p, q) = (h, h.exp(3));
+
+    let assertions = vec![Assertion::single(0, 0, BaseElement::new(3))];
+    let domain_points = vec![
+        (1, p, BaseElement::new(5)),
+        (0, q, BaseElement::new(7)),
+        (2, p, BaseElement::new(9)),
+    ];
+    let air = MockAir::with_domain_points(assertions, domain_points, trace_length);
+
+    // coefficients for domain point constraints follow the coefficients for assertions
+    let mut prng = build_prng();
+    let coefficients = (0..4)
+        .map(|_| prng.draw_pair().unwrap())
+        .collect::<Vec<(BaseElement, BaseElement)>>();
+    let groups = air.get_boundary_constraints(&air.get_assertions(), &coefficients);
+    assert_eq!(3, groups.len());
+
+    // assertion groups come first since all groups have the same degree adjustment
+    let group = &groups[0];
+    assert_eq!(vec![(1, BaseElement::ONE)], group.divisor().numerator());
+    assert_eq!(None, group.constraints()[0].domain_point());
+
+    // constraints placed at the same point are in the same group
+    let group = &groups[1];
+    assert_eq!(groups[0].degree_adjustment(), group.degree_adjustment());
+    assert_eq!(vec![(1, p)], group.divisor().numerator());
+    assert_eq!(2, group.constraints().len());
+
+    let constraint = &group.constraints()[0];
+    assert_eq!(1, constraint.register());
+    assert_eq!(Some(p), constraint.domain_point());
+    assert_eq!(vec![BaseElement::new(5)], constraint.poly());
+    assert_eq!(coefficients[1], constraint.cc().clone());
+
+    let constraint = &group.constraints()[1];
+    assert_eq!(2, constraint.register());
+    assert_eq!(vec![BaseElement::new(9)], constraint.poly());
+    assert_eq!(coefficients[3], constraint.cc().clone());
+
+    let group = &groups[2];
+    assert_eq!(vec![(1, q)], group.divisor().numerator());
+    assert_eq!(1, group.constraints().len());
+
+    let constraint = &group.constraints()[0];
+    assert_eq!(0, constraint.register());
+    assert_eq!(Some(q), constraint.domain_point());
+    assert_eq!(coefficients[2], constraint.cc().clone());
+}
+
+#[test]
+#[should_panic(expected = "belongs to the constraint evaluation domain")]
+fn get_boundary_constraints_with_domain_point_in_ce_domain() {
+    // the constraint evaluation domain is offset by the field generator
+    let point = BaseElement::GENERATOR;
+    let air = MockAir::with_domain_points(vec![], vec![(0, point, BaseElement::ONE)], 16);
+    let coefficients = vec![build_prng().draw_pair().unwrap()];
+    air.get_boundary_constraints::<BaseElement>(&[], &coefficients);
+}
+
+#[test]
+fn get_boundary_constraints_with_lagrange_assertions() {
+    // steps 0, 1, 2 of register 0 form a single consecutive assertion, and step 5 is asserted on
+    // its own
+    let values = [
+        (5, BaseElement::new(89)),
+        (0, BaseElement::ONE),
+        (1, BaseElement::new(2)),
+        (2, BaseElement::new(5)),
+    ];
+    let mut assertions = LagrangeBoundaryConstraints::new(0, &values)
+        .into_iter()
+        .collect::<Vec<_>>();
+    assertions.push(Assertion::single(1, 0, BaseElement::ONE));
+
+    let trace_length = 16;
+    let air = MockAir::with_assertions(assertions, trace_length);
+    let g = BaseElement::get_root_of_unity(log2(trace_length));
+
+    let mut prng = build_prng();
+    let coefficients = (0..3)
+        .map(|_| prng.draw_pair().unwrap())
+        .collect::<Vec<(BaseElement, BaseElement)>>();
+    let groups = air.get_boundary_constraints(&air.get_assertions(), &coefficients);
+    assert_eq!(3, groups.len());
+
+    // the consecutive assertion is not merged with the single assertion at step 0 since their
+    // divisors are different
+    let group = groups
+        .iter()
+        .find(|group| group.divisor().degree() == 3)
+        .unwrap();
+    assert_eq!(1, group.constraints().len());
+    let constraint = &group.constraints()[0];
+    assert_eq!(0, constraint.register());
+    for (step, &(_, value)) in values[1..].iter().enumerate() {
+        let x = g.exp((step as u64).into());
+        assert_eq!(BaseElement::ZERO, constraint.evaluate_at(x, value));
+    }
+
+    let group = groups
+        .iter()
+        .find(|group| group.divisor().numerator() == [(1, BaseElement::ONE)])
+        .unwrap();
+    assert_eq!(1, group.constraints().len());
+    assert_eq!(1, group.constraints()[0].register());
+
+    let group = groups
+        .iter()
+        .find(|group| group.divisor().numerator() == [(1, g.exp(5))])
+        .unwrap();
+    assert_eq!(1, group.constraints().len());
+    assert_eq!(0, group.constraints()[0].register());
+    assert_eq!(vec![BaseElement::new(89)], group.constraints()[0].poly());
+}
+
 // PREPARE ASSERTIONS
 // ================================================================================================
 
@@ -247,16 +562,28 @@ fn prepare_assertions() {
         Assertion::periodic(1, 3, 8, BaseElement::new(7)), //register 1, steps 3, 11
     ];
 
-    let context = build_context(16, 2);
-    let result = super::prepare_assertions(assertions.clone(), &context);
+    let context = build_context::<BaseElement>(16, 2);
+    let result = super::prepare_assertions(
+        assertions.clone(),
+        context.trace_info.width(),
+        context.trace_info.length(),
+    );
     assert_eq!(expected, result);
 
     shuffle(&mut assertions);
-    let result = super::prepare_assertions(assertions.clone(), &context);
+    let result = super::prepare_assertions(
+        assertions.clone(),
+        context.trace_info.width(),
+        context.trace_info.length(),
+    );
     assert_eq!(expected, result);
 
     shuffle(&mut assertions);
-    let result = super::prepare_assertions(assertions.clone(), &context);
+    let result = super::prepare_assertions(
+        assertions.clone(),
+        context.trace_info.width(),
+        context.trace_info.length(),
+    );
     assert_eq!(expected, result);
 }
 
@@ -269,8 +596,12 @@ fn prepare_assertions_with_overlap() {
         Assertion::single(0, 9, BaseElement::new(5)),
         Assertion::periodic(0, 1, 8, BaseElement::new(7)),
     ];
-    let context = build_context(16, 2);
-    let _ = super::prepare_assertions(assertions.clone(), &context);
+    let context = build_context::<BaseElement>(16, 2);
+    let _ = super::prepare_assertions(
+        assertions.clone(),
+        context.trace_info.width(),
+        context.trace_info.length(),
+    );
 }
 
 #[test]
@@ -279,8 +610,12 @@ fn prepare_assertions_with_overlap() {
 )]
 fn prepare_assertions_with_invalid_trace_length() {
     let assertions = vec![Assertion::single(0, 16, BaseElement::new(5))];
-    let context = build_context(16, 2);
-    let _ = super::prepare_assertions(assertions.clone(), &context);
+    let context = build_context::<BaseElement>(16, 2);
+    let _ = super::prepare_assertions(
+        assertions.clone(),
+        context.trace_info.width(),
+        context.trace_info.length(),
+    );
 }
 
 #[test]
@@ -289,8 +624,261 @@ fn prepare_assertions_with_invalid_trace_length() {
 )]
 fn prepare_assertions_with_invalid_trace_width() {
     let assertions = vec![Assertion::single(3, 17, BaseElement::new(5))];
-    let context = build_context(16, 2);
-    let _ = super::prepare_assertions(assertions.clone(), &context);
+    let context = build_context::<BaseElement>(16, 2);
+    let _ = super::prepare_assertions(
+        assertions.clone(),
+        context.trace_info.width(),
+        context.trace_info.length(),
+    );
+}
+
+// CONSTRAINT DEGREES
+// ================================================================================================
+
+#[test]
+fn context_max_constraint_degree() {
+    let context = AirContext::<BaseElement>::new(
+        TraceInfo::new(2, 16),
+        vec![
+            TransitionConstraintDegree::new(1),
+            TransitionConstraintDegree::with_cycles(3, vec![8]),
+            TransitionConstraintDegree::new(2),
+        ],
+        ProofOptions::new(
+            32,
+            8,
+            0,
+            HashFunction::Blake3_256,
+            FieldExtension::None,
+            4,
+            256,
+        ),
+    );
+    assert_eq!(3, context.max_constraint_degree());
+
+    let air = MockAir::with_assertions(Vec::new(), 16);
+    assert_eq!(
+        MockAir::MAX_CONSTRAINT_DEGREE,
+        air.context().max_constraint_degree()
+    );
+}
+
+#[test]
+#[should_panic(expected = "is an element of the subgroup of order 2^40")]
+fn options_coset_offset_in_lde_domain() {
+    // trace length 16 with blowup factor 8 results in an LDE domain of size 128
+    let offset = BaseElement::get_root_of_unity(log2(128usize));
+    let _ = build_options().with_coset_offset(offset);
+}
+
+#[test]
+fn options_validate_domain_offset() {
+    assert!(build_options()
+        .validate_domain_offset::<BaseElement>()
+        .is_ok());
+    let options = build_options().with_coset_offset(BaseElement::new(7));
+    assert!(options.validate_domain_offset::<BaseElement>().is_ok());
+
+    // offsets read from bytes are not bound to any field; they are validated once the field is
+    // known
+    let modulus = u128::from_le_bytes(BaseElement::get_modulus_le_bytes().try_into().unwrap());
+    let root = BaseElement::get_root_of_unity(BaseElement::TWO_ADICITY);
+    let root = u128::from_le_bytes(root.to_bytes().try_into().unwrap());
+    for (offset, message) in [
+        (modulus + 7, "is not smaller than the field modulus"),
+        (modulus, "is not smaller than the field modulus"),
+        (root, "is an element of the subgroup of order 2^40"),
+    ] {
+        let options = read_options_with_offset(offset);
+        let err = options.validate_domain_offset::<BaseElement>().unwrap_err();
+        assert!(err.to_string().contains(message), "{}", err);
+    }
+}
+
+#[test]
+#[should_panic(expected = "invalid domain offset: domain offset")]
+fn context_invalid_domain_offset() {
+    let modulus = u128::from_le_bytes(BaseElement::get_modulus_le_bytes().try_into().unwrap());
+    let _ = AirContext::<BaseElement>::new(
+        TraceInfo::new(2, 16),
+        vec![TransitionConstraintDegree::new(2)],
+        read_options_with_offset(modulus + 7),
+    );
+}
+
+// CONTEXT COMPOSITION
+// ================================================================================================
+
+#[test]
+fn compose_contexts() {
+    let options = ProofOptions::new(
+        32,
+        8,
+        0,
+        HashFunction::Blake3_256,
+        FieldExtension::None,
+        4,
+        256,
+    );
+    let first = AirContext::<BaseElement>::new(
+        TraceInfo::with_meta(2, 16, vec![1, 2]),
+        vec![TransitionConstraintDegree::new(2)],
+        options.clone(),
+    );
+    let second = AirContext::<BaseElement>::new(
+        TraceInfo::new(3, 16),
+        vec![
+            TransitionConstraintDegree::new(1),
+            TransitionConstraintDegree::new(3),
+        ],
+        options,
+    );
+
+    let composed = first.clone().compose(second);
+    assert_eq!(5, composed.trace_info.width());
+    assert_eq!(16, composed.trace_info.length());
+    assert_eq!(&[1, 2], composed.trace_info.meta());
+    assert_eq!(
+        vec![
+            TransitionConstraintDegree::new(2),
+            TransitionConstraintDegree::new(1),
+            TransitionConstraintDegree::new(3),
+        ],
+        composed.transition_constraint_degrees
+    );
+    assert_eq!(4, composed.ce_blowup_factor);
+    assert_eq!(
+        first.trace_domain_generator,
+        composed.trace_domain_generator
+    );
+    assert_eq!(first.lde_domain_generator, composed.lde_domain_generator);
+}
+
+#[test]
+#[should_panic(expected = "trace lengths of composed contexts must be the same")]
+fn compose_contexts_with_different_trace_lengths() {
+    let first = build_context::<BaseElement>(16, 2);
+    let second = build_context::<BaseElement>(32, 2);
+    let _ = first.compose(second);
+}
+
+#[test]
+fn composite_air_context() {
+    let options = ProofOptions::new(
+        32,
+        8,
+        0,
+        HashFunction::Blake3_256,
+        FieldExtension::None,
+        4,
+        256,
+    );
+    let pub_inputs = CompositePublicInputs::new(4, (), ());
+    let air = CompositeAir::<MockAir, MockAir>::new(TraceInfo::new(7, 16), pub_inputs, options);
+    assert_eq!(7, air.trace_width());
+    assert_eq!(4, air.first().trace_width());
+    assert_eq!(3, air.second().trace_width());
+    assert_eq!(2, air.num_transition_constraints());
+    assert_eq!(
+        <CompositeAir<MockAir, MockAir>>::MAX_CONSTRAINT_DEGREE,
+        air.context().max_constraint_degree()
+    );
+}
+
+#[test]
+#[should_panic(expected = "computations with auxiliary trace segments cannot be composed")]
+fn composite_air_with_aux_segment() {
+    let pub_inputs = CompositePublicInputs::new(4, (), ());
+    let trace_info = TraceInfo::new(7, 16);
+    let _ = CompositeAir::<MockAir, AuxMockAir>::new(trace_info, pub_inputs, build_options());
+}
+
+#[test]
+fn composite_air_channel_seed() {
+    // computations without seeds are composed into a computation without a seed
+    assert_eq!([0; 32], get_composite_seed::<MockAir, MockAir>());
+
+    // otherwise, the seed depends on the seeds of both computations and on their order
+    let seed_1_2 = get_composite_seed::<SeededMockAir<1>, SeededMockAir<2>>();
+    let seed_2_1 = get_composite_seed::<SeededMockAir<2>, SeededMockAir<1>>();
+    let seed_1_1 = get_composite_seed::<SeededMockAir<1>, SeededMockAir<1>>();
+    let seed_1_0 = get_composite_seed::<SeededMockAir<1>, MockAir>();
+    assert_ne!([0; 32], seed_1_2);
+    assert_ne!([0; 32], seed_1_0);
+    assert_ne!([1; 32], seed_1_0);
+    assert_ne!(seed_1_2, seed_2_1);
+    assert_ne!(seed_1_2, seed_1_1);
+    assert_ne!(seed_1_1, seed_1_0);
+}
+
+#[test]
+fn stacked_air_context() {
+    let options = ProofOptions::new(
+        32,
+        8,
+        0,
+        HashFunction::Blake3_256,
+        FieldExtension::None,
+        4,
+        256,
+    );
+    let pub_inputs = StackedPublicInputs::new(vec![(); 4]);
+    let air = StackedAir::<MockAir>::new(TraceInfo::new(4, 64), pub_inputs, options);
+    assert_eq!(4, air.instances().len());
+    assert_eq!(16, air.segment_length());
+    assert_eq!(16, air.instances()[0].trace_length());
+    assert_eq!(
+        &[TransitionConstraintDegree::with_cycles(2, vec![16])],
+        air.transition_constraint_degrees()
+    );
+
+    // the selector column disables the transition constraint at the last step of each segment
+    let mut selector = vec![BaseElement::ONE; 16];
+    selector[15] = BaseElement::ZERO;
+    assert_eq!(vec![selector], air.get_periodic_column_values());
+}
+
+#[test]
+#[should_panic(expected = "number of stacked instances must be a power of two, but was 3")]
+fn stacked_public_inputs_with_invalid_number_of_instances() {
+    StackedPublicInputs::new(vec![(); 3]);
+}
+
+// EVALUATION FRAME
+// ================================================================================================
+
+#[test]
+fn evaluation_frame_split_registers() {
+    let rows = vec![
+        vec![
+            BaseElement::new(1),
+            BaseElement::new(2),
+            BaseElement::new(3),
+        ],
+        vec![
+            BaseElement::new(4),
+            BaseElement::new(5),
+            BaseElement::new(6),
+        ],
+    ];
+    let mut frame = EvaluationFrame::from_row_list(rows);
+    {
+        let parts = frame.split_registers(1);
+        assert_eq!(&[BaseElement::new(1)], parts[0].current());
+        assert_eq!(&[BaseElement::new(4)], parts[0].next());
+        assert_eq!(
+            &[BaseElement::new(2), BaseElement::new(3)],
+            parts[1].current()
+        );
+        assert_eq!(&[BaseElement::new(5), BaseElement::new(6)], parts[1].next());
+    }
+
+    // splitting a modified frame again re-uses the same parts
+    let first_ptr = frame.split_registers(1)[0].current().as_ptr();
+    frame.next_mut()[2] = BaseElement::new(7);
+    let parts = frame.split_registers(1);
+    assert_eq!(first_ptr, parts[0].current().as_ptr());
+    assert_eq!(&[BaseElement::new(5), BaseElement::new(7)], parts[1].next());
 }
 
 // MOCK AIR
@@ -300,6 +888,9 @@ struct MockAir {
     context: AirContext<BaseElement>,
     assertions: Vec<Assertion<BaseElement>>,
     periodic_columns: Vec<Vec<BaseElement>>,
+    constraint_groups: Vec<ConstraintGroup>,
+    combination_mode: CombinationMode,
+    domain_points: Vec<(usize, BaseElement, BaseElement)>,
 }
 
 impl MockAir {
@@ -341,18 +932,59 @@ impl MockAir {
         result.assertions = assertions;
         result
     }
+
+    pub fn with_domain_points(
+        assertions: Vec<Assertion<BaseElement>>,
+        domain_points: Vec<(usize, BaseElement, BaseElement)>,
+        trace_length: usize,
+    ) -> Self {
+        let mut result = Self::with_assertions(assertions, trace_length);
+        result.domain_points = domain_points;
+        result
+    }
+
+    pub fn with_constraint_groups(
+        constraint_groups: Vec<ConstraintGroup>,
+        num_constraints: usize,
+        trace_length: usize,
+    ) -> Self {
+        let options = ProofOptions::new(
+            32,
+            8,
+            0,
+            HashFunction::Blake3_256,
+            FieldExtension::None,
+            4,
+            256,
+        );
+        let t_degrees = vec![TransitionConstraintDegree::new(2); num_constraints];
+        let context = AirContext::new(TraceInfo::new(4, trace_length), t_degrees, options);
+        MockAir {
+            context,
+            assertions: Vec::new(),
+            periodic_columns: Vec::new(),
+            constraint_groups,
+            combination_mode: CombinationMode::Linear,
+            domain_points: Vec::new(),
+        }
+    }
 }
 
 impl Air for MockAir {
     type BaseElement = BaseElement;
     type PublicInputs = ();
 
+    const MAX_CONSTRAINT_DEGREE: usize = 2;
+
     fn new(trace_info: TraceInfo, _pub_inputs: (), _options: ProofOptions) -> Self {
         let context = build_context(trace_info.length(), trace_info.width());
         MockAir {
             context,
             assertions: Vec::new(),
             periodic_columns: Vec::new(),
+            constraint_groups: Vec::new(),
+            combination_mode: CombinationMode::Linear,
+            domain_points: Vec::new(),
         }
     }
 
@@ -368,6 +1000,25 @@ impl Air for MockAir {
         self.assertions.clone()
     }
 
+    fn get_constraint_groups(&self) -> Vec<ConstraintGroup> {
+        self.constraint_groups.clone()
+    }
+
+    fn constraint_combination_mode(&self) -> CombinationMode {
+        self.combination_mode
+    }
+
+    fn get_domain_point_constraints<E: FieldElement<BaseField = Self::BaseElement>>(
+        &self,
+    ) -> Vec<BoundaryConstraint<Self::BaseElement, E>> {
+        self.domain_points
+            .iter()
+            .map(|&(register, point, value)| {
+                BoundaryConstraint::at_domain_point(register, point, value)
+            })
+            .collect()
+    }
+
     fn evaluate_transition<E: FieldElement + From<Self::BaseElement>>(
         &self,
         _frame: &EvaluationFrame<E>,
@@ -377,9 +1028,88 @@ impl Air for MockAir {
     }
 }
 
+/// Same as [MockAir] but with a single-column auxiliary trace segment.
+struct AuxMockAir(MockAir);
+
+impl Air for AuxMockAir {
+    type BaseElement = BaseElement;
+    type PublicInputs = ();
+
+    const MAX_CONSTRAINT_DEGREE: usize = MockAir::MAX_CONSTRAINT_DEGREE;
+
+    fn new(trace_info: TraceInfo, pub_inputs: (), options: ProofOptions) -> Self {
+        AuxMockAir(MockAir::new(trace_info, pub_inputs, options))
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseElement> {
+        self.0.context()
+    }
+
+    fn auxiliary_trace_width(&self) -> usize {
+        1
+    }
+
+    fn evaluate_transition<E: FieldElement<BaseField = Self::BaseElement>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        self.0.evaluate_transition(frame, periodic_values, result)
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseElement>> {
+        self.0.get_assertions()
+    }
+}
+
+/// Same as [MockAir] but with a channel seed consisting of `SEED` bytes.
+struct SeededMockAir<const SEED: u8>(MockAir);
+
+impl<const SEED: u8> Air for SeededMockAir<SEED> {
+    type BaseElement = BaseElement;
+    type PublicInputs = ();
+
+    const MAX_CONSTRAINT_DEGREE: usize = MockAir::MAX_CONSTRAINT_DEGREE;
+
+    fn new(trace_info: TraceInfo, pub_inputs: (), options: ProofOptions) -> Self {
+        SeededMockAir(MockAir::new(trace_info, pub_inputs, options))
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseElement> {
+        self.0.context()
+    }
+
+    fn evaluate_transition<E: FieldElement<BaseField = Self::BaseElement>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        self.0.evaluate_transition(frame, periodic_values, result)
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseElement>> {
+        self.0.get_assertions()
+    }
+
+    fn channel_seed(&self) -> [u8; 32] {
+        [SEED; 32]
+    }
+}
+
 // UTILITY FUNCTIONS
 // ================================================================================================
 
+fn get_composite_seed<A1, A2>() -> [u8; 32]
+where
+    A1: Air<BaseElement = BaseElement, PublicInputs = ()>,
+    A2: Air<BaseElement = BaseElement, PublicInputs = ()>,
+{
+    let pub_inputs = CompositePublicInputs::new(4, (), ());
+    CompositeAir::<A1, A2>::new(TraceInfo::new(7, 16), pub_inputs, build_options()).channel_seed()
+}
+
 pub fn build_context<B: StarkField>(trace_length: usize, trace_width: usize) -> AirContext<B> {
     let options = ProofOptions::new(
         32,
@@ -395,6 +1125,28 @@ pub fn build_context<B: StarkField>(trace_length: usize, trace_width: usize) ->
     AirContext::new(trace_info, t_degrees, options)
 }
 
+pub fn build_options() -> ProofOptions {
+    ProofOptions::new(
+        32,
+        8,
+        0,
+        HashFunction::Blake3_256,
+        FieldExtension::None,
+        4,
+        256,
+    )
+}
+
+/// Returns default options read from bytes which specify the domain `offset`; the offset is not
+/// validated against any field during deserialization.
+pub fn read_options_with_offset(offset: u128) -> ProofOptions {
+    let mut bytes = build_options().to_bytes();
+    let flags = bytes.len() - 1;
+    bytes[flags] |= 1;
+    bytes.extend_from_slice(&offset.to_le_bytes());
+    ProofOptions::read_from(&mut SliceReader::new(&bytes)).unwrap()
+}
+
 pub fn build_prng() -> RandomCoin<BaseElement, Blake3_256<BaseElement>> {
     RandomCoin::new(&[0; 32])
 }
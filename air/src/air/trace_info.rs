@@ -9,12 +9,25 @@ use utils::collections::Vec;
 // ================================================================================================
 /// Information about a specific execution trace.
 ///
-/// Trace info consists of trace width, length, and optional custom metadata. Metadata is just a
-/// vector of bytes and can store any values up to 64KB in size.
+/// Trace info consists of trace width, length, the number of padding rows, and optional custom
+/// metadata. Metadata is just a vector of bytes and can store any values up to 64KB in size.
+///
+/// Metadata is included in the proof context, and thus, it is absorbed into the public coin by
+/// both the prover and the verifier. This can be used for domain separation: e.g., a hash of the
+/// program executed by the computation makes proofs of different programs with the same trace
+/// dimensions distinct. `verify_with_meta()` function of the verifier crate checks that the
+/// metadata of a proof matches the expected metadata.
+///
+/// Padding rows are appended to execution traces of computations whose length is not a power of
+/// two (see `ExecutionTrace::init_with_padding()` in the prover crate). The number of padding
+/// rows specified by a trace info is the number claimed by the prover; it is accepted only if it
+/// is equal to the number of padding rows declared by the AIR of the computation via
+/// [AirContext::with_num_padding_rows()](crate::AirContext::with_num_padding_rows).
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct TraceInfo {
     width: usize,
     length: usize,
+    num_padding_rows: usize,
     meta: Vec<u8>,
 }
 
@@ -74,10 +87,27 @@ impl TraceInfo {
         TraceInfo {
             width,
             length,
+            num_padding_rows: 0,
             meta,
         }
     }
 
+    /// Returns this trace info updated to specify that the last `num_padding_rows` rows of the
+    /// execution trace are padding rows.
+    ///
+    /// # Panics
+    /// Panics if `num_padding_rows` is not smaller than the trace length.
+    pub fn with_num_padding_rows(mut self, num_padding_rows: usize) -> Self {
+        assert!(
+            num_padding_rows < self.length,
+            "number of padding rows must be smaller than trace length {}, but was {}",
+            self.length,
+            num_padding_rows
+        );
+        self.num_padding_rows = num_padding_rows;
+        self
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -90,11 +120,22 @@ impl TraceInfo {
 
     /// Returns execution trace length.
     ///
-    /// The length is guaranteed to be a power of two.
+    /// The length is guaranteed to be a power of two, and includes padding rows.
     pub fn length(&self) -> usize {
         self.length
     }
 
+    /// Returns the number of padding rows at the end of the execution trace.
+    pub fn num_padding_rows(&self) -> usize {
+        self.num_padding_rows
+    }
+
+    /// Returns the length of the execution trace without the padding rows; this is the number of
+    /// steps performed by the computation.
+    pub fn unpadded_length(&self) -> usize {
+        self.length - self.num_padding_rows
+    }
+
     /// Returns execution trace metadata.
     pub fn meta(&self) -> &[u8] {
         &self.meta
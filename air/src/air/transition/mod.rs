@@ -3,7 +3,10 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use core::cmp;
+use core::{
+    cell::{Ref, RefCell},
+    cmp,
+};
 use math::FieldElement;
 use utils::collections::Vec;
 
@@ -30,11 +33,17 @@ const MIN_CYCLE_LENGTH: usize = 2;
 ///
 /// The divisor polynomial is the same for all transition constraints (see
 /// [Air::transition_constraint_divisor()](crate::Air::transition_constraint_divisor())) and for
-/// this reason is not stored in a transition constraint group.
+/// this reason is not stored in a transition constraint group. Constraints which are exempted
+/// from more steps than the divisor (see
+/// [AirContext::num_constraint_exemptions()](crate::AirContext::num_constraint_exemptions)) are
+/// grouped separately; for such groups, the merged evaluation is multiplied by
+/// $\prod_{j}(x - g^{n - j})$ over the additionally exempted steps, which cancels out the
+/// corresponding factors of the divisor.
 #[derive(Clone, Debug)]
 pub struct TransitionConstraintGroup<E: FieldElement> {
     degree: TransitionConstraintDegree,
     degree_adjustment: u32,
+    exemption_points: Vec<E>,
     indexes: Vec<usize>,
     coefficients: Vec<(E, E)>,
 }
@@ -43,21 +52,30 @@ impl<E: FieldElement> TransitionConstraintGroup<E> {
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
     /// Returns a new transition constraint group to hold constraints of the specified degree.
+    ///
+    /// `exemption_points` are the points of the trace domain at which constraints of this group
+    /// are not enforced in addition to the points excluded by the transition constraint divisor.
     pub(super) fn new(
         degree: TransitionConstraintDegree,
         trace_poly_degree: usize,
+        divisor_degree: usize,
         composition_degree: usize,
+        exemption_points: Vec<E>,
     ) -> Self {
         // We want to make sure that once we divide a constraint polynomial by its divisor, the
         // degree of the resulting polynomial will be exactly equal to the composition_degree.
-        // For transition constraints, divisor degree = deg(trace). So, target degree for all
-        // transitions constraints is simply: deg(composition) + deg(trace)
-        let target_degree = composition_degree + trace_poly_degree;
-        let evaluation_degree = degree.get_evaluation_degree(trace_poly_degree + 1);
+        // For transition constraints, divisor degree = deg(trace) + 1 - number of exemptions.
+        // So, target degree for all transitions constraints is: deg(composition) + deg(divisor).
+        // Merged evaluations are multiplied by a polynomial with a root at each of the exemption
+        // points, and thus, the degree of this polynomial is included into the evaluation degree.
+        let target_degree = composition_degree + divisor_degree;
+        let evaluation_degree =
+            degree.get_evaluation_degree(trace_poly_degree + 1) + exemption_points.len();
         let degree_adjustment = (target_degree - evaluation_degree) as u32;
         TransitionConstraintGroup {
             degree,
             degree_adjustment,
+            exemption_points,
             indexes: vec![],
             coefficients: vec![],
         }
@@ -76,6 +94,34 @@ impl<E: FieldElement> TransitionConstraintGroup<E> {
         &self.degree
     }
 
+    /// Returns the number of steps on which constraints in this group are not enforced in
+    /// addition to the steps excluded by the transition constraint divisor.
+    pub fn num_extra_exemptions(&self) -> usize {
+        self.exemption_points.len()
+    }
+
+    /// Returns the points of the trace domain at which constraints in this group are not
+    /// enforced in addition to the points excluded by the transition constraint divisor.
+    pub fn exemption_points(&self) -> &[E] {
+        &self.exemption_points
+    }
+
+    /// Returns composition coefficients for all constraints in this group; coefficients are
+    /// listed in the same order as constraint indexes.
+    pub fn coefficients(&self) -> &[(E, E)] {
+        &self.coefficients
+    }
+
+    /// Returns the degree adjustment factor for constraints in this group.
+    pub fn degree_adjustment(&self) -> u32 {
+        self.degree_adjustment
+    }
+
+    /// Returns true if this group does not contain any constraints.
+    pub fn is_empty(&self) -> bool {
+        self.indexes.is_empty()
+    }
+
     /// Adds a new constraint to the group. The constraint is identified by an index in the
     /// evaluation table.
     pub fn add(&mut self, constraint_idx: usize, coefficients: (E, E)) {
@@ -83,6 +129,20 @@ impl<E: FieldElement> TransitionConstraintGroup<E> {
         self.coefficients.push(coefficients);
     }
 
+    /// Removes from this group all constraints for whose indexes `f` returns false.
+    pub fn retain<F: FnMut(usize) -> bool>(&mut self, mut f: F) {
+        let mut indexes = Vec::with_capacity(self.indexes.len());
+        let mut coefficients = Vec::with_capacity(self.coefficients.len());
+        for (&index, &cc) in self.indexes.iter().zip(self.coefficients.iter()) {
+            if f(index) {
+                indexes.push(index);
+                coefficients.push(cc);
+            }
+        }
+        self.indexes = indexes;
+        self.coefficients = coefficients;
+    }
+
     // EVALUATOR
     // --------------------------------------------------------------------------------------------
     /// Computes a linear combination of evaluations relevant to this constraint group.
@@ -95,18 +155,23 @@ impl<E: FieldElement> TransitionConstraintGroup<E> {
     /// * $C_i(x)$ is the evaluation of the $i$th constraint at `x` (same as `evaluations[i]`).
     /// * $\alpha$ and $\beta$ are random field elements. In the interactive version of the
     ///   protocol, these are provided by the verifier.
-    /// * $d$ is the degree adjustment factor computed as $D + (n - 1) - deg(C_i(x))$, where
+    /// * $d$ is the degree adjustment factor computed as $D + (n - k) - deg(C_i(x))$, where
     ///   $D$ is the degree of the composition polynomial, $n$ is the length of the execution
-    ///   trace, and $deg(C_i(x))$ is the evaluation degree of the $i$th constraint.
+    ///   trace, $k$ is the number of transition exemptions, and $deg(C_i(x))$ is the evaluation
+    ///   degree of the $i$th constraint.
     ///
     /// There are two things to note here. First, the degree adjustment factor $d$ is the same
     /// for all constraints in the group (since all constraints have the same degree). Second,
-    /// the merged evaluations represent a polynomial of degree $D + n - 1$, which is higher
+    /// the merged evaluations represent a polynomial of degree $D + n - k$, which is higher
     /// then the target degree of the composition polynomial. This is because at this stage,
     /// we are merging only the numerators of transition constraints, and we will need to divide
     /// them by the divisor later on. The degree of the divisor for transition constraints is
-    /// always $n - 1$. Thus, once we divide out the divisor, the evaluations will represent a
-    /// polynomial of degree $D$.
+    /// $n - k$ (for traces without padding, $k = 1$). Thus, once we divide out the divisor, the
+    /// evaluations will represent a polynomial of degree $D$.
+    ///
+    /// If constraints of this group are exempted from additional steps, the linear combination
+    /// is also multiplied by $(x - g^{n - j})$ for each additionally exempted step $j$, and the
+    /// degree adjustment factor is reduced accordingly.
     pub fn merge_evaluations<B>(&self, evaluations: &[B], x: B) -> E
     where
         B: FieldElement,
@@ -122,10 +187,163 @@ impl<E: FieldElement> TransitionConstraintGroup<E> {
             let evaluation = E::from(evaluations[constraint_idx]);
             result += evaluation * (coefficients.0 + coefficients.1 * xp);
         }
+
+        // cancel out divisor factors at the steps from which the constraints are exempted
+        let x = E::from(x);
+        for &point in self.exemption_points.iter() {
+            result *= x - point;
+        }
         result
     }
 }
 
+// PADDING CONSTRAINT GROUP
+// ================================================================================================
+/// Constraints which require padding rows of an execution trace to repeat the last row of the
+/// computation.
+///
+/// For an execution trace with $k$ padding rows, a padding constraint $T_i(g \cdot x) - T_i(x)$
+/// is defined for each register $i$ of the main trace segment; the constraints are enforced on
+/// the last $k$ steps preceding the last step of the trace (see
+/// [Air::padding_constraint_divisor()](crate::Air::padding_constraint_divisor)). Since all
+/// padding constraints have the same degree and the same divisor, they are always merged into a
+/// single group.
+#[derive(Clone, Debug)]
+pub struct PaddingConstraintGroup<E: FieldElement> {
+    degree_adjustment: u32,
+    coefficients: Vec<(E, E)>,
+}
+
+impl<E: FieldElement> PaddingConstraintGroup<E> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new padding constraint group for the registers with the specified composition
+    /// coefficients.
+    pub(super) fn new(
+        trace_poly_degree: usize,
+        divisor_degree: usize,
+        composition_degree: usize,
+        coefficients: Vec<(E, E)>,
+    ) -> Self {
+        // padding constraints have the same degree as trace polynomials; so, the degree is
+        // adjusted in the same way as for boundary constraints
+        let target_degree = composition_degree + divisor_degree;
+        let degree_adjustment = (target_degree - trace_poly_degree) as u32;
+        PaddingConstraintGroup {
+            degree_adjustment,
+            coefficients,
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns composition coefficients for padding constraints; coefficients are listed in the
+    /// order of trace registers.
+    pub fn coefficients(&self) -> &[(E, E)] {
+        &self.coefficients
+    }
+
+    /// Returns the degree adjustment factor for padding constraints.
+    pub fn degree_adjustment(&self) -> u32 {
+        self.degree_adjustment
+    }
+
+    // EVALUATOR
+    // --------------------------------------------------------------------------------------------
+    /// Computes a linear combination of padding constraints evaluated over the specified
+    /// evaluation `frame` at `x`.
+    ///
+    /// The linear combination is computed as:
+    /// $$
+    /// \sum_{i=0}^{w-1}{(T_i(g \cdot x) - T_i(x)) \cdot (\alpha_i + \beta_i \cdot x^d)}
+    /// $$
+    /// where $w$ is the width of the frame, $T_i(x)$ and $T_i(g \cdot x)$ are values of the
+    /// $i$th register in the current and the next rows of the frame respectively, and $d$ is the
+    /// degree adjustment factor.
+    pub fn merge_evaluations<B>(&self, frame: &EvaluationFrame<B>, x: B) -> E
+    where
+        B: FieldElement,
+        E: From<B>,
+    {
+        debug_assert_eq!(
+            self.coefficients.len(),
+            frame.current().len(),
+            "number of padding constraints must be equal to the width of the evaluation frame"
+        );
+        let xp = E::from(x.exp(self.degree_adjustment.into()));
+
+        let mut result = E::ZERO;
+        let rows = frame.current().iter().zip(frame.next().iter());
+        for ((&current, &next), coefficients) in rows.zip(self.coefficients.iter()) {
+            let evaluation = E::from(next - current);
+            result += evaluation * (coefficients.0 + coefficients.1 * xp);
+        }
+        result
+    }
+}
+
+// CONSTRAINT GROUP
+// ================================================================================================
+/// A set of transition constraints which are combined using the same random coefficients.
+///
+/// By default, each transition constraint is assigned its own pair of random coefficients when
+/// constraints are merged into the constraint composition polynomial. Some protocols (e.g.,
+/// sumcheck-style arguments) require a subset of constraints to be combined with the same
+/// challenge instead. Such subsets can be described by constraint groups returned from the
+/// [Air::get_constraint_groups()](crate::Air::get_constraint_groups) method.
+///
+/// Constraint indexes are assumed to be consistent with the order in which constraint
+/// evaluations are written into the `result` slice by the
+/// [Air::evaluate_transition()](crate::Air::evaluate_transition) function.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConstraintGroup {
+    constraints: Vec<usize>,
+    shared_coefficient: bool,
+}
+
+impl ConstraintGroup {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new group of transition constraints with the specified indexes.
+    ///
+    /// When `shared_coefficient` is true, a single pair of composition coefficients is drawn for
+    /// the entire group; otherwise, each constraint in the group is assigned its own coefficients.
+    ///
+    /// # Panics
+    /// Panics if `constraints` is empty or contains duplicate indexes.
+    pub fn new(constraints: Vec<usize>, shared_coefficient: bool) -> Self {
+        assert!(
+            !constraints.is_empty(),
+            "a constraint group must contain at least one constraint"
+        );
+        for (i, index) in constraints.iter().enumerate() {
+            assert!(
+                !constraints[..i].contains(index),
+                "constraint {} is included in the group more than once",
+                index
+            );
+        }
+        ConstraintGroup {
+            constraints,
+            shared_coefficient,
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns indexes of transition constraints in this group.
+    pub fn constraints(&self) -> &[usize] {
+        &self.constraints
+    }
+
+    /// Returns true if all constraints in this group are combined using the same coefficients.
+    pub fn shared_coefficient(&self) -> bool {
+        self.shared_coefficient
+    }
+}
+
 // TRANSITION CONSTRAINT DEGREE
 // ================================================================================================
 /// Degree descriptor of a transition constraint.
@@ -199,6 +417,17 @@ impl TransitionConstraintDegree {
         }
     }
 
+    /// Returns the degree of this constraint with respect to trace registers; multiplications by
+    /// periodic columns are not included in this degree.
+    pub fn base(&self) -> usize {
+        self.base
+    }
+
+    /// Returns cycle lengths of periodic columns involved in multiplications of this constraint.
+    pub fn cycles(&self) -> &[usize] {
+        &self.cycles
+    }
+
     /// Computes a degree to which this degree description expands in the context of execution
     /// trace of the specified length.
     ///
@@ -242,74 +471,188 @@ impl TransitionConstraintDegree {
 // ================================================================================================
 /// A set of execution trace rows required for evaluation of transition constraints.
 ///
-/// In the current implementation, an evaluation frame always contains two consecutive rows of the
-/// execution trace. It is passed in as one of the parameters into
-/// [Air::evaluate_transition()](crate::Air::evaluate_transition) function.
+/// An evaluation frame contains consecutive rows of the execution trace. By default, a frame
+/// contains two rows: the current row and the next row; the number of rows can be increased via
+/// [AirContext::with_frame_size()](crate::AirContext::with_frame_size). It is passed in as one
+/// of the parameters into [Air::evaluate_transition()](crate::Air::evaluate_transition) function.
+///
+/// An evaluation frame does not carry the step of the execution trace at which it was read.
+/// This is inherent to the protocol: the prover evaluates constraints over the constraint
+/// evaluation domain, and the verifier evaluates them at an out-of-domain point; neither of these
+/// points corresponds to a step of the execution trace. Constraints which should apply only at
+/// specific steps (e.g., the first or the last step) should instead use a periodic column (see
+/// [Air::get_periodic_column_values()](crate::Air::get_periodic_column_values)) which is set to
+/// one at these steps and to zero elsewhere, or be expressed as assertions.
 #[derive(Debug, Clone)]
 pub struct EvaluationFrame<E: FieldElement> {
-    current: Vec<E>,
-    next: Vec<E>,
+    rows: Vec<Vec<E>>,
+    parts: RefCell<Vec<EvaluationFrame<E>>>,
 }
 
 impl<E: FieldElement> EvaluationFrame<E> {
     // CONSTRUCTORS
     // --------------------------------------------------------------------------------------------
 
-    /// Returns a new evaluation frame instantiated with the specified number of registers.
+    /// Returns a new two-row evaluation frame instantiated with the specified number of
+    /// registers.
     ///
     /// # Panics
     /// Panics if `num_registers` is zero.
     pub fn new(num_registers: usize) -> Self {
+        Self::with_size(num_registers, 2)
+    }
+
+    /// Returns a new evaluation frame with `frame_size` rows instantiated with the specified
+    /// number of registers.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `num_registers` is zero.
+    /// * `frame_size` is smaller than 2.
+    pub fn with_size(num_registers: usize, frame_size: usize) -> Self {
         assert!(
             num_registers > 0,
             "number of registers must be greater than zero"
         );
+        assert!(
+            frame_size >= 2,
+            "frame must contain at least two rows, but was {}",
+            frame_size
+        );
         EvaluationFrame {
-            current: E::zeroed_vector(num_registers),
-            next: E::zeroed_vector(num_registers),
+            rows: (0..frame_size)
+                .map(|_| E::zeroed_vector(num_registers))
+                .collect(),
+            parts: RefCell::new(Vec::new()),
         }
     }
 
-    /// Returns a new evaluation frame instantiated from the provided rows.
+    /// Returns a new two-row evaluation frame instantiated from the provided rows.
     ///
     /// # Panics
     /// Panics if:
     /// * Lengths of the provided rows are zero.
     /// * Lengths of the provided rows are not the same.
     pub fn from_rows(current: Vec<E>, next: Vec<E>) -> Self {
-        assert!(!current.is_empty(), "a row must contain at least one value");
-        assert_eq!(
-            current.len(),
-            next.len(),
-            "number of values in the rows must be the same"
+        Self::from_row_list(vec![current, next])
+    }
+
+    /// Returns a new evaluation frame instantiated from the provided list of consecutive rows.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * Fewer than two rows were provided.
+    /// * Lengths of the provided rows are zero.
+    /// * Lengths of the provided rows are not the same.
+    pub fn from_row_list(rows: Vec<Vec<E>>) -> Self {
+        assert!(
+            rows.len() >= 2,
+            "frame must contain at least two rows, but was {}",
+            rows.len()
         );
-        Self { current, next }
+        assert!(!rows[0].is_empty(), "a row must contain at least one value");
+        for row in rows.iter().skip(1) {
+            assert_eq!(
+                rows[0].len(),
+                row.len(),
+                "number of values in the rows must be the same"
+            );
+        }
+        Self {
+            rows,
+            parts: RefCell::new(Vec::new()),
+        }
     }
 
     // ROW ACCESSORS
     // --------------------------------------------------------------------------------------------
 
+    /// Returns the number of rows in this frame.
+    #[inline(always)]
+    pub fn size(&self) -> usize {
+        self.rows.len()
+    }
+
     /// Returns a reference to the current row.
     #[inline(always)]
     pub fn current(&self) -> &[E] {
-        &self.current
+        &self.rows[0]
     }
 
     /// Returns a mutable reference to the current row.
     #[inline(always)]
     pub fn current_mut(&mut self) -> &mut [E] {
-        &mut self.current
+        &mut self.rows[0]
     }
 
     /// Returns a reference to the next row.
     #[inline(always)]
     pub fn next(&self) -> &[E] {
-        &self.next
+        &self.rows[1]
     }
 
     /// Returns a mutable reference to the next row.
     #[inline(always)]
     pub fn next_mut(&mut self) -> &mut [E] {
-        &mut self.next
+        &mut self.rows[1]
+    }
+
+    /// Returns a reference to the row at the specified offset from the current row; offset 0
+    /// refers to the current row, offset 1 refers to the next row etc.
+    ///
+    /// # Panics
+    /// Panics if `offset` is not smaller than the number of rows in this frame.
+    #[inline(always)]
+    pub fn row(&self, offset: usize) -> &[E] {
+        &self.rows[offset]
+    }
+
+    /// Returns a mutable reference to the row at the specified offset from the current row.
+    ///
+    /// # Panics
+    /// Panics if `offset` is not smaller than the number of rows in this frame.
+    #[inline(always)]
+    pub fn row_mut(&mut self, offset: usize) -> &mut [E] {
+        &mut self.rows[offset]
+    }
+
+    // REGISTER SPLITTING
+    // --------------------------------------------------------------------------------------------
+
+    /// Splits registers of this frame at the specified index and returns two frames: the first
+    /// one contains registers [0, at), and the second one contains registers [at, num_registers).
+    ///
+    /// The returned frames are retained by this frame, and are overwritten on subsequent calls.
+    /// Thus, when the same frame is re-used across steps of the execution trace (as done by the
+    /// prover), splitting it does not allocate memory.
+    ///
+    /// # Panics
+    /// Panics if `at` is zero or is not smaller than the number of registers in this frame.
+    pub(crate) fn split_registers(&self, at: usize) -> Ref<'_, [EvaluationFrame<E>]> {
+        let num_registers = self.current().len();
+        assert!(
+            at > 0 && at < num_registers,
+            "split index must be between 1 and {}, but was {}",
+            num_registers - 1,
+            at
+        );
+
+        {
+            let mut parts = self.parts.borrow_mut();
+            if parts.is_empty() || parts[0].current().len() != at {
+                *parts = vec![
+                    Self::with_size(at, self.size()),
+                    Self::with_size(num_registers - at, self.size()),
+                ];
+            }
+            let (first, second) = parts.split_at_mut(1);
+            for (i, row) in self.rows.iter().enumerate() {
+                let (first_row, second_row) = row.split_at(at);
+                first[0].rows[i].copy_from_slice(first_row);
+                second[0].rows[i].copy_from_slice(second_row);
+            }
+        }
+
+        Ref::map(self.parts.borrow(), |parts| parts.as_slice())
     }
 }
@@ -0,0 +1,143 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::Air;
+use crate::{HashFunction, ProofOptions};
+use math::log2;
+use utils::{
+    string::ToString, ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+    SliceReader,
+};
+
+// VERIFICATION KEY
+// ================================================================================================
+/// A summary of the parameters of a specific execution of a computation against which proofs are
+/// verified.
+///
+/// A verification key is derived from an instance of an [Air] and can be stored separately from
+/// proofs (e.g., embedded into a verifier binary). When a proof is verified against a key, the
+/// verifier makes sure that the proof was generated for a computation with exactly the same
+/// parameters, rather than trusting the parameters carried by the proof itself.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VerificationKey {
+    trace_width: usize,
+    trace_length: usize,
+    num_constraints: usize,
+    constraint_composition_degree: usize,
+    options: ProofOptions,
+}
+
+impl VerificationKey {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a verification key summarizing the parameters of the specified `air`.
+    pub fn new<A: Air>(air: &A) -> Self {
+        VerificationKey {
+            trace_width: air.trace_width(),
+            trace_length: air.trace_length(),
+            num_constraints: air.num_transition_constraints(),
+            constraint_composition_degree: air.composition_degree(),
+            options: air.options().clone(),
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the width of the execution trace of the computation.
+    pub fn trace_width(&self) -> usize {
+        self.trace_width
+    }
+
+    /// Returns the length of the execution trace of the computation.
+    pub fn trace_length(&self) -> usize {
+        self.trace_length
+    }
+
+    /// Returns the number of transition constraints of the computation.
+    pub fn num_constraints(&self) -> usize {
+        self.num_constraints
+    }
+
+    /// Returns the degree of the constraint composition polynomial of the computation.
+    pub fn constraint_composition_degree(&self) -> usize {
+        self.constraint_composition_degree
+    }
+
+    /// Returns the hash function used by the protocol.
+    pub fn hash_fn(&self) -> HashFunction {
+        self.options.hash_fn()
+    }
+
+    /// Returns the options with which proofs for the computation must be generated.
+    pub fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+
+    // SERIALIZATION / DESERIALIZATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a verification key read from the specified `source`.
+    ///
+    /// # Errors
+    /// Returns an error if a valid verification key could not be read from the specified
+    /// `source`, or if any bytes remained unconsumed after the key was read.
+    pub fn from_bytes(source: &[u8]) -> Result<Self, DeserializationError> {
+        let mut source = SliceReader::new(source);
+        let key = Self::read_from(&mut source)?;
+        if source.has_more_bytes() {
+            return Err(DeserializationError::UnconsumedBytes);
+        }
+        Ok(key)
+    }
+}
+
+impl Serializable for VerificationKey {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u8(self.trace_width as u8);
+        target.write_u8(log2(self.trace_length) as u8);
+        target.write_u16(self.num_constraints as u16);
+        target.write_u32(self.constraint_composition_degree as u32);
+        self.options.write_into(target);
+    }
+}
+
+impl Deserializable for VerificationKey {
+    /// Reads a verification key from the specified `source` and returns the result.
+    ///
+    /// # Errors
+    /// Returns an error if a valid verification key could not be read from the specified
+    /// `source`.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let trace_width = source.read_u8()? as usize;
+        if trace_width == 0 {
+            return Err(DeserializationError::InvalidValue(
+                "trace width must be greater than zero".to_string(),
+            ));
+        }
+
+        let num_bits = source.read_u8()?;
+        if num_bits == 0 || num_bits >= 32 {
+            return Err(DeserializationError::InvalidValue(format!(
+                "log2 of trace length must be between 1 and 31, but was {}",
+                num_bits
+            )));
+        }
+        let trace_length = 2_usize.pow(num_bits as u32);
+
+        let num_constraints = source.read_u16()? as usize;
+        let constraint_composition_degree = source.read_u32()? as usize;
+        let options = ProofOptions::read_from(source)?;
+
+        Ok(VerificationKey {
+            trace_width,
+            trace_length,
+            num_constraints,
+            constraint_composition_degree,
+            options,
+        })
+    }
+}
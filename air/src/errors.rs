@@ -4,6 +4,7 @@
 // LICENSE file in the root directory of this source tree.
 
 use core::fmt;
+use utils::DeserializationError;
 
 // ASSERTION ERROR
 // ================================================================================================
@@ -43,3 +44,46 @@ impl fmt::Display for AssertionError {
         }
     }
 }
+
+// EVALUATION ERROR
+// ================================================================================================
+/// Represents an error returned when constraints of an AIR could not be evaluated.
+#[derive(Debug, PartialEq)]
+pub enum EvaluationError {
+    /// This error occurs when an AIR declares transition constraints against the auxiliary trace
+    /// segment, but does not implement
+    /// [Air::evaluate_aux_transition()](crate::Air::evaluate_aux_transition).
+    MissingAuxTransitionEvaluator,
+}
+
+impl fmt::Display for EvaluationError {
+    #[rustfmt::skip]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingAuxTransitionEvaluator => {
+                write!(f, "the AIR declares auxiliary transition constraints, but does not evaluate them")
+            }
+        }
+    }
+}
+
+// MIGRATION ERROR
+// ================================================================================================
+/// Represents an error returned during migration of a serialized proof to the current format.
+#[derive(Debug, PartialEq)]
+pub enum MigrationError {
+    /// This error occurs when the bytes being migrated do not represent a valid proof in the
+    /// source format.
+    InvalidSourceProof(DeserializationError),
+}
+
+impl fmt::Display for MigrationError {
+    #[rustfmt::skip]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidSourceProof(err) => {
+                write!(f, "source proof could not be deserialized: {}", err)
+            }
+        }
+    }
+}
@@ -36,14 +36,18 @@ extern crate alloc;
 pub mod proof;
 
 mod errors;
-pub use errors::AssertionError;
+pub use errors::{AssertionError, EvaluationError, MigrationError};
 
 mod options;
-pub use options::{FieldExtension, HashFunction, ProofOptions};
+pub use options::{
+    ConcurrencyConfig, FieldExtension, FriRemainderEncoding, HashFunction, ProofOptions,
+};
 
 mod air;
 pub use air::{
-    Air, AirContext, Assertion, BoundaryConstraint, BoundaryConstraintGroup,
-    ConstraintCompositionCoefficients, ConstraintDivisor, DeepCompositionCoefficients,
-    EvaluationFrame, TraceInfo, TransitionConstraintDegree, TransitionConstraintGroup,
+    Air, AirContext, Assertion, BoundaryConstraint, BoundaryConstraintGroup, CombinationMode,
+    CompositeAir, CompositePublicInputs, ConstraintCompositionCoefficients, ConstraintDivisor,
+    ConstraintGroup, DeepCompositionCoefficients, EvaluationFrame, LagrangeBoundaryConstraints,
+    PaddingConstraintGroup, StackedAir, StackedPublicInputs, TaggedAir, TraceInfo,
+    TransitionConstraintDegree, TransitionConstraintGroup, VerificationKey,
 };
@@ -3,10 +3,26 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
+use crate::proof::get_conjectured_security;
+use core::cmp;
 use fri::FriOptions;
-use math::StarkField;
+pub use fri::FriRemainderEncoding;
+use math::{fields::f128, StarkField};
 use utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
 
+// CONSTANTS
+// ================================================================================================
+
+/// Bit of the serialized proof options flags set when a domain offset follows the flags.
+const DOMAIN_OFFSET_FLAG: u8 = 0b01;
+
+/// Bit of the serialized proof options flags set when the FRI remainder is transmitted as
+/// polynomial coefficients.
+const REMAINDER_COEFFICIENTS_FLAG: u8 = 0b10;
+
+/// Trace length for which the number of queries of the default proof options is computed.
+const DEFAULT_OPTIONS_TRACE_LENGTH: usize = 1 << 20;
+
 // TYPES AND INTERFACES
 // ================================================================================================
 
@@ -32,6 +48,11 @@ pub enum HashFunction {
     ///
     /// When this function is used in the STARK protocol, proof security cannot exceed 128 bits.
     Sha3_256 = 3,
+
+    /// Keccak hash function with 256 bit output (as used by Ethereum's `KECCAK256` opcode).
+    ///
+    /// When this function is used in the STARK protocol, proof security cannot exceed 128 bits.
+    Keccak256 = 4,
 }
 
 /// Defines an extension field for the composition polynomial.
@@ -58,6 +79,16 @@ pub enum FieldExtension {
     Cubic = 3,
 }
 
+/// Configuration of concurrent proof generation.
+///
+/// These settings affect only how a proof is generated by a prover compiled with `concurrent`
+/// feature enabled. They are not included in the proof, and have no impact on proof soundness or
+/// proof size.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub struct ConcurrencyConfig {
+    constraint_eval_chunk_size: Option<usize>,
+}
+
 /// STARK protocol parameters.
 ///
 /// These parameters have a direct impact on proof soundness, proof generation time, and proof
@@ -78,7 +109,10 @@ pub enum FieldExtension {
 /// 5. Grinding factor - higher values increase proof soundness, but also may increase proof
 ///    generation time. More precisely, proof soundness is bounded by
 ///    `num_queries * log2(blowup_factor) + grinding_factor`.
-#[derive(Debug, Clone, Eq, PartialEq)]
+///
+/// Proof options are compared only by the parameters which are included in a proof; thus, the
+/// [ConcurrencyConfig] of the options does not affect their equality.
+#[derive(Debug, Clone, Eq)]
 pub struct ProofOptions {
     num_queries: u8,
     blowup_factor: u8,
@@ -87,6 +121,9 @@ pub struct ProofOptions {
     field_extension: FieldExtension,
     fri_folding_factor: u8,
     fri_max_remainder_size: u8, // stored as power of 2
+    domain_offset: Option<u128>,
+    fri_remainder_encoding: FriRemainderEncoding,
+    concurrency_config: ConcurrencyConfig,
 }
 
 // PROOF OPTIONS IMPLEMENTATION
@@ -139,7 +176,89 @@ impl ProofOptions {
             field_extension,
             fri_folding_factor: fri_folding_factor as u8,
             fri_max_remainder_size: fri_max_remainder_size.trailing_zeros() as u8,
+            domain_offset: None,
+            fri_remainder_encoding: FriRemainderEncoding::Evaluations,
+            concurrency_config: ConcurrencyConfig::default(),
+        }
+    }
+
+    /// Returns proof options which provide 128 bits of conjectured security for computations
+    /// over the default [128-bit field](math::fields::f128).
+    ///
+    /// The options use blowup factor of 8, grinding factor of 16, BLAKE3 hash function with
+    /// 256-bit output, and quadratic field extension. The number of queries is the smallest one
+    /// for which the conjectured security of proofs for a trace of $2^{20}$ steps reaches 128
+    /// bits; with the current security formula, this is 38 queries.
+    ///
+    /// Thus, the soundness error of proofs generated with these options is bounded by
+    /// $2^{-128}$. For a trace of $2^{20}$ steps and 2 registers, the size of a proof is
+    /// expected to be about 150 KB.
+    pub fn default_128bit() -> Self {
+        Self::with_min_queries_for_security(128, FieldExtension::Quadratic)
+    }
+
+    /// Returns proof options which provide 96 bits of conjectured security for computations
+    /// over the default [128-bit field](math::fields::f128).
+    ///
+    /// The options use blowup factor of 8, grinding factor of 16, BLAKE3 hash function with
+    /// 256-bit output, and no field extension. The number of queries is the smallest one for
+    /// which the conjectured security of proofs for a trace of $2^{20}$ steps reaches 96 bits;
+    /// with the current security formula, this is 27 queries.
+    ///
+    /// Thus, the soundness error of proofs generated with these options is bounded by
+    /// $2^{-96}$. For a trace of $2^{20}$ steps and 2 registers, the size of a proof is expected
+    /// to be about 90 KB.
+    pub fn default_96bit() -> Self {
+        Self::with_min_queries_for_security(96, FieldExtension::None)
+    }
+
+    /// Returns these options with the offset of the low-degree extension domain set to the
+    /// specified `offset`.
+    ///
+    /// By default, the low-degree extension domain is shifted by the primitive element of the
+    /// base field; this method can be used to specify a different coset of the LDE domain. The
+    /// offset is included in the proof, and thus, the verifier will use the same offset.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * Elements of field `B` cannot be represented with 128 bits.
+    /// * `offset` is not a valid domain offset as defined by
+    ///   [validate_domain_offset()](ProofOptions::validate_domain_offset).
+    pub fn with_coset_offset<B: StarkField>(mut self, offset: B) -> Self {
+        let bytes = offset.to_bytes();
+        assert!(
+            bytes.len() <= 16,
+            "field elements larger than 16 bytes are not supported"
+        );
+        let mut buf = [0u8; 16];
+        buf[..bytes.len()].copy_from_slice(&bytes);
+        let offset = u128::from_le_bytes(buf);
+        if let Err(err) = validate_coset_offset::<B>(offset) {
+            panic!("{}", err);
         }
+        self.domain_offset = Some(offset);
+        self
+    }
+
+    /// Returns these options with the FRI remainder transmitted using the specified `encoding`.
+    ///
+    /// By default, the remainder is transmitted as evaluations over the domain of the last FRI
+    /// layer. Transmitting the remainder as coefficients of the remainder polynomial reduces the
+    /// size of the remainder by the blowup factor. The encoding is included in the proof, and
+    /// thus, the verifier will use the same encoding.
+    pub fn with_fri_remainder_encoding(mut self, encoding: FriRemainderEncoding) -> Self {
+        self.fri_remainder_encoding = encoding;
+        self
+    }
+
+    /// Returns these options with concurrent proof generation configured by the specified
+    /// `config`.
+    ///
+    /// The config is not serialized with proof options, and thus, proof options read from a proof
+    /// always use the default config.
+    pub fn with_concurrency_config(mut self, config: ConcurrencyConfig) -> Self {
+        self.concurrency_config = config;
+        self
     }
 
     // PUBLIC ACCESSORS
@@ -197,16 +316,209 @@ impl ProofOptions {
     /// Returns the offset by which the low-degree extension domain is shifted in relation to the
     /// trace domain.
     ///
-    /// Currently, this is hard-coded to the primitive element of the underlying base field.
+    /// Unless set via [with_coset_offset()](ProofOptions::with_coset_offset), this is the
+    /// primitive element of the underlying base field.
     pub fn domain_offset<B: StarkField>(&self) -> B {
-        B::GENERATOR
+        match self.domain_offset {
+            Some(offset) => B::from(offset),
+            None => B::GENERATOR,
+        }
+    }
+
+    /// Makes sure the offset of the low-degree extension domain specified by these options is a
+    /// valid offset in field `B`.
+    ///
+    /// A valid offset is a canonical encoding of an element of `B` (i.e., it is smaller than the
+    /// field modulus) which is not zero and is not an element of the largest multiplicative
+    /// subgroup of `B` with a power-of-two order. The latter guarantees that the shifted LDE
+    /// domain does not intersect the trace domain for any trace length. The default offset is
+    /// always valid.
+    ///
+    /// # Errors
+    /// Returns an error if the domain offset is not valid in field `B`.
+    pub fn validate_domain_offset<B: StarkField>(&self) -> Result<(), DeserializationError> {
+        match self.domain_offset {
+            Some(offset) => validate_coset_offset::<B>(offset),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns the encoding used to transmit the FRI remainder in a proof.
+    ///
+    /// Unless set via [with_fri_remainder_encoding()](ProofOptions::with_fri_remainder_encoding),
+    /// the remainder is transmitted as evaluations.
+    pub fn fri_remainder_encoding(&self) -> FriRemainderEncoding {
+        self.fri_remainder_encoding
+    }
+
+    /// Returns the configuration of concurrent proof generation.
+    ///
+    /// Unless set via [with_concurrency_config()](ProofOptions::with_concurrency_config), all
+    /// concurrency settings are detected automatically.
+    pub fn concurrency_config(&self) -> &ConcurrencyConfig {
+        &self.concurrency_config
+    }
+
+    /// Returns conjectured security level (in bits) of proofs generated with these options for a
+    /// computation with the specified `trace_length` defined over base field `B`.
+    ///
+    /// This is the same security level as the one reported by
+    /// [StarkProof::security_level()](crate::proof::StarkProof::security_level) for such proofs.
+    pub fn security_bits<B: StarkField>(&self, trace_length: usize) -> f64 {
+        let lde_domain_size = (trace_length * self.blowup_factor()) as u64;
+        get_conjectured_security(self, B::MODULUS_BITS, lde_domain_size) as f64
+    }
+
+    /// Returns an estimate of the size (in bytes) of a proof generated with these options for a
+    /// computation over base field `B` with the specified `trace_width` and `trace_length`, and
+    /// with transition constraints of degree at most `max_constraint_degree`.
+    ///
+    /// The estimate assumes that queried positions are distributed uniformly over the LDE domain,
+    /// and uses the expected number of distinct nodes in Merkle authentication paths of queries.
+    /// Thus, sizes of actual proofs vary slightly around the estimate.
+    ///
+    /// # Panics
+    /// Panics if `trace_length` is not a power of two.
+    pub fn estimate_proof_size<B: StarkField>(
+        &self,
+        trace_width: usize,
+        trace_length: usize,
+        max_constraint_degree: usize,
+    ) -> usize {
+        assert!(
+            trace_length.is_power_of_two(),
+            "trace length must be a power of two, but was {}",
+            trace_length
+        );
+        let lde_domain_size = trace_length * self.blowup_factor();
+        let num_queries = cmp::min(self.num_queries(), lde_domain_size);
+        let digest_size = self.hash_fn().digest_size();
+        let element_size = B::ELEMENT_BYTES;
+        let ext_element_size = element_size * self.field_extension().degree() as usize;
+
+        // the composition polynomial is split into as many columns as the blowup factor needed
+        // to evaluate the constraints
+        let composition_width = cmp::max(max_constraint_degree.next_power_of_two(), 2);
+
+        // expected number of distinct positions among `num_queries` uniformly drawn positions of
+        // a domain of the specified size
+        let num_distinct = |domain_size: usize| {
+            let miss = (0..num_queries).fold(1.0, |acc, _| acc * (1.0 - 1.0 / domain_size as f64));
+            domain_size as f64 * (1.0 - miss)
+        };
+
+        // expected size of a batch Merkle proof for the queries against a tree with the specified
+        // number of leaves; at each level of the tree, the proof contains the nodes which are not
+        // on the path of any query but whose siblings are
+        let batch_proof_size = |num_leaves: usize| {
+            let mut num_nodes = 0.0;
+            let mut level_size = num_leaves;
+            while level_size > 1 {
+                let touched = num_distinct(level_size) / level_size as f64;
+                num_nodes += level_size as f64 * touched * (1.0 - touched);
+                level_size /= 2;
+            }
+            num_nodes as usize * digest_size + num_queries + 1
+        };
+
+        // FRI layers and remainder
+        let fri_options = self.to_fri_options::<B>();
+        let folding_factor = fri_options.folding_factor();
+        let num_fri_layers = fri_options.num_fri_layers(lde_domain_size);
+        let mut fri_size = 0;
+        let mut layer_domain_size = lde_domain_size;
+        for _ in 0..num_fri_layers {
+            layer_domain_size /= folding_factor;
+            let num_layer_queries = num_distinct(layer_domain_size) as usize;
+            fri_size += num_layer_queries * folding_factor * ext_element_size
+                + batch_proof_size(layer_domain_size)
+                + 8;
+        }
+        let remainder_size = match self.fri_remainder_encoding() {
+            FriRemainderEncoding::Evaluations => layer_domain_size,
+            FriRemainderEncoding::Coefficients => {
+                cmp::max(layer_domain_size / self.blowup_factor(), 1)
+            }
+        };
+        fri_size += remainder_size * ext_element_size + 4;
+
+        // context, commitments, and proof-of-work nonce
+        let header_size = 32 + (num_fri_layers + 2) * digest_size + 8;
+
+        // trace and constraint queries, and the out-of-domain frame
+        let trace_queries_size =
+            num_queries * trace_width * element_size + batch_proof_size(lde_domain_size) + 8;
+        let constraint_queries_size = num_queries * composition_width * ext_element_size
+            + batch_proof_size(lde_domain_size)
+            + 8;
+        let ood_frame_size = (2 * trace_width + composition_width) * ext_element_size + 4;
+
+        header_size + trace_queries_size + constraint_queries_size + ood_frame_size + fri_size
     }
 
     /// Returns options for FRI protocol instantiated with parameters from this proof options.
-    pub fn to_fri_options(&self) -> FriOptions {
+    ///
+    /// Domain offset of the returned FRI options is set to the domain offset of these options
+    /// in field `B`.
+    pub fn to_fri_options<B: StarkField>(&self) -> FriOptions {
         let folding_factor = self.fri_folding_factor as usize;
         let max_remainder_size = 2usize.pow(self.fri_max_remainder_size as u32);
-        FriOptions::new(self.blowup_factor(), folding_factor, max_remainder_size)
+        let options = FriOptions::new(self.blowup_factor(), folding_factor, max_remainder_size)
+            .with_remainder_encoding(self.fri_remainder_encoding);
+        match self.domain_offset {
+            Some(_) => options.with_domain_offset(self.domain_offset::<B>()),
+            None => options,
+        }
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns default proof options with the specified field extension and the smallest number
+    /// of queries for which the options achieve the specified security level.
+    ///
+    /// # Panics
+    /// Panics if the security level cannot be achieved with any number of queries.
+    fn with_min_queries_for_security(security_bits: u32, field_extension: FieldExtension) -> Self {
+        (1..=128)
+            .map(|num_queries| {
+                ProofOptions::new(
+                    num_queries,
+                    8,
+                    16,
+                    HashFunction::Blake3_256,
+                    field_extension,
+                    8,
+                    256,
+                )
+            })
+            .find(|options| {
+                let security =
+                    options.security_bits::<f128::BaseElement>(DEFAULT_OPTIONS_TRACE_LENGTH);
+                security >= security_bits as f64
+            })
+            .unwrap_or_else(|| {
+                panic!(
+                    "{} bits of security cannot be achieved with default proof options",
+                    security_bits
+                )
+            })
+    }
+}
+
+impl PartialEq for ProofOptions {
+    fn eq(&self, other: &Self) -> bool {
+        // concurrency config is a setting of the prover which is not serialized, and thus, the
+        // options read from a proof must be equal to the options with which it was generated
+        self.num_queries == other.num_queries
+            && self.blowup_factor == other.blowup_factor
+            && self.grinding_factor == other.grinding_factor
+            && self.hash_fn == other.hash_fn
+            && self.field_extension == other.field_extension
+            && self.fri_folding_factor == other.fri_folding_factor
+            && self.fri_max_remainder_size == other.fri_max_remainder_size
+            && self.domain_offset == other.domain_offset
+            && self.fri_remainder_encoding == other.fri_remainder_encoding
     }
 }
 
@@ -220,6 +532,19 @@ impl Serializable for ProofOptions {
         target.write(self.field_extension);
         target.write_u8(self.fri_folding_factor);
         target.write_u8(self.fri_max_remainder_size);
+        // the flags byte records whether the domain offset follows, and which encoding is used
+        // for the FRI remainder; with default options, the flags byte is 0
+        let mut flags = 0;
+        if self.domain_offset.is_some() {
+            flags |= DOMAIN_OFFSET_FLAG;
+        }
+        if self.fri_remainder_encoding == FriRemainderEncoding::Coefficients {
+            flags |= REMAINDER_COEFFICIENTS_FLAG;
+        }
+        target.write_u8(flags);
+        if let Some(offset) = self.domain_offset {
+            target.write_u8_slice(&offset.to_le_bytes());
+        }
     }
 }
 
@@ -229,7 +554,7 @@ impl Deserializable for ProofOptions {
     /// # Errors
     /// Returns an error of a valid proof options could not be read from the specified `source`.
     fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
-        Ok(ProofOptions::new(
+        let mut result = ProofOptions::new(
             source.read_u8()? as usize,
             source.read_u8()? as usize,
             source.read_u8()? as u32,
@@ -237,7 +562,68 @@ impl Deserializable for ProofOptions {
             FieldExtension::read_from(source)?,
             source.read_u8()? as usize,
             2usize.pow(source.read_u8()? as u32),
-        ))
+        );
+        let flags = source.read_u8()?;
+        if flags & !(DOMAIN_OFFSET_FLAG | REMAINDER_COEFFICIENTS_FLAG) != 0 {
+            return Err(DeserializationError::InvalidValue(format!(
+                "value {} cannot be deserialized as proof options flags",
+                flags
+            )));
+        }
+        if flags & DOMAIN_OFFSET_FLAG != 0 {
+            let offset = source.read_u128()?;
+            if offset <= 1 {
+                return Err(DeserializationError::InvalidValue(format!(
+                    "domain offset must be a non-unit element, but was {}",
+                    offset
+                )));
+            }
+            result.domain_offset = Some(offset);
+        }
+        if flags & REMAINDER_COEFFICIENTS_FLAG != 0 {
+            result.fri_remainder_encoding = FriRemainderEncoding::Coefficients;
+        }
+        Ok(result)
+    }
+}
+
+// CONCURRENCY CONFIG IMPLEMENTATION
+// ================================================================================================
+
+impl ConcurrencyConfig {
+    /// Returns a new config with all settings detected automatically.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns this config with constraint evaluation domain broken into chunks of the specified
+    /// number of rows; the chunks are evaluated in parallel.
+    ///
+    /// Chunks larger than the constraint evaluation domain are truncated to the size of the
+    /// domain.
+    ///
+    /// # Panics
+    /// Panics if `chunk_size` is smaller than 16 or is not a power of two.
+    pub fn with_constraint_eval_chunk_size(mut self, chunk_size: usize) -> Self {
+        assert!(
+            chunk_size.is_power_of_two(),
+            "constraint evaluation chunk size must be a power of 2"
+        );
+        assert!(
+            chunk_size >= 16,
+            "constraint evaluation chunk size cannot be smaller than 16"
+        );
+        self.constraint_eval_chunk_size = Some(chunk_size);
+        self
+    }
+
+    /// Returns the number of rows of the constraint evaluation domain evaluated as a single
+    /// chunk, or None if the chunk size should be detected automatically.
+    ///
+    /// When detected automatically, the chunk size is chosen such that the data accessed while
+    /// evaluating a single chunk fits into the CPU cache.
+    pub fn constraint_eval_chunk_size(&self) -> Option<usize> {
+        self.constraint_eval_chunk_size
     }
 }
 
@@ -286,12 +672,23 @@ impl Deserializable for FieldExtension {
 // ================================================================================================
 
 impl HashFunction {
+    /// Returns the size of digests produced by this hash function in bytes.
+    pub fn digest_size(&self) -> usize {
+        match self {
+            Self::Blake3_192 => 24,
+            Self::Blake3_256 => 32,
+            Self::Sha3_256 => 32,
+            Self::Keccak256 => 32,
+        }
+    }
+
     /// Returns collision resistance of this hash function in bits.
     pub fn collision_resistance(&self) -> u32 {
         match self {
             Self::Blake3_192 => 96,
             Self::Blake3_256 => 128,
             Self::Sha3_256 => 128,
+            Self::Keccak256 => 128,
         }
     }
 }
@@ -310,6 +707,7 @@ impl Deserializable for HashFunction {
             1 => Ok(HashFunction::Blake3_192),
             2 => Ok(HashFunction::Blake3_256),
             3 => Ok(HashFunction::Sha3_256),
+            4 => Ok(HashFunction::Keccak256),
             value => Err(DeserializationError::InvalidValue(format!(
                 "value {} cannot be deserialized as HashFunction enum",
                 value
@@ -317,3 +715,43 @@ impl Deserializable for HashFunction {
         }
     }
 }
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns an error if `offset` is not a canonical encoding of an element of field `B`, or if the
+/// element is zero or belongs to the largest power-of-two order subgroup of `B`.
+fn validate_coset_offset<B: StarkField>(offset: u128) -> Result<(), DeserializationError> {
+    let modulus_bytes = B::get_modulus_le_bytes();
+    if modulus_bytes.len() <= 16 {
+        let mut buf = [0u8; 16];
+        buf[..modulus_bytes.len()].copy_from_slice(&modulus_bytes);
+        let modulus = u128::from_le_bytes(buf);
+        if offset >= modulus {
+            return Err(DeserializationError::InvalidValue(format!(
+                "domain offset {} is not smaller than the field modulus {}",
+                offset, modulus
+            )));
+        }
+    }
+
+    let element = B::from(offset);
+    if element == B::ZERO {
+        return Err(DeserializationError::InvalidValue(
+            "domain offset cannot be zero".into(),
+        ));
+    }
+
+    let mut power = element;
+    for _ in 0..B::TWO_ADICITY {
+        power = power.square();
+    }
+    if power == B::ONE {
+        return Err(DeserializationError::InvalidValue(format!(
+            "domain offset {} is an element of the subgroup of order 2^{}, which contains the LDE domain",
+            offset,
+            B::TWO_ADICITY
+        )));
+    }
+    Ok(())
+}
@@ -15,6 +15,8 @@ use utils::{
 ///
 /// These commitments include:
 /// * Commitment to the extended execution trace.
+/// * Commitment to the extended auxiliary trace segment (only for computations with an
+///   auxiliary trace segment).
 /// * Commitment to the evaluations of constraint composition polynomial over LDE domain.
 /// * Commitments to the evaluations of polynomials at all FRI layers.
 ///
@@ -75,6 +77,30 @@ impl Commitments {
         }
         Ok((commitments[0], commitments[1], commitments[2..].to_vec()))
     }
+
+    /// Parses the serialized commitments of a computation with an auxiliary trace segment into
+    /// distinct parts.
+    ///
+    /// The parts are (in the order in which they appear in the tuple):
+    /// 1. Extended execution trace commitment.
+    /// 2. Extended auxiliary trace segment commitment.
+    /// 3. Constraint composition polynomial evaluation commitment.
+    /// 4. FRI layer commitments.
+    ///
+    /// # Errors
+    /// Returns an error if the bytes stored in self could not be parsed into the requested number
+    /// of commitments, or if there are any unconsumed bytes remaining after the parsing completes.
+    #[allow(clippy::type_complexity)]
+    pub fn parse_with_aux_trace<H: Hasher>(
+        self,
+        num_fri_layers: usize,
+    ) -> Result<(H::Digest, H::Digest, H::Digest, Vec<H::Digest>), DeserializationError> {
+        // the auxiliary trace commitment is placed right after the trace commitment, and thus,
+        // the remaining commitments can be parsed as if there were an extra FRI layer
+        let (trace_root, aux_trace_root, mut roots) = self.parse::<H>(num_fri_layers + 1)?;
+        let constraint_root = roots.remove(0);
+        Ok((trace_root, aux_trace_root, constraint_root, roots))
+    }
 }
 
 impl Serializable for Commitments {
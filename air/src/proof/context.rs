@@ -10,13 +10,26 @@ use utils::{
     DeserializationError, Serializable,
 };
 
+// CONSTANTS
+// ================================================================================================
+
+/// Bit of the serialized trace length set when the number of padding rows follows the trace
+/// length; the remaining bits of the serialized trace length hold log2 of the trace length.
+const PADDING_FLAG: u8 = 0b1000_0000;
+
+/// Bit of the serialized trace length set when the width of the auxiliary trace segment follows
+/// the trace length (and the number of padding rows, if any).
+const AUX_SEGMENT_FLAG: u8 = 0b0100_0000;
+
 // PROOF CONTEXT
 // ================================================================================================
 /// Basic metadata about a specific execution of a computation.
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct Context {
     trace_width: u8,
+    aux_trace_width: u8,
     trace_length: u8, // stored as power of two
+    num_padding_rows: u32,
     trace_meta: Vec<u8>,
     field_modulus_bytes: Vec<u8>,
     options: ProofOptions,
@@ -30,13 +43,31 @@ impl Context {
     pub fn new<B: StarkField>(trace_info: &TraceInfo, options: ProofOptions) -> Self {
         Context {
             trace_width: trace_info.width() as u8,
+            aux_trace_width: 0,
             trace_length: log2(trace_info.length()) as u8,
+            num_padding_rows: trace_info.num_padding_rows() as u32,
             trace_meta: trace_info.meta().to_vec(),
             field_modulus_bytes: B::get_modulus_le_bytes(),
             options,
         }
     }
 
+    /// Returns this context updated to specify that the execution trace has an auxiliary
+    /// segment of the specified width.
+    ///
+    /// # Panics
+    /// Panics if the combined width of the main and the auxiliary segments is greater than 255.
+    pub fn with_aux_trace_width(mut self, aux_trace_width: usize) -> Self {
+        assert!(
+            self.trace_width() + aux_trace_width <= TraceInfo::MAX_TRACE_WIDTH,
+            "combined trace width cannot be greater than {}, but was {}",
+            TraceInfo::MAX_TRACE_WIDTH,
+            self.trace_width() + aux_trace_width
+        );
+        self.aux_trace_width = aux_trace_width as u8;
+        self
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -50,6 +81,12 @@ impl Context {
         self.trace_width as usize
     }
 
+    /// Returns the width of the auxiliary segment of the execution trace of the computation
+    /// described by this context; this is 0 for computations without an auxiliary segment.
+    pub fn aux_trace_width(&self) -> usize {
+        self.aux_trace_width as usize
+    }
+
     /// Returns execution trace info for the computation described by this context.
     pub fn get_trace_info(&self) -> TraceInfo {
         TraceInfo::with_meta(
@@ -57,6 +94,7 @@ impl Context {
             self.trace_length(),
             self.trace_meta.clone(),
         )
+        .with_num_padding_rows(self.num_padding_rows as usize)
     }
 
     /// Returns the size of the LDE domain for the computation described by this context.
@@ -96,7 +134,23 @@ impl Serializable for Context {
     /// Serializes `self` and writes the resulting bytes into the `target`.
     fn write_into<W: ByteWriter>(&self, target: &mut W) {
         target.write_u8(self.trace_width);
-        target.write_u8(self.trace_length);
+        // the number of padding rows and the width of the auxiliary segment are written only for
+        // traces which have them so that other contexts are serialized in the same way as before
+        // these were supported
+        let mut trace_length = self.trace_length;
+        if self.num_padding_rows != 0 {
+            trace_length |= PADDING_FLAG;
+        }
+        if self.aux_trace_width != 0 {
+            trace_length |= AUX_SEGMENT_FLAG;
+        }
+        target.write_u8(trace_length);
+        if self.num_padding_rows != 0 {
+            target.write_u32(self.num_padding_rows);
+        }
+        if self.aux_trace_width != 0 {
+            target.write_u8(self.aux_trace_width);
+        }
         target.write_u16(self.trace_meta.len() as u16);
         target.write_u8_slice(&self.trace_meta);
         assert!(self.field_modulus_bytes.len() < u8::MAX as usize);
@@ -129,6 +183,16 @@ impl Deserializable for Context {
 
         // read and validate trace length
         let trace_length = source.read_u8()?;
+        let is_padded = trace_length & PADDING_FLAG != 0;
+        let has_aux_segment = trace_length & AUX_SEGMENT_FLAG != 0;
+        let trace_length = trace_length & !(PADDING_FLAG | AUX_SEGMENT_FLAG);
+        if trace_length as u32 >= usize::BITS {
+            return Err(DeserializationError::InvalidValue(format!(
+                "log2 of trace length must be smaller than {}, but had {}",
+                usize::BITS,
+                trace_length
+            )));
+        }
         if 2_usize.pow(trace_length as u32) < TraceInfo::MIN_TRACE_LENGTH {
             return Err(DeserializationError::InvalidValue(format!(
                 "Trace length cannot be smaller than {}, but had {}",
@@ -137,6 +201,36 @@ impl Deserializable for Context {
             )));
         }
 
+        // read and validate the number of padding rows
+        let num_padding_rows = if is_padded { source.read_u32()? } else { 0 };
+        if is_padded
+            && (num_padding_rows == 0
+                || num_padding_rows as usize >= 2_usize.pow(trace_length as u32))
+        {
+            return Err(DeserializationError::InvalidValue(format!(
+                "Number of padding rows must be between 1 and {}, but was {}",
+                2_usize.pow(trace_length as u32) - 1,
+                num_padding_rows
+            )));
+        }
+
+        // read and validate the width of the auxiliary trace segment
+        let aux_trace_width = if has_aux_segment {
+            source.read_u8()?
+        } else {
+            0
+        };
+        if has_aux_segment
+            && (aux_trace_width == 0
+                || trace_width as usize + aux_trace_width as usize > TraceInfo::MAX_TRACE_WIDTH)
+        {
+            return Err(DeserializationError::InvalidValue(format!(
+                "Auxiliary trace width must be between 1 and {}, but was {}",
+                TraceInfo::MAX_TRACE_WIDTH - trace_width as usize,
+                aux_trace_width
+            )));
+        }
+
         // read trace metadata
         let num_meta_bytes = source.read_u16()? as usize;
         let trace_meta = if num_meta_bytes != 0 {
@@ -159,7 +253,9 @@ impl Deserializable for Context {
 
         Ok(Context {
             trace_width,
+            aux_trace_width,
             trace_length,
+            num_padding_rows,
             trace_meta,
             field_modulus_bytes,
             options,
@@ -0,0 +1,193 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    check_context_version, check_format_version, Commitments, Context, OodFrame, Queries,
+    StarkProof,
+};
+use fri::FriProof;
+use serde_json::{Map, Value};
+use utils::{
+    collections::Vec,
+    string::{String, ToString},
+    ByteReader, Deserializable, DeserializationError, Serializable, SliceReader,
+};
+
+// JSON PROOF ENCODING
+// ================================================================================================
+
+impl StarkProof {
+    /// Serializes this proof into a JSON string.
+    ///
+    /// The result is a JSON object with the following fields:
+    /// * `version` - a number specifying the version of the proof format in which the proof was
+    ///   generated.
+    /// * `context`, `commitments`, `trace_queries`, `constraint_queries`, `ood_frame`, and
+    ///   `fri_proof` - hex strings encoding the bytes of the respective component of the proof.
+    /// * `aux_trace_queries` - a hex string encoding the bytes of the auxiliary trace queries, or
+    ///   `null` for computations without an auxiliary trace segment.
+    /// * `pow_nonce` - a hex string encoding the proof-of-work nonce as 8 little-endian bytes.
+    ///
+    /// Hex strings are lowercase and start with `0x`. The bytes of each component are the same
+    /// as the bytes written for this component by [to_bytes()](StarkProof::to_bytes); thus,
+    /// concatenating the version byte and the bytes of all components (skipping `null` values)
+    /// in the order listed above, followed by the nonce, yields the binary encoding of the proof.
+    pub fn to_json(&self) -> String {
+        let mut result = Map::new();
+        result.insert("version".into(), self.format_version.into());
+        result.insert("context".into(), encode(&self.context));
+        result.insert("commitments".into(), encode(&self.commitments));
+        result.insert("trace_queries".into(), encode(&self.trace_queries));
+        result.insert(
+            "aux_trace_queries".into(),
+            self.aux_trace_queries.as_ref().map_or(Value::Null, encode),
+        );
+        result.insert(
+            "constraint_queries".into(),
+            encode(&self.constraint_queries),
+        );
+        result.insert("ood_frame".into(), encode(&self.ood_frame));
+        result.insert("fri_proof".into(), encode(&self.fri_proof));
+        result.insert(
+            "pow_nonce".into(),
+            Value::String(to_hex(&self.pow_nonce.to_le_bytes())),
+        );
+        Value::Object(result).to_string()
+    }
+
+    /// Returns a STARK proof parsed from the JSON string produced by
+    /// [to_json()](StarkProof::to_json).
+    ///
+    /// Hex strings may be in either upper or lower case, and the `0x` prefix is optional.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * `source` is not a valid JSON object or any of the proof fields is missing.
+    /// * The proof format version is greater than [StarkProof::FORMAT_VERSION].
+    /// * Any of the fields is not a valid hex string, or a valid proof component could not be
+    ///   read from the bytes encoded by it.
+    /// * The auxiliary trace queries are present for a computation without an auxiliary trace
+    ///   segment, or are missing for a computation with one.
+    pub fn from_json(source: &str) -> Result<Self, DeserializationError> {
+        let value: Value = serde_json::from_str(source).map_err(|err| {
+            DeserializationError::InvalidValue(format!("invalid proof JSON: {}", err))
+        })?;
+        let fields = value.as_object().ok_or_else(|| {
+            DeserializationError::InvalidValue("proof JSON must be an object".into())
+        })?;
+
+        let version = get_field(fields, "version")?
+            .as_u64()
+            .ok_or_else(|| invalid_field("version", "expected a number"))?;
+        let format_version = check_format_version(version)?;
+
+        let context: Context = decode(fields, "context")?;
+        check_context_version(&context, format_version)?;
+        let aux_trace_queries = match get_field(fields, "aux_trace_queries")? {
+            Value::Null => None,
+            _ => Some(decode::<Queries>(fields, "aux_trace_queries")?),
+        };
+        if aux_trace_queries.is_some() != (context.aux_trace_width() > 0) {
+            return Err(invalid_field(
+                "aux_trace_queries",
+                "inconsistent with auxiliary trace width of the proof context",
+            ));
+        }
+
+        let nonce_bytes = parse_hex("pow_nonce", get_field(fields, "pow_nonce")?)?;
+        if nonce_bytes.len() != 8 {
+            return Err(invalid_field("pow_nonce", "expected 8 bytes"));
+        }
+        let mut pow_nonce = [0u8; 8];
+        pow_nonce.copy_from_slice(&nonce_bytes);
+
+        Ok(StarkProof {
+            context,
+            commitments: decode::<Commitments>(fields, "commitments")?,
+            trace_queries: decode::<Queries>(fields, "trace_queries")?,
+            aux_trace_queries,
+            constraint_queries: decode::<Queries>(fields, "constraint_queries")?,
+            ood_frame: decode::<OodFrame>(fields, "ood_frame")?,
+            fri_proof: decode::<FriProof>(fields, "fri_proof")?,
+            pow_nonce: u64::from_le_bytes(pow_nonce),
+            format_version,
+        })
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+/// Returns a JSON string containing the hex encoding of the serialized `component`.
+fn encode<T: Serializable>(component: &T) -> Value {
+    let mut bytes = Vec::new();
+    component.write_into(&mut bytes);
+    Value::String(to_hex(&bytes))
+}
+
+/// Reads a proof component from the hex string stored in the field `name`; the string must
+/// encode the component without any extra bytes.
+fn decode<T: Deserializable>(
+    fields: &Map<String, Value>,
+    name: &str,
+) -> Result<T, DeserializationError> {
+    let bytes = parse_hex(name, get_field(fields, name)?)?;
+    let mut source = SliceReader::new(&bytes);
+    let result = T::read_from(&mut source)?;
+    if source.has_more_bytes() {
+        return Err(DeserializationError::UnconsumedBytes);
+    }
+    Ok(result)
+}
+
+fn get_field<'a>(
+    fields: &'a Map<String, Value>,
+    name: &str,
+) -> Result<&'a Value, DeserializationError> {
+    fields
+        .get(name)
+        .ok_or_else(|| DeserializationError::InvalidValue(format!("missing proof field {}", name)))
+}
+
+fn invalid_field(name: &str, reason: &str) -> DeserializationError {
+    DeserializationError::InvalidValue(format!("invalid proof field {}: {}", name, reason))
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    let mut result = String::with_capacity(2 + bytes.len() * 2);
+    result.push_str("0x");
+    for &byte in bytes {
+        result.push(HEX_DIGITS[(byte >> 4) as usize] as char);
+        result.push(HEX_DIGITS[(byte & 0xf) as usize] as char);
+    }
+    result
+}
+
+fn parse_hex(name: &str, value: &Value) -> Result<Vec<u8>, DeserializationError> {
+    let hex = value
+        .as_str()
+        .ok_or_else(|| invalid_field(name, "expected a hex string"))?;
+    let hex = hex.strip_prefix("0x").unwrap_or(hex).as_bytes();
+    if hex.len() & 1 == 1 {
+        return Err(invalid_field(name, "odd number of hex digits"));
+    }
+    hex.chunks(2)
+        .map(|pair| match (hex_digit(pair[0]), hex_digit(pair[1])) {
+            (Some(high), Some(low)) => Ok((high << 4) | low),
+            _ => Err(invalid_field(name, "invalid hex digit")),
+        })
+        .collect()
+}
+
+fn hex_digit(c: u8) -> Option<u8> {
+    match c {
+        b'0'..=b'9' => Some(c - b'0'),
+        b'a'..=b'f' => Some(c - b'a' + 10),
+        b'A'..=b'F' => Some(c - b'A' + 10),
+        _ => None,
+    }
+}
@@ -5,12 +5,13 @@
 
 //! Contains STARK proof struct and associated components.
 
-use crate::{ProofOptions, TraceInfo};
+use crate::{MigrationError, ProofOptions, TraceInfo};
 use core::cmp;
 use fri::FriProof;
 use math::log2;
 use utils::{
-    collections::Vec, ByteReader, Deserializable, DeserializationError, Serializable, SliceReader,
+    collections::Vec, ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+    SliceReader,
 };
 
 mod context;
@@ -25,11 +26,35 @@ pub use queries::Queries;
 mod ood_frame;
 pub use ood_frame::OodFrame;
 
+mod tagged;
+pub use tagged::TaggedProof;
+
+#[cfg(feature = "serde_json")]
+mod json;
+
 // CONSTANTS
 // ================================================================================================
 
 const GRINDING_CONTRIBUTION_FLOOR: u32 = 80;
 
+/// Domain separation tag absorbed into the public coin before the trace commitment.
+pub const TRACE_COMMITMENT_TAG: [u8; 4] = *b"TRCE";
+
+/// Domain separation tag absorbed into the public coin before the commitment to the auxiliary
+/// trace segment.
+pub const AUX_TRACE_COMMITMENT_TAG: [u8; 4] = *b"AUXT";
+
+/// Domain separation tag absorbed into the public coin before the constraint commitment.
+pub const CONSTRAINT_COMMITMENT_TAG: [u8; 4] = *b"CNST";
+
+/// Domain separation tag absorbed into the public coin before the hash of each row of the
+/// out-of-domain trace frame.
+pub const OOD_FRAME_TAG: [u8; 4] = *b"OODF";
+
+/// Domain separation tag absorbed into the public coin before the hash of out-of-domain
+/// constraint composition polynomial evaluations.
+pub const OOD_EVALUATIONS_TAG: [u8; 4] = *b"OODE";
+
 // STARK PROOF
 // ================================================================================================
 /// A proof generated by Winterfell prover.
@@ -40,7 +65,12 @@ const GRINDING_CONTRIBUTION_FLOOR: u32 = 80;
 ///
 /// A proof can be serialized into a sequence of bytes using [to_bytes()](StarkProof::to_bytes)
 /// function, and deserialized from a sequence of bytes using [from_bytes()](StarkProof::from_bytes)
-/// function.
+/// function. Serialized proofs start with a single byte specifying the version of the proof
+/// format; proofs generated by the current prover are of version [StarkProof::FORMAT_VERSION],
+/// but proofs of earlier versions can still be read and verified. When `serde_json` feature is
+/// enabled, a proof can also be converted into a JSON object with hex-encoded components (e.g.,
+/// for passing it to verifiers written in other languages) using `to_json()` function, and parsed
+/// back using `from_json()` function.
 ///
 /// To estimate soundness of a proof (in bits), [security_level()](StarkProof::security_level)
 /// function can be used.
@@ -52,6 +82,10 @@ pub struct StarkProof {
     pub commitments: Commitments,
     /// Decommitments of extended execution trace values at positions queried by the verifier.
     pub trace_queries: Queries,
+    /// Decommitments of the extended auxiliary trace segment values at positions queried by the
+    /// verifier; this is set only for computations with an auxiliary trace segment, as
+    /// specified by the proof context.
+    pub aux_trace_queries: Option<Queries>,
     /// Decommitments of constraint composition polynomial evaluations at positions queried by
     /// the verifier.
     pub constraint_queries: Queries,
@@ -61,9 +95,23 @@ pub struct StarkProof {
     pub fri_proof: FriProof,
     /// Proof-of-work nonce for query seed grinding.
     pub pow_nonce: u64,
+    /// Version of the proof format in which this proof was generated; the version defines the
+    /// transcript via which the verifier derives its challenges.
+    pub format_version: u8,
 }
 
 impl StarkProof {
+    /// Version of the proof format used by the current prover.
+    ///
+    /// Proofs of earlier versions differ from the current proofs as follows:
+    /// * Version 1 proofs were not prefixed with a version byte, and their proof options were
+    ///   not terminated by the flags byte which specifies the domain offset and the FRI remainder
+    ///   encoding. Such proofs can be converted into the versioned layout using
+    ///   [migrate_v1_to_v2()](StarkProof::migrate_v1_to_v2) function.
+    /// * Version 1 and version 2 proofs were generated without domain separation tags in the
+    ///   proof transcript.
+    pub const FORMAT_VERSION: u8 = 3;
+
     /// Returns STARK protocol parameters used to generate this proof.
     pub fn options(&self) -> &ProofOptions {
         self.context.options()
@@ -110,36 +158,167 @@ impl StarkProof {
         }
     }
 
+    /// Returns the security level (in bits) actually achieved by this proof.
+    ///
+    /// Unlike [security_level()](StarkProof::security_level), which assumes that the number of
+    /// queried positions is equal to the number of queries specified in proof options, this
+    /// method uses the number of distinct positions at which the execution trace was queried
+    /// (query positions drawn by the verifier may repeat, in which case the repeated queries do
+    /// not contribute to security). The grinding factor specified in proof options is included
+    /// in query security as the verifier rejects proofs with insufficient proof-of-work.
+    ///
+    /// The soundness error is computed as a sum of the errors contributed by the size of the
+    /// field and by the queries, which results in a tighter bound than the one used by the
+    /// conjectured security level.
+    #[cfg(feature = "std")]
+    pub fn achieved_security_level(&self) -> f64 {
+        let options = self.context.options();
+
+        // compute max security we can get for a given field size
+        let field_size = self.context.num_modulus_bits() * options.field_extension().degree();
+        let field_security = (field_size - self.lde_domain_size().trailing_zeros()) as f64;
+
+        // compute security we get from distinct query positions
+        let security_per_query = log2(options.blowup_factor()) as f64;
+        let mut query_security = security_per_query * self.num_unique_queries() as f64;
+        if query_security >= GRINDING_CONTRIBUTION_FLOOR as f64 {
+            query_security += options.grinding_factor() as f64;
+        }
+
+        let soundness_error = 2f64.powf(-field_security) + 2f64.powf(-query_security);
+        let hash_fn_security = options.hash_fn().collision_resistance() as f64;
+        (-soundness_error.log2()).min(hash_fn_security)
+    }
+
+    /// Returns true if the transcript of this proof absorbs prover messages together with
+    /// domain separation tags; this is the case for all proofs of version 3 and later.
+    pub fn is_domain_separated(&self) -> bool {
+        self.format_version >= 3
+    }
+
+    /// Writes the proof context into the `target` in the same way as the prover which generated
+    /// this proof did when seeding its public coin.
+    ///
+    /// For proofs of version 1, proof options are written without the trailing flags byte.
+    pub fn write_context_seed_into(&self, target: &mut Vec<u8>) {
+        let mut context_bytes = self.context.to_bytes();
+        if self.format_version == 1 {
+            context_bytes.pop();
+        }
+        target.extend_from_slice(&context_bytes);
+    }
+
+    /// Returns the number of distinct positions at which the execution trace was queried.
+    pub fn num_unique_queries(&self) -> usize {
+        let values_per_query = self.trace_width() * self.context.field_modulus_bytes().len();
+        self.trace_queries.values_size() / values_per_query
+    }
+
     // SERIALIZATION / DESERIALIZATION
     // --------------------------------------------------------------------------------------------
 
     /// Serializes this proof into a vector of bytes.
+    ///
+    /// The first byte of the result is the version of the proof format in which this proof was
+    /// generated.
     pub fn to_bytes(&self) -> Vec<u8> {
         let mut result = Vec::new();
-        self.context.write_into(&mut result);
-        self.commitments.write_into(&mut result);
-        self.trace_queries.write_into(&mut result);
-        self.constraint_queries.write_into(&mut result);
-        self.ood_frame.write_into(&mut result);
-        self.fri_proof.write_into(&mut result);
-        result.extend_from_slice(&self.pow_nonce.to_le_bytes());
+        self.write_into(&mut result);
         result
     }
 
+    /// Serializes this proof and writes the resulting bytes into the `target`.
+    ///
+    /// The bytes written are the same as the bytes returned by
+    /// [to_bytes()](StarkProof::to_bytes). Passing a [CountingWriter](utils::CountingWriter) as
+    /// the `target` can be used to determine the size of the serialized proof without allocating
+    /// memory for it.
+    pub fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u8(self.format_version);
+        self.write_body_into(target);
+    }
+
     /// Returns a STARK proof read from the specified `source`.
     ///
     /// # Errors
-    /// Returns an error of a valid STARK proof could not be read from the specified `source`.
+    /// Returns an error of a valid STARK proof could not be read from the specified `source`, or
+    /// if the proof format version of the `source` is greater than [StarkProof::FORMAT_VERSION].
     pub fn from_bytes(source: &[u8]) -> Result<Self, DeserializationError> {
         let mut source = SliceReader::new(source);
+        let format_version = check_format_version(source.read_u8()? as u64)?;
+        Self::read_body_from(&mut source, format_version)
+    }
+
+    /// Converts a proof serialized in version 1 of the proof format into the versioned layout.
+    ///
+    /// Version 1 proofs consist of the same components as the current proofs but do not start
+    /// with a version byte, and their proof options do not end with the flags byte; the flags
+    /// byte of migrated proofs specifies the default domain offset and FRI remainder encoding,
+    /// which are the ones used by all version 1 proofs. Migrated proofs keep version 1 in their
+    /// header, so that the verifier replays the transcript with which they were generated.
+    ///
+    /// # Errors
+    /// Returns an error if a valid version 1 proof could not be read from `bytes`.
+    #[deprecated(
+        since = "0.3.0",
+        note = "version 1 proof format is deprecated; migrated proofs should be stored in the versioned layout"
+    )]
+    pub fn migrate_v1_to_v2(bytes: &[u8]) -> Result<Vec<u8>, MigrationError> {
+        // insert the default options flags after the proof context
+        let context_size = v1_context_size(bytes).map_err(MigrationError::InvalidSourceProof)?;
+        let mut body = Vec::with_capacity(bytes.len() + 1);
+        body.extend_from_slice(&bytes[..context_size]);
+        body.push(0);
+        body.extend_from_slice(&bytes[context_size..]);
+
+        let mut source = SliceReader::new(&body);
+        let proof =
+            Self::read_body_from(&mut source, 1).map_err(MigrationError::InvalidSourceProof)?;
+        Ok(proof.to_bytes())
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Writes all components of this proof into the `target` without the version prefix.
+    fn write_body_into<W: ByteWriter>(&self, target: &mut W) {
+        self.context.write_into(target);
+        self.commitments.write_into(target);
+        self.trace_queries.write_into(target);
+        if let Some(aux_trace_queries) = &self.aux_trace_queries {
+            aux_trace_queries.write_into(target);
+        }
+        self.constraint_queries.write_into(target);
+        self.ood_frame.write_into(target);
+        self.fri_proof.write_into(target);
+        target.write_u64(self.pow_nonce);
+    }
+
+    /// Reads all components of a proof of the specified `format_version` from the `source`; the
+    /// `source` must not contain any bytes after the proof.
+    fn read_body_from(
+        source: &mut SliceReader,
+        format_version: u8,
+    ) -> Result<Self, DeserializationError> {
+        let context = Context::read_from(source)?;
+        check_context_version(&context, format_version)?;
+        let commitments = Commitments::read_from(source)?;
+        let trace_queries = Queries::read_from(source)?;
+        let aux_trace_queries = if context.aux_trace_width() > 0 {
+            Some(Queries::read_from(source)?)
+        } else {
+            None
+        };
         let proof = StarkProof {
-            context: Context::read_from(&mut source)?,
-            commitments: Commitments::read_from(&mut source)?,
-            trace_queries: Queries::read_from(&mut source)?,
-            constraint_queries: Queries::read_from(&mut source)?,
-            ood_frame: OodFrame::read_from(&mut source)?,
-            fri_proof: FriProof::read_from(&mut source)?,
+            context,
+            commitments,
+            trace_queries,
+            aux_trace_queries,
+            constraint_queries: Queries::read_from(source)?,
+            ood_frame: OodFrame::read_from(source)?,
+            fri_proof: FriProof::read_from(source)?,
             pow_nonce: source.read_u64()?,
+            format_version,
         };
         if source.has_more_bytes() {
             return Err(DeserializationError::UnconsumedBytes);
@@ -151,8 +330,56 @@ impl StarkProof {
 // HELPER FUNCTIONS
 // ================================================================================================
 
+/// Number of bytes in serialized proof options of version 1 proofs.
+const V1_PROOF_OPTIONS_SIZE: usize = 7;
+
+/// Returns the number of bytes occupied by the proof context at the start of a version 1 proof.
+fn v1_context_size(bytes: &[u8]) -> Result<usize, DeserializationError> {
+    let mut source = SliceReader::new(bytes);
+    source.read_u8_vec(2)?; // trace width and trace length
+    let num_meta_bytes = source.read_u16()? as usize;
+    source.read_u8_vec(num_meta_bytes)?;
+    let num_modulus_bytes = source.read_u8()? as usize;
+    source.read_u8_vec(num_modulus_bytes)?;
+    source.read_u8_vec(V1_PROOF_OPTIONS_SIZE)?;
+
+    Ok(2 + 2 + num_meta_bytes + 1 + num_modulus_bytes + V1_PROOF_OPTIONS_SIZE)
+}
+
+/// Returns the specified format `version` if proofs of this version can be read by this version
+/// of the library, and an error otherwise.
+pub(crate) fn check_format_version(version: u64) -> Result<u8, DeserializationError> {
+    if version == 0 || version > StarkProof::FORMAT_VERSION as u64 {
+        return Err(DeserializationError::InvalidValue(format!(
+            "unsupported proof format version: expected at most {}, but was {}",
+            StarkProof::FORMAT_VERSION,
+            version
+        )));
+    }
+    Ok(version as u8)
+}
+
+/// Returns an error if the proof `context` could not have been produced by a prover of the
+/// specified format `version`.
+///
+/// Version 1 proofs could not specify a domain offset or a FRI remainder encoding; thus, the
+/// flags byte of their proof options must be 0.
+pub(crate) fn check_context_version(
+    context: &Context,
+    version: u8,
+) -> Result<(), DeserializationError> {
+    if version == 1 && context.to_bytes().last() != Some(&0) {
+        return Err(DeserializationError::InvalidValue(
+            "proof options of version 1 proofs cannot specify a domain offset or a FRI \
+            remainder encoding"
+                .into(),
+        ));
+    }
+    Ok(())
+}
+
 /// Computes conjectured security level for the specified proof parameters.
-fn get_conjectured_security(
+pub(crate) fn get_conjectured_security(
     options: &ProofOptions,
     base_field_bits: u32,
     lde_domain_size: u64,
@@ -10,6 +10,14 @@ use utils::{
     SliceReader,
 };
 
+// CONSTANTS
+// ================================================================================================
+
+/// Bit of the serialized trace row length set when the number of additional trace rows (i.e.,
+/// rows beyond the first two) follows the row length; the remaining bits of the serialized row
+/// length hold the number of bytes in each row.
+const MULTI_ROW_FLAG: u16 = 0b1000_0000_0000_0000;
+
 // OUT-OF-DOMAIN FRAME
 // ================================================================================================
 /// Trace and constraint polynomial evaluations at an out-of-domain point.
@@ -17,16 +25,22 @@ use utils::{
 /// This struct contains the following evaluations:
 /// * Evaluations of all trace polynomials at *z*.
 /// * Evaluations of all trace polynomials at *z * g*.
+/// * For evaluation frames with more than two rows, evaluations of all trace polynomials at
+///   *z * g^i* for each of the additional rows *i*.
 /// * Evaluations of constraint composition column polynomials at *z*.
 ///
 /// where *z* is an out-of-domain point and *g* is the generator of the trace domain.
 ///
 /// Internally, the evaluations are stored as a sequence of bytes. Thus, to retrieve the
-/// evaluations, [parse()](OodFrame::parse) function should be used.
+/// evaluations, [parse_trace_z1()](OodFrame::parse_trace_z1),
+/// [parse_trace_z2()](OodFrame::parse_trace_z2), [parse_trace_row()](OodFrame::parse_trace_row),
+/// and [parse_evaluations()](OodFrame::parse_evaluations) functions should be used. Each of these
+/// functions deserializes only the requested component of the frame.
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
 pub struct OodFrame {
     trace_at_z1: Vec<u8>,
     trace_at_z2: Vec<u8>,
+    trace_at_zn: Vec<Vec<u8>>,
     evaluations: Vec<u8>,
 }
 
@@ -63,6 +77,13 @@ impl OodFrame {
         );
         frame.current().write_into(&mut self.trace_at_z1);
         frame.next().write_into(&mut self.trace_at_z2);
+        self.trace_at_zn = (2..frame.size())
+            .map(|i| {
+                let mut row = Vec::new();
+                frame.row(i).write_into(&mut row);
+                row
+            })
+            .collect();
     }
 
     /// Updates constraint evaluation portion of this out-of-domain frame.
@@ -83,8 +104,121 @@ impl OodFrame {
         evaluations.write_into(&mut self.evaluations)
     }
 
-    // PARSER
+    // PARSERS
     // --------------------------------------------------------------------------------------------
+    /// Returns evaluations of all trace polynomials at the out-of-domain point *z*.
+    ///
+    /// # Panics
+    /// Panics if `trace_width` is equal to zero.
+    ///
+    /// # Errors
+    /// Returns an error if exactly `trace_width` elements could not be parsed from the internal
+    /// bytes.
+    pub fn parse_trace_z1<E: FieldElement>(
+        &self,
+        trace_width: usize,
+    ) -> Result<Vec<E>, DeserializationError> {
+        assert!(trace_width > 0, "trace width cannot be zero");
+        parse_row(&self.trace_at_z1, trace_width)
+    }
+
+    /// Returns evaluations of all trace polynomials at *z * g*, where *z* is the out-of-domain
+    /// point and *g* is the generator of the trace domain.
+    ///
+    /// # Panics
+    /// Panics if `trace_width` is equal to zero.
+    ///
+    /// # Errors
+    /// Returns an error if exactly `trace_width` elements could not be parsed from the internal
+    /// bytes.
+    pub fn parse_trace_z2<E: FieldElement>(
+        &self,
+        trace_width: usize,
+    ) -> Result<Vec<E>, DeserializationError> {
+        assert!(trace_width > 0, "trace width cannot be zero");
+        parse_row(&self.trace_at_z2, trace_width)
+    }
+
+    /// Returns the number of trace rows in this out-of-domain frame.
+    pub fn num_trace_rows(&self) -> usize {
+        2 + self.trace_at_zn.len()
+    }
+
+    /// Returns evaluations of all trace polynomials at *z * g^offset*, where *z* is the
+    /// out-of-domain point and *g* is the generator of the trace domain.
+    ///
+    /// Offsets 0 and 1 refer to the same rows as [parse_trace_z1()](OodFrame::parse_trace_z1)
+    /// and [parse_trace_z2()](OodFrame::parse_trace_z2) respectively.
+    ///
+    /// # Panics
+    /// Panics if `trace_width` is equal to zero.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * `offset` is not smaller than the number of trace rows in this frame.
+    /// * Exactly `trace_width` elements could not be parsed from the internal bytes.
+    pub fn parse_trace_row<E: FieldElement>(
+        &self,
+        offset: usize,
+        trace_width: usize,
+    ) -> Result<Vec<E>, DeserializationError> {
+        assert!(trace_width > 0, "trace width cannot be zero");
+        let row = match offset {
+            0 => &self.trace_at_z1,
+            1 => &self.trace_at_z2,
+            _ => self.trace_at_zn.get(offset - 2).ok_or_else(|| {
+                DeserializationError::InvalidValue(format!(
+                    "trace row offset must be smaller than {}, but was {}",
+                    self.num_trace_rows(),
+                    offset
+                ))
+            })?,
+        };
+        parse_row(row, trace_width)
+    }
+
+    /// Returns evaluations of constraint composition column polynomials at the out-of-domain
+    /// point *z*.
+    ///
+    /// # Panics
+    /// Panics if `num_evaluations` is equal to zero.
+    ///
+    /// # Errors
+    /// Returns an error if exactly `num_evaluations` elements could not be parsed from the
+    /// internal bytes.
+    pub fn parse_evaluations<E: FieldElement>(
+        &self,
+        num_evaluations: usize,
+    ) -> Result<Vec<E>, DeserializationError> {
+        assert!(num_evaluations > 0, "number of evaluations cannot be zero");
+        parse_row(&self.evaluations, num_evaluations)
+    }
+
+    /// Returns an evaluation frame and a vector of out-of-domain constraint evaluations contained
+    /// in `self` without consuming `self`.
+    ///
+    /// # Panics
+    /// Panics if either `trace_width` or `num_evaluations` are equal to zero.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * A valid [EvaluationFrame] for the specified `trace_width` could not be parsed from the
+    ///   internal bytes.
+    /// * A vector of evaluations specified by `num_evaluations` could not be parsed from the
+    ///   internal bytes.
+    /// * Any unconsumed bytes remained after the parsing was complete.
+    pub fn parse_borrowed<E: FieldElement>(
+        &self,
+        trace_width: usize,
+        num_evaluations: usize,
+    ) -> Result<(EvaluationFrame<E>, Vec<E>), DeserializationError> {
+        let rows = (0..self.num_trace_rows())
+            .map(|i| self.parse_trace_row(i, trace_width))
+            .collect::<Result<Vec<_>, _>>()?;
+        let evaluations = self.parse_evaluations(num_evaluations)?;
+        Ok((EvaluationFrame::from_row_list(rows), evaluations))
+    }
+
     /// Returns an evaluation frame and a vector of out-of-domain constraint evaluations contained
     /// in `self`.
     ///
@@ -98,43 +232,47 @@ impl OodFrame {
     /// * A vector of evaluations specified by `num_evaluations` could not be parsed from the
     ///   internal bytes.
     /// * Any unconsumed bytes remained after the parsing was complete.
+    #[deprecated(
+        since = "0.3.0",
+        note = "use parse_borrowed(), or parse_trace_z1(), parse_trace_z2(), and parse_evaluations() instead"
+    )]
     pub fn parse<E: FieldElement>(
         self,
         trace_width: usize,
         num_evaluations: usize,
     ) -> Result<(EvaluationFrame<E>, Vec<E>), DeserializationError> {
-        assert!(trace_width > 0, "trace width cannot be zero");
-        assert!(num_evaluations > 0, "number of evaluations cannot be zero");
-
-        let mut reader = SliceReader::new(&self.trace_at_z1);
-        let current = E::read_batch_from(&mut reader, trace_width)?;
-        if reader.has_more_bytes() {
-            return Err(DeserializationError::UnconsumedBytes);
-        }
-
-        let mut reader = SliceReader::new(&self.trace_at_z2);
-        let next = E::read_batch_from(&mut reader, trace_width)?;
-        if reader.has_more_bytes() {
-            return Err(DeserializationError::UnconsumedBytes);
-        }
-
-        let mut reader = SliceReader::new(&self.evaluations);
-        let evaluations = E::read_batch_from(&mut reader, num_evaluations)?;
-        if reader.has_more_bytes() {
-            return Err(DeserializationError::UnconsumedBytes);
-        }
-
-        Ok((EvaluationFrame::from_rows(current, next), evaluations))
+        self.parse_borrowed(trace_width, num_evaluations)
     }
 }
 
 impl Serializable for OodFrame {
     /// Serializes `self` and writes the resulting bytes into the `target`.
     fn write_into<W: ByteWriter>(&self, target: &mut W) {
-        // write trace rows (both rows have the same number of bytes)
-        target.write_u16(self.trace_at_z1.len() as u16);
+        // write trace rows (all rows have the same number of bytes); the number of additional
+        // rows is written only for frames with more than two rows so that two-row frames are
+        // serialized in the same way as before multi-row frames were supported
+        let row_bytes = self.trace_at_z1.len() as u16;
+        assert!(
+            row_bytes & MULTI_ROW_FLAG == 0,
+            "trace row cannot be longer than {} bytes",
+            MULTI_ROW_FLAG - 1
+        );
+        if self.trace_at_zn.is_empty() {
+            target.write_u16(row_bytes);
+        } else {
+            assert!(
+                self.trace_at_zn.len() <= u8::MAX as usize,
+                "number of additional trace rows cannot be greater than {}",
+                u8::MAX
+            );
+            target.write_u16(row_bytes | MULTI_ROW_FLAG);
+            target.write_u8(self.trace_at_zn.len() as u8);
+        }
         target.write_u8_slice(&self.trace_at_z1);
         target.write_u8_slice(&self.trace_at_z2);
+        for row in self.trace_at_zn.iter() {
+            target.write_u8_slice(row);
+        }
 
         // write constraint evaluations row
         target.write_u16(self.evaluations.len() as u16);
@@ -149,9 +287,18 @@ impl Deserializable for OodFrame {
     /// Returns an error of a valid OOD frame could not be read from the specified `source`.
     fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
         // read trace rows
-        let trace_row_bytes = source.read_u16()? as usize;
+        let trace_row_bytes = source.read_u16()?;
+        let num_additional_rows = if trace_row_bytes & MULTI_ROW_FLAG != 0 {
+            source.read_u8()? as usize
+        } else {
+            0
+        };
+        let trace_row_bytes = (trace_row_bytes & !MULTI_ROW_FLAG) as usize;
         let trace_at_z1 = source.read_u8_vec(trace_row_bytes)?;
         let trace_at_z2 = source.read_u8_vec(trace_row_bytes)?;
+        let trace_at_zn = (0..num_additional_rows)
+            .map(|_| source.read_u8_vec(trace_row_bytes))
+            .collect::<Result<Vec<_>, _>>()?;
 
         // read constraint evaluations row
         let constraint_row_bytes = source.read_u16()? as usize;
@@ -160,7 +307,90 @@ impl Deserializable for OodFrame {
         Ok(OodFrame {
             trace_at_z1,
             trace_at_z2,
+            trace_at_zn,
             evaluations,
         })
     }
 }
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Parses exactly `num_elements` field elements from the provided bytes.
+fn parse_row<E: FieldElement>(
+    bytes: &[u8],
+    num_elements: usize,
+) -> Result<Vec<E>, DeserializationError> {
+    let mut reader = SliceReader::new(bytes);
+    let result = E::read_batch_from(&mut reader, num_elements)?;
+    if reader.has_more_bytes() {
+        return Err(DeserializationError::UnconsumedBytes);
+    }
+    Ok(result)
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use math::fields::f128::BaseElement;
+
+    #[test]
+    fn ood_frame_parse_borrowed() {
+        let current = vec![BaseElement::new(1), BaseElement::new(2)];
+        let next = vec![BaseElement::new(3), BaseElement::new(4)];
+        let evaluations = vec![
+            BaseElement::new(5),
+            BaseElement::new(6),
+            BaseElement::new(7),
+        ];
+        let frame = EvaluationFrame::from_rows(current.clone(), next.clone());
+        let ood_frame = OodFrame::new(frame, evaluations.clone());
+
+        // the frame can be parsed multiple times
+        for _ in 0..2 {
+            let (frame, parsed) = ood_frame.parse_borrowed::<BaseElement>(2, 3).unwrap();
+            assert_eq!(current, frame.current());
+            assert_eq!(next, frame.next());
+            assert_eq!(evaluations, parsed);
+        }
+
+        assert_eq!(
+            Err(DeserializationError::UnconsumedBytes),
+            ood_frame.parse_borrowed::<BaseElement>(1, 3).map(|_| ())
+        );
+    }
+
+    #[test]
+    fn ood_frame_multi_row() {
+        let rows = (0..4u128)
+            .map(|i| vec![BaseElement::new(2 * i), BaseElement::new(2 * i + 1)])
+            .collect::<Vec<_>>();
+        let evaluations = vec![BaseElement::new(8)];
+        let ood_frame = OodFrame::new(EvaluationFrame::from_row_list(rows.clone()), evaluations);
+        assert_eq!(4, ood_frame.num_trace_rows());
+        assert_eq!(
+            rows[2],
+            ood_frame.parse_trace_row::<BaseElement>(2, 2).unwrap()
+        );
+        assert!(ood_frame.parse_trace_row::<BaseElement>(4, 2).is_err());
+
+        // multi-row frames survive a serialization round trip
+        let bytes = ood_frame.to_bytes();
+        let parsed = OodFrame::read_from(&mut SliceReader::new(&bytes)).unwrap();
+        assert_eq!(ood_frame, parsed);
+        let (frame, _) = parsed.parse_borrowed::<BaseElement>(2, 1).unwrap();
+        for (i, row) in rows.iter().enumerate() {
+            assert_eq!(row.as_slice(), frame.row(i));
+        }
+
+        // two-row frames are serialized without the number of additional rows
+        let two_rows = OodFrame::new(
+            EvaluationFrame::from_rows(rows[0].clone(), rows[1].clone()),
+            vec![BaseElement::new(8)],
+        );
+        assert_eq!(bytes.len() - 1 - 2 * 32, two_rows.to_bytes().len());
+    }
+}
@@ -78,6 +78,14 @@ impl Queries {
         Queries { paths, values }
     }
 
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of bytes occupied by query values.
+    pub fn values_size(&self) -> usize {
+        self.values.len()
+    }
+
     // PARSER
     // --------------------------------------------------------------------------------------------
     /// Convert internally stored bytes into a set of query values and the corresponding Merkle
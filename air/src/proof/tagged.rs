@@ -0,0 +1,81 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::StarkProof;
+use crate::TaggedAir;
+use utils::{
+    collections::Vec,
+    string::{String, ToString},
+    ByteReader, DeserializationError, SliceReader,
+};
+
+// TAGGED PROOF
+// ================================================================================================
+/// A [StarkProof] accompanied by a tag of the AIR against which the proof should be verified.
+///
+/// When proofs for several different computations are handled by the same application, the tag
+/// makes it possible to select the right AIR for verification without relying on any
+/// out-of-band information (see [TaggedAir]).
+///
+/// A tagged proof is serialized as a single byte specifying the length of the tag, followed by
+/// the UTF-8 encoded tag, followed by the serialized proof.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct TaggedProof {
+    /// Tag of the AIR against which the proof should be verified.
+    pub air_tag: String,
+    /// The proof itself.
+    pub proof: StarkProof,
+}
+
+impl TaggedProof {
+    /// Max number of bytes in an AIR tag.
+    pub const MAX_TAG_LENGTH: usize = u8::MAX as usize;
+
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns the specified `proof` tagged with the tag of the AIR specified by type parameter
+    /// `A`.
+    pub fn new<A: TaggedAir>(proof: StarkProof) -> Self {
+        TaggedProof {
+            air_tag: A::AIR_TAG.to_string(),
+            proof,
+        }
+    }
+
+    // SERIALIZATION / DESERIALIZATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Serializes this tagged proof into a vector of bytes.
+    ///
+    /// # Panics
+    /// Panics if the AIR tag is longer than [TaggedProof::MAX_TAG_LENGTH] bytes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        assert!(
+            self.air_tag.len() <= Self::MAX_TAG_LENGTH,
+            "AIR tag cannot be longer than {} bytes, but was {} bytes",
+            Self::MAX_TAG_LENGTH,
+            self.air_tag.len()
+        );
+        let mut result = vec![self.air_tag.len() as u8];
+        result.extend_from_slice(self.air_tag.as_bytes());
+        result.extend_from_slice(&self.proof.to_bytes());
+        result
+    }
+
+    /// Returns a tagged proof read from the specified `source`.
+    ///
+    /// # Errors
+    /// Returns an error if the AIR tag read from the `source` is not a valid UTF-8 string, or if
+    /// a valid STARK proof could not be read from the remainder of the `source`.
+    pub fn from_bytes(source: &[u8]) -> Result<Self, DeserializationError> {
+        let mut reader = SliceReader::new(source);
+        let tag_length = reader.read_u8()? as usize;
+        let air_tag = String::from_utf8(reader.read_u8_vec(tag_length)?).map_err(|err| {
+            DeserializationError::InvalidValue(format!("invalid AIR tag: {}", err))
+        })?;
+        let proof = StarkProof::from_bytes(&source[tag_length + 1..])?;
+        Ok(TaggedProof { air_tag, proof })
+    }
+}
@@ -0,0 +1,103 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use math::{fields::f128, FieldElement};
+use rand_utils::rand_vector;
+use std::time::Duration;
+use utils::Serializable;
+use winter_crypto::{
+    hashers::{Blake3_192, Blake3_256, Rp62_248, Rp64_256, Sha3_256},
+    ElementHasher, MerkleTree,
+};
+
+/// Sizes of the hashed data sets (for Merkle trees, the number of leaves in the tree).
+const SIZES: [usize; 3] = [1 << 16, 1 << 20, 1 << 24];
+
+/// Number of evaluations hashed into a single leaf of a FRI layer commitment; this is the
+/// default folding factor used by the examples.
+const FRI_FOLDING_FACTOR: usize = 8;
+
+// BENCHMARKS
+// ================================================================================================
+
+fn hash_throughput(c: &mut Criterion) {
+    bench_hasher::<Blake3_256<f128::BaseElement>>(c, "blake3_256");
+    bench_hasher::<Blake3_192<f128::BaseElement>>(c, "blake3_192");
+    bench_hasher::<Sha3_256<f128::BaseElement>>(c, "sha3_256");
+    bench_hasher::<Rp62_248>(c, "rp62_248");
+    bench_hasher::<Rp64_256>(c, "rp64_256");
+}
+
+/// Measures throughput of the hasher `H` for:
+/// * hashing individual field elements,
+/// * building Merkle trees from digests,
+/// * committing to FRI layers (hashing groups of evaluations and building a Merkle tree over the
+///   resulting digests).
+///
+/// Throughput is reported in terms of the number of input bytes processed per second.
+fn bench_hasher<H: ElementHasher>(c: &mut Criterion, name: &str) {
+    let mut group = c.benchmark_group(format!("hash_throughput/{}", name));
+    group.sample_size(10);
+    group.measurement_time(Duration::from_secs(10));
+
+    let element_size = H::BaseField::ELEMENT_BYTES;
+    let digest_size = H::hash(&[0]).to_bytes().len();
+
+    for &size in SIZES.iter() {
+        let elements: Vec<H::BaseField> = rand_vector(size);
+
+        // hash each element individually
+        group.throughput(Throughput::Bytes((size * element_size) as u64));
+        group.bench_with_input(
+            BenchmarkId::new("single_element", size),
+            &elements,
+            |bench, elements| bench.iter(|| hash_leaves::<H>(elements)),
+        );
+
+        // build a Merkle tree with the element digests as leaves
+        let leaves = hash_leaves::<H>(&elements);
+        group.throughput(Throughput::Bytes((size * digest_size) as u64));
+        group.bench_with_input(
+            BenchmarkId::new("merkle_tree", size),
+            &leaves,
+            |bench, leaves| bench.iter(|| MerkleTree::<H>::new(leaves.clone()).unwrap()),
+        );
+
+        // commit to a FRI layer of the specified size
+        group.throughput(Throughput::Bytes((size * element_size) as u64));
+        group.bench_with_input(
+            BenchmarkId::new("fri_layer_commitment", size),
+            &elements,
+            |bench, elements| bench.iter(|| commit_fri_layer::<H>(elements)),
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(hash_throughput_group, hash_throughput);
+criterion_main!(hash_throughput_group);
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns a vector containing a hash of each of the provided elements.
+fn hash_leaves<H: ElementHasher>(elements: &[H::BaseField]) -> Vec<H::Digest> {
+    elements
+        .iter()
+        .map(|element| H::hash_elements(&[*element]))
+        .collect()
+}
+
+/// Hashes each group of [FRI_FOLDING_FACTOR] consecutive evaluations into a single digest and
+/// builds a Merkle tree from the resulting digests.
+fn commit_fri_layer<H: ElementHasher>(evaluations: &[H::BaseField]) -> MerkleTree<H> {
+    let leaves = evaluations
+        .chunks(FRI_FOLDING_FACTOR)
+        .map(|chunk| H::hash_elements(chunk))
+        .collect();
+    MerkleTree::new(leaves).unwrap()
+}
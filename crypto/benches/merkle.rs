@@ -7,7 +7,7 @@ use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
 use math::fields::f128::BaseElement;
 use rand_utils::rand_value;
 use utils::uninit_vector;
-use winter_crypto::{build_merkle_nodes, concurrent, hashers::Blake3_256, Hasher};
+use winter_crypto::{build_merkle_nodes, concurrent, hashers::Blake3_256, Hasher, MerkleTree};
 
 type Blake3 = Blake3_256<BaseElement>;
 type Blake3Digest = <Blake3 as Hasher>::Digest;
@@ -34,5 +34,46 @@ pub fn merkle_tree_construction(c: &mut Criterion) {
     }
 }
 
-criterion_group!(merkle_group, merkle_tree_construction,);
+pub fn merkle_path_verification(c: &mut Criterion) {
+    let mut merkle_group = c.benchmark_group("merkle path verification");
+
+    static NUM_QUERIES: [usize; 3] = [32, 64, 128];
+    let num_leaves = 1 << 22;
+
+    let data: Vec<Blake3Digest> = (0..num_leaves)
+        .map(|_| Blake3::hash(&rand_value::<u128>().to_le_bytes()))
+        .collect();
+    let tree = MerkleTree::<Blake3>::new(data).unwrap();
+
+    for &num_queries in &NUM_QUERIES {
+        let indexes: Vec<usize> = (0..num_queries)
+            .map(|_| rand_value::<u64>() as usize % num_leaves)
+            .collect();
+        let paths: Vec<Vec<Blake3Digest>> =
+            indexes.iter().map(|&i| tree.prove(i).unwrap()).collect();
+
+        merkle_group.bench_function(BenchmarkId::new("sequential", num_queries), |b| {
+            b.iter(|| {
+                for (&index, path) in indexes.iter().zip(paths.iter()) {
+                    MerkleTree::<Blake3>::verify(*tree.root(), index, path).unwrap();
+                }
+            })
+        });
+        merkle_group.bench_function(BenchmarkId::new("concurrent", num_queries), |b| {
+            b.iter(|| {
+                assert!(concurrent::verify_paths::<Blake3>(
+                    *tree.root(),
+                    &indexes,
+                    &paths
+                ))
+            })
+        });
+    }
+}
+
+criterion_group!(
+    merkle_group,
+    merkle_tree_construction,
+    merkle_path_verification
+);
 criterion_main!(merkle_group);
@@ -11,7 +11,7 @@ mod blake;
 pub use blake::{Blake3_192, Blake3_256};
 
 mod sha;
-pub use sha::Sha3_256;
+pub use sha::{Keccak256, Sha3_256};
 
 mod rescue;
 pub use rescue::{Rp62_248, Rp64_256};
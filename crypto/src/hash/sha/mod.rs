@@ -9,6 +9,9 @@ use math::{FieldElement, StarkField};
 use sha3::Digest;
 use utils::ByteWriter;
 
+#[cfg(test)]
+mod tests;
+
 // SHA3 WITH 256-BIT OUTPUT
 // ================================================================================================
 
@@ -47,7 +50,65 @@ impl<B: StarkField> ElementHasher for Sha3_256<B> {
         } else {
             // when elements' internal and canonical representations differ, we need to serialize
             // them before hashing
-            let mut hasher = ShaHasher::new();
+            let mut hasher = ShaHasher::<sha3::Sha3_256>::new();
+            hasher.write(elements);
+            ByteDigest(hasher.finalize())
+        }
+    }
+}
+
+// KECCAK WITH 256-BIT OUTPUT
+// ================================================================================================
+
+/// Implementation of the [Hasher](super::Hasher) trait for Keccak hash function with 256-bit
+/// output.
+///
+/// This is the original Keccak-256 function (as used by Ethereum's `KECCAK256` opcode), which
+/// differs from SHA3-256 in its padding. Inputs of this hasher are encoded as follows:
+/// * [merge()](Hasher::merge) hashes the 64-byte concatenation of the two digests.
+/// * [merge_with_int()](Hasher::merge_with_int) hashes the 32-byte seed followed by the value
+///   encoded as 8 little-endian bytes.
+/// * [hash_elements()](ElementHasher::hash_elements) hashes the concatenation of canonical
+///   encodings of the elements, where each base field element is encoded as
+///   `B::ELEMENT_BYTES` little-endian bytes of its integer value, and elements in extension
+///   fields are encoded as a concatenation of their base field coefficients.
+///
+/// Thus, a verifier which has access only to Keccak-256 (e.g., a smart contract) can recompute
+/// all digests used in a proof.
+pub struct Keccak256<B: StarkField>(PhantomData<B>);
+
+impl<B: StarkField> Hasher for Keccak256<B> {
+    type Digest = ByteDigest<32>;
+
+    fn hash(bytes: &[u8]) -> Self::Digest {
+        ByteDigest(sha3::Keccak256::digest(bytes).into())
+    }
+
+    fn merge(values: &[Self::Digest; 2]) -> Self::Digest {
+        ByteDigest(sha3::Keccak256::digest(ByteDigest::digests_as_bytes(values)).into())
+    }
+
+    fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest {
+        let mut data = [0; 40];
+        data[..32].copy_from_slice(&seed.0);
+        data[32..].copy_from_slice(&value.to_le_bytes());
+        ByteDigest(sha3::Keccak256::digest(&data).into())
+    }
+}
+
+impl<B: StarkField> ElementHasher for Keccak256<B> {
+    type BaseField = B;
+
+    fn hash_elements<E: FieldElement<BaseField = Self::BaseField>>(elements: &[E]) -> Self::Digest {
+        if B::IS_CANONICAL {
+            // when element's internal and canonical representations are the same, we can hash
+            // element bytes directly
+            let bytes = E::elements_as_bytes(elements);
+            ByteDigest(sha3::Keccak256::digest(bytes).into())
+        } else {
+            // when elements' internal and canonical representations differ, we need to serialize
+            // them before hashing
+            let mut hasher = ShaHasher::<sha3::Keccak256>::new();
             hasher.write(elements);
             ByteDigest(hasher.finalize())
         }
@@ -57,12 +118,12 @@ impl<B: StarkField> ElementHasher for Sha3_256<B> {
 // SHA HASHER
 // ================================================================================================
 
-/// Wrapper around SHA3 hasher to implement [ByteWriter] trait for it.
-struct ShaHasher(sha3::Sha3_256);
+/// Wrapper around SHA3 and Keccak hashers to implement [ByteWriter] trait for them.
+struct ShaHasher<D: Digest>(D);
 
-impl ShaHasher {
+impl<D: Digest<OutputSize = sha3::digest::consts::U32>> ShaHasher<D> {
     pub fn new() -> Self {
-        Self(sha3::Sha3_256::new())
+        Self(D::new())
     }
 
     pub fn finalize(self) -> [u8; 32] {
@@ -70,7 +131,7 @@ impl ShaHasher {
     }
 }
 
-impl ByteWriter for ShaHasher {
+impl<D: Digest> ByteWriter for ShaHasher<D> {
     fn write_u8(&mut self, value: u8) {
         self.0.update(&[value]);
     }
@@ -0,0 +1,72 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{ElementHasher, Hasher, Keccak256, Sha3_256};
+use crate::Digest;
+use math::{
+    fields::{f62, f64, QuadExtension},
+    FieldElement,
+};
+use rand_utils::rand_array;
+use utils::{collections::Vec, string::String};
+
+#[test]
+fn keccak_hash() {
+    // Keccak-256 of the empty string (as computed by Ethereum's KECCAK256 opcode) is different
+    // from SHA3-256 of the empty string
+    let expected = "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470";
+    let result = Keccak256::<f62::BaseElement>::hash(&[]);
+    assert_eq!(expected, hex_string(&result.as_bytes()));
+    assert_ne!(Sha3_256::<f62::BaseElement>::hash(&[]), result);
+}
+
+#[test]
+fn keccak_merge_with_int() {
+    let seed = Keccak256::<f62::BaseElement>::hash(&[1, 2, 3]);
+    let mut data = seed.as_bytes().to_vec();
+    data.extend_from_slice(&5u64.to_le_bytes());
+    assert_eq!(
+        Keccak256::<f62::BaseElement>::hash(&data),
+        Keccak256::<f62::BaseElement>::merge_with_int(seed, 5)
+    );
+}
+
+#[test]
+fn keccak_hash_elements_canonical() {
+    // f62 field uses Montgomery representation, while f64 field does not; in both cases, the
+    // elements are hashed as the concatenation of their canonical encodings
+    let elements: [f62::BaseElement; 4] = rand_array();
+    assert_eq!(
+        Keccak256::<f62::BaseElement>::hash(&to_bytes(&elements)),
+        Keccak256::hash_elements(&elements)
+    );
+
+    let elements: [f64::BaseElement; 4] = rand_array();
+    assert_eq!(
+        Keccak256::<f64::BaseElement>::hash(&to_bytes(&elements)),
+        Keccak256::hash_elements(&elements)
+    );
+
+    let elements: [QuadExtension<f62::BaseElement>; 2] = rand_array();
+    assert_eq!(
+        Keccak256::<f62::BaseElement>::hash(&to_bytes(&elements)),
+        Keccak256::hash_elements(&elements)
+    );
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn to_bytes<E: FieldElement>(elements: &[E]) -> Vec<u8> {
+    let mut result = Vec::new();
+    for element in elements {
+        element.write_into(&mut result);
+    }
+    result
+}
+
+fn hex_string(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
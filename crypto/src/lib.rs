@@ -14,7 +14,9 @@
 //!   [Octopus](https://eprint.iacr.org/2017/933) algorithm.
 //! * **PRNG** - which is used to generate pseudo-random elements in a finite field. The
 //!   [RandomCoin] implementation uses a cryptographic hash function to generate pseudo-random
-//!   elements form a seed.
+//!   elements form a seed. Other sources of randomness (e.g., transcripts compatible with
+//!   verifiers written in other languages) can be plugged into the protocol by implementing the
+//!   [PublicCoin] trait.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -29,6 +31,7 @@ pub mod hashers {
 
     pub use super::hash::Blake3_192;
     pub use super::hash::Blake3_256;
+    pub use super::hash::Keccak256;
     pub use super::hash::Rp62_248;
     pub use super::hash::Rp64_256;
     pub use super::hash::Sha3_256;
@@ -41,7 +44,7 @@ pub use merkle::{build_merkle_nodes, BatchMerkleProof, MerkleTree};
 pub use merkle::concurrent;
 
 mod random;
-pub use random::RandomCoin;
+pub use random::{PublicCoin, RandomCoin};
 
 mod errors;
 pub use errors::{MerkleTreeError, RandomCoinError};
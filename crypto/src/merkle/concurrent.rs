@@ -3,8 +3,12 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
+use super::MerkleTree;
 use crate::Hasher;
-use core::slice;
+use core::{
+    slice,
+    sync::atomic::{AtomicBool, Ordering},
+};
 use utils::{collections::Vec, iterators::*, rayon};
 
 // CONSTANTS
@@ -12,6 +16,8 @@ use utils::{collections::Vec, iterators::*, rayon};
 
 pub const MIN_CONCURRENT_LEAVES: usize = 1024;
 
+pub const MIN_CONCURRENT_PATHS: usize = 16;
+
 // PUBLIC FUNCTIONS
 // ================================================================================================
 
@@ -69,12 +75,40 @@ pub fn build_merkle_nodes<H: Hasher>(leaves: &[H::Digest]) -> Vec<H::Digest> {
     nodes
 }
 
+/// Verifies each of the `paths` against the `root` for the index at the same position in
+/// `indexes` using all available threads, and returns true if all paths are valid.
+///
+/// Each path is verified independently; once an invalid path is found, verification of the
+/// remaining paths is skipped.
+pub fn verify_paths<H: Hasher>(
+    root: H::Digest,
+    indexes: &[usize],
+    paths: &[Vec<H::Digest>],
+) -> bool {
+    let failed = AtomicBool::new(false);
+    indexes
+        .par_iter()
+        .zip(paths.par_iter())
+        .for_each(|(&index, path)| {
+            if failed.load(Ordering::Relaxed) {
+                return;
+            }
+            if MerkleTree::<H>::verify(root, index, path).is_err() {
+                failed.store(true, Ordering::Relaxed);
+            }
+        });
+    !failed.load(Ordering::Relaxed)
+}
+
 // TESTS
 // ================================================================================================
 
 #[cfg(test)]
 mod tests {
-    use crate::hash::{ByteDigest, Sha3_256};
+    use crate::{
+        hash::{ByteDigest, Sha3_256},
+        MerkleTree,
+    };
     use math::fields::f128::BaseElement;
     use proptest::collection::vec;
     use proptest::prelude::*;
@@ -87,5 +121,17 @@ mod tests {
             let concurrent = super::build_merkle_nodes::<Sha3_256<BaseElement>>(&leaves);
             assert_eq!(concurrent, sequential);
         }
+
+        #[test]
+        fn verify_paths_concurrent(ref data in vec(any::<[u8; 32]>(), 256..257).no_shrink()) {
+            let leaves = ByteDigest::bytes_as_digests(data).to_vec();
+            let tree = MerkleTree::<Sha3_256<BaseElement>>::new(leaves).unwrap();
+            let indexes = (0..64).map(|i| i * 3).collect::<Vec<_>>();
+            let mut paths = indexes.iter().map(|&i| tree.prove(i).unwrap()).collect::<Vec<_>>();
+            assert!(super::verify_paths::<Sha3_256<BaseElement>>(*tree.root(), &indexes, &paths));
+
+            paths[40][2] = paths[40][3];
+            assert!(!super::verify_paths::<Sha3_256<BaseElement>>(*tree.root(), &indexes, &paths));
+        }
     }
 }
@@ -301,6 +301,45 @@ impl<H: Hasher> MerkleTree<H> {
         }
         Ok(())
     }
+
+    /// Checks whether each of the `paths` is a valid Merkle path for the index at the same
+    /// position in `indexes`.
+    ///
+    /// Unlike [verify_batch()](MerkleTree::verify_batch), each path is verified independently.
+    /// When `concurrent` feature is enabled, the paths are verified using multiple threads, and
+    /// verification stops as soon as an invalid path is found.
+    ///
+    /// # Errors
+    /// Returns an error if any of the `paths` does not resolve to the specified `root`.
+    ///
+    /// # Panics
+    /// Panics if the number of `paths` is not equal to the number of `indexes`.
+    pub fn verify_paths(
+        root: H::Digest,
+        indexes: &[usize],
+        paths: &[Vec<H::Digest>],
+    ) -> Result<(), MerkleTreeError> {
+        assert_eq!(
+            indexes.len(),
+            paths.len(),
+            "number of paths must be equal to the number of indexes"
+        );
+
+        #[cfg(not(feature = "concurrent"))]
+        let is_valid = verify_paths::<H>(root, indexes, paths);
+
+        #[cfg(feature = "concurrent")]
+        let is_valid = if paths.len() < concurrent::MIN_CONCURRENT_PATHS {
+            verify_paths::<H>(root, indexes, paths)
+        } else {
+            concurrent::verify_paths::<H>(root, indexes, paths)
+        };
+
+        if !is_valid {
+            return Err(MerkleTreeError::InvalidProof);
+        }
+        Ok(())
+    }
 }
 
 // HELPER FUNCTIONS
@@ -366,3 +405,12 @@ fn normalize_indexes(indexes: &[usize]) -> Vec<usize> {
     }
     set.into_iter().collect()
 }
+
+/// Returns true if each of the `paths` resolves to the specified `root` for the index at the
+/// same position in `indexes`.
+fn verify_paths<H: Hasher>(root: H::Digest, indexes: &[usize], paths: &[Vec<H::Digest>]) -> bool {
+    indexes
+        .iter()
+        .zip(paths.iter())
+        .all(|(&index, path)| MerkleTree::<H>::verify(root, index, path).is_ok())
+}
@@ -37,6 +37,10 @@ pub struct BatchMerkleProof<H: Hasher> {
 impl<H: Hasher> BatchMerkleProof<H> {
     /// Constructs a batch Merkle proof from individual Merkle authentication paths.
     ///
+    /// The resulting proof is the same as the proof generated by
+    /// [MerkleTree::prove_batch()](crate::MerkleTree::prove_batch) for the same `indexes`; in
+    /// particular, leaves are stored in the order in which the `indexes` are listed.
+    ///
     /// # Panics
     /// Panics if:
     /// * No paths have been provided (i.e., `paths` is an empty slice).
@@ -59,6 +63,13 @@ impl<H: Hasher> BatchMerkleProof<H> {
 
         let depth = paths[0].len();
 
+        // record positions of leaf values so that leaves are stored in the original order
+        let leaf_positions = indexes
+            .iter()
+            .enumerate()
+            .map(|(i, &index)| (index, i))
+            .collect::<BTreeMap<_, _>>();
+
         // sort indexes in ascending order, and also re-arrange paths accordingly
         let mut path_map = BTreeMap::new();
         for (&index, path) in indexes.iter().zip(paths.iter().cloned()) {
@@ -75,9 +86,9 @@ impl<H: Hasher> BatchMerkleProof<H> {
         // populate values and the first layer of proof nodes
         let mut i = 0;
         while i < indexes.len() {
-            leaves[i] = paths[i][0];
+            leaves[leaf_positions[&indexes[i]]] = paths[i][0];
             if indexes.len() > i + 1 && are_siblings(indexes[i], indexes[i + 1]) {
-                leaves[i + 1] = paths[i][1];
+                leaves[leaf_positions[&indexes[i + 1]]] = paths[i][1];
                 nodes.push(vec![]);
                 i += 1;
             } else {
@@ -151,6 +151,23 @@ fn verify() {
     assert!(MerkleTree::<Blake3_256>::verify(*tree.root(), 6, &proof).is_ok());
 }
 
+#[test]
+fn verify_paths() {
+    let leaves = Digest256::bytes_as_digests(&LEAVES8).to_vec();
+    let tree = MerkleTree::<Blake3_256>::new(leaves).unwrap();
+
+    let indexes = [1, 3, 6];
+    let mut paths = indexes
+        .iter()
+        .map(|&i| tree.prove(i).unwrap())
+        .collect::<Vec<_>>();
+    assert!(MerkleTree::<Blake3_256>::verify_paths(*tree.root(), &indexes, &paths).is_ok());
+    assert!(MerkleTree::<Blake3_256>::verify_paths(*tree.root(), &[1, 3, 7], &paths).is_err());
+
+    paths[1][2] = paths[0][2];
+    assert!(MerkleTree::<Blake3_256>::verify_paths(*tree.root(), &indexes, &paths).is_err());
+}
+
 #[test]
 fn prove_batch() {
     let leaves = Digest256::bytes_as_digests(&LEAVES8).to_vec();
@@ -269,6 +286,21 @@ proptest! {
 
         prop_assert!(proof1 == proof2);
     }
+
+    #[test]
+    fn batch_proof_from_unsorted_paths(tree in random_blake3_merkle_tree(128),
+                      proof_indices in prop::collection::vec(any::<prop::sample::Index>(), 10..20)
+    )  {
+        let mut indices: Vec<usize> = proof_indices.iter().map(|idx| idx.index(128)).collect();
+        indices.sort_unstable(); indices.dedup(); indices.reverse();
+        let proof1 = tree.prove_batch(&indices[..]).unwrap();
+
+        let paths = indices.iter().map(|&idx| tree.prove(idx).unwrap()).collect::<Vec<_>>();
+        let proof2 = BatchMerkleProof::from_paths(&paths, &indices);
+
+        prop_assert!(proof1 == proof2);
+        prop_assert!(MerkleTree::verify_batch(tree.root(), &indices[..], &proof2).is_ok());
+    }
 }
 
 // HELPER FUNCTIONS
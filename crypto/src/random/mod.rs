@@ -3,10 +3,12 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use crate::{errors::RandomCoinError, Digest, Hasher};
+use crate::{errors::RandomCoinError, Digest, ElementHasher, Hasher};
 use core::{convert::TryInto, marker::PhantomData};
 use math::{FieldElement, StarkField};
-use utils::collections::Vec;
+use utils::{
+    collections::Vec, ByteReader, Deserializable, DeserializationError, Serializable, SliceReader,
+};
 
 // RANDOM COIN
 // ================================================================================================
@@ -79,6 +81,63 @@ impl<B: StarkField, H: Hasher> RandomCoin<B, H> {
         }
     }
 
+    // STATE EXPORT / IMPORT
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the internal state of this coin serialized into a vector of bytes.
+    ///
+    /// The state is encoded as the current seed serialized via its [Serializable] implementation
+    /// (for BLAKE3 and SHA3 digests these are the raw digest bytes), followed by the current
+    /// counter encoded as an 8-byte little-endian integer. Since the coin is fully defined by
+    /// this state, implementations in other languages can exchange transcripts with this coin
+    /// by using the same encoding.
+    ///
+    /// # Examples
+    /// ```
+    /// # use winter_crypto::{RandomCoin, Hasher, hashers::Blake3_256};
+    /// # use math::fields::f128::BaseElement;
+    /// let mut coin1 = RandomCoin::<BaseElement, Blake3_256<BaseElement>>::new(&[1, 2, 3, 4]);
+    /// coin1.reseed(Blake3_256::<BaseElement>::hash(&[2, 3, 4, 5]));
+    /// let _ = coin1.draw::<BaseElement>().unwrap();
+    ///
+    /// // a coin imported from the exported state continues the transcript of the first coin
+    /// let state = coin1.export_state();
+    /// assert_eq!(32 + 8, state.len());
+    /// let mut coin2 = RandomCoin::<BaseElement, Blake3_256<BaseElement>>::import_state(&state)
+    ///     .unwrap();
+    /// assert_eq!(coin1.draw::<BaseElement>(), coin2.draw::<BaseElement>());
+    ///
+    /// // a truncated state cannot be imported
+    /// let result = RandomCoin::<BaseElement, Blake3_256<BaseElement>>::import_state(&state[..39]);
+    /// assert!(result.is_err());
+    /// ```
+    pub fn export_state(&self) -> Vec<u8> {
+        let mut result = Vec::new();
+        self.seed.write_into(&mut result);
+        result.extend_from_slice(&self.counter.to_le_bytes());
+        result
+    }
+
+    /// Returns a new random coin instantiated from a state exported via
+    /// [export_state()](RandomCoin::export_state).
+    ///
+    /// # Errors
+    /// Returns an error if the seed could not be read from the `state`, or if the `state` contains
+    /// less or more bytes than needed to encode the seed and the counter.
+    pub fn import_state(state: &[u8]) -> Result<Self, DeserializationError> {
+        let mut source = SliceReader::new(state);
+        let seed = H::Digest::read_from(&mut source)?;
+        let counter = source.read_u64()?;
+        if source.has_more_bytes() {
+            return Err(DeserializationError::UnconsumedBytes);
+        }
+        Ok(RandomCoin {
+            seed,
+            counter,
+            _base_field: PhantomData,
+        })
+    }
+
     // RESEEDING
     // --------------------------------------------------------------------------------------------
 
@@ -133,6 +192,35 @@ impl<B: StarkField, H: Hasher> RandomCoin<B, H> {
         self.counter = 0;
     }
 
+    /// Reseeds the coin with the specified data prefixed by a domain separation `tag`.
+    ///
+    /// The tag is absorbed into the seed before the data, that is, the new seed is set to
+    /// hash(hash(`seed` || `tag`) || `data`). Tagging data with the protocol component which
+    /// produced it (e.g., trace commitment, out-of-domain evaluations, or FRI layer commitment)
+    /// makes sure that the same data absorbed in different phases of the protocol results in
+    /// different seeds.
+    ///
+    /// # Examples
+    /// ```
+    /// # use winter_crypto::{RandomCoin, Hasher, hashers::Blake3_256};
+    /// # use math::fields::f128::BaseElement;
+    /// let mut coin1 = RandomCoin::<BaseElement, Blake3_256<BaseElement>>::new(&[1, 2, 3, 4]);
+    /// let mut coin2 = RandomCoin::<BaseElement, Blake3_256<BaseElement>>::new(&[1, 2, 3, 4]);
+    ///
+    /// // reseeding with the same data but different tags should draw different elements
+    /// let data = Blake3_256::<BaseElement>::hash(&[2, 3, 4, 5]);
+    /// coin1.reseed_with_tag(b"abcd", data);
+    /// coin2.reseed_with_tag(b"efgh", data);
+    /// let e1 = coin1.draw::<BaseElement>().unwrap();
+    /// let e2 = coin2.draw::<BaseElement>().unwrap();
+    /// assert_ne!(e1, e2);
+    /// ```
+    pub fn reseed_with_tag(&mut self, tag: &[u8; 4], data: H::Digest) {
+        let tagged_seed = H::merge_with_int(self.seed, u32::from_le_bytes(*tag) as u64);
+        self.seed = H::merge(&[tagged_seed, data]);
+        self.counter = 0;
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -305,3 +393,122 @@ impl<B: StarkField, H: Hasher> RandomCoin<B, H> {
         H::merge_with_int(self.seed, self.counter)
     }
 }
+
+// PUBLIC COIN
+// ================================================================================================
+
+/// Defines a source of verifier randomness for the non-interactive (Fiat-Shamir) version of the
+/// STARK protocol.
+///
+/// The prover and the verifier absorb the same sequence of values into a public coin and draw
+/// the same sequence of challenges from it. Thus, any implementation of this trait can be used
+/// as a transcript as long as both parties use it. The STARK protocol absorbs values exclusively
+/// via [reseed_with_tag()](PublicCoin::reseed_with_tag), with the tags defined in the
+/// [air](https://docs.rs/winter-air) crate, and via [reseed_with_int()](PublicCoin::reseed_with_int)
+/// when applying proof-of-work; a custom coin can, for example, replicate the transcript of a
+/// verifier implemented in another language, or use an algebraic sponge.
+///
+/// [RandomCoin] is the implementation used by default.
+pub trait PublicCoin: Sized {
+    /// Base field of the elements drawn from this coin.
+    type BaseField: StarkField;
+
+    /// Hash function used for all commitments absorbed into this coin.
+    type Hasher: ElementHasher<BaseField = Self::BaseField>;
+
+    /// Returns a new coin instantiated with the provided `seed`.
+    fn new(seed: &[u8]) -> Self;
+
+    /// Absorbs the specified `data` prefixed by a domain separation `tag` into this coin.
+    fn reseed_with_tag(&mut self, tag: &[u8; 4], data: <Self::Hasher as Hasher>::Digest);
+
+    /// Absorbs the specified integer `value` into this coin.
+    fn reseed_with_int(&mut self, value: u64);
+
+    /// Returns the number of leading zeros of the current state of this coin; this is used to
+    /// verify proof-of-work.
+    fn leading_zeros(&self) -> u32;
+
+    /// Returns the number of leading zeros of the state this coin would have after absorbing
+    /// `value` via [reseed_with_int()](PublicCoin::reseed_with_int); this is used to search for
+    /// a proof-of-work nonce.
+    fn check_leading_zeros(&self, value: u64) -> u32;
+
+    /// Returns the next pseudo-random field element.
+    ///
+    /// # Errors
+    /// Returns an error if a valid field element could not be drawn.
+    fn draw<E: FieldElement<BaseField = Self::BaseField>>(&mut self) -> Result<E, RandomCoinError>;
+
+    /// Returns the next pair of pseudo-random field elements.
+    ///
+    /// # Errors
+    /// Returns an error if any of the field elements could not be drawn.
+    fn draw_pair<E: FieldElement<BaseField = Self::BaseField>>(
+        &mut self,
+    ) -> Result<(E, E), RandomCoinError> {
+        Ok((self.draw()?, self.draw()?))
+    }
+
+    /// Returns the next triplet of pseudo-random field elements.
+    ///
+    /// # Errors
+    /// Returns an error if any of the field elements could not be drawn.
+    fn draw_triple<E: FieldElement<BaseField = Self::BaseField>>(
+        &mut self,
+    ) -> Result<(E, E, E), RandomCoinError> {
+        Ok((self.draw()?, self.draw()?, self.draw()?))
+    }
+
+    /// Returns a vector of `num_values` unique integers selected from the range
+    /// [0, `domain_size`).
+    ///
+    /// # Errors
+    /// Returns an error if the specified number of unique integers could not be drawn.
+    fn draw_integers(
+        &mut self,
+        num_values: usize,
+        domain_size: usize,
+    ) -> Result<Vec<usize>, RandomCoinError>;
+}
+
+impl<B, H> PublicCoin for RandomCoin<B, H>
+where
+    B: StarkField,
+    H: ElementHasher<BaseField = B>,
+{
+    type BaseField = B;
+    type Hasher = H;
+
+    fn new(seed: &[u8]) -> Self {
+        RandomCoin::new(seed)
+    }
+
+    fn reseed_with_tag(&mut self, tag: &[u8; 4], data: H::Digest) {
+        RandomCoin::reseed_with_tag(self, tag, data)
+    }
+
+    fn reseed_with_int(&mut self, value: u64) {
+        RandomCoin::reseed_with_int(self, value)
+    }
+
+    fn leading_zeros(&self) -> u32 {
+        RandomCoin::leading_zeros(self)
+    }
+
+    fn check_leading_zeros(&self, value: u64) -> u32 {
+        RandomCoin::check_leading_zeros(self, value)
+    }
+
+    fn draw<E: FieldElement<BaseField = B>>(&mut self) -> Result<E, RandomCoinError> {
+        RandomCoin::draw(self)
+    }
+
+    fn draw_integers(
+        &mut self,
+        num_values: usize,
+        domain_size: usize,
+    ) -> Result<Vec<usize>, RandomCoinError> {
+        RandomCoin::draw_integers(self, num_values, domain_size)
+    }
+}
@@ -0,0 +1,430 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! This crate contains `#[derive(Air)]` procedural macro for computations with simple
+//! transition constraints.
+//!
+//! The macro is re-exported by the `winterfell` crate, and the code it generates refers to items
+//! of that crate. See the documentation of [macro@Air] for details.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Literal, Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::{
+    parse_macro_input, BinOp, Data, DeriveInput, Error, Expr, Field, Fields, GenericArgument,
+    Ident, Lit, PathArguments, Result, Type, UnOp,
+};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Name of the field holding the context of the computation.
+const CONTEXT_FIELD: &str = "context";
+
+// DERIVE MACRO
+// ================================================================================================
+
+/// Derives an implementation of the `Air` trait for a struct describing a computation with
+/// simple transition constraints.
+///
+/// Each column of the execution trace is described by a struct field; columns are placed into
+/// the trace in the order in which the fields are declared. Fields are interpreted as follows:
+///
+/// * `context` field must be of type `AirContext<B>`; `B` is used as the base field of the
+///   computation.
+/// * A field marked with `#[public_inputs]` holds public inputs of the computation; at most one
+///   field can be marked this way. If no field is marked, public inputs are `()`.
+/// * A field marked with `#[transition(expr)]` is a column whose next value must be equal to
+///   `expr` evaluated over the current values of the columns.
+/// * A field marked with `#[column]` is a column without transition constraints.
+///
+/// Column fields are initialized with `Default::default()`, and thus, are usually of type `()`.
+///
+/// Transition expressions can contain integer literals, column names, unary `-`, and binary `+`,
+/// `-`, `*`, and `^` operators; the exponent of `^` must be an integer literal. Since `^` has
+/// lower precedence than other operators in Rust, both of its operands must be either columns,
+/// literals, or parenthesized expressions, e.g. `(a ^ 2) + b`. Degrees of transition constraints
+/// are inferred from the expressions.
+///
+/// Columns can also be marked with `#[assert_first(value)]` and `#[assert_last(value)]`, which
+/// assert values of the column at the first and the last step of the execution trace; a value
+/// is either an integer literal or the name of the public inputs field (which must then be of
+/// type `B`).
+///
+/// Computations which require periodic columns, transition constraints against next values of
+/// other columns, or assertions at other steps must implement the `Air` trait manually.
+#[proc_macro_derive(
+    Air,
+    attributes(transition, column, public_inputs, assert_first, assert_last)
+)]
+pub fn derive_air(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand_air(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+// AIR DESCRIPTION
+// ================================================================================================
+
+/// A single column of the execution trace.
+struct Column {
+    name: Ident,
+    transition: Option<Expr>,
+    assert_first: Option<Expr>,
+    assert_last: Option<Expr>,
+}
+
+/// A description of a computation parsed from the fields of a struct.
+struct AirDescription {
+    base_field: Type,
+    public_inputs: Option<(Ident, Type)>,
+    columns: Vec<Column>,
+}
+
+impl AirDescription {
+    /// Parses the computation description from the fields of the struct.
+    fn parse(input: &DeriveInput) -> Result<Self> {
+        let fields = match &input.data {
+            Data::Struct(data) => match &data.fields {
+                Fields::Named(fields) => &fields.named,
+                _ => {
+                    return Err(Error::new_spanned(
+                        &input.ident,
+                        "Air can be derived only for structs with named fields",
+                    ))
+                }
+            },
+            _ => {
+                return Err(Error::new_spanned(
+                    &input.ident,
+                    "Air can be derived only for structs",
+                ))
+            }
+        };
+
+        let mut base_field = None;
+        let mut public_inputs = None;
+        let mut columns = Vec::new();
+        for field in fields.iter() {
+            let name = field.ident.clone().expect("named field");
+            if name == CONTEXT_FIELD {
+                base_field = Some(parse_base_field(field)?);
+            } else if has_attr(field, "public_inputs") {
+                if public_inputs.is_some() {
+                    return Err(Error::new_spanned(
+                        field,
+                        "only one field can be marked with #[public_inputs]",
+                    ));
+                }
+                public_inputs = Some((name, field.ty.clone()));
+            } else if has_attr(field, "transition") || has_attr(field, "column") {
+                columns.push(Column {
+                    name,
+                    transition: parse_attr_expr(field, "transition")?,
+                    assert_first: parse_attr_expr(field, "assert_first")?,
+                    assert_last: parse_attr_expr(field, "assert_last")?,
+                });
+            } else {
+                return Err(Error::new_spanned(
+                    field,
+                    "field must be the context, public inputs, or a column marked with \
+                     #[transition(...)] or #[column]",
+                ));
+            }
+        }
+
+        let base_field = base_field.ok_or_else(|| {
+            Error::new_spanned(
+                &input.ident,
+                "struct must contain `context` field of type AirContext",
+            )
+        })?;
+        if columns.is_empty() {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "struct must contain at least one column",
+            ));
+        }
+
+        Ok(AirDescription {
+            base_field,
+            public_inputs,
+            columns,
+        })
+    }
+
+    /// Returns index of the column with the specified name.
+    fn column_index(&self, name: &Ident) -> Option<usize> {
+        self.columns.iter().position(|column| &column.name == name)
+    }
+}
+
+// CODE GENERATION
+// ================================================================================================
+
+/// Generates an implementation of the `Air` trait for the struct described by `input`.
+fn expand_air(input: &DeriveInput) -> Result<TokenStream2> {
+    let air = AirDescription::parse(input)?;
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let base_field = &air.base_field;
+    let trace_width = air.columns.len();
+    let context_field = Ident::new(CONTEXT_FIELD, Span::call_site());
+
+    // public inputs
+    let (pub_inputs_type, pub_inputs_init) = match &air.public_inputs {
+        Some((field, ty)) => (quote! { #ty }, quote! { #field: __pub_inputs, }),
+        None => (quote! { () }, quote! {}),
+    };
+
+    // columns are read from the current row into local variables named after the columns
+    let column_names = air.columns.iter().map(|c| &c.name).collect::<Vec<_>>();
+    let column_indexes = (0..trace_width).collect::<Vec<_>>();
+
+    // transition constraints
+    let mut degrees = Vec::new();
+    let mut constraints = Vec::new();
+    for (i, column) in air.columns.iter().enumerate() {
+        if let Some(expr) = &column.transition {
+            let (tokens, degree) = expand_expr(&air, expr)?;
+            let index = constraints.len();
+            constraints.push(quote! {
+                __result[#index] = __next[#i] - (#tokens);
+            });
+            degrees.push(degree.max(1));
+        }
+    }
+    let max_degree = degrees.iter().copied().max().unwrap_or(1);
+
+    // assertions
+    let mut assertions = Vec::new();
+    for (i, column) in air.columns.iter().enumerate() {
+        if let Some(value) = &column.assert_first {
+            let value = expand_assertion_value(&air, value)?;
+            assertions.push(quote! { ::winterfell::Assertion::single(#i, 0, #value) });
+        }
+        if let Some(value) = &column.assert_last {
+            let value = expand_assertion_value(&air, value)?;
+            assertions.push(quote! { ::winterfell::Assertion::single(#i, __last_step, #value) });
+        }
+    }
+
+    Ok(quote! {
+        impl #impl_generics ::winterfell::Air for #name #ty_generics #where_clause {
+            type BaseElement = #base_field;
+            type PublicInputs = #pub_inputs_type;
+
+            const MAX_CONSTRAINT_DEGREE: usize = #max_degree;
+
+            fn new(
+                __trace_info: ::winterfell::TraceInfo,
+                __pub_inputs: Self::PublicInputs,
+                __options: ::winterfell::ProofOptions,
+            ) -> Self {
+                assert_eq!(
+                    #trace_width,
+                    __trace_info.width(),
+                    "execution trace must consist of {} registers, but was {}",
+                    #trace_width,
+                    __trace_info.width()
+                );
+                let __degrees = vec![
+                    #(::winterfell::TransitionConstraintDegree::new(#degrees)),*
+                ];
+                Self {
+                    #context_field: ::winterfell::AirContext::new(__trace_info, __degrees, __options),
+                    #pub_inputs_init
+                    #(#column_names: ::core::default::Default::default(),)*
+                }
+            }
+
+            fn context(&self) -> &::winterfell::AirContext<Self::BaseElement> {
+                // column fields do not hold any data; they are referenced here so that they are
+                // not reported as unused
+                let _ = (#(&self.#column_names,)*);
+                &self.#context_field
+            }
+
+            #[allow(unused_variables)]
+            fn evaluate_transition<
+                E: ::winterfell::math::FieldElement<BaseField = Self::BaseElement>,
+            >(
+                &self,
+                __frame: &::winterfell::EvaluationFrame<E>,
+                __periodic_values: &[E],
+                __result: &mut [E],
+            ) {
+                let __current = __frame.current();
+                let __next = __frame.next();
+                #(let #column_names = __current[#column_indexes];)*
+                #(#constraints)*
+            }
+
+            fn get_assertions(&self) -> Vec<::winterfell::Assertion<Self::BaseElement>> {
+                let __last_step = ::winterfell::Air::trace_length(self) - 1;
+                vec![#(#assertions),*]
+            }
+        }
+    })
+}
+
+/// Returns code evaluating the specified transition expression together with the degree of the
+/// expression in the columns of the trace.
+fn expand_expr(air: &AirDescription, expr: &Expr) -> Result<(TokenStream2, usize)> {
+    match expr {
+        Expr::Lit(lit) => {
+            let value = parse_int_literal(&lit.lit)?;
+            Ok((quote! { E::from(#value) }, 0))
+        }
+        Expr::Path(path) => {
+            let name = path.path.get_ident().ok_or_else(|| {
+                Error::new_spanned(path, "transition expressions can refer only to columns")
+            })?;
+            match air.column_index(name) {
+                Some(_) => Ok((quote! { #name }, 1)),
+                None => Err(Error::new_spanned(
+                    name,
+                    format!("unknown column `{}`", name),
+                )),
+            }
+        }
+        Expr::Paren(paren) => {
+            let (tokens, degree) = expand_expr(air, &paren.expr)?;
+            Ok((quote! { (#tokens) }, degree))
+        }
+        Expr::Group(group) => expand_expr(air, &group.expr),
+        Expr::Unary(unary) => match unary.op {
+            UnOp::Neg(_) => {
+                let (tokens, degree) = expand_expr(air, &unary.expr)?;
+                Ok((quote! { (-#tokens) }, degree))
+            }
+            _ => Err(Error::new_spanned(unary, "unsupported unary operator")),
+        },
+        Expr::Binary(binary) => match binary.op {
+            BinOp::Add(_) | BinOp::Sub(_) | BinOp::Mul(_) => {
+                let (lhs, lhs_degree) = expand_expr(air, &binary.left)?;
+                let (rhs, rhs_degree) = expand_expr(air, &binary.right)?;
+                let op = &binary.op;
+                let degree = match binary.op {
+                    BinOp::Mul(_) => lhs_degree + rhs_degree,
+                    _ => lhs_degree.max(rhs_degree),
+                };
+                Ok((quote! { (#lhs #op #rhs) }, degree))
+            }
+            BinOp::BitXor(_) => {
+                if !matches!(*binary.left, Expr::Lit(_) | Expr::Path(_) | Expr::Paren(_)) {
+                    return Err(Error::new_spanned(
+                        &binary.left,
+                        "base of ^ must be a column, a literal, or a parenthesized expression",
+                    ));
+                }
+                let exponent = match &*binary.right {
+                    Expr::Lit(lit) => match &lit.lit {
+                        Lit::Int(int) => int.base10_parse::<usize>()?,
+                        _ => return Err(Error::new_spanned(lit, "exponent must be an integer")),
+                    },
+                    right => {
+                        return Err(Error::new_spanned(
+                            right,
+                            "exponent of ^ must be an integer literal",
+                        ))
+                    }
+                };
+                let (base, degree) = expand_expr(air, &binary.left)?;
+                let factors = (0..exponent).map(|_| quote! { __base });
+                Ok((
+                    quote! {{
+                        let __base = #base;
+                        E::ONE #(* #factors)*
+                    }},
+                    degree * exponent,
+                ))
+            }
+            _ => Err(Error::new_spanned(binary, "unsupported binary operator")),
+        },
+        _ => Err(Error::new_spanned(
+            expr,
+            "unsupported expression; only literals, columns, and +, -, *, ^ operators are allowed",
+        )),
+    }
+}
+
+/// Returns code evaluating to the specified asserted value in the base field.
+fn expand_assertion_value(air: &AirDescription, value: &Expr) -> Result<TokenStream2> {
+    let base_field = &air.base_field;
+    match value {
+        Expr::Lit(lit) => {
+            let value = parse_int_literal(&lit.lit)?;
+            Ok(quote! { <#base_field>::from(#value) })
+        }
+        Expr::Path(path) => match (path.path.get_ident(), &air.public_inputs) {
+            (Some(name), Some((field, _))) if name == field => Ok(quote! { self.#field }),
+            _ => Err(Error::new_spanned(
+                path,
+                "asserted value must be an integer literal or the public inputs field",
+            )),
+        },
+        _ => Err(Error::new_spanned(
+            value,
+            "asserted value must be an integer literal or the public inputs field",
+        )),
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns true if the field is marked with the attribute of the specified name.
+fn has_attr(field: &Field, name: &str) -> bool {
+    field.attrs.iter().any(|attr| attr.path.is_ident(name))
+}
+
+/// Parses the expression specified by the attribute of the specified name, if any.
+fn parse_attr_expr(field: &Field, name: &str) -> Result<Option<Expr>> {
+    let mut result = None;
+    for attr in field.attrs.iter().filter(|attr| attr.path.is_ident(name)) {
+        if result.is_some() {
+            return Err(Error::new_spanned(
+                attr,
+                format!("#[{}] can be specified only once per column", name),
+            ));
+        }
+        result = Some(attr.parse_args::<Expr>()?);
+    }
+    Ok(result)
+}
+
+/// Returns the type parameter of `AirContext` type of the specified field.
+fn parse_base_field(field: &Field) -> Result<Type> {
+    let error = || Error::new_spanned(&field.ty, "`context` field must be of type AirContext<B>");
+    let segment = match &field.ty {
+        Type::Path(path) => path.path.segments.last().ok_or_else(error)?,
+        _ => return Err(error()),
+    };
+    if segment.ident != "AirContext" {
+        return Err(error());
+    }
+    match &segment.arguments {
+        PathArguments::AngleBracketed(args) => match args.args.first() {
+            Some(GenericArgument::Type(ty)) if args.args.len() == 1 => Ok(ty.clone()),
+            _ => Err(error()),
+        },
+        _ => Err(error()),
+    }
+}
+
+/// Parses an integer literal into a `u128` literal token.
+fn parse_int_literal(lit: &Lit) -> Result<Literal> {
+    match lit {
+        Lit::Int(int) => Ok(Literal::u128_suffixed(int.base10_parse::<u128>()?)),
+        _ => Err(Error::new_spanned(
+            lit,
+            "only integer literals are supported",
+        )),
+    }
+}
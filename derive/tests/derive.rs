@@ -0,0 +1,96 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use winterfell::{
+    math::{fields::f128::BaseElement, FieldElement},
+    Air, AirContext, ExecutionTrace, FieldExtension, HashFunction, ProofOptions, TraceInfo,
+};
+
+// DERIVED AIRS
+// ================================================================================================
+
+/// Computes Fibonacci sequence with 2 terms per step; the second column is expressed over the
+/// current row only.
+#[derive(Air)]
+struct DerivedFibAir {
+    context: AirContext<BaseElement>,
+    #[public_inputs]
+    result: BaseElement,
+    #[transition(a + b)]
+    #[assert_first(1)]
+    a: (),
+    #[transition(a + 2 * b)]
+    #[assert_first(1)]
+    #[assert_last(result)]
+    b: (),
+}
+
+/// A derived AIR with higher-degree transition constraints and an unconstrained column.
+#[derive(Air)]
+struct DerivedPowerAir {
+    context: AirContext<BaseElement>,
+    #[transition((a ^ 3) * (b ^ 2) - 7)]
+    a: (),
+    #[transition(-(a * c) + b)]
+    b: (),
+    #[column]
+    c: (),
+}
+
+// TESTS
+// ================================================================================================
+
+#[test]
+fn derived_fib_air() {
+    let trace = build_fib_trace(8);
+    let result = trace.get(1, trace.length() - 1);
+    let air = DerivedFibAir::new(trace.get_info(), result, build_options());
+    assert_eq!(1, DerivedFibAir::MAX_CONSTRAINT_DEGREE);
+    assert_eq!(2, air.num_transition_constraints());
+    assert_eq!(3, air.get_assertions().len());
+
+    let proof = winterfell::prove::<DerivedFibAir>(trace, result, build_options()).unwrap();
+    assert!(winterfell::verify::<DerivedFibAir>(proof.clone(), result).is_ok());
+    assert!(winterfell::verify::<DerivedFibAir>(proof, result + BaseElement::ONE).is_err());
+}
+
+#[test]
+fn derived_constraint_degrees() {
+    // degrees of transition constraints are inferred from the expressions
+    let air = DerivedPowerAir::new(TraceInfo::new(3, 8), (), build_options());
+    assert_eq!(5, DerivedPowerAir::MAX_CONSTRAINT_DEGREE);
+    assert_eq!(2, air.num_transition_constraints());
+    assert!(air.get_assertions().is_empty());
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn build_fib_trace(trace_length: usize) -> ExecutionTrace<BaseElement> {
+    let mut trace = ExecutionTrace::new(2, trace_length);
+    trace.fill(
+        |state| {
+            state[0] = BaseElement::ONE;
+            state[1] = BaseElement::ONE;
+        },
+        |_, state| {
+            state[0] += state[1];
+            state[1] += state[0];
+        },
+    );
+    trace
+}
+
+fn build_options() -> ProofOptions {
+    ProofOptions::new(
+        36,
+        8,
+        0,
+        HashFunction::Blake3_256,
+        FieldExtension::None,
+        4,
+        256,
+    )
+}
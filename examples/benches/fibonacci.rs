@@ -16,7 +16,7 @@ fn fibonacci(c: &mut Criterion) {
     group.measurement_time(Duration::from_secs(20));
 
     let options = ProofOptions::new(
-        32,
+        36,
         8,
         0,
         HashFunction::Blake3_256,
@@ -0,0 +1,92 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use examples::fibonacci::fib2::{build_trace, FibAir};
+use std::time::Duration;
+use winterfell::{
+    math::{fields::f128::BaseElement, FieldElement},
+    Air, AirContext, Assertion, EvaluationFrame, FieldExtension, HashFunction, ProofOptions,
+    TraceInfo,
+};
+
+const SIZES: [usize; 3] = [16_384, 65_536, 262_144];
+
+// Compares proving time for the Fibonacci computation when its transition constraints are
+// declared as linear (and are thus combined directly from trace columns) against the time when
+// the constraints are evaluated and merged at every step.
+fn linear_constraints(c: &mut Criterion) {
+    let mut group = c.benchmark_group("linear_constraints");
+    group.sample_size(10);
+    group.measurement_time(Duration::from_secs(20));
+
+    let options = ProofOptions::new(
+        36,
+        8,
+        0,
+        HashFunction::Blake3_256,
+        FieldExtension::None,
+        4,
+        256,
+    );
+
+    for &size in SIZES.iter() {
+        let result = compute_result(size);
+        group.bench_function(BenchmarkId::new("linear", size), |bench| {
+            bench.iter(|| winterfell::prove::<FibAir>(build_trace(size), result, options.clone()));
+        });
+        group.bench_function(BenchmarkId::new("general", size), |bench| {
+            bench.iter(|| {
+                winterfell::prove::<OpaqueFibAir>(build_trace(size), result, options.clone())
+            });
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(linear_constraints_group, linear_constraints);
+criterion_main!(linear_constraints_group);
+
+// OPAQUE FIBONACCI AIR
+// ================================================================================================
+
+/// Same as Fibonacci AIR but without declaring transition constraints as linear.
+struct OpaqueFibAir(FibAir);
+
+impl Air for OpaqueFibAir {
+    type BaseElement = BaseElement;
+    type PublicInputs = BaseElement;
+
+    const MAX_CONSTRAINT_DEGREE: usize = FibAir::MAX_CONSTRAINT_DEGREE;
+
+    fn new(trace_info: TraceInfo, pub_inputs: Self::BaseElement, options: ProofOptions) -> Self {
+        OpaqueFibAir(FibAir::new(trace_info, pub_inputs, options))
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseElement> {
+        self.0.context()
+    }
+
+    fn evaluate_transition<E: FieldElement<BaseField = Self::BaseElement>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        self.0.evaluate_transition(frame, periodic_values, result);
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseElement>> {
+        self.0.get_assertions()
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn compute_result(size: usize) -> BaseElement {
+    let trace = build_trace(size);
+    trace.get(1, trace.length() - 1)
+}
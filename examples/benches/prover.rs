@@ -0,0 +1,210 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use examples::{fibonacci, rescue, Example};
+use fri::DefaultProverChannel;
+use std::time::Duration;
+use winterfell::{
+    crypto::{hashers::Blake3_256, RandomCoin},
+    math::{fft, fields::f128::BaseElement, FieldElement},
+    Air, AirContext, Assertion, EvaluationFrame, ExecutionTrace, FieldExtension, HashFunction,
+    InteractiveProver, ProofOptions, TraceInfo, TracePolyTable, TransitionConstraintDegree,
+};
+
+type Hasher = Blake3_256<BaseElement>;
+type Channel = DefaultProverChannel<BaseElement, BaseElement, Hasher>;
+
+const NUM_QUERIES: usize = 36;
+const BLOWUP_FACTOR: usize = 8;
+
+/// Number of registers in the wide trace used to measure the trace LDE.
+const WIDE_TRACE_WIDTH: usize = 64;
+
+/// Log2 of the length of the wide trace; at 2^16 steps, the extended trace already takes up
+/// 512 MB, and longer traces would not fit into memory of typical CI machines.
+const WIDE_TRACE_LOG_LENGTH: usize = 16;
+
+/// Number of registers (and degree 2 transition constraints) of [SquaresAir].
+const NUM_SQUARES: usize = 10;
+
+const SQUARES_TRACE_LENGTH: usize = 16_384;
+
+// BENCHMARKS
+// ================================================================================================
+
+/// Measures performance of the major prover phases not covered by the per-example benchmarks:
+/// * low-degree extension of a trace with 64 registers,
+/// * evaluation of 10 degree 2 transition constraints over the constraint evaluation domain,
+/// * proof generation followed by proof verification for the Fibonacci and Rescue examples.
+///
+/// Noise threshold and significance level are set in the same way as for proving time scaling
+/// benchmarks so that the results can be compared against a saved baseline (e.g., via
+/// `cargo bench -- --baseline main`).
+fn prover(c: &mut Criterion) {
+    let mut group = c.benchmark_group("prover");
+    group.sample_size(10);
+    group.measurement_time(Duration::from_secs(20));
+    group.noise_threshold(0.05);
+    group.significance_level(0.01);
+
+    let options = build_options();
+
+    // 1 ----- extend a wide execution trace ------------------------------------------------------
+    let trace_length = 1 << WIDE_TRACE_LOG_LENGTH;
+    let polys = TracePolyTable::from(&build_wide_trace(trace_length)).into_vec();
+    let domain_offset = options.domain_offset::<BaseElement>();
+    group.bench_function(BenchmarkId::new("wide_trace_lde", trace_length), |bench| {
+        bench.iter(|| extend_trace(&polys, domain_offset));
+    });
+
+    // 2 ----- evaluate transition constraints ----------------------------------------------------
+    // the prover does not expose constraint evaluation on its own, and thus, this also includes
+    // interpolation and commitment of the resulting constraint composition polynomial
+    let trace_info = TraceInfo::new(NUM_SQUARES, SQUARES_TRACE_LENGTH);
+    let air = SquaresAir::new(trace_info, (), options.clone());
+    group.bench_function(
+        BenchmarkId::new("constraint_evaluation", SQUARES_TRACE_LENGTH),
+        |bench| {
+            bench.iter_batched(
+                || {
+                    let mut prover = InteractiveProver::<_, BaseElement, Hasher, Channel>::new(
+                        &air,
+                        build_squares_trace(SQUARES_TRACE_LENGTH),
+                    )
+                    .unwrap();
+                    let mut coin = RandomCoin::<BaseElement, Hasher>::new(&[]);
+                    coin.reseed(prover.commit_trace());
+                    let coefficients = air
+                        .get_constraint_composition_coefficients(
+                            &mut coin,
+                            prover.num_boundary_constraints(),
+                        )
+                        .unwrap();
+                    (prover, coefficients)
+                },
+                |(mut prover, coefficients)| prover.commit_composition(coefficients).unwrap(),
+                BatchSize::LargeInput,
+            );
+        },
+    );
+
+    // 3 ----- generate and verify proofs for the examples ----------------------------------------
+    let fib = fibonacci::fib2::FibExample::new(SQUARES_TRACE_LENGTH * 2, options.clone());
+    group.bench_function(
+        BenchmarkId::new("prove_verify_fibonacci", SQUARES_TRACE_LENGTH),
+        |bench| {
+            bench.iter(|| fib.verify(fib.prove()).unwrap());
+        },
+    );
+
+    let resc = rescue::RescueExample::new(256, options);
+    group.bench_function(BenchmarkId::new("prove_verify_rescue", 256), |bench| {
+        bench.iter(|| resc.verify(resc.prove()).unwrap());
+    });
+
+    group.finish();
+}
+
+criterion_group!(prover_group, prover);
+criterion_main!(prover_group);
+
+// SQUARES AIR
+// ================================================================================================
+
+/// AIR of a computation in which the next value of each register is the square of its current
+/// value plus the current value of the following register; this defines [NUM_SQUARES] degree 2
+/// transition constraints.
+struct SquaresAir {
+    context: AirContext<BaseElement>,
+}
+
+impl Air for SquaresAir {
+    type BaseElement = BaseElement;
+    type PublicInputs = ();
+
+    const MAX_CONSTRAINT_DEGREE: usize = 2;
+
+    fn new(trace_info: TraceInfo, _pub_inputs: (), options: ProofOptions) -> Self {
+        let degrees = vec![TransitionConstraintDegree::new(2); NUM_SQUARES];
+        SquaresAir {
+            context: AirContext::new(trace_info, degrees, options),
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseElement> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement<BaseField = Self::BaseElement>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+        for (i, result) in result.iter_mut().enumerate() {
+            *result = next[i] - (current[i].square() + current[(i + 1) % NUM_SQUARES]);
+        }
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseElement>> {
+        (0..NUM_SQUARES)
+            .map(|i| Assertion::single(i, 0, BaseElement::from(i as u8)))
+            .collect()
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn build_options() -> ProofOptions {
+    ProofOptions::new(
+        NUM_QUERIES,
+        BLOWUP_FACTOR,
+        0,
+        HashFunction::Blake3_256,
+        FieldExtension::None,
+        4,
+        256,
+    )
+}
+
+/// Builds an execution trace for [SquaresAir].
+fn build_squares_trace(length: usize) -> ExecutionTrace<BaseElement> {
+    let mut trace = ExecutionTrace::new(NUM_SQUARES, length);
+    let initial = (0..NUM_SQUARES)
+        .map(|i| BaseElement::from(i as u8))
+        .collect();
+    trace.fill_columns_from_state(initial, |state| {
+        (0..NUM_SQUARES)
+            .map(|i| state[i].square() + state[(i + 1) % NUM_SQUARES])
+            .collect()
+    });
+    trace
+}
+
+/// Builds a trace with [WIDE_TRACE_WIDTH] registers, each holding a sequence of consecutive
+/// integers starting with the index of the register.
+fn build_wide_trace(length: usize) -> ExecutionTrace<BaseElement> {
+    let registers = (0..WIDE_TRACE_WIDTH)
+        .map(|i| {
+            (0..length)
+                .map(|step| BaseElement::from((i + step) as u64))
+                .collect()
+        })
+        .collect();
+    ExecutionTrace::init(registers)
+}
+
+/// Evaluates trace polynomials over the LDE domain.
+fn extend_trace(polys: &[Vec<BaseElement>], domain_offset: BaseElement) -> Vec<Vec<BaseElement>> {
+    let twiddles = fft::get_twiddles::<BaseElement>(polys[0].len());
+    polys
+        .iter()
+        .map(|poly| fft::evaluate_poly_with_offset(poly, &twiddles, domain_offset, BLOWUP_FACTOR))
+        .collect()
+}
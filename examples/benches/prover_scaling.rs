@@ -0,0 +1,146 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use examples::{fibonacci, Example};
+use fri::{DefaultProverChannel, FriProver};
+use std::time::Duration;
+use winterfell::{
+    crypto::{hashers::Blake3_256, ElementHasher, MerkleTree},
+    math::{fft, fields::f128::BaseElement, FieldElement},
+    FieldExtension, HashFunction, ProofOptions, TracePolyTable,
+};
+
+type Hasher = Blake3_256<BaseElement>;
+
+/// Log2 of the smallest and the largest trace lengths for which proving time is measured.
+const MIN_LOG_TRACE_LENGTH: usize = 16;
+const MAX_LOG_TRACE_LENGTH: usize = 24;
+
+const NUM_QUERIES: usize = 36;
+const BLOWUP_FACTOR: usize = 8;
+
+// BENCHMARKS
+// ================================================================================================
+
+/// Measures proving time of the Fibonacci example (fib2) at each power-of-two trace length
+/// between 2^16 and 2^24, both end-to-end and broken down by the following phases:
+/// * low-degree extension of the execution trace,
+/// * commitment to the extended execution trace,
+/// * folding of the FRI layers,
+/// * building of the FRI proof for the queried positions.
+///
+/// The noise threshold and the significance level are set so that changes of less than 5% are
+/// reported as noise, while regressions of 10% or more are reliably reported as changes.
+fn prover_scaling(c: &mut Criterion) {
+    let mut group = c.benchmark_group("prover_scaling");
+    group.sample_size(10);
+    group.measurement_time(Duration::from_secs(20));
+    group.noise_threshold(0.05);
+    group.significance_level(0.01);
+
+    let options = ProofOptions::new(
+        NUM_QUERIES,
+        BLOWUP_FACTOR,
+        0,
+        HashFunction::Blake3_256,
+        FieldExtension::None,
+        4,
+        256,
+    );
+    let fri_options = options.to_fri_options::<BaseElement>();
+    let domain_offset = options.domain_offset::<BaseElement>();
+
+    for log_trace_length in MIN_LOG_TRACE_LENGTH..=MAX_LOG_TRACE_LENGTH {
+        let trace_length = 1 << log_trace_length;
+        let lde_domain_size = trace_length * BLOWUP_FACTOR;
+
+        // fib2 computes 2 terms of the sequence per step
+        let fib = fibonacci::fib2::FibExample::new(trace_length * 2, options.clone());
+        group.bench_function(BenchmarkId::new("end_to_end", trace_length), |bench| {
+            bench.iter(|| fib.prove());
+        });
+
+        // 1 ----- extend execution trace ---------------------------------------------------------
+        let trace = fibonacci::fib2::build_trace(trace_length * 2);
+        let polys = TracePolyTable::from(&trace).into_vec();
+        group.bench_function(BenchmarkId::new("trace_lde", trace_length), |bench| {
+            bench.iter(|| extend_trace(&polys, domain_offset));
+        });
+
+        // 2 ----- commit to the extended execution trace -----------------------------------------
+        let extended_trace = extend_trace(&polys, domain_offset);
+        group.bench_function(
+            BenchmarkId::new("trace_commitment", trace_length),
+            |bench| {
+                bench.iter(|| commit_to_trace(&extended_trace));
+            },
+        );
+
+        // 3 ----- fold FRI layers ----------------------------------------------------------------
+        // extended trace registers are used in place of DEEP composition polynomial evaluations;
+        // both are evaluations of polynomials of degree trace_length - 1 over the LDE domain
+        let evaluations = extended_trace[0].clone();
+        group.bench_function(BenchmarkId::new("fri_folding", trace_length), |bench| {
+            let mut prover = FriProver::new(fri_options.clone());
+            bench.iter_batched(
+                || evaluations.clone(),
+                |evaluations| {
+                    let mut channel = build_fri_channel(lde_domain_size);
+                    prover.build_layers(&mut channel, evaluations);
+                    prover.reset();
+                },
+                BatchSize::LargeInput,
+            );
+        });
+
+        // 4 ----- build FRI proof for queried positions ------------------------------------------
+        let mut prover = FriProver::new(fri_options.clone());
+        let mut channel = build_fri_channel(lde_domain_size);
+        prover.build_layers(&mut channel, evaluations);
+        let positions = channel.draw_query_positions();
+        group.bench_function(BenchmarkId::new("fri_queries", trace_length), |bench| {
+            bench.iter(|| prover.build_proof(&positions));
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(prover_scaling_group, prover_scaling);
+criterion_main!(prover_scaling_group);
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Evaluates trace polynomials over the LDE domain.
+fn extend_trace(polys: &[Vec<BaseElement>], domain_offset: BaseElement) -> Vec<Vec<BaseElement>> {
+    let twiddles = fft::get_twiddles::<BaseElement>(polys[0].len());
+    polys
+        .iter()
+        .map(|poly| fft::evaluate_poly_with_offset(poly, &twiddles, domain_offset, BLOWUP_FACTOR))
+        .collect()
+}
+
+/// Hashes each row of the extended execution trace and builds a Merkle tree from the resulting
+/// digests.
+fn commit_to_trace(extended_trace: &[Vec<BaseElement>]) -> MerkleTree<Hasher> {
+    let mut row = vec![BaseElement::ZERO; extended_trace.len()];
+    let leaves = (0..extended_trace[0].len())
+        .map(|i| {
+            for (value, register) in row.iter_mut().zip(extended_trace) {
+                *value = register[i];
+            }
+            Hasher::hash_elements(&row)
+        })
+        .collect();
+    MerkleTree::new(leaves).unwrap()
+}
+
+fn build_fri_channel(
+    lde_domain_size: usize,
+) -> DefaultProverChannel<BaseElement, BaseElement, Hasher> {
+    DefaultProverChannel::new(lde_domain_size, NUM_QUERIES)
+}
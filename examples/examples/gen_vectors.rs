@@ -0,0 +1,83 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use examples::{fibonacci::fib2, rescue};
+use serde_json::{json, Value};
+use std::{fs, path::Path};
+use winterfell::{FieldExtension, HashFunction, ProofOptions, Serializable, StarkProof};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Location of the fixture file relative to the root of the examples crate.
+const VECTORS_PATH: &str = "tests/compat/vectors.json";
+
+// TEST VECTOR GENERATION
+// ================================================================================================
+
+/// Generates known-good (proof, public inputs, options) triples for the Fibonacci and Rescue
+/// hash chain examples, and writes them into the fixture file read by the compatibility tests.
+///
+/// Run as `cargo run --example gen_vectors` from the examples crate.
+fn main() {
+    let options = ProofOptions::new(
+        36,
+        8,
+        0,
+        HashFunction::Blake3_256,
+        FieldExtension::None,
+        4,
+        256,
+    );
+
+    let vectors = vec![
+        fibonacci_vector(64, options.clone()),
+        rescue_vector(16, options),
+    ];
+
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(VECTORS_PATH);
+    let json = serde_json::to_string_pretty(&Value::Array(vectors)).unwrap();
+    fs::write(&path, json + "\n").unwrap();
+    println!("wrote test vectors to {}", path.display());
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn fibonacci_vector(sequence_length: usize, options: ProofOptions) -> Value {
+    let trace = fib2::build_trace(sequence_length);
+    let result = trace.get(1, trace.length() - 1);
+    let proof = winterfell::prove::<fib2::FibAir>(trace, result, options.clone()).unwrap();
+    build_vector("fib2", sequence_length, &options, result.to_bytes(), proof)
+}
+
+fn rescue_vector(chain_length: usize, options: ProofOptions) -> Value {
+    let seed = [42u8.into(), 43u8.into()];
+    let trace = rescue::build_trace(seed, chain_length);
+    let last_step = trace.length() - 1;
+    let pub_inputs = rescue::PublicInputs {
+        seed,
+        result: [trace.get(0, last_step), trace.get(1, last_step)],
+    };
+    let pub_inputs_bytes = pub_inputs.to_bytes();
+    let proof = winterfell::prove::<rescue::RescueAir>(trace, pub_inputs, options.clone()).unwrap();
+    build_vector("rescue", chain_length, &options, pub_inputs_bytes, proof)
+}
+
+fn build_vector(
+    example: &str,
+    size: usize,
+    options: &ProofOptions,
+    pub_inputs: Vec<u8>,
+    proof: StarkProof,
+) -> Value {
+    json!({
+        "example": example,
+        "size": size,
+        "options": hex::encode(options.to_bytes()),
+        "pub_inputs": hex::encode(pub_inputs),
+        "proof": hex::encode(proof.to_bytes()),
+    })
+}
@@ -0,0 +1,75 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use examples::params::{estimate_proving_time, recommend_options};
+use structopt::StructOpt;
+
+// COMMAND-LINE OPTIONS
+// ================================================================================================
+
+#[derive(StructOpt, Debug)]
+#[structopt(
+    name = "recommend_params",
+    about = "Recommends proof options for a target security level"
+)]
+struct Options {
+    /// Target conjectured security level (in bits)
+    #[structopt(long = "security", default_value = "100")]
+    security: u32,
+
+    /// Length of the execution trace; must be a power of two
+    #[structopt(long = "trace-len", default_value = "1048576")]
+    trace_length: usize,
+
+    /// Number of registers in the execution trace
+    #[structopt(long = "trace-width", default_value = "2")]
+    trace_width: usize,
+
+    /// Highest degree of transition constraints
+    #[structopt(long = "constraint-degree", default_value = "2")]
+    constraint_degree: usize,
+}
+
+// PARAMETER RECOMMENDATION
+// ================================================================================================
+
+fn main() {
+    let options = Options::from_args();
+
+    let recommendation = match recommend_options(
+        options.security,
+        options.trace_length,
+        options.trace_width,
+        options.constraint_degree,
+    ) {
+        Ok(recommendation) => recommendation,
+        Err(message) => {
+            eprintln!("error: {}", message);
+            std::process::exit(1);
+        }
+    };
+
+    println!("Recommended proof options:\n");
+    println!("{}\n", recommendation);
+    println!(
+        "Conjectured security:  {} bits (soundness error <= 2^-{})",
+        recommendation.security_level, recommendation.security_level
+    );
+    println!(
+        "Estimated proof size:  {:.1} KB (upper bound)",
+        recommendation.proof_size as f64 / 1024.0
+    );
+
+    println!("Benchmarking proof generation on this machine...");
+    let proving_time = estimate_proving_time(
+        &recommendation.to_proof_options(),
+        options.trace_length,
+        options.trace_width,
+    );
+    println!(
+        "Estimated proving time: {:.2} s",
+        proving_time.as_secs_f64()
+    );
+}
@@ -0,0 +1,142 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{
+    fibonacci::{
+        fib2::{self, FibAir},
+        utils::compute_fib_term,
+    },
+    rescue::{self, compute_hash_chain, RescueAir},
+    Example, ExampleOptions,
+};
+use log::debug;
+use std::time::Instant;
+use winterfell::{
+    math::{fields::f128::BaseElement, log2, FieldElement},
+    CompositeAir, CompositePublicInputs, ExecutionTrace, ProofOptions, StarkProof, VerifierError,
+};
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Number of Fibonacci terms computed per Rescue hash; fib2 computes 2 terms per step and a
+/// single Rescue hash requires 16 steps.
+const FIB_TERMS_PER_HASH: usize = 32;
+
+/// Number of registers in the Fibonacci portion of the composite execution trace.
+const FIB_TRACE_WIDTH: usize = 2;
+
+type FibRescueAir = CompositeAir<FibAir, RescueAir>;
+
+// FIBONACCI + RESCUE COMPOSITE EXAMPLE
+// ================================================================================================
+
+pub fn get_example(options: ExampleOptions, chain_length: usize) -> Box<dyn Example> {
+    Box::new(CompositeExample::new(
+        chain_length,
+        options.to_proof_options(28, 8),
+    ))
+}
+
+pub struct CompositeExample {
+    options: ProofOptions,
+    chain_length: usize,
+    fib_result: BaseElement,
+    seed: [BaseElement; 2],
+    hash_result: [BaseElement; 2],
+}
+
+impl CompositeExample {
+    pub fn new(chain_length: usize, options: ProofOptions) -> CompositeExample {
+        assert!(
+            chain_length.is_power_of_two(),
+            "chain length must a power of 2"
+        );
+        let seed = [BaseElement::from(42u8), BaseElement::from(43u8)];
+
+        // compute the Fibonacci sequence and the hash chain so that execution traces of both
+        // computations have the same length
+        let now = Instant::now();
+        let fib_result = compute_fib_term(chain_length * FIB_TERMS_PER_HASH);
+        let hash_result = compute_hash_chain(seed, chain_length);
+        debug!(
+            "Computed {}th Fibonacci term and a chain of {} Rescue hashes in {} ms",
+            chain_length * FIB_TERMS_PER_HASH,
+            chain_length,
+            now.elapsed().as_millis(),
+        );
+
+        CompositeExample {
+            options,
+            chain_length,
+            fib_result,
+            seed,
+            hash_result,
+        }
+    }
+
+    fn build_pub_inputs(
+        &self,
+        fib_result: BaseElement,
+        hash_result: [BaseElement; 2],
+    ) -> CompositePublicInputs<BaseElement, rescue::PublicInputs> {
+        let rescue_inputs = rescue::PublicInputs {
+            seed: self.seed,
+            result: hash_result,
+        };
+        CompositePublicInputs::new(FIB_TRACE_WIDTH, fib_result, rescue_inputs)
+    }
+}
+
+// EXAMPLE IMPLEMENTATION
+// ================================================================================================
+
+impl Example for CompositeExample {
+    fn prove(&self) -> StarkProof {
+        debug!(
+            "Generating proof for computing {}th Fibonacci term and a chain of {} Rescue hashes\n\
+            ---------------------",
+            self.chain_length * FIB_TERMS_PER_HASH,
+            self.chain_length
+        );
+
+        // build execution traces of both computations and place their registers side-by-side
+        let now = Instant::now();
+        let fib_trace = fib2::build_trace(self.chain_length * FIB_TERMS_PER_HASH);
+        let rescue_trace = rescue::build_trace(self.seed, self.chain_length);
+
+        let mut registers = Vec::with_capacity(fib_trace.width() + rescue_trace.width());
+        for trace in [&fib_trace, &rescue_trace] {
+            for i in 0..trace.width() {
+                registers.push(trace.get_register(i).to_vec());
+            }
+        }
+        let trace = ExecutionTrace::init(registers);
+        debug!(
+            "Generated execution trace of {} registers and 2^{} steps in {} ms",
+            trace.width(),
+            log2(trace.length()),
+            now.elapsed().as_millis()
+        );
+
+        // generate the proof
+        let pub_inputs = self.build_pub_inputs(self.fib_result, self.hash_result);
+        winterfell::prove::<FibRescueAir>(trace, pub_inputs, self.options.clone()).unwrap()
+    }
+
+    fn verify(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        let pub_inputs = self.build_pub_inputs(self.fib_result, self.hash_result);
+        winterfell::verify::<FibRescueAir>(proof, pub_inputs)
+    }
+
+    fn verify_with_wrong_inputs(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        let hash_result = [self.hash_result[0], self.hash_result[1] + BaseElement::ONE];
+        let pub_inputs = self.build_pub_inputs(self.fib_result, hash_result);
+        winterfell::verify::<FibRescueAir>(proof, pub_inputs)
+    }
+}
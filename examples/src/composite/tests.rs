@@ -0,0 +1,24 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::fibonacci::utils::build_proof_options;
+
+#[test]
+fn composite_test_basic_proof_verification() {
+    let composite_eg = Box::new(super::CompositeExample::new(16, build_proof_options(false)));
+    crate::tests::test_basic_proof_verification(composite_eg);
+}
+
+#[test]
+fn composite_test_basic_proof_verification_extension() {
+    let composite_eg = Box::new(super::CompositeExample::new(16, build_proof_options(true)));
+    crate::tests::test_basic_proof_verification(composite_eg);
+}
+
+#[test]
+fn composite_test_basic_proof_verification_fail() {
+    let composite_eg = Box::new(super::CompositeExample::new(16, build_proof_options(false)));
+    crate::tests::test_basic_proof_verification_fail(composite_eg);
+}
@@ -24,6 +24,8 @@ impl Air for FibAir {
     type BaseElement = BaseElement;
     type PublicInputs = BaseElement;
 
+    const MAX_CONSTRAINT_DEGREE: usize = 1;
+
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
     fn new(trace_info: TraceInfo, pub_inputs: Self::BaseElement, options: ProofOptions) -> Self {
@@ -61,6 +63,11 @@ impl Air for FibAir {
         result[1] = are_equal(next[1], current[1] + next[0]);
     }
 
+    fn is_transition_constraint_linear(&self, _index: usize) -> bool {
+        // both constraints are affine functions of the current and the next states
+        true
+    }
+
     fn get_assertions(&self) -> Vec<Assertion<Self::BaseElement>> {
         // a valid Fibonacci sequence should start with two ones and terminate with
         // the expected result
@@ -13,7 +13,7 @@ use winterfell::{
 };
 
 mod air;
-use air::{build_trace, FibAir};
+pub use air::{build_trace, FibAir};
 
 #[cfg(test)]
 mod tests;
@@ -4,6 +4,10 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::super::utils::build_proof_options;
+use winterfell::{
+    math::fields::{f128::BaseElement, QuadExtension},
+    Air, FieldExtension, HashFunction, ProofOptions, TraceInfo,
+};
 
 #[test]
 fn fib2_test_basic_proof_verification() {
@@ -17,8 +21,44 @@ fn fib2_test_basic_proof_verification_extension() {
     crate::tests::test_basic_proof_verification(fib);
 }
 
+#[test]
+fn fib2_test_basic_proof_verification_keccak() {
+    let options = ProofOptions::new(
+        36,
+        8,
+        0,
+        HashFunction::Keccak256,
+        FieldExtension::Quadratic,
+        4,
+        256,
+    );
+    let fib = Box::new(super::FibExample::new(16, options));
+    crate::tests::test_basic_proof_verification(fib);
+}
+
 #[test]
 fn fib2_test_basic_proof_verification_fail() {
     let fib = Box::new(super::FibExample::new(16, build_proof_options(false)));
     crate::tests::test_basic_proof_verification_fail(fib);
 }
+
+#[test]
+fn fib2_test_proof_format_versions() {
+    let fib = Box::new(super::FibExample::new(16, build_proof_options(false)));
+    crate::tests::test_proof_format_versions(fib);
+}
+
+#[test]
+fn fib2_test_air_on_random_trace() {
+    let fib = super::FibExample::new(16, build_proof_options(false));
+    let trace_info = TraceInfo::new(2, 8);
+    let air = super::FibAir::new(trace_info, fib.result, fib.options);
+    let mut rng = rand::thread_rng();
+    assert!(crate::tests::test_air_on_random_trace::<_, BaseElement>(
+        &air, &mut rng
+    ));
+    assert!(crate::tests::test_air_on_random_trace::<
+        _,
+        QuadExtension<BaseElement>,
+    >(&air, &mut rng));
+}
@@ -8,4 +8,4 @@ pub mod fib8;
 pub mod mulfib2;
 pub mod mulfib8;
 
-mod utils;
+pub(crate) mod utils;
@@ -24,6 +24,8 @@ impl Air for MulFib2Air {
     type BaseElement = BaseElement;
     type PublicInputs = BaseElement;
 
+    const MAX_CONSTRAINT_DEGREE: usize = 2;
+
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
     fn new(trace_info: TraceInfo, pub_inputs: Self::BaseElement, options: ProofOptions) -> Self {
@@ -82,13 +84,11 @@ pub fn build_trace(length: usize) -> ExecutionTrace<BaseElement> {
         "sequence length must be a power of 2"
     );
 
-    let mut reg0 = vec![BaseElement::new(1)];
-    let mut reg1 = vec![BaseElement::new(2)];
-
-    for i in 0..(length / 2 - 1) {
-        reg0.push(reg0[i] * reg1[i]);
-        reg1.push(reg1[i] * reg0[i + 1]);
-    }
+    let mut trace = ExecutionTrace::new(2, length / 2);
+    trace.fill_columns_from_state(vec![BaseElement::new(1), BaseElement::new(2)], |state| {
+        let reg0 = state[0] * state[1];
+        vec![reg0, state[1] * reg0]
+    });
 
-    ExecutionTrace::init(vec![reg0, reg1])
+    trace
 }
@@ -24,6 +24,8 @@ impl Air for MulFib8Air {
     type BaseElement = BaseElement;
     type PublicInputs = BaseElement;
 
+    const MAX_CONSTRAINT_DEGREE: usize = 2;
+
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
     fn new(trace_info: TraceInfo, pub_inputs: Self::BaseElement, options: ProofOptions) -> Self {
@@ -42,6 +42,8 @@ impl Air for LamportAggregateAir {
     type BaseElement = BaseElement;
     type PublicInputs = PublicInputs;
 
+    const MAX_CONSTRAINT_DEGREE: usize = 5;
+
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
     fn new(trace_info: TraceInfo, pub_inputs: PublicInputs, options: ProofOptions) -> Self {
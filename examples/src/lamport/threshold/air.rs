@@ -49,6 +49,8 @@ impl Air for LamportThresholdAir {
     type BaseElement = BaseElement;
     type PublicInputs = PublicInputs;
 
+    const MAX_CONSTRAINT_DEGREE: usize = 5;
+
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
     #[rustfmt::skip]
@@ -6,13 +6,17 @@
 use structopt::StructOpt;
 use winterfell::{FieldExtension, HashFunction, ProofOptions, StarkProof, VerifierError};
 
+pub mod composite;
 pub mod fibonacci;
 #[cfg(feature = "std")]
 pub mod lamport;
 #[cfg(feature = "std")]
 pub mod merkle;
+pub mod params;
 pub mod rescue;
+pub mod rescue_preimage;
 pub mod utils;
+pub mod vm;
 
 #[cfg(test)]
 mod tests;
@@ -74,6 +78,7 @@ impl ExampleOptions {
             "blake3_192" => HashFunction::Blake3_192,
             "blake3_256" => HashFunction::Blake3_256,
             "sha3_256" => HashFunction::Sha3_256,
+            "keccak_256" => HashFunction::Keccak256,
             val => panic!("'{}' is not a valid hash function option", val),
         };
 
@@ -122,6 +127,20 @@ pub enum ExampleType {
         #[structopt(short = "n", default_value = "1024")]
         chain_length: usize,
     },
+    /// Prove knowledge of a preimage of a Rescue hash
+    RescuePreimage,
+    /// Sum a variable number of inputs in a minimal virtual machine
+    Vm {
+        /// Number of inputs on the input tape of the program
+        #[structopt(short = "n", default_value = "1000")]
+        num_inputs: usize,
+    },
+    /// Compute a Fibonacci sequence and a Rescue hash chain in a single composite trace
+    Composite {
+        /// Length of the hash chain; must be a power of two
+        #[structopt(short = "n", default_value = "1024")]
+        chain_length: usize,
+    },
     /// Compute a root of a Merkle path using Rescue hash function
     #[cfg(feature = "std")]
     Merkle {
@@ -9,7 +9,7 @@ use std::time::Instant;
 use structopt::StructOpt;
 use winterfell::StarkProof;
 
-use examples::{fibonacci, rescue, ExampleOptions, ExampleType};
+use examples::{composite, fibonacci, rescue, rescue_preimage, vm, ExampleOptions, ExampleType};
 #[cfg(feature = "std")]
 use examples::{lamport, merkle};
 
@@ -43,6 +43,9 @@ fn main() {
             fibonacci::mulfib8::get_example(options, sequence_length)
         }
         ExampleType::Rescue { chain_length } => rescue::get_example(options, chain_length),
+        ExampleType::RescuePreimage => rescue_preimage::get_example(options),
+        ExampleType::Vm { num_inputs } => vm::get_example(options, num_inputs),
+        ExampleType::Composite { chain_length } => composite::get_example(options, chain_length),
         #[cfg(feature = "std")]
         ExampleType::Merkle { tree_depth } => merkle::get_example(options, tree_depth),
         #[cfg(feature = "std")]
@@ -0,0 +1,293 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{fibonacci::fib2::FibExample, Example};
+use core::fmt;
+use std::time::{Duration, Instant};
+use winterfell::{
+    math::{fields::f128::BaseElement, log2},
+    FieldExtension, FriRemainderEncoding, HashFunction, ProofOptions,
+};
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Number of bits in the modulus of the base field used by the examples (f128).
+const BASE_FIELD_BITS: u32 = 128;
+
+/// Query security below this threshold does not benefit from grinding; this mirrors the logic
+/// of [StarkProof::security_level()](winterfell::StarkProof::security_level).
+const GRINDING_CONTRIBUTION_FLOOR: u32 = 80;
+
+/// Smallest blowup factor recommended regardless of constraint degree.
+const MIN_RECOMMENDED_BLOWUP: usize = 8;
+
+const MAX_BLOWUP_FACTOR: usize = 128;
+const MAX_NUM_QUERIES: usize = 128;
+const GRINDING_FACTOR: u32 = 16;
+const FRI_FOLDING_FACTOR: usize = 8;
+const FRI_MAX_REMAINDER_SIZE: usize = 256;
+
+/// Trace length of the computation proven to benchmark the current machine.
+const BENCHMARK_TRACE_LENGTH: usize = 4096;
+
+/// Trace width of the computation proven to benchmark the current machine (fib2).
+const BENCHMARK_TRACE_WIDTH: usize = 2;
+
+// RECOMMENDATION
+// ================================================================================================
+
+/// Recommended proof options for a computation, together with estimates of the resulting proof
+/// size and the conjectured security level which the options achieve.
+pub struct Recommendation {
+    pub num_queries: usize,
+    pub blowup_factor: usize,
+    pub grinding_factor: u32,
+    pub hash_fn: HashFunction,
+    pub field_extension: FieldExtension,
+    pub fri_folding_factor: usize,
+    pub fri_max_remainder_size: usize,
+    /// Conjectured security level (in bits) achieved by the recommended options; the soundness
+    /// error is bounded by 2^-security_level.
+    pub security_level: u32,
+    /// Estimated size of a proof (in bytes) generated with the recommended options.
+    pub proof_size: usize,
+}
+
+impl Recommendation {
+    /// Returns [ProofOptions] described by this recommendation.
+    pub fn to_proof_options(&self) -> ProofOptions {
+        ProofOptions::new(
+            self.num_queries,
+            self.blowup_factor,
+            self.grinding_factor,
+            self.hash_fn,
+            self.field_extension,
+            self.fri_folding_factor,
+            self.fri_max_remainder_size,
+        )
+    }
+}
+
+impl fmt::Display for Recommendation {
+    /// Writes the recommended options as a Rust expression which can be copied into user code.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "ProofOptions::new(")?;
+        writeln!(f, "    {}, // number of queries", self.num_queries)?;
+        writeln!(f, "    {}, // blowup factor", self.blowup_factor)?;
+        writeln!(f, "    {}, // grinding factor", self.grinding_factor)?;
+        writeln!(f, "    HashFunction::{:?},", self.hash_fn)?;
+        writeln!(f, "    FieldExtension::{:?},", self.field_extension)?;
+        writeln!(f, "    {}, // FRI folding factor", self.fri_folding_factor)?;
+        writeln!(
+            f,
+            "    {}, // FRI max remainder size",
+            self.fri_max_remainder_size
+        )?;
+        write!(f, ")")
+    }
+}
+
+// RECOMMENDATION PROCEDURE
+// ================================================================================================
+
+/// Returns proof options which achieve at least `security` bits of conjectured security for a
+/// computation with the specified trace length, trace width, and max transition constraint
+/// degree, or an error message if no such options exist.
+///
+/// The blowup factor is chosen to be the smallest power of two sufficient to evaluate
+/// constraints of the specified degree (but not smaller than 8); the hash function, field
+/// extension, and the number of queries are then chosen to be the cheapest ones which achieve
+/// the target security level.
+pub fn recommend_options(
+    security: u32,
+    trace_length: usize,
+    trace_width: usize,
+    constraint_degree: usize,
+) -> Result<Recommendation, String> {
+    if !trace_length.is_power_of_two() || trace_length < 8 {
+        return Err(format!(
+            "trace length must be a power of two of at least 8, but was {}",
+            trace_length
+        ));
+    }
+    if trace_width == 0 {
+        return Err("trace width must be greater than zero".to_string());
+    }
+    if constraint_degree == 0 {
+        return Err("constraint degree must be greater than zero".to_string());
+    }
+
+    let blowup_factor = constraint_degree
+        .next_power_of_two()
+        .max(MIN_RECOMMENDED_BLOWUP);
+    if blowup_factor > MAX_BLOWUP_FACTOR {
+        return Err(format!(
+            "constraint degree {} requires blowup factor greater than {}",
+            constraint_degree, MAX_BLOWUP_FACTOR
+        ));
+    }
+    let lde_domain_size = (trace_length * blowup_factor) as u64;
+
+    let hash_fn = [HashFunction::Blake3_192, HashFunction::Blake3_256]
+        .iter()
+        .copied()
+        .find(|hash_fn| hash_fn.collision_resistance() >= security)
+        .ok_or(format!(
+            "none of the available hash functions provides {} bits of security",
+            security
+        ))?;
+
+    let field_extension = [
+        FieldExtension::None,
+        FieldExtension::Quadratic,
+        FieldExtension::Cubic,
+    ]
+    .iter()
+    .copied()
+    .find(|extension| field_security(*extension, lde_domain_size) > security)
+    .ok_or(format!(
+        "none of the available field extensions provides {} bits of security",
+        security
+    ))?;
+
+    for num_queries in 1..=MAX_NUM_QUERIES {
+        let options = ProofOptions::new(
+            num_queries,
+            blowup_factor,
+            GRINDING_FACTOR,
+            hash_fn,
+            field_extension,
+            FRI_FOLDING_FACTOR,
+            FRI_MAX_REMAINDER_SIZE,
+        );
+        let security_level = conjectured_security(&options, lde_domain_size);
+        if security_level >= security {
+            return Ok(Recommendation {
+                num_queries,
+                blowup_factor,
+                grinding_factor: GRINDING_FACTOR,
+                hash_fn,
+                field_extension,
+                fri_folding_factor: FRI_FOLDING_FACTOR,
+                fri_max_remainder_size: FRI_MAX_REMAINDER_SIZE,
+                security_level,
+                proof_size: estimate_proof_size(&options, trace_length, trace_width),
+            });
+        }
+    }
+
+    Err(format!(
+        "{} bits of security cannot be achieved with at most {} queries",
+        security, MAX_NUM_QUERIES
+    ))
+}
+
+// ESTIMATES
+// ================================================================================================
+
+/// Returns conjectured security level (in bits) of proofs generated with the specified options
+/// over an LDE domain of the specified size.
+///
+/// This mirrors the computation performed by
+/// [StarkProof::security_level()](winterfell::StarkProof::security_level).
+pub fn conjectured_security(options: &ProofOptions, lde_domain_size: u64) -> u32 {
+    let field_security = field_security(options.field_extension(), lde_domain_size);
+    let hash_fn_security = options.hash_fn().collision_resistance();
+
+    let security_per_query = log2(options.blowup_factor());
+    let mut query_security = security_per_query * options.num_queries() as u32;
+    if query_security >= GRINDING_CONTRIBUTION_FLOOR {
+        query_security += options.grinding_factor();
+    }
+
+    (field_security.min(query_security) - 1).min(hash_fn_security)
+}
+
+/// Returns an upper bound on the size (in bytes) of a proof generated with the specified options
+/// for a computation with the specified trace length and width.
+///
+/// The estimate assumes that Merkle authentication paths of queried positions do not share any
+/// nodes, and thus, actual proofs are usually smaller.
+pub fn estimate_proof_size(
+    options: &ProofOptions,
+    trace_length: usize,
+    trace_width: usize,
+) -> usize {
+    let element_size = (BASE_FIELD_BITS / 8) as usize;
+    let extension_degree = options.field_extension().degree() as usize;
+    let digest_size = (options.hash_fn().collision_resistance() / 4) as usize;
+    let num_queries = options.num_queries();
+    let lde_domain_size = trace_length * options.blowup_factor();
+    let path_size = log2(lde_domain_size) as usize * digest_size;
+
+    // commitments to the trace and constraint evaluations and the out-of-domain frame
+    let mut size = 2 * digest_size + 2 * trace_width * element_size * extension_degree;
+
+    // trace and constraint queries
+    size += num_queries * (trace_width * element_size + path_size);
+    size += num_queries * (extension_degree * element_size + path_size);
+
+    // FRI layers; each layer reduces the domain by the folding factor
+    let fri_options = options.to_fri_options::<BaseElement>();
+    let folding_factor = fri_options.folding_factor();
+    let mut domain_size = lde_domain_size;
+    for _ in 0..fri_options.num_fri_layers(lde_domain_size) {
+        domain_size /= folding_factor;
+        let layer_path_size = log2(domain_size) as usize * digest_size;
+        size += digest_size;
+        size += num_queries * (folding_factor * extension_degree * element_size + layer_path_size);
+    }
+
+    // FRI remainder; when sent as coefficients, only the coefficients which can be non-zero are
+    // included in the proof
+    let mut remainder_size = fri_options.fri_remainder_size(lde_domain_size);
+    if fri_options.remainder_encoding() == FriRemainderEncoding::Coefficients {
+        remainder_size = (remainder_size / options.blowup_factor()).max(1);
+    }
+    size += remainder_size * extension_degree * element_size;
+
+    size
+}
+
+/// Returns the estimated time needed to generate a proof with the specified options for a
+/// computation with the specified trace length and width on the current machine.
+///
+/// The estimate is obtained by generating a proof for a small Fibonacci computation with the
+/// same options and scaling the measured time to the specified trace dimensions.
+///
+/// # Panics
+/// Panics if `options` provide a lower security level than the one required by the Fibonacci
+/// AIR (see [Air::REQUIRED_SECURITY_BITS](winterfell::Air::REQUIRED_SECURITY_BITS)).
+pub fn estimate_proving_time(
+    options: &ProofOptions,
+    trace_length: usize,
+    trace_width: usize,
+) -> Duration {
+    // fib2 computes 2 terms of the sequence per step
+    let example = FibExample::new(BENCHMARK_TRACE_LENGTH * 2, options.clone());
+    let now = Instant::now();
+    example.prove();
+    let elapsed = now.elapsed();
+
+    // proving time is dominated by the LDE of the trace, which scales as n log(n)
+    let lde_log = |length: usize| log2(length * options.blowup_factor()) as f64;
+    let length_ratio = (trace_length as f64 / BENCHMARK_TRACE_LENGTH as f64)
+        * (lde_log(trace_length) / lde_log(BENCHMARK_TRACE_LENGTH));
+    let width_ratio = trace_width as f64 / BENCHMARK_TRACE_WIDTH as f64;
+    elapsed.mul_f64(length_ratio * width_ratio)
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the max security (in bits) which can be achieved with the specified field extension
+/// over an LDE domain of the specified size.
+fn field_security(extension: FieldExtension, lde_domain_size: u64) -> u32 {
+    BASE_FIELD_BITS * extension.degree() - lde_domain_size.trailing_zeros()
+}
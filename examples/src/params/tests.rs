@@ -0,0 +1,67 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{conjectured_security, estimate_proof_size, recommend_options};
+use crate::{fibonacci::fib2::FibExample, Example};
+use winterfell::{FieldExtension, HashFunction};
+
+#[test]
+fn recommend_options_achieves_target_security() {
+    for &security in [80, 96, 100, 128].iter() {
+        let recommendation = recommend_options(security, 1 << 20, 2, 2).unwrap();
+        assert!(recommendation.security_level >= security);
+        assert_eq!(8, recommendation.blowup_factor);
+
+        let options = recommendation.to_proof_options();
+        let lde_domain_size = ((1 << 20) * recommendation.blowup_factor) as u64;
+        assert_eq!(
+            recommendation.security_level,
+            conjectured_security(&options, lde_domain_size)
+        );
+    }
+
+    let recommendation = recommend_options(96, 1 << 20, 2, 2).unwrap();
+    assert_eq!(HashFunction::Blake3_192, recommendation.hash_fn);
+    assert_eq!(FieldExtension::None, recommendation.field_extension);
+
+    let recommendation = recommend_options(128, 1 << 20, 2, 2).unwrap();
+    assert_eq!(HashFunction::Blake3_256, recommendation.hash_fn);
+    assert_eq!(FieldExtension::Quadratic, recommendation.field_extension);
+}
+
+#[test]
+fn recommend_options_blowup_follows_constraint_degree() {
+    assert_eq!(
+        8,
+        recommend_options(100, 1 << 10, 1, 3).unwrap().blowup_factor
+    );
+    assert_eq!(
+        16,
+        recommend_options(100, 1 << 10, 1, 9).unwrap().blowup_factor
+    );
+    assert!(recommend_options(100, 1 << 10, 1, 129).is_err());
+}
+
+#[test]
+fn recommend_options_invalid_inputs() {
+    assert!(recommend_options(160, 1 << 20, 2, 2).is_err());
+    assert!(recommend_options(100, 1000, 2, 2).is_err());
+    assert!(recommend_options(100, 1 << 20, 0, 2).is_err());
+    assert!(recommend_options(100, 1 << 20, 2, 0).is_err());
+}
+
+#[test]
+fn recommendation_matches_generated_proof() {
+    let trace_length = 1 << 10;
+    let recommendation = recommend_options(100, trace_length, 2, 1).unwrap();
+    let options = recommendation.to_proof_options();
+
+    // fib2 computes 2 terms of the sequence per step using 2 registers
+    let fib = FibExample::new(trace_length * 2, options.clone());
+    let proof = fib.prove();
+    assert_eq!(recommendation.security_level, proof.security_level(true));
+    assert!(proof.to_bytes().len() <= estimate_proof_size(&options, trace_length, 2));
+    assert!(fib.verify(proof).is_ok());
+}
@@ -41,6 +41,7 @@ const CYCLE_MASK: [BaseElement; CYCLE_LENGTH] = [
 // RESCUE AIR
 // ================================================================================================
 
+#[derive(Clone)]
 pub struct PublicInputs {
     pub seed: [BaseElement; 2],
     pub result: [BaseElement; 2],
@@ -63,6 +64,8 @@ impl Air for RescueAir {
     type BaseElement = BaseElement;
     type PublicInputs = PublicInputs;
 
+    const MAX_CONSTRAINT_DEGREE: usize = 3;
+
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
     fn new(trace_info: TraceInfo, pub_inputs: PublicInputs, options: ProofOptions) -> Self {
@@ -15,7 +15,7 @@ use winterfell::{
 mod rescue;
 
 mod air;
-use air::{build_trace, PublicInputs, RescueAir};
+pub use air::{build_trace, PublicInputs, RescueAir};
 
 #[cfg(test)]
 mod tests;
@@ -111,7 +111,7 @@ impl Example for RescueExample {
 
 // HELPER FUNCTIONS
 // ================================================================================================
-fn compute_hash_chain(seed: [BaseElement; 2], length: usize) -> [BaseElement; 2] {
+pub(crate) fn compute_hash_chain(seed: [BaseElement; 2], length: usize) -> [BaseElement; 2] {
     let mut values = seed;
     let mut result = [BaseElement::ZERO; 2];
     for _ in 0..length {
@@ -3,7 +3,11 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use winterfell::{FieldExtension, HashFunction, ProofOptions};
+use super::{build_trace, compute_hash_chain, PublicInputs, RescueAir};
+use winterfell::{
+    math::{fields::f128::BaseElement, FieldElement},
+    Air, FieldExtension, HashFunction, ProofOptions, ProverError, TraceInfo,
+};
 
 #[test]
 fn rescue_test_basic_proof_verification() {
@@ -23,11 +27,62 @@ fn rescue_test_basic_proof_verification_fail() {
     crate::tests::test_basic_proof_verification_fail(rescue_eg);
 }
 
+#[test]
+fn rescue_test_air_on_random_trace() {
+    let pub_inputs = PublicInputs {
+        seed: [BaseElement::ONE, BaseElement::ZERO],
+        result: [BaseElement::ZERO, BaseElement::ONE],
+    };
+    let air = RescueAir::new(TraceInfo::new(4, 128), pub_inputs, build_options(false));
+    let mut rng = rand::thread_rng();
+    assert!(crate::tests::test_air_on_random_trace::<_, BaseElement>(
+        &air, &mut rng
+    ));
+}
+
+#[test]
+fn rescue_test_prove_many() {
+    // prove hash chains for 4 different seeds with a single proof
+    let options = build_options(false);
+    let (traces, pub_inputs): (Vec<_>, Vec<_>) = (0..4u8)
+        .map(|i| {
+            let seed = [BaseElement::from(i), BaseElement::from(i + 1)];
+            let result = compute_hash_chain(seed, 8);
+            (build_trace(seed, 8), PublicInputs { seed, result })
+        })
+        .unzip();
+    let proof =
+        winterfell::prove_many::<RescueAir>(traces, pub_inputs.clone(), options.clone()).unwrap();
+    assert_eq!(512, proof.trace_length());
+    assert!(winterfell::verify_many::<RescueAir>(proof.clone(), pub_inputs.clone()).is_ok());
+
+    // the proof does not verify against a different result for any of the instances, or
+    // against a different order or number of instances
+    let mut wrong_inputs = pub_inputs.clone();
+    wrong_inputs[2].result[0] += BaseElement::ONE;
+    assert!(winterfell::verify_many::<RescueAir>(proof.clone(), wrong_inputs).is_err());
+    let mut wrong_inputs = pub_inputs.clone();
+    wrong_inputs.swap(0, 1);
+    assert!(winterfell::verify_many::<RescueAir>(proof.clone(), wrong_inputs).is_err());
+    assert!(winterfell::verify_many::<RescueAir>(proof, pub_inputs[..2].to_vec()).is_err());
+
+    // all traces must have the same length
+    let seed = [BaseElement::ONE, BaseElement::ZERO];
+    let traces = vec![build_trace(seed, 8), build_trace(seed, 16)];
+    let result = winterfell::prove_many::<RescueAir>(traces, pub_inputs[..2].to_vec(), options);
+    assert_eq!(
+        Err(ProverError::InternalPanic(
+            "all stacked traces must have the same width and length".to_string()
+        )),
+        result
+    );
+}
+
 fn build_options(use_extension_field: bool) -> ProofOptions {
     let extension = if use_extension_field {
         FieldExtension::Quadratic
     } else {
         FieldExtension::None
     };
-    ProofOptions::new(28, 8, 0, HashFunction::Blake3_256, extension, 4, 256)
+    ProofOptions::new(36, 8, 0, HashFunction::Blake3_256, extension, 4, 256)
 }
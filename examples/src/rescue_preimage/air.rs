@@ -0,0 +1,148 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::utils::rescue::{self, CYCLE_LENGTH, NUM_ROUNDS, STATE_WIDTH};
+use winterfell::{
+    math::{fields::f128::BaseElement, FieldElement},
+    Air, AirContext, Assertion, ByteWriter, EvaluationFrame, ExecutionTrace, ProofOptions,
+    Serializable, TraceInfo, TransitionConstraintDegree,
+};
+
+// CONSTANTS
+// ================================================================================================
+
+/// The trace consists of the Rescue state only: 4 rate registers (of which the first 2 hold the
+/// preimage at the first step and the hash at the last step) and 2 capacity registers.
+const TRACE_WIDTH: usize = STATE_WIDTH;
+
+/// Number of elements in the preimage (and in the resulting hash).
+const PREIMAGE_SIZE: usize = 2;
+
+/// Specifies steps on which Rescue round function is applied; a single permutation requires
+/// 7 rounds, and thus, occupies 8 steps of the trace.
+const CYCLE_MASK: [BaseElement; CYCLE_LENGTH] = [
+    BaseElement::ONE,
+    BaseElement::ONE,
+    BaseElement::ONE,
+    BaseElement::ONE,
+    BaseElement::ONE,
+    BaseElement::ONE,
+    BaseElement::ONE,
+    BaseElement::ZERO,
+];
+
+// RESCUE PREIMAGE AIR
+// ================================================================================================
+
+pub struct PublicInputs {
+    pub hash: [BaseElement; PREIMAGE_SIZE],
+}
+
+impl Serializable for PublicInputs {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write(&self.hash[..]);
+    }
+}
+
+/// AIR for proving knowledge of a preimage `x` such that Rescue(`x`) = `y`, where `y` is a
+/// public input.
+///
+/// Each row of the execution trace holds the state of Rescue permutation, and each transition
+/// applies a single round of the permutation. The preimage itself is not constrained by any
+/// assertions: the verifier only checks that the state starts with padded input (the registers
+/// not occupied by the preimage are zeros), and that the hash in the final state equals `y`.
+///
+/// Note that Winterfell proofs are not zero-knowledge, and thus, the proof may leak some
+/// information about the preimage.
+pub struct RescuePreimageAir {
+    context: AirContext<BaseElement>,
+    hash: [BaseElement; PREIMAGE_SIZE],
+}
+
+impl Air for RescuePreimageAir {
+    type BaseElement = BaseElement;
+    type PublicInputs = PublicInputs;
+
+    const MAX_CONSTRAINT_DEGREE: usize = 5;
+
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    fn new(trace_info: TraceInfo, pub_inputs: PublicInputs, options: ProofOptions) -> Self {
+        let degrees = vec![TransitionConstraintDegree::with_cycles(5, vec![CYCLE_LENGTH]); 6];
+        assert_eq!(TRACE_WIDTH, trace_info.width());
+        RescuePreimageAir {
+            context: AirContext::new(trace_info, degrees, options),
+            hash: pub_inputs.hash,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseElement> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseElement>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+        // expected state width is 6 field elements
+        debug_assert_eq!(TRACE_WIDTH, current.len());
+        debug_assert_eq!(TRACE_WIDTH, next.len());
+
+        // split periodic values into hash_flag and Rescue round constants
+        let hash_flag = periodic_values[0];
+        let ark = &periodic_values[1..];
+
+        // when hash_flag = 1, constraints for Rescue round are enforced; hash_flag = 0 only for
+        // the transition from the last step of the trace, which is not constrained
+        rescue::enforce_round(result, current, next, ark, hash_flag);
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseElement>> {
+        // the initial state must contain the preimage padded with zeros, and the first two
+        // registers of the final state must contain the hash
+        let last_step = self.trace_length() - 1;
+        let mut assertions = (PREIMAGE_SIZE..TRACE_WIDTH)
+            .map(|register| Assertion::single(register, 0, BaseElement::ZERO))
+            .collect::<Vec<_>>();
+        for (register, &value) in self.hash.iter().enumerate() {
+            assertions.push(Assertion::single(register, last_step, value));
+        }
+        assertions
+    }
+
+    fn get_periodic_column_values(&self) -> Vec<Vec<Self::BaseElement>> {
+        let mut result = vec![CYCLE_MASK.to_vec()];
+        result.append(&mut rescue::get_round_constants());
+        result
+    }
+}
+
+// TRACE GENERATOR
+// ================================================================================================
+
+pub fn build_trace(preimage: [BaseElement; PREIMAGE_SIZE]) -> ExecutionTrace<BaseElement> {
+    // a single Rescue permutation fits into a single cycle
+    let mut trace = ExecutionTrace::new(TRACE_WIDTH, CYCLE_LENGTH);
+
+    trace.fill(
+        |state| {
+            // initialize the state with the preimage padded with zeros
+            state[..PREIMAGE_SIZE].copy_from_slice(&preimage);
+            state[PREIMAGE_SIZE..].fill(BaseElement::ZERO);
+        },
+        |step, state| {
+            // apply a single round of Rescue permutation on each of the first 7 steps; the
+            // state after the last round contains the hash in the first two registers
+            debug_assert!(step < NUM_ROUNDS);
+            rescue::apply_round(state, step);
+        },
+    );
+
+    trace
+}
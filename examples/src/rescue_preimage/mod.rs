@@ -0,0 +1,98 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{
+    utils::rescue::{Hash, Rescue128},
+    Example, ExampleOptions,
+};
+use log::debug;
+use std::time::Instant;
+use winterfell::{
+    math::{fields::f128::BaseElement, log2, FieldElement},
+    ProofOptions, StarkProof, VerifierError,
+};
+
+mod air;
+pub(crate) use air::{build_trace, PublicInputs, RescuePreimageAir};
+
+#[cfg(test)]
+mod tests;
+
+// RESCUE PREIMAGE EXAMPLE
+// ================================================================================================
+
+pub fn get_example(options: ExampleOptions) -> Box<dyn Example> {
+    Box::new(RescuePreimageExample::new(
+        [BaseElement::new(42), BaseElement::new(43)],
+        options.to_proof_options(28, 8),
+    ))
+}
+
+pub struct RescuePreimageExample {
+    options: ProofOptions,
+    preimage: [BaseElement; 2],
+    hash: Hash,
+}
+
+impl RescuePreimageExample {
+    pub fn new(preimage: [BaseElement; 2], options: ProofOptions) -> RescuePreimageExample {
+        // compute the hash of the preimage using external implementation of Rescue hash
+        let now = Instant::now();
+        let hash = Rescue128::digest(&preimage);
+        debug!(
+            "Computed Rescue hash of the preimage in {} ms",
+            now.elapsed().as_millis(),
+        );
+
+        RescuePreimageExample {
+            options,
+            preimage,
+            hash,
+        }
+    }
+}
+
+// EXAMPLE IMPLEMENTATION
+// ================================================================================================
+
+impl Example for RescuePreimageExample {
+    fn prove(&self) -> StarkProof {
+        // generate the execution trace
+        debug!(
+            "Generating proof for knowing a preimage of a Rescue hash\n\
+            ---------------------"
+        );
+        let now = Instant::now();
+        let trace = build_trace(self.preimage);
+        let trace_length = trace.length();
+        debug!(
+            "Generated execution trace of {} registers and 2^{} steps in {} ms",
+            trace.width(),
+            log2(trace_length),
+            now.elapsed().as_millis()
+        );
+
+        // generate the proof; the preimage is not a part of public inputs
+        let pub_inputs = PublicInputs {
+            hash: self.hash.to_elements(),
+        };
+        winterfell::prove::<RescuePreimageAir>(trace, pub_inputs, self.options.clone()).unwrap()
+    }
+
+    fn verify(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        let pub_inputs = PublicInputs {
+            hash: self.hash.to_elements(),
+        };
+        winterfell::verify::<RescuePreimageAir>(proof, pub_inputs)
+    }
+
+    fn verify_with_wrong_inputs(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        let hash = self.hash.to_elements();
+        let pub_inputs = PublicInputs {
+            hash: [hash[0], hash[1] + BaseElement::ONE],
+        };
+        winterfell::verify::<RescuePreimageAir>(proof, pub_inputs)
+    }
+}
@@ -0,0 +1,59 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use winterfell::{
+    math::{fields::f128::BaseElement, FieldElement},
+    FieldExtension, HashFunction, ProofOptions,
+};
+
+#[test]
+fn rescue_preimage_test_basic_proof_verification() {
+    let rescue_eg = Box::new(super::RescuePreimageExample::new(
+        build_preimage(),
+        build_options(false),
+    ));
+    crate::tests::test_basic_proof_verification(rescue_eg);
+}
+
+#[test]
+fn rescue_preimage_test_basic_proof_verification_extension() {
+    let rescue_eg = Box::new(super::RescuePreimageExample::new(
+        build_preimage(),
+        build_options(true),
+    ));
+    crate::tests::test_basic_proof_verification(rescue_eg);
+}
+
+#[test]
+fn rescue_preimage_test_basic_proof_verification_fail() {
+    let rescue_eg = Box::new(super::RescuePreimageExample::new(
+        build_preimage(),
+        build_options(false),
+    ));
+    crate::tests::test_basic_proof_verification_fail(rescue_eg);
+}
+
+#[test]
+fn rescue_preimage_test_trace_computes_hash() {
+    let preimage = build_preimage();
+    let trace = super::build_trace(preimage);
+    let hash = super::Rescue128::digest(&preimage).to_elements();
+    let last_step = trace.length() - 1;
+    assert_eq!(hash[0], trace.get(0, last_step));
+    assert_eq!(hash[1], trace.get(1, last_step));
+}
+
+fn build_preimage() -> [BaseElement; 2] {
+    [BaseElement::new(42), BaseElement::ONE]
+}
+
+fn build_options(use_extension_field: bool) -> ProofOptions {
+    let extension = if use_extension_field {
+        FieldExtension::Quadratic
+    } else {
+        FieldExtension::None
+    };
+    ProofOptions::new(36, 8, 0, HashFunction::Blake3_256, extension, 4, 256)
+}
@@ -4,6 +4,16 @@
 // LICENSE file in the root directory of this source tree.
 
 use crate::Example;
+use rand::Rng;
+use winterfell::{
+    math::{fields::f128::BaseElement, FieldElement},
+    Air, AirContext, Assertion, EvaluationFrame, ProofOptions, StarkProof, TraceInfo,
+    TransitionConstraintDegree,
+};
+
+/// Number of random evaluation frames against which transition constraints are evaluated by
+/// [test_air_on_random_trace()].
+const NUM_RANDOM_FRAMES: usize = 16;
 
 pub fn test_basic_proof_verification(e: Box<dyn Example>) {
     let proof = e.prove();
@@ -15,3 +25,122 @@ pub fn test_basic_proof_verification_fail(e: Box<dyn Example>) {
     let verified = e.verify_with_wrong_inputs(proof);
     assert!(verified.is_err());
 }
+
+pub fn test_proof_format_versions(e: Box<dyn Example>) {
+    let proof = e.prove();
+    let mut bytes = proof.to_bytes();
+    assert_eq!(StarkProof::FORMAT_VERSION, bytes[0]);
+
+    // proofs of earlier versions are read, but they are verified against the transcript of
+    // their version, and thus, a relabeled proof should be rejected
+    bytes[0] = StarkProof::FORMAT_VERSION - 1;
+    let relabeled = StarkProof::from_bytes(&bytes).unwrap();
+    assert!(!relabeled.is_domain_separated());
+    assert!(e.verify(relabeled).is_err());
+
+    bytes[0] = 0;
+    let err = StarkProof::from_bytes(&bytes).unwrap_err();
+    assert!(err.to_string().contains("unsupported proof format version"));
+
+    bytes[0] = StarkProof::FORMAT_VERSION + 1;
+    let err = StarkProof::from_bytes(&bytes).unwrap_err();
+    assert!(err.to_string().contains("unsupported proof format version"));
+
+    bytes[0] = StarkProof::FORMAT_VERSION;
+    assert!(e.verify(StarkProof::from_bytes(&bytes).unwrap()).is_ok());
+}
+
+/// Returns true if each transition constraint of the `air` evaluates to a non-zero value for at
+/// least one of several random evaluation frames.
+///
+/// Transition constraints should be satisfied only by rows of an honest execution trace. A
+/// constraint which evaluates to zero for random rows (and random periodic values) does not
+/// constrain the trace at all, and thus, an AIR containing such a constraint may accept proofs
+/// of false statements.
+pub fn test_air_on_random_trace<A, E>(air: &A, rng: &mut impl Rng) -> bool
+where
+    A: Air,
+    E: FieldElement<BaseField = A::BaseElement>,
+{
+    let num_constraints = air.num_transition_constraints();
+    let num_periodic_columns = air.get_periodic_column_values().len();
+
+    let mut is_constrained = vec![false; num_constraints];
+    for _ in 0..NUM_RANDOM_FRAMES {
+        let frame = EvaluationFrame::<E>::from_rows(
+            rand_row(air.trace_width(), rng),
+            rand_row(air.trace_width(), rng),
+        );
+        let periodic_values = rand_row::<E>(num_periodic_columns, rng);
+
+        let mut result = vec![E::ZERO; num_constraints];
+        air.evaluate_transition(&frame, &periodic_values, &mut result);
+        for (flag, value) in is_constrained.iter_mut().zip(result) {
+            *flag |= value != E::ZERO;
+        }
+    }
+
+    is_constrained.into_iter().all(|flag| flag)
+}
+
+/// Returns a vector of `n` random field elements drawn from the specified `rng`.
+fn rand_row<E: FieldElement>(n: usize, rng: &mut impl Rng) -> Vec<E> {
+    (0..n)
+        .map(|_| loop {
+            let bytes = rng.gen::<[u8; 32]>();
+            if let Some(value) = E::from_random_bytes(&bytes[..E::VALUE_SIZE]) {
+                break value;
+            }
+        })
+        .collect()
+}
+
+// TESTS
+// ================================================================================================
+
+#[test]
+fn air_on_random_trace_with_unconstrained_register() {
+    // the second transition constraint of the AIR is satisfied by any rows
+    let air = UnconstrainedAir::new(TraceInfo::new(2, 8), (), ProofOptions::default_96bit());
+    let mut rng = rand::thread_rng();
+    assert!(!test_air_on_random_trace::<_, BaseElement>(&air, &mut rng));
+}
+
+/// Describes a Fibonacci-like computation in which the second register is not constrained.
+struct UnconstrainedAir {
+    context: AirContext<BaseElement>,
+}
+
+impl Air for UnconstrainedAir {
+    type BaseElement = BaseElement;
+    type PublicInputs = ();
+
+    const MAX_CONSTRAINT_DEGREE: usize = 1;
+
+    fn new(trace_info: TraceInfo, _pub_inputs: (), options: ProofOptions) -> Self {
+        let degrees = vec![TransitionConstraintDegree::new(1); 2];
+        UnconstrainedAir {
+            context: AirContext::new(trace_info, degrees, options),
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseElement> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement<BaseField = Self::BaseElement>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+        result[0] = next[0] - (current[0] + current[1]);
+        result[1] = E::ZERO;
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseElement>> {
+        vec![Assertion::single(0, 0, BaseElement::ONE)]
+    }
+}
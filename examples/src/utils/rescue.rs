@@ -266,7 +266,7 @@ pub fn get_round_constants() -> Vec<Vec<BaseElement>> {
 #[allow(clippy::needless_range_loop)]
 fn apply_sbox<E: FieldElement>(state: &mut [E]) {
     for i in 0..STATE_WIDTH {
-        state[i] = state[i].exp(ALPHA.into());
+        state[i] = state[i].pow_const::<ALPHA>();
     }
 }
 
@@ -317,7 +317,7 @@ fn apply_inv_mds<E: FieldElement + From<BaseElement>>(state: &mut [E]) {
 
 /// S-Box and Inverse S-Box powers;
 /// computed using algorithm 6 from <https://eprint.iacr.org/2020/1143.pdf>
-const ALPHA: u32 = 5;
+const ALPHA: u64 = 5;
 const INV_ALPHA: u128 = 272225893536750770770699646362995969229;
 
 /// Rescue MDS matrix
@@ -0,0 +1,155 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use winterfell::{
+    math::{fields::f128::BaseElement, FieldElement},
+    Air, AirContext, Assertion, ByteWriter, EvaluationFrame, ExecutionTrace, ProofOptions,
+    Serializable, TraceInfo, TransitionConstraintDegree,
+};
+
+// CONSTANTS
+// ================================================================================================
+
+/// The trace consists of 2 registers: the input tape and the accumulator.
+const TRACE_WIDTH: usize = 2;
+
+/// Minimum length of an execution trace.
+const MIN_TRACE_LENGTH: usize = 8;
+
+// PUBLIC INPUTS
+// ================================================================================================
+
+/// Public inputs of the VM: a variable number of values on the input tape of the program and
+/// the output of the program.
+pub struct PublicInputs {
+    pub inputs: Vec<u64>,
+    pub output: BaseElement,
+}
+
+impl Serializable for PublicInputs {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        // the number of inputs is written first to make sure that different sets of inputs
+        // always result in different serializations
+        target.write_u32(self.inputs.len() as u32);
+        target.write(&self.inputs);
+        target.write(self.output);
+    }
+}
+
+// VM AIR
+// ================================================================================================
+
+/// AIR for a minimal virtual machine which reads all values from its input tape and outputs
+/// their sum.
+///
+/// The first register of the execution trace holds the input tape (padded with zeros), and the
+/// second register holds the accumulator. At each step, the current value of the tape is added
+/// to the accumulator. The number of inputs is not fixed: the values on the tape are defined via
+/// a single consecutive assertion, and the output is asserted against the accumulator at the
+/// step immediately following the last input.
+pub struct VmAir {
+    context: AirContext<BaseElement>,
+    inputs: Vec<BaseElement>,
+    output: BaseElement,
+}
+
+impl Air for VmAir {
+    type BaseElement = BaseElement;
+    type PublicInputs = PublicInputs;
+
+    const MAX_CONSTRAINT_DEGREE: usize = 1;
+
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    fn new(trace_info: TraceInfo, pub_inputs: PublicInputs, options: ProofOptions) -> Self {
+        assert_eq!(TRACE_WIDTH, trace_info.width());
+        assert!(
+            !pub_inputs.inputs.is_empty(),
+            "at least one input must be provided"
+        );
+        assert!(
+            pub_inputs.inputs.len() < trace_info.length(),
+            "number of inputs must be smaller than trace length {}, but was {}",
+            trace_info.length(),
+            pub_inputs.inputs.len()
+        );
+        let degrees = vec![TransitionConstraintDegree::new(1)];
+        VmAir {
+            context: AirContext::new(trace_info, degrees, options),
+            inputs: to_elements(&pub_inputs.inputs),
+            output: pub_inputs.output,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseElement> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseElement>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+        // expected state width is 2 field elements
+        debug_assert_eq!(TRACE_WIDTH, current.len());
+        debug_assert_eq!(TRACE_WIDTH, next.len());
+
+        // acc' = acc + tape
+        result[0] = next[1] - (current[1] + current[0]);
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseElement>> {
+        // the tape must start with the inputs, and the accumulator must start with zero and
+        // contain the output once all inputs have been read; values on the tape past the last
+        // input do not affect the output, and thus, are not constrained
+        vec![
+            Assertion::consecutive(0, 0, self.inputs.clone()),
+            Assertion::single(1, 0, BaseElement::ZERO),
+            Assertion::single(1, self.inputs.len(), self.output),
+        ]
+    }
+}
+
+// TRACE GENERATOR
+// ================================================================================================
+
+/// Returns length of the execution trace needed to process the specified number of inputs.
+pub fn get_trace_length(num_inputs: usize) -> usize {
+    (num_inputs + 1).next_power_of_two().max(MIN_TRACE_LENGTH)
+}
+
+pub fn build_trace(inputs: &[u64]) -> ExecutionTrace<BaseElement> {
+    let tape = to_elements(inputs);
+    let mut trace = ExecutionTrace::new(TRACE_WIDTH, get_trace_length(inputs.len()));
+
+    trace.fill(
+        |state| {
+            state[0] = tape[0];
+            state[1] = BaseElement::ZERO;
+        },
+        |step, state| {
+            // add the current tape value to the accumulator and move to the next value on the
+            // tape; once all inputs have been read, the tape contains zeros
+            state[1] += state[0];
+            state[0] = tape.get(step + 1).copied().unwrap_or(BaseElement::ZERO);
+        },
+    );
+
+    trace
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Converts program inputs into field elements.
+pub fn to_elements(inputs: &[u64]) -> Vec<BaseElement> {
+    inputs
+        .iter()
+        .map(|&value| BaseElement::new(value as u128))
+        .collect()
+}
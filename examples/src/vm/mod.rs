@@ -0,0 +1,103 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{Example, ExampleOptions};
+use log::debug;
+use std::time::Instant;
+use winterfell::{
+    math::{fields::f128::BaseElement, log2, FieldElement},
+    ProofOptions, StarkProof, VerifierError,
+};
+
+mod air;
+pub(crate) use air::{build_trace, to_elements, PublicInputs, VmAir};
+
+#[cfg(test)]
+mod tests;
+
+// VM EXAMPLE
+// ================================================================================================
+
+pub fn get_example(options: ExampleOptions, num_inputs: usize) -> Box<dyn Example> {
+    Box::new(VmExample::new(
+        (1..=num_inputs as u64).collect(),
+        options.to_proof_options(28, 8),
+    ))
+}
+
+pub struct VmExample {
+    options: ProofOptions,
+    inputs: Vec<u64>,
+    output: BaseElement,
+}
+
+impl VmExample {
+    pub fn new(inputs: Vec<u64>, options: ProofOptions) -> VmExample {
+        assert!(!inputs.is_empty(), "at least one input must be provided");
+
+        // compute the output of the program
+        let now = Instant::now();
+        let output = to_elements(&inputs)
+            .into_iter()
+            .fold(BaseElement::ZERO, |acc, value| acc + value);
+        debug!(
+            "Computed the sum of {} inputs in {} ms",
+            inputs.len(),
+            now.elapsed().as_millis(),
+        );
+
+        VmExample {
+            options,
+            inputs,
+            output,
+        }
+    }
+}
+
+// EXAMPLE IMPLEMENTATION
+// ================================================================================================
+
+impl Example for VmExample {
+    fn prove(&self) -> StarkProof {
+        // generate the execution trace
+        debug!(
+            "Generating proof for summing {} inputs in a VM\n\
+            ---------------------",
+            self.inputs.len()
+        );
+        let now = Instant::now();
+        let trace = build_trace(&self.inputs);
+        let trace_length = trace.length();
+        debug!(
+            "Generated execution trace of {} registers and 2^{} steps in {} ms",
+            trace.width(),
+            log2(trace_length),
+            now.elapsed().as_millis()
+        );
+
+        // generate the proof
+        let pub_inputs = PublicInputs {
+            inputs: self.inputs.clone(),
+            output: self.output,
+        };
+        winterfell::prove::<VmAir>(trace, pub_inputs, self.options.clone()).unwrap()
+    }
+
+    fn verify(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        let pub_inputs = PublicInputs {
+            inputs: self.inputs.clone(),
+            output: self.output,
+        };
+        winterfell::verify::<VmAir>(proof, pub_inputs)
+    }
+
+    fn verify_with_wrong_inputs(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        let pub_inputs = PublicInputs {
+            inputs: self.inputs.clone(),
+            output: self.output + BaseElement::ONE,
+        };
+        winterfell::verify::<VmAir>(proof, pub_inputs)
+    }
+}
@@ -0,0 +1,63 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use winterfell::{
+    math::fields::f128::BaseElement, FieldExtension, HashFunction, ProofOptions, Serializable,
+};
+
+#[test]
+fn vm_test_basic_proof_verification() {
+    let vm = Box::new(super::VmExample::new(vec![1, 2, 3], build_options(false)));
+    crate::tests::test_basic_proof_verification(vm);
+}
+
+#[test]
+fn vm_test_basic_proof_verification_extension() {
+    let vm = Box::new(super::VmExample::new(vec![1, 2, 3], build_options(true)));
+    crate::tests::test_basic_proof_verification(vm);
+}
+
+#[test]
+fn vm_test_basic_proof_verification_fail() {
+    let vm = Box::new(super::VmExample::new(vec![1, 2, 3], build_options(false)));
+    crate::tests::test_basic_proof_verification_fail(vm);
+}
+
+#[test]
+fn vm_test_variable_number_of_inputs() {
+    for num_inputs in [1, 7, 8, 20] {
+        let inputs = (0..num_inputs).map(|i| i * 1000 + 1).collect::<Vec<u64>>();
+        let trace = super::build_trace(&inputs);
+        assert_eq!(
+            super::air::get_trace_length(num_inputs as usize),
+            trace.length()
+        );
+
+        let vm = Box::new(super::VmExample::new(inputs, build_options(false)));
+        crate::tests::test_basic_proof_verification(vm);
+    }
+}
+
+#[test]
+fn vm_test_public_inputs_serialization() {
+    // the number of inputs must be written before the inputs
+    let pub_inputs = super::PublicInputs {
+        inputs: vec![1, 2],
+        output: BaseElement::new(3),
+    };
+    let bytes = pub_inputs.to_bytes();
+    assert_eq!(4 + 2 * 8 + 16, bytes.len());
+    assert_eq!(2u32.to_le_bytes(), bytes[..4]);
+    assert_eq!(1u64.to_le_bytes(), bytes[4..12]);
+}
+
+fn build_options(use_extension_field: bool) -> ProofOptions {
+    let extension = if use_extension_field {
+        FieldExtension::Quadratic
+    } else {
+        FieldExtension::None
+    };
+    ProofOptions::new(36, 8, 0, HashFunction::Blake3_256, extension, 4, 256)
+}
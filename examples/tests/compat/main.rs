@@ -0,0 +1,213 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Verifies known-good proofs for the Fibonacci and Rescue hash chain examples.
+//!
+//! Test vectors are read from `vectors.json` in this directory; if a change to the prover or
+//! the verifier makes any of these proofs invalid, proofs generated by previous versions of the
+//! library can no longer be verified. The vectors can be re-generated by running
+//! `cargo run --example gen_vectors` from the examples crate, but this should be done only when
+//! the change in the proof format is intentional; such a change must also increment
+//! `StarkProof::FORMAT_VERSION`.
+//!
+//! Proofs of version 2 of the proof format, which were generated before domain separation tags
+//! were added to the proof transcript, are kept in `legacy_vectors.json`. Similarly,
+//! `baseline_vectors.json` contains unversioned proofs generated by version 0.2.0 of the library;
+//! these are migrated via `StarkProof::migrate_v1_to_v2()` before being verified. Proofs of both
+//! kinds are expected to be accepted by the current verifier.
+
+use examples::{fibonacci::fib2, rescue};
+use serde_json::Value;
+use std::convert::TryFrom;
+use winterfell::{
+    math::{fields::f128::BaseElement, FieldElement},
+    Serializable, StarkProof, VerifierError,
+};
+
+const VECTORS: &str = include_str!("vectors.json");
+const LEGACY_VECTORS: &str = include_str!("legacy_vectors.json");
+const BASELINE_VECTORS: &str = include_str!("baseline_vectors.json");
+
+// TESTS
+// ================================================================================================
+
+#[test]
+fn compat_known_good_vectors() {
+    let vectors = read_vectors(VECTORS);
+    assert!(vectors.iter().any(|v| v.example == "fib2"));
+    assert!(vectors.iter().any(|v| v.example == "rescue"));
+
+    for vector in vectors {
+        assert_eq!(StarkProof::FORMAT_VERSION, vector.proof[0]);
+        let proof = StarkProof::from_bytes(&vector.proof).unwrap();
+        assert_eq!(
+            vector.options,
+            proof.options().to_bytes(),
+            "options of {} proof do not match the test vector",
+            vector.example
+        );
+
+        let pub_inputs = parse_elements(&vector.pub_inputs);
+        let result = verify_vector(&vector.example, proof, &pub_inputs);
+        assert!(
+            result.is_ok(),
+            "failed to verify {} proof of size {}: {}",
+            vector.example,
+            vector.size,
+            result.unwrap_err()
+        );
+    }
+}
+
+#[test]
+fn compat_vectors_reject_wrong_inputs() {
+    for vector in read_vectors(VECTORS) {
+        let proof = StarkProof::from_bytes(&vector.proof).unwrap();
+        let mut pub_inputs = parse_elements(&vector.pub_inputs);
+        let last = pub_inputs.len() - 1;
+        pub_inputs[last] += BaseElement::ONE;
+
+        let result = verify_vector(&vector.example, proof, &pub_inputs);
+        assert!(result.is_err());
+    }
+}
+
+#[test]
+fn compat_legacy_vectors() {
+    let vectors = read_vectors(LEGACY_VECTORS);
+    assert!(!vectors.is_empty());
+
+    for vector in vectors {
+        assert_eq!(2, vector.proof[0]);
+        let proof = StarkProof::from_bytes(&vector.proof).unwrap();
+        assert!(!proof.is_domain_separated());
+        assert_eq!(vector.proof, proof.to_bytes());
+
+        let pub_inputs = parse_elements(&vector.pub_inputs);
+        let result = verify_vector(&vector.example, proof, &pub_inputs);
+        assert!(
+            result.is_ok(),
+            "failed to verify legacy {} proof of size {}: {}",
+            vector.example,
+            vector.size,
+            result.unwrap_err()
+        );
+
+        // legacy proofs are verified against the transcript without domain separation tags, and
+        // thus, the same proof relabeled with the current version should be rejected
+        let mut bytes = vector.proof.clone();
+        bytes[0] = StarkProof::FORMAT_VERSION;
+        let proof = StarkProof::from_bytes(&bytes).unwrap();
+        assert!(verify_vector(&vector.example, proof, &pub_inputs).is_err());
+    }
+}
+
+#[test]
+#[allow(deprecated)]
+fn compat_baseline_vectors_migration() {
+    let vectors = read_vectors(BASELINE_VECTORS);
+    assert!(vectors.iter().any(|v| v.example == "fib2"));
+    assert!(vectors.iter().any(|v| v.example == "rescue"));
+
+    for vector in vectors {
+        // unversioned proofs cannot be read without migration
+        assert!(StarkProof::from_bytes(&vector.proof).is_err());
+
+        let migrated = StarkProof::migrate_v1_to_v2(&vector.proof).unwrap();
+        assert_eq!(1, migrated[0]);
+        assert_eq!(vector.proof.len() + 2, migrated.len());
+        let proof = StarkProof::from_bytes(&migrated).unwrap();
+        assert_eq!(
+            vector.options,
+            proof.options().to_bytes()[..vector.options.len()]
+        );
+
+        let pub_inputs = parse_elements(&vector.pub_inputs);
+        let result = verify_vector(&vector.example, proof, &pub_inputs);
+        assert!(
+            result.is_ok(),
+            "failed to verify migrated {} proof of size {}: {}",
+            vector.example,
+            vector.size,
+            result.unwrap_err()
+        );
+
+        // version 1 proofs could not specify a FRI remainder encoding
+        let flags_index = migrated
+            .windows(vector.options.len())
+            .position(|window| window == vector.options.as_slice())
+            .unwrap()
+            + vector.options.len();
+        let mut altered = migrated.clone();
+        altered[flags_index] = 0b10;
+        assert!(StarkProof::from_bytes(&altered).is_err());
+
+        // migrated proofs are rejected for wrong public inputs as well
+        let proof = StarkProof::from_bytes(&migrated).unwrap();
+        let mut pub_inputs = pub_inputs;
+        pub_inputs[0] += BaseElement::ONE;
+        assert!(verify_vector(&vector.example, proof, &pub_inputs).is_err());
+    }
+
+    // truncated proofs cannot be migrated
+    let vectors = read_vectors(BASELINE_VECTORS);
+    assert!(StarkProof::migrate_v1_to_v2(&vectors[0].proof[..20]).is_err());
+}
+
+// TEST VECTORS
+// ================================================================================================
+
+struct TestVector {
+    example: String,
+    size: u64,
+    options: Vec<u8>,
+    pub_inputs: Vec<u8>,
+    proof: Vec<u8>,
+}
+
+fn read_vectors(source: &str) -> Vec<TestVector> {
+    let vectors: Value = serde_json::from_str(source).unwrap();
+    vectors
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|vector| TestVector {
+            example: vector["example"].as_str().unwrap().to_string(),
+            size: vector["size"].as_u64().unwrap(),
+            options: read_hex(vector, "options"),
+            pub_inputs: read_hex(vector, "pub_inputs"),
+            proof: read_hex(vector, "proof"),
+        })
+        .collect()
+}
+
+fn verify_vector(
+    example: &str,
+    proof: StarkProof,
+    pub_inputs: &[BaseElement],
+) -> Result<(), VerifierError> {
+    match example {
+        "fib2" => winterfell::verify::<fib2::FibAir>(proof, pub_inputs[0]),
+        "rescue" => {
+            let pub_inputs = rescue::PublicInputs {
+                seed: [pub_inputs[0], pub_inputs[1]],
+                result: [pub_inputs[2], pub_inputs[3]],
+            };
+            winterfell::verify::<rescue::RescueAir>(proof, pub_inputs)
+        }
+        example => panic!("unknown example: {}", example),
+    }
+}
+
+fn read_hex(vector: &Value, field: &str) -> Vec<u8> {
+    hex::decode(vector[field].as_str().unwrap()).unwrap()
+}
+
+fn parse_elements(bytes: &[u8]) -> Vec<BaseElement> {
+    bytes
+        .chunks(BaseElement::ELEMENT_BYTES)
+        .map(|chunk| BaseElement::try_from(chunk).unwrap())
+        .collect()
+}
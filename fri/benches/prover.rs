@@ -12,6 +12,7 @@ use winter_fri::{DefaultProverChannel, FriOptions, FriProver};
 
 static BATCH_SIZES: [usize; 3] = [65536, 131072, 262144];
 static BLOWUP_FACTOR: usize = 8;
+static NUM_QUERIES: usize = 64;
 
 pub fn build_layers(c: &mut Criterion) {
     let mut fri_group = c.benchmark_group("FRI prover");
@@ -46,7 +47,44 @@ pub fn build_layers(c: &mut Criterion) {
     }
 }
 
-criterion_group!(fri_prover_group, build_layers);
+pub fn build_proof(c: &mut Criterion) {
+    let mut fri_group = c.benchmark_group("FRI prover");
+    fri_group.sample_size(10);
+    fri_group.measurement_time(Duration::from_secs(10));
+
+    let options = FriOptions::new(BLOWUP_FACTOR, 4, 256);
+
+    for &domain_size in &BATCH_SIZES {
+        let evaluations = build_evaluations(domain_size);
+        let positions = rand_vector::<u64>(NUM_QUERIES)
+            .into_iter()
+            .map(|v| v as usize % domain_size)
+            .collect::<Vec<_>>();
+
+        fri_group.bench_with_input(
+            BenchmarkId::new("build_proof", domain_size),
+            &evaluations,
+            |b, e| {
+                b.iter_batched(
+                    || {
+                        let mut prover = FriProver::new(options.clone());
+                        let mut channel = DefaultProverChannel::<
+                            BaseElement,
+                            BaseElement,
+                            Blake3_256<BaseElement>,
+                        >::new(domain_size, NUM_QUERIES);
+                        prover.build_layers(&mut channel, e.clone());
+                        prover
+                    },
+                    |mut prover| prover.build_proof(&positions),
+                    BatchSize::LargeInput,
+                );
+            },
+        );
+    }
+}
+
+criterion_group!(fri_prover_group, build_layers, build_proof);
 criterion_main!(fri_prover_group);
 
 // HELPER FUNCTIONS
@@ -76,7 +76,7 @@ mod verifier;
 pub use verifier::{DefaultVerifierChannel, FriVerifier, VerifierChannel};
 
 mod options;
-pub use options::FriOptions;
+pub use options::{FriOptions, FriRemainderEncoding};
 
 mod proof;
 pub use proof::FriProof;
@@ -85,3 +85,9 @@ mod errors;
 pub use errors::VerifierError;
 
 mod utils;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Domain separation tag absorbed into the public coin before each FRI layer commitment.
+pub const FRI_LAYER_TAG: [u8; 4] = *b"FRIL";
@@ -5,6 +5,25 @@
 
 use math::StarkField;
 
+// FRI REMAINDER ENCODING
+// ================================================================================================
+
+/// Defines how the remainder (the last FRI layer) is transmitted in a FRI proof.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum FriRemainderEncoding {
+    /// The remainder is transmitted as evaluations of the remainder polynomial over the domain of
+    /// the last FRI layer. The verifier interpolates the evaluations to check the degree of the
+    /// polynomial.
+    Evaluations,
+    /// The remainder is transmitted as coefficients of the remainder polynomial in the domain
+    /// of the last FRI layer (not shifted by the domain offset). Since the degree of the
+    /// polynomial is smaller than the size of the domain by the blowup factor, this reduces the
+    /// size of the remainder by the same factor. The verifier checks the degree by counting the
+    /// coefficients, and evaluates the polynomial over the domain to check the commitment and
+    /// query consistency.
+    Coefficients,
+}
+
 // FRI OPTIONS
 // ================================================================================================
 
@@ -14,6 +33,8 @@ pub struct FriOptions {
     folding_factor: usize,
     max_remainder_size: usize,
     blowup_factor: usize,
+    domain_offset: Option<u128>,
+    remainder_encoding: FriRemainderEncoding,
 }
 
 impl FriOptions {
@@ -46,17 +67,52 @@ impl FriOptions {
             folding_factor,
             max_remainder_size,
             blowup_factor,
+            domain_offset: None,
+            remainder_encoding: FriRemainderEncoding::Evaluations,
         }
     }
 
+    /// Returns these options with the evaluation domain offset set to the specified `offset`.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `offset` is ZERO or ONE.
+    /// * Elements of field `B` cannot be represented with 128 bits.
+    pub fn with_domain_offset<B: StarkField>(mut self, offset: B) -> Self {
+        assert!(
+            offset != B::ZERO && offset != B::ONE,
+            "domain offset must be a non-unit element of the multiplicative group"
+        );
+        self.domain_offset = Some(element_to_u128(offset));
+        self
+    }
+
+    /// Returns these options with the remainder (the last FRI layer) transmitted using the
+    /// specified `encoding`.
+    pub fn with_remainder_encoding(mut self, encoding: FriRemainderEncoding) -> Self {
+        self.remainder_encoding = encoding;
+        self
+    }
+
     /// Returns the offset by which the evaluation domain is shifted.
     ///
     /// The domain is shifted by multiplying every element in the domain by this offset.
     ///
-    /// Currently, the offset is hard-coded to be the primitive element in the field specified by
-    /// type parameter `B`.
+    /// Unless set via [with_domain_offset()](FriOptions::with_domain_offset), the offset is the
+    /// primitive element in the field specified by type parameter `B`.
     pub fn domain_offset<B: StarkField>(&self) -> B {
-        B::GENERATOR
+        match self.domain_offset {
+            Some(offset) => B::from(offset),
+            None => B::GENERATOR,
+        }
+    }
+
+    /// Returns the encoding used to transmit the remainder (the last FRI layer) in a proof.
+    ///
+    /// Unless set via [with_remainder_encoding()](FriOptions::with_remainder_encoding), the
+    /// remainder is transmitted as evaluations over the remainder domain.
+    pub fn remainder_encoding(&self) -> FriRemainderEncoding {
+        self.remainder_encoding
     }
 
     /// Returns the factor by which the degree of a polynomial is reduced with each FRI layer.
@@ -111,3 +167,18 @@ impl FriOptions {
         domain_size
     }
 }
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns canonical integer representation of the specified field element.
+fn element_to_u128<B: StarkField>(value: B) -> u128 {
+    let bytes = value.to_bytes();
+    assert!(
+        bytes.len() <= 16,
+        "field elements larger than 16 bytes are not supported"
+    );
+    let mut buf = [0u8; 16];
+    buf[..bytes.len()].copy_from_slice(&bytes);
+    u128::from_le_bytes(buf)
+}
@@ -3,6 +3,7 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
+use crate::{FriOptions, FriRemainderEncoding};
 use crypto::{BatchMerkleProof, ElementHasher, Hasher};
 use math::{log2, FieldElement};
 use utils::{
@@ -109,20 +110,24 @@ impl FriProof {
     /// Decomposes this proof into vectors of query values for each layer and corresponding Merkle
     /// authentication paths for each query (grouped into batch Merkle proofs).
     ///
+    /// Folding factor, blowup factor, and remainder encoding are taken from the specified
+    /// `options`.
+    ///
     /// # Panics
-    /// Panics if:
-    /// * `domain_size` is not a power of two.
-    /// * `folding_factor` is smaller than two or is not a power of two.
+    /// Panics if `domain_size` is not a power of two.
     ///
     /// # Errors
     /// Returns an error if:
-    /// * This proof is not consistent with the specified `domain_size` and `folding_factor`.
+    /// * This proof is not consistent with the specified `domain_size` and `options`.
+    /// * The remainder of this proof is encoded as evaluations and contains a number of elements
+    ///   other than the size of the remainder domain, or is encoded as coefficients and contains
+    ///   more elements than the size of the remainder domain divided by the blowup factor.
     /// * Any of the layers could not be parsed successfully.
     #[allow(clippy::type_complexity)]
     pub fn parse_layers<H, E>(
         self,
         mut domain_size: usize,
-        folding_factor: usize,
+        options: &FriOptions,
     ) -> Result<(Vec<Vec<E>>, Vec<BatchMerkleProof<H>>), DeserializationError>
     where
         E: FieldElement,
@@ -132,15 +137,44 @@ impl FriProof {
             domain_size.is_power_of_two(),
             "domain size must be a power of two"
         );
-        assert!(
-            folding_factor.is_power_of_two(),
-            "folding factor must be a power of two"
-        );
-        assert!(folding_factor > 1, "folding factor must be greater than 1");
+        let folding_factor = options.folding_factor();
+
+        // make sure the remainder is consistent with the size of the remainder domain before
+        // parsing any of the layers
+        let mut remainder_domain_size = domain_size;
+        for _ in 0..self.layers.len() {
+            remainder_domain_size /= folding_factor;
+        }
+        if remainder_domain_size == 0 {
+            return Err(DeserializationError::InvalidValue(format!(
+                "FRI proof with {} layers is inconsistent with domain size {}",
+                self.layers.len(),
+                domain_size
+            )));
+        }
+        let num_remainder_elements = self.num_remainder_elements::<E>();
+        match options.remainder_encoding() {
+            FriRemainderEncoding::Evaluations => {
+                if num_remainder_elements != remainder_domain_size {
+                    return Err(DeserializationError::InvalidValue(format!(
+                        "FRI remainder must contain exactly {} evaluations, but had {}",
+                        remainder_domain_size, num_remainder_elements,
+                    )));
+                }
+            }
+            FriRemainderEncoding::Coefficients => {
+                let max_coefficients = remainder_domain_size / options.blowup_factor();
+                if num_remainder_elements > max_coefficients {
+                    return Err(DeserializationError::InvalidValue(format!(
+                        "FRI remainder cannot contain more than {} coefficients, but had {}",
+                        max_coefficients, num_remainder_elements,
+                    )));
+                }
+            }
+        }
 
         let mut layer_proofs = Vec::new();
         let mut layer_queries = Vec::new();
-        let num_remainder_elements = self.num_remainder_elements::<E>();
 
         // parse all layers
         for (i, layer) in self.layers.into_iter().enumerate() {
@@ -155,14 +189,6 @@ impl FriProof {
             layer_queries.push(qv);
         }
 
-        // make sure the remaining domain size matches remainder length
-        if domain_size != num_remainder_elements {
-            return Err(DeserializationError::InvalidValue(format!(
-                "FRI remainder domain size must be {}, but was {}",
-                num_remainder_elements, domain_size,
-            )));
-        }
-
         Ok((layer_queries, layer_proofs))
     }
 
@@ -3,6 +3,7 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
+use crate::FRI_LAYER_TAG;
 use core::marker::PhantomData;
 use crypto::{Hasher, RandomCoin};
 use math::{FieldElement, StarkField};
@@ -52,6 +53,10 @@ pub trait ProverChannel<E: FieldElement> {
 
 /// Provides a default implementation of the [ProverChannel] trait.
 ///
+/// All state of the channel (the public coin and the list of layer commitments) is owned by the
+/// channel itself. Thus, a separate channel should be instantiated for every proving session,
+/// and channels for different sessions can be moved to and used from different threads.
+///
 /// Though this implementation is intended primarily for testing purposes, it can be used in
 /// production use cases as well.
 pub struct DefaultProverChannel<B: StarkField, E: FieldElement<BaseField = B>, H: Hasher> {
@@ -125,7 +130,7 @@ where
 
     fn commit_fri_layer(&mut self, layer_root: H::Digest) {
         self.commitments.push(layer_root);
-        self.public_coin.reseed(layer_root);
+        self.public_coin.reseed_with_tag(&FRI_LAYER_TAG, layer_root);
     }
 
     fn draw_fri_alpha(&mut self) -> E {
@@ -7,13 +7,16 @@ use crate::{
     folding::{apply_drp, fold_positions},
     proof::{FriProof, FriProofLayer},
     utils::hash_values,
-    FriOptions,
+    FriOptions, FriRemainderEncoding,
 };
 use core::marker::PhantomData;
 use crypto::{ElementHasher, Hasher, MerkleTree};
-use math::{FieldElement, StarkField};
+use math::{fft, FieldElement, StarkField};
 use utils::{collections::Vec, flatten_vector_elements, group_slice_elements, transpose_slice};
 
+#[cfg(feature = "concurrent")]
+use utils::iterators::*;
+
 mod channel;
 pub use channel::{DefaultProverChannel, ProverChannel};
 
@@ -223,7 +226,8 @@ where
     /// For each of the provided `positions`, corresponding evaluations from each of the layers
     /// (excluding the remainder layer) are recorded into the proof together with Merkle
     /// authentication paths from the root of layer commitment trees. For the remainder, we include
-    /// the whole set of evaluations into the proof.
+    /// either the whole set of evaluations or the coefficients of the remainder polynomial into the
+    /// proof, depending on the remainder encoding specified by the FRI options.
     ///
     /// # Panics
     /// Panics is the prover state is clean (no FRI layers have been build yet).
@@ -265,6 +269,16 @@ where
             }
         }
 
+        // when the remainder is sent as coefficients, interpolate it over the (unshifted) remainder
+        // domain; the degree of an honestly folded polynomial is smaller than the size of the
+        // domain by the blowup factor, and thus, the higher coefficients are all zeros
+        if self.options.remainder_encoding() == FriRemainderEncoding::Coefficients {
+            let inv_twiddles = fft::get_inv_twiddles::<B>(remainder.len());
+            fft::interpolate_poly(&mut remainder, &inv_twiddles);
+            let num_coefficients = (remainder.len() / self.options.blowup_factor()).max(1);
+            remainder.truncate(num_coefficients);
+        }
+
         // clear layers so that another proof can be generated
         self.reset();
 
@@ -277,16 +291,35 @@ where
 
 /// Builds a single proof layer by querying the evaluations of the passed in FRI layer at the
 /// specified positions.
+///
+/// When `concurrent` feature is enabled, Merkle authentication paths for query positions are
+/// looked up in multiple threads; the paths are then merged into a batch proof in the order of
+/// `positions`, and thus, the result is the same as for the single-threaded version.
 fn query_layer<B: StarkField, E: FieldElement<BaseField = B>, H: Hasher, const N: usize>(
     layer: &FriLayer<B, E, H>,
     positions: &[usize],
 ) -> FriProofLayer {
     // build Merkle authentication paths for all query positions
+    #[cfg(not(feature = "concurrent"))]
     let proof = layer
         .tree
         .prove_batch(positions)
         .expect("failed to generate a Merkle proof for FRI layer queries");
 
+    #[cfg(feature = "concurrent")]
+    let proof = {
+        let paths = positions
+            .par_iter()
+            .map(|&position| {
+                layer
+                    .tree
+                    .prove(position)
+                    .expect("failed to generate a Merkle proof for FRI layer queries")
+            })
+            .collect::<Vec<_>>();
+        crypto::BatchMerkleProof::<H>::from_paths(&paths, positions)
+    };
+
     // build a list of polynomial evaluations at each position; since evaluations in FRI layers
     // are stored in transposed form, a position refers to N evaluations which are committed
     // in a single leaf
@@ -6,11 +6,11 @@
 use super::{DefaultProverChannel, FriProver};
 use crate::{
     verifier::{DefaultVerifierChannel, FriVerifier},
-    FriOptions, FriProof, VerifierError,
+    FriOptions, FriProof, FriRemainderEncoding, VerifierError,
 };
 use crypto::{hashers::Blake3_256, Hasher, RandomCoin};
-use math::{fft, fields::f128::BaseElement, FieldElement};
-use utils::{collections::Vec, Deserializable, Serializable, SliceReader};
+use math::{fft, fields::f128::BaseElement, log2, FieldElement};
+use utils::{collections::Vec, string::ToString, Deserializable, Serializable, SliceReader};
 
 type Blake3 = Blake3_256<BaseElement>;
 
@@ -59,6 +59,266 @@ fn fri_prove_verify() {
     assert!(result.is_err());
 }
 
+#[test]
+fn fri_prove_verify_remainder_degree_too_high() {
+    let trace_length = 4096;
+    let lde_blowup = 8;
+
+    let options = FriOptions::new(lde_blowup, 4, 256);
+    let mut channel = build_prover_channel(trace_length, &options);
+    let evaluations = build_evaluations(trace_length, lde_blowup);
+
+    // build a proof for a polynomial of degree trace_length - 1; all layers are folded honestly,
+    // so the proof is consistent with the layer commitments
+    let mut prover = FriProver::new(options.clone());
+    prover.build_layers(&mut channel, evaluations.clone());
+    let positions = channel.draw_query_positions();
+    let proof = prover.build_proof(&positions);
+
+    // when a smaller degree is expected over the same domain, the remainder is the only place
+    // where the degree of the folded polynomial is checked; make sure the verifier rejects it
+    let commitments = channel.layer_commitments().to_vec();
+    let max_degree = trace_length - 257;
+    let result = verify_proof(
+        proof,
+        commitments,
+        &evaluations,
+        max_degree,
+        trace_length * lde_blowup,
+        &positions,
+        &options,
+    );
+    let num_layers = options.num_fri_layers(trace_length * lde_blowup);
+    let expected_degree = (max_degree + 1) / options.folding_factor().pow(num_layers as u32) - 1;
+    assert_eq!(
+        Err(VerifierError::RemainderDegreeMismatch(expected_degree)),
+        result
+    );
+}
+
+#[test]
+fn fri_prove_verify_remainder_coefficients() {
+    let trace_length = 4096;
+    let lde_blowup = 8;
+    let domain_size = trace_length * lde_blowup;
+
+    let options = FriOptions::new(lde_blowup, 4, 256)
+        .with_remainder_encoding(FriRemainderEncoding::Coefficients);
+    let mut channel = build_prover_channel(trace_length, &options);
+    let evaluations = build_evaluations(trace_length, lde_blowup);
+
+    let mut prover = FriProver::new(options.clone());
+    prover.build_layers(&mut channel, evaluations.clone());
+    let positions = channel.draw_query_positions();
+    let proof = prover.build_proof(&positions);
+
+    // the remainder should contain only the coefficients which can be non-zero
+    let remainder_size = options.fri_remainder_size(domain_size);
+    assert_eq!(
+        remainder_size / lde_blowup,
+        proof.num_remainder_elements::<BaseElement>()
+    );
+
+    // make sure the proof can be verified
+    let commitments = channel.layer_commitments().to_vec();
+    let max_degree = trace_length - 1;
+    let result = verify_proof(
+        proof.clone(),
+        commitments.clone(),
+        &evaluations,
+        max_degree,
+        domain_size,
+        &positions,
+        &options,
+    );
+    assert!(result.is_ok(), "{:}", result.err().unwrap());
+
+    // make sure the degree of the remainder is checked against the number of coefficients
+    let max_degree = trace_length - 257;
+    let result = verify_proof(
+        proof.clone(),
+        commitments.clone(),
+        &evaluations,
+        max_degree,
+        domain_size,
+        &positions,
+        &options,
+    );
+    let num_layers = options.num_fri_layers(domain_size);
+    let expected_degree = (max_degree + 1) / options.folding_factor().pow(num_layers as u32) - 1;
+    assert_eq!(
+        Err(VerifierError::RemainderDegreeMismatch(expected_degree)),
+        result
+    );
+
+    // make sure the proof cannot be parsed when the remainder is expected as evaluations
+    let result = proof
+        .parse_layers::<Blake3, BaseElement>(domain_size, &FriOptions::new(lde_blowup, 4, 256));
+    assert!(result.is_err());
+}
+
+#[test]
+fn fri_parse_layers_rejects_inconsistent_remainder() {
+    let trace_length = 4096;
+    let lde_blowup = 8;
+    let domain_size = trace_length * lde_blowup;
+
+    // evaluations: the remainder must contain exactly as many elements as the remainder domain
+    let options = FriOptions::new(lde_blowup, 4, 256);
+    let proof = build_proof(trace_length, &options);
+    let remainder_size = options.fri_remainder_size(domain_size);
+    let short_proof = resize_remainder(&proof, remainder_size / 2);
+    let err = short_proof
+        .parse_layers::<Blake3, BaseElement>(domain_size, &options)
+        .unwrap_err();
+    assert_eq!(
+        format!(
+            "FRI remainder must contain exactly {} evaluations, but had {}",
+            remainder_size,
+            remainder_size / 2
+        ),
+        err.to_string()
+    );
+    assert!(proof
+        .parse_layers::<Blake3, BaseElement>(domain_size, &options)
+        .is_ok());
+
+    // coefficients: the remainder cannot contain more elements than the remainder domain divided
+    // by the blowup factor
+    let options = options.with_remainder_encoding(FriRemainderEncoding::Coefficients);
+    let proof = build_proof(trace_length, &options);
+    let max_coefficients = remainder_size / lde_blowup;
+    let long_proof = resize_remainder(&proof, max_coefficients * 2);
+    let err = long_proof
+        .parse_layers::<Blake3, BaseElement>(domain_size, &options)
+        .unwrap_err();
+    assert_eq!(
+        format!(
+            "FRI remainder cannot contain more than {} coefficients, but had {}",
+            max_coefficients,
+            max_coefficients * 2
+        ),
+        err.to_string()
+    );
+    let short_proof = resize_remainder(&proof, max_coefficients / 2);
+    assert!(short_proof
+        .parse_layers::<Blake3, BaseElement>(domain_size, &options)
+        .is_ok());
+}
+
+#[test]
+fn fri_layer_proofs_are_compressed() {
+    let trace_length = 4096;
+    let lde_blowup = 8;
+    let folding_factor = 4;
+    let domain_size = trace_length * lde_blowup;
+
+    let options = FriOptions::new(lde_blowup, folding_factor, 256);
+    let mut channel = build_prover_channel(trace_length, &options);
+    let evaluations = build_evaluations(trace_length, lde_blowup);
+
+    let mut prover = FriProver::new(options.clone());
+    prover.build_layers(&mut channel, evaluations);
+    let positions = channel.draw_query_positions();
+    let proof = prover.build_proof(&positions);
+
+    // authentication paths in each layer are aggregated into a batch Merkle proof which removes
+    // duplicate internal nodes; make sure this is smaller than naively concatenated paths
+    let (layer_queries, layer_proofs) = proof
+        .parse_layers::<Blake3, BaseElement>(domain_size, &options)
+        .unwrap();
+    let mut layer_domain_size = domain_size;
+    for (queries, merkle_proof) in layer_queries.iter().zip(layer_proofs.iter()) {
+        let num_leaves = queries.len() / folding_factor;
+        let tree_depth = log2(layer_domain_size / folding_factor) as usize;
+        let naive_size = num_leaves * tree_depth * 32;
+        assert!(merkle_proof.serialize_nodes().len() < naive_size);
+        layer_domain_size /= folding_factor;
+    }
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn fri_concurrent_proving_sessions() {
+    let trace_length = 1024;
+    let lde_blowup = 8;
+    let options = FriOptions::new(lde_blowup, 4, 256);
+
+    // each session owns its channel; the channels are created on the main thread and moved into
+    // the proving threads to make sure they can be sent across threads
+    let handles = (0..2u128)
+        .map(|session| {
+            let options = options.clone();
+            let mut channel = build_prover_channel(trace_length, &options);
+            std::thread::spawn(move || {
+                let evaluations = build_evaluations_with_shift(trace_length, lde_blowup, session);
+                let mut prover = FriProver::new(options.clone());
+                prover.build_layers(&mut channel, evaluations.clone());
+                let positions = channel.draw_query_positions();
+                let proof = prover.build_proof(&positions);
+
+                let commitments = channel.layer_commitments().to_vec();
+                verify_proof(
+                    proof,
+                    commitments.clone(),
+                    &evaluations,
+                    trace_length - 1,
+                    trace_length * lde_blowup,
+                    &positions,
+                    &options,
+                )
+                .map(|_| commitments)
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let results = handles
+        .into_iter()
+        .map(|handle| handle.join().expect("proving thread panicked"))
+        .collect::<Vec<_>>();
+    for result in results.iter() {
+        assert!(result.is_ok(), "{:}", result.as_ref().err().unwrap());
+    }
+
+    // different inputs must result in different layer commitments
+    assert_ne!(results[0], results[1]);
+}
+
+#[test]
+fn fri_layer_alphas_are_layer_specific() {
+    let trace_length = 1024;
+    let lde_blowup = 8;
+    let evaluations = build_evaluations(trace_length, lde_blowup);
+
+    // a smaller max remainder size results in more FRI layers; a fresh alpha is drawn for every
+    // layer, and the verifier must replay the same sequence of draws to accept the proof
+    let mut proofs = Vec::new();
+    for &max_remainder_size in [256, 64].iter() {
+        let options = FriOptions::new(lde_blowup, 4, max_remainder_size);
+        let mut channel = build_prover_channel(trace_length, &options);
+        let mut prover = FriProver::new(options.clone());
+        prover.build_layers(&mut channel, evaluations.clone());
+        let positions = channel.draw_query_positions();
+        let proof = prover.build_proof(&positions);
+
+        let commitments = channel.layer_commitments().to_vec();
+        let result = verify_proof(
+            proof.clone(),
+            commitments,
+            &evaluations,
+            trace_length - 1,
+            trace_length * lde_blowup,
+            &positions,
+            &options,
+        );
+        assert!(result.is_ok(), "{:}", result.err().unwrap());
+        proofs.push((proof.num_layers(), proof.to_bytes()));
+    }
+
+    assert_ne!(proofs[0].0, proofs[1].0);
+    assert_ne!(proofs[0].1, proofs[1].1);
+}
+
 // TEST UTILS
 // ================================================================================================
 
@@ -69,8 +329,42 @@ pub fn build_prover_channel(
     DefaultProverChannel::new(trace_length * options.blowup_factor(), 32)
 }
 
+pub fn build_proof(trace_length: usize, options: &FriOptions) -> FriProof {
+    let mut channel = build_prover_channel(trace_length, options);
+    let evaluations = build_evaluations(trace_length, options.blowup_factor());
+    let mut prover = FriProver::new(options.clone());
+    prover.build_layers(&mut channel, evaluations);
+    let positions = channel.draw_query_positions();
+    prover.build_proof(&positions)
+}
+
+/// Returns a copy of the `proof` with the remainder truncated or extended with zeros to contain
+/// `num_elements` elements; the remainder is followed only by the number of partitions in a
+/// serialized proof.
+pub fn resize_remainder(proof: &FriProof, num_elements: usize) -> FriProof {
+    let bytes = proof.to_bytes();
+    let num_remainder_bytes = proof.num_remainder_elements::<BaseElement>() * 16;
+    let remainder_start = bytes.len() - 1 - num_remainder_bytes;
+    let mut remainder = bytes[remainder_start..bytes.len() - 1].to_vec();
+    remainder.resize(num_elements * 16, 0);
+
+    let mut result = bytes[..remainder_start - 2].to_vec();
+    result.extend_from_slice(&(remainder.len() as u16).to_le_bytes());
+    result.extend_from_slice(&remainder);
+    result.push(bytes[bytes.len() - 1]);
+    FriProof::read_from(&mut SliceReader::new(&result)).unwrap()
+}
+
 pub fn build_evaluations(trace_length: usize, lde_blowup: usize) -> Vec<BaseElement> {
-    let mut p = (0..trace_length as u128)
+    build_evaluations_with_shift(trace_length, lde_blowup, 0)
+}
+
+pub fn build_evaluations_with_shift(
+    trace_length: usize,
+    lde_blowup: usize,
+    shift: u128,
+) -> Vec<BaseElement> {
+    let mut p = (shift..trace_length as u128 + shift)
         .map(BaseElement::new)
         .collect::<Vec<_>>();
     let domain_size = trace_length * lde_blowup;
@@ -103,7 +397,7 @@ pub fn verify_proof(
         proof,
         commitments,
         domain_size,
-        options.folding_factor(),
+        options,
     )
     .unwrap();
     let mut coin = RandomCoin::<BaseElement, Blake3>::new(&[]);
@@ -3,7 +3,7 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use crate::{utils::hash_values, FriProof, VerifierError};
+use crate::{utils::hash_values, FriOptions, FriProof, VerifierError};
 use crypto::{BatchMerkleProof, ElementHasher, Hasher, MerkleTree};
 use math::FieldElement;
 use utils::{collections::Vec, group_vector_elements, transpose_slice, DeserializationError};
@@ -105,9 +105,25 @@ pub trait VerifierChannel<E: FieldElement> {
         commitment: &<<Self as VerifierChannel<E>>::Hasher as Hasher>::Digest,
     ) -> Result<Vec<E>, VerifierError> {
         let remainder = self.take_fri_remainder();
+        self.verify_remainder_commitment::<N>(&remainder, commitment)?;
+        Ok(remainder)
+    }
 
+    /// Checks whether the provided remainder evaluations (last FRI layer) are valid against the
+    /// specified commitment.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The `remainder` values cannot be used to construct a fully-balanced Merkle tree.
+    /// - If the root of the Merkle tree constructed from the `remainder` values does not match
+    ///   the specified `commitment`.
+    fn verify_remainder_commitment<const N: usize>(
+        &self,
+        remainder: &[E],
+        commitment: &<<Self as VerifierChannel<E>>::Hasher as Hasher>::Digest,
+    ) -> Result<(), VerifierError> {
         // build remainder Merkle tree
-        let remainder_values = transpose_slice(&remainder);
+        let remainder_values = transpose_slice(remainder);
         let hashed_values = hash_values::<Self::Hasher, E, N>(&remainder_values);
         let remainder_tree = MerkleTree::<Self::Hasher>::new(hashed_values)
             .map_err(|err| VerifierError::RemainderTreeConstructionFailed(format!("{}", err)))?;
@@ -117,7 +133,7 @@ pub trait VerifierChannel<E: FieldElement> {
             return Err(VerifierError::RemainderCommitmentMismatch);
         }
 
-        Ok(remainder)
+        Ok(())
     }
 }
 
@@ -146,18 +162,18 @@ where
     /// Builds a new verifier channel from the specified [FriProof].
     ///
     /// # Errors
-    /// Returns an error if the specified `proof` could not be parsed correctly.
+    /// Returns an error if the specified `proof` could not be parsed correctly or is not
+    /// consistent with the specified `domain_size` and `options`.
     pub fn new(
         proof: FriProof,
         layer_commitments: Vec<H::Digest>,
         domain_size: usize,
-        folding_factor: usize,
+        options: &FriOptions,
     ) -> Result<Self, DeserializationError> {
         let num_partitions = proof.num_partitions();
 
         let remainder = proof.parse_remainder()?;
-        let (layer_queries, layer_proofs) =
-            proof.parse_layers::<H, E>(domain_size, folding_factor)?;
+        let (layer_queries, layer_proofs) = proof.parse_layers::<H, E>(domain_size, options)?;
 
         Ok(DefaultVerifierChannel {
             layer_commitments,
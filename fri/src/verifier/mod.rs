@@ -5,9 +5,12 @@
 
 //! Contains an implementation of FRI verifier and associated components.
 
-use crate::{folding::fold_positions, utils::map_positions_to_indexes, FriOptions, VerifierError};
+use crate::{
+    folding::fold_positions, utils::map_positions_to_indexes, FriOptions, FriRemainderEncoding,
+    VerifierError, FRI_LAYER_TAG,
+};
 use core::{convert::TryInto, marker::PhantomData, mem};
-use crypto::{ElementHasher, RandomCoin};
+use crypto::{ElementHasher, PublicCoin};
 use math::{fft, log2, polynom, FieldElement, StarkField};
 use utils::collections::Vec;
 
@@ -98,9 +101,9 @@ where
     /// * `max_poly_degree` is inconsistent with the number of FRI layers read from the channel
     ///   and `folding_factor` specified in the `options` parameter.
     /// * An error was encountered while drawing a random α value from the coin.
-    pub fn new(
+    pub fn new<R: PublicCoin<BaseField = B, Hasher = H>>(
         channel: &mut C,
-        public_coin: &mut RandomCoin<B, H>,
+        public_coin: &mut R,
         options: FriOptions,
         max_poly_degree: usize,
     ) -> Result<Self, VerifierError> {
@@ -115,7 +118,7 @@ where
         let mut layer_alphas = Vec::with_capacity(layer_commitments.len());
         let mut max_degree_plus_1 = max_poly_degree + 1;
         for (depth, commitment) in layer_commitments.iter().enumerate() {
-            public_coin.reseed(*commitment);
+            public_coin.reseed_with_tag(&FRI_LAYER_TAG, *commitment);
             let alpha = public_coin.draw().map_err(VerifierError::PublicCoinError)?;
             layer_alphas.push(alpha);
 
@@ -307,7 +310,22 @@ where
         // read the remainder from the channel and make sure it matches with the columns
         // of the previous layer
         let remainder_commitment = self.layer_commitments.last().unwrap();
-        let remainder = channel.read_remainder::<N>(remainder_commitment)?;
+        let encoding = self.options.remainder_encoding();
+        let remainder = match encoding {
+            FriRemainderEncoding::Evaluations => {
+                channel.read_remainder::<N>(remainder_commitment)?
+            }
+            FriRemainderEncoding::Coefficients => {
+                // when the remainder is sent as coefficients, its degree can be checked directly;
+                // the coefficients are then evaluated over the remainder domain to check them
+                // against the commitment
+                let coefficients = channel.take_fri_remainder();
+                verify_remainder_coefficients(&coefficients, max_degree_plus_1 - 1, domain_size)?;
+                let remainder = evaluate_remainder(coefficients, domain_size);
+                channel.verify_remainder_commitment::<N>(&remainder, remainder_commitment)?;
+                remainder
+            }
+        };
         for (&position, evaluation) in positions.iter().zip(evaluations) {
             if remainder[position] != evaluation {
                 return Err(VerifierError::InvalidRemainderFolding);
@@ -315,14 +333,21 @@ where
         }
 
         // make sure the remainder values satisfy the degree
-        verify_remainder(remainder, max_degree_plus_1 - 1)
+        match encoding {
+            FriRemainderEncoding::Evaluations => verify_remainder(remainder, max_degree_plus_1 - 1),
+            FriRemainderEncoding::Coefficients => Ok(()),
+        }
     }
 }
 
 // REMAINDER DEGREE VERIFICATION
 // ================================================================================================
-/// Returns Ok(true) if values in the `remainder` slice represent evaluations of a polynomial
+/// Returns Ok(()) if values in the `remainder` slice represent evaluations of a polynomial
 /// with degree <= `max_degree` against a domain of the same size as `remainder`.
+///
+/// This is the only place where the degree of the folded polynomial is checked directly. Layer
+/// checks only ensure that the layers are folded consistently, and thus, a remainder of a degree
+/// higher than `max_degree` must be rejected here with [VerifierError::RemainderDegreeMismatch].
 fn verify_remainder<B: StarkField, E: FieldElement<BaseField = B>>(
     mut remainder: Vec<E>,
     max_degree: usize,
@@ -346,6 +371,37 @@ fn verify_remainder<B: StarkField, E: FieldElement<BaseField = B>>(
     }
 }
 
+/// Returns Ok(()) if the `coefficients` describe a polynomial with degree <= `max_degree` which
+/// can be evaluated over a domain of size `domain_size`.
+///
+/// Since the number of coefficients bounds the degree of the polynomial, the degree is checked
+/// directly against the number of coefficients.
+fn verify_remainder_coefficients<E: FieldElement>(
+    coefficients: &[E],
+    max_degree: usize,
+    domain_size: usize,
+) -> Result<(), VerifierError> {
+    if max_degree >= domain_size - 1 {
+        return Err(VerifierError::RemainderDegreeNotValid);
+    }
+    if coefficients.len() > max_degree + 1 {
+        return Err(VerifierError::RemainderDegreeMismatch(max_degree));
+    }
+    Ok(())
+}
+
+/// Evaluates the remainder polynomial defined by the `coefficients` over the (unshifted) domain
+/// of size `domain_size`, and returns the evaluations in natural order.
+fn evaluate_remainder<B: StarkField, E: FieldElement<BaseField = B>>(
+    mut coefficients: Vec<E>,
+    domain_size: usize,
+) -> Vec<E> {
+    coefficients.resize(domain_size, E::ZERO);
+    let twiddles = fft::get_twiddles::<B>(domain_size);
+    fft::evaluate_poly(&mut coefficients, &twiddles);
+    coefficients
+}
+
 // HELPER FUNCTIONS
 // ================================================================================================
 fn get_query_values<E: FieldElement, const N: usize>(
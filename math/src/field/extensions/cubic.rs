@@ -24,7 +24,7 @@ use utils::{
 /// polynomial defined by the implementation of the [ExtensibleField] trait, and α, β, γ are base
 /// field elements.
 #[repr(C)]
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Default)]
 pub struct CubeExtension<B: ExtensibleField<3>>(B, B, B);
 
 impl<B: ExtensibleField<3>> CubeExtension<B> {
@@ -434,6 +434,12 @@ mod tests {
         assert!(matches!(result, Err(DeserializationError::InvalidValue(_))));
     }
 
+    #[test]
+    fn hex_string() {
+        let r: CubeExtension<BaseElement> = rand_value();
+        assert_eq!(r, CubeExtension::from_hex_str(&r.to_hex_string()).unwrap());
+    }
+
     // UTILITIES
     // --------------------------------------------------------------------------------------------
 
@@ -9,4 +9,7 @@ pub use quadratic::QuadExtension;
 mod cubic;
 pub use cubic::CubeExtension;
 
-use super::{ExtensibleField, FieldElement};
+mod tower;
+pub use tower::TowerExtension;
+
+use super::{ExtensibleField, FieldElement, StarkField, TowerField};
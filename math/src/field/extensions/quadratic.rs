@@ -24,7 +24,7 @@ use utils::{
 /// defined by the implementation of the [ExtensibleField] trait, and α and β are base field
 /// elements.
 #[repr(C)]
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Default)]
 pub struct QuadExtension<B: ExtensibleField<2>>(B, B);
 
 impl<B: ExtensibleField<2>> QuadExtension<B> {
@@ -409,6 +409,17 @@ mod tests {
         assert!(matches!(result, Err(DeserializationError::InvalidValue(_))));
     }
 
+    #[test]
+    fn hex_string() {
+        let value = QuadExtension(BaseElement::new(1), BaseElement::new(2));
+        let expected = "0x0000000000000000000000000000000200000000000000000000000000000001";
+        assert_eq!(expected, value.to_hex_string());
+        assert_eq!(value, QuadExtension::from_hex_str(expected).unwrap());
+
+        let r: QuadExtension<BaseElement> = rand_value();
+        assert_eq!(r, QuadExtension::from_hex_str(&r.to_hex_string()).unwrap());
+    }
+
     // UTILITIES
     // --------------------------------------------------------------------------------------------
 
@@ -0,0 +1,387 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{FieldElement, StarkField, TowerField};
+use core::{
+    convert::TryFrom,
+    fmt,
+    marker::PhantomData,
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+    slice,
+};
+use utils::{
+    collections::Vec, AsBytes, ByteReader, ByteWriter, Deserializable, DeserializationError,
+    Randomizable, Serializable, SliceReader,
+};
+
+// TOWER EXTENSION FIELD
+// ================================================================================================
+
+/// Represents an element in a quadratic extension of a field which is itself an extension of a
+/// [StarkField](crate::StarkField).
+///
+/// The extension element is defined as α + β * y, where y is a root of the irreducible
+/// polynomial y<sup>2</sup> - ξ, ξ is the quadratic non-residue defined by the implementation of
+/// the [TowerField] trait, and α and β are elements of the underlying field E whose base field is
+/// B. For example, building this extension on top of a quadratic extension of a 64-bit field
+/// yields a degree 4 extension of the base field.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub struct TowerExtension<E: TowerField<BaseField = B>, B: StarkField>(E, E, PhantomData<B>);
+
+impl<B: StarkField, E: TowerField<BaseField = B>> TowerExtension<E, B> {
+    /// Returns a new extension element instantiated from the provided elements of the
+    /// underlying field.
+    pub fn new(a: E, b: E) -> Self {
+        Self(a, b, PhantomData)
+    }
+
+    /// Converts a vector of elements of the underlying field into a vector of elements in the
+    /// tower extension field by fusing two adjacent elements together. The output vector is half
+    /// the length of the source vector.
+    fn pairs_to_tower_vector(source: Vec<E>) -> Vec<Self> {
+        debug_assert!(
+            source.len() & 1 == 0,
+            "source vector length must be divisible by two, but was {}",
+            source.len()
+        );
+        let mut v = core::mem::ManuallyDrop::new(source);
+        let p = v.as_mut_ptr();
+        let len = v.len() / 2;
+        let cap = v.capacity() / 2;
+        unsafe { Vec::from_raw_parts(p as *mut Self, len, cap) }
+    }
+}
+
+impl<B: StarkField, E: TowerField<BaseField = B>> FieldElement for TowerExtension<E, B> {
+    type PositiveInteger = E::PositiveInteger;
+    type BaseField = B;
+
+    const ELEMENT_BYTES: usize = E::ELEMENT_BYTES * 2;
+    const IS_CANONICAL: bool = E::IS_CANONICAL;
+    const ZERO: Self = Self(E::ZERO, E::ZERO, PhantomData);
+    const ONE: Self = Self(E::ONE, E::ZERO, PhantomData);
+
+    #[inline]
+    fn inv(self) -> Self {
+        if self == Self::ZERO {
+            return self;
+        }
+
+        // (α + β * y)^-1 = (α - β * y) / (α^2 - ξ * β^2), where the denominator is the norm of
+        // the element in the underlying field
+        let norm = self.0.square() - E::tower_nonresidue() * self.1.square();
+        let norm_inv = norm.inv();
+        Self(self.0 * norm_inv, -self.1 * norm_inv, PhantomData)
+    }
+
+    #[inline]
+    fn conjugate(&self) -> Self {
+        Self(self.0, -self.1, PhantomData)
+    }
+
+    fn elements_as_bytes(elements: &[Self]) -> &[u8] {
+        unsafe {
+            slice::from_raw_parts(
+                elements.as_ptr() as *const u8,
+                elements.len() * Self::ELEMENT_BYTES,
+            )
+        }
+    }
+
+    unsafe fn bytes_as_elements(bytes: &[u8]) -> Result<&[Self], DeserializationError> {
+        // the check for memory alignment is delegated to the underlying field
+        let elements = E::bytes_as_elements(bytes)?;
+        if elements.len() & 1 == 1 {
+            return Err(DeserializationError::InvalidValue(format!(
+                "number of bytes ({}) does not divide into whole number of field elements",
+                bytes.len(),
+            )));
+        }
+
+        Ok(slice::from_raw_parts(
+            elements.as_ptr() as *const Self,
+            elements.len() / 2,
+        ))
+    }
+
+    fn zeroed_vector(n: usize) -> Vec<Self> {
+        // get twice the number of underlying elements, and re-interpret them as tower elements
+        let result = E::zeroed_vector(n * 2);
+        Self::pairs_to_tower_vector(result)
+    }
+
+    fn as_base_elements(elements: &[Self]) -> &[Self::BaseField] {
+        // re-interpret the elements as twice as many elements of the underlying field
+        let ptr = elements.as_ptr();
+        let len = elements.len() * 2;
+        E::as_base_elements(unsafe { slice::from_raw_parts(ptr as *const E, len) })
+    }
+}
+
+impl<B: StarkField, E: TowerField<BaseField = B>> Randomizable for TowerExtension<E, B> {
+    const VALUE_SIZE: usize = Self::ELEMENT_BYTES;
+
+    fn from_random_bytes(bytes: &[u8]) -> Option<Self> {
+        Self::try_from(bytes).ok()
+    }
+}
+
+impl<B: StarkField, E: TowerField<BaseField = B>> fmt::Display for TowerExtension<E, B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {})", self.0, self.1)
+    }
+}
+
+// OVERLOADED OPERATORS
+// ------------------------------------------------------------------------------------------------
+
+impl<B: StarkField, E: TowerField<BaseField = B>> Add for TowerExtension<E, B> {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self {
+        Self(self.0 + rhs.0, self.1 + rhs.1, PhantomData)
+    }
+}
+
+impl<B: StarkField, E: TowerField<BaseField = B>> AddAssign for TowerExtension<E, B> {
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs
+    }
+}
+
+impl<B: StarkField, E: TowerField<BaseField = B>> Sub for TowerExtension<E, B> {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, rhs: Self) -> Self {
+        Self(self.0 - rhs.0, self.1 - rhs.1, PhantomData)
+    }
+}
+
+impl<B: StarkField, E: TowerField<BaseField = B>> SubAssign for TowerExtension<E, B> {
+    #[inline]
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<B: StarkField, E: TowerField<BaseField = B>> Mul for TowerExtension<E, B> {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, rhs: Self) -> Self {
+        // uses Karatsuba multiplication and the fact that y^2 = ξ; this requires 3
+        // multiplications in the underlying field, plus a multiplication by ξ
+        let a0b0 = self.0 * rhs.0;
+        let a1b1 = self.1 * rhs.1;
+        Self(
+            a0b0 + E::tower_nonresidue() * a1b1,
+            (self.0 + self.1) * (rhs.0 + rhs.1) - a0b0 - a1b1,
+            PhantomData,
+        )
+    }
+}
+
+impl<B: StarkField, E: TowerField<BaseField = B>> MulAssign for TowerExtension<E, B> {
+    #[inline]
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs
+    }
+}
+
+impl<B: StarkField, E: TowerField<BaseField = B>> Div for TowerExtension<E, B> {
+    type Output = Self;
+
+    #[inline]
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self {
+        self * rhs.inv()
+    }
+}
+
+impl<B: StarkField, E: TowerField<BaseField = B>> DivAssign for TowerExtension<E, B> {
+    #[inline]
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs
+    }
+}
+
+impl<B: StarkField, E: TowerField<BaseField = B>> Neg for TowerExtension<E, B> {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self {
+        Self(-self.0, -self.1, PhantomData)
+    }
+}
+
+// TYPE CONVERSIONS
+// ------------------------------------------------------------------------------------------------
+
+impl<B: StarkField, E: TowerField<BaseField = B>> From<B> for TowerExtension<E, B> {
+    fn from(value: B) -> Self {
+        Self(E::from(value), E::ZERO, PhantomData)
+    }
+}
+
+impl<B: StarkField, E: TowerField<BaseField = B>> From<u128> for TowerExtension<E, B> {
+    fn from(value: u128) -> Self {
+        Self(E::from(value), E::ZERO, PhantomData)
+    }
+}
+
+impl<B: StarkField, E: TowerField<BaseField = B>> From<u64> for TowerExtension<E, B> {
+    fn from(value: u64) -> Self {
+        Self(E::from(value), E::ZERO, PhantomData)
+    }
+}
+
+impl<B: StarkField, E: TowerField<BaseField = B>> From<u32> for TowerExtension<E, B> {
+    fn from(value: u32) -> Self {
+        Self(E::from(value), E::ZERO, PhantomData)
+    }
+}
+
+impl<B: StarkField, E: TowerField<BaseField = B>> From<u16> for TowerExtension<E, B> {
+    fn from(value: u16) -> Self {
+        Self(E::from(value), E::ZERO, PhantomData)
+    }
+}
+
+impl<B: StarkField, E: TowerField<BaseField = B>> From<u8> for TowerExtension<E, B> {
+    fn from(value: u8) -> Self {
+        Self(E::from(value), E::ZERO, PhantomData)
+    }
+}
+
+impl<B: StarkField, E: TowerField<BaseField = B>> TryFrom<&[u8]> for TowerExtension<E, B> {
+    type Error = DeserializationError;
+
+    /// Converts a slice of bytes into a field element; returns error if the value encoded in bytes
+    /// is not a valid field element. The bytes are assumed to be in little-endian byte order.
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        if bytes.len() < Self::ELEMENT_BYTES {
+            return Err(DeserializationError::InvalidValue(format!(
+                "not enough bytes for a full field element; expected {} bytes, but was {} bytes",
+                Self::ELEMENT_BYTES,
+                bytes.len(),
+            )));
+        }
+        if bytes.len() > Self::ELEMENT_BYTES {
+            return Err(DeserializationError::InvalidValue(format!(
+                "too many bytes for a field element; expected {} bytes, but was {} bytes",
+                Self::ELEMENT_BYTES,
+                bytes.len(),
+            )));
+        }
+        let mut reader = SliceReader::new(bytes);
+        Self::read_from(&mut reader)
+    }
+}
+
+impl<B: StarkField, E: TowerField<BaseField = B>> AsBytes for TowerExtension<E, B> {
+    fn as_bytes(&self) -> &[u8] {
+        // TODO: take endianness into account
+        let self_ptr: *const Self = self;
+        unsafe { slice::from_raw_parts(self_ptr as *const u8, Self::ELEMENT_BYTES) }
+    }
+}
+
+// SERIALIZATION / DESERIALIZATION
+// ------------------------------------------------------------------------------------------------
+
+impl<B: StarkField, E: TowerField<BaseField = B>> Serializable for TowerExtension<E, B> {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.0.write_into(target);
+        self.1.write_into(target);
+    }
+}
+
+impl<B: StarkField, E: TowerField<BaseField = B>> Deserializable for TowerExtension<E, B> {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let value0 = E::read_from(source)?;
+        let value1 = E::read_from(source)?;
+        Ok(Self(value0, value1, PhantomData))
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{FieldElement, TowerExtension};
+    use crate::field::{f64::BaseElement, QuadExtension};
+    use rand_utils::rand_value;
+
+    type QuadElement = QuadExtension<BaseElement>;
+    type TowerElement = TowerExtension<QuadElement, BaseElement>;
+
+    // BASIC ALGEBRA
+    // --------------------------------------------------------------------------------------------
+
+    #[test]
+    fn add() {
+        // identity
+        let r: TowerElement = rand_value();
+        assert_eq!(r, r + TowerElement::ZERO);
+
+        // test random values
+        let r1: TowerElement = rand_value();
+        let r2: TowerElement = rand_value();
+
+        let expected = TowerElement::new(r1.0 + r2.0, r1.1 + r2.1);
+        assert_eq!(expected, r1 + r2);
+    }
+
+    #[test]
+    fn sub() {
+        // identity
+        let r: TowerElement = rand_value();
+        assert_eq!(r, r - TowerElement::ZERO);
+
+        // test random values
+        let r1: TowerElement = rand_value();
+        let r2: TowerElement = rand_value();
+
+        let expected = TowerElement::new(r1.0 - r2.0, r1.1 - r2.1);
+        assert_eq!(expected, r1 - r2);
+    }
+
+    // INITIALIZATION
+    // --------------------------------------------------------------------------------------------
+
+    #[test]
+    fn zeroed_vector() {
+        let result = TowerElement::zeroed_vector(4);
+        assert_eq!(4, result.len());
+        for element in result.into_iter() {
+            assert_eq!(TowerElement::ZERO, element);
+        }
+    }
+
+    // UTILITIES
+    // --------------------------------------------------------------------------------------------
+
+    #[test]
+    fn as_base_elements() {
+        let elements = vec![
+            TowerElement::new(
+                QuadExtension::new(BaseElement::new(1), BaseElement::new(2)),
+                QuadExtension::new(BaseElement::new(3), BaseElement::new(4)),
+            ),
+            TowerElement::new(
+                QuadExtension::new(BaseElement::new(5), BaseElement::new(6)),
+                QuadExtension::new(BaseElement::new(7), BaseElement::new(8)),
+            ),
+        ];
+
+        let expected = (1..=8).map(BaseElement::new).collect::<Vec<_>>();
+        assert_eq!(expected, TowerElement::as_base_elements(&elements));
+    }
+}
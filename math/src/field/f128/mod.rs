@@ -50,7 +50,7 @@ const ELEMENT_BYTES: usize = core::mem::size_of::<u128>();
 ///
 /// Internal values are stored in their canonical form in the range [0, M). The backing type is
 /// `u128`.
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Default)]
 pub struct BaseElement(u128);
 
 impl BaseElement {
@@ -58,7 +58,7 @@ impl BaseElement {
     /// the field modulus, modular reduction is silently performed. This function can also be used
     /// to initialize constants.
     pub const fn new(value: u128) -> Self {
-        BaseElement(if value < M { value } else { value - M })
+        BaseElement(reduce(value))
     }
 }
 
@@ -385,6 +385,7 @@ impl Deserializable for BaseElement {
 // ================================================================================================
 
 /// Computes (a + b) % m; a and b are assumed to be valid field elements.
+#[cfg(not(feature = "constant-time"))]
 fn add(a: u128, b: u128) -> u128 {
     let z = M - b;
     if a < z {
@@ -394,7 +395,17 @@ fn add(a: u128, b: u128) -> u128 {
     }
 }
 
+/// Computes (a + b) % m; a and b are assumed to be valid field elements.
+///
+/// This function does not branch on the values of a and b.
+#[cfg(feature = "constant-time")]
+fn add(a: u128, b: u128) -> u128 {
+    let (z, under) = a.overflowing_sub(M - b);
+    z.wrapping_add(M & mask128(under))
+}
+
 /// Computes (a - b) % m; a and b are assumed to be valid field elements.
+#[cfg(not(feature = "constant-time"))]
 fn sub(a: u128, b: u128) -> u128 {
     if a < b {
         M - b + a
@@ -403,48 +414,45 @@ fn sub(a: u128, b: u128) -> u128 {
     }
 }
 
+/// Computes (a - b) % m; a and b are assumed to be valid field elements.
+///
+/// This function does not branch on the values of a and b.
+#[cfg(feature = "constant-time")]
+fn sub(a: u128, b: u128) -> u128 {
+    let (z, under) = a.overflowing_sub(b);
+    z.wrapping_add(M & mask128(under))
+}
+
 /// Computes (a * b) % m; a and b are assumed to be valid field elements.
 fn mul(a: u128, b: u128) -> u128 {
     let (x0, x1, x2) = mul_128x64(a, (b >> 64) as u64); // x = a * b_hi
-    let (mut x0, mut x1, x2) = mul_reduce(x0, x1, x2); // x = x - (x >> 128) * m
-    if x2 == 1 {
-        // if there was an overflow beyond 128 bits, subtract
-        // modulus from the result to make sure it fits into
-        // 128 bits; this can potentially be removed in favor
-        // of checking overflow later
-        let (t0, t1) = sub_modulus(x0, x1); // x = x - m
-        x0 = t0;
-        x1 = t1;
-    }
+    let (x0, x1, x2) = mul_reduce(x0, x1, x2); // x = x - (x >> 128) * m
+
+    // if there was an overflow beyond 128 bits, subtract modulus from the result to make sure
+    // it fits into 128 bits; this can potentially be removed in favor of checking overflow later
+    let (x0, x1) = sub_modulus_if(x0, x1, x2); // x = x - m
 
     let (y0, y1, y2) = mul_128x64(a, b as u64); // y = a * b_lo
 
-    let (mut y1, carry) = add64_with_carry(y1, x0, 0); // y = y + (x << 64)
-    let (mut y2, y3) = add64_with_carry(y2, x1, carry);
-    if y3 == 1 {
-        // if there was an overflow beyond 192 bits, subtract
-        // modulus * 2^64 from the result to make sure it fits
-        // into 192 bits; this can potentially replace the
-        // previous overflow check (but needs to be proven)
-        let (t0, t1) = sub_modulus(y1, y2); // y = y - (m << 64)
-        y1 = t0;
-        y2 = t1;
-    }
+    let (y1, carry) = add64_with_carry(y1, x0, 0); // y = y + (x << 64)
+    let (y2, y3) = add64_with_carry(y2, x1, carry);
+
+    // if there was an overflow beyond 192 bits, subtract modulus * 2^64 from the result to make
+    // sure it fits into 192 bits; this can potentially replace the previous overflow check (but
+    // needs to be proven)
+    let (y1, y2) = sub_modulus_if(y1, y2, y3); // y = y - (m << 64)
 
-    let (mut z0, mut z1, z2) = mul_reduce(y0, y1, y2); // z = y - (y >> 128) * m
+    let (z0, z1, z2) = mul_reduce(y0, y1, y2); // z = y - (y >> 128) * m
 
     // make sure z is smaller than m
-    if z2 == 1 || (z1 == (M >> 64) as u64 && z0 >= (M as u64)) {
-        let (t0, t1) = sub_modulus(z0, z1); // z = z - m
-        z0 = t0;
-        z1 = t1;
-    }
+    let (z0, z1) = normalize(z0, z1, z2);
 
     ((z1 as u128) << 64) + (z0 as u128)
 }
 
 /// Computes y such that (x * y) % m = 1 except for when when x = 0; in such a case,
 /// 0 is returned; x is assumed to be a valid field element.
+#[cfg(not(feature = "constant-time"))]
 fn inv(x: u128) -> u128 {
     if x == 0 {
         return 0;
@@ -540,9 +548,102 @@ fn inv(x: u128) -> u128 {
     a
 }
 
+/// Computes y such that (x * y) % m = 1 except for when when x = 0; in such a case,
+/// 0 is returned; x is assumed to be a valid field element.
+///
+/// The inverse is computed as x^(m - 2) using a fixed sequence of squarings and multiplications
+/// which depends only on the modulus; this also maps 0 to 0 without checking x for zero.
+#[cfg(feature = "constant-time")]
+fn inv(x: u128) -> u128 {
+    let power = M - 2;
+    let mut result = 1;
+    for i in (0..128).rev() {
+        result = mul(result, result);
+        if (power >> i) & 1 == 1 {
+            result = mul(result, x);
+        }
+    }
+    result
+}
+
 // HELPER FUNCTIONS
 // ================================================================================================
 
+/// Reduces any value in [0, 2^128) range to [0, M) range.
+#[cfg(not(feature = "constant-time"))]
+const fn reduce(value: u128) -> u128 {
+    if value < M {
+        value
+    } else {
+        value - M
+    }
+}
+
+/// Reduces any value in [0, 2^128) range to [0, M) range without branching on the value.
+#[cfg(feature = "constant-time")]
+const fn reduce(value: u128) -> u128 {
+    let (z, under) = value.overflowing_sub(M);
+    z.wrapping_add(M & mask128(under))
+}
+
+/// Subtracts the modulus from a 128-bit value `a` if `flag` is 1; `flag` must be either 0 or 1.
+#[inline]
+#[cfg(not(feature = "constant-time"))]
+fn sub_modulus_if(a_lo: u64, a_hi: u64, flag: u64) -> (u64, u64) {
+    if flag == 1 {
+        sub_modulus(a_lo, a_hi)
+    } else {
+        (a_lo, a_hi)
+    }
+}
+
+/// Subtracts the modulus from a 128-bit value `a` if `flag` is 1; `flag` must be either 0 or 1.
+///
+/// This function does not branch on the values of `a` and `flag`.
+#[inline]
+#[cfg(feature = "constant-time")]
+fn sub_modulus_if(a_lo: u64, a_hi: u64, flag: u64) -> (u64, u64) {
+    let (t_lo, t_hi) = sub_modulus(a_lo, a_hi);
+    let mask = 0u64.wrapping_sub(flag);
+    (select64(mask, t_lo, a_lo), select64(mask, t_hi, a_hi))
+}
+
+/// Reduces a value z = z0 + z1 * 2^64 + z2 * 2^128 in [0, 2M) range to [0, M) range.
+#[inline]
+#[cfg(not(feature = "constant-time"))]
+fn normalize(z0: u64, z1: u64, z2: u64) -> (u64, u64) {
+    if z2 == 1 || (z1 == (M >> 64) as u64 && z0 >= (M as u64)) {
+        sub_modulus(z0, z1) // z = z - m
+    } else {
+        (z0, z1)
+    }
+}
+
+/// Reduces a value z = z0 + z1 * 2^64 + z2 * 2^128 in [0, 2M) range to [0, M) range.
+///
+/// This function does not branch on the value of z.
+#[inline]
+#[cfg(feature = "constant-time")]
+fn normalize(z0: u64, z1: u64, z2: u64) -> (u64, u64) {
+    // t = z - m; since z < 2m, t2 is 0 when z >= m, and 2^64 - 1 otherwise
+    let (t0, t1, t2) = sub_192x192(z0, z1, z2, M as u64, (M >> 64) as u64, 0);
+    (select64(t2, z0, t0), select64(t2, z1, t1))
+}
+
+/// Returns a mask with all bits set if `flag` is true, and with all bits cleared otherwise.
+#[inline(always)]
+#[cfg(feature = "constant-time")]
+const fn mask128(flag: bool) -> u128 {
+    0u128.wrapping_sub(flag as u128)
+}
+
+/// Returns `a` if all bits of `mask` are set, and `b` if all bits of `mask` are cleared.
+#[inline(always)]
+#[cfg(feature = "constant-time")]
+fn select64(mask: u64, a: u64, b: u64) -> u64 {
+    (a & mask) | (b & !mask)
+}
+
 #[inline]
 fn mul_128x64(a: u128, b: u64) -> (u64, u64, u64) {
     let z_lo = ((a as u64) as u128) * (b as u128);
@@ -561,7 +662,11 @@ fn mul_reduce(z0: u64, z1: u64, z2: u64) -> (u64, u64, u64) {
 #[inline]
 fn mul_by_modulus(a: u64) -> (u64, u64, u64) {
     let a_lo = (a as u128).wrapping_mul(M);
+    #[cfg(not(feature = "constant-time"))]
     let a_hi = if a == 0 { 0 } else { a - 1 };
+    // a_hi = a - 1 for a > 0, and 0 otherwise; (a | -a) >> 63 is 1 for any non-zero a
+    #[cfg(feature = "constant-time")]
+    let a_hi = a.wrapping_sub((a | a.wrapping_neg()) >> 63);
     (a_lo as u64, (a_lo >> 64) as u64, a_hi)
 }
 
@@ -582,6 +687,7 @@ fn sub_192x192(a0: u64, a1: u64, a2: u64, b0: u64, b1: u64, b2: u64) -> (u64, u6
 }
 
 #[inline]
+#[cfg(not(feature = "constant-time"))]
 fn add_192x192(a0: u64, a1: u64, a2: u64, b0: u64, b1: u64, b2: u64) -> (u64, u64, u64) {
     let z0 = (a0 as u128) + (b0 as u128);
     let z1 = (a1 as u128) + (b1 as u128) + (z0 >> 64);
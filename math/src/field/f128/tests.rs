@@ -100,6 +100,21 @@ fn mul() {
     }
 }
 
+#[test]
+fn pow_const() {
+    let x: BaseElement = rand_value();
+    assert_eq!(BaseElement::ONE, x.pow_const::<0>());
+    assert_eq!(x, x.pow_const::<1>());
+    assert_eq!(x.exp(2), x.pow_const::<2>());
+    assert_eq!(x.exp(3), x.pow_const::<3>());
+    assert_eq!(x.exp(4), x.pow_const::<4>());
+    assert_eq!(x.exp(5), x.pow_const::<5>());
+    assert_eq!(x.exp(7), x.pow_const::<7>());
+    assert_eq!(x.exp(11), x.pow_const::<11>());
+    assert_eq!(x.exp(12), x.pow_const::<12>());
+    assert_eq!(BaseElement::ZERO, BaseElement::ZERO.pow_const::<11>());
+}
+
 #[test]
 fn inv() {
     // identity
@@ -114,6 +129,20 @@ fn inv() {
     }
 }
 
+#[test]
+fn inv_ext_gcd() {
+    assert_eq!(None, BaseElement::ZERO.inv_ext_gcd());
+    assert_eq!(Some(BaseElement::ONE), BaseElement::ONE.inv_ext_gcd());
+    let max = BaseElement::from(super::M - 1);
+    assert_eq!(Some(max.inv()), max.inv_ext_gcd());
+
+    // both implementations should agree on random values
+    let x: Vec<BaseElement> = rand_vector(10000);
+    for &a in x.iter().filter(|&&a| a != BaseElement::ZERO) {
+        assert_eq!(Some(a.inv()), a.inv_ext_gcd());
+    }
+}
+
 #[test]
 fn conjugate() {
     let a: BaseElement = rand_value();
@@ -145,6 +174,18 @@ fn test_g_is_2_exp_40_root() {
     assert_eq!(g.exp(1u128 << 40), BaseElement::ONE);
 }
 
+#[test]
+fn sqrt() {
+    assert_eq!(Some(BaseElement::ZERO), BaseElement::ZERO.sqrt());
+    assert_eq!(None, BaseElement::GENERATOR.sqrt());
+
+    for _ in 0..100 {
+        let r: BaseElement = rand_value();
+        let root = r.square().sqrt().unwrap();
+        assert!(root == r || root == -r);
+    }
+}
+
 // SERIALIZATION / DESERIALIZATION
 // ================================================================================================
 
@@ -232,6 +273,48 @@ fn read_elements_from() {
     }
 }
 
+#[test]
+fn hex_string() {
+    assert_eq!(
+        "0x00000000000000000000000000000000",
+        BaseElement::ZERO.to_hex_string()
+    );
+    assert_eq!(
+        "0x00000000000000000000000000000001",
+        BaseElement::ONE.to_hex_string()
+    );
+    assert_eq!(
+        "0xffffffffffffffffffffd30000000000",
+        BaseElement::new(M - 1).to_hex_string()
+    );
+
+    // round trip
+    let r: BaseElement = rand_value();
+    assert_eq!(r, BaseElement::from_hex_str(&r.to_hex_string()).unwrap());
+    let r = BaseElement::new(M - 1);
+    assert_eq!(r, BaseElement::from_hex_str(&r.to_hex_string()).unwrap());
+
+    // leading zeros may be omitted and upper-case digits are accepted
+    assert_eq!(BaseElement::ONE, BaseElement::from_hex_str("0x1").unwrap());
+    assert_eq!(
+        BaseElement::new(255),
+        BaseElement::from_hex_str("0xFf").unwrap()
+    );
+
+    // invalid strings
+    let invalid = [
+        "1",
+        "0x",
+        "0xffffffffffffffffffffd30000000001",
+        "0x100000000000000000000000000000000",
+        "0x1g",
+    ];
+    for source in invalid.iter() {
+        let result = BaseElement::from_hex_str(source);
+        assert!(matches!(result, Err(DeserializationError::InvalidValue(_))));
+    }
+}
+
 // INITIALIZATION
 // ================================================================================================
 
@@ -14,6 +14,7 @@ use super::{
     ExtensibleField,
 };
 use core::{
+    cmp::Ordering,
     convert::{TryFrom, TryInto},
     fmt::{Debug, Display, Formatter},
     mem,
@@ -219,6 +220,24 @@ impl PartialEq for BaseElement {
 
 impl Eq for BaseElement {}
 
+// ORDERING
+// ================================================================================================
+
+impl PartialOrd for BaseElement {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BaseElement {
+    /// Compares elements by their canonical integer representations in [0, M) range.
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_int().cmp(&other.as_int())
+    }
+}
+
 // OVERLOADED OPERATORS
 // ================================================================================================
 
@@ -499,6 +518,7 @@ fn add(a: u64, b: u64) -> u64 {
 /// Computes (a - b) reduced by M such that the output is in [0, 2M) range; a and b are assumed to
 /// be in [0, 2M).
 #[inline(always)]
+#[cfg(not(feature = "constant-time"))]
 fn sub(a: u64, b: u64) -> u64 {
     if a < b {
         2 * M - b + a
@@ -507,6 +527,17 @@ fn sub(a: u64, b: u64) -> u64 {
     }
 }
 
+/// Computes (a - b) reduced by M such that the output is in [0, 2M) range; a and b are assumed to
+/// be in [0, 2M).
+///
+/// This function does not branch on the values of a and b.
+#[inline(always)]
+#[cfg(feature = "constant-time")]
+fn sub(a: u64, b: u64) -> u64 {
+    let (z, under) = a.overflowing_sub(b);
+    z.wrapping_add((2 * M) & mask64(under))
+}
+
 /// Computes (a * b) reduced by M such that the output is in [0, 2M) range; a and b are assumed to
 /// be in [0, 2M).
 #[inline(always)]
@@ -521,6 +552,7 @@ const fn mul(a: u64, b: u64) -> u64 {
 /// x is assumed to in [0, 2M) range, and the output will also be in [0, 2M) range.
 #[inline(always)]
 #[allow(clippy::many_single_char_names)]
+#[cfg(not(feature = "constant-time"))]
 fn inv(x: u64) -> u64 {
     if x == 0 {
         return 0;
@@ -567,11 +599,32 @@ fn inv(x: u64) -> u64 {
     mul(a as u64, R3)
 }
 
+/// Computes y such that (x * y) % M = 1 except for when when x = 0; in such a case, 0 is returned;
+/// x is assumed to in [0, 2M) range, and the output will also be in [0, 2M) range.
+///
+/// The inverse is computed as x^(M - 2) using a fixed sequence of squarings and multiplications
+/// which depends only on the modulus; this also maps 0 to 0 without checking x for zero.
+#[inline(always)]
+#[cfg(feature = "constant-time")]
+fn inv(x: u64) -> u64 {
+    let power = M - 2;
+    // 1 in Montgomery representation
+    let mut result = mul(1, R2);
+    for i in (0..64).rev() {
+        result = mul(result, result);
+        if (power >> i) & 1 == 1 {
+            result = mul(result, x);
+        }
+    }
+    result
+}
+
 // HELPER FUNCTIONS
 // ================================================================================================
 
 /// Reduces any value in [0, 2M) range to [0, M) range
 #[inline(always)]
+#[cfg(not(feature = "constant-time"))]
 fn normalize(value: u64) -> u64 {
     if value >= M {
         value - M
@@ -579,3 +632,18 @@ fn normalize(value: u64) -> u64 {
         value
     }
 }
+
+/// Reduces any value in [0, 2M) range to [0, M) range without branching on the value.
+#[inline(always)]
+#[cfg(feature = "constant-time")]
+fn normalize(value: u64) -> u64 {
+    let (z, under) = value.overflowing_sub(M);
+    z.wrapping_add(M & mask64(under))
+}
+
+/// Returns a mask with all bits set if `flag` is true, and with all bits cleared otherwise.
+#[inline(always)]
+#[cfg(feature = "constant-time")]
+fn mask64(flag: bool) -> u64 {
+    0u64.wrapping_sub(flag as u64)
+}
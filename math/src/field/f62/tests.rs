@@ -3,11 +3,14 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::{AsBytes, BaseElement, DeserializationError, FieldElement, Serializable, StarkField};
+use super::{
+    AsBytes, BaseElement, DeserializationError, FieldElement, Serializable, StarkField, M,
+};
 use crate::field::{CubeExtension, QuadExtension};
 use core::convert::TryFrom;
 use num_bigint::BigUint;
 use proptest::prelude::*;
+use rand::{rngs::StdRng, SeedableRng};
 use rand_utils::rand_value;
 
 // MANUAL TESTS
@@ -119,6 +122,34 @@ fn equals() {
     assert_ne!(a.as_bytes(), b.as_bytes());
 }
 
+#[test]
+fn ordering() {
+    let a = BaseElement::new(3);
+    let b = BaseElement::new(super::M - 1);
+
+    // ordering follows canonical integer values rather than internal representations
+    assert!(a < b);
+    assert!(BaseElement::ZERO < a);
+    assert_eq!(
+        b.cmp(&(BaseElement::new(super::M - 1) * BaseElement::ONE)),
+        core::cmp::Ordering::Equal
+    );
+
+    let mut elements: Vec<BaseElement> = [5, 1, 3, 5, 1, 1, 7, 3]
+        .iter()
+        .map(|&v| BaseElement::new(v))
+        .collect();
+    elements.sort();
+    elements.dedup();
+    let expected: Vec<BaseElement> = [1, 3, 5, 7].iter().map(|&v| BaseElement::new(v)).collect();
+    assert_eq!(expected, elements);
+
+    // extension elements are ordered lexicographically by their coordinates
+    let x = QuadExtension::new(BaseElement::new(1), BaseElement::new(9));
+    let y = QuadExtension::new(BaseElement::new(2), BaseElement::new(0));
+    assert!(x < y);
+}
+
 // CUBIC EXTENSION
 // ------------------------------------------------------------------------------------------------
 #[test]
@@ -200,6 +231,18 @@ fn get_root_of_unity() {
     assert_eq!(BaseElement::ONE, root_38.exp(1u64 << 38));
 }
 
+#[test]
+fn sqrt() {
+    assert_eq!(Some(BaseElement::ZERO), BaseElement::ZERO.sqrt());
+    assert_eq!(None, BaseElement::GENERATOR.sqrt());
+
+    for _ in 0..100 {
+        let r: BaseElement = rand_value();
+        let root = r.square().sqrt().unwrap();
+        assert!(root == r || root == -r);
+    }
+}
+
 // SERIALIZATION AND DESERIALIZATION
 // ------------------------------------------------------------------------------------------------
 
@@ -275,6 +318,21 @@ fn bytes_as_elements() {
     assert!(matches!(result, Err(DeserializationError::InvalidValue(_))));
 }
 
+#[test]
+fn hex_string() {
+    assert_eq!("0x0000000000000001", BaseElement::ONE.to_hex_string());
+    assert_eq!(
+        "0x3fffc88000000000",
+        BaseElement::new(super::M - 1).to_hex_string()
+    );
+
+    let r: BaseElement = rand_value();
+    assert_eq!(r, BaseElement::from_hex_str(&r.to_hex_string()).unwrap());
+
+    let result = BaseElement::from_hex_str("0x3fffc88000000001");
+    assert!(matches!(result, Err(DeserializationError::InvalidValue(_))));
+}
+
 // INITIALIZATION
 // ------------------------------------------------------------------------------------------------
 
@@ -287,6 +345,30 @@ fn zeroed_vector() {
     }
 }
 
+#[test]
+fn random() {
+    let mut rng = StdRng::seed_from_u64(42);
+
+    // about half of uniformly sampled elements should be in the upper half of the field
+    let elements = (0..1000)
+        .map(|_| BaseElement::random(&mut rng))
+        .collect::<Vec<_>>();
+    let num_upper = elements.iter().filter(|e| e.as_int() > M / 2).count();
+    assert!(num_upper > 400 && num_upper < 600);
+
+    for _ in 0..100 {
+        assert_ne!(BaseElement::ZERO, BaseElement::random_nonzero(&mut rng));
+        assert_ne!(
+            QuadExtension::<BaseElement>::ZERO,
+            QuadExtension::<BaseElement>::random_nonzero(&mut rng)
+        );
+        assert_ne!(
+            CubeExtension::<BaseElement>::ZERO,
+            CubeExtension::<BaseElement>::random_nonzero(&mut rng)
+        );
+    }
+}
+
 // RANDOMIZED TESTS
 // ================================================================================================
 
@@ -344,6 +426,18 @@ proptest! {
         prop_assert_eq!(expected, a * b);
     }
 
+    #[test]
+    fn inv_ext_gcd_proptest(a in any::<u64>()) {
+        let a = BaseElement::from(a);
+        match a.inv_ext_gcd() {
+            Some(b) => {
+                prop_assert_eq!(BaseElement::ONE, a * b);
+                prop_assert_eq!(a.inv(), b);
+            }
+            None => prop_assert_eq!(BaseElement::ZERO, a),
+        }
+    }
+
     #[test]
     fn element_as_int_proptest(a in any::<u64>()) {
         let e = BaseElement::new(a);
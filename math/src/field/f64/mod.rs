@@ -16,9 +16,10 @@
 
 use super::{
     traits::{FieldElement, StarkField},
-    ExtensibleField,
+    ExtensibleField, QuadExtension, TowerField,
 };
 use core::{
+    cmp::Ordering,
     convert::{TryFrom, TryInto},
     fmt::{Debug, Display, Formatter},
     mem,
@@ -98,6 +99,7 @@ impl FieldElement for BaseElement {
 
     #[inline]
     #[allow(clippy::many_single_char_names)]
+    #[cfg(not(feature = "constant-time"))]
     fn inv(self) -> Self {
         let x = self.as_int();
 
@@ -146,6 +148,22 @@ impl FieldElement for BaseElement {
         Self(a as u64)
     }
 
+    /// Computes the inverse as x^(M - 2) using a fixed sequence of squarings and multiplications
+    /// which depends only on the modulus; this also maps ZERO to ZERO without checking x for zero.
+    #[inline]
+    #[cfg(feature = "constant-time")]
+    fn inv(self) -> Self {
+        let power = M - 2;
+        let mut result = Self::ONE;
+        for i in (0..64).rev() {
+            result = result.square();
+            if (power >> i) & 1 == 1 {
+                result *= self;
+            }
+        }
+        result
+    }
+
     fn conjugate(&self) -> Self {
         Self(self.0)
     }
@@ -228,11 +246,7 @@ impl StarkField for BaseElement {
     fn as_int(&self) -> Self::PositiveInteger {
         // since the internal value of the element can be in [0, 2^64) range, we do an extra check
         // here to convert it to the canonical form
-        if self.0 >= M {
-            self.0 - M
-        } else {
-            self.0
-        }
+        normalize(self.0)
     }
 }
 
@@ -264,6 +278,24 @@ impl PartialEq for BaseElement {
 
 impl Eq for BaseElement {}
 
+// ORDERING
+// ================================================================================================
+
+impl PartialOrd for BaseElement {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BaseElement {
+    /// Compares elements by their canonical integer representations in [0, M) range.
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_int().cmp(&other.as_int())
+    }
+}
+
 // OVERLOADED OPERATORS
 // ================================================================================================
 
@@ -341,6 +373,7 @@ impl Neg for BaseElement {
     type Output = Self;
 
     #[inline]
+    #[cfg(not(feature = "constant-time"))]
     fn neg(self) -> Self {
         let v = self.as_int();
         if v == 0 {
@@ -349,6 +382,13 @@ impl Neg for BaseElement {
             Self(M - v)
         }
     }
+
+    #[inline]
+    #[cfg(feature = "constant-time")]
+    fn neg(self) -> Self {
+        // M - 0 = M is a valid internal representation of ZERO, and thus, no check is needed here
+        Self(M - self.as_int())
+    }
 }
 
 // QUADRATIC EXTENSION
@@ -376,6 +416,19 @@ impl ExtensibleField<2> for BaseElement {
     }
 }
 
+// TOWER EXTENSION
+// ================================================================================================
+
+/// Defines a quadratic extension of the quadratic extension field over an irreducible polynomial
+/// y<sup>2</sup> - (3 + φ); 3 + φ is not a square because its norm 14 is a quadratic non-residue
+/// in the base field. Thus, the tower extension yields a degree 4 extension of the base field.
+impl TowerField for QuadExtension<BaseElement> {
+    #[inline(always)]
+    fn tower_nonresidue() -> Self {
+        QuadExtension::new(BaseElement::new(3), BaseElement::ONE)
+    }
+}
+
 // CUBIC EXTENSION
 // ================================================================================================
 
@@ -544,6 +597,25 @@ impl Deserializable for BaseElement {
 // HELPER FUNCTIONS
 // ================================================================================================
 
+/// Reduces any value in [0, 2^64) range to [0, M) range.
+#[inline(always)]
+#[cfg(not(feature = "constant-time"))]
+fn normalize(value: u64) -> u64 {
+    if value >= M {
+        value - M
+    } else {
+        value
+    }
+}
+
+/// Reduces any value in [0, 2^64) range to [0, M) range without branching on the value.
+#[inline(always)]
+#[cfg(feature = "constant-time")]
+fn normalize(value: u64) -> u64 {
+    let (z, under) = value.overflowing_sub(M);
+    z.wrapping_add(M & 0u64.wrapping_sub(under as u64))
+}
+
 /// Reduces a 128-bit value by M such that the output is in [0, 2^64) range.
 ///
 /// Adapted from: <https://github.com/mir-protocol/plonky2/blob/main/src/field/goldilocks_field.rs>
@@ -6,7 +6,7 @@
 use super::{
     AsBytes, BaseElement, DeserializationError, FieldElement, Serializable, StarkField, E, M,
 };
-use crate::field::{CubeExtension, QuadExtension};
+use crate::field::{CubeExtension, QuadExtension, TowerExtension, TowerField};
 use core::convert::TryFrom;
 use num_bigint::BigUint;
 use proptest::prelude::*;
@@ -150,6 +150,18 @@ fn get_root_of_unity() {
     assert_eq!(BaseElement::ONE, root_31.exp(1u64 << 31));
 }
 
+#[test]
+fn sqrt() {
+    assert_eq!(Some(BaseElement::ZERO), BaseElement::ZERO.sqrt());
+    assert_eq!(None, BaseElement::GENERATOR.sqrt());
+
+    for _ in 0..100 {
+        let r: BaseElement = rand_value();
+        let root = r.square().sqrt().unwrap();
+        assert!(root == r || root == -r);
+    }
+}
+
 // SERIALIZATION AND DESERIALIZATION
 // ------------------------------------------------------------------------------------------------
 
@@ -225,6 +237,18 @@ fn bytes_as_elements() {
     assert!(matches!(result, Err(DeserializationError::InvalidValue(_))));
 }
 
+#[test]
+fn hex_string() {
+    assert_eq!("0x0000000000000001", BaseElement::ONE.to_hex_string());
+    assert_eq!(
+        "0xffffffff00000000",
+        BaseElement::new(M - 1).to_hex_string()
+    );
+
+    let result = BaseElement::from_hex_str("0xffffffff00000001");
+    assert!(matches!(result, Err(DeserializationError::InvalidValue(_))));
+}
+
 // INITIALIZATION
 // ------------------------------------------------------------------------------------------------
 
@@ -297,6 +321,57 @@ fn quad_conjugate() {
     assert_eq!(expected, a.conjugate());
 }
 
+// TOWER EXTENSION
+// ------------------------------------------------------------------------------------------------
+#[test]
+fn tower_nonresidue() {
+    // ξ is a non-residue in the quadratic extension iff ξ^((p^2 - 1) / 2) = -1; since p is odd,
+    // (p^2 - 1) / 2 = (p - 1) * (p / 2 + 1)
+    let m = BaseElement::MODULUS;
+    let xi = <QuadExtension<BaseElement>>::tower_nonresidue();
+    assert_eq!(
+        -<QuadExtension<BaseElement>>::ONE,
+        xi.exp(m - 1).exp(m / 2 + 1)
+    );
+}
+
+#[test]
+fn tower_mul() {
+    type TowerElement = TowerExtension<QuadExtension<BaseElement>, BaseElement>;
+
+    // identity
+    let r: TowerElement = rand_value();
+    assert_eq!(TowerElement::ZERO, r * TowerElement::ZERO);
+    assert_eq!(r, r * TowerElement::ONE);
+
+    // y^2 = ξ
+    let xi = <QuadExtension<BaseElement>>::tower_nonresidue();
+    let y = TowerElement::new(QuadExtension::ZERO, QuadExtension::ONE);
+    assert_eq!(TowerElement::new(xi, QuadExtension::ZERO), y * y);
+
+    // test random values against schoolbook multiplication
+    let (a0, a1) = (rand_value(), rand_value());
+    let (b0, b1) = (rand_value(), rand_value());
+    let expected = TowerElement::new(a0 * b0 + xi * a1 * b1, a0 * b1 + a1 * b0);
+    assert_eq!(
+        expected,
+        TowerElement::new(a0, a1) * TowerElement::new(b0, b1)
+    );
+}
+
+#[test]
+fn tower_inv() {
+    type TowerElement = TowerExtension<QuadExtension<BaseElement>, BaseElement>;
+
+    // zero is its own inverse
+    assert_eq!(TowerElement::ZERO, TowerElement::ZERO.inv());
+
+    // test random values
+    let r: TowerElement = rand_value();
+    assert_eq!(TowerElement::ONE, r * r.inv());
+    assert_eq!(r, r.inv().inv());
+}
+
 // CUBIC EXTENSION
 // ------------------------------------------------------------------------------------------------
 #[test]
@@ -391,6 +466,12 @@ proptest! {
         prop_assert_eq!(expected, result.as_int());
     }
 
+    #[test]
+    fn hex_string_proptest(a in any::<u64>()) {
+        let v = BaseElement::from(a);
+        prop_assert_eq!(v, BaseElement::from_hex_str(&v.to_hex_string()).unwrap());
+    }
+
     #[test]
     fn neg_proptest(a in any::<u64>()) {
         let v = BaseElement::from(a);
@@ -437,6 +518,18 @@ proptest! {
         prop_assert_eq!(expected, a * b);
     }
 
+    #[test]
+    fn inv_ext_gcd_proptest(a in any::<u64>()) {
+        let a = BaseElement::from(a);
+        match a.inv_ext_gcd() {
+            Some(b) => {
+                prop_assert_eq!(BaseElement::ONE, a * b);
+                prop_assert_eq!(a.inv(), b);
+            }
+            None => prop_assert_eq!(BaseElement::ZERO, a),
+        }
+    }
+
     #[test]
     fn element_as_int_proptest(a in any::<u64>()) {
         let e = BaseElement::new(a);
@@ -0,0 +1,381 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! A field element wrapper which counts arithmetic operations performed over the wrapped field.
+//!
+//! [InstrumentedField] delegates all arithmetic to the wrapped [StarkField], and on each
+//! operation increments one of the global counters. The counters are shared by all instrumented
+//! fields and can be read via [op_stats()] and cleared via [reset_op_stats()]. This is intended
+//! for measuring the exact number of field operations performed by a computation (e.g., by a
+//! proving run) rather than for use in production.
+//!
+//! Operations are counted as follows:
+//! * Additions, subtractions, negations, and doublings are counted as additions.
+//! * Multiplications and squarings are counted as multiplications.
+//! * Inversions are counted as inversions; a division is counted as one inversion and one
+//!   multiplication.
+//! * Exponentiations are counted as exponentiations; multiplications performed by the wrapped
+//!   field while computing an exponentiation are not counted.
+//! * A multiplication in an extension of an instrumented field is counted as a single
+//!   multiplication; additions in extension fields are counted per coordinate.
+//!
+//! Since the counters are global, operations performed concurrently by unrelated computations
+//! over instrumented fields are counted together.
+
+use super::{
+    traits::{FieldElement, StarkField},
+    ExtensibleField,
+};
+use core::{
+    convert::TryFrom,
+    fmt::{Debug, Display, Formatter},
+    mem,
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+    slice,
+    sync::atomic::{AtomicU64, Ordering},
+};
+use utils::{
+    collections::Vec, AsBytes, ByteReader, ByteWriter, Deserializable, DeserializationError,
+    Randomizable, Serializable,
+};
+
+#[cfg(test)]
+mod tests;
+
+// OPERATION COUNTERS
+// ================================================================================================
+
+static NUM_ADDITIONS: AtomicU64 = AtomicU64::new(0);
+static NUM_MULTIPLICATIONS: AtomicU64 = AtomicU64::new(0);
+static NUM_INVERSIONS: AtomicU64 = AtomicU64::new(0);
+static NUM_EXPONENTIATIONS: AtomicU64 = AtomicU64::new(0);
+
+/// Numbers of field operations performed over instrumented fields.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub struct FieldOpStats {
+    /// Number of additions, subtractions, negations, and doublings.
+    pub additions: u64,
+    /// Number of multiplications (including squarings and divisions).
+    pub multiplications: u64,
+    /// Number of inversions (including divisions).
+    pub inversions: u64,
+    /// Number of exponentiations.
+    pub exponentiations: u64,
+}
+
+/// Returns the numbers of field operations performed over instrumented fields since the last
+/// time the counters were reset.
+pub fn op_stats() -> FieldOpStats {
+    FieldOpStats {
+        additions: NUM_ADDITIONS.load(Ordering::Relaxed),
+        multiplications: NUM_MULTIPLICATIONS.load(Ordering::Relaxed),
+        inversions: NUM_INVERSIONS.load(Ordering::Relaxed),
+        exponentiations: NUM_EXPONENTIATIONS.load(Ordering::Relaxed),
+    }
+}
+
+/// Sets all field operation counters to zero.
+pub fn reset_op_stats() {
+    NUM_ADDITIONS.store(0, Ordering::Relaxed);
+    NUM_MULTIPLICATIONS.store(0, Ordering::Relaxed);
+    NUM_INVERSIONS.store(0, Ordering::Relaxed);
+    NUM_EXPONENTIATIONS.store(0, Ordering::Relaxed);
+}
+
+#[inline(always)]
+fn count(counter: &AtomicU64) {
+    counter.fetch_add(1, Ordering::Relaxed);
+}
+
+// INSTRUMENTED FIELD ELEMENT
+// ================================================================================================
+
+/// Represents an element of field `F` for which all arithmetic operations are counted.
+///
+/// The wrapper has the same memory layout as the wrapped element, and its byte and integer
+/// representations are the same as the ones of the wrapped element.
+#[repr(transparent)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct InstrumentedField<F: StarkField>(F);
+
+impl<F: StarkField> InstrumentedField<F> {
+    /// Returns a new instrumented field element wrapping the provided `value`.
+    pub const fn new(value: F) -> Self {
+        Self(value)
+    }
+
+    /// Returns the wrapped field element.
+    pub fn inner(&self) -> F {
+        self.0
+    }
+}
+
+impl<F: StarkField> FieldElement for InstrumentedField<F> {
+    type PositiveInteger = F::PositiveInteger;
+    type BaseField = Self;
+
+    const ZERO: Self = Self(F::ZERO);
+    const ONE: Self = Self(F::ONE);
+
+    const ELEMENT_BYTES: usize = F::ELEMENT_BYTES;
+    const IS_CANONICAL: bool = F::IS_CANONICAL;
+
+    fn exp(self, power: Self::PositiveInteger) -> Self {
+        count(&NUM_EXPONENTIATIONS);
+        Self(self.0.exp(power))
+    }
+
+    fn inv(self) -> Self {
+        count(&NUM_INVERSIONS);
+        Self(self.0.inv())
+    }
+
+    fn conjugate(&self) -> Self {
+        Self(self.0.conjugate())
+    }
+
+    fn elements_as_bytes(elements: &[Self]) -> &[u8] {
+        F::elements_as_bytes(as_inner_elements(elements))
+    }
+
+    unsafe fn bytes_as_elements(bytes: &[u8]) -> Result<&[Self], DeserializationError> {
+        let elements = F::bytes_as_elements(bytes)?;
+        // this is safe because InstrumentedField has the same memory layout as F
+        Ok(slice::from_raw_parts(
+            elements.as_ptr() as *const Self,
+            elements.len(),
+        ))
+    }
+
+    fn zeroed_vector(n: usize) -> Vec<Self> {
+        // translate a zero-filled vector of wrapped elements into a vector of instrumented
+        // elements; this is safe because InstrumentedField has the same memory layout as F
+        let mut v = mem::ManuallyDrop::new(F::zeroed_vector(n));
+        let p = v.as_mut_ptr();
+        let len = v.len();
+        let cap = v.capacity();
+        unsafe { Vec::from_raw_parts(p as *mut Self, len, cap) }
+    }
+
+    fn as_base_elements(elements: &[Self]) -> &[Self::BaseField] {
+        elements
+    }
+}
+
+impl<F: StarkField> StarkField for InstrumentedField<F> {
+    const MODULUS: Self::PositiveInteger = F::MODULUS;
+    const MODULUS_BITS: u32 = F::MODULUS_BITS;
+    const GENERATOR: Self = Self(F::GENERATOR);
+    const TWO_ADICITY: u32 = F::TWO_ADICITY;
+    const TWO_ADIC_ROOT_OF_UNITY: Self = Self(F::TWO_ADIC_ROOT_OF_UNITY);
+
+    fn get_modulus_le_bytes() -> Vec<u8> {
+        F::get_modulus_le_bytes()
+    }
+
+    fn as_int(&self) -> Self::PositiveInteger {
+        self.0.as_int()
+    }
+}
+
+impl<F: StarkField> Randomizable for InstrumentedField<F> {
+    const VALUE_SIZE: usize = F::VALUE_SIZE;
+
+    fn from_random_bytes(bytes: &[u8]) -> Option<Self> {
+        F::from_random_bytes(bytes).map(Self)
+    }
+}
+
+impl<F: StarkField> Display for InstrumentedField<F> {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+// OVERLOADED OPERATORS
+// ================================================================================================
+
+impl<F: StarkField> Add for InstrumentedField<F> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        count(&NUM_ADDITIONS);
+        Self(self.0 + rhs.0)
+    }
+}
+
+impl<F: StarkField> AddAssign for InstrumentedField<F> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs
+    }
+}
+
+impl<F: StarkField> Sub for InstrumentedField<F> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        count(&NUM_ADDITIONS);
+        Self(self.0 - rhs.0)
+    }
+}
+
+impl<F: StarkField> SubAssign for InstrumentedField<F> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<F: StarkField> Mul for InstrumentedField<F> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        count(&NUM_MULTIPLICATIONS);
+        Self(self.0 * rhs.0)
+    }
+}
+
+impl<F: StarkField> MulAssign for InstrumentedField<F> {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs
+    }
+}
+
+impl<F: StarkField> Div for InstrumentedField<F> {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self {
+        count(&NUM_MULTIPLICATIONS);
+        count(&NUM_INVERSIONS);
+        Self(self.0 / rhs.0)
+    }
+}
+
+impl<F: StarkField> DivAssign for InstrumentedField<F> {
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs
+    }
+}
+
+impl<F: StarkField> Neg for InstrumentedField<F> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        count(&NUM_ADDITIONS);
+        Self(-self.0)
+    }
+}
+
+// EXTENSIONS
+// ================================================================================================
+
+impl<F: ExtensibleField<2>> ExtensibleField<2> for InstrumentedField<F> {
+    #[inline(always)]
+    fn mul(a: [Self; 2], b: [Self; 2]) -> [Self; 2] {
+        count(&NUM_MULTIPLICATIONS);
+        let [r0, r1] = <F as ExtensibleField<2>>::mul([a[0].0, a[1].0], [b[0].0, b[1].0]);
+        [Self(r0), Self(r1)]
+    }
+
+    #[inline(always)]
+    fn frobenius(x: [Self; 2]) -> [Self; 2] {
+        let [r0, r1] = <F as ExtensibleField<2>>::frobenius([x[0].0, x[1].0]);
+        [Self(r0), Self(r1)]
+    }
+
+    fn is_supported() -> bool {
+        <F as ExtensibleField<2>>::is_supported()
+    }
+}
+
+impl<F: ExtensibleField<3>> ExtensibleField<3> for InstrumentedField<F> {
+    #[inline(always)]
+    fn mul(a: [Self; 3], b: [Self; 3]) -> [Self; 3] {
+        count(&NUM_MULTIPLICATIONS);
+        let [r0, r1, r2] =
+            <F as ExtensibleField<3>>::mul([a[0].0, a[1].0, a[2].0], [b[0].0, b[1].0, b[2].0]);
+        [Self(r0), Self(r1), Self(r2)]
+    }
+
+    #[inline(always)]
+    fn frobenius(x: [Self; 3]) -> [Self; 3] {
+        let [r0, r1, r2] = <F as ExtensibleField<3>>::frobenius([x[0].0, x[1].0, x[2].0]);
+        [Self(r0), Self(r1), Self(r2)]
+    }
+
+    fn is_supported() -> bool {
+        <F as ExtensibleField<3>>::is_supported()
+    }
+}
+
+// TYPE CONVERSIONS
+// ================================================================================================
+
+impl<F: StarkField> From<u128> for InstrumentedField<F> {
+    fn from(value: u128) -> Self {
+        Self(F::from(value))
+    }
+}
+
+impl<F: StarkField> From<u64> for InstrumentedField<F> {
+    fn from(value: u64) -> Self {
+        Self(F::from(value))
+    }
+}
+
+impl<F: StarkField> From<u32> for InstrumentedField<F> {
+    fn from(value: u32) -> Self {
+        Self(F::from(value))
+    }
+}
+
+impl<F: StarkField> From<u16> for InstrumentedField<F> {
+    fn from(value: u16) -> Self {
+        Self(F::from(value))
+    }
+}
+
+impl<F: StarkField> From<u8> for InstrumentedField<F> {
+    fn from(value: u8) -> Self {
+        Self(F::from(value))
+    }
+}
+
+impl<'a, F: StarkField> TryFrom<&'a [u8]> for InstrumentedField<F> {
+    type Error = <F as TryFrom<&'a [u8]>>::Error;
+
+    fn try_from(bytes: &'a [u8]) -> Result<Self, Self::Error> {
+        F::try_from(bytes).map(Self)
+    }
+}
+
+impl<F: StarkField> AsBytes for InstrumentedField<F> {
+    fn as_bytes(&self) -> &[u8] {
+        self.0.as_bytes()
+    }
+}
+
+// SERIALIZATION / DESERIALIZATION
+// ------------------------------------------------------------------------------------------------
+
+impl<F: StarkField> Serializable for InstrumentedField<F> {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.0.write_into(target)
+    }
+}
+
+impl<F: StarkField> Deserializable for InstrumentedField<F> {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        F::read_from(source).map(Self)
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Re-interprets a slice of instrumented elements as a slice of wrapped elements.
+fn as_inner_elements<F: StarkField>(elements: &[InstrumentedField<F>]) -> &[F] {
+    // this is safe because InstrumentedField has the same memory layout as F
+    unsafe { slice::from_raw_parts(elements.as_ptr() as *const F, elements.len()) }
+}
@@ -0,0 +1,131 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{op_stats, reset_op_stats, FieldOpStats, InstrumentedField};
+use crate::{
+    fft,
+    field::{f128::BaseElement, FieldElement, QuadExtension, StarkField},
+};
+use rand_utils::{rand_value, rand_vector};
+use utils::{collections::Vec, Serializable};
+
+type Element = InstrumentedField<BaseElement>;
+
+// MANUAL TESTS
+// ================================================================================================
+
+// counters are global and thus, all instrumented operations are performed in a single test to
+// prevent tests running in parallel from interfering with the counts
+#[test]
+fn instrumented_ops() {
+    arithmetic_matches_wrapped_field();
+    fft_matches_wrapped_field();
+    op_counters();
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn arithmetic_matches_wrapped_field() {
+    let a: BaseElement = rand_value();
+    let b: BaseElement = rand_value();
+    let (x, y) = (Element::new(a), Element::new(b));
+
+    assert_eq!(a + b, (x + y).inner());
+    assert_eq!(a - b, (x - y).inner());
+    assert_eq!(a * b, (x * y).inner());
+    assert_eq!(a / b, (x / y).inner());
+    assert_eq!(-a, (-x).inner());
+    assert_eq!(a.inv(), x.inv().inner());
+    assert_eq!(a.exp(7), x.exp(7).inner());
+    assert_eq!(a.to_bytes(), x.to_bytes());
+    assert_eq!(a.as_int(), x.as_int());
+    assert_eq!(
+        BaseElement::get_root_of_unity(4),
+        Element::get_root_of_unity(4).inner()
+    );
+}
+
+fn fft_matches_wrapped_field() {
+    let values: Vec<BaseElement> = rand_vector(16);
+    let instrumented = values.iter().map(|&v| Element::new(v)).collect::<Vec<_>>();
+
+    // interpolating and evaluating polynomials over the instrumented field yields the same
+    // results as over the wrapped field, and the work is counted
+    reset_op_stats();
+    let mut poly = values.clone();
+    fft::interpolate_poly(&mut poly, &fft::get_inv_twiddles::<BaseElement>(16));
+    let evaluations = fft::evaluate_poly_with_offset(
+        &poly,
+        &fft::get_twiddles::<BaseElement>(16),
+        BaseElement::GENERATOR,
+        4,
+    );
+    assert_eq!(FieldOpStats::default(), op_stats());
+
+    let mut instrumented_poly = instrumented;
+    fft::interpolate_poly(
+        &mut instrumented_poly,
+        &fft::get_inv_twiddles::<Element>(16),
+    );
+    let instrumented_evaluations = fft::evaluate_poly_with_offset(
+        &instrumented_poly,
+        &fft::get_twiddles::<Element>(16),
+        Element::GENERATOR,
+        4,
+    );
+    let stats = op_stats();
+    assert!(stats.additions > 0);
+    assert!(stats.multiplications > 0);
+
+    let unwrap = |values: &[Element]| values.iter().map(|v| v.inner()).collect::<Vec<_>>();
+    assert_eq!(poly, unwrap(&instrumented_poly));
+    assert_eq!(evaluations, unwrap(&instrumented_evaluations));
+}
+
+fn op_counters() {
+    let x = Element::new(rand_value());
+    let y = Element::new(rand_value());
+
+    reset_op_stats();
+    assert_eq!(FieldOpStats::default(), op_stats());
+
+    let _ = x + y;
+    let _ = x - y;
+    let _ = -x;
+    let _ = x.double();
+    let _ = x * y;
+    let _ = x.square();
+    let _ = x / y;
+    let _ = x.inv();
+    let _ = x.exp(5);
+
+    let expected = FieldOpStats {
+        additions: 4,
+        multiplications: 3,
+        inversions: 2,
+        exponentiations: 1,
+    };
+    assert_eq!(expected, op_stats());
+
+    // a multiplication in the extension field is counted as a single multiplication, while
+    // addition is counted per coordinate
+    reset_op_stats();
+    let a = QuadExtension::<Element>::new(x, y);
+    let b = QuadExtension::<Element>::new(y, x);
+    let _ = a * b;
+    let _ = a + b;
+
+    let expected = FieldOpStats {
+        additions: 2,
+        multiplications: 1,
+        inversions: 0,
+        exponentiations: 0,
+    };
+    assert_eq!(expected, op_stats());
+
+    reset_op_stats();
+    assert_eq!(FieldOpStats::default(), op_stats());
+}
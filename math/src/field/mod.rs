@@ -4,11 +4,12 @@
 // LICENSE file in the root directory of this source tree.
 
 mod traits;
-pub use traits::{ExtensibleField, FieldElement, StarkField};
+pub use traits::{ExtensibleField, FieldElement, StarkField, TowerField};
 
 pub mod f128;
 pub mod f62;
 pub mod f64;
+pub mod instrumented;
 
 mod extensions;
-pub use extensions::{CubeExtension, QuadExtension};
+pub use extensions::{CubeExtension, QuadExtension, TowerExtension};
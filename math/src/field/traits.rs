@@ -11,8 +11,11 @@ use core::{
         SubAssign,
     },
 };
+use rand_core::RngCore;
 use utils::{
-    collections::Vec, AsBytes, Deserializable, DeserializationError, Randomizable, Serializable,
+    collections::Vec,
+    string::{String, ToString},
+    AsBytes, Deserializable, DeserializationError, Randomizable, Serializable, SliceReader,
 };
 
 // FIELD ELEMENT
@@ -26,6 +29,9 @@ use utils::{
 ///
 /// The elements could be in a prime field or an extension of a prime field. Currently, only
 /// quadratic and cubic field extensions are supported.
+///
+/// Elements are ordered by their canonical integer representations; extension field elements
+/// are ordered lexicographically by their coordinates.
 pub trait FieldElement:
     Copy
     + Clone
@@ -36,6 +42,8 @@ pub trait FieldElement:
     + Sync
     + Eq
     + PartialEq
+    + Ord
+    + PartialOrd
     + Sized
     + Add<Self, Output = Self>
     + Sub<Self, Output = Self>
@@ -68,8 +76,11 @@ pub trait FieldElement:
         + Shl<u32, Output = Self::PositiveInteger>
         + Shr<u32, Output = Self::PositiveInteger>
         + BitAnd<Output = Self::PositiveInteger>
+        + Add<Output = Self::PositiveInteger>
+        + Sub<Output = Self::PositiveInteger>
         + From<u32>
-        + From<u64>;
+        + From<u64>
+        + Into<u128>;
 
     /// Base field type for this finite field. For prime fields, `BaseField` should be set
     /// to `Self`.
@@ -134,6 +145,32 @@ pub trait FieldElement:
         r
     }
 
+    /// Exponentiates this field element by a power `N` known at compile time.
+    ///
+    /// For powers 0, 1, 2, 3, 5, 7, and 11 (which are commonly used as S-Box exponents of
+    /// arithmetization-friendly hash functions), the exponentiation is computed via a fixed
+    /// sequence of squarings and multiplications which can be inlined by the compiler; for all
+    /// other powers, this is the same as [exp()](FieldElement::exp).
+    #[inline(always)]
+    fn pow_const<const N: u64>(self) -> Self {
+        match N {
+            0 => Self::ONE,
+            1 => self,
+            2 => self.square(),
+            3 => self.cube(),
+            5 => self.square().square() * self,
+            7 => {
+                let x2 = self.square();
+                x2.square() * x2 * self
+            }
+            11 => {
+                let x2 = self.square();
+                x2.square().square() * x2 * self
+            }
+            _ => self.exp(N.into()),
+        }
+    }
+
     /// Returns a multiplicative inverse of this field element. If this element is ZERO, ZERO is
     /// returned.
     fn inv(self) -> Self;
@@ -167,6 +204,102 @@ pub trait FieldElement:
     /// field elements according to their internal representation.
     unsafe fn bytes_as_elements(bytes: &[u8]) -> Result<&[Self], DeserializationError>;
 
+    /// Returns canonical representation of this field element as a `0x`-prefixed hex string.
+    ///
+    /// The string encodes the bytes of the serialized element in big-endian order and always
+    /// contains `2 * ELEMENT_BYTES` hex digits. For extension fields, the string is a big-endian
+    /// encoding of the concatenated base field coefficients (i.e., the last coefficient comes
+    /// first).
+    fn to_hex_string(&self) -> String {
+        let bytes = self.to_bytes();
+        let mut result = String::with_capacity(2 + bytes.len() * 2);
+        result.push_str("0x");
+        for byte in bytes.iter().rev() {
+            result.push_str(&format!("{:02x}", byte));
+        }
+        result
+    }
+
+    /// Parses a field element from a hex string in the format returned by
+    /// [to_hex_string()](FieldElement::to_hex_string).
+    ///
+    /// The `0x` prefix is required, but leading zeros may be omitted, and both lower-case and
+    /// upper-case hex digits are accepted.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The string does not start with `0x` or contains characters other than hex digits.
+    /// * The string contains more than `2 * ELEMENT_BYTES` hex digits.
+    /// * The encoded value is not a valid field element (e.g., it is greater than or equal to
+    ///   the field modulus).
+    fn from_hex_str(source: &str) -> Result<Self, DeserializationError> {
+        let digits = source.strip_prefix("0x").ok_or_else(|| {
+            DeserializationError::InvalidValue("hex string must start with 0x".to_string())
+        })?;
+        if digits.is_empty() || digits.len() > Self::ELEMENT_BYTES * 2 {
+            return Err(DeserializationError::InvalidValue(format!(
+                "hex string must contain between 1 and {} digits, but contained {}",
+                Self::ELEMENT_BYTES * 2,
+                digits.len()
+            )));
+        }
+
+        // decode digits starting from the least significant one into little-endian bytes
+        let mut bytes = vec![0u8; Self::ELEMENT_BYTES];
+        for (i, digit) in digits.chars().rev().enumerate() {
+            let value = digit.to_digit(16).ok_or_else(|| {
+                DeserializationError::InvalidValue(format!("invalid hex digit '{}'", digit))
+            })?;
+            bytes[i / 2] |= (value as u8) << (4 * (i % 2));
+        }
+
+        let mut reader = SliceReader::new(&bytes);
+        Self::read_from(&mut reader)
+    }
+
+    // RANDOM SAMPLING
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a field element sampled uniformly at random using the provided `rng`.
+    ///
+    /// Elements are generated via rejection sampling: random bytes are drawn from `rng` and are
+    /// discarded unless they encode a valid field element. Before each attempt, bits above the
+    /// bit length of the base field modulus are cleared, and thus, the value of each base element
+    /// is valid with probability of at least 1/2.
+    fn random<R: RngCore>(rng: &mut R) -> Self {
+        let base_bytes = Self::BaseField::ELEMENT_BYTES;
+        let num_excess_bits = (base_bytes * 8) as u32 - Self::BaseField::MODULUS_BITS;
+        let high_byte_mask = if num_excess_bits < 8 {
+            0xffu8 >> num_excess_bits
+        } else {
+            0
+        };
+
+        let mut bytes = vec![0u8; Self::VALUE_SIZE];
+        loop {
+            rng.fill_bytes(&mut bytes);
+            for chunk in bytes.chunks_mut(base_bytes) {
+                chunk[base_bytes - 1] &= high_byte_mask;
+            }
+            if let Some(element) = Self::from_random_bytes(&bytes) {
+                return element;
+            }
+        }
+    }
+
+    /// Returns a non-zero field element sampled uniformly at random using the provided `rng`.
+    ///
+    /// This is the same as [random()](FieldElement::random), except that ZERO values are
+    /// rejected as well.
+    fn random_nonzero<R: RngCore>(rng: &mut R) -> Self {
+        loop {
+            let element = Self::random(rng);
+            if element != Self::ZERO {
+                return element;
+            }
+        }
+    }
+
     // UTILITIES
     // --------------------------------------------------------------------------------------------
 
@@ -226,6 +359,133 @@ pub trait StarkField: FieldElement<BaseField = Self> {
         Self::TWO_ADIC_ROOT_OF_UNITY.exp(power)
     }
 
+    /// Returns a square root of this field element if it exists, or None if this element is not
+    /// a quadratic residue.
+    ///
+    /// If the root exists, the other root is its negation; which of the two roots is returned
+    /// is not specified. When Self::MODULUS = 3 (mod 4), the root is computed directly as
+    /// `self`^((Self::MODULUS + 1) / 4); otherwise, Tonelli-Shanks algorithm is used.
+    ///
+    /// # Examples
+    /// ```
+    /// # use winter_math::{fields::f128::BaseElement, FieldElement, StarkField};
+    /// let a = BaseElement::new(42).square();
+    /// let root = a.sqrt().unwrap();
+    /// assert_eq!(a, root.square());
+    ///
+    /// // a generator of the multiplicative group is never a quadratic residue
+    /// assert_eq!(None, BaseElement::GENERATOR.sqrt());
+    /// ```
+    fn sqrt(&self) -> Option<Self> {
+        if *self == Self::ZERO {
+            return Some(Self::ZERO);
+        }
+
+        // by Euler's criterion, self is a quadratic residue iff self^((p - 1) / 2) = 1; since the
+        // modulus is odd, (p - 1) / 2 = p >> 1
+        let one = Self::PositiveInteger::from(1u32);
+        if self.exp(Self::MODULUS >> 1) != Self::ONE {
+            return None;
+        }
+
+        // p - 1 = q * 2^s where q is odd, and thus, q = p >> s and (q + 1) / 2 = (q >> 1) + 1
+        let q = Self::MODULUS >> Self::TWO_ADICITY;
+        let mut result = self.exp(q >> 1) * *self;
+        if Self::TWO_ADICITY == 1 {
+            // p = 3 (mod 4), and thus, (q + 1) / 2 = (p + 1) / 4
+            return Some(result);
+        }
+
+        // TWO_ADIC_ROOT_OF_UNITY = GENERATOR^q where GENERATOR is a quadratic non-residue
+        let mut c = Self::TWO_ADIC_ROOT_OF_UNITY;
+        let mut t = self.exp(q);
+        let mut m = Self::TWO_ADICITY;
+        while t != Self::ONE {
+            // find the least i such that t^(2^i) = 1; i < m because self is a quadratic residue
+            let mut i = 1;
+            let mut t_pow = t.square();
+            while t_pow != Self::ONE {
+                t_pow = t_pow.square();
+                i += 1;
+            }
+
+            let b = c.exp(one << (m - i - 1));
+            m = i;
+            c = b.square();
+            t *= c;
+            result *= b;
+        }
+
+        Some(result)
+    }
+
+    /// Returns the multiplicative inverse of this field element, or None if this element is ZERO.
+    ///
+    /// Unlike [inv()](FieldElement::inv), which relies on Fermat's little theorem, the inverse is
+    /// computed via the binary extended Euclidean algorithm over canonical integer representations
+    /// of this element and Self::MODULUS. This method is intended primarily as a reference
+    /// implementation: it is not constant time, and is usually slower than inv().
+    ///
+    /// # Examples
+    /// ```
+    /// # use winter_math::{fields::f128::BaseElement, FieldElement, StarkField};
+    /// let a = BaseElement::new(42);
+    /// assert_eq!(Some(a.inv()), a.inv_ext_gcd());
+    /// assert_eq!(None, BaseElement::ZERO.inv_ext_gcd());
+    /// ```
+    fn inv_ext_gcd(&self) -> Option<Self> {
+        let zero = Self::PositiveInteger::from(0u32);
+        let one = Self::PositiveInteger::from(1u32);
+        let p = Self::MODULUS;
+
+        let mut u = self.as_int();
+        if u == zero {
+            return None;
+        }
+        let mut v = p;
+
+        // x1 and x2 are kept in [0, p) such that x1 * self = u (mod p) and x2 * self = v (mod p);
+        // halving an odd x is computed as (x >> 1) + (p >> 1) + 1 = (x + p) / 2 to avoid overflow
+        let half = |x: Self::PositiveInteger| {
+            if x & one == zero {
+                x >> 1
+            } else {
+                (x >> 1) + (p >> 1) + one
+            }
+        };
+        let sub = |a: Self::PositiveInteger, b: Self::PositiveInteger| {
+            if a >= b {
+                a - b
+            } else {
+                a + (p - b)
+            }
+        };
+        let mut x1 = one;
+        let mut x2 = zero;
+
+        // since p is prime, gcd(u, v) = 1, and thus, either u or v eventually becomes 1
+        while u != one && v != one {
+            while u & one == zero {
+                u = u >> 1;
+                x1 = half(x1);
+            }
+            while v & one == zero {
+                v = v >> 1;
+                x2 = half(x2);
+            }
+            if u >= v {
+                u = u - v;
+                x1 = sub(x1, x2);
+            } else {
+                v = v - u;
+                x2 = sub(x2, x1);
+            }
+        }
+
+        let result = if u == one { x1 } else { x2 };
+        Some(Self::from(result.into()))
+    }
+
     /// Returns byte representation of the field modulus in little-endian byte order.
     fn get_modulus_le_bytes() -> Vec<u8>;
 
@@ -256,3 +516,16 @@ pub trait ExtensibleField<const N: usize>: StarkField {
         true
     }
 }
+
+// TOWER FIELD
+// ================================================================================================
+
+/// Defines a quadratic extension of a field which is itself an extension of a [StarkField].
+///
+/// The extension field is defined over the irreducible polynomial y<sup>2</sup> - ξ, where ξ is
+/// a quadratic non-residue in the implementing field; it is the responsibility of the implementor
+/// to make sure that ξ is not a square.
+pub trait TowerField: FieldElement {
+    /// Returns the quadratic non-residue ξ which defines the tower extension of this field.
+    fn tower_nonresidue() -> Self;
+}
@@ -0,0 +1,82 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Multi-precision integer arithmetic.
+//!
+//! This module re-exports [BigUint] from the `num-bigint` crate, and extends it with methods for
+//! converting between big integers and elements of STARK fields. This is useful for parameter
+//! generation (e.g., computing the order of the multiplicative group of a field, or deriving
+//! round constants of algebraic hash functions) where intermediate values do not fit into the
+//! native integer types.
+
+use crate::StarkField;
+use core::fmt;
+
+pub use num_bigint::BigUint;
+
+#[cfg(test)]
+mod tests;
+
+// FIELD INTEGER
+// ================================================================================================
+
+/// Defines conversions between multi-precision integers and elements of a [StarkField].
+pub trait FieldInteger: Sized {
+    /// Returns the modulus of the field `F` as a big integer.
+    fn from_field_modulus<F: StarkField>() -> Self;
+
+    /// Returns the canonical integer representation of the specified field `element`.
+    fn from_field_element<F: StarkField>(element: F) -> Self;
+
+    /// Converts this integer into an element of the field `F`.
+    ///
+    /// # Errors
+    /// Returns an error if this integer is greater than or equal to the modulus of `F`.
+    fn to_field_element<F: StarkField>(&self) -> Result<F, IntegerConversionError>;
+}
+
+impl FieldInteger for BigUint {
+    fn from_field_modulus<F: StarkField>() -> Self {
+        BigUint::from_bytes_le(&F::get_modulus_le_bytes())
+    }
+
+    fn from_field_element<F: StarkField>(element: F) -> Self {
+        BigUint::from_bytes_le(&element.to_bytes())
+    }
+
+    fn to_field_element<F: StarkField>(&self) -> Result<F, IntegerConversionError> {
+        if *self >= Self::from_field_modulus::<F>() {
+            return Err(IntegerConversionError::ValueTooLarge(self.bits()));
+        }
+
+        let mut bytes = self.to_bytes_le();
+        bytes.resize(F::ELEMENT_BYTES, 0);
+        F::try_from(&bytes[..]).map_err(|_| IntegerConversionError::ValueTooLarge(self.bits()))
+    }
+}
+
+// CONVERSION ERROR
+// ================================================================================================
+
+/// Defines errors which can occur when converting a big integer into a field element.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IntegerConversionError {
+    /// The integer with the specified number of bits is not smaller than the field modulus.
+    ValueTooLarge(u64),
+}
+
+impl fmt::Display for IntegerConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ValueTooLarge(num_bits) => {
+                write!(
+                    f,
+                    "a {}-bit integer is not smaller than the field modulus",
+                    num_bits
+                )
+            }
+        }
+    }
+}
@@ -0,0 +1,56 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{BigUint, FieldInteger, IntegerConversionError};
+use crate::{
+    fields::{f128, f62},
+    FieldElement, StarkField,
+};
+use rand_utils::rand_value;
+
+#[test]
+fn from_field_modulus() {
+    assert_eq!(
+        BigUint::from(f62::BaseElement::MODULUS),
+        BigUint::from_field_modulus::<f62::BaseElement>()
+    );
+    assert_eq!(
+        BigUint::from(f128::BaseElement::MODULUS),
+        BigUint::from_field_modulus::<f128::BaseElement>()
+    );
+
+    // the order of the multiplicative group is divisible by 2^TWO_ADICITY
+    let order = BigUint::from_field_modulus::<f128::BaseElement>() - 1u32;
+    let power_of_two = BigUint::from(1u32) << f128::BaseElement::TWO_ADICITY;
+    assert_eq!(BigUint::from(0u32), order % power_of_two);
+}
+
+#[test]
+fn to_field_element() {
+    let a: f62::BaseElement = rand_value();
+    let value = BigUint::from_field_element(a);
+    assert_eq!(BigUint::from(a.as_int()), value);
+    assert_eq!(Ok(a), value.to_field_element::<f62::BaseElement>());
+
+    let b: f128::BaseElement = rand_value();
+    let value = BigUint::from_field_element(b);
+    assert_eq!(Ok(b), value.to_field_element::<f128::BaseElement>());
+
+    // arithmetic over big integers matches field arithmetic
+    let modulus = BigUint::from_field_modulus::<f128::BaseElement>();
+    let product = BigUint::from_field_element(b) * BigUint::from_field_element(b) % &modulus;
+    assert_eq!(Ok(b * b), product.to_field_element::<f128::BaseElement>());
+
+    // values which are not smaller than the modulus cannot be converted
+    let modulus = BigUint::from_field_modulus::<f62::BaseElement>();
+    assert_eq!(
+        Err(IntegerConversionError::ValueTooLarge(62)),
+        modulus.to_field_element::<f62::BaseElement>()
+    );
+    assert_eq!(
+        Ok(-f62::BaseElement::ONE),
+        (modulus - 1u32).to_field_element::<f62::BaseElement>()
+    );
+}
@@ -49,12 +49,18 @@
 //! * For [f64](crate::fields::f64) field, the polynomial is x<sup>3</sup> - x - 1.
 //! * For [f128](crate::fields::f128) field, cubic extensions are not supported.
 //!
+//! Extensions of extension fields can be built using [TowerExtension](crate::fields::TowerExtension)
+//! for any field implementing the [TowerField] trait. Currently, this trait is implemented for the
+//! quadratic extension of the [f64](crate::fields::f64) field with non-residue 3 + φ, which yields
+//! a degree 4 extension of the base field.
+//!
 //! # Polynomials
 //! [Polynomials](polynom) module implements basic polynomial operations such as:
 //!
 //! * Evaluation of a polynomial at a single or multiple point.
 //! * Interpolation of a polynomial from a set of points (using
 //!   [Lagrange](https://en.wikipedia.org/wiki/Lagrange_polynomial) interpolation).
+//! * Evaluation of a polynomial from its values at a set of points (using barycentric formula).
 //! * Addition, multiplication, subtraction, and division of polynomials.
 //! * Synthetic polynomial division (using
 //!   [Ruffini's](https://en.wikipedia.org/wiki/Ruffini%27s_rule) method).
@@ -65,6 +71,11 @@
 //! This can be used to interpolate and evaluate polynomials in *O(n log n)* time as long as
 //! the domain of the polynomial is a multiplicative subgroup with size which is a power of 2.
 //!
+//! # Multi-precision integers
+//! [Integer](integer) module re-exports `BigUint` type from the `num-bigint` crate and provides
+//! conversions between big integers and field elements via the
+//! [FieldInteger](integer::FieldInteger) trait.
+//!
 //! # Concurrent execution
 //!
 //! When the crate is compiled with `concurrent` feature enabled, some operations will be
@@ -94,10 +105,11 @@
 extern crate alloc;
 
 pub mod fft;
+pub mod integer;
 pub mod polynom;
 
 mod field;
-pub use field::{ExtensibleField, FieldElement, StarkField};
+pub use field::{ExtensibleField, FieldElement, StarkField, TowerField};
 pub mod fields {
     //! Finite field implementations.
     //!
@@ -107,8 +119,10 @@ pub mod fields {
     pub use super::field::f128;
     pub use super::field::f62;
     pub use super::field::f64;
+    pub use super::field::instrumented;
     pub use super::field::CubeExtension;
     pub use super::field::QuadExtension;
+    pub use super::field::TowerExtension;
 }
 
 mod utils;
@@ -8,6 +8,7 @@
 //! This module provides a set of function for basic polynomial operations, including:
 //! - Polynomial evaluation using Horner method.
 //! - Polynomial interpolation using Lagrange method.
+//! - Evaluation of polynomials given in evaluation form using barycentric formula.
 //! - Polynomial addition, subtraction, multiplication, and division.
 //! - Synthetic polynomial division for efficient division by polynomials of the form
 //!   `x`^`a` - `b`.
@@ -26,7 +27,10 @@
 
 use crate::{field::FieldElement, utils::batch_inversion};
 use core::mem;
-use utils::{collections::Vec, group_vector_elements};
+use utils::{collections::Vec, group_vector_elements, iter};
+
+#[cfg(feature = "concurrent")]
+use utils::iterators::*;
 
 #[cfg(test)]
 mod tests;
@@ -66,6 +70,9 @@ where
 /// Evaluates polynomial `p` at all coordinates in `xs` slice by repeatedly invoking
 /// `polynom::eval()` function.
 ///
+/// When `concurrent` feature is enabled, the points are evaluated concurrently in multiple
+/// threads.
+///
 /// # Examples
 /// ```
 /// # use winter_math::polynom::*;
@@ -82,7 +89,7 @@ where
     B: FieldElement,
     E: FieldElement + From<B>,
 {
-    xs.iter().map(|x| eval(p, *x)).collect()
+    iter!(xs).map(|x| eval(p, *x)).collect()
 }
 
 // POLYNOMIAL INTERPOLATION
@@ -221,6 +228,113 @@ where
     result
 }
 
+// BARYCENTRIC EVALUATION
+// ================================================================================================
+
+/// Returns barycentric weights for the specified set of X coordinates.
+///
+/// The weight for coordinate $x_j$ is $w_j = 1 / \prod_{k \neq j}(x_j - x_k)$. The weights
+/// depend only on the X coordinates, and thus, they can be computed once and used to evaluate
+/// many polynomials defined over the same coordinates via [eval_barycentric()]. Computing the
+/// weights requires $O(n^2)$ operations and a single inversion.
+///
+/// When `concurrent` feature is enabled, the weights are computed concurrently in multiple
+/// threads.
+///
+/// # Panics
+/// Panics if `xs` is empty or, in debug mode, if `xs` contains duplicate values.
+///
+/// # Examples
+/// ```
+/// # use winter_math::polynom::*;
+/// # use winter_math::{fields::{f128::BaseElement}, FieldElement};
+/// let xs = (1_u32..4).map(BaseElement::from).collect::<Vec<_>>();
+/// let weights = get_barycentric_weights(&xs);
+///
+/// // w_0 = 1 / ((1 - 2) * (1 - 3)) = 1 / 2
+/// assert_eq!(BaseElement::new(2).inv(), weights[0]);
+/// ```
+pub fn get_barycentric_weights<E>(xs: &[E]) -> Vec<E>
+where
+    E: FieldElement,
+{
+    assert!(!xs.is_empty(), "at least one X coordinate must be provided");
+
+    // the product of (x_j - x_k) for all k != j is the value of the derivative of the zero
+    // polynomial of xs at x_j
+    let roots = get_zero_roots(xs);
+    let derivative = roots
+        .iter()
+        .enumerate()
+        .skip(1)
+        .map(|(i, &c)| c * E::from(i as u64))
+        .collect::<Vec<_>>();
+    let denominators = eval_many(&derivative, xs);
+    debug_assert!(
+        denominators.iter().all(|&d| d != E::ZERO),
+        "X coordinates must be distinct"
+    );
+    batch_inversion(&denominators)
+}
+
+/// Evaluates a polynomial defined by its values at a set of points at the specified point `x`.
+///
+/// The polynomial is the unique polynomial of degree smaller than `xs.len()` which evaluates
+/// to `ys[j]` at `xs[j]`; `weights` must be the barycentric weights of `xs` returned from
+/// [get_barycentric_weights()]. The polynomial is evaluated using the barycentric formula
+/// $p(x) = l(x) \cdot \sum_j{w_j \cdot y_j / (x - x_j)}$, where $l(x) = \prod_j (x - x_j)$,
+/// without interpolating it into coefficient form. This requires $O(n)$ operations and a single
+/// inversion.
+///
+/// # Panics
+/// Panics if `xs`, `weights`, and `ys` do not have the same length.
+///
+/// # Examples
+/// ```
+/// # use winter_math::polynom::*;
+/// # use winter_math::{fields::{f128::BaseElement}, FieldElement};
+/// # use rand_utils::rand_vector;
+/// let xs: Vec<BaseElement> = rand_vector(16);
+/// let ys: Vec<BaseElement> = rand_vector(16);
+/// let weights = get_barycentric_weights(&xs);
+///
+/// let p = interpolate(&xs, &ys, false);
+/// let x = BaseElement::new(42);
+/// assert_eq!(eval(&p, x), eval_barycentric(&xs, &weights, &ys, x));
+/// ```
+pub fn eval_barycentric<B, E>(xs: &[B], weights: &[B], ys: &[E], x: E) -> E
+where
+    B: FieldElement,
+    E: FieldElement + From<B>,
+{
+    assert_eq!(
+        xs.len(),
+        weights.len(),
+        "number of X coordinates and weights must be the same"
+    );
+    assert_eq!(
+        xs.len(),
+        ys.len(),
+        "number of X and Y coordinates must be the same"
+    );
+
+    // the formula cannot be used when x is one of the X coordinates
+    let differences = xs.iter().map(|&xj| x - E::from(xj)).collect::<Vec<_>>();
+    if let Some(j) = differences.iter().position(|&d| d == E::ZERO) {
+        return ys[j];
+    }
+
+    let inv_differences = batch_inversion(&differences);
+    let l = differences.iter().fold(E::ONE, |acc, &d| acc * d);
+    let sum = inv_differences
+        .iter()
+        .zip(weights.iter().zip(ys))
+        .fold(E::ZERO, |acc, (&inv_d, (&w, &y))| {
+            acc + inv_d * E::from(w) * y
+        });
+    l * sum
+}
+
 // POLYNOMIAL MATH OPERATIONS
 // ================================================================================================
 
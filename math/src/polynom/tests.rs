@@ -5,9 +5,10 @@
 
 use super::remove_leading_zeros;
 use crate::{
-    field::{f128::BaseElement, FieldElement, StarkField},
+    field::{f128::BaseElement, FieldElement, QuadExtension, StarkField},
     utils::{get_power_series, log2},
 };
+use rand_utils::{rand_value, rand_vector};
 use utils::collections::Vec;
 
 #[test]
@@ -43,6 +44,37 @@ fn eval() {
     );
 }
 
+#[test]
+fn eval_barycentric() {
+    let xs: Vec<BaseElement> = rand_vector(16);
+    let ys: Vec<BaseElement> = rand_vector(16);
+    let weights = super::get_barycentric_weights(&xs);
+    let poly = super::interpolate(&xs, &ys, false);
+
+    // at the X coordinates, the polynomial evaluates to the Y coordinates
+    for (&x, &y) in xs.iter().zip(ys.iter()) {
+        assert_eq!(y, super::eval_barycentric(&xs, &weights, &ys, x));
+    }
+
+    // at other points, the result is the same as for the interpolated polynomial
+    for &x in rand_vector::<BaseElement>(4).iter() {
+        assert_eq!(
+            super::eval(&poly, x),
+            super::eval_barycentric(&xs, &weights, &ys, x)
+        );
+    }
+
+    // values and the evaluation point can be in an extension field
+    let ys: Vec<QuadExtension<BaseElement>> = rand_vector(16);
+    let x: QuadExtension<BaseElement> = rand_value();
+    let xs_ext = xs.iter().map(|&x| x.into()).collect::<Vec<_>>();
+    let poly = super::interpolate(&xs_ext, &ys, false);
+    assert_eq!(
+        super::eval(&poly, x),
+        super::eval_barycentric(&xs, &weights, &ys, x)
+    );
+}
+
 #[test]
 fn add() {
     let poly1: [BaseElement; 3] = [
@@ -4,11 +4,14 @@
 // LICENSE file in the root directory of this source tree.
 
 use air::{
-    proof::{Commitments, Context, OodFrame, Queries, StarkProof},
-    Air, ConstraintCompositionCoefficients, DeepCompositionCoefficients, EvaluationFrame,
+    proof::{
+        Commitments, Context, OodFrame, Queries, StarkProof, AUX_TRACE_COMMITMENT_TAG,
+        CONSTRAINT_COMMITMENT_TAG, OOD_EVALUATIONS_TAG, OOD_FRAME_TAG, TRACE_COMMITMENT_TAG,
+    },
+    Air, ConstraintCompositionCoefficients, DeepCompositionCoefficients,
 };
 use core::marker::PhantomData;
-use crypto::{ElementHasher, RandomCoin};
+use crypto::{ElementHasher, PublicCoin};
 use fri::{self, FriProof};
 use math::FieldElement;
 use utils::{collections::Vec, Serializable};
@@ -19,14 +22,15 @@ use utils::iterators::*;
 // TYPES AND INTERFACES
 // ================================================================================================
 
-pub struct ProverChannel<'a, A, E, H>
+pub struct ProverChannel<'a, A, E, H, R>
 where
     A: Air,
     E: FieldElement<BaseField = A::BaseElement>,
     H: ElementHasher<BaseField = A::BaseElement>,
+    R: PublicCoin<BaseField = A::BaseElement, Hasher = H> + Sync,
 {
     air: &'a A,
-    public_coin: RandomCoin<A::BaseElement, H>,
+    public_coin: R,
     context: Context,
     commitments: Commitments,
     ood_frame: OodFrame,
@@ -37,27 +41,33 @@ where
 // PROVER CHANNEL IMPLEMENTATION
 // ================================================================================================
 
-impl<'a, A, E, H> ProverChannel<'a, A, E, H>
+impl<'a, A, E, H, R> ProverChannel<'a, A, E, H, R>
 where
     A: Air,
     E: FieldElement<BaseField = A::BaseElement>,
     H: ElementHasher<BaseField = A::BaseElement>,
+    R: PublicCoin<BaseField = A::BaseElement, Hasher = H> + Sync,
 {
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
     /// Creates a new prover channel for the specified `air` and public inputs.
     pub fn new(air: &'a A, pub_inputs_bytes: Vec<u8>) -> Self {
-        let context = Context::new::<A::BaseElement>(air.trace_info(), air.options().clone());
+        let context = Context::new::<A::BaseElement>(air.trace_info(), air.options().clone())
+            .with_aux_trace_width(air.auxiliary_trace_width());
 
         // build a seed for the public coin; the initial seed is the hash of public inputs and proof
         // context, but as the protocol progresses, the coin will be reseeded with the info sent to
         // the verifier
         let mut coin_seed = pub_inputs_bytes;
         context.write_into(&mut coin_seed);
+        let channel_seed = air.channel_seed();
+        if channel_seed != [0; 32] {
+            coin_seed.extend_from_slice(&channel_seed);
+        }
 
         ProverChannel {
             air,
-            public_coin: RandomCoin::new(&coin_seed),
+            public_coin: R::new(&coin_seed),
             context,
             commitments: Commitments::default(),
             ood_frame: OodFrame::default(),
@@ -72,38 +82,69 @@ where
     /// Commits the prover the extended execution trace.
     pub fn commit_trace(&mut self, trace_root: H::Digest) {
         self.commitments.add::<H>(&trace_root);
-        self.public_coin.reseed(trace_root);
+        self.public_coin
+            .reseed_with_tag(&TRACE_COMMITMENT_TAG, trace_root);
+    }
+
+    /// Commits the prover the extended auxiliary trace segment.
+    pub fn commit_aux_trace(&mut self, aux_trace_root: H::Digest) {
+        self.commitments.add::<H>(&aux_trace_root);
+        self.public_coin
+            .reseed_with_tag(&AUX_TRACE_COMMITMENT_TAG, aux_trace_root);
     }
 
     /// Commits the prover to the evaluations of the constraint composition polynomial.
     pub fn commit_constraints(&mut self, constraint_root: H::Digest) {
         self.commitments.add::<H>(&constraint_root);
-        self.public_coin.reseed(constraint_root);
-    }
-
-    /// Saves the out-of-domain evaluation frame. This also reseeds the public coin with the
-    /// hashes of the evaluation frame states.
-    pub fn send_ood_evaluation_frame(&mut self, frame: &EvaluationFrame<E>) {
-        self.ood_frame.set_evaluation_frame(frame);
-        self.public_coin.reseed(H::hash_elements(frame.current()));
-        self.public_coin.reseed(H::hash_elements(frame.next()));
+        self.public_coin
+            .reseed_with_tag(&CONSTRAINT_COMMITMENT_TAG, constraint_root);
     }
 
-    /// Saves the evaluations of constraint composition polynomial columns at the out-of-domain
-    /// point. This also reseeds the public coin wit the hash of the evaluations.
-    pub fn send_ood_constraint_evaluations(&mut self, evaluations: &[E]) {
-        self.ood_frame.set_constraint_evaluations(evaluations);
-        self.public_coin.reseed(H::hash_elements(evaluations));
+    /// Saves the out-of-domain frame. This also reseeds the public coin with the hashes of the
+    /// evaluation frame states and the hash of constraint composition polynomial evaluations.
+    pub fn send_ood_frame(&mut self, frame: OodFrame) {
+        for i in 0..frame.num_trace_rows() {
+            let row = frame
+                .parse_trace_row::<E>(
+                    i,
+                    self.context.trace_width() + self.context.aux_trace_width(),
+                )
+                .expect("failed to parse OOD trace evaluations");
+            self.public_coin
+                .reseed_with_tag(&OOD_FRAME_TAG, H::hash_elements(&row));
+        }
+        let evaluations = frame
+            .parse_evaluations::<E>(self.air.ce_blowup_factor())
+            .expect("failed to parse OOD constraint evaluations");
+        self.public_coin
+            .reseed_with_tag(&OOD_EVALUATIONS_TAG, H::hash_elements(&evaluations));
+        self.ood_frame = frame;
     }
 
     // PUBLIC COIN METHODS
     // --------------------------------------------------------------------------------------------
 
+    /// Returns random elements for building the auxiliary trace segment drawn from the public
+    /// coin; the elements are drawn from the field in which the protocol is executed.
+    pub fn get_aux_rand_elements(&mut self) -> Vec<E> {
+        (0..self.air.num_aux_rand_elements())
+            .map(|_| {
+                self.public_coin
+                    .draw()
+                    .expect("failed to draw auxiliary random element")
+            })
+            .collect()
+    }
+
     /// Returns a set of coefficients for constructing a constraint composition polynomial drawn
-    /// from the public coin.
-    pub fn get_constraint_composition_coeffs(&mut self) -> ConstraintCompositionCoefficients<E> {
+    /// from the public coin; `num_assertions` is the number of assertions of the computation,
+    /// including domain point constraints.
+    pub fn get_constraint_composition_coeffs(
+        &mut self,
+        num_assertions: usize,
+    ) -> ConstraintCompositionCoefficients<E> {
         self.air
-            .get_constraint_composition_coefficients(&mut self.public_coin)
+            .get_constraint_composition_coefficients(&mut self.public_coin, num_assertions)
             .expect("failed to draw composition coefficients")
     }
 
@@ -133,6 +174,10 @@ where
     /// Determines a nonce, which when hashed with the current seed of the public coin results
     /// in a new seed with the number of leading zeros equal to the grinding_factor specified
     /// in the proof options.
+    ///
+    /// The smallest such nonce is always selected, also when the nonce is searched for in
+    /// multiple threads; thus, the proof does not depend on the number of threads or on the order
+    /// in which the threads are scheduled.
     pub fn grind_query_seed(&mut self) {
         let grinding_factor = self.context.options().grinding_factor();
 
@@ -144,7 +189,7 @@ where
         #[cfg(feature = "concurrent")]
         let nonce = (1..u64::MAX)
             .into_par_iter()
-            .find_any(|&nonce| self.public_coin.check_leading_zeros(nonce) >= grinding_factor)
+            .find_first(|&nonce| self.public_coin.check_leading_zeros(nonce) >= grinding_factor)
             .expect("nonce not found");
 
         self.pow_nonce = nonce;
@@ -158,6 +203,7 @@ where
     pub fn build_proof(
         self,
         trace_queries: Queries,
+        aux_trace_queries: Option<Queries>,
         constraint_queries: Queries,
         fri_proof: FriProof,
     ) -> StarkProof {
@@ -166,9 +212,11 @@ where
             commitments: self.commitments,
             ood_frame: self.ood_frame,
             trace_queries,
+            aux_trace_queries,
             constraint_queries,
             fri_proof,
             pow_nonce: self.pow_nonce,
+            format_version: StarkProof::FORMAT_VERSION,
         }
     }
 }
@@ -176,18 +224,20 @@ where
 // FRI PROVER CHANNEL IMPLEMENTATION
 // ================================================================================================
 
-impl<'a, A, E, H> fri::ProverChannel<E> for ProverChannel<'a, A, E, H>
+impl<'a, A, E, H, R> fri::ProverChannel<E> for ProverChannel<'a, A, E, H, R>
 where
     A: Air,
     E: FieldElement<BaseField = A::BaseElement>,
     H: ElementHasher<BaseField = A::BaseElement>,
+    R: PublicCoin<BaseField = A::BaseElement, Hasher = H> + Sync,
 {
     type Hasher = H;
 
     /// Commits the prover to a FRI layer.
     fn commit_fri_layer(&mut self, layer_root: H::Digest) {
         self.commitments.add::<H>(&layer_root);
-        self.public_coin.reseed(layer_root);
+        self.public_coin
+            .reseed_with_tag(&fri::FRI_LAYER_TAG, layer_root);
     }
 
     /// Returns a new alpha drawn from the public coin.
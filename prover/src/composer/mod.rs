@@ -65,12 +65,19 @@ impl<A: Air, E: FieldElement<BaseField = A::BaseElement>> DeepCompositionPoly<A,
     /// - In cases when we generate the proof using an extension field, we also compute
     ///   T'''_i(x) = (T_i(x) - T_i(z_conjugate)) / (x - z_conjugate), and add it to T(x) similarly
     ///   to the way described above. This is needed in order to verify that the trace is defined
-    ///   over the base field, rather than the extension field.
+    ///   over the base field, rather than the extension field. Polynomials of the auxiliary trace
+    ///   segment are defined over the extension field, and thus, are not included into T'''(x).
+    /// - For evaluation frames with more than two rows, we also compute
+    ///   (T_i(x) - T_i(z * g^k)) / (x - z * g^k) for each additional row k, and add them to T(x)
+    ///   similarly to the way described above.
     ///
-    /// Note that evaluations of T_i(z) and T_i(z * g) are passed in via the `ood_frame` parameter.
+    /// Note that evaluations of T_i(z), T_i(z * g) etc. are passed in via the `ood_frame`
+    /// parameter; in each row of the frame, evaluations of the auxiliary segment polynomials
+    /// follow evaluations of the main segment polynomials.
     pub fn add_trace_polys(
         &mut self,
         trace_polys: TracePolyTable<A::BaseElement>,
+        aux_trace_polys: Option<TracePolyTable<E>>,
         ood_frame: EvaluationFrame<E>,
     ) {
         assert!(self.coefficients.is_empty());
@@ -81,64 +88,112 @@ impl<A: Air, E: FieldElement<BaseField = A::BaseElement>> DeepCompositionPoly<A,
         let g = E::from(A::BaseElement::get_root_of_unity(log2(trace_length)));
         let next_z = self.z * g;
 
-        // cache state of registers at points z and z * g
-        let trace_state1 = ood_frame.current();
-        let trace_state2 = ood_frame.next();
-
         // combine trace polynomials into 2 composition polynomials T'(x) and T''(x), and if
-        // we are using a field extension, also T'''(x)
-        let polys = trace_polys.into_vec();
-        let mut t1_composition = E::zeroed_vector(trace_length);
-        let mut t2_composition = E::zeroed_vector(trace_length);
-        let mut t3_composition = if self.field_extension {
-            E::zeroed_vector(trace_length)
-        } else {
-            Vec::new()
-        };
-        for (i, poly) in polys.into_iter().enumerate() {
-            // compute T'(x) = T(x) - T(z), multiply it by a pseudo-random coefficient,
-            // and add the result into composition polynomial
-            acc_poly(
-                &mut t1_composition,
-                &poly,
-                trace_state1[i],
-                self.cc.trace[i].0,
-            );
+        // we are using a field extension, also T'''(x); compositions for additional rows of the
+        // evaluation frame follow these polynomials
+        let mut compositions = vec![
+            E::zeroed_vector(trace_length),
+            E::zeroed_vector(trace_length),
+            if self.field_extension {
+                E::zeroed_vector(trace_length)
+            } else {
+                Vec::new()
+            },
+        ];
+        compositions.extend((2..ood_frame.size()).map(|_| E::zeroed_vector(trace_length)));
 
-            // compute T''(x) = T(x) - T(z * g), multiply it by a pseudo-random coefficient,
-            // and add the result into composition polynomial
-            acc_poly(
-                &mut t2_composition,
+        let main_trace_width = trace_polys.num_polys();
+        for (i, poly) in trace_polys.into_vec().into_iter().enumerate() {
+            self.acc_trace_poly(
+                &mut compositions,
                 &poly,
-                trace_state2[i],
-                self.cc.trace[i].1,
+                i,
+                &ood_frame,
+                self.field_extension,
             );
-
-            // when extension field is enabled, compute T'''(x) = T(x) - T(z_conjugate), multiply
-            // it by a pseudo-random coefficient, and add the result into composition polynomial
-            if self.field_extension {
-                acc_poly(
-                    &mut t3_composition,
-                    &poly,
-                    trace_state1[i].conjugate(),
-                    self.cc.trace[i].2,
-                );
+        }
+        if let Some(aux_trace_polys) = aux_trace_polys {
+            for (i, poly) in aux_trace_polys.into_vec().into_iter().enumerate() {
+                let register = main_trace_width + i;
+                self.acc_trace_poly(&mut compositions, &poly, register, &ood_frame, false);
             }
         }
 
-        // divide the composition polynomials by (x - z), (x - z * g), and (x - z_conjugate)
-        // respectively, and add the resulting polynomials together; the output of this step
-        // is a single trace polynomial T(x) and deg(T(x)) = trace_length - 2.
-        let trace_poly = merge_trace_compositions(
-            vec![t1_composition, t2_composition, t3_composition],
-            vec![self.z, next_z, self.z.conjugate()],
-        );
+        // divide the composition polynomials by (x - z), (x - z * g), (x - z_conjugate), and
+        // (x - z * g^k) for additional rows respectively, and add the resulting polynomials
+        // together; the output of this step is a single trace polynomial T(x) and
+        // deg(T(x)) = trace_length - 2.
+        let mut divisors = vec![self.z, next_z, self.z.conjugate()];
+        let mut row_z = next_z;
+        for _ in 2..ood_frame.size() {
+            row_z *= g;
+            divisors.push(row_z);
+        }
+        let trace_poly = merge_trace_compositions(compositions, divisors);
 
         // set the coefficients of the DEEP composition polynomial
         self.coefficients = trace_poly;
         assert_eq!(self.poly_size() - 2, self.degree());
     }
 
+    /// Adds the trace polynomial for the specified `register` into the trace composition
+    /// polynomials T'(x), T''(x), T'''(x), and compositions for additional rows of the
+    /// evaluation frame (in this order); T'''(x) is updated only if `include_conjugate` is true.
+    fn acc_trace_poly<F>(
+        &self,
+        compositions: &mut [Vec<E>],
+        poly: &[F],
+        register: usize,
+        ood_frame: &EvaluationFrame<E>,
+        include_conjugate: bool,
+    ) where
+        F: FieldElement<BaseField = A::BaseElement>,
+        E: From<F>,
+    {
+        let (compositions, tn_compositions) = compositions.split_at_mut(3);
+
+        // compute T'(x) = T(x) - T(z), multiply it by a pseudo-random coefficient,
+        // and add the result into composition polynomial
+        acc_poly(
+            &mut compositions[0],
+            poly,
+            ood_frame.current()[register],
+            self.cc.trace[register].0,
+        );
+
+        // compute T''(x) = T(x) - T(z * g), multiply it by a pseudo-random coefficient,
+        // and add the result into composition polynomial
+        acc_poly(
+            &mut compositions[1],
+            poly,
+            ood_frame.next()[register],
+            self.cc.trace[register].1,
+        );
+
+        // when extension field is enabled, compute T'''(x) = T(x) - T(z_conjugate), multiply
+        // it by a pseudo-random coefficient, and add the result into composition polynomial
+        if include_conjugate {
+            acc_poly(
+                &mut compositions[2],
+                poly,
+                ood_frame.current()[register].conjugate(),
+                self.cc.trace[register].2,
+            );
+        }
+
+        // for each additional row of the evaluation frame, compute T(x) - T(z * g^k),
+        // multiply it by a pseudo-random coefficient, and add the result into the
+        // composition polynomial for this row
+        for (k, composition) in tn_compositions.iter_mut().enumerate() {
+            acc_poly(
+                composition,
+                poly,
+                ood_frame.row(k + 2)[register],
+                self.cc.additional_trace[k][register],
+            );
+        }
+    }
+
     // CONSTRAINT POLYNOMIAL COMPOSITION
     // --------------------------------------------------------------------------------------------
     /// Divides out OOD point z from the constraint composition polynomial and saves the result
@@ -248,10 +303,10 @@ fn merge_trace_compositions<E: FieldElement>(mut polys: Vec<Vec<E>>, divisors: V
 }
 
 /// Computes (P(x) - value) * k and saves the result into the accumulator
-fn acc_poly<B, E>(accumulator: &mut Vec<E>, poly: &[B], value: E, k: E)
+fn acc_poly<F, E>(accumulator: &mut Vec<E>, poly: &[F], value: E, k: E)
 where
-    B: StarkField,
-    E: FieldElement<BaseField = B>,
+    F: FieldElement,
+    E: FieldElement<BaseField = F::BaseField> + From<F>,
 {
     mul_acc(accumulator, poly, k);
     let adjusted_tz = value * k;
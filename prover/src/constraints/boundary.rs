@@ -4,7 +4,7 @@
 // LICENSE file in the root directory of this source tree.
 
 use air::Air;
-use math::{fft, polynom, FieldElement, StarkField};
+use math::{fft, polynom, FieldElement};
 use utils::collections::{BTreeMap, Vec};
 
 // CONSTANTS
@@ -18,31 +18,51 @@ const SMALL_POLY_DEGREE: usize = 63;
 // ================================================================================================
 
 /// Contains constraints all having the same divisor. The constraints are separated into single
-/// value constraints, small polynomial constraints, and large polynomial constraints.
-pub struct BoundaryConstraintGroup<B: StarkField, E: FieldElement<BaseField = B>> {
+/// value constraints, linear combination constraints, small polynomial constraints, and large
+/// polynomial constraints.
+///
+/// Values of the constraints are elements of field `F`; this is the base field for constraints
+/// against the main trace segment, and the extension field `E` for constraints against the
+/// auxiliary trace segment.
+pub struct BoundaryConstraintGroup<
+    F: FieldElement,
+    E: FieldElement<BaseField = F::BaseField> + From<F>,
+> {
     pub(super) degree_adjustment: u32,
-    single_value_constraints: Vec<SingleValueConstraint<B, E>>,
-    small_poly_constraints: Vec<SmallPolyConstraint<B, E>>,
-    large_poly_constraints: Vec<LargePolyConstraint<B, E>>,
+    single_value_constraints: Vec<SingleValueConstraint<F, E>>,
+    linear_combination_constraints: Vec<LinearCombinationConstraint<F, E>>,
+    small_poly_constraints: Vec<SmallPolyConstraint<F, E>>,
+    large_poly_constraints: Vec<LargePolyConstraint<F, E>>,
 }
 
-impl<B: StarkField, E: FieldElement<BaseField = B>> BoundaryConstraintGroup<B, E> {
+impl<F: FieldElement, E: FieldElement<BaseField = F::BaseField> + From<F>>
+    BoundaryConstraintGroup<F, E>
+{
     /// Creates a new specialized constraint group; twiddles and ce_blowup_factor are passed in for
     /// evaluating large polynomial constraints (if any).
-    pub fn new<A: Air<BaseElement = B>>(
-        group: air::BoundaryConstraintGroup<B, E>,
+    pub fn new<A: Air<BaseElement = F::BaseField>>(
+        group: air::BoundaryConstraintGroup<F, E>,
         air: &A,
-        twiddle_map: &mut BTreeMap<usize, Vec<B>>,
-    ) -> BoundaryConstraintGroup<B, E> {
+        twiddle_map: &mut BTreeMap<usize, Vec<F::BaseField>>,
+    ) -> BoundaryConstraintGroup<F, E> {
         let mut result = BoundaryConstraintGroup {
             degree_adjustment: group.degree_adjustment(),
             single_value_constraints: Vec::new(),
+            linear_combination_constraints: Vec::new(),
             small_poly_constraints: Vec::new(),
             large_poly_constraints: Vec::new(),
         };
 
         for constraint in group.constraints() {
-            if constraint.poly().len() == 1 {
+            if !constraint.terms().is_empty() {
+                result
+                    .linear_combination_constraints
+                    .push(LinearCombinationConstraint {
+                        terms: constraint.terms().to_vec(),
+                        value: constraint.poly()[0],
+                        coefficients: *constraint.cc(),
+                    });
+            } else if constraint.poly().len() == 1 {
                 result.single_value_constraints.push(SingleValueConstraint {
                     register: constraint.register(),
                     value: constraint.poly()[0],
@@ -84,7 +104,7 @@ impl<B: StarkField, E: FieldElement<BaseField = B>> BoundaryConstraintGroup<B, E
 
     /// Evaluates the constraints contained in this group at the specified step of the
     /// execution trace.
-    pub fn evaluate(&self, state: &[B], ce_step: usize, x: B, xp: E) -> E {
+    pub fn evaluate(&self, state: &[F], ce_step: usize, x: F::BaseField, xp: E) -> E {
         let mut result = E::ZERO;
 
         // evaluate all single-value constraints
@@ -92,6 +112,11 @@ impl<B: StarkField, E: FieldElement<BaseField = B>> BoundaryConstraintGroup<B, E
             result += constraint.evaluate(state, xp);
         }
 
+        // evaluate all linear combination constraints
+        for constraint in self.linear_combination_constraints.iter() {
+            result += constraint.evaluate(state, xp);
+        }
+
         // evaluate all small polynomial constraints
         for constraint in self.small_poly_constraints.iter() {
             result += constraint.evaluate(state, x, xp);
@@ -111,34 +136,65 @@ impl<B: StarkField, E: FieldElement<BaseField = B>> BoundaryConstraintGroup<B, E
 
 /// A constraint where the numerator can be represented by p(x) - v, where v is the asserted value,
 /// and p(x) is the trace polynomial for the register against which the constraint is applied.
-struct SingleValueConstraint<B: StarkField, E: FieldElement<BaseField = B>> {
+struct SingleValueConstraint<F: FieldElement, E: FieldElement<BaseField = F::BaseField> + From<F>> {
     register: usize,
-    value: B,
+    value: F,
     coefficients: (E, E),
 }
 
-impl<B: StarkField, E: FieldElement<BaseField = B>> SingleValueConstraint<B, E> {
-    pub fn evaluate(&self, state: &[B], xp: E) -> E {
+impl<F: FieldElement, E: FieldElement<BaseField = F::BaseField> + From<F>>
+    SingleValueConstraint<F, E>
+{
+    pub fn evaluate(&self, state: &[F], xp: E) -> E {
         let evaluation = E::from(state[self.register] - self.value);
         evaluation * (self.coefficients.0 + self.coefficients.1 * xp)
     }
 }
 
+/// A constraint where the numerator can be represented by $\sum_i{a_i \cdot p_i(x)} - v$, where
+/// v is the asserted value, and $p_i(x)$ are trace polynomials for the registers of the linear
+/// combination against which the constraint is applied.
+struct LinearCombinationConstraint<
+    F: FieldElement,
+    E: FieldElement<BaseField = F::BaseField> + From<F>,
+> {
+    terms: Vec<(usize, F::BaseField)>,
+    value: F,
+    coefficients: (E, E),
+}
+
+impl<F: FieldElement, E: FieldElement<BaseField = F::BaseField> + From<F>>
+    LinearCombinationConstraint<F, E>
+{
+    pub fn evaluate(&self, state: &[F], xp: E) -> E {
+        let combination = self
+            .terms
+            .iter()
+            .fold(F::ZERO, |acc, &(register, coefficient)| {
+                acc + state[register] * F::from(coefficient)
+            });
+        let evaluation = E::from(combination - self.value);
+        evaluation * (self.coefficients.0 + self.coefficients.1 * xp)
+    }
+}
+
 /// A constraint where the numerator can be represented by p(x) - c(x), where c(x) is the
 /// polynomial describing a set of asserted values. This specialization is useful when the
 // degree of c(x) is relatively small, and thus, is cheap to evaluate on the fly.
-struct SmallPolyConstraint<B: StarkField, E: FieldElement<BaseField = B>> {
+struct SmallPolyConstraint<F: FieldElement, E: FieldElement<BaseField = F::BaseField> + From<F>> {
     register: usize,
-    poly: Vec<B>,
-    x_offset: B,
+    poly: Vec<F>,
+    x_offset: F::BaseField,
     coefficients: (E, E),
 }
 
-impl<B: StarkField, E: FieldElement<BaseField = B>> SmallPolyConstraint<B, E> {
-    pub fn evaluate(&self, state: &[B], x: B, xp: E) -> E {
+impl<F: FieldElement, E: FieldElement<BaseField = F::BaseField> + From<F>>
+    SmallPolyConstraint<F, E>
+{
+    pub fn evaluate(&self, state: &[F], x: F::BaseField, xp: E) -> E {
         let x = x * self.x_offset;
         // evaluate constraint polynomial as x * offset
-        let assertion_value = polynom::eval(&self.poly, x);
+        let assertion_value = polynom::eval(&self.poly, F::from(x));
         let evaluation = E::from(state[self.register] - assertion_value);
         evaluation * (self.coefficients.0 + self.coefficients.1 * xp)
     }
@@ -147,15 +203,17 @@ impl<B: StarkField, E: FieldElement<BaseField = B>> SmallPolyConstraint<B, E> {
 /// A constraint where the numerator can be represented by p(x) - c(x), where c(x) is a large
 /// polynomial. In such cases, we pre-compute evaluations of c(x) by evaluating it over the
 /// entire constraint evaluation domain (using FFT).
-struct LargePolyConstraint<B: StarkField, E: FieldElement<BaseField = B>> {
+struct LargePolyConstraint<F: FieldElement, E: FieldElement<BaseField = F::BaseField> + From<F>> {
     register: usize,
-    values: Vec<B>,
+    values: Vec<F>,
     step_offset: usize,
     coefficients: (E, E),
 }
 
-impl<B: StarkField, E: FieldElement<BaseField = B>> LargePolyConstraint<B, E> {
-    pub fn evaluate(&self, state: &[B], ce_step: usize, xp: E) -> E {
+impl<F: FieldElement, E: FieldElement<BaseField = F::BaseField> + From<F>>
+    LargePolyConstraint<F, E>
+{
+    pub fn evaluate(&self, state: &[F], ce_step: usize, xp: E) -> E {
         let value_index = if self.step_offset > 0 {
             // if the assertion happens on steps which are not a power of 2, we need to offset the
             // evaluation; the below basically computes (ce_step - step_offset) % values.len();
@@ -11,9 +11,35 @@ use utils::{batch_iter_mut, collections::Vec, uninit_vector};
 #[cfg(feature = "concurrent")]
 use utils::iterators::*;
 
+// COMPOSITION COMMITMENT
+// ================================================================================================
+
+/// Defines how the prover commits to the evaluations of the constraint composition polynomial
+/// columns over the LDE domain, and how it opens the commitment at the queried positions.
+///
+/// The default strategy used by the prover is [ConstraintCommitment]. The verifier checks the
+/// openings as batch Merkle proofs against hashes of evaluation rows (i.e., of the evaluations of
+/// all columns at the same position), and thus, proofs generated using an alternative strategy
+/// can be verified only if its openings have the same shape.
+pub trait CompositionCommitment<E, H>: Sized
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    /// Commits to the specified evaluations of the composition polynomial columns, and returns
+    /// the root of the commitment together with the committed data.
+    fn commit(evaluations: Vec<Vec<E>>) -> (H::Digest, Self);
+
+    /// Returns evaluations of all columns at the specified `positions` together with a proof
+    /// which opens the commitment at these positions.
+    fn open(self, positions: &[usize]) -> Queries;
+}
+
 // CONSTRAINT COMMITMENT
 // ================================================================================================
 
+/// Commitment to the evaluations of the constraint composition polynomial columns in a single
+/// Merkle tree.
 pub struct ConstraintCommitment<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> {
     evaluations: Vec<Vec<E>>,
     commitment: MerkleTree<H>,
@@ -85,6 +111,21 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> ConstraintComm
     }
 }
 
+impl<E, H> CompositionCommitment<E, H> for ConstraintCommitment<E, H>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    fn commit(evaluations: Vec<Vec<E>>) -> (H::Digest, Self) {
+        let commitment = Self::new(evaluations);
+        (commitment.root(), commitment)
+    }
+
+    fn open(self, positions: &[usize]) -> Queries {
+        self.query(positions)
+    }
+}
+
 // HELPER FUNCTIONS
 // ================================================================================================
 
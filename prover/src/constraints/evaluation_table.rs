@@ -5,13 +5,12 @@
 
 use super::{CompositionPoly, ProverError, StarkDomain};
 use air::ConstraintDivisor;
-use math::{batch_inversion, fft, FieldElement, StarkField};
+use math::{batch_inversion, fft, get_power_series_with_offset, FieldElement, StarkField};
 use utils::{batch_iter_mut, collections::Vec, iter_mut, uninit_vector};
 
 #[cfg(feature = "concurrent")]
 use utils::iterators::*;
 
-#[cfg(not(debug_assertions))]
 use core::marker::PhantomData;
 
 // CONSTANTS
@@ -29,7 +28,7 @@ pub struct ConstraintEvaluationTable<B: StarkField, E: FieldElement<BaseField =
     trace_length: usize,
 
     #[cfg(debug_assertions)]
-    t_evaluations: Vec<Vec<B>>,
+    t_evaluations: Vec<Vec<E>>,
     #[cfg(debug_assertions)]
     t_expected_degrees: Vec<usize>,
 }
@@ -88,7 +87,8 @@ impl<B: StarkField, E: FieldElement<BaseField = B>> ConstraintEvaluationTable<B,
 
     /// Returns number of columns in this table. The first column always contains the value of
     /// combined transition constraint evaluations; the remaining columns contain values of
-    /// assertion constraint evaluations combined based on common divisors.
+    /// assertion constraint evaluations combined based on common divisors, followed by combined
+    /// padding constraint evaluations for padded execution traces.
     #[allow(dead_code)]
     pub fn num_columns(&self) -> usize {
         self.evaluations.len()
@@ -135,6 +135,7 @@ impl<B: StarkField, E: FieldElement<BaseField = B>> ConstraintEvaluationTable<B,
                         offset: i * fragment_size,
                         evaluations,
                         t_evaluations,
+                        _base_field: PhantomData,
                     },
                 )
                 .collect()
@@ -177,7 +178,13 @@ impl<B: StarkField, E: FieldElement<BaseField = B>> ConstraintEvaluationTable<B,
             validate_column_degree(&column, divisor, domain_offset, column.len() - 1)?;
 
             // divide the column by the divisor and accumulate the result into combined_poly
-            acc_column(column, divisor, self.domain_offset, &mut combined_poly);
+            acc_column(
+                column,
+                divisor,
+                self.domain_offset,
+                self.trace_length,
+                &mut combined_poly,
+            );
         }
 
         // at this point, combined_poly contains evaluations of the combined constraint polynomial;
@@ -236,9 +243,8 @@ pub struct EvaluationTableFragment<'a, B: StarkField, E: FieldElement<BaseField
     evaluations: Vec<&'a mut [E]>,
 
     #[cfg(debug_assertions)]
-    t_evaluations: Vec<&'a mut [B]>,
+    t_evaluations: Vec<&'a mut [E]>,
 
-    #[cfg(not(debug_assertions))]
     _base_field: PhantomData<B>,
 }
 
@@ -266,10 +272,17 @@ impl<'a, B: StarkField, E: FieldElement<BaseField = B>> EvaluationTableFragment<
     }
 
     /// Updates transition evaluations row with the provided data; available only in debug mode.
+    ///
+    /// The data may be in the base field or in the extension field `E`; the latter is the case
+    /// for computations with transition constraints against the auxiliary trace segment.
     #[cfg(debug_assertions)]
-    pub fn update_transition_evaluations(&mut self, row_idx: usize, row_data: &[B]) {
+    pub fn update_transition_evaluations<F>(&mut self, row_idx: usize, row_data: &[F])
+    where
+        F: FieldElement<BaseField = B>,
+        E: From<F>,
+    {
         for (column, &value) in self.t_evaluations.iter_mut().zip(row_data) {
-            column[row_idx] = value;
+            column[row_idx] = E::from(value);
         }
     }
 }
@@ -282,27 +295,28 @@ fn acc_column<B: StarkField, E: FieldElement<BaseField = B>>(
     column: Vec<E>,
     divisor: &ConstraintDivisor<B>,
     domain_offset: B,
+    trace_length: usize,
     result: &mut [E],
 ) {
-    let numerator = divisor.numerator();
-    assert_eq!(numerator.len(), 1, "complex divisors are not yet supported");
     assert!(
-        divisor.exclude().len() <= 1,
-        "multiple exclusion points are not yet supported"
+        divisor.numerator().len() == 1 || divisor.exclude().is_empty(),
+        "multi-term divisors cannot have exclusion points"
     );
 
-    // compute inverse evaluations of the divisor's numerator, which has the form (x^a - b)
+    // compute inverse evaluations of the divisor's numerator, which has the form (x^a - b) or
+    // is a product of such terms
     let domain_size = column.len();
-    let z = get_inv_evaluation(divisor, domain_size, domain_offset);
+    let z = get_inv_evaluation(divisor, domain_size, domain_offset, trace_length);
 
     // divide column values by the divisor; for boundary constraints this computed simply as
     // multiplication of column value by the inverse of divisor numerator; for transition
     // constraints, it is computed similarly, but the result is also multiplied by the divisor's
-    // denominator (exclusion point).
+    // denominator (exclusion points).
     if divisor.exclude().is_empty() {
         // the column represents merged evaluations of boundary constraints, and divisor has the
-        // form of (x^a - b); thus to divide the column by the divisor, we compute: value * z,
-        // where z = 1 / (x^a - 1) and has already been computed above.
+        // form of (x^a - b) (or a product of such terms); thus to divide the column by the
+        // divisor, we compute: value * z, where z = 1 / (x^a - b) and has already been computed
+        // above.
         iter_mut!(result, 1024)
             .zip(column)
             .enumerate()
@@ -312,7 +326,7 @@ fn acc_column<B: StarkField, E: FieldElement<BaseField = B>>(
                 // compute value * z and add it to the result
                 *acc_value += value * z;
             });
-    } else {
+    } else if divisor.exclude().len() == 1 {
         // the column represents merged evaluations of transition constraints, and divisor has the
         // form of (x^a - 1) / (x - b); thus, to divide the column by the divisor, we compute:
         // value * (x - b) * z, where z = 1 / (x^a - 1) and has already been computed above.
@@ -337,17 +351,122 @@ fn acc_column<B: StarkField, E: FieldElement<BaseField = B>>(
                 }
             }
         );
+    } else {
+        // the column represents merged evaluations of transition constraints for a padded trace,
+        // and divisor has the form of (x^a - 1) / ((x - b_1) * ... * (x - b_k)); thus, to divide
+        // the column by the divisor, we compute: value * (x - b_1) * ... * (x - b_k) * z, where
+        // z = 1 / (x^a - 1) and has already been computed above.
+        let e = get_consecutive_product_evaluations(
+            divisor.exclude(),
+            trace_length,
+            domain_size,
+            domain_offset,
+        );
+        iter_mut!(result, 1024)
+            .zip(column)
+            .zip(e)
+            .enumerate()
+            .for_each(|(i, ((acc_value, value), e))| {
+                // determine which value of z corresponds to the current domain point
+                let z = z[i % z.len()];
+                // compute value * (x - b_1) * ... * (x - b_k) * z and add it to the result
+                *acc_value += value * E::from(z * e);
+            });
     }
 }
 
-/// Computes evaluations of the divisor's numerator over the domain of the specified size and offset.
+/// Computes evaluations of the product (x - b_1) * ... * (x - b_k) over the domain of the
+/// specified size and offset, where b_1, ..., b_k are consecutive points of the trace domain.
+///
+/// Such products are the denominators of transition constraint divisors (where the points are
+/// the last k points of the trace domain), as well as the numerators of padding constraint
+/// divisors and of divisors of assertions against consecutive steps. Denoting the points as
+/// g^a, ..., g^{a + k - 1}, where g is the generator of the trace domain, the product P(x)
+/// satisfies:
+///
+/// P(x * g) = g^k * P(x) * (x - g^{a - 1}) / (x - g^{a + k - 1})
+///
+/// Since multiplying a domain point by g shifts it by domain_size / n positions (where n is the
+/// trace length), only the first domain_size / n evaluations are computed directly, and the
+/// remaining evaluations are derived from them. This requires O(domain_size) operations
+/// regardless of the number of points. Values (x - g^{a + k - 1}) are never zero because the
+/// domain is a coset of a subgroup which does not intersect the trace domain.
+fn get_consecutive_product_evaluations<B: StarkField>(
+    points: &[B],
+    trace_length: usize,
+    domain_size: usize,
+    domain_offset: B,
+) -> Vec<B> {
+    let num_points = points.len();
+    let g = B::get_root_of_unity(trace_length.trailing_zeros());
+    debug_assert!(
+        is_consecutive(points, trace_length),
+        "points must be consecutive points of the trace domain"
+    );
+
+    // compute all points of the domain
+    let domain_generator = B::get_root_of_unity(domain_size.trailing_zeros());
+    let domain = get_power_series_with_offset(domain_generator, domain_offset, domain_size);
+
+    // compute 1 / (x - g^{a + k - 1}) for all x
+    let last_point = points[num_points - 1];
+    let inv_last = batch_inversion(&domain.iter().map(|&x| x - last_point).collect::<Vec<_>>());
+
+    let blowup = domain_size / trace_length;
+    let point_before_first = points[0] / g;
+    let gk = g.exp((num_points as u64).into());
+
+    let mut result = Vec::with_capacity(domain_size);
+    for &x in domain.iter().take(blowup) {
+        result.push(points.iter().fold(B::ONE, |e, &b| e * (x - b)));
+    }
+    for i in 0..domain_size - blowup {
+        let x = domain[i];
+        let e = gk * result[i] * (x - point_before_first) * inv_last[i];
+        result.push(e);
+    }
+
+    result
+}
+
+/// Returns true if the specified points are consecutive points of the trace domain of the
+/// specified length.
+fn is_consecutive<B: StarkField>(points: &[B], trace_length: usize) -> bool {
+    let g = B::get_root_of_unity(trace_length.trailing_zeros());
+    (1..points.len()).all(|i| points[i] == points[i - 1] * g)
+        && matches!(points.first(), Some(p) if p.exp((trace_length as u64).into()) == B::ONE)
+}
+
+/// Computes inverse evaluations of the divisor's numerator over the domain of the specified size
+/// and offset.
+///
+/// For single-term numerators, the evaluations repeat with period domain_size / a, and thus,
+/// only the first domain_size / a evaluations are returned; for multi-term numerators,
+/// evaluations over the entire domain are returned.
 #[allow(clippy::many_single_char_names)]
 fn get_inv_evaluation<B: StarkField>(
     divisor: &ConstraintDivisor<B>,
     domain_size: usize,
     domain_offset: B,
+    trace_length: usize,
 ) -> Vec<B> {
     let numerator = divisor.numerator();
+    if numerator.len() > 1 {
+        // for products of (x - b) terms over consecutive points of the trace domain, the product
+        // can be evaluated in O(domain_size) operations regardless of the number of terms
+        let points = numerator.iter().map(|&(_, b)| b).collect::<Vec<_>>();
+        if numerator.iter().all(|&(a, _)| a == 1) && is_consecutive(&points, trace_length) {
+            let evaluations = get_consecutive_product_evaluations(
+                &points,
+                trace_length,
+                domain_size,
+                domain_offset,
+            );
+            return batch_inversion(&evaluations);
+        }
+        return get_inv_product_evaluation(numerator, domain_size, domain_offset);
+    }
+
     let a = numerator[0].0 as u64; // numerator degree
     let b = numerator[0].1;
 
@@ -372,6 +491,34 @@ fn get_inv_evaluation<B: StarkField>(
     batch_inversion(&evaluations)
 }
 
+/// Computes inverse evaluations of the product of numerator terms (x^a - b) over the domain of
+/// the specified size and offset.
+fn get_inv_product_evaluation<B: StarkField>(
+    numerator: &[(usize, B)],
+    domain_size: usize,
+    domain_offset: B,
+) -> Vec<B> {
+    let g = B::get_root_of_unity(domain_size.trailing_zeros());
+
+    // compute the product of all numerator terms for all x
+    let mut evaluations = unsafe { uninit_vector(domain_size) };
+    batch_iter_mut!(
+        &mut evaluations,
+        128, // min batch size
+        |batch: &mut [B], batch_offset: usize| {
+            let mut x = domain_offset * g.exp((batch_offset as u64).into());
+            for evaluation in batch.iter_mut() {
+                *evaluation = numerator.iter().fold(B::ONE, |result, &(a, b)| {
+                    result * (x.exp((a as u64).into()) - b)
+                });
+                x *= g;
+            }
+        }
+    );
+
+    batch_inversion(&evaluations)
+}
+
 // DEBUG HELPERS
 // ================================================================================================
 
@@ -4,14 +4,15 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::{
-    evaluation_table::EvaluationTableFragment, BoundaryConstraintGroup, ConstraintEvaluationTable,
-    PeriodicValueTable, StarkDomain, TraceTable,
+    evaluation_table::EvaluationTableFragment, AffineTerms, BoundaryConstraintGroup,
+    ConstraintEvaluationTable, LinearConstraintGroup, PeriodicValueTable, StarkDomain,
+    TracePolyTable, TraceTable,
 };
 use air::{
-    Air, ConstraintCompositionCoefficients, ConstraintDivisor, EvaluationFrame,
-    TransitionConstraintGroup,
+    Air, Assertion, ConstraintCompositionCoefficients, ConstraintDivisor, EvaluationError,
+    EvaluationFrame, PaddingConstraintGroup, TransitionConstraintGroup,
 };
-use math::FieldElement;
+use math::{polynom, FieldElement};
 use utils::{
     collections::{BTreeMap, Vec},
     iter_mut,
@@ -26,15 +27,28 @@ use utils::{iterators::*, rayon};
 #[cfg(feature = "concurrent")]
 const MIN_CONCURRENT_DOMAIN_SIZE: usize = 8192;
 
+/// Size of the CPU cache (in bytes) assumed when the size of constraint evaluation chunks is
+/// detected automatically; this is a conservative estimate of per-core L2 cache size.
+#[cfg(feature = "concurrent")]
+const ASSUMED_CACHE_SIZE: usize = 256 * 1024;
+
+/// Constraint evaluation chunks are never smaller than this number of rows.
+#[cfg(feature = "concurrent")]
+const MIN_CHUNK_SIZE: usize = 16;
+
 // CONSTRAINT EVALUATOR
 // ================================================================================================
 
 pub struct ConstraintEvaluator<'a, A: Air, E: FieldElement<BaseField = A::BaseElement>> {
     air: &'a A,
     boundary_constraints: Vec<BoundaryConstraintGroup<A::BaseElement, E>>,
+    aux_boundary_constraints: Vec<BoundaryConstraintGroup<E, E>>,
     transition_constraints: Vec<TransitionConstraintGroup<E>>,
+    linear_constraints: Vec<LinearConstraintGroup<E>>,
+    padding_constraints: Option<PaddingConstraintGroup<E>>,
     periodic_values: PeriodicValueTable<A::BaseElement>,
     divisors: Vec<ConstraintDivisor<A::BaseElement>>,
+    aux_rand_elements: Vec<E>,
 
     #[cfg(debug_assertions)]
     transition_constraint_degrees: Vec<usize>,
@@ -44,8 +58,17 @@ impl<'a, A: Air, E: FieldElement<BaseField = A::BaseElement>> ConstraintEvaluato
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
     /// Returns a new evaluator which can be used to evaluate transition and boundary constraints
-    /// over extended execution trace.
-    pub fn new(air: &'a A, coefficients: ConstraintCompositionCoefficients<E>) -> Self {
+    /// over extended execution trace; `assertions` must be the assertions returned from
+    /// [Air::get_assertions()] for the `air`, while `aux_assertions` and `aux_rand_elements`
+    /// must be the assertions against the auxiliary trace segment and the random elements used
+    /// to build the auxiliary segment (if any).
+    pub fn new(
+        air: &'a A,
+        assertions: &[Assertion<A::BaseElement>],
+        aux_assertions: &[Assertion<E>],
+        coefficients: ConstraintCompositionCoefficients<E>,
+        aux_rand_elements: Vec<E>,
+    ) -> Self {
         // collect expected degrees for all transition constraints to compare them against actual
         // degrees; we do this in debug mode only because this comparison is expensive
         #[cfg(debug_assertions)]
@@ -57,7 +80,29 @@ impl<'a, A: Air, E: FieldElement<BaseField = A::BaseElement>> ConstraintEvaluato
 
         // build transition constraint groups; these will be used later to compute a random
         // linear combination of transition constraint evaluations.
-        let transition_constraints = air.get_transition_constraints(&coefficients.transition);
+        let mut transition_constraints = air.get_transition_constraints(&coefficients.transition);
+
+        // linear transition constraints are combined directly from trace columns, and thus, we
+        // remove them from the transition constraint groups; constraints against the auxiliary
+        // trace segment are never linear
+        let num_main_constraints = air.context().num_main_transition_constraints();
+        let is_linear = (0..air.num_transition_constraints())
+            .map(|i| i < num_main_constraints && air.is_transition_constraint_linear(i))
+            .collect::<Vec<_>>();
+        let linear_constraints = if is_linear.contains(&true) {
+            let terms = AffineTerms::new(air, &is_linear);
+            let linear_constraints = transition_constraints
+                .iter()
+                .filter_map(|group| LinearConstraintGroup::new(group, &terms, &is_linear))
+                .collect();
+            for group in transition_constraints.iter_mut() {
+                group.retain(|i| !is_linear[i]);
+            }
+            transition_constraints.retain(|group| !group.is_empty());
+            linear_constraints
+        } else {
+            Vec::new()
+        };
 
         // build periodic value table
         let periodic_values = PeriodicValueTable::new(air);
@@ -66,10 +111,22 @@ impl<'a, A: Air, E: FieldElement<BaseField = A::BaseElement>> ConstraintEvaluato
         let mut divisors = vec![air.transition_constraint_divisor()];
 
         // build boundary constraints and also append divisors for each group of boundary
-        // constraints to the divisor list
+        // constraints to the divisor list; coefficients for the constraints against the
+        // auxiliary trace segment follow the coefficients for all other boundary constraints
+        let (main_coefficients, aux_coefficients) = coefficients
+            .boundary
+            .split_at(coefficients.boundary.len() - aux_assertions.len());
         let mut twiddle_map = BTreeMap::new();
         let boundary_constraints = air
-            .get_boundary_constraints(&coefficients.boundary)
+            .get_boundary_constraints(assertions, main_coefficients)
+            .into_iter()
+            .map(|group| {
+                divisors.push(group.divisor().clone());
+                BoundaryConstraintGroup::new(group, air, &mut twiddle_map)
+            })
+            .collect();
+        let aux_boundary_constraints = air
+            .get_aux_boundary_constraints(aux_assertions, aux_coefficients)
             .into_iter()
             .map(|group| {
                 divisors.push(group.divisor().clone());
@@ -77,12 +134,23 @@ impl<'a, A: Air, E: FieldElement<BaseField = A::BaseElement>> ConstraintEvaluato
             })
             .collect();
 
+        // for padded execution traces, padding constraints are evaluated into the last column of
+        // the evaluation table
+        let padding_constraints = air.get_padding_constraints(&coefficients.padding);
+        if padding_constraints.is_some() {
+            divisors.push(air.padding_constraint_divisor());
+        }
+
         ConstraintEvaluator {
             air,
             boundary_constraints,
+            aux_boundary_constraints,
             transition_constraints,
+            linear_constraints,
+            padding_constraints,
             periodic_values,
             divisors,
+            aux_rand_elements,
             #[cfg(debug_assertions)]
             transition_constraint_degrees,
         }
@@ -90,14 +158,16 @@ impl<'a, A: Air, E: FieldElement<BaseField = A::BaseElement>> ConstraintEvaluato
 
     // EVALUATOR
     // --------------------------------------------------------------------------------------------
-    /// Evaluates constraints against the provided extended execution trace. Constraints are
+    /// Evaluates constraints against the provided extended execution trace and, for computations
+    /// with an auxiliary trace segment, the extended auxiliary segment. Constraints are
     /// evaluated over a constraint evaluation domain. This is an optimization because constraint
     /// evaluation domain can be many times smaller than the full LDE domain.
     pub fn evaluate(
         &self,
         trace: &TraceTable<A::BaseElement>,
+        aux_trace: Option<&TraceTable<E>>,
         domain: &StarkDomain<A::BaseElement>,
-    ) -> ConstraintEvaluationTable<A::BaseElement, E> {
+    ) -> Result<ConstraintEvaluationTable<A::BaseElement, E>, EvaluationError> {
         assert_eq!(
             trace.len(),
             domain.lde_domain_size(),
@@ -117,44 +187,78 @@ impl<'a, A: Air, E: FieldElement<BaseField = A::BaseElement>> ConstraintEvaluato
         );
 
         // when `concurrent` feature is enabled, break the evaluation table into multiple fragments
-        // to evaluate them into multiple threads; unless the constraint evaluation domain is small,
-        // then don't bother with concurrent evaluation
+        // (chunks of rows) to evaluate them into multiple threads; each fragment writes its
+        // results directly into the evaluation table
 
         #[cfg(not(feature = "concurrent"))]
         let num_fragments = 1;
 
         #[cfg(feature = "concurrent")]
-        let num_fragments = if domain.ce_domain_size() >= MIN_CONCURRENT_DOMAIN_SIZE {
-            rayon::current_num_threads().next_power_of_two()
-        } else {
-            1
-        };
+        let num_fragments = domain.ce_domain_size() / self.get_chunk_size(domain);
 
         let mut fragments = evaluation_table.fragments(num_fragments);
-        iter_mut!(fragments).for_each(|fragment| self.evaluate_fragment(trace, domain, fragment));
+        iter_mut!(fragments)
+            .try_for_each(|fragment| self.evaluate_fragment(trace, aux_trace, domain, fragment))?;
 
         // when in debug mode, make sure expected transition constraint degrees align with
         // actual degrees we got during constraint evaluation
         #[cfg(debug_assertions)]
         evaluation_table.validate_transition_degrees();
 
-        evaluation_table
+        Ok(evaluation_table)
     }
 
     // EVALUATION HELPERS
     // --------------------------------------------------------------------------------------------
 
+    /// Returns the number of rows of the constraint evaluation domain evaluated in a single
+    /// fragment.
+    ///
+    /// Unless the chunk size is specified via the concurrency config of the proof options, the
+    /// size is chosen such that trace rows and constraint evaluations of a fragment fit into
+    /// [ASSUMED_CACHE_SIZE], while each thread still gets at least one fragment. When the
+    /// constraint evaluation domain is small, we don't bother with concurrent evaluation.
+    #[cfg(feature = "concurrent")]
+    fn get_chunk_size(&self, domain: &StarkDomain<A::BaseElement>) -> usize {
+        let ce_domain_size = domain.ce_domain_size();
+        let config = self.air.options().concurrency_config();
+        if let Some(chunk_size) = config.constraint_eval_chunk_size() {
+            return chunk_size.min(ce_domain_size);
+        }
+        if ce_domain_size < MIN_CONCURRENT_DOMAIN_SIZE {
+            return ce_domain_size;
+        }
+
+        // constraint evaluation at each row reads all trace rows of the evaluation frame, and
+        // writes a value into each column of the evaluation table; values of the auxiliary trace
+        // segment are elements of the extension field
+        let frame_row_size = self.air.trace_width() * A::BaseElement::ELEMENT_BYTES
+            + self.air.auxiliary_trace_width() * E::ELEMENT_BYTES;
+        let row_size =
+            self.air.frame_size() * frame_row_size + self.divisors.len() * E::ELEMENT_BYTES;
+        // the largest power of two such that chunk data fits into cache
+        let cache_chunk_size = (ASSUMED_CACHE_SIZE / row_size + 1).next_power_of_two() / 2;
+        let max_chunk_size = ce_domain_size / rayon::current_num_threads().next_power_of_two();
+        cache_chunk_size.clamp(MIN_CHUNK_SIZE, max_chunk_size.max(MIN_CHUNK_SIZE))
+    }
+
     /// Evaluates constraints for a single fragment of the evaluation table.
     fn evaluate_fragment(
         &self,
         trace: &TraceTable<A::BaseElement>,
+        aux_trace: Option<&TraceTable<E>>,
         domain: &StarkDomain<A::BaseElement>,
         fragment: &mut EvaluationTableFragment<A::BaseElement, E>,
-    ) {
-        // initialize buffers to hold trace values and evaluation results at each step;
-        let mut ev_frame = EvaluationFrame::new(trace.width());
+    ) -> Result<(), EvaluationError> {
+        // initialize buffers to hold trace values and evaluation results at each step; for
+        // computations with an auxiliary trace segment, additional buffers are needed to
+        // evaluate transition constraints over the extension field
+        let mut ev_frame = EvaluationFrame::with_size(trace.width(), self.air.frame_size());
+        let mut aux_buffers =
+            aux_trace.map(|aux_trace| AuxEvaluationBuffers::new(self.air, aux_trace));
         let mut evaluations = vec![E::ZERO; fragment.num_columns()];
         let mut t_evaluations = vec![A::BaseElement::ZERO; self.air.num_transition_constraints()];
+        let num_boundary_groups = self.boundary_constraints.len();
 
         // pre-compute values needed to determine x coordinates in the constraint evaluation domain
         let g = domain.ce_domain_generator();
@@ -164,27 +268,70 @@ impl<'a, A: Air, E: FieldElement<BaseField = A::BaseElement>> ConstraintEvaluato
         // LDE domain
         let lde_shift = domain.ce_to_lde_blowup().trailing_zeros();
 
+        // evaluate the combination of linear transition constraints for all rows of the fragment
+        let linear_evaluations = self.evaluate_linear_constraints(trace, fragment, x, g, lde_shift);
+
         for i in 0..fragment.num_rows() {
             let step = i + fragment.offset();
 
             // update evaluation frame buffer with data from the execution trace; this will
-            // read current and next rows from the trace into the buffer; data in the trace
+            // read all rows of the frame from the trace into the buffer; data in the trace
             // table is extended over the LDE domain, so, we need to convert step in constraint
             // evaluation domain, into a step in LDE domain, in case these domains are different
             trace.read_frame_into(step << lde_shift, &mut ev_frame);
+            if let (Some(aux_trace), Some(aux_buffers)) = (aux_trace, aux_buffers.as_mut()) {
+                aux_trace.read_frame_into(step << lde_shift, &mut aux_buffers.aux_frame);
+            }
 
             // evaluate transition constraints and save the merged result the first slot of the
             // evaluations buffer
-            evaluations[0] =
-                self.evaluate_transition_constraints(&ev_frame, x, step, &mut t_evaluations);
+            evaluations[0] = self.evaluate_transition_constraints(
+                &ev_frame,
+                aux_buffers.as_mut(),
+                x,
+                step,
+                &mut t_evaluations,
+            )?;
+            if let Some(linear_evaluations) = &linear_evaluations {
+                evaluations[0] += linear_evaluations[i];
+            }
 
             // when in debug mode, save transition constraint evaluations
             #[cfg(debug_assertions)]
-            fragment.update_transition_evaluations(step, &t_evaluations);
+            match aux_buffers.as_ref() {
+                Some(aux_buffers) => {
+                    fragment.update_transition_evaluations(i, &aux_buffers.evaluations)
+                }
+                None => fragment.update_transition_evaluations(i, &t_evaluations),
+            }
 
-            // evaluate boundary constraints; the results go into remaining slots of the
+            // evaluate boundary constraints; the results go into the slots of the evaluations
+            // buffer following the transition constraint slot, and results for the constraints
+            // against the auxiliary trace segment follow results for the main segment
+            let (main_results, aux_results) = evaluations[1..].split_at_mut(num_boundary_groups);
+            evaluate_boundary_constraints(
+                &self.boundary_constraints,
+                ev_frame.current(),
+                x,
+                step,
+                main_results,
+            );
+            if let Some(aux_buffers) = aux_buffers.as_ref() {
+                evaluate_boundary_constraints(
+                    &self.aux_boundary_constraints,
+                    aux_buffers.aux_frame.current(),
+                    x,
+                    step,
+                    aux_results,
+                );
+            }
+
+            // evaluate padding constraints; the result goes into the last slot of the
             // evaluations buffer
-            self.evaluate_boundary_constraints(ev_frame.current(), x, step, &mut evaluations[1..]);
+            if let Some(padding_constraints) = &self.padding_constraints {
+                let num_columns = evaluations.len();
+                evaluations[num_columns - 1] = padding_constraints.merge_evaluations(&ev_frame, x);
+            }
 
             // record the result in the evaluation table
             fragment.update_row(i, &evaluations);
@@ -192,62 +339,464 @@ impl<'a, A: Air, E: FieldElement<BaseField = A::BaseElement>> ConstraintEvaluato
             // update x to the next value
             x *= g;
         }
+        Ok(())
     }
 
     /// Evaluates transition constraints at the specified step of the execution trace. `step` is
     /// the step in the constraint evaluation, and `x` is the corresponding domain value. That
     /// is, x = s * g^step, where g is the generator of the constraint evaluation domain, and s
-    /// is the domain offset.
+    /// is the domain offset. `aux_buffers` is set only for computations with an auxiliary trace
+    /// segment; in this case, evaluations of all transition constraints are saved into these
+    /// buffers rather than into `evaluations`.
     fn evaluate_transition_constraints(
         &self,
         frame: &EvaluationFrame<A::BaseElement>,
+        aux_buffers: Option<&mut AuxEvaluationBuffers<E>>,
         x: A::BaseElement,
         step: usize,
         evaluations: &mut [A::BaseElement],
-    ) -> E {
+    ) -> Result<E, EvaluationError> {
+        // when all transition constraints are linear, there is nothing to evaluate here; in
+        // debug mode, we still evaluate the constraints so that their degrees can be validated
+        #[cfg(not(debug_assertions))]
+        if self.transition_constraints.is_empty() {
+            return Ok(E::ZERO);
+        }
+
         // TODO: use a more efficient way to zero out memory
         evaluations.fill(A::BaseElement::ZERO);
 
         // get periodic values at the evaluation step
         let periodic_values = self.periodic_values.get_row(step);
 
-        // evaluate transition constraints and save the results into evaluations buffer
-        self.air
-            .evaluate_transition(frame, periodic_values, evaluations);
+        // evaluate main transition constraints and save the results into evaluations buffer
+        let num_main_constraints = self.air.context().num_main_transition_constraints();
+        self.air.evaluate_transition(
+            frame,
+            periodic_values,
+            &mut evaluations[..num_main_constraints],
+        );
+
+        // auxiliary transition constraints are evaluated over the extension field; thus, the
+        // main frame and periodic values are lifted into the extension field, and evaluations
+        // of auxiliary constraints follow (lifted) evaluations of main constraints
+        if let Some(aux_buffers) = aux_buffers {
+            aux_buffers.lift_main_values(frame, periodic_values);
+            let (main_evaluations, aux_evaluations) =
+                aux_buffers.evaluations.split_at_mut(num_main_constraints);
+            for (target, &value) in main_evaluations.iter_mut().zip(evaluations.iter()) {
+                *target = E::from(value);
+            }
+            aux_evaluations.fill(E::ZERO);
+            if !aux_evaluations.is_empty() {
+                self.air.evaluate_aux_transition(
+                    &aux_buffers.main_frame,
+                    &aux_buffers.aux_frame,
+                    &aux_buffers.periodic_values,
+                    &self.aux_rand_elements,
+                    aux_evaluations,
+                )?;
+            }
+
+            let x = E::from(x);
+            return Ok(self
+                .transition_constraints
+                .iter()
+                .fold(E::ZERO, |result, group| {
+                    result + group.merge_evaluations(&aux_buffers.evaluations, x)
+                }));
+        }
 
         // merge transition constraint evaluations into a single value and return it;
         // we can do this here because all transition constraints have the same divisor.
-        self.transition_constraints
+        Ok(self
+            .transition_constraints
             .iter()
             .fold(E::ZERO, |result, group| {
                 result + group.merge_evaluations(evaluations, x)
-            })
+            }))
     }
 
-    /// Evaluates all boundary constraint groups at a specific step of the execution trace.
-    /// `step` is the step in the constraint evaluation domain, and `x` is the corresponding
-    /// domain value. That is, x = s * g^step, where g is the generator of the constraint
-    /// evaluation domain, and s is the domain offset.
-    fn evaluate_boundary_constraints(
+    /// Evaluates the random linear combination of linear transition constraints at all steps of
+    /// the specified `fragment`; `x` is the domain value at the first step of the fragment, and
+    /// `g` is the generator of the constraint evaluation domain. Returns None if the computation
+    /// does not have any linear transition constraints.
+    fn evaluate_linear_constraints(
         &self,
-        state: &[A::BaseElement],
+        trace: &TraceTable<A::BaseElement>,
+        fragment: &EvaluationTableFragment<A::BaseElement, E>,
         x: A::BaseElement,
-        step: usize,
-        result: &mut [E],
+        g: A::BaseElement,
+        lde_shift: u32,
+    ) -> Option<Vec<E>> {
+        if self.linear_constraints.is_empty() {
+            return None;
+        }
+
+        let mut x_values = Vec::with_capacity(fragment.num_rows());
+        let mut x = x;
+        for _ in 0..fragment.num_rows() {
+            x_values.push(x);
+            x *= g;
+        }
+
+        let mut result = vec![E::ZERO; fragment.num_rows()];
+        for group in self.linear_constraints.iter() {
+            group.evaluate(trace, fragment.offset(), lde_shift, &x_values, &mut result);
+        }
+        Some(result)
+    }
+}
+
+// AUXILIARY EVALUATION BUFFERS
+// ================================================================================================
+
+/// Buffers used for evaluating transition constraints against the auxiliary trace segment.
+///
+/// Auxiliary transition constraints are evaluated over the extension field, and thus, values of
+/// the main trace segment and of periodic columns are lifted into the extension field before
+/// the constraints are evaluated.
+struct AuxEvaluationBuffers<E: FieldElement> {
+    main_frame: EvaluationFrame<E>,
+    aux_frame: EvaluationFrame<E>,
+    periodic_values: Vec<E>,
+    evaluations: Vec<E>,
+}
+
+impl<E: FieldElement> AuxEvaluationBuffers<E> {
+    /// Allocates buffers for evaluating constraints of the `air` against the `aux_trace`.
+    fn new<A: Air<BaseElement = E::BaseField>>(air: &A, aux_trace: &TraceTable<E>) -> Self {
+        AuxEvaluationBuffers {
+            main_frame: EvaluationFrame::with_size(air.trace_width(), air.frame_size()),
+            aux_frame: EvaluationFrame::with_size(aux_trace.width(), air.frame_size()),
+            periodic_values: Vec::with_capacity(air.get_periodic_column_values().len()),
+            evaluations: vec![E::ZERO; air.num_transition_constraints()],
+        }
+    }
+
+    /// Copies the specified main segment `frame` and `periodic_values` into the buffers.
+    fn lift_main_values(
+        &mut self,
+        frame: &EvaluationFrame<E::BaseField>,
+        periodic_values: &[E::BaseField],
     ) {
-        // compute the adjustment degree outside of the group so that we can re-use
-        // it for groups which have the same adjustment degree
-        let mut degree_adjustment = self.boundary_constraints[0].degree_adjustment;
-        let mut xp = E::from(x.exp(degree_adjustment.into()));
-
-        for (group, result) in self.boundary_constraints.iter().zip(result.iter_mut()) {
-            // recompute adjustment degree only when it has changed
-            if group.degree_adjustment != degree_adjustment {
-                degree_adjustment = group.degree_adjustment;
-                xp = E::from(x.exp(degree_adjustment.into()));
+        for i in 0..frame.size() {
+            for (target, &value) in self.main_frame.row_mut(i).iter_mut().zip(frame.row(i)) {
+                *target = E::from(value);
             }
-            // evaluate the group and save the result
-            *result = group.evaluate(state, step, x, xp);
         }
+        self.periodic_values.clear();
+        self.periodic_values
+            .extend(periodic_values.iter().map(|&value| E::from(value)));
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Evaluates all boundary constraint `groups` at a specific step of the execution trace.
+/// `step` is the step in the constraint evaluation domain, and `x` is the corresponding
+/// domain value. That is, x = s * g^step, where g is the generator of the constraint
+/// evaluation domain, and s is the domain offset.
+fn evaluate_boundary_constraints<F, E>(
+    groups: &[BoundaryConstraintGroup<F, E>],
+    state: &[F],
+    x: F::BaseField,
+    step: usize,
+    result: &mut [E],
+) where
+    F: FieldElement,
+    E: FieldElement<BaseField = F::BaseField> + From<F>,
+{
+    let first_group = match groups.first() {
+        Some(group) => group,
+        None => return,
+    };
+
+    // compute the adjustment degree outside of the group so that we can re-use
+    // it for groups which have the same adjustment degree
+    let mut degree_adjustment = first_group.degree_adjustment;
+    let mut xp = E::from(x.exp(degree_adjustment.into()));
+
+    for (group, result) in groups.iter().zip(result.iter_mut()) {
+        // recompute adjustment degree only when it has changed
+        if group.degree_adjustment != degree_adjustment {
+            degree_adjustment = group.degree_adjustment;
+            xp = E::from(x.exp(degree_adjustment.into()));
+        }
+        // evaluate the group and save the result
+        *result = group.evaluate(state, step, x, xp);
+    }
+}
+
+// POINT EVALUATION
+// ================================================================================================
+
+/// Returns evaluations of all main transition constraints of the `air` at the specified point
+/// `z`; constraints against the auxiliary trace segment (if any) are not evaluated.
+///
+/// The evaluation frame is obtained by evaluating the trace polynomials at `z`, `z * g`, ...,
+/// `z * g^(frame_size - 1)` (where `g` is the generator of the trace domain) via Horner's method, and values of periodic
+/// columns are obtained by evaluating periodic column polynomials at `z`. Thus, this does not
+/// require the trace to be extended over the LDE domain, and is cheap for traces of small width.
+///
+/// For a valid execution trace, all evaluations are zeros when `z` is a point of the trace
+/// domain at which transition constraints are enforced. For an out-of-domain `z`, the
+/// evaluations are the numerators of the transition constraints checked by the verifier; the
+/// results can thus be used to check the out-of-domain evaluations of the constraint composition
+/// polynomial.
+pub fn eval_constraint_poly_at<A: Air, E: FieldElement<BaseField = A::BaseElement>>(
+    air: &A,
+    trace_polys: &TracePolyTable<A::BaseElement>,
+    z: E,
+) -> Vec<E> {
+    let frame = trace_polys.get_ood_frame(z, air.frame_size());
+
+    // compute values of periodic columns at z
+    let periodic_values = air
+        .get_periodic_column_polys()
+        .iter()
+        .map(|poly| {
+            let num_cycles = air.trace_length() / poly.len();
+            let x = z.exp((num_cycles as u32).into());
+            polynom::eval(poly, x)
+        })
+        .collect::<Vec<_>>();
+
+    let mut result = E::zeroed_vector(air.context().num_main_transition_constraints());
+    air.evaluate_transition(&frame, &periodic_values, &mut result);
+    result
+}
+
+// DEBUG HELPERS
+// ================================================================================================
+
+/// Makes sure that the base degrees of transition constraints declared in the context of the
+/// `air` are equal to the actual degrees of the constraints.
+///
+/// Restricted to a random line u + t * v in the space of evaluation frames, a constraint of
+/// degree d becomes a univariate polynomial in t which, with high probability, has degree d as
+/// well. Thus, we evaluate the constraints at `max_degree + 2` points of such a line, and then
+/// interpolate the evaluations to find the actual degrees. Values of periodic columns and
+/// auxiliary random elements are fixed to random values, and thus, do not contribute to the
+/// measured degrees. Unlike the degree check performed after constraint evaluation, this does
+/// not depend on the values in the execution trace.
+///
+/// # Errors
+/// Returns an error if auxiliary transition constraints could not be evaluated.
+#[cfg(debug_assertions)]
+pub fn validate_constraint_degrees<A: Air>(air: &A) -> Result<(), EvaluationError> {
+    use crypto::{hashers::Blake3_256, RandomCoin};
+
+    let mut coin = RandomCoin::<A::BaseElement, Blake3_256<A::BaseElement>>::new(
+        b"transition constraint degrees",
+    );
+    let mut draw_vector = |n: usize| {
+        (0..n)
+            .map(|_| {
+                coin.draw::<A::BaseElement>()
+                    .expect("failed to draw random value")
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let declared_degrees = air
+        .transition_constraint_degrees()
+        .iter()
+        .map(|degree| degree.base())
+        .collect::<Vec<_>>();
+    let max_degree = declared_degrees.iter().copied().max().unwrap_or(0);
+    let num_main_constraints = air.context().num_main_transition_constraints();
+    let has_aux_constraints = declared_degrees.len() > num_main_constraints;
+
+    // pick a random line for both trace segments, and random values for periodic columns and
+    // auxiliary random elements
+    let main_width = air.trace_width() * air.frame_size();
+    let aux_width = air.auxiliary_trace_width() * air.frame_size();
+    let origin = draw_vector(main_width + aux_width);
+    let direction = draw_vector(main_width + aux_width);
+    let periodic_values = draw_vector(air.get_periodic_column_values().len());
+    let aux_rand_elements = draw_vector(air.num_aux_rand_elements());
+
+    // evaluate all constraints at max_degree + 2 points of the line; polynomial interpolation
+    // requires all points to be non-zero
+    let xs = (1..max_degree as u64 + 3)
+        .map(A::BaseElement::from)
+        .collect::<Vec<_>>();
+    let mut main_frame = EvaluationFrame::with_size(air.trace_width(), air.frame_size());
+    let mut aux_frame = EvaluationFrame::with_size(
+        core::cmp::max(air.auxiliary_trace_width(), 1),
+        air.frame_size(),
+    );
+    let mut evaluations = vec![Vec::with_capacity(xs.len()); declared_degrees.len()];
+    let mut result = vec![A::BaseElement::ZERO; declared_degrees.len()];
+    for &t in xs.iter() {
+        let mut point = origin
+            .iter()
+            .zip(direction.iter())
+            .map(|(&u, &v)| u + t * v);
+        for i in 0..air.frame_size() {
+            for value in main_frame.row_mut(i).iter_mut() {
+                *value = point.next().unwrap();
+            }
+        }
+        for i in 0..air.frame_size() {
+            for value in aux_frame
+                .row_mut(i)
+                .iter_mut()
+                .take(air.auxiliary_trace_width())
+            {
+                *value = point.next().unwrap();
+            }
+        }
+
+        result.fill(A::BaseElement::ZERO);
+        let (main_result, aux_result) = result.split_at_mut(num_main_constraints);
+        air.evaluate_transition(&main_frame, &periodic_values, main_result);
+        if has_aux_constraints {
+            air.evaluate_aux_transition(
+                &main_frame,
+                &aux_frame,
+                &periodic_values,
+                &aux_rand_elements,
+                aux_result,
+            )?;
+        }
+        for (evaluations, &value) in evaluations.iter_mut().zip(result.iter()) {
+            evaluations.push(value);
+        }
+    }
+
+    let actual_degrees = evaluations
+        .iter()
+        .map(|ys| polynom::degree_of(&polynom::interpolate(&xs, ys, false)))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        declared_degrees, actual_degrees,
+        "declared transition constraint degrees didn't match actual degrees\nexpected: {:>3?}\nactual:   {:>3?}",
+        declared_degrees, actual_degrees
+    );
+    Ok(())
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        tests::{build_fib_trace, build_proof_options, FibAir, MockAir, TestFibAir, TestFibInputs},
+        TracePolyTable,
+    };
+    use air::{
+        proof::StarkProof, Air, ConcurrencyConfig, FieldExtension, HashFunction, ProofOptions,
+        VerificationKey,
+    };
+    use math::{
+        fields::{f128::BaseElement, QuadExtension},
+        FieldElement,
+    };
+
+    #[test]
+    fn constraint_eval_chunk_size() {
+        let trace = build_fib_trace(1024);
+        let result = trace.get(1, trace.length() - 1);
+        let expected =
+            crate::prove::<FibAir<BaseElement>>(trace, result, build_proof_options()).unwrap();
+
+        // the way constraint evaluation is broken into chunks must not affect the proof
+        for &chunk_size in [16, 256, 1 << 20].iter() {
+            let options = build_chunked_options(chunk_size);
+            let trace = build_fib_trace(1024);
+            let proof = crate::prove::<FibAir<BaseElement>>(trace, result, options).unwrap();
+            assert_eq!(expected.to_bytes(), proof.to_bytes());
+            verifier::verify::<FibAir<BaseElement>>(proof, result).unwrap();
+        }
+    }
+
+    #[test]
+    fn constraint_eval_chunk_size_with_key() {
+        // the chunk size is not serialized, but a proof read from bytes should still match the
+        // key of the AIR with which it was generated
+        let options = build_chunked_options(64);
+        let trace = build_fib_trace(128);
+        let result = trace.get(1, trace.length() - 1);
+        let key = VerificationKey::new(&FibAir::new(trace.get_info(), result, options.clone()));
+        let proof = crate::prove::<FibAir<BaseElement>>(trace, result, options.clone()).unwrap();
+
+        let proof = StarkProof::from_bytes(&proof.to_bytes()).unwrap();
+        assert_eq!(&options, proof.options());
+        verifier::verify_with_key::<FibAir<BaseElement>>(proof, result, &key).unwrap();
+    }
+
+    #[test]
+    fn linear_constraint_evaluation() {
+        // combining linear constraints directly from trace columns must not change the proof
+        for &extension in [FieldExtension::None, FieldExtension::Quadratic].iter() {
+            let options = ProofOptions::new(36, 8, 0, HashFunction::Blake3_256, extension, 4, 256);
+            let trace = build_fib_trace(64);
+            let result = trace.get(1, trace.length() - 1);
+            let opaque =
+                crate::prove::<FibAir<BaseElement>>(trace.clone(), result, options.clone())
+                    .unwrap();
+            let inputs = TestFibInputs::new(result).with_linear_constraints();
+            let linear = crate::prove::<TestFibAir>(trace, inputs, options).unwrap();
+            assert_eq!(opaque.to_bytes(), linear.to_bytes());
+            verifier::verify::<FibAir<BaseElement>>(linear, result).unwrap();
+        }
+    }
+
+    fn build_chunked_options(chunk_size: usize) -> ProofOptions {
+        let config = ConcurrencyConfig::new().with_constraint_eval_chunk_size(chunk_size);
+        build_proof_options().with_concurrency_config(config)
+    }
+
+    #[test]
+    fn eval_constraint_poly_at() {
+        let trace = build_fib_trace(16);
+        let result = trace.get(1, trace.length() - 1);
+        let air = FibAir::new(trace.get_info(), result, build_proof_options());
+        let trace_polys = TracePolyTable::from(&trace);
+        let g = air.trace_domain_generator();
+
+        // constraints are satisfied at all steps of the trace domain except for the last one
+        for step in 0..air.trace_length() {
+            let evaluations =
+                super::eval_constraint_poly_at(&air, &trace_polys, g.exp(step as u128));
+            let is_satisfied = evaluations.iter().all(|&e| e == BaseElement::ZERO);
+            assert_eq!(step < air.trace_length() - 1, is_satisfied);
+        }
+
+        // at an out-of-domain point, evaluations are the same as the evaluations of transition
+        // constraints over the out-of-domain frame
+        let z = QuadExtension::<BaseElement>::new(BaseElement::new(3), BaseElement::new(5));
+        let frame = trace_polys.get_ood_frame(z, air.frame_size());
+        let mut expected = vec![QuadExtension::ZERO; air.num_transition_constraints()];
+        air.evaluate_transition(&frame, &[], &mut expected);
+        assert_eq!(
+            expected,
+            super::eval_constraint_poly_at(&air, &trace_polys, z)
+        );
+
+        // after the trace is tampered with, constraints are not satisfied on the transitions into
+        // and out of the tampered step
+        let mut trace = trace;
+        trace.set(0, 4, BaseElement::new(42));
+        let trace_polys = TracePolyTable::from(&trace);
+        for step in 0..air.trace_length() - 1 {
+            let evaluations =
+                super::eval_constraint_poly_at(&air, &trace_polys, g.exp(step as u128));
+            let is_satisfied = evaluations.iter().all(|&e| e == BaseElement::ZERO);
+            assert_eq!(step != 3 && step != 4, is_satisfied);
+        }
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "declared transition constraint degrees didn't match actual degrees")]
+    fn validate_constraint_degrees_mismatch() {
+        // the mock AIR declares a single constraint of degree 2, but its evaluations are zeros
+        let air = MockAir::with_trace_length(16);
+        super::validate_constraint_degrees(&air).unwrap();
     }
 }
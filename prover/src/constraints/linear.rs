@@ -0,0 +1,234 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::TraceTable;
+use air::{Air, EvaluationFrame, TransitionConstraintGroup};
+use math::{FieldElement, StarkField};
+use utils::collections::Vec;
+
+// LINEAR CONSTRAINT GROUP
+// ================================================================================================
+
+/// A random linear combination of linear transition constraints which belong to the same
+/// transition constraint group.
+///
+/// Each linear constraint $C_i$ is an affine function of the rows of the evaluation frame. Thus, the linear combinations $\sum_i{\alpha_i \cdot C_i}$ and
+/// $\sum_i{\beta_i \cdot C_i}$ are affine functions of these rows as well, and can be described
+/// by a single coefficient per trace register and row, plus a constant term. This allows us to
+/// evaluate the combination over the constraint evaluation domain by accumulating scaled trace
+/// columns (similar to BLAS axpy operation), instead of evaluating and merging each constraint
+/// at every step.
+pub struct LinearConstraintGroup<E: FieldElement> {
+    alpha_terms: Vec<E>,
+    alpha_constant: E,
+    beta_terms: Vec<E>,
+    beta_constant: E,
+    degree_adjustment: u32,
+    exemption_points: Vec<E>,
+}
+
+impl<E: FieldElement> LinearConstraintGroup<E> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a linear combination of constraints described by `terms` for all constraints in
+    /// the specified transition constraint `group`; `is_linear` specifies which constraints are
+    /// linear. Returns None if the group does not contain any linear constraints.
+    pub fn new(
+        group: &TransitionConstraintGroup<E>,
+        terms: &AffineTerms<E::BaseField>,
+        is_linear: &[bool],
+    ) -> Option<Self> {
+        let num_terms = terms.num_terms();
+        let mut alpha_terms = vec![E::ZERO; num_terms];
+        let mut beta_terms = vec![E::ZERO; num_terms];
+        let mut alpha_constant = E::ZERO;
+        let mut beta_constant = E::ZERO;
+        let mut num_constraints = 0;
+
+        for (&index, &(alpha, beta)) in group.indexes().iter().zip(group.coefficients()) {
+            if !is_linear[index] {
+                continue;
+            }
+            num_constraints += 1;
+
+            alpha_constant += alpha * E::from(terms.constants[index]);
+            beta_constant += beta * E::from(terms.constants[index]);
+            for (j, coefficients) in terms.coefficients.iter().enumerate() {
+                alpha_terms[j] += alpha * E::from(coefficients[index]);
+                beta_terms[j] += beta * E::from(coefficients[index]);
+            }
+        }
+
+        if num_constraints == 0 {
+            return None;
+        }
+
+        Some(LinearConstraintGroup {
+            alpha_terms,
+            alpha_constant,
+            beta_terms,
+            beta_constant,
+            degree_adjustment: group.degree_adjustment(),
+            exemption_points: group.exemption_points().to_vec(),
+        })
+    }
+
+    // EVALUATOR
+    // --------------------------------------------------------------------------------------------
+    /// Evaluates the linear combination at `x_values.len()` consecutive steps of the constraint
+    /// evaluation domain starting at step `offset`, and adds the results to the `result` slice.
+    ///
+    /// The combination is computed in the same way as
+    /// [TransitionConstraintGroup::merge_evaluations()]; `lde_shift` is log2 of the ratio between
+    /// the sizes of the LDE domain and the constraint evaluation domain.
+    pub fn evaluate(
+        &self,
+        trace: &TraceTable<E::BaseField>,
+        offset: usize,
+        lde_shift: u32,
+        x_values: &[E::BaseField],
+        result: &mut [E],
+    ) {
+        let num_rows = x_values.len();
+        let width = trace.width();
+        let mut alpha_evaluations = vec![self.alpha_constant; num_rows];
+        let mut beta_evaluations = vec![self.beta_constant; num_rows];
+
+        // accumulate scaled trace columns; the first `width` terms apply to the current row, the
+        // next `width` terms apply to the next row of the trace etc.
+        for (j, (&alpha, &beta)) in self.alpha_terms.iter().zip(&self.beta_terms).enumerate() {
+            if alpha == E::ZERO && beta == E::ZERO {
+                continue;
+            }
+            let (register, row_offset) = (j % width, (j / width) * trace.blowup());
+            let column = trace.get_register(register);
+            for (i, (a, b)) in alpha_evaluations
+                .iter_mut()
+                .zip(beta_evaluations.iter_mut())
+                .enumerate()
+            {
+                let lde_step = (((offset + i) << lde_shift) + row_offset) % column.len();
+                *a += alpha * E::from(column[lde_step]);
+                *b += beta * E::from(column[lde_step]);
+            }
+        }
+
+        // apply degree adjustment and cancel out divisor factors at the exempted steps
+        for ((result, &x), (&a, &b)) in result
+            .iter_mut()
+            .zip(x_values)
+            .zip(alpha_evaluations.iter().zip(beta_evaluations.iter()))
+        {
+            let xp = x.exp(self.degree_adjustment.into());
+            let mut evaluation = a + b * E::from(xp);
+            let x = E::from(x);
+            for &point in self.exemption_points.iter() {
+                evaluation *= x - point;
+            }
+            *result += evaluation;
+        }
+    }
+}
+
+// AFFINE TERMS
+// ================================================================================================
+
+/// Coefficients of the affine functions describing linear transition constraints of an AIR.
+///
+/// For a linear constraint $C_i$, `constants[i]` is the value of the constraint at the all-zero
+/// evaluation frame, and `coefficients[j][i]` is the coefficient of the $j$th value of the frame,
+/// where values of the current row come first, followed by values of the next row etc.
+pub struct AffineTerms<B: StarkField> {
+    constants: Vec<B>,
+    coefficients: Vec<Vec<B>>,
+}
+
+impl<B: StarkField> AffineTerms<B> {
+    /// Extracts affine terms of linear transition constraints of the specified `air` by
+    /// evaluating transition constraints at the all-zero frame and at each of the unit frames.
+    ///
+    /// # Panics
+    /// Panics if any of the constraints for which `is_linear` is set has degree other than 1
+    /// or, in debug mode, if any such constraint is not an affine function of the frame.
+    pub fn new<A: Air<BaseElement = B>>(air: &A, is_linear: &[bool]) -> Self {
+        for (i, degree) in air.transition_constraint_degrees().iter().enumerate() {
+            assert!(
+                !is_linear[i]
+                    || degree.get_evaluation_degree(air.trace_length()) + 1 == air.trace_length(),
+                "linear transition constraint {} must have degree 1",
+                i
+            );
+        }
+
+        let frame_len = air.frame_size() * air.trace_width();
+        let periodic_values = vec![B::ZERO; air.get_periodic_column_values().len()];
+
+        let constants = evaluate_at(air, &vec![B::ZERO; frame_len], &periodic_values);
+        let coefficients = (0..frame_len)
+            .map(|j| {
+                let mut values = vec![B::ZERO; frame_len];
+                values[j] = B::ONE;
+                evaluate_at(air, &values, &periodic_values)
+                    .into_iter()
+                    .zip(constants.iter())
+                    .map(|(evaluation, &constant)| evaluation - constant)
+                    .collect()
+            })
+            .collect::<Vec<Vec<B>>>();
+
+        // make sure the constraints declared as linear are consistent with the extracted terms
+        // at a frame which is different from the ones used for extraction
+        #[cfg(debug_assertions)]
+        {
+            let values = (0..frame_len)
+                .map(|j| B::from((j + 2) as u64))
+                .collect::<Vec<_>>();
+            let evaluations = evaluate_at(air, &values, &periodic_values);
+            let num_constraints = air.context().num_main_transition_constraints();
+            for i in (0..num_constraints).filter(|&i| is_linear[i]) {
+                let expected = values
+                    .iter()
+                    .zip(coefficients.iter())
+                    .fold(constants[i], |acc, (&v, c)| acc + v * c[i]);
+                assert!(
+                    evaluations[i] == expected,
+                    "transition constraint {} is declared as linear but is not an affine function \
+                    of the evaluation frame",
+                    i
+                );
+            }
+        }
+        AffineTerms {
+            constants,
+            coefficients,
+        }
+    }
+
+    /// Returns the number of terms in the affine function of each constraint (i.e., the trace
+    /// width multiplied by the frame size).
+    pub fn num_terms(&self) -> usize {
+        self.coefficients.len()
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Evaluates main transition constraints of the `air` at the evaluation frame defined by `values`;
+/// `values` is a concatenation of the rows of the frame, starting with the current row.
+fn evaluate_at<A: Air>(
+    air: &A,
+    values: &[A::BaseElement],
+    periodic_values: &[A::BaseElement],
+) -> Vec<A::BaseElement> {
+    let rows = values
+        .chunks(air.trace_width())
+        .map(|row| row.to_vec())
+        .collect();
+    let frame = EvaluationFrame::from_row_list(rows);
+    let mut result = vec![A::BaseElement::ZERO; air.context().num_main_transition_constraints()];
+    air.evaluate_transition(&frame, periodic_values, &mut result);
+    result
+}
@@ -3,7 +3,10 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::{trace::TraceTable, ProverError, StarkDomain};
+use super::{
+    trace::{TracePolyTable, TraceTable},
+    ProverError, StarkDomain,
+};
 
 mod boundary;
 use boundary::BoundaryConstraintGroup;
@@ -11,8 +14,14 @@ use boundary::BoundaryConstraintGroup;
 mod periodic_table;
 use periodic_table::PeriodicValueTable;
 
+mod linear;
+use linear::{AffineTerms, LinearConstraintGroup};
+
 mod evaluator;
-pub use evaluator::ConstraintEvaluator;
+pub use evaluator::{eval_constraint_poly_at, ConstraintEvaluator};
+
+#[cfg(debug_assertions)]
+pub use evaluator::validate_constraint_degrees;
 
 mod composition_poly;
 pub use composition_poly::CompositionPoly;
@@ -21,4 +30,4 @@ mod evaluation_table;
 pub use evaluation_table::ConstraintEvaluationTable;
 
 mod commitment;
-pub use commitment::ConstraintCommitment;
+pub use commitment::{CompositionCommitment, ConstraintCommitment};
@@ -5,7 +5,10 @@
 
 //! Contains common error types for prover and verifier.
 
+use crate::ProvingStage;
+use air::EvaluationError;
 use core::fmt;
+use utils::string::String;
 
 // PROVER ERROR
 // ================================================================================================
@@ -21,6 +24,51 @@ pub enum ProverError {
     /// This error occurs when the base field specified by the AIR does not support field extension
     /// of degree specified by proof options.
     UnsupportedFieldExtension(usize),
+    /// This error occurs when [Air::MAX_CONSTRAINT_DEGREE](air::Air::MAX_CONSTRAINT_DEGREE) of
+    /// the AIR is not equal to the largest base degree of the transition constraints declared in
+    /// the context of the AIR.
+    InconsistentConstraintDegree {
+        /// Value of the associated constant of the AIR.
+        declared: usize,
+        /// Largest base degree declared in the context of the AIR.
+        actual: usize,
+    },
+    /// This error occurs when the number of auxiliary columns declared by the AIR is different
+    /// from the number of auxiliary columns in the provided execution trace.
+    MismatchedAuxiliaryTraceWidth(usize, usize),
+    /// This error occurs when the AIR declares transition constraints against the auxiliary
+    /// trace segment, but does not implement
+    /// [Air::evaluate_aux_transition()](air::Air::evaluate_aux_transition) (i.e., the method
+    /// returns [EvaluationError::MissingAuxTransitionEvaluator](air::EvaluationError)).
+    MissingAuxTransitionEvaluator,
+    /// This error occurs when the number of padding rows declared by the AIR is different from
+    /// the number of padding rows in the provided execution trace.
+    MismatchedTracePadding(usize, usize),
+    /// This error occurs when a value in the execution trace is different from the value asserted
+    /// for this cell by the AIR; the values are recorded in their string representation.
+    BoundaryConstraintNotSatisfied {
+        col: usize,
+        step: usize,
+        expected: String,
+        actual: String,
+    },
+    /// This error occurs when the security level provided by the proof options is lower than
+    /// the security level required by the AIR.
+    InsufficientSecurity { required: usize, achieved: f64 },
+    /// This error occurs when the prover panics during proof generation; the panic message is
+    /// recorded in its string representation.
+    InternalPanic(String),
+    /// This error occurs when a [ProvingObserver](crate::ProvingObserver) requests cancellation
+    /// of proof generation; the stage which was about to be executed is recorded.
+    ProvingCancelled(ProvingStage),
+}
+
+impl From<EvaluationError> for ProverError {
+    fn from(err: EvaluationError) -> Self {
+        match err {
+            EvaluationError::MissingAuxTransitionEvaluator => Self::MissingAuxTransitionEvaluator,
+        }
+    }
 }
 
 impl fmt::Display for ProverError {
@@ -36,6 +84,30 @@ impl fmt::Display for ProverError {
             Self::UnsupportedFieldExtension(degree) => {
                 write!(f, "field extension of degree {} is not supported for the specified base field", degree)
             }
+            Self::InconsistentConstraintDegree { declared, actual } => {
+                write!(f, "declared max constraint degree {} is inconsistent with transition constraint degrees; expected {}", declared, actual)
+            }
+            Self::MismatchedAuxiliaryTraceWidth(expected, actual) => {
+                write!(f, "the AIR declares {} auxiliary trace columns, but the execution trace contains {}", expected, actual)
+            }
+            Self::MissingAuxTransitionEvaluator => {
+                write!(f, "the AIR declares auxiliary transition constraints, but does not evaluate them")
+            }
+            Self::MismatchedTracePadding(expected, actual) => {
+                write!(f, "the AIR declares {} padding rows, but the execution trace contains {}", expected, actual)
+            }
+            Self::BoundaryConstraintNotSatisfied { col, step, expected, actual } => {
+                write!(f, "a boundary constraint was not satisfied at step {} of column {}; expected {}, but was {}", step, col, expected, actual)
+            }
+            Self::InsufficientSecurity { required, achieved } => {
+                write!(f, "proof options provide {} bits of security, but at least {} bits are required", achieved, required)
+            }
+            Self::InternalPanic(message) => {
+                write!(f, "the prover panicked during proof generation: {}", message)
+            }
+            Self::ProvingCancelled(stage) => {
+                write!(f, "proof generation was cancelled before {}", stage)
+            }
         }
     }
 }
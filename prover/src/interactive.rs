@@ -0,0 +1,679 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    AuxSegmentBuilder, AuxTraceSegment, CompositionCommitment, CompositionPoly,
+    ConstraintCommitment, ConstraintEvaluator, DeepCompositionPoly, ExecutionTrace, ProverError,
+    StarkDomain, TracePolyTable, TraceTable,
+};
+use air::{
+    proof::{OodFrame, Queries},
+    Air, Assertion, ConstraintCompositionCoefficients, DeepCompositionCoefficients,
+    EvaluationFrame,
+};
+use crypto::{ElementHasher, MerkleTree};
+use fri::{FriProof, FriProver, ProverChannel};
+use math::{fft::infer_degree, FieldElement};
+use utils::collections::Vec;
+
+#[cfg(feature = "std")]
+use log::debug;
+#[cfg(feature = "std")]
+use math::log2;
+#[cfg(feature = "std")]
+use std::time::Instant;
+
+// QUERY SET
+// ================================================================================================
+/// Responses of the prover to the queries of the verifier.
+///
+/// These are the values which, together with the commitments and the out-of-domain frame sent
+/// during earlier rounds, make up a [StarkProof](air::proof::StarkProof).
+pub struct QuerySet {
+    /// Evaluations of the extended execution trace at the queried positions together with
+    /// Merkle authentication paths to the trace commitment.
+    pub trace_queries: Queries,
+    /// Evaluations of the extended auxiliary trace segment at the queried positions together
+    /// with Merkle authentication paths to the auxiliary trace commitment; this is set only for
+    /// computations with an auxiliary trace segment.
+    pub aux_trace_queries: Option<Queries>,
+    /// Evaluations of the constraint composition polynomial columns at the queried positions
+    /// together with Merkle authentication paths to the constraint commitment.
+    pub constraint_queries: Queries,
+    /// FRI proof for the DEEP composition polynomial queried at the same positions.
+    pub fri_proof: FriProof,
+}
+
+// INTERACTIVE PROVER
+// ================================================================================================
+/// A STARK prover which exposes each round of the protocol separately.
+///
+/// [prove()](crate::prove) generates proofs non-interactively by drawing all verifier
+/// challenges from a public coin (the Fiat-Shamir heuristic). This struct instead allows the
+/// challenges to be supplied externally, e.g. by a verifier running an interactive version of
+/// the protocol. [prove()](crate::prove) itself is implemented on top of this struct.
+///
+/// The rounds must be executed in the following order:
+/// 1. [commit_trace()](InteractiveProver::commit_trace) extends the execution trace and returns
+///    a commitment to the extended trace. For computations with an auxiliary trace segment,
+///    [commit_aux_trace()](InteractiveProver::commit_aux_trace) then builds the auxiliary
+///    segment using the random elements provided by the verifier, and returns a commitment to
+///    the extended auxiliary segment.
+/// 2. [commit_composition()](InteractiveProver::commit_composition) evaluates constraints using
+///    the composition coefficients provided by the verifier, and returns a commitment to the
+///    constraint composition polynomial.
+/// 3. [receive_ood_challenge()](InteractiveProver::receive_ood_challenge) evaluates the trace and
+///    constraint composition polynomials at the out-of-domain point provided by the verifier.
+/// 4. [commit_fri_layers()](InteractiveProver::commit_fri_layers) builds the DEEP composition
+///    polynomial using the coefficients provided by the verifier, and executes the commit phase
+///    of the FRI protocol; FRI layer commitments are sent and FRI challenges are received via
+///    the provided FRI channel.
+/// 5. [answer_queries()](InteractiveProver::answer_queries) responds to the queries of the
+///    verifier at the specified positions.
+///
+/// Calling any of the methods out of order results in a panic.
+///
+/// The strategy for committing to the constraint composition polynomial is defined by the `K`
+/// type parameter, and defaults to [ConstraintCommitment].
+pub struct InteractiveProver<'a, A, E, H, C, K = ConstraintCommitment<E, H>>
+where
+    A: Air,
+    E: FieldElement<BaseField = A::BaseElement>,
+    H: ElementHasher<BaseField = A::BaseElement>,
+    C: ProverChannel<E, Hasher = H>,
+    K: CompositionCommitment<E, H>,
+{
+    air: &'a A,
+    assertions: Vec<Assertion<A::BaseElement>>,
+    domain: StarkDomain<A::BaseElement>,
+    trace: Option<ExecutionTrace<A::BaseElement>>,
+    extended_trace: Option<TraceTable<A::BaseElement>>,
+    trace_polys: Option<TracePolyTable<A::BaseElement>>,
+    trace_tree: Option<MerkleTree<H>>,
+    main_trace: Option<ExecutionTrace<A::BaseElement>>,
+    aux_rand_elements: Vec<E>,
+    aux_assertions: Vec<Assertion<E>>,
+    extended_aux_trace: Option<TraceTable<E>>,
+    aux_trace_polys: Option<TracePolyTable<E>>,
+    aux_trace_tree: Option<MerkleTree<H>>,
+    composition_poly: Option<CompositionPoly<A::BaseElement, E>>,
+    constraint_commitment: Option<K>,
+    ood_challenge: Option<(E, EvaluationFrame<E>, Vec<E>)>,
+    fri_prover: Option<FriProver<A::BaseElement, E, C, H>>,
+}
+
+impl<'a, A, E, H, C, K> InteractiveProver<'a, A, E, H, C, K>
+where
+    A: Air,
+    E: FieldElement<BaseField = A::BaseElement>,
+    H: ElementHasher<BaseField = A::BaseElement>,
+    C: ProverChannel<E, Hasher = H>,
+    K: CompositionCommitment<E, H>,
+{
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new prover for the specified `trace` of the computation described by `air`.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * [Air::MAX_CONSTRAINT_DEGREE] of the `air` is not equal to the largest base degree of the
+    ///   transition constraints declared in its [AirContext](air::AirContext).
+    /// * The security level provided by the proof options of the `air` is lower than
+    ///   [Air::REQUIRED_SECURITY_BITS].
+    /// * In debug mode, the `air` declares auxiliary transition constraints, but does not
+    ///   evaluate them.
+    /// * The number of padding rows in the `trace` is different from the number of padding rows
+    ///   declared by the `air`.
+    /// * The `trace` does not satisfy any of the assertions returned by [Air::get_assertions()].
+    /// * In debug mode, the `trace` does not satisfy any of the transition constraints of the
+    ///   `air`.
+    pub fn new(air: &'a A, trace: ExecutionTrace<A::BaseElement>) -> Result<Self, ProverError> {
+        if A::MAX_CONSTRAINT_DEGREE != air.context().max_constraint_degree() {
+            return Err(ProverError::InconsistentConstraintDegree {
+                declared: A::MAX_CONSTRAINT_DEGREE,
+                actual: air.context().max_constraint_degree(),
+            });
+        }
+
+        // make sure the declared degrees of transition constraints are the actual degrees of the
+        // constraints; this is a sanity check of the AIR rather than of the trace, and thus, we do
+        // this in debug mode only
+        #[cfg(debug_assertions)]
+        crate::constraints::validate_constraint_degrees(air)?;
+
+        // make sure the proof options provide the level of security required by the AIR
+        let achieved = air
+            .options()
+            .security_bits::<A::BaseElement>(air.trace_length());
+        if achieved < A::REQUIRED_SECURITY_BITS as f64 {
+            return Err(ProverError::InsufficientSecurity {
+                required: A::REQUIRED_SECURITY_BITS,
+                achieved,
+            });
+        }
+
+        // make sure the trace is padded as expected by the AIR; otherwise, the verifier would
+        // reject the proof
+        if trace.num_padding_rows() != air.context().num_padding_rows() {
+            return Err(ProverError::MismatchedTracePadding(
+                air.context().num_padding_rows(),
+                trace.num_padding_rows(),
+            ));
+        }
+
+        // make sure the specified trace satisfies all assertions; this is cheap because only the
+        // asserted cells are read, and the error is much easier to act on than a failed proof
+        // the assertions are computed only once; they are passed by reference to the places where
+        // they are needed
+        let assertions = air.get_assertions();
+        trace.check_assertions(&assertions)?;
+
+        // make sure the specified trace satisfies all transition constraints of the AIR;
+        // assertions have already been checked above. we do this in debug mode only because this
+        // is a very expensive operation.
+        #[cfg(debug_assertions)]
+        trace.validate_transitions(air)?;
+
+        // build computation domain; this is used later for polynomial evaluations
+        #[cfg(feature = "std")]
+        let now = Instant::now();
+        let domain = StarkDomain::new(air);
+        #[cfg(feature = "std")]
+        debug!(
+            "Built domain of 2^{} elements in {} ms",
+            log2(domain.lde_domain_size()),
+            now.elapsed().as_millis()
+        );
+
+        Ok(InteractiveProver {
+            air,
+            assertions,
+            domain,
+            trace: Some(trace),
+            extended_trace: None,
+            trace_polys: None,
+            trace_tree: None,
+            main_trace: None,
+            aux_rand_elements: Vec::new(),
+            aux_assertions: Vec::new(),
+            extended_aux_trace: None,
+            aux_trace_polys: None,
+            aux_trace_tree: None,
+            composition_poly: None,
+            constraint_commitment: None,
+            ood_challenge: None,
+            fri_prover: None,
+        })
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the AIR of the computation for which the proof is generated.
+    pub fn air(&self) -> &A {
+        self.air
+    }
+
+    /// Returns the assertions against the execution trace of the computation.
+    pub fn assertions(&self) -> &[Assertion<A::BaseElement>] {
+        &self.assertions
+    }
+
+    /// Returns the number of boundary constraints of the computation.
+    ///
+    /// This is the number of assertions plus the number of domain point constraints returned
+    /// from [Air::get_domain_point_constraints()], and it determines the number of boundary
+    /// composition coefficients which must be provided to
+    /// [commit_composition()](InteractiveProver::commit_composition). For computations with an
+    /// auxiliary trace segment, assertions against the auxiliary segment are included only
+    /// after the segment has been committed to.
+    pub fn num_boundary_constraints(&self) -> usize {
+        let num_domain_point_constraints = self
+            .air
+            .get_domain_point_constraints::<A::BaseElement>()
+            .len();
+        self.assertions.len() + num_domain_point_constraints + self.aux_assertions.len()
+    }
+
+    /// Returns evaluations of the constraint composition polynomial columns over the LDE domain.
+    ///
+    /// These are the same values which were committed to by
+    /// [commit_composition()](InteractiveProver::commit_composition); they are re-computed from
+    /// the composition polynomial on every call, and thus, this method is intended for debugging
+    /// and analysis (e.g., for checking that the columns are of low degree).
+    ///
+    /// # Panics
+    /// Panics if the composition polynomial has not been committed to yet, or if FRI layers have
+    /// already been committed to.
+    pub fn composition_poly_evaluations(&self) -> Vec<Vec<E>> {
+        self.composition_poly
+            .as_ref()
+            .expect("constraint composition polynomial not built")
+            .evaluate(&self.domain)
+    }
+
+    // PROTOCOL ROUNDS
+    // --------------------------------------------------------------------------------------------
+
+    /// Extends the execution trace over the LDE domain and returns a commitment to the extended
+    /// trace.
+    ///
+    /// # Panics
+    /// Panics if the trace has already been committed to.
+    pub fn commit_trace(&mut self) -> H::Digest {
+        let trace = self
+            .trace
+            .take()
+            .expect("execution trace has already been committed to");
+
+        // extend the execution trace; this interpolates each register of the trace into a
+        // polynomial, and then evaluates the polynomial over the LDE domain; each of the trace
+        // polynomials has degree = trace_length - 1
+        #[cfg(feature = "std")]
+        let now = Instant::now();
+        // the auxiliary trace segment is built from the main segment only after the commitment
+        // to the main segment is sent, and thus, the main segment needs to be retained
+        if self.air.auxiliary_trace_width() > 0 {
+            self.main_trace = Some(trace.clone());
+        }
+        let (extended_trace, trace_polys) = trace.extend(&self.domain);
+        #[cfg(feature = "std")]
+        debug!(
+            "Extended execution trace of {} registers from 2^{} to 2^{} steps ({}x blowup) in {} ms",
+            extended_trace.width(),
+            log2(trace_polys.poly_size()),
+            log2(extended_trace.len()),
+            extended_trace.blowup(),
+            now.elapsed().as_millis()
+        );
+
+        // commit to the extended execution trace
+        #[cfg(feature = "std")]
+        let now = Instant::now();
+        let trace_tree = extended_trace.build_commitment::<H>();
+        let trace_root = *trace_tree.root();
+        #[cfg(feature = "std")]
+        debug!(
+            "Committed to extended execution trace by building a Merkle tree of depth {} in {} ms",
+            trace_tree.depth(),
+            now.elapsed().as_millis()
+        );
+
+        self.extended_trace = Some(extended_trace);
+        self.trace_polys = Some(trace_polys);
+        self.trace_tree = Some(trace_tree);
+        trace_root
+    }
+
+    /// Builds the auxiliary segment of the execution trace using the provided random elements,
+    /// extends it over the LDE domain, and returns a commitment to the extended segment.
+    ///
+    /// The segment is built by invoking `builder` with the main segment of the trace and
+    /// `rand_elements`; all registers of the resulting segment must have the same length as the
+    /// main segment.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The width of the built segment is not equal to [Air::auxiliary_trace_width()].
+    /// * The built segment does not satisfy any of the assertions returned by
+    ///   [Air::get_aux_assertions()].
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * The computation does not have an auxiliary trace segment.
+    /// * The trace has not been committed to yet, or if the auxiliary segment has already been
+    ///   committed to.
+    /// * The number of `rand_elements` is not equal to [Air::num_aux_rand_elements()].
+    /// * The length of any register of the built segment is not equal to the length of the main
+    ///   segment.
+    pub fn commit_aux_trace<F>(
+        &mut self,
+        rand_elements: Vec<E>,
+        builder: &F,
+    ) -> Result<H::Digest, ProverError>
+    where
+        F: AuxSegmentBuilder<A::BaseElement>,
+    {
+        assert!(
+            self.air.auxiliary_trace_width() > 0,
+            "the computation does not have an auxiliary trace segment"
+        );
+        assert!(
+            self.extended_aux_trace.is_none(),
+            "auxiliary trace segment has already been committed to"
+        );
+        let main_trace = self
+            .main_trace
+            .take()
+            .expect("execution trace must be committed to before the auxiliary segment is built");
+        assert_eq!(
+            self.air.num_aux_rand_elements(),
+            rand_elements.len(),
+            "expected {} random elements for building the auxiliary trace segment, but received {}",
+            self.air.num_aux_rand_elements(),
+            rand_elements.len()
+        );
+
+        // build the auxiliary segment and make sure its shape matches the one declared by the AIR
+        let aux_trace =
+            AuxTraceSegment::new(builder.build_aux_segment(&main_trace, &rand_elements));
+        if aux_trace.width() != self.air.auxiliary_trace_width() {
+            return Err(ProverError::MismatchedAuxiliaryTraceWidth(
+                self.air.auxiliary_trace_width(),
+                aux_trace.width(),
+            ));
+        }
+        assert!(
+            aux_trace.has_length(main_trace.length()),
+            "auxiliary trace segment must have the same length as the main segment"
+        );
+
+        // make sure the auxiliary segment satisfies its assertions; registers of these
+        // assertions refer to the registers of the auxiliary segment
+        let aux_assertions = self.air.get_aux_assertions(&rand_elements);
+        aux_trace.check_assertions(&aux_assertions)?;
+
+        // extend and commit to the auxiliary segment in the same way as to the main segment
+        #[cfg(feature = "std")]
+        let now = Instant::now();
+        let (extended_aux_trace, aux_trace_polys) = aux_trace.extend(&self.domain);
+        let aux_trace_tree = extended_aux_trace.build_commitment::<H>();
+        let aux_trace_root = *aux_trace_tree.root();
+        #[cfg(feature = "std")]
+        debug!(
+            "Extended and committed to auxiliary trace segment of {} registers in {} ms",
+            extended_aux_trace.width(),
+            now.elapsed().as_millis()
+        );
+
+        self.aux_rand_elements = rand_elements;
+        self.aux_assertions = aux_assertions;
+        self.extended_aux_trace = Some(extended_aux_trace);
+        self.aux_trace_polys = Some(aux_trace_polys);
+        self.aux_trace_tree = Some(aux_trace_tree);
+        Ok(aux_trace_root)
+    }
+
+    /// Evaluates constraints of the computation using the provided composition `coefficients`,
+    /// and returns a commitment to the evaluations of the resulting constraint composition
+    /// polynomial over the LDE domain.
+    ///
+    /// # Errors
+    /// Returns an error if the constraint composition polynomial could not be built from the
+    /// constraint evaluations (this may happen when the execution trace does not satisfy the
+    /// constraints of the computation).
+    ///
+    /// # Panics
+    /// Panics if the trace (or, for computations with an auxiliary trace segment, the auxiliary
+    /// segment) has not been committed to yet, or if the constraint composition polynomial has
+    /// already been committed to.
+    pub fn commit_composition(
+        &mut self,
+        coefficients: ConstraintCompositionCoefficients<E>,
+    ) -> Result<H::Digest, ProverError> {
+        assert!(
+            self.constraint_commitment.is_none(),
+            "constraint composition polynomial has already been committed to"
+        );
+        let extended_trace = self
+            .extended_trace
+            .as_ref()
+            .expect("execution trace must be committed to before constraints are evaluated");
+        assert!(
+            self.air.auxiliary_trace_width() == 0 || self.extended_aux_trace.is_some(),
+            "auxiliary trace segment must be committed to before constraints are evaluated"
+        );
+
+        // evaluate constraints specified by the AIR over the constraint evaluation domain, and
+        // compute random linear combinations of these evaluations using the provided
+        // coefficients; this step evaluates only constraint numerators, thus, only constraints
+        // with identical denominators are merged together. the results are saved into a
+        // constraint evaluation table where each column contains merged evaluations of
+        // constraints with identical denominators.
+        #[cfg(feature = "std")]
+        let now = Instant::now();
+        let evaluator = ConstraintEvaluator::new(
+            self.air,
+            &self.assertions,
+            &self.aux_assertions,
+            coefficients,
+            self.aux_rand_elements.clone(),
+        );
+        let constraint_evaluations = evaluator.evaluate(
+            extended_trace,
+            self.extended_aux_trace.as_ref(),
+            &self.domain,
+        )?;
+        #[cfg(feature = "std")]
+        debug!(
+            "Evaluated constraints over domain of 2^{} elements in {} ms",
+            log2(constraint_evaluations.num_rows()),
+            now.elapsed().as_millis()
+        );
+
+        // build constraint composition polynomial from the constraint evaluation table:
+        // - divide all constraint evaluation columns by their respective divisors
+        // - combine them into a single column of evaluations,
+        // - interpolate the column into a polynomial in coefficient form
+        // - "break" the polynomial into a set of column polynomials each of degree equal to
+        //   trace_length - 1
+        #[cfg(feature = "std")]
+        let now = Instant::now();
+        let composition_poly = constraint_evaluations.into_poly()?;
+        #[cfg(feature = "std")]
+        debug!(
+            "Converted constraint evaluations into {} composition polynomial columns of degree {} in {} ms",
+            composition_poly.num_columns(),
+            composition_poly.column_degree(),
+            now.elapsed().as_millis()
+        );
+
+        // then, evaluate composition polynomial columns over the LDE domain
+        #[cfg(feature = "std")]
+        let now = Instant::now();
+        let composed_evaluations = composition_poly.evaluate(&self.domain);
+        #[cfg(feature = "std")]
+        debug!(
+            "Evaluated composition polynomial columns over LDE domain (2^{} elements) in {} ms",
+            log2(composed_evaluations[0].len()),
+            now.elapsed().as_millis()
+        );
+
+        // finally, commit to the composition polynomial evaluations
+        #[cfg(feature = "std")]
+        let now = Instant::now();
+        let (constraint_root, constraint_commitment) = K::commit(composed_evaluations);
+        #[cfg(feature = "std")]
+        debug!(
+            "Committed to composed evaluations in {} ms",
+            now.elapsed().as_millis()
+        );
+
+        self.composition_poly = Some(composition_poly);
+        self.constraint_commitment = Some(constraint_commitment);
+        Ok(constraint_root)
+    }
+
+    /// Evaluates trace polynomials at the out-of-domain point `z` and at `z * g`, where `g` is
+    /// the generator of the trace domain, as well as constraint composition polynomial columns
+    /// at `z`, and returns the results.
+    ///
+    /// # Panics
+    /// Panics if the constraint composition polynomial has not been committed to yet, or if the
+    /// out-of-domain challenge has already been received.
+    pub fn receive_ood_challenge(&mut self, z: E) -> OodFrame {
+        assert!(
+            self.ood_challenge.is_none(),
+            "out-of-domain challenge has already been received"
+        );
+        let trace_polys = self
+            .trace_polys
+            .as_ref()
+            .expect("execution trace must be committed to before the out-of-domain challenge");
+        let composition_poly = self.composition_poly.as_ref().expect(
+            "constraint composition polynomial must be committed to before the out-of-domain challenge",
+        );
+
+        // the trace polynomials are evaluated over the points z, z * g, ..., one point for each
+        // row of the evaluation frame; in each row, evaluations of the auxiliary segment
+        // polynomials follow evaluations of the main segment polynomials
+        let mut ood_frame = trace_polys.get_ood_frame(z, self.air.frame_size());
+        if let Some(aux_trace_polys) = self.aux_trace_polys.as_ref() {
+            let aux_ood_frame = aux_trace_polys.get_ood_frame(z, self.air.frame_size());
+            let rows = (0..ood_frame.size())
+                .map(|i| {
+                    let mut row = ood_frame.row(i).to_vec();
+                    row.extend_from_slice(aux_ood_frame.row(i));
+                    row
+                })
+                .collect();
+            ood_frame = EvaluationFrame::from_row_list(rows);
+        }
+        let ood_evaluations = composition_poly.evaluate_at(z);
+
+        let mut result = OodFrame::default();
+        result.set_evaluation_frame(&ood_frame);
+        result.set_constraint_evaluations(&ood_evaluations);
+
+        self.ood_challenge = Some((z, ood_frame, ood_evaluations));
+        result
+    }
+
+    /// Builds the DEEP composition polynomial using the provided `coefficients`, and executes the
+    /// commit phase of the FRI protocol for this polynomial.
+    ///
+    /// FRI layer commitments are sent to the verifier, and the randomness for folding each layer
+    /// is received from the verifier via the provided `channel`.
+    ///
+    /// # Panics
+    /// Panics if the out-of-domain challenge has not been received yet, or if FRI layers have
+    /// already been committed to.
+    pub fn commit_fri_layers(
+        &mut self,
+        coefficients: DeepCompositionCoefficients<E>,
+        channel: &mut C,
+    ) {
+        assert!(
+            self.fri_prover.is_none(),
+            "FRI layers have already been committed to"
+        );
+        let (z, ood_frame, ood_evaluations) = self
+            .ood_challenge
+            .take()
+            .expect("out-of-domain challenge must be received before FRI layers are committed to");
+        let trace_polys = self
+            .trace_polys
+            .take()
+            .expect("trace polynomials not built");
+        let composition_poly = self
+            .composition_poly
+            .take()
+            .expect("constraint composition polynomial not built");
+
+        // use the coefficients to initialize the DEEP composition polynomial
+        #[cfg(feature = "std")]
+        let now = Instant::now();
+        let mut deep_composition_poly = DeepCompositionPoly::new(self.air, z, coefficients);
+
+        // combine all trace polynomials together and merge them into the DEEP composition
+        // polynomial
+        deep_composition_poly.add_trace_polys(trace_polys, self.aux_trace_polys.take(), ood_frame);
+
+        // merge columns of constraint composition polynomial into the DEEP composition polynomial
+        deep_composition_poly.add_composition_poly(composition_poly, ood_evaluations);
+
+        // raise the degree of the DEEP composition polynomial by one to make sure it is equal to
+        // trace_length - 1
+        deep_composition_poly.adjust_degree();
+
+        #[cfg(feature = "std")]
+        debug!(
+            "Built DEEP composition polynomial of degree {} in {} ms",
+            deep_composition_poly.degree(),
+            now.elapsed().as_millis()
+        );
+
+        // make sure the degree of the DEEP composition polynomial is equal to trace polynomial
+        // degree
+        assert_eq!(
+            self.domain.trace_length() - 1,
+            deep_composition_poly.degree()
+        );
+
+        // evaluate DEEP composition polynomial over LDE domain
+        #[cfg(feature = "std")]
+        let now = Instant::now();
+        let deep_evaluations = deep_composition_poly.evaluate(&self.domain);
+        // we check the following condition in debug mode only because infer_degree is an
+        // expensive operation
+        debug_assert_eq!(
+            self.domain.trace_length() - 1,
+            infer_degree(&deep_evaluations, self.domain.offset())
+        );
+        #[cfg(feature = "std")]
+        debug!(
+            "Evaluated DEEP composition polynomial over LDE domain (2^{} elements) in {} ms",
+            log2(self.domain.lde_domain_size()),
+            now.elapsed().as_millis()
+        );
+
+        // compute FRI layers for the DEEP composition polynomial
+        #[cfg(feature = "std")]
+        let now = Instant::now();
+        let mut fri_prover = FriProver::new(self.air.options().to_fri_options::<A::BaseElement>());
+        fri_prover.build_layers(channel, deep_evaluations);
+        #[cfg(feature = "std")]
+        debug!(
+            "Computed {} FRI layers from composition polynomial evaluations in {} ms",
+            fri_prover.num_layers(),
+            now.elapsed().as_millis()
+        );
+
+        self.fri_prover = Some(fri_prover);
+    }
+
+    /// Returns evaluations of the extended execution trace, constraint composition polynomial,
+    /// and FRI layers at the specified `positions` of the LDE domain together with Merkle
+    /// authentication paths to the previously sent commitments.
+    ///
+    /// This is the last round of the protocol, and thus, the prover is consumed.
+    ///
+    /// # Panics
+    /// Panics if FRI layers have not been committed to yet.
+    pub fn answer_queries(self, positions: &[usize]) -> QuerySet {
+        let mut fri_prover = self
+            .fri_prover
+            .expect("FRI layers must be committed to before queries are answered");
+        let extended_trace = self.extended_trace.expect("execution trace not extended");
+        let trace_tree = self.trace_tree.expect("execution trace not committed to");
+        let constraint_commitment = self
+            .constraint_commitment
+            .expect("constraint composition polynomial not committed to");
+
+        // generate FRI proof
+        let fri_proof = fri_prover.build_proof(positions);
+
+        // query the execution trace at the selected position; for each query, we need the
+        // state of the trace at that position + Merkle authentication path
+        let trace_queries = extended_trace.query(trace_tree, positions);
+        let aux_trace_queries = self.extended_aux_trace.zip(self.aux_trace_tree).map(
+            |(extended_aux_trace, aux_trace_tree)| {
+                extended_aux_trace.query(aux_trace_tree, positions)
+            },
+        );
+
+        // query the constraint commitment at the selected positions; for each query, we need just
+        // a Merkle authentication path. this is because constraint evaluations for each step are
+        // merged into a single value and Merkle authentication paths contain these values already
+        let constraint_queries = constraint_commitment.open(positions);
+
+        QuerySet {
+            trace_queries,
+            aux_trace_queries,
+            constraint_queries,
+            fri_proof,
+        }
+    }
+}
@@ -14,6 +14,16 @@
 //! machine). The number of threads can be configured via `RAYON_NUM_THREADS` environment
 //! variable.
 //!
+//! All parallel sections of proof generation (trace LDE, constraint evaluation, commitments
+//! etc.) run in the rayon thread pool of the calling thread. Thus, to limit the number of
+//! threads used by a specific proving job, or to keep the job isolated from other work, a proof
+//! can be generated inside `ThreadPool::install()` of a dedicated thread pool. The version of
+//! rayon used by this crate is re-exported as `rayon` for this purpose.
+//!
+//! Proof generation is deterministic: the proof is a function of the execution trace, public
+//! inputs, and proof options only, and does not depend on whether it is generated in a single
+//! thread or in multiple threads.
+//!
 //! # Usage
 //! To generate a proof that a computation was executed correctly, you'll need to do the
 //! following:
@@ -41,35 +51,46 @@
 extern crate alloc;
 
 pub use air::{
-    proof::StarkProof, Air, AirContext, Assertion, BoundaryConstraint, BoundaryConstraintGroup,
-    ConstraintCompositionCoefficients, ConstraintDivisor, DeepCompositionCoefficients,
-    EvaluationFrame, FieldExtension, HashFunction, ProofOptions, TraceInfo,
-    TransitionConstraintDegree, TransitionConstraintGroup,
+    proof::{
+        Commitments, Context, OodFrame, Queries, StarkProof, TaggedProof, AUX_TRACE_COMMITMENT_TAG,
+        CONSTRAINT_COMMITMENT_TAG, OOD_EVALUATIONS_TAG, OOD_FRAME_TAG, TRACE_COMMITMENT_TAG,
+    },
+    register_air, Air, AirContext, Assertion, BoundaryConstraint, BoundaryConstraintGroup,
+    CombinationMode, CompositeAir, CompositePublicInputs, ConcurrencyConfig,
+    ConstraintCompositionCoefficients, ConstraintDivisor, ConstraintGroup,
+    DeepCompositionCoefficients, EvaluationError, EvaluationFrame, FieldExtension,
+    FriRemainderEncoding, HashFunction, LagrangeBoundaryConstraints, MigrationError,
+    PaddingConstraintGroup, ProofOptions, StackedAir, StackedPublicInputs, TaggedAir, TraceInfo,
+    TransitionConstraintDegree, TransitionConstraintGroup, VerificationKey,
 };
+#[cfg(feature = "concurrent")]
+pub use utils::rayon;
 pub use utils::{
-    iterators, ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+    iterators, ByteReader, ByteWriter, CountingWriter, Deserializable, DeserializationError,
+    Serializable,
 };
 
-use fri::FriProver;
 use utils::collections::Vec;
 
+pub use fri;
+
 pub use math;
 use math::{
-    fft::infer_degree,
-    fields::{CubeExtension, QuadExtension},
-    FieldElement,
+    fields::{
+        instrumented::{self, FieldOpStats, InstrumentedField},
+        CubeExtension, QuadExtension,
+    },
+    ExtensibleField, FieldElement, StarkField,
 };
 
 pub use crypto;
 use crypto::{
-    hashers::{Blake3_192, Blake3_256, Sha3_256},
-    ElementHasher,
+    hashers::{Blake3_192, Blake3_256, Keccak256, Sha3_256},
+    PublicCoin, RandomCoin,
 };
 
 #[cfg(feature = "std")]
-use log::debug;
-#[cfg(feature = "std")]
-use math::log2;
+use log::{debug, warn};
 #[cfg(feature = "std")]
 use std::time::Instant;
 
@@ -77,14 +98,21 @@ mod domain;
 use domain::StarkDomain;
 
 mod constraints;
-use constraints::{ConstraintCommitment, ConstraintEvaluator};
+pub use constraints::{eval_constraint_poly_at, CompositionCommitment, ConstraintCommitment};
+use constraints::{CompositionPoly, ConstraintEvaluator};
 
 mod composer;
 use composer::DeepCompositionPoly;
 
 mod trace;
-use trace::TracePolyTable;
-pub use trace::{ExecutionTrace, ExecutionTraceFragment};
+pub use trace::{
+    AuxSegmentBuilder, ConstraintViolation, ExecutionTrace, ExecutionTraceFragment, TracePolyTable,
+    TraceValidationReport,
+};
+use trace::{AuxTraceSegment, TraceTable};
+
+mod interactive;
+pub use interactive::{InteractiveProver, QuerySet};
 
 mod channel;
 use channel::ProverChannel;
@@ -92,9 +120,25 @@ use channel::ProverChannel;
 mod errors;
 pub use errors::ProverError;
 
+mod observer;
+pub use observer::{ProvingObserver, ProvingStage};
+
+#[cfg(feature = "std")]
+mod stats;
+#[cfg(feature = "std")]
+pub use stats::ProofStats;
+
 #[cfg(test)]
 pub mod tests;
 
+// CONSTANTS
+// ================================================================================================
+
+/// Share of padding rows in an execution trace (in percent) above which the prover suggests
+/// restructuring the computation.
+#[cfg(feature = "std")]
+const MAX_PADDING_OVERHEAD: usize = 25;
+
 // PROVER
 // ================================================================================================
 /// Returns a STARK proof attesting to a correct execution of a computation.
@@ -113,189 +157,412 @@ pub mod tests;
 /// The function returns a [StarkProof] attesting that the specified `trace` is a valid execution
 /// trace of the computation described by the specified `AIR` and generated using the specified
 /// public inputs.
-#[rustfmt::skip]
+///
+/// # Errors
+/// Returns an error if:
+/// * [Air::MAX_CONSTRAINT_DEGREE] of the `AIR` is not equal to the largest base degree of the
+///   transition constraints declared in its [AirContext].
+/// * The security level provided by `options` is lower than [Air::REQUIRED_SECURITY_BITS].
+/// * The `AIR` declares auxiliary trace columns via [Air::auxiliary_trace_width()]; proofs for
+///   such computations must be generated via [prove_with_aux_segment()].
+/// * The `trace` does not satisfy any of the assertions returned by [Air::get_assertions()].
+/// * The base field does not support the field extension specified by `options`.
+/// * In debug mode, the `trace` does not satisfy any of the transition constraints of the `AIR`.
+/// * When compiled with `std` feature, the prover panicked during proof generation (e.g., because
+///   `AIR::new()` panicked). In this case, [ProverError::InternalPanic] with the panic message is
+///   returned. Panics cannot be caught if the binary is compiled with `panic = "abort"`.
+///
+/// # Panics
+/// When compiled without `std` feature, panics if the prover panicked during proof generation.
 pub fn prove<AIR: Air>(
     trace: ExecutionTrace<AIR::BaseElement>,
     pub_inputs: AIR::PublicInputs,
     options: ProofOptions,
 ) -> Result<StarkProof, ProverError> {
-    // serialize public inputs; these will be included in the seed for the public coin
-    let mut pub_inputs_bytes = Vec::new();
-    pub_inputs.write_into(&mut pub_inputs_bytes);
+    catch_panics(|| {
+        let build_aux_segment = None::<&NoAuxSegment>;
+        prove_unguarded::<AIR, _, _>(trace, pub_inputs, options, build_aux_segment, &mut ())
+    })
+}
 
-    // create an instance of AIR for the provided parameters. this takes a generic description of
-    // the computation (provided via AIR type), and creates a description of a specific execution
-    // of the computation for the provided public inputs.
-    let air = AIR::new(trace.get_info(), pub_inputs, options);
+/// Returns a STARK proof attesting to a correct execution of a computation, reporting progress
+/// of proof generation to the specified `observer`.
+///
+/// The `observer` is notified before and after each [ProvingStage] is executed, and is checked
+/// for cancellation before each stage (see [ProvingObserver] for details). The remaining
+/// parameters have the same meanings as the parameters of [prove()], and the generated proof is
+/// the same as the proof generated by [prove()] for the same parameters.
+///
+/// # Errors
+/// Returns an error under the same conditions as [prove()], and also returns
+/// [ProverError::ProvingCancelled] if the `observer` requests cancellation; the error specifies
+/// the stage which was about to be executed.
+///
+/// # Panics
+/// Panics under the same conditions as [prove()].
+pub fn prove_with_observer<AIR, O>(
+    trace: ExecutionTrace<AIR::BaseElement>,
+    pub_inputs: AIR::PublicInputs,
+    options: ProofOptions,
+    observer: &mut O,
+) -> Result<StarkProof, ProverError>
+where
+    AIR: Air,
+    O: ProvingObserver,
+{
+    catch_panics(|| {
+        let build_aux_segment = None::<&NoAuxSegment>;
+        prove_unguarded::<AIR, _, _>(trace, pub_inputs, options, build_aux_segment, observer)
+    })
+}
+
+/// Returns a STARK proof attesting to a correct execution of a computation using a custom source
+/// of verifier randomness.
+///
+/// This works in the same way as [prove()], but all verifier challenges are drawn from a public
+/// coin of type `C` rather than from the default [RandomCoin](crypto::RandomCoin), and all
+/// commitments are built using `C::Hasher`; the hash function specified in `options` must
+/// match `C::Hasher` as it is used to estimate the security level of the proof, and the verifier
+/// rejects proofs for which this is not the case. The coin is
+/// instantiated via [PublicCoin::new()] with the same seed as the default coin (i.e., the
+/// serialized public inputs followed by the serialized proof context). The resulting proof can
+/// be verified using `verify_with_coin()` function of the verifier crate with the same coin.
+///
+/// # Errors
+/// Returns an error under the same conditions as [prove()].
+///
+/// # Panics
+/// Panics under the same conditions as [prove()].
+pub fn prove_with_coin<AIR, C>(
+    trace: ExecutionTrace<AIR::BaseElement>,
+    pub_inputs: AIR::PublicInputs,
+    options: ProofOptions,
+) -> Result<StarkProof, ProverError>
+where
+    AIR: Air,
+    C: PublicCoin<BaseField = AIR::BaseElement> + Sync,
+{
+    catch_panics(|| {
+        let (air, pub_inputs_bytes) = instantiate_air::<AIR>(&trace, pub_inputs, options);
+        let build_aux_segment = None::<&NoAuxSegment>;
+
+        prove_air_with_coin::<AIR, C, _, _>(
+            air,
+            trace,
+            pub_inputs_bytes,
+            build_aux_segment,
+            &mut (),
+        )
+    })
+}
+
+/// Returns a single STARK proof attesting to correct executions of several instances of the same
+/// computation.
+///
+/// Execution traces of the instances are stacked one below another into a single execution
+/// trace, and the proof is generated for this trace against [StackedAir] of the specified `AIR`
+/// (see [StackedAir] for the description of the resulting constraints). Public inputs of the
+/// instances are specified in the same order as the traces; metadata of the first trace is used
+/// as metadata of the stacked trace. Proof size grows logarithmically with the number of
+/// instances. The resulting proof can be verified using `verify_many()` function of the verifier
+/// crate with the same list of public inputs.
+///
+/// # Errors
+/// Returns an error under the same conditions as [prove()] for the stacked trace and
+/// [StackedAir]. When compiled with `std` feature, [ProverError::InternalPanic] is also returned
+/// if:
+/// * The numbers of traces and public inputs are different, or the number of traces is not a
+///   power of two.
+/// * The traces have different widths, lengths, or metadata, or any of them contains padding
+///   rows.
+/// * [StackedAir] cannot be instantiated for the traces (e.g., because the `AIR` has an
+///   auxiliary trace segment).
+///
+/// # Panics
+/// Panics under the same conditions as [prove()]; when compiled without `std` feature, also
+/// panics under the conditions listed above for [ProverError::InternalPanic].
+pub fn prove_many<AIR: Air>(
+    traces: Vec<ExecutionTrace<AIR::BaseElement>>,
+    pub_inputs: Vec<AIR::PublicInputs>,
+    options: ProofOptions,
+) -> Result<StarkProof, ProverError> {
+    catch_panics(|| {
+        assert_eq!(
+            traces.len(),
+            pub_inputs.len(),
+            "number of traces must be equal to the number of public inputs"
+        );
+        let pub_inputs = StackedPublicInputs::new(pub_inputs);
+        let trace = stack_traces(traces);
+        let build_aux_segment = None::<&NoAuxSegment>;
+        prove_unguarded::<StackedAir<AIR>, _, _>(
+            trace,
+            pub_inputs,
+            options,
+            build_aux_segment,
+            &mut (),
+        )
+    })
+}
+
+/// Returns a STARK proof attesting to a correct execution of a computation with an auxiliary
+/// trace segment.
+///
+/// The auxiliary segment is built by invoking `aux_segment_builder` with the main segment of the
+/// trace (i.e., the `trace`) and random elements drawn from the public coin after the prover
+/// commits to the main segment; the number of the elements is specified by
+/// [Air::num_aux_rand_elements()], and the elements are drawn from the field in which the
+/// protocol is executed (see [AuxSegmentBuilder]). The remaining parameters have the same
+/// meanings as the parameters of [prove()].
+///
+/// # Errors
+/// Returns an error under the same conditions as [prove()] (except for the computation having
+/// auxiliary trace columns), and also if:
+/// * The width of the auxiliary segment is not equal to [Air::auxiliary_trace_width()].
+/// * The auxiliary segment does not satisfy any of the assertions returned by
+///   [Air::get_aux_assertions()].
+/// * When compiled with `std` feature, the `AIR` does not declare auxiliary trace columns, or
+///   the length of any register of the auxiliary segment is different from the length of the
+///   `trace` (in this case, [ProverError::InternalPanic] is returned).
+///
+/// # Panics
+/// Panics under the same conditions as [prove()]; when compiled without `std` feature, also
+/// panics under the conditions listed above for [ProverError::InternalPanic].
+pub fn prove_with_aux_segment<AIR, F>(
+    trace: ExecutionTrace<AIR::BaseElement>,
+    pub_inputs: AIR::PublicInputs,
+    options: ProofOptions,
+    aux_segment_builder: F,
+) -> Result<StarkProof, ProverError>
+where
+    AIR: Air,
+    F: AuxSegmentBuilder<AIR::BaseElement>,
+{
+    catch_panics(|| {
+        let build_aux_segment = Some(&aux_segment_builder);
+        prove_unguarded::<AIR, F, _>(trace, pub_inputs, options, build_aux_segment, &mut ())
+    })
+}
 
-    // make sure the specified trace is valid against the AIR. This checks validity of both,
-    // assertions and state transitions. we do this in debug mode only because this is a very
-    // expensive operation.
-    #[cfg(debug_assertions)]
-    trace.validate(&air);
+/// Generates a STARK proof as described in [prove()] without converting panics into errors; the
+/// auxiliary trace segment is built via `build_aux_segment` if it is provided, and progress is
+/// reported to the `observer`.
+#[rustfmt::skip]
+fn prove_unguarded<AIR, F, O>(
+    trace: ExecutionTrace<AIR::BaseElement>,
+    pub_inputs: AIR::PublicInputs,
+    options: ProofOptions,
+    build_aux_segment: Option<&F>,
+    observer: &mut O,
+) -> Result<StarkProof, ProverError>
+where
+    AIR: Air,
+    O: ProvingObserver,
+    F: AuxSegmentBuilder<AIR::BaseElement>,
+{
+    let (air, pub_inputs_bytes) = instantiate_air::<AIR>(&trace, pub_inputs, options);
+
+    // figure out which hash function to instantiate the public coin with; the extension field is
+    // selected by prove_air_with_coin()
+    match air.options().hash_fn() {
+        HashFunction::Blake3_256 => prove_air_with_coin::
+            <AIR, RandomCoin<AIR::BaseElement, Blake3_256<AIR::BaseElement>>, _, _>(air, trace, pub_inputs_bytes, build_aux_segment, observer),
+        HashFunction::Blake3_192 => prove_air_with_coin::
+            <AIR, RandomCoin<AIR::BaseElement, Blake3_192<AIR::BaseElement>>, _, _>(air, trace, pub_inputs_bytes, build_aux_segment, observer),
+        HashFunction::Sha3_256 => prove_air_with_coin::
+            <AIR, RandomCoin<AIR::BaseElement, Sha3_256<AIR::BaseElement>>, _, _>(air, trace, pub_inputs_bytes, build_aux_segment, observer),
+        HashFunction::Keccak256 => prove_air_with_coin::
+            <AIR, RandomCoin<AIR::BaseElement, Keccak256<AIR::BaseElement>>, _, _>(air, trace, pub_inputs_bytes, build_aux_segment, observer),
+    }
+}
 
+/// Generates a STARK proof for the provided instance of `air` drawing all verifier challenges
+/// from a public coin of type `C`; the auxiliary trace segment is built via `build_aux_segment`
+/// if it is provided, and progress is reported to the `observer`.
+#[rustfmt::skip]
+fn prove_air_with_coin<AIR, C, F, O>(
+    air: AIR,
+    trace: ExecutionTrace<AIR::BaseElement>,
+    pub_inputs_bytes: Vec<u8>,
+    build_aux_segment: Option<&F>,
+    observer: &mut O,
+) -> Result<StarkProof, ProverError>
+where
+    AIR: Air,
+    C: PublicCoin<BaseField = AIR::BaseElement> + Sync,
+    F: AuxSegmentBuilder<AIR::BaseElement>,
+    O: ProvingObserver,
+{
     // figure out which version of the generic proof generation procedure to run. this is a sort
-    // of static dispatch for selecting two generic parameter: extension field and hash function.
+    // of static dispatch for selecting the extension field; the hash function is defined by the
+    // public coin.
     match air.options().field_extension() {
-        FieldExtension::None => match air.options().hash_fn() {
-            HashFunction::Blake3_256 => generate_proof::
-                <AIR, AIR::BaseElement, Blake3_256<AIR::BaseElement>>(air, trace, pub_inputs_bytes),
-            HashFunction::Blake3_192 => generate_proof::
-                <AIR, AIR::BaseElement, Blake3_192<AIR::BaseElement>>(air, trace, pub_inputs_bytes),
-            HashFunction::Sha3_256 => generate_proof::
-                <AIR, AIR::BaseElement, Sha3_256<AIR::BaseElement>>(air, trace, pub_inputs_bytes)
-        },
+        FieldExtension::None => generate_proof::
+            <AIR, AIR::BaseElement, C, _, _>(air, trace, pub_inputs_bytes, build_aux_segment, observer),
         FieldExtension::Quadratic => {
             if !<QuadExtension<AIR::BaseElement>>::is_supported() {
                 return Err(ProverError::UnsupportedFieldExtension(2));
             }
-            match air.options().hash_fn() {
-                HashFunction::Blake3_256 => generate_proof::
-                    <AIR, QuadExtension<AIR::BaseElement>, Blake3_256<AIR::BaseElement>>(air, trace, pub_inputs_bytes),
-                HashFunction::Blake3_192 => generate_proof::
-                    <AIR, QuadExtension<AIR::BaseElement>, Blake3_192<AIR::BaseElement>>(air, trace, pub_inputs_bytes),
-                HashFunction::Sha3_256 => generate_proof::
-                    <AIR, QuadExtension<AIR::BaseElement>, Sha3_256<AIR::BaseElement>>(air, trace, pub_inputs_bytes),
-            }
-        },
+            generate_proof::
+                <AIR, QuadExtension<AIR::BaseElement>, C, _, _>(air, trace, pub_inputs_bytes, build_aux_segment, observer)
+        }
         FieldExtension::Cubic => {
             if !<CubeExtension<AIR::BaseElement>>::is_supported() {
                 return Err(ProverError::UnsupportedFieldExtension(3));
             }
-            match air.options().hash_fn() {
-                HashFunction::Blake3_256 => generate_proof::
-                    <AIR, CubeExtension<AIR::BaseElement>, Blake3_256<AIR::BaseElement>>(air, trace, pub_inputs_bytes),
-                HashFunction::Blake3_192 => generate_proof::
-                    <AIR, CubeExtension<AIR::BaseElement>, Blake3_192<AIR::BaseElement>>(air, trace, pub_inputs_bytes),
-                HashFunction::Sha3_256 => generate_proof::
-                    <AIR, CubeExtension<AIR::BaseElement>, Sha3_256<AIR::BaseElement>>(air, trace, pub_inputs_bytes),
-            }
-        },
+            generate_proof::
+                <AIR, CubeExtension<AIR::BaseElement>, C, _, _>(air, trace, pub_inputs_bytes, build_aux_segment, observer)
+        }
     }
 }
 
+/// Returns a STARK proof attesting to a correct execution of a computation together with the
+/// numbers of field operations performed while generating the proof.
+///
+/// The computation must be defined over an [InstrumentedField], which counts all operations
+/// performed over the wrapped field `B` (see the [instrumented](math::fields::instrumented)
+/// module for details on how operations are counted). The counters are reset before the proof is
+/// generated, and thus, the returned stats describe this proving run only, provided no other
+/// computations over instrumented fields are executed concurrently. In debug mode, the stats
+/// also include operations performed while validating the `trace` against the transition
+/// constraints.
+///
+/// Parameters of this function have the same meanings as the parameters of [prove()].
+///
+/// # Errors
+/// Returns an error under the same conditions as [prove()].
+///
+/// # Panics
+/// Panics under the same conditions as [prove()].
+pub fn prove_instrumented<AIR, B>(
+    trace: ExecutionTrace<AIR::BaseElement>,
+    pub_inputs: AIR::PublicInputs,
+    options: ProofOptions,
+) -> Result<(StarkProof, FieldOpStats), ProverError>
+where
+    AIR: Air<BaseElement = InstrumentedField<B>>,
+    B: StarkField + ExtensibleField<2> + ExtensibleField<3>,
+{
+    instrumented::reset_op_stats();
+    let proof = prove::<AIR>(trace, pub_inputs, options)?;
+    Ok((proof, instrumented::op_stats()))
+}
+
+/// Returns a STARK proof attesting to a correct execution of a computation together with a
+/// report on the time spent on each proving stage and on the sizes of the proof components.
+///
+/// Stage times are measured via a [ProvingObserver] and cover the same stages as reported to
+/// observers by [prove_with_observer()]; see [ProofStats] for details. Parameters of this function
+/// have the same meanings as the parameters of [prove()], and the generated proof is the same as
+/// the proof generated by [prove()] for the same parameters.
+///
+/// # Errors
+/// Returns an error under the same conditions as [prove()].
+///
+/// # Panics
+/// Panics under the same conditions as [prove()].
+#[cfg(feature = "std")]
+pub fn prove_with_stats<AIR: Air>(
+    trace: ExecutionTrace<AIR::BaseElement>,
+    pub_inputs: AIR::PublicInputs,
+    options: ProofOptions,
+) -> Result<(StarkProof, ProofStats), ProverError> {
+    let mut observer = stats::StatsObserver::default();
+    let proof = prove_with_observer::<AIR, _>(trace, pub_inputs, options, &mut observer)?;
+    let stats = ProofStats::new(observer.into_stage_times(), &proof);
+    Ok((proof, stats))
+}
+
+// TRACE VALIDATION
+// ================================================================================================
+/// Checks the specified execution trace against all constraints of a computation, and returns a
+/// report describing each of the violated constraints.
+///
+/// The `AIR` is instantiated for the `trace` in the same way as by [prove()], and the trace is
+/// checked via [ExecutionTrace::check_constraints()]. This is intended for diagnosing invalid
+/// traces: unlike the prover, which checks transition constraints only in debug mode and stops
+/// at the first violation, this function checks all constraints in release builds as well and
+/// reports the index of each violated constraint together with the first step at which it is
+/// violated.
+///
+/// # Errors
+/// Returns a [TraceValidationReport] if the `trace` violates any of the constraints of the
+/// `AIR`.
+///
+/// # Panics
+/// Panics if `AIR::new()` panics, or if the width of the `trace` is different from the trace
+/// width of the `AIR`.
+pub fn validate_trace<AIR: Air>(
+    trace: &ExecutionTrace<AIR::BaseElement>,
+    pub_inputs: AIR::PublicInputs,
+    options: ProofOptions,
+) -> Result<(), TraceValidationReport> {
+    let air = AIR::new(trace.get_info(), pub_inputs, options);
+    trace.check_constraints(&air)
+}
+
 // PROOF GENERATION PROCEDURE
 // ================================================================================================
 /// Performs the actual proof generation procedure, generating the proof that the provided
 /// execution `trace` is valid against the provided `air`.
-fn generate_proof<A, E, H>(
+///
+/// The procedure runs all rounds of an [InteractiveProver], drawing verifier challenges from a
+/// public coin seeded with the public inputs and the values sent by the prover in prior rounds.
+/// Each of the rounds is reported to the `observer` as a [ProvingStage].
+fn generate_proof<A, E, R, F, O>(
     air: A,
     trace: ExecutionTrace<A::BaseElement>,
     pub_inputs_bytes: Vec<u8>,
+    build_aux_segment: Option<&F>,
+    observer: &mut O,
 ) -> Result<StarkProof, ProverError>
 where
+    O: ProvingObserver,
     A: Air,
     E: FieldElement<BaseField = A::BaseElement>,
-    H: ElementHasher<BaseField = A::BaseElement>,
+    R: PublicCoin<BaseField = A::BaseElement> + Sync,
+    F: AuxSegmentBuilder<A::BaseElement>,
 {
+    // make sure the auxiliary trace segment can be built if the AIR declares one
+    if build_aux_segment.is_none() && air.auxiliary_trace_width() > 0 {
+        return Err(ProverError::MismatchedAuxiliaryTraceWidth(
+            air.auxiliary_trace_width(),
+            0,
+        ));
+    }
+
+    // make sure the trace is valid against the AIR and build the computation domain
+    let mut prover =
+        InteractiveProver::<A, E, R::Hasher, ProverChannel<A, E, R::Hasher, R>>::new(&air, trace)?;
+
     // create a channel which is used to simulate interaction between the prover and the verifier;
     // the channel will be used to commit to values and to draw randomness that should come from
     // the verifier.
-    let mut channel = ProverChannel::<A, E, H>::new(&air, pub_inputs_bytes);
-
-    // 1 ----- extend execution trace -------------------------------------------------------------
-
-    // build computation domain; this is used later for polynomial evaluations
-    #[cfg(feature = "std")]
-    let now = Instant::now();
-    let domain = StarkDomain::new(&air);
-    #[cfg(feature = "std")]
-    debug!(
-        "Built domain of 2^{} elements in {} ms",
-        log2(domain.lde_domain_size()),
-        now.elapsed().as_millis()
-    );
-
-    // extend the execution trace; this interpolates each register of the trace into a polynomial,
-    // and then evaluates the polynomial over the LDE domain; each of the trace polynomials has
-    // degree = trace_length - 1
-    let (extended_trace, trace_polys) = trace.extend(&domain);
-    #[cfg(feature = "std")]
-    debug!(
-        "Extended execution trace of {} registers from 2^{} to 2^{} steps ({}x blowup) in {} ms",
-        extended_trace.width(),
-        log2(trace_polys.poly_size()),
-        log2(extended_trace.len()),
-        extended_trace.blowup(),
-        now.elapsed().as_millis()
-    );
-
-    // 2 ----- commit to the extended execution trace ---------------------------------------------
-    #[cfg(feature = "std")]
-    let now = Instant::now();
-    let trace_tree = extended_trace.build_commitment::<H>();
-    channel.commit_trace(*trace_tree.root());
-    #[cfg(feature = "std")]
-    debug!(
-        "Committed to extended execution trace by building a Merkle tree of depth {} in {} ms",
-        trace_tree.depth(),
-        now.elapsed().as_millis()
-    );
-
-    // 3 ----- evaluate constraints ---------------------------------------------------------------
-    // evaluate constraints specified by the AIR over the constraint evaluation domain, and compute
-    // random linear combinations of these evaluations using coefficients drawn from the channel;
-    // this step evaluates only constraint numerators, thus, only constraints with identical
-    // denominators are merged together. the results are saved into a constraint evaluation table
-    // where each column contains merged evaluations of constraints with identical denominators.
-    #[cfg(feature = "std")]
-    let now = Instant::now();
-    let constraint_coeffs = channel.get_constraint_composition_coeffs();
-    let evaluator = ConstraintEvaluator::new(&air, constraint_coeffs);
-    let constraint_evaluations = evaluator.evaluate(&extended_trace, &domain);
-    #[cfg(feature = "std")]
-    debug!(
-        "Evaluated constraints over domain of 2^{} elements in {} ms",
-        log2(constraint_evaluations.num_rows()),
-        now.elapsed().as_millis()
-    );
-
-    // 4 ----- commit to constraint evaluations ---------------------------------------------------
-
-    // first, build constraint composition polynomial from the constraint evaluation table:
-    // - divide all constraint evaluation columns by their respective divisors
-    // - combine them into a single column of evaluations,
-    // - interpolate the column into a polynomial in coefficient form
-    // - "break" the polynomial into a set of column polynomials each of degree equal to
-    //   trace_length - 1
-    #[cfg(feature = "std")]
-    let now = Instant::now();
-    let composition_poly = constraint_evaluations.into_poly()?;
-    #[cfg(feature = "std")]
-    debug!(
-        "Converted constraint evaluations into {} composition polynomial columns of degree {} in {} ms",
-        composition_poly.num_columns(),
-        composition_poly.column_degree(),
-        now.elapsed().as_millis()
-    );
-
-    // then, evaluate composition polynomial columns over the LDE domain
-    #[cfg(feature = "std")]
-    let now = Instant::now();
-    let composed_evaluations = composition_poly.evaluate(&domain);
-    #[cfg(feature = "std")]
-    debug!(
-        "Evaluated composition polynomial columns over LDE domain (2^{} elements) in {} ms",
-        log2(composed_evaluations[0].len()),
-        now.elapsed().as_millis()
-    );
-
-    // finally, commit to the composition polynomial evaluations
-    #[cfg(feature = "std")]
-    let now = Instant::now();
-    let constraint_commitment = ConstraintCommitment::<E, H>::new(composed_evaluations);
-    channel.commit_constraints(constraint_commitment.root());
-    #[cfg(feature = "std")]
-    debug!(
-        "Committed to composed evaluations by building a Merkle tree of depth {} in {} ms",
-        constraint_commitment.tree_depth(),
-        now.elapsed().as_millis()
-    );
+    let mut channel = ProverChannel::<A, E, R::Hasher, R>::new(&air, pub_inputs_bytes);
+
+    // 1 ----- extend and commit to the execution trace -------------------------------------------
+    start_stage(observer, ProvingStage::TraceCommitment)?;
+    channel.commit_trace(prover.commit_trace());
+    observer.stage_completed(ProvingStage::TraceCommitment);
+
+    // build the auxiliary trace segment using random elements drawn from the channel after the
+    // commitment to the main segment, and commit to the extended auxiliary segment
+    if let Some(build_aux_segment) = build_aux_segment {
+        start_stage(observer, ProvingStage::AuxTraceCommitment)?;
+        let aux_rand_elements = channel.get_aux_rand_elements();
+        channel.commit_aux_trace(prover.commit_aux_trace(aux_rand_elements, build_aux_segment)?);
+        observer.stage_completed(ProvingStage::AuxTraceCommitment);
+    }
 
-    // 5 ----- build DEEP composition polynomial --------------------------------------------------
-    #[cfg(feature = "std")]
-    let now = Instant::now();
+    // 2 ----- evaluate and commit to constraints -------------------------------------------------
+    // constraint evaluations are combined using random coefficients drawn from the channel
+    start_stage(observer, ProvingStage::ConstraintEvaluation)?;
+    let constraint_coeffs =
+        channel.get_constraint_composition_coeffs(prover.num_boundary_constraints());
+    channel.commit_constraints(prover.commit_composition(constraint_coeffs)?);
+    observer.stage_completed(ProvingStage::ConstraintEvaluation);
 
+    // 3 ----- evaluate polynomials at an out-of-domain point -------------------------------------
     // draw an out-of-domain point z. Depending on the type of E, the point is drawn either
     // from the base field or from an extension field defined by E.
     //
@@ -303,72 +570,25 @@ where
     // increase security. Soundness is limited by the size of the field that the random point
     // is drawn from, and we can potentially save on performance by only drawing this point
     // from an extension field, rather than increasing the size of the field overall.
+    start_stage(observer, ProvingStage::OodEvaluation)?;
     let z = channel.get_ood_point();
 
     // evaluate trace and constraint polynomials at the OOD point z, and send the results to
     // the verifier. the trace polynomials are actually evaluated over two points: z and z * g,
     // where g is the generator of the trace domain.
-    let ood_frame = trace_polys.get_ood_frame(z);
-    channel.send_ood_evaluation_frame(&ood_frame);
-
-    let ood_evaluations = composition_poly.evaluate_at(z);
-    channel.send_ood_constraint_evaluations(&ood_evaluations);
+    channel.send_ood_frame(prover.receive_ood_challenge(z));
+    observer.stage_completed(ProvingStage::OodEvaluation);
 
-    // draw random coefficients to use during DEEP polynomial composition, and use them to
-    // initialize the DEEP composition polynomial
+    // 4 ----- build DEEP composition polynomial and compute FRI layers ---------------------------
+    // draw random coefficients to use during DEEP polynomial composition; FRI layers are
+    // committed to and folding challenges are drawn via the same channel
+    start_stage(observer, ProvingStage::FriCommitment)?;
     let deep_coefficients = channel.get_deep_composition_coeffs();
-    let mut deep_composition_poly = DeepCompositionPoly::new(&air, z, deep_coefficients);
-
-    // combine all trace polynomials together and merge them into the DEEP composition polynomial
-    deep_composition_poly.add_trace_polys(trace_polys, ood_frame);
-
-    // merge columns of constraint composition polynomial into the DEEP composition polynomial;
-    deep_composition_poly.add_composition_poly(composition_poly, ood_evaluations);
-
-    // raise the degree of the DEEP composition polynomial by one to make sure it is equal to
-    // trace_length - 1
-    deep_composition_poly.adjust_degree();
+    prover.commit_fri_layers(deep_coefficients, &mut channel);
+    observer.stage_completed(ProvingStage::FriCommitment);
 
-    #[cfg(feature = "std")]
-    debug!(
-        "Built DEEP composition polynomial of degree {} in {} ms",
-        deep_composition_poly.degree(),
-        now.elapsed().as_millis()
-    );
-
-    // make sure the degree of the DEEP composition polynomial is equal to trace polynomial degree
-    assert_eq!(domain.trace_length() - 1, deep_composition_poly.degree());
-
-    // 6 ----- evaluate DEEP composition polynomial over LDE domain -------------------------------
-    #[cfg(feature = "std")]
-    let now = Instant::now();
-    let deep_evaluations = deep_composition_poly.evaluate(&domain);
-    // we check the following condition in debug mode only because infer_degree is an expensive
-    // operation
-    debug_assert_eq!(
-        domain.trace_length() - 1,
-        infer_degree(&deep_evaluations, domain.offset())
-    );
-    #[cfg(feature = "std")]
-    debug!(
-        "Evaluated DEEP composition polynomial over LDE domain (2^{} elements) in {} ms",
-        log2(domain.lde_domain_size()),
-        now.elapsed().as_millis()
-    );
-
-    // 7 ----- compute FRI layers for the composition polynomial ----------------------------------
-    #[cfg(feature = "std")]
-    let now = Instant::now();
-    let mut fri_prover = FriProver::new(air.options().to_fri_options());
-    fri_prover.build_layers(&mut channel, deep_evaluations);
-    #[cfg(feature = "std")]
-    debug!(
-        "Computed {} FRI layers from composition polynomial evaluations in {} ms",
-        fri_prover.num_layers(),
-        now.elapsed().as_millis()
-    );
-
-    // 8 ----- determine query positions ----------------------------------------------------------
+    // 5 ----- determine query positions ----------------------------------------------------------
+    start_stage(observer, ProvingStage::QueryGeneration)?;
     #[cfg(feature = "std")]
     let now = Instant::now();
 
@@ -383,27 +603,158 @@ where
         query_positions.len(),
         now.elapsed().as_millis()
     );
+    observer.stage_completed(ProvingStage::QueryGeneration);
 
-    // 9 ----- build proof object -----------------------------------------------------------------
+    // 6 ----- build proof object -----------------------------------------------------------------
+    start_stage(observer, ProvingStage::ProofAssembly)?;
     #[cfg(feature = "std")]
     let now = Instant::now();
+    let queries = prover.answer_queries(&query_positions);
+    let proof = channel.build_proof(
+        queries.trace_queries,
+        queries.aux_trace_queries,
+        queries.constraint_queries,
+        queries.fri_proof,
+    );
+    #[cfg(feature = "std")]
+    debug!("Built proof object in {} ms", now.elapsed().as_millis());
+    observer.stage_completed(ProvingStage::ProofAssembly);
 
-    // generate FRI proof
-    let fri_proof = fri_prover.build_proof(&query_positions);
+    Ok(proof)
+}
 
-    // query the execution trace at the selected position; for each query, we need the
-    // state of the trace at that position + Merkle authentication path
-    let trace_queries = extended_trace.query(trace_tree, &query_positions);
+// HELPER FUNCTIONS
+// ================================================================================================
 
-    // query the constraint commitment at the selected positions; for each query, we need just
-    // a Merkle authentication path. this is because constraint evaluations for each step are
-    // merged into a single value and Merkle authentication paths contain these values already
-    let constraint_queries = constraint_commitment.query(&query_positions);
+/// Instantiates the `AIR` for the specified `trace`, and returns it together with the serialized
+/// public inputs; this also warns about the overhead of padding rows in the trace.
+fn instantiate_air<AIR: Air>(
+    trace: &ExecutionTrace<AIR::BaseElement>,
+    pub_inputs: AIR::PublicInputs,
+    options: ProofOptions,
+) -> (AIR, Vec<u8>) {
+    // serialize public inputs; these will be included in the seed for the public coin
+    let mut pub_inputs_bytes = Vec::new();
+    pub_inputs.write_into(&mut pub_inputs_bytes);
 
-    // build the proof object
-    let proof = channel.build_proof(trace_queries, constraint_queries, fri_proof);
+    // create an instance of AIR for the provided parameters. this takes a generic description of
+    // the computation (provided via AIR type), and creates a description of a specific execution
+    // of the computation for the provided public inputs.
+    let air = AIR::new(trace.get_info(), pub_inputs, options);
+
+    // padding rows make the proof more expensive without contributing to the computation; warn
+    // the user about the overhead so that it does not go unnoticed
     #[cfg(feature = "std")]
-    debug!("Built proof object in {} ms", now.elapsed().as_millis());
+    {
+        let trace_info = air.trace_info();
+        if trace_info.num_padding_rows() > 0 {
+            let overhead = trace_info.num_padding_rows() * 100 / trace_info.length();
+            let advice = if overhead > MAX_PADDING_OVERHEAD {
+                "consider restructuring the computation to reduce the number of padding rows"
+            } else {
+                "the proving overhead is small"
+            };
+            warn!(
+                "Execution trace was padded from {} to {} steps ({}% of the trace are padding rows); {}",
+                trace_info.unpadded_length(),
+                trace_info.length(),
+                overhead,
+                advice
+            );
+        }
+    }
 
-    Ok(proof)
+    (air, pub_inputs_bytes)
+}
+
+/// Builder of the auxiliary trace segment used as a placeholder when no auxiliary segment is
+/// built; proofs for computations with an auxiliary segment cannot be generated with this builder.
+struct NoAuxSegment;
+
+impl<B: StarkField> AuxSegmentBuilder<B> for NoAuxSegment {
+    fn build_aux_segment<E: FieldElement<BaseField = B>>(
+        &self,
+        _main_trace: &ExecutionTrace<B>,
+        _rand_elements: &[E],
+    ) -> Vec<Vec<E>> {
+        unreachable!("auxiliary trace segment cannot be built without a builder")
+    }
+}
+
+/// Notifies the `observer` that the specified `stage` is about to be executed, unless the
+/// observer requests cancellation, in which case [ProverError::ProvingCancelled] is returned.
+fn start_stage<O: ProvingObserver>(
+    observer: &mut O,
+    stage: ProvingStage,
+) -> Result<(), ProverError> {
+    if observer.is_cancelled() {
+        return Err(ProverError::ProvingCancelled(stage));
+    }
+    observer.stage_started(stage);
+    Ok(())
+}
+
+/// Returns an execution trace in which the specified traces are placed one below another.
+///
+/// # Panics
+/// Panics if the traces have different widths, lengths, or metadata, or if any of them contains
+/// padding rows.
+fn stack_traces<B: StarkField>(traces: Vec<ExecutionTrace<B>>) -> ExecutionTrace<B> {
+    let first = &traces[0];
+    for trace in traces.iter() {
+        assert!(
+            trace.width() == first.width() && trace.length() == first.length(),
+            "all stacked traces must have the same width and length"
+        );
+        assert!(
+            trace.get_meta() == first.get_meta(),
+            "all stacked traces must have the same metadata"
+        );
+        assert_eq!(
+            trace.num_padding_rows(),
+            0,
+            "stacked traces cannot contain padding rows"
+        );
+    }
+
+    let registers = (0..first.width())
+        .map(|i| {
+            traces
+                .iter()
+                .flat_map(|trace| trace.get_register(i).iter().copied())
+                .collect()
+        })
+        .collect();
+    let mut result = ExecutionTrace::init(registers);
+    result.set_meta(first.get_meta().to_vec());
+    result
+}
+
+/// Executes the provided proof generation procedure and converts any panic raised during its
+/// execution into [ProverError::InternalPanic].
+#[cfg(feature = "std")]
+fn catch_panics<F>(f: F) -> Result<StarkProof, ProverError>
+where
+    F: FnOnce() -> Result<StarkProof, ProverError>,
+{
+    std::panic::catch_unwind(std::panic::AssertUnwindSafe(f)).unwrap_or_else(|payload| {
+        let message = if let Some(message) = payload.downcast_ref::<&str>() {
+            message.to_string()
+        } else if let Some(message) = payload.downcast_ref::<String>() {
+            message.clone()
+        } else {
+            "unknown panic".to_string()
+        };
+        Err(ProverError::InternalPanic(message))
+    })
+}
+
+/// Executes the provided proof generation procedure; panics cannot be caught in `no_std`
+/// environments and thus are propagated to the caller.
+#[cfg(not(feature = "std"))]
+fn catch_panics<F>(f: F) -> Result<StarkProof, ProverError>
+where
+    F: FnOnce() -> Result<StarkProof, ProverError>,
+{
+    f()
 }
@@ -0,0 +1,80 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use core::fmt;
+
+// PROVING STAGE
+// ================================================================================================
+
+/// Stages of proof generation reported to a [ProvingObserver].
+///
+/// The stages are listed in the order in which they are executed by the prover.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProvingStage {
+    /// Low-degree extension of the execution trace and commitment to the extended trace.
+    TraceCommitment,
+    /// Construction, extension, and commitment to the auxiliary trace segment; this stage is
+    /// executed only for computations with an auxiliary trace segment.
+    AuxTraceCommitment,
+    /// Evaluation of constraints over the constraint evaluation domain and commitment to the
+    /// constraint composition polynomial.
+    ConstraintEvaluation,
+    /// Evaluation of trace and constraint composition polynomials at an out-of-domain point.
+    OodEvaluation,
+    /// Construction of the DEEP composition polynomial and commitment to all FRI layers.
+    FriCommitment,
+    /// Proof-of-work grinding and generation of query positions.
+    QueryGeneration,
+    /// Construction of query decommitments and of the proof object.
+    ProofAssembly,
+}
+
+impl fmt::Display for ProvingStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TraceCommitment => write!(f, "trace commitment"),
+            Self::AuxTraceCommitment => write!(f, "auxiliary trace commitment"),
+            Self::ConstraintEvaluation => write!(f, "constraint evaluation"),
+            Self::OodEvaluation => write!(f, "out-of-domain evaluation"),
+            Self::FriCommitment => write!(f, "FRI commitment"),
+            Self::QueryGeneration => write!(f, "query generation"),
+            Self::ProofAssembly => write!(f, "proof assembly"),
+        }
+    }
+}
+
+// PROVING OBSERVER
+// ================================================================================================
+
+/// Receives progress notifications from the prover and controls cancellation of proof
+/// generation.
+///
+/// An observer is passed to [prove_with_observer()](crate::prove_with_observer). Before each
+/// [ProvingStage], the prover checks [is_cancelled()](ProvingObserver::is_cancelled) and, if
+/// the observer requests cancellation, stops with
+/// [ProverError::ProvingCancelled](crate::ProverError::ProvingCancelled). Otherwise, the prover
+/// invokes [stage_started()](ProvingObserver::stage_started) before executing the stage, and
+/// [stage_completed()](ProvingObserver::stage_completed) after the stage is executed.
+///
+/// Cancellation is checked only between stages, and thus, a cancelled prover stops once the
+/// currently executing stage is completed. For a cancellation token shared with another thread,
+/// [is_cancelled()](ProvingObserver::is_cancelled) can read an atomic flag.
+///
+/// All methods have default implementations which do nothing and never request cancellation.
+pub trait ProvingObserver {
+    /// Invoked by the prover right before the specified `stage` is executed.
+    fn stage_started(&mut self, _stage: ProvingStage) {}
+
+    /// Invoked by the prover right after the specified `stage` is executed.
+    fn stage_completed(&mut self, _stage: ProvingStage) {}
+
+    /// Returns true if proof generation should be stopped before the next stage.
+    fn is_cancelled(&self) -> bool {
+        false
+    }
+}
+
+/// An observer which ignores all notifications and never requests cancellation.
+impl ProvingObserver for () {}
@@ -0,0 +1,193 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{ProvingObserver, ProvingStage};
+use air::proof::StarkProof;
+use core::fmt;
+use std::time::{Duration, Instant};
+use utils::{CountingWriter, Serializable};
+
+// PROOF STATS
+// ================================================================================================
+
+/// A report describing the time spent on each stage of proof generation and the sizes of the
+/// components of the resulting proof.
+///
+/// The report is returned from [prove_with_stats()](crate::prove_with_stats), and is intended
+/// for tuning [ProofOptions](air::ProofOptions): e.g., increasing the blowup factor reduces the
+/// number of queries needed for a given security level, and thus, the size of the query
+/// components, at the expense of longer trace commitment and constraint evaluation stages.
+///
+/// Stage times are listed in the order in which the stages are executed; stages which are not
+/// executed for the computation (e.g., [ProvingStage::AuxTraceCommitment]) are not listed.
+/// Component sizes are listed in the order in which the components are serialized, and add up
+/// to the size of the serialized proof.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProofStats {
+    stage_times: Vec<(ProvingStage, Duration)>,
+    component_sizes: Vec<(&'static str, usize)>,
+}
+
+impl ProofStats {
+    /// Returns stats for the specified stage times and the generated `proof`.
+    pub(crate) fn new(stage_times: Vec<(ProvingStage, Duration)>, proof: &StarkProof) -> Self {
+        let mut component_sizes = vec![
+            ("version", 1),
+            ("context", size_of(&proof.context)),
+            ("commitments", size_of(&proof.commitments)),
+            ("trace_queries", size_of(&proof.trace_queries)),
+        ];
+        if let Some(aux_trace_queries) = &proof.aux_trace_queries {
+            component_sizes.push(("aux_trace_queries", size_of(aux_trace_queries)));
+        }
+        component_sizes.push(("constraint_queries", size_of(&proof.constraint_queries)));
+        component_sizes.push(("ood_frame", size_of(&proof.ood_frame)));
+        component_sizes.push(("fri_proof", size_of(&proof.fri_proof)));
+        component_sizes.push(("pow_nonce", 8));
+
+        ProofStats {
+            stage_times,
+            component_sizes,
+        }
+    }
+
+    /// Returns the time spent on each of the executed proving stages.
+    pub fn stage_times(&self) -> &[(ProvingStage, Duration)] {
+        &self.stage_times
+    }
+
+    /// Returns the time spent on the specified proving `stage`, or None if the stage was not
+    /// executed.
+    pub fn stage_time(&self, stage: ProvingStage) -> Option<Duration> {
+        self.stage_times
+            .iter()
+            .find(|(s, _)| *s == stage)
+            .map(|&(_, time)| time)
+    }
+
+    /// Returns the total time spent on all proving stages.
+    pub fn total_time(&self) -> Duration {
+        self.stage_times.iter().map(|&(_, time)| time).sum()
+    }
+
+    /// Returns the name and the size in bytes of each component of the serialized proof.
+    ///
+    /// The names are the same as the names of the fields returned by `StarkProof::to_json()`,
+    /// plus `version` for the proof format version byte.
+    pub fn component_sizes(&self) -> &[(&'static str, usize)] {
+        &self.component_sizes
+    }
+
+    /// Returns the size of the serialized proof in bytes.
+    pub fn proof_size(&self) -> usize {
+        self.component_sizes.iter().map(|&(_, size)| size).sum()
+    }
+}
+
+impl fmt::Display for ProofStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "proof generated in {} ms", self.total_time().as_millis())?;
+        for (stage, time) in self.stage_times.iter() {
+            write!(f, "\n  - {}: {} ms", stage, time.as_millis())?;
+        }
+        write!(f, "\nproof size: {} bytes", self.proof_size())?;
+        for (name, size) in self.component_sizes.iter() {
+            write!(f, "\n  - {}: {} bytes", name, size)?;
+        }
+        Ok(())
+    }
+}
+
+// STATS OBSERVER
+// ================================================================================================
+
+/// A proving observer which records the time spent on each proving stage.
+#[derive(Default)]
+pub(crate) struct StatsObserver {
+    started: Option<Instant>,
+    stage_times: Vec<(ProvingStage, Duration)>,
+}
+
+impl StatsObserver {
+    /// Returns the recorded stage times.
+    pub fn into_stage_times(self) -> Vec<(ProvingStage, Duration)> {
+        self.stage_times
+    }
+}
+
+impl ProvingObserver for StatsObserver {
+    fn stage_started(&mut self, _stage: ProvingStage) {
+        self.started = Some(Instant::now());
+    }
+
+    fn stage_completed(&mut self, stage: ProvingStage) {
+        if let Some(started) = self.started.take() {
+            self.stage_times.push((stage, started.elapsed()));
+        }
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn size_of<T: Serializable>(component: &T) -> usize {
+    let mut writer = CountingWriter::new();
+    component.write_into(&mut writer);
+    writer.bytes_written()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        tests::{build_fib_trace, build_proof_options, FibAir},
+        ProvingStage,
+    };
+    use math::fields::f128::BaseElement;
+    use utils::Serializable;
+
+    #[test]
+    fn proof_stats() {
+        let trace = build_fib_trace(64);
+        let result = trace.get(1, trace.length() - 1);
+        let (proof, stats) = crate::prove_with_stats::<FibAir<BaseElement>>(
+            trace.clone(),
+            result,
+            build_proof_options(),
+        )
+        .unwrap();
+        let expected = crate::prove::<FibAir<BaseElement>>(trace, result, build_proof_options());
+        assert_eq!(expected.unwrap(), proof);
+
+        // all stages except for the auxiliary trace commitment are executed in order
+        let stages = stats
+            .stage_times()
+            .iter()
+            .map(|&(stage, _)| stage)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                ProvingStage::TraceCommitment,
+                ProvingStage::ConstraintEvaluation,
+                ProvingStage::OodEvaluation,
+                ProvingStage::FriCommitment,
+                ProvingStage::QueryGeneration,
+                ProvingStage::ProofAssembly,
+            ],
+            stages
+        );
+        assert_eq!(None, stats.stage_time(ProvingStage::AuxTraceCommitment));
+        assert!(stats.stage_time(ProvingStage::TraceCommitment).unwrap() <= stats.total_time());
+
+        // component sizes add up to the size of the serialized proof
+        assert_eq!(proof.to_bytes().len(), stats.proof_size());
+        let fri_size = stats
+            .component_sizes()
+            .iter()
+            .find(|(name, _)| *name == "fri_proof")
+            .map(|&(_, size)| size);
+        assert_eq!(Some(proof.fri_proof.to_bytes().len()), fri_size);
+        assert!(stats.to_string().starts_with("proof generated in"));
+    }
+}
@@ -0,0 +1,25 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{build_fib_trace, build_proof_options, TestFibAir, TestFibInputs};
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+// TESTS
+// ================================================================================================
+
+#[test]
+fn assertions_requested_once_per_proof() {
+    static NUM_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+    let trace = build_fib_trace(16);
+    let result = trace.get(1, trace.length() - 1);
+    let inputs = TestFibInputs::new(result).with_assertion_counter(&NUM_CALLS);
+
+    let proof = crate::prove::<TestFibAir>(trace, inputs.clone(), build_proof_options()).unwrap();
+    assert_eq!(1, NUM_CALLS.load(Ordering::SeqCst));
+
+    assert!(verifier::verify::<TestFibAir>(proof, inputs).is_ok());
+    assert_eq!(2, NUM_CALLS.load(Ordering::SeqCst));
+}
@@ -0,0 +1,125 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    build_fib_trace, build_proof_options, AccumulatorSegmentBuilder, FibAir, TestFibAir,
+    TestFibInputs,
+};
+use crate::{AuxSegmentBuilder, ExecutionTrace, ProverError};
+use air::{proof::StarkProof, FieldExtension, HashFunction, ProofOptions};
+use math::{fields::f128::BaseElement, FieldElement};
+use utils::collections::Vec;
+use verifier::VerifierError;
+
+// TESTS
+// ================================================================================================
+
+#[test]
+fn prove_with_aux_segment() {
+    let trace = build_fib_trace(32);
+    let result = trace.get(1, trace.length() - 1);
+    let inputs = TestFibInputs::new(result).with_accumulator();
+    let options = ProofOptions::new(
+        36,
+        8,
+        0,
+        HashFunction::Blake3_256,
+        FieldExtension::Quadratic,
+        4,
+        256,
+    );
+    let proof = crate::prove_with_aux_segment::<TestFibAir, _>(
+        trace,
+        inputs.clone(),
+        options,
+        AccumulatorSegmentBuilder,
+    )
+    .unwrap();
+    assert_eq!(1, proof.context.aux_trace_width());
+    assert!(proof.aux_trace_queries.is_some());
+
+    // the proof survives serialization and fails against an AIR without the auxiliary segment
+    let proof = StarkProof::from_bytes(&proof.to_bytes()).unwrap();
+    assert!(verifier::verify::<FibAir<BaseElement>>(proof.clone(), result).is_err());
+    verifier::verify::<TestFibAir>(proof.clone(), inputs.clone()).unwrap();
+    let wrong_inputs = TestFibInputs::new(result + BaseElement::ONE).with_accumulator();
+    assert!(verifier::verify::<TestFibAir>(proof.clone(), wrong_inputs).is_err());
+
+    // proofs with an auxiliary trace segment survive a JSON round trip as well
+    let parsed = StarkProof::from_json(&proof.to_json()).unwrap();
+    verifier::verify::<TestFibAir>(parsed, inputs).unwrap();
+}
+
+#[test]
+fn prove_with_mismatched_aux_segment_width() {
+    let trace = build_fib_trace(32);
+    let result = trace.get(1, trace.length() - 1);
+    let inputs = TestFibInputs::new(result).with_accumulator();
+    assert_eq!(
+        Err(ProverError::MismatchedAuxiliaryTraceWidth(1, 2)),
+        crate::prove_with_aux_segment::<TestFibAir, _>(
+            trace,
+            inputs,
+            build_proof_options(),
+            MainSegmentCopier,
+        )
+        .map(|_| ())
+    );
+}
+
+#[test]
+fn prove_without_aux_transition_evaluator() {
+    let trace = build_fib_trace(32);
+    let result = trace.get(1, trace.length() - 1);
+    let inputs = TestFibInputs::new(result).with_accumulator();
+    let unevaluated_inputs = inputs.clone().without_aux_transition_evaluator();
+    assert_eq!(
+        Err(ProverError::MissingAuxTransitionEvaluator),
+        crate::prove_with_aux_segment::<TestFibAir, _>(
+            trace.clone(),
+            unevaluated_inputs.clone(),
+            build_proof_options(),
+            AccumulatorSegmentBuilder,
+        )
+        .map(|_| ())
+    );
+
+    let proof = crate::prove_with_aux_segment::<TestFibAir, _>(
+        trace,
+        inputs,
+        build_proof_options(),
+        AccumulatorSegmentBuilder,
+    )
+    .unwrap();
+    assert_eq!(
+        Err(VerifierError::MissingAuxTransitionEvaluator),
+        verifier::verify::<TestFibAir>(proof, unevaluated_inputs)
+    );
+}
+
+// MAIN SEGMENT COPIER
+// ================================================================================================
+
+/// Builds an auxiliary segment which is a copy of the main segment; this is inconsistent with
+/// the width of the auxiliary segment declared by [TestFibAir] with an accumulator column.
+struct MainSegmentCopier;
+
+impl AuxSegmentBuilder<BaseElement> for MainSegmentCopier {
+    fn build_aux_segment<E: FieldElement<BaseField = BaseElement>>(
+        &self,
+        main_trace: &ExecutionTrace<BaseElement>,
+        _rand_elements: &[E],
+    ) -> Vec<Vec<E>> {
+        (0..main_trace.width())
+            .map(|i| {
+                main_trace
+                    .get_register(i)
+                    .iter()
+                    .map(|&v| E::from(v))
+                    .collect()
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,49 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{build_fib_trace, build_proof_options, TestFibAir, TestFibInputs};
+use math::{fft, fields::f128::BaseElement, log2, polynom, FieldElement, StarkField};
+
+// TESTS
+// ================================================================================================
+
+#[test]
+fn prove_with_domain_point_constraint() {
+    let trace = build_fib_trace(32);
+    let result = trace.get(1, trace.length() - 1);
+
+    // the point is a root of unity of a domain twice as large as the trace domain, and thus, it
+    // does not correspond to any step of the execution trace
+    let point = BaseElement::get_root_of_unity(log2(trace.length() * 2));
+    let mut poly = trace.get_register(0).to_vec();
+    fft::interpolate_poly(&mut poly, &fft::get_inv_twiddles(trace.length()));
+    let value = polynom::eval(&poly, point);
+
+    let inputs = TestFibInputs::new(result).with_domain_point_constraint(point, value);
+    let proof = crate::prove::<TestFibAir>(trace, inputs.clone(), build_proof_options()).unwrap();
+    assert!(verifier::verify::<TestFibAir>(proof.clone(), inputs).is_ok());
+
+    let wrong_inputs =
+        TestFibInputs::new(result).with_domain_point_constraint(point, value + BaseElement::ONE);
+    assert!(verifier::verify::<TestFibAir>(proof, wrong_inputs).is_err());
+}
+
+#[test]
+fn prove_with_linear_combination_constraint() {
+    let trace = build_fib_trace(32);
+    let result = trace.get(1, trace.length() - 1);
+
+    // the constraint binds 2 * s_0 + s_1 at the last step of the trace
+    let last_step = trace.length() - 1;
+    let value = BaseElement::new(2) * trace.get(0, last_step) + trace.get(1, last_step);
+
+    let inputs = TestFibInputs::new(result).with_linear_combination_constraint(value);
+    let proof = crate::prove::<TestFibAir>(trace, inputs.clone(), build_proof_options()).unwrap();
+    assert!(verifier::verify::<TestFibAir>(proof.clone(), inputs).is_ok());
+
+    let wrong_inputs =
+        TestFibInputs::new(result).with_linear_combination_constraint(value + BaseElement::ONE);
+    assert!(verifier::verify::<TestFibAir>(proof, wrong_inputs).is_err());
+}
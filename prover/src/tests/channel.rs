@@ -0,0 +1,195 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{build_fib_trace, build_proof_options, FibAir, TestFibAir, TestFibInputs};
+use air::{proof::StarkProof, FieldExtension, HashFunction, ProofOptions};
+use crypto::{hashers::Blake3_256, Hasher, PublicCoin, RandomCoin, RandomCoinError};
+use math::{fields::f128::BaseElement, FieldElement};
+use utils::collections::Vec;
+use verifier::VerifierError;
+
+// TESTS
+// ================================================================================================
+
+#[test]
+fn prove_with_channel_seed() {
+    let trace = build_fib_trace(16);
+    let result = trace.get(1, trace.length() - 1);
+    let inputs = |seed: u8| {
+        let mut channel_seed = [0; 32];
+        channel_seed[0] = seed;
+        TestFibInputs::new(result).with_channel_seed(channel_seed)
+    };
+    let prove = |seed: u8| -> StarkProof {
+        crate::prove::<TestFibAir>(trace.clone(), inputs(seed), build_proof_options()).unwrap()
+    };
+
+    // an all-zero seed does not change the transcript
+    let expected =
+        crate::prove::<FibAir<BaseElement>>(trace.clone(), result, build_proof_options()).unwrap();
+    assert_eq!(expected.to_bytes(), prove(0).to_bytes());
+
+    // different seeds produce different transcripts for the same trace: the trace commitment is
+    // the same, but all challenges drawn afterwards differ
+    let proof1 = prove(1);
+    let proof2 = prove(2);
+    let num_fri_layers = proof1
+        .options()
+        .to_fri_options::<BaseElement>()
+        .num_fri_layers(proof1.lde_domain_size());
+    let (trace_root1, constraint_root1, _) = proof1
+        .commitments
+        .clone()
+        .parse::<Blake3_256<BaseElement>>(num_fri_layers)
+        .unwrap();
+    let (trace_root2, constraint_root2, _) = proof2
+        .commitments
+        .clone()
+        .parse::<Blake3_256<BaseElement>>(num_fri_layers)
+        .unwrap();
+    assert_eq!(trace_root1, trace_root2);
+    assert_ne!(constraint_root1, constraint_root2);
+    assert_ne!(proof1.ood_frame, proof2.ood_frame);
+
+    // the verifier absorbs the seed of the AIR, and thus, proofs are accepted only by the AIR
+    // they were generated for
+    assert!(verifier::verify::<TestFibAir>(proof1.clone(), inputs(1)).is_ok());
+    assert!(verifier::verify::<TestFibAir>(proof2, inputs(2)).is_ok());
+    assert!(verifier::verify::<TestFibAir>(proof1.clone(), inputs(2)).is_err());
+    assert!(verifier::verify::<FibAir<BaseElement>>(proof1, result).is_err());
+}
+
+#[test]
+fn prove_with_custom_public_coin() {
+    for &extension in [FieldExtension::None, FieldExtension::Quadratic].iter() {
+        let trace = build_fib_trace(32);
+        let result = trace.get(1, trace.length() - 1);
+        let options = ProofOptions::new(36, 8, 0, HashFunction::Blake3_256, extension, 4, 256);
+        let proof = crate::prove_with_coin::<FibAir<BaseElement>, LabeledCoin>(
+            trace.clone(),
+            result,
+            options.clone(),
+        )
+        .unwrap();
+        verifier::verify_with_coin::<FibAir<BaseElement>, LabeledCoin>(proof.clone(), result)
+            .unwrap();
+        assert!(
+            verifier::verify_with_coin::<FibAir<BaseElement>, LabeledCoin>(
+                proof.clone(),
+                result + BaseElement::ONE
+            )
+            .is_err()
+        );
+
+        // the default verifier draws different challenges, and thus, rejects the proof
+        assert!(verifier::verify::<FibAir<BaseElement>>(proof, result).is_err());
+
+        // the default coin produces the same transcript as the default prover and verifier
+        let proof =
+            crate::prove::<FibAir<BaseElement>>(trace.clone(), result, options.clone()).unwrap();
+        let coin_proof =
+            crate::prove_with_coin::<FibAir<BaseElement>, DefaultCoin>(trace, result, options)
+                .unwrap();
+        assert_eq!(proof, coin_proof);
+        verifier::verify_with_coin::<FibAir<BaseElement>, DefaultCoin>(proof, result).unwrap();
+    }
+}
+
+#[test]
+fn prove_with_public_coin_hasher_mismatch() {
+    // the proof is committed to using the hasher of the coin, but its options specify a
+    // different hash function
+    let trace = build_fib_trace(32);
+    let result = trace.get(1, trace.length() - 1);
+    let options = ProofOptions::new(
+        36,
+        8,
+        0,
+        HashFunction::Sha3_256,
+        FieldExtension::None,
+        4,
+        256,
+    );
+    let proof =
+        crate::prove_with_coin::<FibAir<BaseElement>, DefaultCoin>(trace, result, options).unwrap();
+    assert_eq!(
+        Err(VerifierError::InconsistentHashFunction(
+            HashFunction::Sha3_256
+        )),
+        verifier::verify_with_coin::<FibAir<BaseElement>, DefaultCoin>(proof, result)
+    );
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn prove_in_concurrent_sessions() {
+    // run two independent proving sessions for different inputs at the same time
+    let handles = [32, 64]
+        .iter()
+        .map(|&length| {
+            std::thread::spawn(move || {
+                let trace = build_fib_trace(length);
+                let result = trace.get(1, trace.length() - 1);
+                let proof =
+                    crate::prove::<FibAir<BaseElement>>(trace, result, build_proof_options())
+                        .unwrap();
+                verifier::verify::<FibAir<BaseElement>>(proof, result)
+            })
+        })
+        .collect::<Vec<_>>();
+
+    for handle in handles {
+        let result = handle.join().expect("proving thread panicked");
+        assert!(result.is_ok());
+    }
+}
+
+// LABELED PUBLIC COIN
+// ================================================================================================
+
+type DefaultCoin = RandomCoin<BaseElement, Blake3_256<BaseElement>>;
+
+/// A public coin which prefixes its seed with a label before instantiating the default coin; all
+/// other operations are delegated to the default coin.
+struct LabeledCoin(DefaultCoin);
+
+impl PublicCoin for LabeledCoin {
+    type BaseField = BaseElement;
+    type Hasher = Blake3_256<BaseElement>;
+
+    fn new(seed: &[u8]) -> Self {
+        let mut labeled_seed = b"labeled coin".to_vec();
+        labeled_seed.extend_from_slice(seed);
+        LabeledCoin(RandomCoin::new(&labeled_seed))
+    }
+
+    fn reseed_with_tag(&mut self, tag: &[u8; 4], data: <Self::Hasher as Hasher>::Digest) {
+        self.0.reseed_with_tag(tag, data)
+    }
+
+    fn reseed_with_int(&mut self, value: u64) {
+        self.0.reseed_with_int(value)
+    }
+
+    fn leading_zeros(&self) -> u32 {
+        self.0.leading_zeros()
+    }
+
+    fn check_leading_zeros(&self, value: u64) -> u32 {
+        self.0.check_leading_zeros(value)
+    }
+
+    fn draw<E: FieldElement<BaseField = BaseElement>>(&mut self) -> Result<E, RandomCoinError> {
+        self.0.draw()
+    }
+
+    fn draw_integers(
+        &mut self,
+        num_values: usize,
+        domain_size: usize,
+    ) -> Result<Vec<usize>, RandomCoinError> {
+        self.0.draw_integers(num_values, domain_size)
+    }
+}
@@ -0,0 +1,58 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{build_fib_trace, build_proof_options, FibAir};
+use air::{FieldExtension, HashFunction, ProofOptions};
+use math::fields::f128::BaseElement;
+
+// TESTS
+// ================================================================================================
+
+#[test]
+fn prove_in_thread_pool() {
+    // proofs generated in a dedicated thread pool are the same as proofs generated in the global
+    // thread pool
+    let trace = build_fib_trace(128);
+    let result = trace.get(1, trace.length() - 1);
+    let prove = || {
+        crate::prove::<FibAir<BaseElement>>(trace.clone(), result, build_proof_options()).unwrap()
+    };
+    let pool = crate::rayon::ThreadPoolBuilder::new()
+        .num_threads(2)
+        .build()
+        .unwrap();
+    let proof = pool.install(|| {
+        assert_eq!(2, crate::iterators::rayon_num_threads());
+        prove()
+    });
+    assert_eq!(prove(), proof);
+}
+
+#[test]
+fn prove_with_deterministic_grinding() {
+    // the proof-of-work nonce found by multiple threads is the same as the nonce found by a
+    // single thread
+    let trace = build_fib_trace(128);
+    let result = trace.get(1, trace.length() - 1);
+    let options = ProofOptions::new(
+        36,
+        8,
+        12,
+        HashFunction::Blake3_256,
+        FieldExtension::None,
+        4,
+        256,
+    );
+    let prove_in_pool = |num_threads| {
+        crate::rayon::ThreadPoolBuilder::new()
+            .num_threads(num_threads)
+            .build()
+            .unwrap()
+            .install(|| {
+                crate::prove::<FibAir<BaseElement>>(trace.clone(), result, options.clone()).unwrap()
+            })
+    };
+    assert_eq!(prove_in_pool(1), prove_in_pool(4));
+}
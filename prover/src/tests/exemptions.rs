@@ -0,0 +1,37 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{build_fib_trace, build_proof_options, TestFibAir, TestFibInputs};
+use math::{fields::f128::BaseElement, FieldElement};
+
+// TESTS
+// ================================================================================================
+
+#[test]
+fn prove_with_transition_constraint_exemptions() {
+    // the second register is overwritten at the last step of the trace, and thus, the second
+    // transition constraint is not satisfied on the second to last step
+    let mut trace = build_fib_trace(32);
+    let last_step = trace.length() - 1;
+    trace.set(1, last_step, BaseElement::new(42));
+    let result = trace.get(1, last_step);
+
+    let inputs = TestFibInputs::new(result).with_exemptions([1, 2]);
+    let proof =
+        crate::prove::<TestFibAir>(trace.clone(), inputs.clone(), build_proof_options()).unwrap();
+    assert!(verifier::verify::<TestFibAir>(proof.clone(), inputs).is_ok());
+    let wrong_inputs = TestFibInputs::new(result + BaseElement::ONE).with_exemptions([1, 2]);
+    assert!(verifier::verify::<TestFibAir>(proof, wrong_inputs).is_err());
+
+    // the constraint is enforced on the second to last step unless it is exempted from it
+    #[cfg(debug_assertions)]
+    {
+        let inputs = TestFibInputs::new(result);
+        assert_eq!(
+            Err(crate::ProverError::UnsatisfiedTransitionConstraintError(14)),
+            crate::prove::<TestFibAir>(trace, inputs, build_proof_options()).map(|_| ())
+        );
+    }
+}
@@ -0,0 +1,61 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{build_fib_trace, build_proof_options, FibAir, TestFibAir, TestFibInputs};
+use air::{proof::StarkProof, Air, FieldExtension, HashFunction, ProofOptions};
+use math::{fields::f128::BaseElement, FieldElement};
+
+// TESTS
+// ================================================================================================
+
+#[test]
+fn prove_with_multi_row_frame() {
+    let trace = build_fib_trace(64);
+    let result = trace.get(1, trace.length() - 1);
+    let inputs = TestFibInputs::new(result).with_frame_size(3);
+
+    for &extension in [FieldExtension::None, FieldExtension::Quadratic].iter() {
+        let options = ProofOptions::new(36, 8, 0, HashFunction::Blake3_256, extension, 4, 256);
+        let proof =
+            crate::prove::<TestFibAir>(trace.clone(), inputs.clone(), options.clone()).unwrap();
+        assert_eq!(3, proof.ood_frame.num_trace_rows());
+        assert!(verifier::verify::<TestFibAir>(proof.clone(), inputs.clone()).is_ok());
+        let wrong_inputs = TestFibInputs::new(result + BaseElement::ONE).with_frame_size(3);
+        assert!(verifier::verify::<TestFibAir>(proof.clone(), wrong_inputs).is_err());
+
+        // serialized multi-row frames survive a round trip
+        let parsed = StarkProof::from_bytes(&proof.to_bytes()).unwrap();
+        assert!(verifier::verify::<TestFibAir>(parsed, inputs.clone()).is_ok());
+
+        // combining linear constraints over all rows of the frame must not change the proof
+        let linear_inputs = inputs.clone().with_linear_constraints();
+        let linear = crate::prove::<TestFibAir>(trace.clone(), linear_inputs, options).unwrap();
+        assert_eq!(proof.to_bytes(), linear.to_bytes());
+    }
+
+    // frames starting at the last two steps wrap around, and thus, are exempted
+    let air = TestFibAir::new(trace.get_info(), inputs.clone(), build_proof_options());
+    assert_eq!(3, air.frame_size());
+    assert_eq!(2, air.context().num_transition_exemptions());
+
+    // a proof with a two-row out-of-domain frame is rejected
+    let proof =
+        crate::prove::<FibAir<BaseElement>>(trace.clone(), result, build_proof_options()).unwrap();
+    assert!(matches!(
+        verifier::verify::<TestFibAir>(proof, inputs.clone()),
+        Err(verifier::VerifierError::ProofDeserializationError(_))
+    ));
+
+    // a violated constraint is detected at the first step of the violating frame
+    #[cfg(debug_assertions)]
+    {
+        let mut trace = trace;
+        trace.set(0, 10, BaseElement::ZERO);
+        assert_eq!(
+            Err(crate::ProverError::UnsatisfiedTransitionConstraintError(8)),
+            crate::prove::<TestFibAir>(trace, inputs, build_proof_options()).map(|_| ())
+        );
+    }
+}
@@ -0,0 +1,46 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{build_fib_trace, build_proof_options, FibAir};
+use crate::ExecutionTrace;
+use math::fields::{f128::BaseElement, instrumented::InstrumentedField};
+
+type Element = InstrumentedField<BaseElement>;
+
+// TESTS
+// ================================================================================================
+
+#[test]
+fn prove_instrumented() {
+    let trace = build_fib_trace(32);
+    let result = trace.get(1, trace.length() - 1);
+    let proof =
+        crate::prove::<FibAir<BaseElement>>(trace.clone(), result, build_proof_options()).unwrap();
+
+    // the same trace lifted into the instrumented field
+    let registers = (0..trace.width())
+        .map(|i| {
+            trace
+                .get_register(i)
+                .iter()
+                .map(|&value| Element::new(value))
+                .collect()
+        })
+        .collect();
+    let instrumented_trace = ExecutionTrace::init(registers);
+    let (instrumented_proof, stats) = crate::prove_instrumented::<FibAir<Element>, _>(
+        instrumented_trace,
+        Element::new(result),
+        build_proof_options(),
+    )
+    .unwrap();
+    assert!(stats.additions > 0);
+    assert!(stats.multiplications > 0);
+    assert!(stats.inversions > 0);
+
+    // instrumentation does not affect the proof
+    assert_eq!(proof.to_bytes(), instrumented_proof.to_bytes());
+    assert!(verifier::verify::<FibAir<Element>>(instrumented_proof, Element::new(result)).is_ok());
+}
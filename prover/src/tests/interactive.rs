@@ -0,0 +1,236 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{build_fib_trace, build_proof_options, FibAir};
+use crate::{CompositionCommitment, ConstraintCommitment, InteractiveProver, QuerySet};
+use air::{
+    proof::{
+        Commitments, Context, OodFrame, Queries, StarkProof, CONSTRAINT_COMMITMENT_TAG,
+        OOD_EVALUATIONS_TAG, OOD_FRAME_TAG, TRACE_COMMITMENT_TAG,
+    },
+    Air,
+};
+use core::sync::atomic::{AtomicUsize, Ordering};
+use crypto::{hashers::Blake3_256, ElementHasher, Hasher, RandomCoin};
+use math::{fft, fields::f128::BaseElement};
+use utils::{collections::Vec, Serializable};
+
+type Blake3 = Blake3_256<BaseElement>;
+type Digest = <Blake3 as Hasher>::Digest;
+
+// TESTS
+// ================================================================================================
+
+#[test]
+fn prove_interactively_with_fiat_shamir_verifier() {
+    let trace = build_fib_trace(32);
+    let result = trace.get(1, trace.length() - 1);
+
+    // the external verifier draws challenges in the same way as the prove() function, and thus,
+    // the resulting proof is the same as the one generated non-interactively
+    let proof = prove_interactively::<ConstraintCommitment<BaseElement, Blake3>>(32);
+    let expected = crate::prove::<FibAir<BaseElement>>(trace, result, build_proof_options());
+    assert_eq!(expected.unwrap().to_bytes(), proof.to_bytes());
+    assert!(verifier::verify::<FibAir<BaseElement>>(proof, result).is_ok());
+}
+
+#[test]
+fn prove_interactively_with_custom_composition_commitment() {
+    let trace = build_fib_trace(32);
+    let result = trace.get(1, trace.length() - 1);
+    let options = build_proof_options();
+
+    // a custom commitment strategy is used to commit to and to open the composition polynomial
+    let proof = prove_interactively::<RecordingCommitment>(32);
+    assert_eq!(
+        options.num_queries(),
+        NUM_OPENED_POSITIONS.load(Ordering::SeqCst)
+    );
+
+    // the openings have the same shape as the ones of the default strategy, and thus, the proof
+    // is valid and the same as the one generated with the default strategy
+    let expected = crate::prove::<FibAir<BaseElement>>(trace, result, options);
+    assert_eq!(expected.unwrap().to_bytes(), proof.to_bytes());
+    assert!(verifier::verify::<FibAir<BaseElement>>(proof, result).is_ok());
+}
+
+#[test]
+fn composition_poly_evaluations_after_commitment() {
+    let trace = build_fib_trace(32);
+    let result = trace.get(1, trace.length() - 1);
+    let air = FibAir::new(trace.get_info(), result, build_proof_options());
+
+    let mut prover =
+        InteractiveProver::<_, BaseElement, Blake3, FiatShamirVerifier>::new(&air, trace).unwrap();
+    let mut verifier = FiatShamirVerifier::new(&air, result);
+    verifier.receive_commitment(&TRACE_COMMITMENT_TAG, prover.commit_trace());
+    let num_assertions = prover.num_boundary_constraints();
+    let coefficients = air
+        .get_constraint_composition_coefficients(&mut verifier.coin, num_assertions)
+        .unwrap();
+    prover.commit_composition(coefficients).unwrap();
+
+    // each column is evaluated over the entire LDE domain and is of degree smaller than the
+    // trace length
+    let evaluations = prover.composition_poly_evaluations();
+    assert_eq!(air.ce_blowup_factor(), evaluations.len());
+    let domain_offset = air.domain_offset();
+    for column in evaluations.iter() {
+        assert_eq!(air.lde_domain_size(), column.len());
+        assert!(fft::infer_degree(column, domain_offset) < air.trace_length());
+    }
+}
+
+// RECORDING COMPOSITION COMMITMENT
+// ================================================================================================
+
+/// Number of positions at which [RecordingCommitment] has been opened.
+static NUM_OPENED_POSITIONS: AtomicUsize = AtomicUsize::new(0);
+
+/// Same as the default composition commitment but records how many positions have been opened.
+struct RecordingCommitment(ConstraintCommitment<BaseElement, Blake3>);
+
+impl CompositionCommitment<BaseElement, Blake3> for RecordingCommitment {
+    fn commit(evaluations: Vec<Vec<BaseElement>>) -> (Digest, Self) {
+        let (root, commitment) = ConstraintCommitment::commit(evaluations);
+        (root, RecordingCommitment(commitment))
+    }
+
+    fn open(self, positions: &[usize]) -> Queries {
+        NUM_OPENED_POSITIONS.fetch_add(positions.len(), Ordering::SeqCst);
+        self.0.open(positions)
+    }
+}
+
+// FIAT-SHAMIR VERIFIER
+// ================================================================================================
+
+/// A verifier which interacts with an [InteractiveProver] by drawing challenges from a public
+/// coin reseeded with the messages of the prover; the messages are collected into a proof.
+struct FiatShamirVerifier {
+    coin: RandomCoin<BaseElement, Blake3>,
+    context: Context,
+    commitments: Commitments,
+    ood_frame: OodFrame,
+    trace_width: usize,
+    num_ood_evaluations: usize,
+    pow_nonce: u64,
+}
+
+impl FiatShamirVerifier {
+    fn new(air: &FibAir<BaseElement>, result: BaseElement) -> Self {
+        let context = Context::new::<BaseElement>(air.trace_info(), air.options().clone());
+        let mut coin_seed = result.to_bytes();
+        context.write_into(&mut coin_seed);
+        FiatShamirVerifier {
+            coin: RandomCoin::new(&coin_seed),
+            context,
+            commitments: Commitments::default(),
+            ood_frame: OodFrame::default(),
+            trace_width: air.trace_width(),
+            num_ood_evaluations: air.ce_blowup_factor(),
+            pow_nonce: 0,
+        }
+    }
+
+    fn receive_commitment(&mut self, tag: &[u8; 4], commitment: Digest) {
+        self.commitments.add::<Blake3>(&commitment);
+        self.coin.reseed_with_tag(tag, commitment);
+    }
+
+    fn receive_ood_frame(&mut self, ood_frame: OodFrame) {
+        let trace_z1: Vec<BaseElement> = ood_frame.parse_trace_z1(self.trace_width).unwrap();
+        let trace_z2: Vec<BaseElement> = ood_frame.parse_trace_z2(self.trace_width).unwrap();
+        let evaluations: Vec<BaseElement> = ood_frame
+            .parse_evaluations(self.num_ood_evaluations)
+            .unwrap();
+        self.coin
+            .reseed_with_tag(&OOD_FRAME_TAG, Blake3::hash_elements(&trace_z1));
+        self.coin
+            .reseed_with_tag(&OOD_FRAME_TAG, Blake3::hash_elements(&trace_z2));
+        self.coin
+            .reseed_with_tag(&OOD_EVALUATIONS_TAG, Blake3::hash_elements(&evaluations));
+        self.ood_frame = ood_frame;
+    }
+
+    fn draw_query_positions(&mut self) -> Vec<usize> {
+        let options = self.context.options().clone();
+        let coin = &self.coin;
+        self.pow_nonce = (1..u64::MAX)
+            .find(|&nonce| coin.check_leading_zeros(nonce) >= options.grinding_factor())
+            .unwrap();
+        self.coin.reseed_with_int(self.pow_nonce);
+        self.coin
+            .draw_integers(options.num_queries(), self.context.lde_domain_size())
+            .unwrap()
+    }
+
+    fn build_proof(self, queries: QuerySet) -> StarkProof {
+        StarkProof {
+            context: self.context,
+            commitments: self.commitments,
+            trace_queries: queries.trace_queries,
+            aux_trace_queries: queries.aux_trace_queries,
+            constraint_queries: queries.constraint_queries,
+            ood_frame: self.ood_frame,
+            fri_proof: queries.fri_proof,
+            pow_nonce: self.pow_nonce,
+            format_version: StarkProof::FORMAT_VERSION,
+        }
+    }
+}
+
+impl fri::ProverChannel<BaseElement> for FiatShamirVerifier {
+    type Hasher = Blake3;
+
+    fn commit_fri_layer(&mut self, layer_root: Digest) {
+        self.receive_commitment(&fri::FRI_LAYER_TAG, layer_root);
+    }
+
+    fn draw_fri_alpha(&mut self) -> BaseElement {
+        self.coin.draw().unwrap()
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Generates a proof of a Fibonacci computation of the specified length by running an
+/// [InteractiveProver] with the specified composition commitment strategy against a
+/// [FiatShamirVerifier].
+fn prove_interactively<K>(length: usize) -> StarkProof
+where
+    K: CompositionCommitment<BaseElement, Blake3>,
+{
+    let trace = build_fib_trace(length);
+    let result = trace.get(1, trace.length() - 1);
+    let air = FibAir::new(trace.get_info(), result, build_proof_options());
+
+    // run the prover round-by-round with the challenges drawn by an external verifier
+    let mut prover = InteractiveProver::<_, BaseElement, Blake3, _, K>::new(&air, trace).unwrap();
+    let mut verifier = FiatShamirVerifier::new(&air, result);
+
+    verifier.receive_commitment(&TRACE_COMMITMENT_TAG, prover.commit_trace());
+    let num_assertions = prover.num_boundary_constraints();
+    let coefficients = air
+        .get_constraint_composition_coefficients(&mut verifier.coin, num_assertions)
+        .unwrap();
+    verifier.receive_commitment(
+        &CONSTRAINT_COMMITMENT_TAG,
+        prover.commit_composition(coefficients).unwrap(),
+    );
+
+    let z = verifier.coin.draw().unwrap();
+    verifier.receive_ood_frame(prover.receive_ood_challenge(z));
+
+    let coefficients = air
+        .get_deep_composition_coefficients(&mut verifier.coin)
+        .unwrap();
+    prover.commit_fri_layers(coefficients, &mut verifier);
+
+    let positions = verifier.draw_query_positions();
+    let queries = prover.answer_queries(&positions);
+    verifier.build_proof(queries)
+}
@@ -0,0 +1,128 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{build_fib_trace, build_proof_options, FibAir, TestFibAir, TestFibInputs};
+use air::{proof::StarkProof, Air, TraceInfo};
+use math::{fields::f128::BaseElement, FieldElement};
+use serde_json::Value;
+use utils::string::ToString;
+
+// TESTS
+// ================================================================================================
+
+#[test]
+fn json_proof_encoding() {
+    let trace = build_fib_trace(32);
+    let result = trace.get(1, trace.length() - 1);
+    let proof = crate::prove::<FibAir<BaseElement>>(trace, result, build_proof_options()).unwrap();
+    let json = proof.to_json();
+    let parsed = StarkProof::from_json(&json).unwrap();
+    assert_eq!(proof, parsed);
+    verifier::verify::<FibAir<BaseElement>>(parsed, result).unwrap();
+
+    // concatenating the version byte and all components yields the binary encoding of the proof
+    let value: Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(u64::from(StarkProof::FORMAT_VERSION), value["version"]);
+    assert!(value["aux_trace_queries"].is_null());
+    let mut bytes = vec![StarkProof::FORMAT_VERSION];
+    for &name in [
+        "context",
+        "commitments",
+        "trace_queries",
+        "constraint_queries",
+        "ood_frame",
+        "fri_proof",
+        "pow_nonce",
+    ]
+    .iter()
+    {
+        let hex = value[name].as_str().unwrap();
+        bytes.extend_from_slice(&hex::decode(hex.strip_prefix("0x").unwrap()).unwrap());
+    }
+    assert_eq!(proof.to_bytes(), bytes);
+
+    // hex strings without the prefix and in upper case are accepted
+    let mut upper = value.clone();
+    for (_, field) in upper.as_object_mut().unwrap().iter_mut() {
+        if let Some(hex) = field.as_str() {
+            *field = hex[2..].to_uppercase().into();
+        }
+    }
+    assert_eq!(proof, StarkProof::from_json(&upper.to_string()).unwrap());
+}
+
+#[test]
+fn json_altered_proof() {
+    let trace = build_fib_trace(32);
+    let result = trace.get(1, trace.length() - 1);
+    let proof = crate::prove::<FibAir<BaseElement>>(trace, result, build_proof_options()).unwrap();
+    let value: Value = serde_json::from_str(&proof.to_json()).unwrap();
+
+    let mut altered = value.clone();
+    altered["fri_proof"] = Value::String("0x00".into());
+    assert!(StarkProof::from_json(&altered.to_string()).is_err());
+
+    // the proof does not have an auxiliary trace segment
+    let mut altered = value.clone();
+    altered["aux_trace_queries"] = value["trace_queries"].clone();
+    assert!(StarkProof::from_json(&altered.to_string()).is_err());
+
+    let mut altered = value;
+    altered["version"] = (StarkProof::FORMAT_VERSION + 1).into();
+    assert!(StarkProof::from_json(&altered.to_string()).is_err());
+}
+
+#[test]
+fn describe_air() {
+    let result = BaseElement::new(21);
+    let inputs = TestFibInputs::new(result).with_linear_constraints();
+    let air = TestFibAir::new(TraceInfo::new(2, 8), inputs, build_proof_options());
+    let value: Value = serde_json::from_str(&air.describe()).unwrap();
+
+    assert_eq!(1, value["version"]);
+    assert_eq!(128, value["field"]["modulus_bits"]);
+    assert_eq!(
+        (BaseElement::ZERO - BaseElement::ONE).to_hex_string(),
+        value["field"]["max_element"]
+    );
+    assert_eq!(2, value["trace"]["width"]);
+    assert_eq!(8, value["trace"]["length"]);
+    assert_eq!("0x", value["trace"]["meta"]);
+
+    let constraints = value["transition_constraints"].as_array().unwrap();
+    assert_eq!(2, constraints.len());
+    for constraint in constraints {
+        assert_eq!("main", constraint["segment"]);
+        assert_eq!(1, constraint["base_degree"]);
+        assert_eq!(7, constraint["evaluation_degree"]);
+        assert_eq!(1, constraint["num_exemptions"]);
+        assert_eq!(true, constraint["linear"]);
+    }
+
+    // transition divisor is (x^8 - 1) / (x - g^7)
+    let g = air.trace_domain_generator();
+    let divisor = &value["transition_divisor"];
+    assert_eq!(8, divisor["numerator"][0][0]);
+    assert_eq!(BaseElement::ONE.to_hex_string(), divisor["numerator"][0][1]);
+    assert_eq!(g.exp(7).to_hex_string(), divisor["exclude"][0]);
+
+    // the last assertion is placed against register 1 at step 7 with divisor (x - g^7)
+    let assertions = value["assertions"].as_array().unwrap();
+    assert_eq!(3, assertions.len());
+    assert_eq!(1, assertions[2]["register"]);
+    assert_eq!(7, assertions[2]["first_step"]);
+    assert_eq!(result.to_hex_string(), assertions[2]["values"][0]);
+    assert_eq!(1, assertions[2]["divisor"]["numerator"][0][0]);
+    assert_eq!(
+        g.exp(7).to_hex_string(),
+        assertions[2]["divisor"]["numerator"][0][1]
+    );
+
+    assert!(value["periodic_columns"].as_array().unwrap().is_empty());
+    assert!(value["domain_point_constraints"]
+        .as_array()
+        .unwrap()
+        .is_empty());
+}
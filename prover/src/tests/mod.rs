@@ -3,13 +3,30 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use crate::ExecutionTrace;
+use crate::{AuxSegmentBuilder, ExecutionTrace};
 use air::{
-    Air, AirContext, Assertion, EvaluationFrame, FieldExtension, HashFunction, ProofOptions,
-    TraceInfo, TransitionConstraintDegree,
+    Air, AirContext, Assertion, BoundaryConstraint, EvaluationError, EvaluationFrame,
+    FieldExtension, HashFunction, ProofOptions, TraceInfo, TransitionConstraintDegree,
 };
-use math::{fields::f128::BaseElement, FieldElement, StarkField};
-use utils::collections::Vec;
+use core::sync::atomic::{AtomicUsize, Ordering};
+use math::{fields::f128::BaseElement, ExtensibleField, FieldElement, StarkField};
+use utils::{collections::Vec, ByteWriter, Serializable};
+
+mod assertions;
+mod aux_segment;
+mod boundary;
+mod channel;
+#[cfg(feature = "concurrent")]
+mod concurrent;
+mod exemptions;
+mod frame;
+mod instrumented;
+mod interactive;
+mod json;
+mod observer;
+mod padding;
+mod stats;
+mod validation;
 
 // FIBONACCI TRACE BUILDER
 // ================================================================================================
@@ -28,6 +45,330 @@ pub fn build_fib_trace(length: usize) -> ExecutionTrace<BaseElement> {
     ExecutionTrace::init(vec![reg1, reg2])
 }
 
+// FIBONACCI AIR
+// ================================================================================================
+
+/// Describes traces built by [build_fib_trace()]; public inputs are the value of the second
+/// register at the last step of the trace.
+pub struct FibAir<B: StarkField> {
+    context: AirContext<B>,
+    result: B,
+}
+
+impl<B: StarkField + ExtensibleField<2> + ExtensibleField<3>> Air for FibAir<B> {
+    type BaseElement = B;
+    type PublicInputs = B;
+
+    const MAX_CONSTRAINT_DEGREE: usize = 1;
+
+    fn new(trace_info: TraceInfo, pub_inputs: B, options: ProofOptions) -> Self {
+        let degrees = vec![TransitionConstraintDegree::new(1); 2];
+        assert_eq!(2, trace_info.width());
+        FibAir {
+            context: AirContext::new(trace_info, degrees, options),
+            result: pub_inputs,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseElement> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement<BaseField = Self::BaseElement>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+        result[0] = next[0] - (current[0] + current[1]);
+        result[1] = next[1] - (current[1] + next[0]);
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseElement>> {
+        let last_step = self.trace_length() - 1;
+        vec![
+            Assertion::single(0, 0, B::ONE),
+            Assertion::single(1, 0, B::ONE),
+            Assertion::single(1, last_step, self.result),
+        ]
+    }
+}
+
+// TEST FIBONACCI AIR
+// ================================================================================================
+
+/// Same as [FibAir] over the f128 field, but with properties configured via its public inputs.
+///
+/// Only the result of the computation is absorbed into the public coin, and thus, proofs
+/// generated against this AIR and against [FibAir] have the same transcripts unless the
+/// configured properties affect them.
+pub struct TestFibAir {
+    fib: FibAir<BaseElement>,
+    context: AirContext<BaseElement>,
+    inputs: TestFibInputs,
+}
+
+/// Public inputs of [TestFibAir]; specify the result of the computation and the properties of
+/// the AIR.
+#[derive(Clone)]
+pub struct TestFibInputs {
+    result: BaseElement,
+    declared_degree: usize,
+    frame_size: usize,
+    linear: bool,
+    aux_width: usize,
+    accumulator: bool,
+    aux_evaluator: bool,
+    exemptions: [usize; 2],
+    domain_point_constraint: Option<(BaseElement, BaseElement)>,
+    linear_combination_constraint: Option<BaseElement>,
+    channel_seed: [u8; 32],
+    assertion_counter: Option<&'static AtomicUsize>,
+}
+
+impl TestFibInputs {
+    /// Returns inputs for an AIR with the same properties as [FibAir].
+    pub fn new(result: BaseElement) -> Self {
+        TestFibInputs {
+            result,
+            declared_degree: 1,
+            frame_size: 2,
+            linear: false,
+            aux_width: 0,
+            accumulator: false,
+            aux_evaluator: true,
+            exemptions: [1, 1],
+            domain_point_constraint: None,
+            linear_combination_constraint: None,
+            channel_seed: [0; 32],
+            assertion_counter: None,
+        }
+    }
+
+    /// Declares transition constraints to be of the specified base degree regardless of their
+    /// actual degree.
+    pub fn with_declared_degree(mut self, degree: usize) -> Self {
+        self.declared_degree = degree;
+        self
+    }
+
+    /// Sets the number of rows in the evaluation frame; for frames of 3 rows, transition
+    /// constraints relate values of each register at steps $i$, $i + 1$, and $i + 2$.
+    ///
+    /// # Panics
+    /// Panics if `frame_size` is neither 2 nor 3.
+    pub fn with_frame_size(mut self, frame_size: usize) -> Self {
+        assert!(
+            frame_size == 2 || frame_size == 3,
+            "frame size must be 2 or 3"
+        );
+        self.frame_size = frame_size;
+        self
+    }
+
+    /// Declares transition constraints to be linear.
+    pub fn with_linear_constraints(mut self) -> Self {
+        self.linear = true;
+        self
+    }
+
+    /// Declares the specified number of auxiliary trace columns without defining any constraints
+    /// against them.
+    pub fn with_aux_width(mut self, width: usize) -> Self {
+        self.aux_width = width;
+        self
+    }
+
+    /// Declares an auxiliary trace column which accumulates values of the first register using a
+    /// random element $\alpha$: the column starts with $\alpha$, and
+    /// $a_{i+1} = \alpha \cdot a_i + s_{0, i}$. The column is built by
+    /// [AccumulatorSegmentBuilder].
+    pub fn with_accumulator(mut self) -> Self {
+        self.aux_width = 1;
+        self.accumulator = true;
+        self
+    }
+
+    /// Declares transition constraints against the auxiliary column without providing an
+    /// evaluator for them.
+    pub fn without_aux_transition_evaluator(mut self) -> Self {
+        self.aux_evaluator = false;
+        self
+    }
+
+    /// Sets the number of steps at the end of the trace on which each of the transition
+    /// constraints is not enforced.
+    pub fn with_exemptions(mut self, exemptions: [usize; 2]) -> Self {
+        self.exemptions = exemptions;
+        self
+    }
+
+    /// Places a constraint against the first register at the specified domain point; the trace
+    /// polynomial of the register must evaluate to `value` at this point.
+    pub fn with_domain_point_constraint(mut self, point: BaseElement, value: BaseElement) -> Self {
+        self.domain_point_constraint = Some((point, value));
+        self
+    }
+
+    /// Places a constraint against the linear combination $2 \cdot s_0 + s_1$ of both registers
+    /// at the last step of the trace; the combination must be equal to `value` at this step.
+    pub fn with_linear_combination_constraint(mut self, value: BaseElement) -> Self {
+        self.linear_combination_constraint = Some(value);
+        self
+    }
+
+    /// Sets the seed with which the public coin is initialized together with the public inputs.
+    pub fn with_channel_seed(mut self, seed: [u8; 32]) -> Self {
+        self.channel_seed = seed;
+        self
+    }
+
+    /// Increments the specified counter each time assertions of the AIR are requested.
+    pub fn with_assertion_counter(mut self, counter: &'static AtomicUsize) -> Self {
+        self.assertion_counter = Some(counter);
+        self
+    }
+}
+
+impl Serializable for TestFibInputs {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write(self.result);
+    }
+}
+
+impl Air for TestFibAir {
+    type BaseElement = BaseElement;
+    type PublicInputs = TestFibInputs;
+
+    const MAX_CONSTRAINT_DEGREE: usize = FibAir::<BaseElement>::MAX_CONSTRAINT_DEGREE;
+
+    fn new(trace_info: TraceInfo, pub_inputs: TestFibInputs, options: ProofOptions) -> Self {
+        let degrees = vec![TransitionConstraintDegree::new(pub_inputs.declared_degree); 2];
+        let context = AirContext::new(trace_info.clone(), degrees, options.clone())
+            .with_frame_size(pub_inputs.frame_size)
+            .with_transition_constraint_exemptions(pub_inputs.exemptions.to_vec());
+        let context = if pub_inputs.accumulator {
+            context.with_aux_transition_constraint_degrees(vec![TransitionConstraintDegree::new(1)])
+        } else {
+            context
+        };
+        TestFibAir {
+            fib: FibAir::new(trace_info, pub_inputs.result, options),
+            context,
+            inputs: pub_inputs,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseElement> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement<BaseField = Self::BaseElement>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        if self.inputs.frame_size == 2 {
+            self.fib.evaluate_transition(frame, periodic_values, result);
+        } else {
+            // each register of the trace satisfies s_{i + 2} = 3 * s_{i + 1} - s_i
+            let three = E::from(3u8);
+            for (i, value) in result.iter_mut().enumerate() {
+                *value = frame.row(2)[i] - (three * frame.row(1)[i] - frame.row(0)[i]);
+            }
+        }
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseElement>> {
+        if let Some(counter) = self.inputs.assertion_counter {
+            counter.fetch_add(1, Ordering::SeqCst);
+        }
+        self.fib.get_assertions()
+    }
+
+    fn is_transition_constraint_linear(&self, _index: usize) -> bool {
+        self.inputs.linear
+    }
+
+    fn get_domain_point_constraints<E: FieldElement<BaseField = Self::BaseElement>>(
+        &self,
+    ) -> Vec<BoundaryConstraint<Self::BaseElement, E>> {
+        let mut result = Vec::new();
+        if let Some((point, value)) = self.inputs.domain_point_constraint {
+            result.push(BoundaryConstraint::at_domain_point(0, point, value));
+        }
+        if let Some(value) = self.inputs.linear_combination_constraint {
+            let last_step = (self.trace_length() - 1) as u64;
+            let point = self.trace_domain_generator().exp(last_step.into());
+            let terms = vec![(0, BaseElement::new(2)), (1, BaseElement::ONE)];
+            result.push(BoundaryConstraint::linear_combination(terms, point, value));
+        }
+        result
+    }
+
+    fn auxiliary_trace_width(&self) -> usize {
+        self.inputs.aux_width
+    }
+
+    fn num_aux_rand_elements(&self) -> usize {
+        self.inputs.accumulator as usize
+    }
+
+    fn evaluate_aux_transition<E: FieldElement<BaseField = Self::BaseElement>>(
+        &self,
+        main_frame: &EvaluationFrame<E>,
+        aux_frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        aux_rand_elements: &[E],
+        result: &mut [E],
+    ) -> Result<(), EvaluationError> {
+        if !self.inputs.aux_evaluator {
+            return Err(EvaluationError::MissingAuxTransitionEvaluator);
+        }
+        let alpha = aux_rand_elements[0];
+        result[0] =
+            aux_frame.next()[0] - (alpha * aux_frame.current()[0] + main_frame.current()[0]);
+        Ok(())
+    }
+
+    fn get_aux_assertions<E: FieldElement<BaseField = Self::BaseElement>>(
+        &self,
+        aux_rand_elements: &[E],
+    ) -> Vec<Assertion<E>> {
+        if self.inputs.accumulator {
+            vec![Assertion::single(0, 0, aux_rand_elements[0])]
+        } else {
+            Vec::new()
+        }
+    }
+
+    fn channel_seed(&self) -> [u8; 32] {
+        self.inputs.channel_seed
+    }
+}
+
+/// Builds the auxiliary column for [TestFibAir] configured via
+/// [TestFibInputs::with_accumulator()].
+pub struct AccumulatorSegmentBuilder;
+
+impl AuxSegmentBuilder<BaseElement> for AccumulatorSegmentBuilder {
+    fn build_aux_segment<E: FieldElement<BaseField = BaseElement>>(
+        &self,
+        main_trace: &ExecutionTrace<BaseElement>,
+        rand_elements: &[E],
+    ) -> Vec<Vec<E>> {
+        let alpha = rand_elements[0];
+        let mut column = vec![alpha];
+        for step in 0..main_trace.length() - 1 {
+            column.push(alpha * column[step] + E::from(main_trace.get(0, step)));
+        }
+        vec![column]
+    }
+}
+
 // MOCK AIR
 // ================================================================================================
 
@@ -98,6 +439,8 @@ impl Air for MockAir {
     type BaseElement = BaseElement;
     type PublicInputs = ();
 
+    const MAX_CONSTRAINT_DEGREE: usize = 2;
+
     fn new(trace_info: TraceInfo, _pub_inputs: (), _options: ProofOptions) -> Self {
         let context = build_context(trace_info, 8);
         MockAir {
@@ -131,6 +474,19 @@ impl Air for MockAir {
 // HELPER FUNCTIONS
 // ================================================================================================
 
+/// Returns options used to generate proofs of the test computations.
+pub fn build_proof_options() -> ProofOptions {
+    ProofOptions::new(
+        36,
+        8,
+        0,
+        HashFunction::Blake3_256,
+        FieldExtension::None,
+        4,
+        256,
+    )
+}
+
 fn build_context<B: StarkField>(trace_info: TraceInfo, blowup_factor: usize) -> AirContext<B> {
     let options = ProofOptions::new(
         32,
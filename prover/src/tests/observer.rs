@@ -0,0 +1,94 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{build_fib_trace, build_proof_options, FibAir};
+use crate::{ProverError, ProvingObserver, ProvingStage};
+use math::fields::f128::BaseElement;
+use utils::collections::Vec;
+
+// TESTS
+// ================================================================================================
+
+#[test]
+fn prove_with_observer() {
+    let trace = build_fib_trace(32);
+    let result = trace.get(1, trace.length() - 1);
+    let proof = crate::prove::<FibAir<BaseElement>>(trace, result, build_proof_options()).unwrap();
+
+    let mut observer = RecordingObserver::new(None);
+    let observed_proof = crate::prove_with_observer::<FibAir<BaseElement>, _>(
+        build_fib_trace(32),
+        result,
+        build_proof_options(),
+        &mut observer,
+    )
+    .unwrap();
+    assert_eq!(proof, observed_proof);
+
+    // each stage is started and completed in order; there is no auxiliary trace segment
+    let stages = [
+        ProvingStage::TraceCommitment,
+        ProvingStage::ConstraintEvaluation,
+        ProvingStage::OodEvaluation,
+        ProvingStage::FriCommitment,
+        ProvingStage::QueryGeneration,
+        ProvingStage::ProofAssembly,
+    ];
+    let expected = stages
+        .iter()
+        .flat_map(|&stage| vec![(stage, true), (stage, false)])
+        .collect::<Vec<_>>();
+    assert_eq!(expected, observer.events);
+
+    // cancellation is checked between stages
+    let mut observer = RecordingObserver::new(Some(ProvingStage::ConstraintEvaluation));
+    let result = crate::prove_with_observer::<FibAir<BaseElement>, _>(
+        build_fib_trace(32),
+        result,
+        build_proof_options(),
+        &mut observer,
+    );
+    assert_eq!(
+        Err(ProverError::ProvingCancelled(ProvingStage::OodEvaluation)),
+        result
+    );
+    assert_eq!(expected[..4].to_vec(), observer.events);
+}
+
+// RECORDING OBSERVER
+// ================================================================================================
+
+/// Records all stage notifications as (stage, started) pairs, and requests cancellation once
+/// the `cancel_after` stage is completed.
+struct RecordingObserver {
+    events: Vec<(ProvingStage, bool)>,
+    cancel_after: Option<ProvingStage>,
+    cancelled: bool,
+}
+
+impl RecordingObserver {
+    fn new(cancel_after: Option<ProvingStage>) -> Self {
+        RecordingObserver {
+            events: Vec::new(),
+            cancel_after,
+            cancelled: false,
+        }
+    }
+}
+
+impl ProvingObserver for RecordingObserver {
+    fn stage_started(&mut self, stage: ProvingStage) {
+        self.events.push((stage, true));
+    }
+
+    fn stage_completed(&mut self, stage: ProvingStage) {
+        self.events.push((stage, false));
+        self.cancelled |= self.cancel_after == Some(stage);
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.cancelled
+    }
+}
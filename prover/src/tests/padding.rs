@@ -0,0 +1,238 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{build_proof_options, FibAir};
+use crate::{ExecutionTrace, ProverError};
+use air::{
+    proof::StarkProof, Air, AirContext, Assertion, EvaluationFrame, ProofOptions, TraceInfo,
+    TransitionConstraintDegree,
+};
+use math::{fields::f128::BaseElement, FieldElement};
+use utils::{collections::Vec, ByteWriter, Serializable};
+use verifier::VerifierError;
+
+// TESTS
+// ================================================================================================
+
+#[test]
+fn prove_padded_trace() {
+    // 12 steps of the computation are padded to 16 steps; the padding rows repeat the last row
+    let (registers, result) = build_unpadded_trace(12);
+    let trace = ExecutionTrace::init_with_padding(registers.clone());
+    assert_eq!(16, trace.length());
+    assert_eq!(4, trace.num_padding_rows());
+
+    // the number of padding rows is derived by the AIR from the number of steps, and is also
+    // carried by the proof
+    let inputs = PaddedFibInputs {
+        result,
+        num_steps: 12,
+    };
+    let options = build_proof_options();
+    let proof = crate::prove::<PaddedFibAir>(trace, inputs, options.clone()).unwrap();
+    let proof = StarkProof::from_bytes(&proof.to_bytes()).unwrap();
+    assert_eq!(4, proof.get_trace_info().num_padding_rows());
+    assert_eq!(12, proof.get_trace_info().unpadded_length());
+    assert!(verifier::verify::<PaddedFibAir>(proof.clone(), inputs).is_ok());
+
+    let wrong_inputs = PaddedFibInputs {
+        result: result + BaseElement::ONE,
+        num_steps: 12,
+    };
+    assert!(verifier::verify::<PaddedFibAir>(proof.clone(), wrong_inputs).is_err());
+
+    // for a different number of steps, the AIR expects a different number of padding rows; an
+    // AIR which does not declare padding rows rejects padded proofs altogether
+    let wrong_inputs = PaddedFibInputs {
+        result,
+        num_steps: 11,
+    };
+    assert_eq!(
+        Err(VerifierError::InconsistentTracePadding {
+            expected: 5,
+            actual: 4
+        }),
+        verifier::verify::<PaddedFibAir>(proof.clone(), wrong_inputs)
+    );
+    assert_eq!(
+        Err(VerifierError::InconsistentTracePadding {
+            expected: 0,
+            actual: 4
+        }),
+        verifier::verify::<FibAir<BaseElement>>(proof, result)
+    );
+
+    // the prover rejects traces padded differently from what the AIR declares
+    let trace = ExecutionTrace::init_with_padding(registers.clone());
+    assert!(matches!(
+        crate::prove::<FibAir<BaseElement>>(trace, result, options.clone()),
+        Err(ProverError::MismatchedTracePadding(0, 4))
+    ));
+
+    // padding rows which differ from the last row of the computation violate padding constraints
+    let mut trace = ExecutionTrace::init_with_padding(registers);
+    trace.update_row(14, &[BaseElement::ZERO, BaseElement::ZERO]);
+    assert!(matches!(
+        crate::prove::<PaddedFibAir>(trace, inputs, options),
+        Err(ProverError::UnsatisfiedTransitionConstraintError(13))
+    ));
+}
+
+#[test]
+fn verify_forged_padded_proof() {
+    // the trace performs 2 steps of the computation, and the remaining rows, which the proof
+    // claims to be padding rows, move a forged result into the last row of the trace
+    let (mut registers, _) = build_unpadded_trace(2);
+    let forged_result = BaseElement::new(424242);
+    registers[0].resize(8, BaseElement::ZERO);
+    registers[1].resize(8, forged_result);
+    let trace = ExecutionTrace::init(registers);
+
+    let options = build_proof_options();
+    let proof = crate::prove::<ForgedPaddingFibAir>(trace, forged_result, options).unwrap();
+    assert_eq!(6, proof.get_trace_info().num_padding_rows());
+
+    // Fibonacci AIR does not declare any padding rows, and thus, the padding claimed by the
+    // proof is rejected
+    assert_eq!(
+        Err(VerifierError::InconsistentTracePadding {
+            expected: 0,
+            actual: 6
+        }),
+        verifier::verify::<FibAir<BaseElement>>(proof.clone(), forged_result)
+    );
+
+    // an AIR which declares the padding rows requires them to repeat the last row of the
+    // computation
+    let inputs = PaddedFibInputs {
+        result: forged_result,
+        num_steps: 2,
+    };
+    assert!(matches!(
+        verifier::verify::<PaddedFibAir>(proof, inputs),
+        Err(VerifierError::OodConstraintMismatch(..))
+    ));
+}
+
+// PADDED FIBONACCI AIR
+// ================================================================================================
+
+/// Same as Fibonacci AIR but for a computation with an arbitrary number of steps; the number of
+/// padding rows of the trace is derived from the number of steps, and the result is asserted
+/// against the last step of the computation.
+struct PaddedFibAir {
+    fib: FibAir<BaseElement>,
+    context: AirContext<BaseElement>,
+    inputs: PaddedFibInputs,
+}
+
+#[derive(Clone, Copy)]
+struct PaddedFibInputs {
+    result: BaseElement,
+    num_steps: usize,
+}
+
+impl Serializable for PaddedFibInputs {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write(self.result);
+        target.write_u32(self.num_steps as u32);
+    }
+}
+
+impl Air for PaddedFibAir {
+    type BaseElement = BaseElement;
+    type PublicInputs = PaddedFibInputs;
+
+    const MAX_CONSTRAINT_DEGREE: usize = FibAir::<BaseElement>::MAX_CONSTRAINT_DEGREE;
+
+    fn new(trace_info: TraceInfo, pub_inputs: PaddedFibInputs, options: ProofOptions) -> Self {
+        let num_padding_rows = trace_info.length().saturating_sub(pub_inputs.num_steps);
+        let degrees = vec![TransitionConstraintDegree::new(1); 2];
+        let context = AirContext::new(trace_info.clone(), degrees, options.clone())
+            .with_num_padding_rows(num_padding_rows);
+        PaddedFibAir {
+            fib: FibAir::new(trace_info, pub_inputs.result, options),
+            context,
+            inputs: pub_inputs,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseElement> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement<BaseField = Self::BaseElement>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        self.fib.evaluate_transition(frame, periodic_values, result);
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseElement>> {
+        let last_step = self.inputs.num_steps - 1;
+        vec![
+            Assertion::single(0, 0, BaseElement::ONE),
+            Assertion::single(1, 0, BaseElement::ONE),
+            Assertion::single(1, last_step, self.inputs.result),
+        ]
+    }
+}
+
+/// Fibonacci AIR used by a malicious prover: the trace info of the AIR claims that the last 6
+/// rows of the trace are padding rows, but instead of declaring the padding rows, the AIR only
+/// exempts transition constraints from the steps at which the frame includes any of these rows.
+struct ForgedPaddingFibAir(FibAir<BaseElement>, AirContext<BaseElement>);
+
+impl Air for ForgedPaddingFibAir {
+    type BaseElement = BaseElement;
+    type PublicInputs = BaseElement;
+
+    const MAX_CONSTRAINT_DEGREE: usize = FibAir::<BaseElement>::MAX_CONSTRAINT_DEGREE;
+
+    fn new(trace_info: TraceInfo, pub_inputs: BaseElement, options: ProofOptions) -> Self {
+        let degrees = vec![TransitionConstraintDegree::new(1); 2];
+        let forged_info = trace_info.clone().with_num_padding_rows(6);
+        let context = AirContext::new(forged_info, degrees, options.clone())
+            .with_transition_constraint_exemptions(vec![7, 7]);
+        let fib_air = FibAir::new(trace_info, pub_inputs, options);
+        ForgedPaddingFibAir(fib_air, context)
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseElement> {
+        &self.1
+    }
+
+    fn evaluate_transition<E: FieldElement<BaseField = Self::BaseElement>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        self.0.evaluate_transition(frame, periodic_values, result);
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseElement>> {
+        self.0.get_assertions()
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns registers of a Fibonacci execution trace with the specified number of steps, together
+/// with the result of the computation.
+fn build_unpadded_trace(num_steps: usize) -> (Vec<Vec<BaseElement>>, BaseElement) {
+    let mut registers = vec![vec![BaseElement::ONE], vec![BaseElement::ONE]];
+    for i in 1..num_steps {
+        let s0 = registers[0][i - 1] + registers[1][i - 1];
+        let s1 = registers[1][i - 1] + s0;
+        registers[0].push(s0);
+        registers[1].push(s1);
+    }
+    let result = registers[1][num_steps - 1];
+    (registers, result)
+}
@@ -0,0 +1,26 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{build_fib_trace, build_proof_options, FibAir};
+use math::fields::f128::BaseElement;
+use utils::{collections::Vec, CountingWriter};
+
+// TESTS
+// ================================================================================================
+
+#[test]
+fn proof_size_with_counting_writer() {
+    let trace = build_fib_trace(1024);
+    let result = trace.get(1, trace.length() - 1);
+    let proof = crate::prove::<FibAir<BaseElement>>(trace, result, build_proof_options()).unwrap();
+
+    let mut writer = CountingWriter::new();
+    proof.write_into(&mut writer);
+    assert_eq!(proof.to_bytes().len(), writer.bytes_written());
+
+    let mut bytes = Vec::new();
+    proof.write_into(&mut bytes);
+    assert_eq!(proof.to_bytes(), bytes);
+}
@@ -0,0 +1,158 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{build_fib_trace, build_proof_options, FibAir, TestFibAir, TestFibInputs};
+use crate::{ConstraintViolation, ExecutionTrace, ProverError};
+use air::{FieldExtension, HashFunction, ProofOptions};
+use math::{fields::f128::BaseElement, FieldElement};
+use utils::string::ToString;
+
+// TESTS
+// ================================================================================================
+
+#[test]
+fn prove_with_inconsistent_constraint_degree() {
+    let trace = build_fib_trace(16);
+    let result = trace.get(1, trace.length() - 1);
+    let inputs = TestFibInputs::new(result);
+    assert!(
+        crate::prove::<TestFibAir>(trace.clone(), inputs.clone(), build_proof_options()).is_ok()
+    );
+
+    // the AIR is rejected before the trace is committed to
+    let inputs = inputs.with_declared_degree(2);
+    assert_eq!(
+        Err(ProverError::InconsistentConstraintDegree {
+            declared: 1,
+            actual: 2
+        }),
+        crate::prove::<TestFibAir>(trace, inputs, build_proof_options()).map(|_| ())
+    );
+}
+
+#[test]
+fn prove_with_mismatched_aux_trace_width() {
+    let trace = build_fib_trace(16);
+    let result = trace.get(1, trace.length() - 1);
+
+    // the AIR expects an auxiliary column, but the trace consists of the main segment only
+    let inputs = TestFibInputs::new(result).with_aux_width(1);
+    assert_eq!(
+        Err(ProverError::MismatchedAuxiliaryTraceWidth(1, 0)),
+        crate::prove::<TestFibAir>(trace, inputs, build_proof_options()).map(|_| ())
+    );
+}
+
+#[test]
+fn prove_with_unsatisfied_boundary_constraint() {
+    let mut trace = build_fib_trace(16);
+    let result = trace.get(1, trace.length() - 1);
+    trace.set(1, 0, BaseElement::new(2));
+    assert_eq!(
+        Err(ProverError::BoundaryConstraintNotSatisfied {
+            col: 1,
+            step: 0,
+            expected: "1".to_string(),
+            actual: "2".to_string(),
+        }),
+        crate::prove::<FibAir<BaseElement>>(trace, result, build_proof_options()).map(|_| ())
+    );
+}
+
+#[test]
+#[cfg(debug_assertions)]
+fn prove_with_unsatisfied_transition_constraint() {
+    let mut trace = build_fib_trace(16);
+    let result = trace.get(1, trace.length() - 1);
+    trace.set(0, 4, BaseElement::new(42));
+    assert_eq!(
+        Err(ProverError::UnsatisfiedTransitionConstraintError(3)),
+        crate::prove::<FibAir<BaseElement>>(trace, result, build_proof_options()).map(|_| ())
+    );
+}
+
+#[test]
+fn prove_with_panicking_air() {
+    // FibAir requires a trace with 2 registers; instantiating it for a trace with 3 registers
+    // panics, and the panic should be reported as an error
+    let trace = ExecutionTrace::init(vec![vec![BaseElement::ONE; 8]; 3]);
+    match crate::prove::<FibAir<BaseElement>>(trace, BaseElement::ONE, build_proof_options()) {
+        Err(ProverError::InternalPanic(message)) => assert!(message.contains("assertion")),
+        result => panic!(
+            "expected an internal panic error, but got {:?}",
+            result.map(|_| ())
+        ),
+    }
+}
+
+#[test]
+fn prove_with_insufficient_security() {
+    // 28 queries with blowup factor 8 provide 83 bits of conjectured security, while FibAir
+    // requires the default 96 bits
+    let options = ProofOptions::new(
+        28,
+        8,
+        0,
+        HashFunction::Blake3_256,
+        FieldExtension::None,
+        4,
+        256,
+    );
+    assert_eq!(83.0, options.security_bits::<BaseElement>(16));
+
+    let trace = build_fib_trace(32);
+    let result = trace.get(1, trace.length() - 1);
+    assert_eq!(
+        Err(ProverError::InsufficientSecurity {
+            required: 96,
+            achieved: 83.0
+        }),
+        crate::prove::<FibAir<BaseElement>>(trace.clone(), result, options).map(|_| ())
+    );
+
+    // conjectured security of the generated proof matches the security computed from options
+    let proof = crate::prove::<FibAir<BaseElement>>(trace, result, build_proof_options()).unwrap();
+    assert_eq!(
+        proof.security_level(true) as f64,
+        proof.options().security_bits::<BaseElement>(16)
+    );
+}
+
+#[test]
+fn validate_trace_with_report() {
+    let trace = build_fib_trace(32);
+    let result = trace.get(1, trace.length() - 1);
+    let options = build_proof_options();
+    let report = crate::validate_trace::<FibAir<BaseElement>>(&trace, result, options.clone());
+    assert_eq!(Ok(()), report);
+
+    // changing the first value of the second register breaks both transition constraints at
+    // step 0 as well as the assertion against this cell
+    let mut trace = trace;
+    trace.set(1, 0, BaseElement::new(2));
+    let report = crate::validate_trace::<FibAir<BaseElement>>(&trace, result, options).unwrap_err();
+    assert_eq!(
+        &[
+            ConstraintViolation::TransitionConstraint {
+                constraint: 0,
+                step: 0
+            },
+            ConstraintViolation::TransitionConstraint {
+                constraint: 1,
+                step: 0
+            },
+            ConstraintViolation::Assertion {
+                register: 1,
+                step: 0,
+                expected: "1".to_string(),
+                actual: "2".to_string(),
+            },
+        ],
+        report.violations()
+    );
+    assert!(report
+        .to_string()
+        .starts_with("3 constraint(s) not satisfied"));
+}
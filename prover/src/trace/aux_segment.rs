@@ -0,0 +1,108 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    execution_trace::{check_assertions, extend_register},
+    ExecutionTrace, StarkDomain, TracePolyTable, TraceTable,
+};
+use crate::ProverError;
+use air::Assertion;
+use math::{fft, FieldElement, StarkField};
+use utils::{collections::Vec, iter_mut};
+
+#[cfg(feature = "concurrent")]
+use utils::iterators::*;
+
+// AUXILIARY SEGMENT BUILDER
+// ================================================================================================
+/// Defines how the auxiliary segment of an execution trace is built from the main segment.
+///
+/// The auxiliary segment is built after the prover commits to the main segment, using random
+/// elements drawn from the public coin. The elements are drawn from the field in which the
+/// protocol is executed, which is selected at runtime via proof options; thus, the builder must
+/// be able to build the segment over any extension of the base field `B`.
+pub trait AuxSegmentBuilder<B: StarkField> {
+    /// Returns registers of the auxiliary trace segment built from the `main_trace` and the
+    /// provided random elements.
+    ///
+    /// The number of `rand_elements` is equal to [Air::num_aux_rand_elements()](air::Air). The
+    /// returned vector must contain one vector of values per auxiliary register, and each of
+    /// the registers must have the same length as the `main_trace`.
+    fn build_aux_segment<E: FieldElement<BaseField = B>>(
+        &self,
+        main_trace: &ExecutionTrace<B>,
+        rand_elements: &[E],
+    ) -> Vec<Vec<E>>;
+}
+
+// AUXILIARY SEGMENT
+// ================================================================================================
+/// Registers of an auxiliary trace segment over the trace domain.
+pub(crate) struct AuxTraceSegment<E: FieldElement>(Vec<Vec<E>>);
+
+impl<E: FieldElement> AuxTraceSegment<E> {
+    /// Wraps the specified registers of an auxiliary trace segment.
+    pub fn new(registers: Vec<Vec<E>>) -> Self {
+        AuxTraceSegment(registers)
+    }
+
+    /// Returns the number of registers in this segment.
+    pub fn width(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns the number of steps in this segment; this assumes that all registers have the
+    /// same length.
+    pub fn length(&self) -> usize {
+        self.0.first().map_or(0, |register| register.len())
+    }
+
+    /// Returns true if all registers of this segment have the specified length.
+    pub fn has_length(&self, length: usize) -> bool {
+        self.0.iter().all(|register| register.len() == length)
+    }
+
+    /// Checks if this segment satisfies all of the specified `assertions`; registers of the
+    /// assertions refer to the registers of this segment.
+    ///
+    /// # Errors
+    /// Returns an error describing the first asserted cell whose value in this segment is
+    /// different from the asserted value.
+    pub fn check_assertions(&self, assertions: &[Assertion<E>]) -> Result<(), ProverError> {
+        check_assertions(assertions, self.length(), |register, step| {
+            self.0[register][step]
+        })
+    }
+
+    /// Extends all registers of this segment to the length of the LDE domain in the same way as
+    /// [ExecutionTrace::extend()] extends registers of the main segment.
+    pub fn extend(
+        mut self,
+        domain: &StarkDomain<E::BaseField>,
+    ) -> (TraceTable<E>, TracePolyTable<E>) {
+        assert_eq!(
+            self.length(),
+            domain.trace_length(),
+            "inconsistent trace length"
+        );
+        let inv_twiddles = fft::get_inv_twiddles::<E::BaseField>(domain.trace_length());
+
+        // extend all registers; the extension procedure first interpolates register traces into
+        // polynomials (in-place), then evaluates these polynomials over a larger domain, and
+        // then returns extended evaluations.
+        let extended_trace = iter_mut!(self.0)
+            .map(|register_trace| extend_register(register_trace, domain, &inv_twiddles))
+            .collect();
+
+        (
+            TraceTable::new(
+                extended_trace,
+                domain.trace_to_lde_blowup(),
+                domain.offset(),
+            ),
+            TracePolyTable::new(self.0),
+        )
+    }
+}
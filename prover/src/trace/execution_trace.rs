@@ -3,10 +3,17 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::{StarkDomain, TracePolyTable, TraceTable};
-use air::{Air, EvaluationFrame, TraceInfo};
-use math::{fft, log2, polynom, StarkField};
-use utils::{collections::Vec, iter_mut, uninit_vector};
+use super::{ConstraintViolation, StarkDomain, TracePolyTable, TraceTable, TraceValidationReport};
+use crate::ProverError;
+use air::{Air, Assertion, EvaluationFrame, TraceInfo};
+use core::cmp;
+use math::{fft, log2, polynom, FieldElement, StarkField};
+use utils::{
+    collections::Vec,
+    iter_mut,
+    string::{String, ToString},
+    uninit_vector,
+};
 
 #[cfg(not(feature = "concurrent"))]
 use utils::collections::vec;
@@ -19,6 +26,13 @@ use utils::{iterators::*, rayon};
 
 const MIN_FRAGMENT_LENGTH: usize = 2;
 
+/// Number of rows before and after the first mismatching row included into the diff printed by
+/// [ExecutionTrace::assert_eq()].
+const DIFF_CONTEXT_ROWS: usize = 2;
+
+/// Number of consecutive rows transposed at once by [ExecutionTrace::init_from_rows()].
+const TRANSPOSE_BLOCK_SIZE: usize = 64;
+
 // TRACE TABLE
 // ================================================================================================
 /// An execution trace of a computation.
@@ -47,10 +61,30 @@ const MIN_FRAGMENT_LENGTH: usize = 2;
 /// 2. The second closure receives the previous state of the execution trace as input, and must
 ///    update it to the next state of the computation.
 ///
+/// For computations which produce one full row of the trace per step, the trace can also be
+/// created from an iterator over rows via [ExecutionTrace::init_from_rows()], or filled row by
+/// row via [ExecutionTrace::update_row()].
+///
 /// You can also use [ExecutionTrace::with_meta()] function to create a blank execution trace.
 /// This function work just like [ExecutionTrace::new()] function, but also takes a metadata
 /// parameter which can be an arbitrary sequence of bytes up to 64KB in size.
 ///
+/// # Non-power-of-two traces
+/// For computations whose number of steps is not a power of two, the
+/// [ExecutionTrace::init_with_padding()] function can be used. It extends the provided registers
+/// to the next power of two by repeating the last row, and records the number of padding rows in
+/// the [TraceInfo] of the trace. The AIR of the computation must declare the same number of
+/// padding rows via [AirContext::with_num_padding_rows()](air::AirContext::with_num_padding_rows).
+///
+/// If the number of steps is not known in advance, a trace can be allocated for the maximum
+/// number of steps, and then truncated at the last filled row and padded via
+/// [ExecutionTrace::finalize_at()].
+///
+/// Alternatively, a trace can be extended with stutter rows via
+/// [ExecutionTrace::init_with_stutter()] or [ExecutionTrace::finalize_with_stutter()]: each
+/// additional row is built from the previous one by a user-supplied "no-op" transition, and
+/// constraints of the computation are enforced on these rows as on any other rows.
+///
 /// # Concurrent trace generation
 /// For computations which consist of many small independent computations, we can generate the
 /// execution trace of the entire computation by building fragments of the trace in parallel,
@@ -62,9 +96,17 @@ const MIN_FRAGMENT_LENGTH: usize = 2;
 /// [fill()](ExecutionTraceFragment::fill) method to fill all fragments with data in parallel.
 /// The semantics of the fragment's [ExecutionTraceFragment::fill()] method are identical to the
 /// semantics of the [ExecutionTrace::fill()] method.
+///
+/// # Comparing traces
+/// Two execution traces are equal if they have the same metadata and the same values in all
+/// registers at all steps. To check that two traces are equal in tests, the
+/// [assert_eq()](ExecutionTrace::assert_eq) method can be used; when the traces differ, it
+/// panics with a message describing the first mismatching value and the rows around it.
+#[derive(Clone, PartialEq, Eq)]
 pub struct ExecutionTrace<B: StarkField> {
     trace: Vec<Vec<B>>,
     meta: Vec<u8>,
+    num_padding_rows: usize,
 }
 
 impl<B: StarkField> ExecutionTrace<B> {
@@ -135,6 +177,7 @@ impl<B: StarkField> ExecutionTrace<B> {
         ExecutionTrace {
             trace: registers,
             meta,
+            num_padding_rows: 0,
         }
     }
 
@@ -187,7 +230,233 @@ impl<B: StarkField> ExecutionTrace<B> {
         ExecutionTrace {
             trace: registers,
             meta: vec![],
+            num_padding_rows: 0,
+        }
+    }
+
+    /// Creates a new execution trace from the provided rows.
+    ///
+    /// This is useful for computations which produce one full row of the trace per step (e.g.,
+    /// virtual machines). Each of the rows must contain one value per register; the rows are
+    /// transposed into the column-major layout of the trace in blocks of consecutive rows, so
+    /// that the values of each block are read and written while they are still in the CPU cache.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * The first row is empty or has over 255 values.
+    /// * Any of the rows contains a different number of values than the first row.
+    /// * Number of rows is smaller than 8, greater than the biggest multiplicative subgroup in
+    ///   the field `B`, or is not a power of two.
+    pub fn init_from_rows<I>(rows: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: AsRef<[B]>,
+    {
+        let mut rows = rows.into_iter().peekable();
+        let width = rows.peek().map_or(0, |row| row.as_ref().len());
+        let mut registers = vec![Vec::new(); width];
+
+        let mut block = Vec::with_capacity(width * TRANSPOSE_BLOCK_SIZE);
+        loop {
+            // copy the next block of rows into a row-major buffer
+            block.clear();
+            for row in rows.by_ref().take(TRANSPOSE_BLOCK_SIZE) {
+                let row = row.as_ref();
+                assert_eq!(
+                    row.len(),
+                    width,
+                    "all rows must contain {} values, but a row contained {}",
+                    width,
+                    row.len()
+                );
+                block.extend_from_slice(row);
+            }
+            if block.is_empty() {
+                break;
+            }
+
+            // append the values of the block to each of the registers
+            for (i, register) in registers.iter_mut().enumerate() {
+                register.extend(block.iter().skip(i).step_by(width));
+            }
+        }
+
+        Self::init(registers)
+    }
+
+    /// Creates a new execution trace from a list of provided register traces whose length is
+    /// not necessarily a power of two.
+    ///
+    /// The registers are extended to the next power of two (but not fewer than 8 steps) by
+    /// appending copies of the last row. Transition constraints of the computation are not
+    /// enforced between the last original row and the padding rows, nor between any two padding
+    /// rows; instead, padding constraints of the AIR require each padding row to be equal to the
+    /// row preceding it (see `Air::get_padding_constraints()`). Thus, assertions against the
+    /// last step of the padded trace are equivalent to assertions against the last step of the
+    /// computation.
+    ///
+    /// If the length of the registers is already a power of two of at least 8, no padding rows
+    /// are added.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * The `registers` vector is empty or has over 255 registers.
+    /// * Number of elements in any of the registers is zero, or, after padding, greater than the
+    ///   biggest multiplicative subgroup in the field `B`.
+    /// * Number of elements is not identical for all registers.
+    pub fn init_with_padding(mut registers: Vec<Vec<B>>) -> Self {
+        assert!(
+            !registers.is_empty(),
+            "execution trace must consist of at least one register"
+        );
+        let trace_length = registers[0].len();
+        assert!(
+            trace_length > 0,
+            "execution trace must contain at least one step"
+        );
+        for register in registers.iter() {
+            assert_eq!(
+                register.len(),
+                trace_length,
+                "all register traces must have the same length"
+            );
+        }
+
+        let padded_length = cmp::max(
+            trace_length.next_power_of_two(),
+            TraceInfo::MIN_TRACE_LENGTH,
+        );
+        for register in registers.iter_mut() {
+            let value = register[trace_length - 1];
+            register.resize(padded_length, value);
+        }
+
+        let mut trace = Self::init(registers);
+        trace.num_padding_rows = padded_length - trace_length;
+        trace
+    }
+
+    /// Truncates this execution trace to the first `num_rows` rows, and pads the remaining rows
+    /// with copies of the last remaining row.
+    ///
+    /// This is useful when the number of steps of a computation is not known in advance: the
+    /// trace can be allocated for the maximum number of steps, and once the computation
+    /// terminates, the trace is finalized at the last filled row. The truncated trace is
+    /// extended to the next power of two (but not fewer than 8 steps) in the same way as by
+    /// [init_with_padding()](ExecutionTrace::init_with_padding). Metadata of the trace is
+    /// preserved.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `num_rows` is zero or greater than the length of this trace.
+    pub fn finalize_at(self, num_rows: usize) -> Self {
+        assert!(
+            num_rows <= self.length(),
+            "cannot finalize a trace of {} steps at step {}",
+            self.length(),
+            num_rows
+        );
+        let mut registers = self.trace;
+        for register in registers.iter_mut() {
+            register.truncate(num_rows);
         }
+
+        let mut trace = Self::init_with_padding(registers);
+        trace.meta = self.meta;
+        trace
+    }
+
+    /// Creates a new execution trace from a list of provided register traces whose length is
+    /// not necessarily a power of two, extending the registers with stutter rows.
+    ///
+    /// The registers are extended to the next power of two (but not fewer than 8 steps) by
+    /// repeatedly invoking `pad_row` with the last row of the trace and a mutable slice for the
+    /// next row; `pad_row` is expected to apply a "no-op" transition of the computation (e.g.,
+    /// to keep the state unchanged once the computation has halted). Unlike padding rows added
+    /// by [init_with_padding()](ExecutionTrace::init_with_padding), stutter rows are regular
+    /// steps of the computation: transition constraints and assertions are enforced against
+    /// them, and thus, assertions against the final state of the computation must be placed at
+    /// the last step of the extended trace. Whether the stutter rows satisfy constraints of a
+    /// computation can be checked via [check_constraints()](ExecutionTrace::check_constraints).
+    ///
+    /// If the length of the registers is already a power of two of at least 8, no rows are
+    /// added.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * The `registers` vector is empty or has over 255 registers.
+    /// * Number of elements in any of the registers is zero, or, after extension, greater than
+    ///   the biggest multiplicative subgroup in the field `B`.
+    /// * Number of elements is not identical for all registers.
+    pub fn init_with_stutter<F>(mut registers: Vec<Vec<B>>, mut pad_row: F) -> Self
+    where
+        F: FnMut(&[B], &mut [B]),
+    {
+        assert!(
+            !registers.is_empty(),
+            "execution trace must consist of at least one register"
+        );
+        let trace_length = registers[0].len();
+        assert!(
+            trace_length > 0,
+            "execution trace must contain at least one step"
+        );
+        for register in registers.iter() {
+            assert_eq!(
+                register.len(),
+                trace_length,
+                "all register traces must have the same length"
+            );
+        }
+
+        let padded_length = cmp::max(
+            trace_length.next_power_of_two(),
+            TraceInfo::MIN_TRACE_LENGTH,
+        );
+        let mut current = registers
+            .iter()
+            .map(|register| register[trace_length - 1])
+            .collect::<Vec<_>>();
+        let mut next = vec![B::ZERO; registers.len()];
+        for _ in trace_length..padded_length {
+            pad_row(&current, &mut next);
+            for (register, &value) in registers.iter_mut().zip(next.iter()) {
+                register.push(value);
+            }
+            core::mem::swap(&mut current, &mut next);
+        }
+
+        Self::init(registers)
+    }
+
+    /// Truncates this execution trace to the first `num_rows` rows, and extends it with stutter
+    /// rows built by `pad_row`.
+    ///
+    /// This works in the same way as [finalize_at()](ExecutionTrace::finalize_at), but the
+    /// truncated trace is extended as described for
+    /// [init_with_stutter()](ExecutionTrace::init_with_stutter). Metadata of the trace is
+    /// preserved.
+    ///
+    /// # Panics
+    /// Panics if `num_rows` is zero or greater than the length of this trace.
+    pub fn finalize_with_stutter<F>(self, num_rows: usize, pad_row: F) -> Self
+    where
+        F: FnMut(&[B], &mut [B]),
+    {
+        assert!(
+            num_rows <= self.length(),
+            "cannot finalize a trace of {} steps at step {}",
+            self.length(),
+            num_rows
+        );
+        let mut registers = self.trace;
+        for register in registers.iter_mut() {
+            register.truncate(num_rows);
+        }
+
+        let mut trace = Self::init_with_stutter(registers, pad_row);
+        trace.meta = self.meta;
+        trace
     }
 
     // DATA MUTATORS
@@ -244,6 +513,66 @@ impl<B: StarkField> ExecutionTrace<B> {
         }
     }
 
+    /// Fills all rows of a single register of the execution trace.
+    ///
+    /// The value at each step is computed by executing the provided closure `f` with the index of
+    /// the step as a parameter.
+    ///
+    /// # Panics
+    /// Panics if `register` is out of bounds for this execution trace.
+    pub fn fill_column<F>(&mut self, register: usize, f: F)
+    where
+        F: Fn(usize) -> B,
+    {
+        assert!(
+            register < self.width(),
+            "register index must be smaller than {}, but was {}",
+            self.width(),
+            register
+        );
+        for (step, value) in self.trace[register].iter_mut().enumerate() {
+            *value = f(step);
+        }
+    }
+
+    /// Fills all rows in the execution trace by applying a step function to the state of the
+    /// computation.
+    ///
+    /// The first row of the trace is set to `initial`; every subsequent row is set to the state
+    /// returned by the `step` closure when it is executed with the previous row.
+    ///
+    /// # Panics
+    /// Panics if `initial` or any of the states returned by `step` does not contain exactly one
+    /// value per register of this execution trace.
+    pub fn fill_columns_from_state<S>(&mut self, initial: Vec<B>, step: S)
+    where
+        S: Fn(&[B]) -> Vec<B>,
+    {
+        let width = self.width();
+        assert_eq!(
+            width,
+            initial.len(),
+            "initial state must contain {} values, but contained {}",
+            width,
+            initial.len()
+        );
+        self.update_row(0, &initial);
+
+        let mut state = initial;
+        for i in 0..self.length() - 1 {
+            state = step(&state);
+            assert_eq!(
+                width,
+                state.len(),
+                "state at step {} must contain {} values, but contained {}",
+                i + 1,
+                width,
+                state.len()
+            );
+            self.update_row(i + 1, &state);
+        }
+    }
+
     /// Updates a single row in the execution trace with provided data.
     pub fn update_row(&mut self, step: usize, state: &[B]) {
         for (register, &value) in self.trace.iter_mut().zip(state) {
@@ -331,6 +660,7 @@ impl<B: StarkField> ExecutionTrace<B> {
     /// Returns trace info for this execution trace.
     pub fn get_info(&self) -> TraceInfo {
         TraceInfo::with_meta(self.width(), self.length(), self.meta.clone())
+            .with_num_padding_rows(self.num_padding_rows)
     }
 
     /// Returns number of registers in the trace table.
@@ -365,15 +695,228 @@ impl<B: StarkField> ExecutionTrace<B> {
         &self.meta
     }
 
+    /// Returns the number of padding rows appended to this execution trace by
+    /// [ExecutionTrace::init_with_padding()].
+    pub fn num_padding_rows(&self) -> usize {
+        self.num_padding_rows
+    }
+
+    // COMPARISON
+    // --------------------------------------------------------------------------------------------
+
+    /// Checks if this execution trace is equal to the `other` execution trace, and panics if not.
+    ///
+    /// This is intended for use in tests: unlike `assert_eq!(a, b)`, this method does not print
+    /// the entire traces, but rather the location of the first mismatching value together with
+    /// a few rows around it.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * Widths or lengths of the traces are not the same.
+    /// * Metadata of the traces is not the same.
+    /// * Values of the traces differ in any register at any step.
+    pub fn assert_eq(&self, other: &Self) {
+        assert_eq!(
+            self.width(),
+            other.width(),
+            "trace widths differ: {} vs. {}",
+            self.width(),
+            other.width()
+        );
+        assert_eq!(
+            self.length(),
+            other.length(),
+            "trace lengths differ: {} vs. {}",
+            self.length(),
+            other.length()
+        );
+        assert!(
+            self.meta == other.meta,
+            "trace metadata differs: {:?} vs. {:?}",
+            self.meta,
+            other.meta
+        );
+
+        for step in 0..self.length() {
+            for register in 0..self.width() {
+                if self.get(register, step) != other.get(register, step) {
+                    panic!(
+                        "traces differ at step {}, register {}: {} vs. {}\n{}",
+                        step,
+                        register,
+                        self.get(register, step),
+                        other.get(register, step),
+                        self.format_diff(other, step)
+                    );
+                }
+            }
+        }
+    }
+
     // VALIDATION
     // --------------------------------------------------------------------------------------------
 
+    /// Checks if this execution trace satisfies all of the specified `assertions` (boundary
+    /// constraints), which are usually obtained via [Air::get_assertions()].
+    ///
+    /// Unlike [validate()](ExecutionTrace::validate), this reads only the asserted cells of the
+    /// trace, and thus, is cheap enough to be performed for every proof.
+    ///
+    /// # Errors
+    /// Returns an error describing the first asserted cell whose value in this trace is different
+    /// from the asserted value.
+    pub fn check_assertions(&self, assertions: &[Assertion<B>]) -> Result<(), ProverError> {
+        check_assertions(assertions, self.length(), |register, step| {
+            self.get(register, step)
+        })
+    }
+
     /// Checks if this execution trace is valid against the specified AIR, and panics if not.
     ///
+    /// This is equivalent to [check_constraints()](ExecutionTrace::check_constraints) except
+    /// that the report of violated constraints is turned into a panic.
+    ///
     /// NOTE: this is a very expensive operation and is intended for use only in debug mode.
     pub fn validate<A: Air<BaseElement = B>>(&self, air: &A) {
-        // TODO: eventually, this should return errors instead of panicking
+        if let Err(report) = self.check_constraints(air) {
+            panic!("trace does not satisfy constraints of the AIR: {}", report);
+        }
+    }
 
+    /// Checks this execution trace against all constraints of the specified AIR, and returns a
+    /// report describing each of the violated constraints.
+    ///
+    /// This checks transition constraints, assertions, and domain point constraints of the main
+    /// trace segment; constraints against the auxiliary trace segment cannot be checked as the
+    /// segment is built only during proof generation. For each violated constraint, the report
+    /// records the first step at which the constraint is violated. Unlike the checks performed
+    /// by the prover, this is done in release builds as well.
+    ///
+    /// NOTE: transition constraints are evaluated at every step of the trace, and thus, this is
+    /// an expensive operation (though much cheaper than generating a proof).
+    ///
+    /// # Errors
+    /// Returns a [TraceValidationReport] if this trace violates any of the constraints.
+    ///
+    /// # Panics
+    /// Panics if the width of this trace is different from the trace width of the AIR.
+    pub fn check_constraints<A: Air<BaseElement = B>>(
+        &self,
+        air: &A,
+    ) -> Result<(), TraceValidationReport> {
+        // --- 1. check transition constraints ----------------------------------------------------
+        let mut violations = self
+            .find_transition_violations(air)
+            .into_iter()
+            .enumerate()
+            .filter_map(|(constraint, step)| {
+                step.map(|step| ConstraintViolation::TransitionConstraint { constraint, step })
+            })
+            .collect::<Vec<_>>();
+
+        // --- 2. check padding constraints -------------------------------------------------------
+        let num_padding_rows = air.context().num_padding_rows();
+        if let Some((register, step)) = self.find_padding_violation(num_padding_rows) {
+            violations.push(ConstraintViolation::PaddingConstraint { register, step });
+        }
+
+        // --- 3. check assertions ----------------------------------------------------------------
+        for assertion in air.get_assertions() {
+            let mut violation = None;
+            assertion.apply(self.length(), |step, expected| {
+                let actual = self.get(assertion.register(), step);
+                if violation.is_none() && actual != expected {
+                    violation = Some(ConstraintViolation::Assertion {
+                        register: assertion.register(),
+                        step,
+                        expected: expected.to_string(),
+                        actual: actual.to_string(),
+                    });
+                }
+            });
+            violations.extend(violation);
+        }
+
+        // --- 4. check domain point constraints --------------------------------------------------
+        // domain points may be outside of the trace domain, and thus, trace polynomials need to
+        // be interpolated to check these constraints
+        let constraints = air.get_domain_point_constraints::<B>();
+        if !constraints.is_empty() {
+            let inv_twiddles = fft::get_inv_twiddles::<B>(self.length());
+            let polys = self
+                .trace
+                .iter()
+                .map(|column| {
+                    let mut poly = column.clone();
+                    fft::interpolate_poly(&mut poly, &inv_twiddles);
+                    poly
+                })
+                .collect::<Vec<_>>();
+            for (index, constraint) in constraints.into_iter().enumerate() {
+                let point = constraint
+                    .domain_point()
+                    .expect("domain point constraint must have a domain point");
+                let state = polys
+                    .iter()
+                    .map(|poly| polynom::eval(poly, point))
+                    .collect::<Vec<_>>();
+                let expected = constraint.poly()[0];
+                let actual = constraint.get_trace_value(&state);
+                if actual != expected {
+                    violations.push(ConstraintViolation::DomainPointConstraint {
+                        index,
+                        register: constraint.register(),
+                        expected: expected.to_string(),
+                        actual: actual.to_string(),
+                    });
+                }
+            }
+        }
+
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(TraceValidationReport::new(violations))
+        }
+    }
+
+    /// Checks if this execution trace satisfies all transition constraints and padding
+    /// constraints of the specified AIR.
+    ///
+    /// NOTE: this is a very expensive operation and is intended for use only in debug mode.
+    ///
+    /// # Errors
+    /// Returns an error describing the first step at which any of the transition constraints
+    /// or padding constraints does not evaluate to zero.
+    ///
+    /// # Panics
+    /// Panics if the width of this trace is different from the trace width of the AIR.
+    pub(crate) fn validate_transitions<A: Air<BaseElement = B>>(
+        &self,
+        air: &A,
+    ) -> Result<(), ProverError> {
+        let padding_violation = self
+            .find_padding_violation(air.context().num_padding_rows())
+            .map(|(_, step)| step);
+        match self
+            .find_transition_violations(air)
+            .into_iter()
+            .flatten()
+            .chain(padding_violation)
+            .min()
+        {
+            Some(step) => Err(ProverError::UnsatisfiedTransitionConstraintError(step)),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns the first step at which each of the main transition constraints of the specified
+    /// AIR is violated by this trace, or None for constraints which are satisfied at all steps
+    /// at which they are enforced.
+    ///
+    /// # Panics
+    /// Panics if the width of this trace is different from the trace width of the AIR.
+    fn find_transition_violations<A: Air<BaseElement = B>>(&self, air: &A) -> Vec<Option<usize>> {
         // make sure the width align; if they don't something went terribly wrong
         assert_eq!(
             self.width(),
@@ -383,21 +926,6 @@ impl<B: StarkField> ExecutionTrace<B> {
             air.trace_width()
         );
 
-        // --- 1. make sure the assertions are valid ----------------------------------------------
-        for assertion in air.get_assertions() {
-            assertion.apply(self.length(), |step, value| {
-                assert!(
-                    value == self.get(assertion.register(), step),
-                    "trace does not satisfy assertion trace({}, {}) == {}",
-                    assertion.register(),
-                    step,
-                    value
-                );
-            });
-        }
-
-        // --- 2. make sure this trace satisfies all transition constraints -----------------------
-
         // collect the info needed to build periodic values for a specific step
         let g = air.trace_domain_generator();
         let periodic_values_polys = air.get_periodic_column_polys();
@@ -405,10 +933,21 @@ impl<B: StarkField> ExecutionTrace<B> {
 
         // initialize buffers to hold evaluation frames and results of constraint evaluations
         let mut x = B::ONE;
-        let mut ev_frame = EvaluationFrame::new(self.width());
-        let mut evaluations = vec![B::ZERO; air.num_transition_constraints()];
+        let mut ev_frame = EvaluationFrame::with_size(self.width(), air.frame_size());
+        // constraints against the auxiliary trace segment cannot be validated here because the
+        // auxiliary segment is built only after the main segment is committed to
+        let num_constraints = air.context().num_main_transition_constraints();
+        let mut evaluations = vec![B::ZERO; num_constraints];
+        let mut violations = vec![None; num_constraints];
 
-        for step in 0..self.length() - 1 {
+        // transition constraints are not enforced on the last `frame_size - 1` steps and on any of
+        // the padding rows, and thus, evaluation frames never wrap around; in addition, some
+        // constraints may be exempted from more steps at the end of the trace
+        let num_steps = self.length() - air.context().num_transition_exemptions();
+        let num_enforced_steps = (0..num_constraints)
+            .map(|i| self.length() - air.context().num_constraint_exemptions(i))
+            .collect::<Vec<_>>();
+        for step in 0..num_steps {
             // build periodic values
             for (p, v) in periodic_values_polys.iter().zip(periodic_values.iter_mut()) {
                 let num_cycles = air.trace_length() / p.len();
@@ -417,25 +956,41 @@ impl<B: StarkField> ExecutionTrace<B> {
             }
 
             // build evaluation frame
-            self.read_row_into(step, ev_frame.current_mut());
-            self.read_row_into(step + 1, ev_frame.next_mut());
+            for i in 0..ev_frame.size() {
+                self.read_row_into(step + i, ev_frame.row_mut(i));
+            }
 
             // evaluate transition constraints
             air.evaluate_transition(&ev_frame, &periodic_values, &mut evaluations);
 
-            // make sure all constraints evaluated to ZERO
-            for (i, &evaluation) in evaluations.iter().enumerate() {
-                assert!(
-                    evaluation == B::ZERO,
-                    "transition constraint {} did not evaluate to ZERO at step {}",
-                    i,
-                    step
-                );
+            // record the first step at which a constraint enforced at this step did not
+            // evaluate to ZERO
+            for ((violation, &evaluation), &num_enforced) in violations
+                .iter_mut()
+                .zip(evaluations.iter())
+                .zip(num_enforced_steps.iter())
+            {
+                if violation.is_none() && step < num_enforced && evaluation != B::ZERO {
+                    *violation = Some(step);
+                }
             }
 
             // update x coordinate of the domain
             x *= g;
         }
+        violations
+    }
+
+    /// Returns the register and the first step at which the next row of this trace differs from
+    /// the current row for steps at which padding constraints are enforced, or None if the last
+    /// `num_padding_rows` rows of this trace are equal to the row preceding them.
+    fn find_padding_violation(&self, num_padding_rows: usize) -> Option<(usize, usize)> {
+        let first_step = self.length().checked_sub(num_padding_rows + 1)?;
+        (first_step..self.length() - 1).find_map(|step| {
+            (0..self.width())
+                .find(|&register| self.get(register, step + 1) != self.get(register, step))
+                .map(|register| (register, step))
+        })
     }
 
     // LOW-DEGREE EXTENSION
@@ -462,10 +1017,41 @@ impl<B: StarkField> ExecutionTrace<B> {
             .collect();
 
         (
-            TraceTable::new(extended_trace, domain.trace_to_lde_blowup()),
+            TraceTable::new(
+                extended_trace,
+                domain.trace_to_lde_blowup(),
+                domain.offset(),
+            ),
             TracePolyTable::new(self.trace),
         )
     }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns rows of this and the `other` trace around the specified `step` formatted for
+    /// display; mismatching rows are marked with `>`.
+    fn format_diff(&self, other: &Self, step: usize) -> String {
+        let first_step = step.saturating_sub(DIFF_CONTEXT_ROWS);
+        let last_step = cmp::min(step + DIFF_CONTEXT_ROWS, self.length() - 1);
+
+        let mut this_row = vec![B::ZERO; self.width()];
+        let mut other_row = vec![B::ZERO; self.width()];
+        let mut result = String::new();
+        for i in first_step..=last_step {
+            self.read_row_into(i, &mut this_row);
+            other.read_row_into(i, &mut other_row);
+            let marker = if this_row == other_row { ' ' } else { '>' };
+            result.push_str(&format!(
+                "{} step {}:\n    left:  {}\n    right: {}\n",
+                marker,
+                i,
+                format_row(&this_row),
+                format_row(&other_row)
+            ));
+        }
+        result
+    }
 }
 
 // TRACE FRAGMENTS
@@ -551,12 +1137,46 @@ impl<'a, B: StarkField> ExecutionTraceFragment<'a, B> {
 // HELPER FUNCTIONS
 // ================================================================================================
 
+/// Checks whether the values read via `read_cell` satisfy the specified `assertions`, and
+/// returns an error describing the first asserted cell with a different value.
+pub(super) fn check_assertions<E, F>(
+    assertions: &[Assertion<E>],
+    trace_length: usize,
+    read_cell: F,
+) -> Result<(), ProverError>
+where
+    E: FieldElement,
+    F: Fn(usize, usize) -> E,
+{
+    for assertion in assertions {
+        let mut error = None;
+        assertion.apply(trace_length, |step, expected| {
+            let actual = read_cell(assertion.register(), step);
+            if error.is_none() && actual != expected {
+                error = Some(ProverError::BoundaryConstraintNotSatisfied {
+                    col: assertion.register(),
+                    step,
+                    expected: expected.to_string(),
+                    actual: actual.to_string(),
+                });
+            }
+        });
+        if let Some(error) = error {
+            return Err(error);
+        }
+    }
+    Ok(())
+}
+
+/// Interpolates the specified register trace into a polynomial (in-place) and returns
+/// evaluations of the polynomial over the LDE domain; values of the register may be elements
+/// of an extension of the base field.
 #[inline(always)]
-fn extend_register<B: StarkField>(
-    trace: &mut [B],
+pub(super) fn extend_register<B: StarkField, E: FieldElement<BaseField = B>>(
+    trace: &mut [E],
     domain: &StarkDomain<B>,
     inv_twiddles: &[B],
-) -> Vec<B> {
+) -> Vec<E> {
     let domain_offset = domain.offset();
     let twiddles = domain.trace_twiddles();
     let blowup_factor = domain.trace_to_lde_blowup();
@@ -568,3 +1188,9 @@ fn extend_register<B: StarkField>(
     // domain_offset
     fft::evaluate_poly_with_offset(trace, twiddles, domain_offset, blowup_factor)
 }
+
+/// Formats the specified trace row as a comma-separated list of values.
+fn format_row<B: StarkField>(row: &[B]) -> String {
+    let values: Vec<String> = row.iter().map(|value| value.to_string()).collect();
+    format!("[{}]", values.join(", "))
+}
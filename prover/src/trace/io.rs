@@ -0,0 +1,75 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::ExecutionTrace;
+use math::StarkField;
+use std::io::{self, Write};
+
+// CSV EXPORT
+// ================================================================================================
+
+impl<B: StarkField> ExecutionTrace<B> {
+    /// Writes this execution trace into the specified `writer` in CSV format.
+    ///
+    /// The first row written is a header containing the specified column names, or "col_0",
+    /// "col_1" etc. if no names are provided. The header is followed by one row per each step of
+    /// the trace, with values of all registers at that step written in decimal.
+    ///
+    /// Column names which contain commas, quotes, or line breaks are quoted.
+    ///
+    /// # Errors
+    /// Returns an error if writing into the `writer` fails.
+    ///
+    /// # Panics
+    /// Panics if `column_names` are provided but their number is not equal to the width of this
+    /// trace.
+    pub fn write_csv<W: Write>(
+        &self,
+        mut writer: W,
+        column_names: Option<&[&str]>,
+    ) -> io::Result<()> {
+        let header = match column_names {
+            Some(names) => {
+                assert_eq!(
+                    self.width(),
+                    names.len(),
+                    "expected {} column names, but received {}",
+                    self.width(),
+                    names.len()
+                );
+                names
+                    .iter()
+                    .map(|name| escape_csv_field(name))
+                    .collect::<Vec<_>>()
+            }
+            None => (0..self.width()).map(|i| format!("col_{}", i)).collect(),
+        };
+        writeln!(writer, "{}", header.join(","))?;
+
+        let mut row = vec![B::ZERO; self.width()];
+        for step in 0..self.length() {
+            self.read_row_into(step, &mut row);
+            let values = row
+                .iter()
+                .map(|value| value.to_string())
+                .collect::<Vec<_>>();
+            writeln!(writer, "{}", values.join(","))?;
+        }
+
+        writer.flush()
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Quotes the specified field if it contains characters which have special meaning in CSV.
+fn escape_csv_field(field: &str) -> String {
+    if field.contains(&[',', '"', '\n', '\r'][..]) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
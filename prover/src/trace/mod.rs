@@ -11,8 +11,18 @@ pub use trace_table::TraceTable;
 mod poly_table;
 pub use poly_table::TracePolyTable;
 
+mod aux_segment;
+pub use aux_segment::AuxSegmentBuilder;
+pub(crate) use aux_segment::AuxTraceSegment;
+
 mod execution_trace;
 pub use execution_trace::{ExecutionTrace, ExecutionTraceFragment};
 
+mod validation;
+pub use validation::{ConstraintViolation, TraceValidationReport};
+
+#[cfg(feature = "csv")]
+mod io;
+
 #[cfg(test)]
 mod tests;
@@ -3,20 +3,32 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
+use super::ExecutionTrace;
 use air::EvaluationFrame;
-use math::{log2, polynom, FieldElement, StarkField};
-use utils::{collections::Vec, iter};
+use math::{fft, log2, polynom, FieldElement, StarkField};
+use utils::{collections::Vec, iter, iter_mut};
 
 #[cfg(feature = "concurrent")]
 use utils::iterators::*;
 
 // POLYNOMIAL TABLE
 // ================================================================================================
-pub struct TracePolyTable<B: StarkField>(Vec<Vec<B>>);
+/// A table of polynomials interpolated from registers of an execution trace.
+///
+/// Polynomials are stored in coefficient form, one polynomial per trace register. The degree of
+/// each polynomial is smaller than the length of the trace from which it was interpolated.
+///
+/// Polynomials of the main trace segment have coefficients in the base field, while polynomials
+/// of the auxiliary trace segment have coefficients in the field in which the protocol is
+/// executed (`E` may be an extension field).
+///
+/// A table can be built directly from an [ExecutionTrace] via the [From] trait. This can be
+/// used to examine trace polynomials of a computation without generating a proof.
+pub struct TracePolyTable<E: FieldElement>(Vec<Vec<E>>);
 
-impl<B: StarkField> TracePolyTable<B> {
+impl<E: FieldElement> TracePolyTable<E> {
     /// Creates a new table of trace polynomials from the provided vectors.
-    pub fn new(polys: Vec<Vec<B>>) -> Self {
+    pub fn new(polys: Vec<Vec<E>>) -> Self {
         assert!(
             !polys.is_empty(),
             "trace polynomial table must contain at least one polynomial"
@@ -43,15 +55,33 @@ impl<B: StarkField> TracePolyTable<B> {
     }
 
     /// Evaluates all trace polynomials the the specified point `x`.
-    pub fn evaluate_at<E: FieldElement<BaseField = B>>(&self, x: E) -> Vec<E> {
+    pub fn evaluate_at<F>(&self, x: F) -> Vec<F>
+    where
+        F: FieldElement<BaseField = E::BaseField> + From<E>,
+    {
         iter!(self.0).map(|p| polynom::eval(p, x)).collect()
     }
 
     /// Returns an out-of-domain evaluation frame constructed by evaluating trace polynomials
-    /// for all registers at points z and z * g, where g is the generator of the trace domain.
-    pub fn get_ood_frame<E: FieldElement<BaseField = B>>(&self, z: E) -> EvaluationFrame<E> {
-        let g = E::from(B::get_root_of_unity(log2(self.poly_size())));
-        EvaluationFrame::from_rows(self.evaluate_at(z), self.evaluate_at(z * g))
+    /// for all registers at points z, z * g, ..., z * g^(frame_size - 1), where g is the
+    /// generator of the trace domain.
+    ///
+    /// # Panics
+    /// Panics if `frame_size` is smaller than 2.
+    pub fn get_ood_frame<F>(&self, z: F, frame_size: usize) -> EvaluationFrame<F>
+    where
+        F: FieldElement<BaseField = E::BaseField> + From<E>,
+    {
+        let g = F::from(E::BaseField::get_root_of_unity(log2(self.poly_size())));
+        let mut x = z;
+        let rows = (0..frame_size)
+            .map(|_| {
+                let row = self.evaluate_at(x);
+                x *= g;
+                row
+            })
+            .collect();
+        EvaluationFrame::from_row_list(rows)
     }
 
     /// Returns the number of trace polynomials in the table.
@@ -60,13 +90,60 @@ impl<B: StarkField> TracePolyTable<B> {
     }
 
     /// Returns a trace polynomial at the specified index.
-    #[cfg(test)]
-    pub fn get_poly(&self, idx: usize) -> &[B] {
+    pub fn get_poly(&self, idx: usize) -> &[E] {
         &self.0[idx]
     }
 
+    /// Returns coefficients of the trace polynomial for the specified column.
+    ///
+    /// Coefficients are in ascending order of degree: the coefficient at index 0 is the constant
+    /// term, and the coefficient at index `i` is the coefficient of $x^i$.
+    ///
+    /// # Panics
+    /// Panics if `col` is not smaller than the number of polynomials in the table.
+    pub fn get_column_poly(&self, col: usize) -> &[E] {
+        assert!(
+            col < self.num_polys(),
+            "column index must be smaller than {}, but was {}",
+            self.num_polys(),
+            col
+        );
+        &self.0[col]
+    }
+
+    /// Evaluates the trace polynomial for the specified column at point `x` using Horner's
+    /// method.
+    ///
+    /// As with [get_column_poly()](TracePolyTable::get_column_poly), the coefficient at index 0
+    /// of the polynomial is its constant term.
+    ///
+    /// # Panics
+    /// Panics if `col` is not smaller than the number of polynomials in the table.
+    pub fn eval_at<F>(&self, col: usize, x: F) -> F
+    where
+        F: FieldElement<BaseField = E::BaseField> + From<E>,
+    {
+        polynom::eval(self.get_column_poly(col), x)
+    }
+
     /// Converts this table into a vector of polynomials.
-    pub fn into_vec(self) -> Vec<Vec<B>> {
+    pub fn into_vec(self) -> Vec<Vec<E>> {
         self.0
     }
 }
+
+// CONVERSIONS
+// ================================================================================================
+
+impl<B: StarkField> From<&ExecutionTrace<B>> for TracePolyTable<B> {
+    /// Interpolates all registers of the specified execution trace into polynomials over the trace
+    /// domain using inverse FFT.
+    fn from(trace: &ExecutionTrace<B>) -> Self {
+        let inv_twiddles = fft::get_inv_twiddles::<B>(trace.length());
+        let mut polys = (0..trace.width())
+            .map(|i| trace.get_register(i).to_vec())
+            .collect::<Vec<_>>();
+        iter_mut!(polys).for_each(|poly| fft::interpolate_poly(poly, &inv_twiddles));
+        TracePolyTable::new(polys)
+    }
+}
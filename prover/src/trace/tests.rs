@@ -5,12 +5,13 @@
 
 use crate::{
     tests::{build_fib_trace, MockAir},
-    StarkDomain,
+    ExecutionTrace, StarkDomain, TracePolyTable,
 };
+use core::cmp;
 use crypto::{hashers::Blake3_256, ElementHasher, MerkleTree};
 use math::{
-    fields::f128::BaseElement, get_power_series, get_power_series_with_offset, log2, polynom,
-    FieldElement, StarkField,
+    fields::{f128::BaseElement, QuadExtension},
+    get_power_series, get_power_series_with_offset, log2, polynom, FieldElement, StarkField,
 };
 use utils::collections::Vec;
 
@@ -80,6 +81,85 @@ fn extend_trace_table() {
     );
 }
 
+#[test]
+fn trace_polys_from_trace_table() {
+    let trace_length = 8;
+    let air = MockAir::with_trace_length(trace_length);
+    let trace = build_fib_trace(trace_length * 2);
+    let trace_polys = TracePolyTable::from(&trace);
+
+    // polynomials should be the same as the ones produced during trace extension
+    let domain = StarkDomain::new(&air);
+    let (_, expected_polys) = build_fib_trace(trace_length * 2).extend(&domain);
+    assert_eq!(expected_polys.num_polys(), trace_polys.num_polys());
+    assert_eq!(expected_polys.get_poly(0), trace_polys.get_poly(0));
+    assert_eq!(expected_polys.get_poly(1), trace_polys.get_poly(1));
+
+    // all polynomials should have degree smaller than trace length
+    for i in 0..trace_polys.num_polys() {
+        assert!(polynom::degree_of(trace_polys.get_poly(i)) < trace_length);
+    }
+}
+
+#[test]
+fn trace_poly_column_access() {
+    let trace_length = 8;
+    let trace = build_fib_trace(trace_length * 2);
+    let trace_polys = TracePolyTable::from(&trace);
+
+    // at x = 0 a polynomial evaluates to its constant term, and at x = 1 to the sum of its
+    // coefficients
+    for col in 0..trace_polys.num_polys() {
+        let poly = trace_polys.get_column_poly(col);
+        assert_eq!(trace_polys.get_poly(col), poly);
+        assert_eq!(poly[0], trace_polys.eval_at(col, BaseElement::ZERO));
+        assert_eq!(
+            poly.iter().fold(BaseElement::ZERO, |acc, &c| acc + c),
+            trace_polys.eval_at(col, BaseElement::ONE)
+        );
+    }
+
+    // at points of the trace domain, polynomials evaluate to the values of the trace
+    let g = BaseElement::get_root_of_unity(log2(trace_length));
+    for step in 0..trace_length {
+        let x = g.exp((step as u64).into());
+        assert_eq!(trace.get(0, step), trace_polys.eval_at(0, x));
+        assert_eq!(trace.get(1, step), trace_polys.eval_at(1, x));
+    }
+}
+
+#[test]
+#[should_panic(expected = "column index must be smaller than 2, but was 2")]
+fn trace_poly_column_out_of_bounds() {
+    TracePolyTable::from(&build_fib_trace(16)).eval_at(2, BaseElement::ONE);
+}
+
+#[test]
+fn trace_table_eval_at() {
+    let trace_length = 8;
+    let air = MockAir::with_trace_length(trace_length);
+    let trace = build_fib_trace(trace_length * 2);
+    let domain = StarkDomain::new(&air);
+    let (extended_trace, trace_polys) = trace.extend(&domain);
+
+    // evaluations recovered from the extended trace are the same as evaluations of trace
+    // polynomials both in the base field and in the extension field
+    let x = BaseElement::new(7);
+    let z = QuadExtension::new(BaseElement::new(3), BaseElement::new(5));
+    for col in 0..extended_trace.width() {
+        assert_eq!(trace_polys.eval_at(col, x), extended_trace.eval_at(col, x));
+        assert_eq!(trace_polys.eval_at(col, z), extended_trace.eval_at(col, z));
+    }
+}
+
+#[test]
+#[should_panic(expected = "column index must be smaller than 2, but was 2")]
+fn trace_table_eval_at_out_of_bounds() {
+    let air = MockAir::with_trace_length(8);
+    let (extended_trace, _) = build_fib_trace(16).extend(&StarkDomain::new(&air));
+    extended_trace.eval_at(2, BaseElement::ONE);
+}
+
 #[test]
 fn commit_trace_table() {
     // build and extend trace table
@@ -109,6 +189,180 @@ fn commit_trace_table() {
     assert_eq!(expected_tree.root(), trace_tree.root())
 }
 
+#[test]
+fn compare_trace_tables() {
+    let trace = build_fib_trace(32);
+    trace.assert_eq(&build_fib_trace(32));
+    assert!(trace == build_fib_trace(32));
+
+    let mut other = build_fib_trace(32);
+    other.set(1, 11, BaseElement::ZERO);
+    assert!(trace != other);
+    assert!(trace != build_fib_trace(64));
+
+    let mut other = build_fib_trace(32);
+    other.set_meta(vec![1, 2, 3]);
+    assert!(trace != other);
+}
+
+#[test]
+#[should_panic(expected = "traces differ at step 11, register 1")]
+fn compare_trace_tables_value_mismatch() {
+    let trace = build_fib_trace(32);
+    let mut other = build_fib_trace(32);
+    other.set(1, 11, BaseElement::ZERO);
+    trace.assert_eq(&other);
+}
+
+#[test]
+#[should_panic(expected = "trace lengths differ: 16 vs. 32")]
+fn compare_trace_tables_length_mismatch() {
+    build_fib_trace(32).assert_eq(&build_fib_trace(64));
+}
+
+#[test]
+fn fill_trace_columns() {
+    let mut trace = ExecutionTrace::new(2, 16);
+    trace.fill_columns_from_state(vec![BaseElement::ONE, BaseElement::ONE], |state| {
+        let reg1 = state[0] + state[1];
+        vec![reg1, reg1 + state[1]]
+    });
+    trace.assert_eq(&build_fib_trace(32));
+
+    trace.fill_column(1, |step| BaseElement::from(step as u64));
+    for step in 0..trace.length() {
+        assert_eq!(build_fib_trace(32).get(0, step), trace.get(0, step));
+        assert_eq!(BaseElement::from(step as u64), trace.get(1, step));
+    }
+}
+
+#[test]
+#[should_panic(expected = "state at step 1 must contain 2 values, but contained 1")]
+fn fill_trace_columns_state_width_mismatch() {
+    let mut trace = ExecutionTrace::new(2, 16);
+    trace.fill_columns_from_state(vec![BaseElement::ONE, BaseElement::ONE], |state| {
+        vec![state[0]]
+    });
+}
+
+#[test]
+fn init_trace_from_rows() {
+    // the trace is longer than a single transpose block
+    let expected = build_fib_trace(512);
+    let rows = (0..expected.length()).map(|step| {
+        let mut row = vec![BaseElement::ZERO; 2];
+        expected.read_row_into(step, &mut row);
+        row
+    });
+    expected.assert_eq(&ExecutionTrace::init_from_rows(rows));
+}
+
+#[test]
+#[should_panic(expected = "all rows must contain 2 values, but a row contained 1")]
+fn init_trace_from_rows_width_mismatch() {
+    let mut rows = vec![vec![BaseElement::ONE; 2]; 8];
+    rows[5].pop();
+    ExecutionTrace::init_from_rows(rows);
+}
+
+#[test]
+fn init_trace_with_padding() {
+    let expected = build_fib_trace(32);
+    let registers = (0..expected.width())
+        .map(|i| expected.get_register(i)[..12].to_vec())
+        .collect::<Vec<_>>();
+
+    // 12 steps are extended to 16 steps by repeating the last row
+    let trace = ExecutionTrace::init_with_padding(registers);
+    assert_eq!(16, trace.length());
+    assert_eq!(4, trace.num_padding_rows());
+    assert_eq!(4, trace.get_info().num_padding_rows());
+    for step in 0..trace.length() {
+        let source_step = cmp::min(step, 11);
+        assert_eq!(expected.get(0, source_step), trace.get(0, step));
+        assert_eq!(expected.get(1, source_step), trace.get(1, step));
+    }
+
+    // short traces are extended to the minimum trace length, and traces of a valid length are
+    // not extended at all
+    let trace = ExecutionTrace::init_with_padding(vec![vec![BaseElement::ONE; 3]]);
+    assert_eq!(8, trace.length());
+    assert_eq!(5, trace.num_padding_rows());
+    let trace = ExecutionTrace::init_with_padding(vec![vec![BaseElement::ONE; 16]]);
+    assert_eq!(16, trace.length());
+    assert_eq!(0, trace.num_padding_rows());
+}
+
+#[test]
+#[should_panic(expected = "all register traces must have the same length")]
+fn init_trace_with_padding_length_mismatch() {
+    ExecutionTrace::init_with_padding(vec![vec![BaseElement::ONE; 3], vec![BaseElement::ONE; 4]]);
+}
+
+#[test]
+fn finalize_trace_at_row() {
+    let mut trace = ExecutionTrace::with_meta(2, 32, vec![1, 2, 3]);
+    trace.fill_columns_from_state(vec![BaseElement::ONE, BaseElement::ONE], |state| {
+        let reg1 = state[0] + state[1];
+        vec![reg1, reg1 + state[1]]
+    });
+    let expected = build_fib_trace(64);
+
+    // the computation terminates after 11 steps, and the trace is padded to 16 steps
+    let trace = trace.finalize_at(11);
+    assert_eq!(16, trace.length());
+    assert_eq!(5, trace.num_padding_rows());
+    assert_eq!(&[1, 2, 3], trace.get_meta());
+    for step in 0..trace.length() {
+        let source_step = cmp::min(step, 10);
+        assert_eq!(expected.get(0, source_step), trace.get(0, step));
+        assert_eq!(expected.get(1, source_step), trace.get(1, step));
+    }
+
+    // finalizing at a power of two does not add padding rows
+    let trace = build_fib_trace(64).finalize_at(16);
+    assert_eq!(16, trace.length());
+    assert_eq!(0, trace.num_padding_rows());
+}
+
+#[test]
+#[should_panic(expected = "cannot finalize a trace of 32 steps at step 33")]
+fn finalize_trace_at_row_out_of_bounds() {
+    build_fib_trace(64).finalize_at(33);
+}
+
+#[test]
+fn finalize_trace_with_stutter() {
+    let mut trace = ExecutionTrace::with_meta(2, 32, vec![1, 2, 3]);
+    trace.fill_columns_from_state(vec![BaseElement::ONE, BaseElement::ONE], |state| {
+        let reg1 = state[0] + state[1];
+        vec![reg1, reg1 + state[1]]
+    });
+    let expected = build_fib_trace(64);
+
+    // after 11 steps, the first register is kept unchanged while the second one is incremented
+    let trace = trace.finalize_with_stutter(11, |current, next| {
+        next[0] = current[0];
+        next[1] = current[1] + BaseElement::ONE;
+    });
+    assert_eq!(16, trace.length());
+    assert_eq!(0, trace.num_padding_rows());
+    assert_eq!(&[1, 2, 3], trace.get_meta());
+    for step in 0..trace.length() {
+        let source_step = cmp::min(step, 10);
+        let increment = BaseElement::from((step - source_step) as u8);
+        assert_eq!(expected.get(0, source_step), trace.get(0, step));
+        assert_eq!(expected.get(1, source_step) + increment, trace.get(1, step));
+    }
+
+    // short traces are extended to the minimum trace length
+    let trace = ExecutionTrace::init_with_stutter(vec![vec![BaseElement::ONE; 3]], |c, n| {
+        n[0] = c[0].double()
+    });
+    assert_eq!(8, trace.length());
+    assert_eq!(BaseElement::from(32u8), trace.get(0, 7));
+}
+
 // HELPER FUNCTIONS
 // ================================================================================================
 
@@ -116,3 +370,24 @@ fn build_lde_domain<B: StarkField>(domain_size: usize) -> Vec<B> {
     let g = B::get_root_of_unity(log2(domain_size));
     get_power_series_with_offset(g, B::GENERATOR, domain_size)
 }
+
+#[test]
+#[cfg(feature = "csv")]
+fn write_trace_table_csv() {
+    let trace = build_fib_trace(16);
+
+    let mut csv = Vec::new();
+    trace.write_csv(&mut csv, None).unwrap();
+    let csv = String::from_utf8(csv).unwrap();
+    let lines = csv.lines().collect::<Vec<_>>();
+    assert_eq!(trace.length() + 1, lines.len());
+    assert_eq!("col_0,col_1", lines[0]);
+    assert_eq!("1,1", lines[1]);
+    assert_eq!("2,3", lines[2]);
+    assert_eq!("610,987", lines[8]);
+
+    let mut csv = Vec::new();
+    trace.write_csv(&mut csv, Some(&["a", "b, c"])).unwrap();
+    let csv = String::from_utf8(csv).unwrap();
+    assert!(csv.starts_with("a,\"b, c\"\n1,1\n2,3\n"));
+}
@@ -5,7 +5,7 @@
 
 use air::{proof::Queries, EvaluationFrame};
 use crypto::{ElementHasher, Hasher, MerkleTree};
-use math::StarkField;
+use math::{fft, polynom, FieldElement};
 use utils::{batch_iter_mut, collections::Vec, uninit_vector};
 
 #[cfg(feature = "concurrent")]
@@ -13,17 +13,28 @@ use utils::iterators::*;
 
 // TRACE TABLE
 // ================================================================================================
-pub struct TraceTable<B: StarkField> {
-    data: Vec<Vec<B>>,
+/// A table of trace registers evaluated over the LDE domain.
+///
+/// Values of the main trace segment are elements of the base field, while values of the auxiliary
+/// trace segment are elements of the field in which the protocol is executed (`E` may be an
+/// extension field).
+pub struct TraceTable<E: FieldElement> {
+    data: Vec<Vec<E>>,
     blowup: usize,
+    domain_offset: E::BaseField,
 }
 
-impl<B: StarkField> TraceTable<B> {
+impl<E: FieldElement> TraceTable<E> {
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
-    /// Creates a new trace table from a list of provided register traces.
-    pub(super) fn new(data: Vec<Vec<B>>, blowup: usize) -> Self {
-        TraceTable { data, blowup }
+    /// Creates a new trace table from a list of provided register traces evaluated over the LDE
+    /// domain with the specified offset.
+    pub(crate) fn new(data: Vec<Vec<E>>, blowup: usize, domain_offset: E::BaseField) -> Self {
+        TraceTable {
+            data,
+            blowup,
+            domain_offset,
+        }
     }
 
     // PUBLIC ACCESSORS
@@ -46,36 +57,65 @@ impl<B: StarkField> TraceTable<B> {
     }
 
     /// Returns value in the specified `register` at the specified `step`.
-    pub fn get(&self, register: usize, step: usize) -> B {
+    pub fn get(&self, register: usize, step: usize) -> E {
         self.data[register][step]
     }
 
     /// Returns the entire register trace for the register at the specified index.
-    #[cfg(test)]
-    pub fn get_register(&self, idx: usize) -> &[B] {
+    pub fn get_register(&self, idx: usize) -> &[E] {
         &self.data[idx]
     }
 
     /// Copies values of all registers at the specified `step` into the `destination` slice.
-    pub fn read_row_into(&self, step: usize, row: &mut [B]) {
+    pub fn read_row_into(&self, step: usize, row: &mut [E]) {
         for (register, value) in self.data.iter().zip(row.iter_mut()) {
             *value = register[step];
         }
     }
 
-    /// Reads current and next rows from the execution trace table into the specified frame.
-    pub fn read_frame_into(&self, lde_step: usize, frame: &mut EvaluationFrame<B>) {
-        // at the end of the trace, next state wraps around and we read the first step again
-        let next_lde_step = (lde_step + self.blowup()) % self.len();
+    /// Reads consecutive rows of the execution trace starting at the specified step of the LDE
+    /// domain into the specified frame; the number of rows read is equal to the frame size.
+    pub fn read_frame_into(&self, lde_step: usize, frame: &mut EvaluationFrame<E>) {
+        // consecutive trace rows are `blowup` steps apart in the LDE domain; at the end of the
+        // trace, the frame wraps around and we read the first steps again
+        for i in 0..frame.size() {
+            let row_lde_step = (lde_step + i * self.blowup()) % self.len();
+            self.read_row_into(row_lde_step, frame.row_mut(i));
+        }
+    }
+
+    // POLYNOMIAL EVALUATION
+    // --------------------------------------------------------------------------------------------
 
-        self.read_row_into(lde_step, frame.current_mut());
-        self.read_row_into(next_lde_step, frame.next_mut());
+    /// Evaluates the trace polynomial for the specified column at point `x`.
+    ///
+    /// The polynomial is recovered by interpolating the extended column over the LDE domain, and
+    /// thus, this requires O(n log n) operations, where n is the size of the LDE domain. When
+    /// trace polynomials are available, [TracePolyTable::eval_at()](crate::TracePolyTable::eval_at)
+    /// should be used instead.
+    ///
+    /// # Panics
+    /// Panics if `col` is not smaller than the number of registers in the table.
+    pub fn eval_at<F>(&self, col: usize, x: F) -> F
+    where
+        F: FieldElement<BaseField = E::BaseField> + From<E>,
+    {
+        assert!(
+            col < self.width(),
+            "column index must be smaller than {}, but was {}",
+            self.width(),
+            col
+        );
+        let mut poly = self.data[col].clone();
+        let inv_twiddles = fft::get_inv_twiddles::<E::BaseField>(poly.len());
+        fft::interpolate_poly_with_offset(&mut poly, &inv_twiddles, self.domain_offset);
+        polynom::eval(&poly, x)
     }
 
     // TRACE COMMITMENT
     // --------------------------------------------------------------------------------------------
     /// Builds a Merkle tree out of trace table rows (hash of each row becomes a leaf in the tree).
-    pub fn build_commitment<H: ElementHasher<BaseField = B>>(&self) -> MerkleTree<H> {
+    pub fn build_commitment<H: ElementHasher<BaseField = E::BaseField>>(&self) -> MerkleTree<H> {
         // allocate vector to store row hashes
         let mut hashed_states = unsafe { uninit_vector::<H::Digest>(self.len()) };
 
@@ -86,7 +126,7 @@ impl<B: StarkField> TraceTable<B> {
             &mut hashed_states,
             128, // min batch size
             |batch: &mut [H::Digest], batch_offset: usize| {
-                let mut trace_state = vec![B::ZERO; self.width()];
+                let mut trace_state = vec![E::ZERO; self.width()];
                 for (i, row_hash) in batch.iter_mut().enumerate() {
                     self.read_row_into(i + batch_offset, &mut trace_state);
                     *row_hash = H::hash_elements(&trace_state);
@@ -0,0 +1,101 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use core::fmt;
+use utils::{collections::Vec, string::String};
+
+// TRACE VALIDATION REPORT
+// ================================================================================================
+
+/// A report describing constraints of a computation which are not satisfied by an execution
+/// trace.
+///
+/// The report is returned from [ExecutionTrace::check_constraints()](crate::ExecutionTrace::check_constraints)
+/// and [validate_trace()](crate::validate_trace). It lists violated constraints in the following
+/// order: transition constraints (by constraint index), assertions (in the order in which they
+/// are returned from [Air::get_assertions()](air::Air::get_assertions)), and domain point
+/// constraints (in the order in which they are returned from
+/// [Air::get_domain_point_constraints()](air::Air::get_domain_point_constraints)).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceValidationReport {
+    violations: Vec<ConstraintViolation>,
+}
+
+impl TraceValidationReport {
+    /// Returns a new report for the specified list of violations.
+    pub(crate) fn new(violations: Vec<ConstraintViolation>) -> Self {
+        TraceValidationReport { violations }
+    }
+
+    /// Returns the list of constraints violated by the execution trace.
+    pub fn violations(&self) -> &[ConstraintViolation] {
+        &self.violations
+    }
+}
+
+impl fmt::Display for TraceValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} constraint(s) not satisfied", self.violations.len())?;
+        for violation in self.violations.iter() {
+            write!(f, "\n  - {}", violation)?;
+        }
+        Ok(())
+    }
+}
+
+// CONSTRAINT VIOLATION
+// ================================================================================================
+
+/// Describes a single constraint which is not satisfied by an execution trace.
+///
+/// Field element values are recorded in their string representation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConstraintViolation {
+    /// A transition constraint does not evaluate to zero at some step at which it is enforced;
+    /// `step` is the first such step.
+    TransitionConstraint { constraint: usize, step: usize },
+    /// A padding row of the execution trace is different from the row preceding it; `step` is
+    /// the first step at which the next row of the trace differs from the current row in
+    /// `register`.
+    PaddingConstraint { register: usize, step: usize },
+    /// A value in the execution trace is different from the value asserted for this cell;
+    /// `step` is the first asserted step at which the values differ.
+    Assertion {
+        register: usize,
+        step: usize,
+        expected: String,
+        actual: String,
+    },
+    /// A trace polynomial (or a linear combination of trace polynomials) does not evaluate to
+    /// the value required by a domain point constraint; `index` is the position of the
+    /// constraint in the list returned from
+    /// [Air::get_domain_point_constraints()](air::Air::get_domain_point_constraints).
+    DomainPointConstraint {
+        index: usize,
+        register: usize,
+        expected: String,
+        actual: String,
+    },
+}
+
+impl fmt::Display for ConstraintViolation {
+    #[rustfmt::skip]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TransitionConstraint { constraint, step } => {
+                write!(f, "transition constraint {} does not evaluate to zero at step {}", constraint, step)
+            }
+            Self::PaddingConstraint { register, step } => {
+                write!(f, "padding row at step {} differs from the preceding row in register {}", step + 1, register)
+            }
+            Self::Assertion { register, step, expected, actual } => {
+                write!(f, "assertion against register {} is not satisfied at step {}; expected {}, but was {}", register, step, expected, actual)
+            }
+            Self::DomainPointConstraint { index, register, expected, actual } => {
+                write!(f, "domain point constraint {} against register {} is not satisfied; expected {}, but was {}", index, register, expected, actual)
+            }
+        }
+    }
+}
@@ -76,6 +76,30 @@ impl Serializable for () {
     fn write_into<W: ByteWriter>(&self, _target: &mut W) {}
 }
 
+impl Serializable for u8 {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u8(*self);
+    }
+}
+
+impl Serializable for u16 {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u16(*self);
+    }
+}
+
+impl Serializable for u32 {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u32(*self);
+    }
+}
+
+impl Serializable for u64 {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u64(*self);
+    }
+}
+
 impl<T: Serializable> Serializable for Vec<T> {
     fn write_into<W: ByteWriter>(&self, target: &mut W) {
         T::write_batch_into(self, target);
@@ -160,6 +184,30 @@ pub trait Deserializable: Sized {
     }
 }
 
+impl Deserializable for u8 {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        source.read_u8()
+    }
+}
+
+impl Deserializable for u16 {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        source.read_u16()
+    }
+}
+
+impl Deserializable for u32 {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        source.read_u32()
+    }
+}
+
+impl Deserializable for u64 {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        source.read_u64()
+    }
+}
+
 // BYTE READER
 // ================================================================================================
 
@@ -393,6 +441,40 @@ impl ByteWriter for Vec<u8> {
     }
 }
 
+// COUNTING WRITER
+// ================================================================================================
+
+/// Implements [ByteWriter] trait by counting the bytes written into it without storing them.
+///
+/// This can be used to determine the exact size of a serialized value (e.g., a proof) without
+/// allocating memory for its bytes.
+#[derive(Debug, Default)]
+pub struct CountingWriter {
+    bytes_written: usize,
+}
+
+impl CountingWriter {
+    /// Creates a new counting writer with the number of written bytes set to zero.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of bytes written into this writer.
+    pub fn bytes_written(&self) -> usize {
+        self.bytes_written
+    }
+}
+
+impl ByteWriter for CountingWriter {
+    fn write_u8(&mut self, _value: u8) {
+        self.bytes_written += 1;
+    }
+
+    fn write_u8_slice(&mut self, values: &[u8]) {
+        self.bytes_written += values.len();
+    }
+}
+
 // AS BYTES
 // ================================================================================================
 
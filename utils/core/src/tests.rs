@@ -3,7 +3,10 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::{collections::Vec, ByteReader, ByteWriter, Serializable, SliceReader};
+use super::{
+    collections::Vec, ByteReader, ByteWriter, CountingWriter, Deserializable, Serializable,
+    SliceReader,
+};
 
 // VECTOR UTILS TESTS
 // ================================================================================================
@@ -28,6 +31,20 @@ fn group_vector_elements() {
     }
 }
 
+// SERIALIZATION TESTS
+// ================================================================================================
+
+#[test]
+fn serialize_integer_vector() {
+    let a = vec![1u64, 12345678910, u64::MAX];
+    let bytes = a.to_bytes();
+    assert_eq!(a.len() * 8, bytes.len());
+
+    let mut reader = SliceReader::new(&bytes);
+    assert_eq!(a, u64::read_batch_from(&mut reader, a.len()).unwrap());
+    assert!(u64::read_from(&mut reader).is_err());
+}
+
 // SLICE READER TESTS
 // ================================================================================================
 
@@ -147,3 +164,23 @@ fn write_serializable_array_batch() {
         assert_eq!(i, reader.read_u128().unwrap());
     }
 }
+
+// COUNTING WRITER TESTS
+// ================================================================================================
+
+#[test]
+fn counting_writer() {
+    let mut writer = CountingWriter::new();
+    assert_eq!(0, writer.bytes_written());
+
+    writer.write_u8(1);
+    writer.write_u16(2);
+    writer.write_u32(3);
+    writer.write_u64(4);
+    writer.write_u8_slice(&[5, 6, 7]);
+    assert_eq!(18, writer.bytes_written());
+
+    // the number of counted bytes is the same as the number of serialized bytes
+    writer.write(234567u128);
+    assert_eq!(18 + 234567u128.to_bytes().len(), writer.bytes_written());
+}
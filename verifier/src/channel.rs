@@ -5,7 +5,7 @@
 
 use crate::VerifierError;
 use air::{proof::StarkProof, Air, EvaluationFrame};
-use crypto::{BatchMerkleProof, ElementHasher, MerkleTree};
+use crypto::{BatchMerkleProof, ElementHasher, MerkleTree, PublicCoin};
 use fri::VerifierChannel as FriVerifierChannel;
 use math::{FieldElement, StarkField};
 use utils::{collections::Vec, string::ToString};
@@ -13,6 +13,12 @@ use utils::{collections::Vec, string::ToString};
 // TYPES AND INTERFACES
 // ================================================================================================
 
+/// A view into a [StarkProof] which provides the verifier with the data sent by the prover in
+/// the order in which it is needed by the protocol.
+///
+/// In addition to the data contained in the proof, the channel keeps track of query positions
+/// drawn by the verifier; these are derived from the transcript, and thus, are the same positions
+/// against which the prover opened its commitments.
 pub struct VerifierChannel<B, E, H>
 where
     B: StarkField,
@@ -23,6 +29,10 @@ where
     trace_root: H::Digest,
     trace_proof: BatchMerkleProof<H>,
     trace_states: Option<Vec<Vec<B>>>,
+    // auxiliary trace queries
+    aux_trace_root: Option<H::Digest>,
+    aux_trace_proof: Option<BatchMerkleProof<H>>,
+    aux_trace_states: Option<Vec<Vec<E>>>,
     // constraint queries
     constraint_root: H::Digest,
     constraint_proof: BatchMerkleProof<H>,
@@ -38,6 +48,8 @@ where
     ood_evaluations: Option<Vec<E>>,
     // query proof-of-work
     pow_nonce: u64,
+    // query positions
+    query_positions: Option<Vec<usize>>,
 }
 
 // VERIFIER CHANNEL IMPLEMENTATION
@@ -58,15 +70,35 @@ where
             return Err(VerifierError::InconsistentBaseField);
         }
 
+        // make sure the proof contains an auxiliary trace segment of the width declared by AIR
+        let aux_trace_width = air.auxiliary_trace_width();
+        if proof.context.aux_trace_width() != aux_trace_width {
+            return Err(VerifierError::ProofDeserializationError(format!(
+                "auxiliary trace segment must contain {} registers, but was {}",
+                aux_trace_width,
+                proof.context.aux_trace_width()
+            )));
+        }
+
         let lde_domain_size = air.lde_domain_size();
         let num_queries = air.options().num_queries();
-        let fri_options = air.options().to_fri_options();
+        let fri_options = air.options().to_fri_options::<A::BaseElement>();
 
         // --- parse commitments ------------------------------------------------------------------
-        let (trace_root, constraint_root, fri_roots) = proof
-            .commitments
-            .parse::<H>(fri_options.num_fri_layers(lde_domain_size))
-            .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
+        let num_fri_layers = fri_options.num_fri_layers(lde_domain_size);
+        let (trace_root, aux_trace_root, constraint_root, fri_roots) = if aux_trace_width > 0 {
+            let (trace_root, aux_trace_root, constraint_root, fri_roots) = proof
+                .commitments
+                .parse_with_aux_trace::<H>(num_fri_layers)
+                .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
+            (trace_root, Some(aux_trace_root), constraint_root, fri_roots)
+        } else {
+            let (trace_root, constraint_root, fri_roots) = proof
+                .commitments
+                .parse::<H>(num_fri_layers)
+                .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
+            (trace_root, None, constraint_root, fri_roots)
+        };
 
         // --- parse trace queries ----------------------------------------------------------------
         let (trace_proof, trace_states) = proof
@@ -79,6 +111,27 @@ where
                 ))
             })?;
 
+        // --- parse auxiliary trace queries ------------------------------------------------------
+        let (aux_trace_proof, aux_trace_states) = match proof.aux_trace_queries {
+            Some(aux_trace_queries) if aux_trace_width > 0 => {
+                let (aux_trace_proof, aux_trace_states) = aux_trace_queries
+                    .parse::<H, E>(lde_domain_size, num_queries, aux_trace_width)
+                    .map_err(|err| {
+                        VerifierError::ProofDeserializationError(format!(
+                            "auxiliary trace query deserialization failed: {}",
+                            err
+                        ))
+                    })?;
+                (Some(aux_trace_proof), Some(aux_trace_states))
+            }
+            None if aux_trace_width == 0 => (None, None),
+            _ => {
+                return Err(VerifierError::ProofDeserializationError(
+                    "auxiliary trace queries are inconsistent with the proof context".to_string(),
+                ))
+            }
+        };
+
         // --- parse constraint evaluation queries ------------------------------------------------
         let (constraint_proof, constraint_evaluations) = proof
             .constraint_queries
@@ -98,13 +151,28 @@ where
             .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
         let (fri_layer_queries, fri_layer_proofs) = proof
             .fri_proof
-            .parse_layers::<H, E>(lde_domain_size, fri_options.folding_factor())
+            .parse_layers::<H, E>(lde_domain_size, &fri_options)
             .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
 
         // --- parse out-of-domain evaluation frame -----------------------------------------------
-        let (ood_frame, ood_evaluations) = proof
-            .ood_frame
-            .parse(air.trace_width(), air.ce_blowup_factor())
+        let proof_ood_frame = &proof.ood_frame;
+        if proof_ood_frame.num_trace_rows() != air.frame_size() {
+            return Err(VerifierError::ProofDeserializationError(format!(
+                "out-of-domain frame must contain {} trace rows, but was {}",
+                air.frame_size(),
+                proof_ood_frame.num_trace_rows()
+            )));
+        }
+        // rows of the frame contain values of the auxiliary trace segment after the values of
+        // the main segment
+        let ood_trace_width = air.trace_width() + aux_trace_width;
+        let ood_trace_rows = (0..air.frame_size())
+            .map(|i| proof_ood_frame.parse_trace_row(i, ood_trace_width))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
+        let ood_frame = EvaluationFrame::from_row_list(ood_trace_rows);
+        let ood_evaluations = proof_ood_frame
+            .parse_evaluations(air.ce_blowup_factor())
             .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
 
         Ok(VerifierChannel {
@@ -112,6 +180,10 @@ where
             trace_root,
             trace_proof,
             trace_states: Some(trace_states),
+            // auxiliary trace queries
+            aux_trace_root,
+            aux_trace_proof,
+            aux_trace_states,
             // constraint queries
             constraint_root,
             constraint_proof,
@@ -127,6 +199,8 @@ where
             ood_evaluations: Some(ood_evaluations),
             // query seed
             pow_nonce: proof.pow_nonce,
+            // query positions
+            query_positions: None,
         })
     }
 
@@ -138,13 +212,20 @@ where
         self.trace_root
     }
 
+    /// Returns auxiliary trace segment commitment sent by the prover, or None if the computation
+    /// does not have an auxiliary trace segment.
+    pub fn read_aux_trace_commitment(&self) -> Option<H::Digest> {
+        self.aux_trace_root
+    }
+
     /// Returns constraint evaluation commitment sent by the prover.
     pub fn read_constraint_commitment(&self) -> H::Digest {
         self.constraint_root
     }
 
     /// Returns trace polynomial evaluations at out-of-domain points z and z * g, where g is the
-    /// generator of the LDE domain.
+    /// generator of the LDE domain; values of the auxiliary trace segment (if any) follow the
+    /// values of the main segment in each row of the frame.
     pub fn read_ood_evaluation_frame(&mut self) -> EvaluationFrame<E> {
         self.ood_frame.take().expect("already read")
     }
@@ -160,6 +241,37 @@ where
         self.pow_nonce
     }
 
+    /// Draws `num_queries` pseudo-random query positions for an LDE domain of the specified size
+    /// from the `public_coin`, saves them in this channel, and returns them.
+    ///
+    /// The public coin is expected to be seeded with all of the data sent by the prover up to and
+    /// including the query proof-of-work nonce.
+    ///
+    /// # Errors
+    /// Returns an error if the positions could not be drawn from the `public_coin`.
+    pub fn read_query_positions<R: PublicCoin<BaseField = B, Hasher = H>>(
+        &mut self,
+        public_coin: &mut R,
+        num_queries: usize,
+        lde_domain_size: usize,
+    ) -> Result<Vec<usize>, VerifierError> {
+        let positions = public_coin
+            .draw_integers(num_queries, lde_domain_size)
+            .map_err(|_| VerifierError::RandomCoinError)?;
+        self.query_positions = Some(positions.clone());
+        Ok(positions)
+    }
+
+    /// Returns query positions drawn via [read_query_positions()](Self::read_query_positions).
+    ///
+    /// # Panics
+    /// Panics if query positions have not been drawn yet.
+    pub fn get_query_positions(&self) -> &[usize] {
+        self.query_positions
+            .as_ref()
+            .expect("query positions have not been drawn yet")
+    }
+
     /// Returns trace states at the specified positions of the LDE domain. This also checks if
     /// the trace states are valid against the trace commitment sent by the prover.
     pub fn read_trace_states(
@@ -174,6 +286,27 @@ where
         Ok(self.trace_states.take().expect("already read"))
     }
 
+    /// Returns auxiliary trace segment states at the specified positions of the LDE domain. This
+    /// also checks if the states are valid against the auxiliary trace commitment sent by the
+    /// prover.
+    ///
+    /// # Panics
+    /// Panics if the computation does not have an auxiliary trace segment.
+    pub fn read_aux_trace_states(
+        &mut self,
+        positions: &[usize],
+        commitment: &H::Digest,
+    ) -> Result<Vec<Vec<E>>, VerifierError> {
+        let aux_trace_proof = self
+            .aux_trace_proof
+            .as_ref()
+            .expect("no auxiliary trace segment");
+        MerkleTree::verify_batch(commitment, positions, aux_trace_proof)
+            .map_err(|_| VerifierError::TraceQueryDoesNotMatchCommitment)?;
+
+        Ok(self.aux_trace_states.take().expect("already read"))
+    }
+
     /// Returns constraint evaluations at the specified positions of the LDE domain. This also
     /// checks if the constraint evaluations are valid against the constraint commitment sent by
     /// the prover.
@@ -0,0 +1,72 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crypto::{ElementHasher, Hasher, PublicCoin, RandomCoin, RandomCoinError};
+use math::{FieldElement, StarkField};
+use utils::collections::Vec;
+
+// VERSIONED COIN
+// ================================================================================================
+/// A [RandomCoin] which replays the transcript of a proof of a specific format version.
+///
+/// Proofs of format versions 1 and 2 were generated without domain separation tags in the proof
+/// transcript; for such proofs, the coin absorbs only the data passed to
+/// [reseed_with_tag()](PublicCoin::reseed_with_tag) and ignores the tag.
+pub struct VersionedCoin<B: StarkField, H: ElementHasher<BaseField = B>> {
+    coin: RandomCoin<B, H>,
+    is_domain_separated: bool,
+}
+
+impl<B: StarkField, H: ElementHasher<BaseField = B>> VersionedCoin<B, H> {
+    /// Returns a new coin instantiated with the provided `seed`; domain separation tags are
+    /// absorbed into the coin only if `is_domain_separated` is true.
+    pub fn with_domain_separation(seed: &[u8], is_domain_separated: bool) -> Self {
+        VersionedCoin {
+            coin: RandomCoin::new(seed),
+            is_domain_separated,
+        }
+    }
+}
+
+impl<B: StarkField, H: ElementHasher<BaseField = B>> PublicCoin for VersionedCoin<B, H> {
+    type BaseField = B;
+    type Hasher = H;
+
+    fn new(seed: &[u8]) -> Self {
+        Self::with_domain_separation(seed, true)
+    }
+
+    fn reseed_with_tag(&mut self, tag: &[u8; 4], data: <Self::Hasher as Hasher>::Digest) {
+        if self.is_domain_separated {
+            self.coin.reseed_with_tag(tag, data);
+        } else {
+            self.coin.reseed(data);
+        }
+    }
+
+    fn reseed_with_int(&mut self, value: u64) {
+        self.coin.reseed_with_int(value)
+    }
+
+    fn leading_zeros(&self) -> u32 {
+        self.coin.leading_zeros()
+    }
+
+    fn check_leading_zeros(&self, value: u64) -> u32 {
+        self.coin.check_leading_zeros(value)
+    }
+
+    fn draw<E: FieldElement<BaseField = B>>(&mut self) -> Result<E, RandomCoinError> {
+        self.coin.draw()
+    }
+
+    fn draw_integers(
+        &mut self,
+        num_values: usize,
+        domain_size: usize,
+    ) -> Result<Vec<usize>, RandomCoinError> {
+        self.coin.draw_integers(num_values, domain_size)
+    }
+}
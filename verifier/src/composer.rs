@@ -16,6 +16,7 @@ pub struct DeepComposer<A: Air, E: FieldElement + From<A::BaseElement>> {
     x_coordinates: Vec<A::BaseElement>,
     z: E,
     next_z: E,
+    additional_z: Vec<E>,
 }
 
 impl<A: Air, E: FieldElement + From<A::BaseElement>> DeepComposer<A, E> {
@@ -34,12 +35,25 @@ impl<A: Air, E: FieldElement + From<A::BaseElement>> DeepComposer<A, E> {
             .map(|&p| g_lde.exp((p as u64).into()) * domain_offset)
             .collect();
 
+        // compute out-of-domain points z * g^k for rows of the evaluation frame beyond the
+        // first two
+        let g = E::from(air.trace_domain_generator());
+        let next_z = z * g;
+        let mut row_z = next_z;
+        let additional_z = (2..air.frame_size())
+            .map(|_| {
+                row_z *= g;
+                row_z
+            })
+            .collect();
+
         DeepComposer {
             field_extension: air.options().field_extension(),
             cc,
             x_coordinates,
             z,
-            next_z: z * E::from(air.trace_domain_generator()),
+            next_z,
+            additional_z,
         }
     }
 
@@ -56,43 +70,46 @@ impl<A: Air, E: FieldElement + From<A::BaseElement>> DeepComposer<A, E> {
     /// - In cases when the proof was generated using an extension field, we also compute
     ///   T'''_i(x) = (T_i(x) - T_i(z_conjugate)) / (x - z_conjugate), and add it to T(x) similarly
     ///   to the way described above. This is needed in order to verify that the trace is defined
-    ///   over the base field, rather than the extension field.
+    ///   over the base field, rather than the extension field. Registers of the auxiliary trace
+    ///   segment are defined over the extension field, and thus, are not included into T'''(x).
+    /// - For evaluation frames with more than two rows, we also compute
+    ///   (T_i(x) - T_i(z * g^k)) / (x - z * g^k) for each additional row k, and add them to T(x)
+    ///   similarly to the way described above.
     ///
-    /// Note that values of T_i(z) and T_i(z * g) are received from teh prover and passed into
-    /// this function via the `ood_frame` parameter.
+    /// Note that values of T_i(z), T_i(z * g) etc. are received from teh prover and passed into
+    /// this function via the `ood_frame` parameter; in each row of the frame, values of the
+    /// auxiliary segment registers follow values of the main segment registers. Queried states
+    /// of the auxiliary segment are set only for computations with an auxiliary trace segment.
     pub fn compose_registers(
         &self,
         queried_trace_states: Vec<Vec<A::BaseElement>>,
+        queried_aux_trace_states: Option<Vec<Vec<E>>>,
         ood_frame: EvaluationFrame<E>,
     ) -> Vec<E> {
-        let trace_at_z1 = ood_frame.current();
-        let trace_at_z2 = ood_frame.next();
-
         // when field extension is enabled, these will be set to conjugates of trace values at
         // z as well as conjugate of z itself
-        let conjugate_values = get_conjugate_values(self.field_extension, trace_at_z1, self.z);
+        let conjugate_values =
+            get_conjugate_values(self.field_extension, ood_frame.current(), self.z);
+        let conjugate_values = conjugate_values
+            .as_ref()
+            .map(|(z_conjugate, values)| (*z_conjugate, values.as_slice()));
 
         let mut result = Vec::with_capacity(queried_trace_states.len());
-        for (registers, &x) in queried_trace_states.iter().zip(&self.x_coordinates) {
+        for (j, (registers, &x)) in queried_trace_states
+            .iter()
+            .zip(&self.x_coordinates)
+            .enumerate()
+        {
             let x = E::from(x);
             let mut composition = E::ZERO;
             for (i, &value) in registers.iter().enumerate() {
-                let value = E::from(value);
-                // compute T'_i(x) = (T_i(x) - T_i(z)) / (x - z)
-                let t1 = (value - trace_at_z1[i]) / (x - self.z);
-                // multiply it by a pseudo-random coefficient, and add the result to T(x)
-                composition += t1 * self.cc.trace[i].0;
-
-                // compute T''_i(x) = (T_i(x) - T_i(z * g)) / (x - z * g)
-                let t2 = (value - trace_at_z2[i]) / (x - self.next_z);
-                // multiply it by a pseudo-random coefficient, and add the result to T(x)
-                composition += t2 * self.cc.trace[i].1;
-
-                // when extension field is enabled compute
-                // T'''_i(x) = (T_i(x) - T_i(z_conjugate)) / (x - z_conjugate)
-                if let Some((z_conjugate, ref trace_at_z1_conjugates)) = conjugate_values {
-                    let t3 = (value - trace_at_z1_conjugates[i]) / (x - z_conjugate);
-                    composition += t3 * self.cc.trace[i].2;
+                composition +=
+                    self.compose_register(E::from(value), i, x, &ood_frame, conjugate_values);
+            }
+            if let Some(aux_states) = queried_aux_trace_states.as_ref() {
+                for (i, &value) in aux_states[j].iter().enumerate() {
+                    let register = registers.len() + i;
+                    composition += self.compose_register(value, register, x, &ood_frame, None);
                 }
             }
 
@@ -102,6 +119,46 @@ impl<A: Air, E: FieldElement + From<A::BaseElement>> DeepComposer<A, E> {
         result
     }
 
+    /// Returns the contribution of the specified `register` with the queried `value` at `x` to
+    /// T(x) as described in [compose_registers()](DeepComposer::compose_registers);
+    /// `conjugate_values` are set only if T'''(x) should be included into the contribution.
+    fn compose_register(
+        &self,
+        value: E,
+        register: usize,
+        x: E,
+        ood_frame: &EvaluationFrame<E>,
+        conjugate_values: Option<(E, &[E])>,
+    ) -> E {
+        let i = register;
+
+        // compute T'_i(x) = (T_i(x) - T_i(z)) / (x - z)
+        let t1 = (value - ood_frame.current()[i]) / (x - self.z);
+        // multiply it by a pseudo-random coefficient, and add the result to T(x)
+        let mut composition = t1 * self.cc.trace[i].0;
+
+        // compute T''_i(x) = (T_i(x) - T_i(z * g)) / (x - z * g)
+        let t2 = (value - ood_frame.next()[i]) / (x - self.next_z);
+        // multiply it by a pseudo-random coefficient, and add the result to T(x)
+        composition += t2 * self.cc.trace[i].1;
+
+        // when extension field is enabled compute
+        // T'''_i(x) = (T_i(x) - T_i(z_conjugate)) / (x - z_conjugate)
+        if let Some((z_conjugate, trace_at_z1_conjugates)) = conjugate_values {
+            let t3 = (value - trace_at_z1_conjugates[i]) / (x - z_conjugate);
+            composition += t3 * self.cc.trace[i].2;
+        }
+
+        // for additional rows of the evaluation frame, compute
+        // (T_i(x) - T_i(z * g^k)) / (x - z * g^k)
+        for (k, &row_z) in self.additional_z.iter().enumerate() {
+            let tn = (value - ood_frame.row(k + 2)[i]) / (x - row_z);
+            composition += tn * self.cc.additional_trace[k][i];
+        }
+
+        composition
+    }
+
     /// For each queried set of composition polynomial column evaluations, combine evaluations
     /// into a single value by computing their random linear combination as follows:
     ///
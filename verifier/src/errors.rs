@@ -5,8 +5,12 @@
 
 //! Contains common error types for prover and verifier.
 
+use air::{EvaluationError, HashFunction};
 use core::fmt;
-use utils::string::String;
+use utils::{collections::Vec, string::String};
+
+#[cfg(not(feature = "std"))]
+use alloc::boxed::Box;
 
 // VERIFIER ERROR
 // ================================================================================================
@@ -16,6 +20,38 @@ pub enum VerifierError {
     /// This error occurs when base field read by a verifier from a proof does not match the
     /// base field of AIR with which the verifier was instantiated.
     InconsistentBaseField,
+    /// This error occurs when the hasher of the public coin passed to
+    /// [verify_with_coin()](crate::verify_with_coin) is not the hash function specified in the
+    /// options of the proof.
+    InconsistentHashFunction(HashFunction),
+    /// This error occurs when a proof of the specified format version is passed to
+    /// [verify_with_coin()](crate::verify_with_coin); proofs of versions prior to 3 were not
+    /// generated with domain separation tags, and thus, can be verified only with the default
+    /// public coin.
+    UnsupportedFormatVersion(u8),
+    /// This error occurs when [Air::MAX_CONSTRAINT_DEGREE](air::Air::MAX_CONSTRAINT_DEGREE) of
+    /// the AIR with which the verifier was instantiated is not equal to the largest base degree
+    /// of the transition constraints declared in the context of the AIR.
+    InconsistentConstraintDegree {
+        /// Value of the associated constant of the AIR.
+        declared: usize,
+        /// Largest base degree declared in the context of the AIR.
+        actual: usize,
+    },
+    /// This error occurs when the AIR with which the verifier was instantiated declares
+    /// transition constraints against the auxiliary trace segment, but does not implement
+    /// [Air::evaluate_aux_transition()](air::Air::evaluate_aux_transition) (i.e., the method
+    /// returns [EvaluationError::MissingAuxTransitionEvaluator](air::EvaluationError)).
+    MissingAuxTransitionEvaluator,
+    /// This error occurs when the number of padding rows of the execution trace specified in a
+    /// proof is different from the number of padding rows declared in the context of the AIR
+    /// with which the verifier was instantiated.
+    InconsistentTracePadding {
+        /// Number of padding rows declared in the context of the AIR.
+        expected: usize,
+        /// Number of padding rows specified in the proof.
+        actual: usize,
+    },
     /// This error occurs when the base field in which the proof was generated does not support
     /// field extension of degree specified by the proof.
     UnsupportedFieldExtension(usize),
@@ -26,7 +62,11 @@ pub enum VerifierError {
     RandomCoinError,
     /// This error occurs when constraints evaluated over out-of-domain trace rows do not match
     /// evaluations of the constraint composition polynomial at the out-of-domain point.
-    InconsistentOodConstraintEvaluations,
+    ///
+    /// The first value is the serialized merged constraint evaluation computed by the verifier
+    /// from the out-of-domain trace rows, and the second value is the serialized evaluation of
+    /// the constraint composition polynomial sent by the prover.
+    OodConstraintMismatch(Vec<u8>, Vec<u8>),
     /// This error occurs when Merkle authentication paths of trace queries do not resolve to the
     /// execution trace commitment included in the proof.
     TraceQueryDoesNotMatchCommitment,
@@ -41,6 +81,43 @@ pub enum VerifierError {
     /// constraint evaluation queries do not represent a polynomial of the degree expected by the
     /// verifier.
     FriVerificationFailed(fri::VerifierError),
+    /// This error occurs when no AIR with the tag of a tagged proof has been registered with an
+    /// AIR registry.
+    UnknownAirTag(String),
+    /// This error occurs when a verifier cannot deserialize public inputs of the specified AIR.
+    PublicInputDeserializationError(String),
+    /// This error occurs when the parameters of the computation specified in a proof do not
+    /// match the parameters recorded in a verification key.
+    VerificationKeyMismatch,
+    /// This error occurs when the trace metadata specified in a proof is different from the
+    /// metadata expected by the verifier.
+    TraceMetadataMismatch,
+    /// This error occurs when a parameter of the computation specified in a proof exceeds the
+    /// corresponding [SanityBounds](crate::SanityBounds) of the verifier.
+    SuspiciousLength {
+        /// Name of the parameter.
+        field: &'static str,
+        /// Value of the parameter derived from the proof.
+        value: usize,
+        /// Largest value accepted by the verifier.
+        max: usize,
+    },
+    /// This error occurs when one of the proofs passed to [verify_batch()](crate::verify_batch)
+    /// fails verification.
+    BatchProofInvalid {
+        /// Index of the first proof in the batch which failed verification.
+        index: usize,
+        /// Error returned from verification of this proof.
+        error: Box<VerifierError>,
+    },
+}
+
+impl From<EvaluationError> for VerifierError {
+    fn from(err: EvaluationError) -> Self {
+        match err {
+            EvaluationError::MissingAuxTransitionEvaluator => Self::MissingAuxTransitionEvaluator,
+        }
+    }
 }
 
 impl fmt::Display for VerifierError {
@@ -50,6 +127,21 @@ impl fmt::Display for VerifierError {
             Self::InconsistentBaseField =>  {
                 write!(f, "base field of the proof does not match base field of the specified AIR")
             }
+            Self::InconsistentHashFunction(hash_fn) => {
+                write!(f, "hasher of the public coin is not {:?} hash function specified by the proof", hash_fn)
+            }
+            Self::UnsupportedFormatVersion(version) => {
+                write!(f, "proofs of format version {} can be verified only with the default public coin", version)
+            }
+            Self::InconsistentConstraintDegree { declared, actual } => {
+                write!(f, "declared max constraint degree {} is inconsistent with transition constraint degrees; expected {}", declared, actual)
+            }
+            Self::MissingAuxTransitionEvaluator => {
+                write!(f, "the AIR declares auxiliary transition constraints, but does not evaluate them")
+            }
+            Self::InconsistentTracePadding { expected, actual } => {
+                write!(f, "execution trace of the proof contains {} padding rows, but the AIR declares {}", actual, expected)
+            }
             Self::UnsupportedFieldExtension(degree) => {
                 write!(f, "field extension of degree {} is not supported for the proof base field", degree)
             }
@@ -59,8 +151,11 @@ impl fmt::Display for VerifierError {
             Self::RandomCoinError => {
                 write!(f, "failed to draw a random value from a random coin")
             }
-            Self::InconsistentOodConstraintEvaluations => {
-                write!(f, "constraint evaluations over the out-of-domain frame are inconsistent")
+            Self::OodConstraintMismatch(expected, actual) => {
+                write!(f, "constraint evaluations over the out-of-domain frame are inconsistent: expected 0x")?;
+                write_hex(f, expected)?;
+                write!(f, ", but composition polynomial evaluates to 0x")?;
+                write_hex(f, actual)
             }
             Self::TraceQueryDoesNotMatchCommitment => {
                 write!(f, "trace query did not match the commitment")
@@ -74,6 +169,35 @@ impl fmt::Display for VerifierError {
             Self::FriVerificationFailed(err) => {
                 write!(f, "verification of low-degree proof failed: {}", err)
             }
+            Self::UnknownAirTag(tag) => {
+                write!(f, "no AIR with tag '{}' has been registered", tag)
+            }
+            Self::PublicInputDeserializationError(msg) => {
+                write!(f, "public input deserialization failed: {}", msg)
+            }
+            Self::VerificationKeyMismatch => {
+                write!(f, "parameters of the proof do not match the verification key")
+            }
+            Self::TraceMetadataMismatch => {
+                write!(f, "trace metadata of the proof does not match the expected metadata")
+            }
+            Self::SuspiciousLength { field, value, max } => {
+                write!(f, "{} of {} exceeds the largest accepted value of {}", field, value, max)
+            }
+            Self::BatchProofInvalid { index, error } => {
+                write!(f, "verification of proof {} in the batch failed: {}", index, error)
+            }
         }
     }
 }
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Writes the specified bytes into the formatter as a hex string.
+fn write_hex(f: &mut fmt::Formatter<'_>, bytes: &[u8]) -> fmt::Result {
+    for byte in bytes {
+        write!(f, "{:02x}", byte)?;
+    }
+    Ok(())
+}
@@ -3,20 +3,33 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use air::{Air, ConstraintCompositionCoefficients, EvaluationFrame};
+use air::{
+    Air, Assertion, BoundaryConstraintGroup, ConstraintCompositionCoefficients, EvaluationError,
+    EvaluationFrame,
+};
 use math::{polynom, FieldElement};
 use utils::collections::Vec;
 
 // CONSTRAINT EVALUATION
 // ================================================================================================
 
-/// Evaluates constraints for the specified evaluation frame.
+/// Evaluates constraints for the specified evaluation frame; `assertions` must be the assertions
+/// returned from [Air::get_assertions()] for the `air`. For computations with an auxiliary trace
+/// segment, values of the segment follow values of the main segment in each row of the
+/// `ood_frame`, and `aux_assertions` and `aux_rand_elements` are the assertions against the
+/// segment and the random elements used to build the segment.
+///
+/// # Errors
+/// Returns an error if auxiliary transition constraints of the `air` could not be evaluated.
 pub fn evaluate_constraints<A: Air, E: FieldElement<BaseField = A::BaseElement>>(
     air: &A,
+    assertions: &[Assertion<A::BaseElement>],
+    aux_assertions: &[Assertion<E>],
     coefficients: ConstraintCompositionCoefficients<E>,
     ood_frame: &EvaluationFrame<E>,
+    aux_rand_elements: &[E],
     x: E,
-) -> E {
+) -> Result<E, EvaluationError> {
     // 1 ----- evaluate transition constraints ----------------------------------------------------
 
     // initialize a buffer to hold transition constraint evaluations
@@ -33,8 +46,34 @@ pub fn evaluate_constraints<A: Air, E: FieldElement<BaseField = A::BaseElement>>
         })
         .collect::<Vec<_>>();
 
-    // evaluate transition constraints over OOD evaluation frame
-    air.evaluate_transition(ood_frame, &periodic_values, &mut t_evaluations);
+    // evaluate transition constraints over OOD evaluation frame; for computations with an
+    // auxiliary trace segment, the frame is split into frames of the main and the auxiliary
+    // segments first
+    if air.auxiliary_trace_width() == 0 {
+        air.evaluate_transition(ood_frame, &periodic_values, &mut t_evaluations);
+    } else {
+        let (main_rows, aux_rows) = (0..ood_frame.size())
+            .map(|i| {
+                let (main_row, aux_row) = ood_frame.row(i).split_at(air.trace_width());
+                (main_row.to_vec(), aux_row.to_vec())
+            })
+            .unzip();
+        let main_frame = EvaluationFrame::from_row_list(main_rows);
+        let aux_frame = EvaluationFrame::from_row_list(aux_rows);
+
+        let (main_evaluations, aux_evaluations) =
+            t_evaluations.split_at_mut(air.context().num_main_transition_constraints());
+        air.evaluate_transition(&main_frame, &periodic_values, main_evaluations);
+        if !aux_evaluations.is_empty() {
+            air.evaluate_aux_transition(
+                &main_frame,
+                &aux_frame,
+                &periodic_values,
+                aux_rand_elements,
+                aux_evaluations,
+            )?;
+        }
+    }
 
     // merge all constraint evaluations into a single value by computing their random linear
     // combination using coefficients drawn from the public coin
@@ -49,17 +88,67 @@ pub fn evaluate_constraints<A: Air, E: FieldElement<BaseField = A::BaseElement>>
 
     // 2 ----- evaluate boundary constraints ------------------------------------------------------
 
-    // get boundary constraints grouped by common divisor from the AIR
-    let b_constraints = air.get_boundary_constraints(&coefficients.boundary);
+    // get boundary constraints grouped by common divisor from the AIR; coefficients for the
+    // constraints against the auxiliary trace segment follow the coefficients for all other
+    // boundary constraints
+    let (main_coefficients, aux_coefficients) = coefficients
+        .boundary
+        .split_at(coefficients.boundary.len() - aux_assertions.len());
+    let b_constraints = air.get_boundary_constraints(assertions, main_coefficients);
+    let (main_state, aux_state) = ood_frame.current().split_at(air.trace_width());
+    result += evaluate_boundary_constraints(&b_constraints, main_state, x);
+
+    // constraints against the auxiliary segment are evaluated against the values of the
+    // auxiliary segment only
+    if !aux_assertions.is_empty() {
+        let b_constraints = air.get_aux_boundary_constraints(aux_assertions, aux_coefficients);
+        result += evaluate_boundary_constraints(&b_constraints, aux_state, x);
+    }
+
+    // 3 ----- evaluate padding constraints -------------------------------------------------------
+
+    // padding constraints are defined only for padded execution traces, and only against the
+    // main segment of the trace
+    if let Some(p_constraints) = air.get_padding_constraints(&coefficients.padding) {
+        let rows = (0..ood_frame.size())
+            .map(|i| ood_frame.row(i)[..air.trace_width()].to_vec())
+            .collect();
+        let main_frame = EvaluationFrame::from_row_list(rows);
+        let z = air.padding_constraint_divisor().evaluate_at(x);
+        result += p_constraints.merge_evaluations(&main_frame, x) / z;
+    }
+
+    Ok(result)
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the sum of evaluations of the boundary constraint `groups` at `x`; `state` contains
+/// values of the trace segment against which the constraints are placed.
+fn evaluate_boundary_constraints<F, E>(
+    groups: &[BoundaryConstraintGroup<F, E>],
+    state: &[E],
+    x: E,
+) -> E
+where
+    F: FieldElement,
+    E: FieldElement<BaseField = F::BaseField> + From<F>,
+{
+    let mut result = E::ZERO;
+    let first_group = match groups.first() {
+        Some(group) => group,
+        None => return result,
+    };
 
     // iterate over boundary constraint groups (each group has a distinct divisor), evaluate
     // constraints in each group and add them to the evaluations vector
 
     // cache power of x here so that we only re-compute it when degree_adjustment changes
-    let mut degree_adjustment = b_constraints[0].degree_adjustment();
+    let mut degree_adjustment = first_group.degree_adjustment();
     let mut xp = x.exp(degree_adjustment.into());
 
-    for group in b_constraints.iter() {
+    for group in groups.iter() {
         // if adjustment degree hasn't changed, no need to recompute `xp` - so just reuse the
         // previous value; otherwise, compute new `xp`
         if group.degree_adjustment() != degree_adjustment {
@@ -67,8 +156,7 @@ pub fn evaluate_constraints<A: Air, E: FieldElement<BaseField = A::BaseElement>>
             xp = x.exp(degree_adjustment.into());
         }
         // evaluate all constraints in the group, and add the evaluation to the result
-        result += group.evaluate_at(ood_frame.current(), x, xp);
+        result += group.evaluate_at(state, x, xp);
     }
-
     result
 }
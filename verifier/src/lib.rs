@@ -15,6 +15,17 @@
 //! 2. Execute [verify()] function and supply the AIR of your computation together with the
 //!    [StarkProof] and related public inputs as parameters.
 //!
+//! If only some of the checks need to be performed (e.g., when a proof is verified as a part of
+//! a larger protocol), [verify_with_options()] function can be used instead. If the larger protocol also
+//! needs to know which positions were queried, [verify_and_get_query_positions()] returns them.
+//! When parameters of the computation are fixed by a [VerificationKey] stored separately from the
+//! proof, [verify_with_key()] makes sure that the proof matches the key. Many proofs of the same
+//! computation can be verified at once via [verify_batch()].
+//!
+//! When proofs for several different computations need to be verified, the computations can be
+//! tagged via [register_air!] macro and added to an [AirRegistry]; [AirRegistry::verify()] then
+//! selects the AIR to verify a [TaggedProof] against based on the tag of the proof.
+//!
 //! # Performance
 //! Proof verification is extremely fast and is nearly independent of the complexity of the
 //! computation being verified. In vast majority of cases proofs can be verified in 3 - 5 ms
@@ -25,6 +36,11 @@
 //! asserted values. But for the impact to be noticeable, the number of asserted values would
 //! need to be in tens of thousands. And even for hundreds of thousands of asserted values, the
 //! verification time should not exceed 50 ms.
+//!
+//! When the crate is compiled with `concurrent` feature enabled, proofs passed to
+//! [verify_batch()] are verified in multiple threads. The number of threads can be configured
+//! via `RAYON_NUM_THREADS` environment variable, and usually defaults to the number of logical
+//! cores on the machine.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -33,31 +49,49 @@
 extern crate alloc;
 
 pub use air::{
-    proof::StarkProof, Air, AirContext, Assertion, BoundaryConstraint, BoundaryConstraintGroup,
-    ConstraintCompositionCoefficients, ConstraintDivisor, DeepCompositionCoefficients,
-    EvaluationFrame, FieldExtension, HashFunction, ProofOptions, TraceInfo,
-    TransitionConstraintDegree, TransitionConstraintGroup,
+    proof::{StarkProof, TaggedProof},
+    register_air, Air, AirContext, Assertion, BoundaryConstraint, BoundaryConstraintGroup,
+    CompositeAir, CompositePublicInputs, ConstraintCompositionCoefficients, ConstraintDivisor,
+    ConstraintGroup, DeepCompositionCoefficients, EvaluationError, EvaluationFrame, FieldExtension,
+    FriRemainderEncoding, HashFunction, LagrangeBoundaryConstraints, MigrationError,
+    PaddingConstraintGroup, ProofOptions, StackedAir, StackedPublicInputs, TaggedAir, TraceInfo,
+    TransitionConstraintDegree, TransitionConstraintGroup, VerificationKey,
 };
 
 pub use math;
 use math::{
     fields::{CubeExtension, QuadExtension},
-    FieldElement,
+    FieldElement, StarkField,
 };
 
-use utils::collections::Vec;
-pub use utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
+use air::proof::{
+    AUX_TRACE_COMMITMENT_TAG, CONSTRAINT_COMMITMENT_TAG, OOD_EVALUATIONS_TAG, OOD_FRAME_TAG,
+    TRACE_COMMITMENT_TAG,
+};
+use utils::{collections::Vec, string::ToString};
+pub use utils::{
+    ByteReader, ByteWriter, CountingWriter, Deserializable, DeserializationError, Serializable,
+};
 
 pub use crypto;
 use crypto::{
-    hashers::{Blake3_192, Blake3_256, Sha3_256},
-    ElementHasher, RandomCoin,
+    hashers::{Blake3_192, Blake3_256, Keccak256, Sha3_256},
+    Digest, ElementHasher, Hasher, PublicCoin,
 };
 
 use fri::FriVerifier;
 
+#[cfg(feature = "concurrent")]
+use utils::iterators::*;
+
+#[cfg(not(feature = "std"))]
+use alloc::boxed::Box;
+
 mod channel;
-use channel::VerifierChannel;
+pub use channel::VerifierChannel;
+
+mod coin;
+use coin::VersionedCoin;
 
 mod evaluator;
 use evaluator::evaluate_constraints;
@@ -68,6 +102,15 @@ use composer::DeepComposer;
 mod errors;
 pub use errors::VerifierError;
 
+mod options;
+pub use options::{SanityBounds, VerificationOptions};
+
+mod registry;
+pub use registry::{AirRegistry, VerificationResult};
+
+#[cfg(test)]
+mod tests;
+
 // VERIFIER
 // ================================================================================================
 /// Verifies that the specified computation was executed correctly against the specified inputs.
@@ -81,85 +124,295 @@ pub use errors::VerifierError;
 /// a correct execution of the computation. This could happen for many various reasons, including:
 /// - The specified proof was generated for a different computation.
 /// - The specified proof was generated for this computation but for different public inputs.
-#[rustfmt::skip]
+/// - The number of padding rows of the execution trace specified in the proof is different from
+///   the number of padding rows declared by the `AIR` for the public inputs.
+///
+/// An error is also returned if [Air::MAX_CONSTRAINT_DEGREE] of the `AIR` is not equal to the
+/// largest base degree of the transition constraints declared in its [AirContext]; this applies
+/// to all other verification functions of this crate as well.
 pub fn verify<AIR: Air>(
     proof: StarkProof,
     pub_inputs: AIR::PublicInputs,
 ) -> Result<(), VerifierError> {
-    // build a seed for the public coin; the initial seed is the hash of public inputs and proof
-    // context, but as the protocol progresses, the coin will be reseeded with the info received
-    // from the prover
-    let mut public_coin_seed = Vec::new();
-    pub_inputs.write_into(&mut public_coin_seed);
-    proof.context.write_into(&mut public_coin_seed);
+    verify_with_options::<AIR>(proof, pub_inputs, VerificationOptions::default())
+}
 
-    // create AIR instance for the computation specified in the proof
-    let air = AIR::new(proof.get_trace_info(), pub_inputs, proof.options().clone());
+/// Verifies the specified proof performing only the checks specified by `options`.
+///
+/// When all checks are enabled (which is the default for [VerificationOptions]), this function
+/// is equivalent to [verify()]. Otherwise, successful verification only means that the checks
+/// which were not skipped have passed.
+///
+/// # Errors
+/// Returns an error if any of the checks which were not skipped by `options` fails.
+pub fn verify_with_options<AIR: Air>(
+    proof: StarkProof,
+    pub_inputs: AIR::PublicInputs,
+    options: VerificationOptions,
+) -> Result<(), VerifierError> {
+    verify_and_get_query_positions::<AIR>(proof, pub_inputs, options).map(|_| ())
+}
 
+/// Verifies the specified proof performing only the checks specified by `options`, and returns
+/// the query positions drawn by the verifier.
+///
+/// Query positions are derived from the proof transcript, and thus, are the same positions
+/// against which the prover opened its trace, constraint, and FRI commitments. This is useful
+/// for protocols which need to batch Merkle proofs across multiple commitments.
+///
+/// # Errors
+/// Returns an error if any of the checks which were not skipped by `options` fails.
+pub fn verify_and_get_query_positions<AIR: Air>(
+    proof: StarkProof,
+    pub_inputs: AIR::PublicInputs,
+    options: VerificationOptions,
+) -> Result<Vec<usize>, VerifierError> {
+    let (air, public_coin_seed) = instantiate_air::<AIR>(&proof, pub_inputs, &options)?;
+    verify_air(air, proof, public_coin_seed, options)
+}
+
+/// Verifies that the specified computation was executed correctly against the specified inputs
+/// using a custom source of verifier randomness.
+///
+/// This works in the same way as [verify()], but all verifier challenges are drawn from a
+/// public coin of type `C` rather than from the default [RandomCoin](crypto::RandomCoin), and
+/// all commitments in the `proof` are verified using `C::Hasher` (the hash function specified in
+/// the proof options is not used to select the hasher). This allows verifying proofs generated via
+/// `prove_with_coin()` function of the prover crate using the same public coin; the coin is
+/// instantiated via [PublicCoin::new()] with the same seed as the default coin.
+///
+/// Since the security level of a proof is bounded by the hash function specified in the proof
+/// options, `C::Hasher` must compute the same function as the hasher specified in the options.
+///
+/// # Errors
+/// Returns an error under the same conditions as [verify()], or if:
+/// * `C::Hasher` is not the hash function specified in the options of the `proof`.
+/// * The `proof` is of a format version which precedes domain separation of the proof
+///   transcript (see [StarkProof::FORMAT_VERSION]).
+pub fn verify_with_coin<AIR, C>(
+    proof: StarkProof,
+    pub_inputs: AIR::PublicInputs,
+) -> Result<(), VerifierError>
+where
+    AIR: Air,
+    C: PublicCoin<BaseField = AIR::BaseElement>,
+{
+    let options = VerificationOptions::default();
+    let (air, public_coin_seed) = instantiate_air::<AIR>(&proof, pub_inputs, &options)?;
+    if !proof.is_domain_separated() {
+        return Err(VerifierError::UnsupportedFormatVersion(
+            proof.format_version,
+        ));
+    }
+    if !is_hash_fn::<AIR::BaseElement, C::Hasher>(air.options().hash_fn()) {
+        return Err(VerifierError::InconsistentHashFunction(
+            air.options().hash_fn(),
+        ));
+    }
+    let public_coin_seed = prepare_coin_seed(&air, public_coin_seed, &options)?;
+
+    verify_air_with_coin(air, proof, C::new(&public_coin_seed), options).map(|_| ())
+}
+
+/// Verifies a batch of proofs of the computation specified by `AIR` type parameter; each proof
+/// is paired with the public inputs it is verified against.
+///
+/// Each proof goes through the same checks as in [verify()]: proofs of the same computation do
+/// not share any part of their transcripts (e.g., query positions are derived from commitments
+/// of each proof), and thus, no check can be skipped for any of them. When the crate is compiled
+/// with `concurrent` feature enabled, the proofs are verified in parallel.
+///
+/// # Errors
+/// Returns [VerifierError::BatchProofInvalid] for the first proof in the batch which fails
+/// verification; the error also contains the reason for the failure.
+pub fn verify_batch<AIR: Air>(
+    batch: Vec<(StarkProof, AIR::PublicInputs)>,
+) -> Result<(), VerifierError>
+where
+    AIR::PublicInputs: Send,
+{
+    #[cfg(not(feature = "concurrent"))]
+    let failure = batch
+        .into_iter()
+        .enumerate()
+        .find_map(|(index, (proof, pub_inputs))| {
+            verify::<AIR>(proof, pub_inputs)
+                .err()
+                .map(|err| (index, err))
+        });
+
+    #[cfg(feature = "concurrent")]
+    let failure =
+        batch
+            .into_par_iter()
+            .enumerate()
+            .find_map_first(|(index, (proof, pub_inputs))| {
+                verify::<AIR>(proof, pub_inputs)
+                    .err()
+                    .map(|err| (index, err))
+            });
+
+    match failure {
+        Some((index, error)) => Err(VerifierError::BatchProofInvalid {
+            index,
+            error: Box::new(error),
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Verifies a proof generated by `prove_many()` function of the prover crate, i.e., a proof
+/// attesting to correct executions of several instances of the computation specified by `AIR`
+/// type parameter.
+///
+/// Public inputs of the instances must be specified in the same order in which the execution
+/// traces of the instances were stacked by the prover. The proof is verified as a proof of
+/// [StackedAir] of the specified `AIR` (see [verify()]).
+///
+/// # Errors
+/// Returns an error if the combination of the provided proof and public inputs does not attest
+/// to correct executions of all instances of the computation.
+///
+/// # Panics
+/// Panics if:
+/// * `pub_inputs` is empty or the number of public inputs is not a power of two.
+/// * [StackedAir] cannot be instantiated for the trace described by the proof (e.g., because the
+///   `AIR` has an auxiliary trace segment).
+pub fn verify_many<AIR: Air>(
+    proof: StarkProof,
+    pub_inputs: Vec<AIR::PublicInputs>,
+) -> Result<(), VerifierError> {
+    verify::<StackedAir<AIR>>(proof, StackedPublicInputs::new(pub_inputs))
+}
+
+/// Verifies that the specified computation was executed correctly against the specified inputs
+/// and that the parameters of the computation match the specified verification `key`.
+///
+/// This is useful when verification keys are stored separately from the proofs (e.g., when they
+/// are embedded into a verifier binary): the parameters of the computation, including proof
+/// options, are then fixed by the key rather than by the proof being verified. Transition
+/// constraints of the computation are still evaluated via the `AIR`.
+///
+/// # Errors
+/// Returns an error if:
+/// * The parameters of the computation specified in the `proof` do not match the `key`.
+/// * The combination of the provided proof and public inputs does not attest to a correct
+///   execution of the computation (see [verify()]).
+pub fn verify_with_key<AIR: Air>(
+    proof: StarkProof,
+    pub_inputs: AIR::PublicInputs,
+    key: &VerificationKey,
+) -> Result<(), VerifierError> {
+    if proof.trace_width() != key.trace_width()
+        || proof.trace_length() != key.trace_length()
+        || proof.options() != key.options()
+    {
+        return Err(VerifierError::VerificationKeyMismatch);
+    }
+
+    let options = VerificationOptions::default();
+    let (air, public_coin_seed) = instantiate_air::<AIR>(&proof, pub_inputs, &options)?;
+    if VerificationKey::new(&air) != *key {
+        return Err(VerifierError::VerificationKeyMismatch);
+    }
+    verify_air(air, proof, public_coin_seed, options).map(|_| ())
+}
+
+/// Verifies that the specified computation was executed correctly against the specified inputs
+/// and that the trace metadata specified in the `proof` is equal to `expected_meta`.
+///
+/// Trace metadata (see [TraceInfo::meta()](air::TraceInfo::meta)) is serialized into the proof
+/// context and is absorbed into the public coin together with the rest of the context. Thus,
+/// placing a program hash or a protocol version into the metadata makes transcripts of
+/// different programs with the same trace dimensions distinct. [verify()] accepts proofs with
+/// any metadata, and an `AIR` may inspect the metadata when it is instantiated; this function
+/// additionally rejects proofs whose metadata was not produced for the expected program.
+///
+/// # Errors
+/// Returns an error if:
+/// * The trace metadata specified in the `proof` is different from `expected_meta`.
+/// * The combination of the provided proof and public inputs does not attest to a correct
+///   execution of the computation (see [verify()]).
+pub fn verify_with_meta<AIR: Air>(
+    proof: StarkProof,
+    pub_inputs: AIR::PublicInputs,
+    expected_meta: &[u8],
+) -> Result<(), VerifierError> {
+    if proof.get_trace_info().meta() != expected_meta {
+        return Err(VerifierError::TraceMetadataMismatch);
+    }
+    verify::<AIR>(proof, pub_inputs)
+}
+
+/// Verifies the specified proof against the provided instance of `air` and returns the query
+/// positions drawn by the verifier.
+#[rustfmt::skip]
+fn verify_air<AIR: Air>(
+    air: AIR,
+    proof: StarkProof,
+    public_coin_seed: Vec<u8>,
+    options: VerificationOptions,
+) -> Result<Vec<usize>, VerifierError> {
+    let public_coin_seed = prepare_coin_seed(&air, public_coin_seed, &options)?;
+    let is_domain_separated = proof.is_domain_separated();
+
+    // figure out which hash function to instantiate the public coin with; the extension field is
+    // selected by verify_air_with_coin()
+    match air.options().hash_fn() {
+        HashFunction::Blake3_256 => {
+            let public_coin = VersionedCoin::<AIR::BaseElement, Blake3_256<AIR::BaseElement>>::with_domain_separation(&public_coin_seed, is_domain_separated);
+            verify_air_with_coin(air, proof, public_coin, options)
+        }
+        HashFunction::Blake3_192 => {
+            let public_coin = VersionedCoin::<AIR::BaseElement, Blake3_192<AIR::BaseElement>>::with_domain_separation(&public_coin_seed, is_domain_separated);
+            verify_air_with_coin(air, proof, public_coin, options)
+        }
+        HashFunction::Sha3_256 => {
+            let public_coin = VersionedCoin::<AIR::BaseElement, Sha3_256<AIR::BaseElement>>::with_domain_separation(&public_coin_seed, is_domain_separated);
+            verify_air_with_coin(air, proof, public_coin, options)
+        }
+        HashFunction::Keccak256 => {
+            let public_coin = VersionedCoin::<AIR::BaseElement, Keccak256<AIR::BaseElement>>::with_domain_separation(&public_coin_seed, is_domain_separated);
+            verify_air_with_coin(air, proof, public_coin, options)
+        }
+    }
+}
+
+/// Verifies the specified proof against the provided instance of `air` drawing all verifier
+/// challenges from the `public_coin`, and returns the query positions drawn by the verifier.
+#[rustfmt::skip]
+fn verify_air_with_coin<AIR, C>(
+    air: AIR,
+    proof: StarkProof,
+    public_coin: C,
+    options: VerificationOptions,
+) -> Result<Vec<usize>, VerifierError>
+where
+    AIR: Air,
+    C: PublicCoin<BaseField = AIR::BaseElement>,
+{
     // figure out which version of the generic proof verification procedure to run. this is a sort
-    // of static dispatch for selecting two generic parameter: extension field and hash function.
+    // of static dispatch for selecting the extension field; the hash function is defined by the
+    // public coin.
     match air.options().field_extension() {
-        FieldExtension::None => match air.options().hash_fn() {
-            HashFunction::Blake3_256 => {
-                let public_coin = RandomCoin::new(&public_coin_seed);
-                let channel = VerifierChannel::new(&air, proof)?;
-                perform_verification::<AIR, AIR::BaseElement, Blake3_256<AIR::BaseElement>>(air, channel, public_coin)
-            }
-            HashFunction::Blake3_192 => {
-                let public_coin = RandomCoin::new(&public_coin_seed);
-                let channel = VerifierChannel::new(&air, proof)?;
-                perform_verification::<AIR, AIR::BaseElement, Blake3_192<AIR::BaseElement>>(air, channel, public_coin)
-            }
-            HashFunction::Sha3_256 => {
-                let public_coin = RandomCoin::new(&public_coin_seed);
-                let channel = VerifierChannel::new(&air, proof)?;
-                perform_verification::<AIR, AIR::BaseElement, Sha3_256<AIR::BaseElement>>(air, channel, public_coin)
-            }
-        },
+        FieldExtension::None => {
+            let channel = VerifierChannel::new(&air, proof)?;
+            perform_verification::<AIR, AIR::BaseElement, C::Hasher, C>(air, channel, public_coin, options)
+        }
         FieldExtension::Quadratic => {
             if !<QuadExtension<AIR::BaseElement>>::is_supported() {
                 return Err(VerifierError::UnsupportedFieldExtension(2));
             }
-            match air.options().hash_fn() {
-                HashFunction::Blake3_256 => {
-                    let public_coin = RandomCoin::new(&public_coin_seed);
-                    let channel = VerifierChannel::new(&air, proof)?;
-                    perform_verification::<AIR, QuadExtension<AIR::BaseElement>, Blake3_256<AIR::BaseElement>>(air, channel, public_coin)
-                }
-                HashFunction::Blake3_192 => {
-                    let public_coin = RandomCoin::new(&public_coin_seed);
-                    let channel = VerifierChannel::new(&air, proof)?;
-                    perform_verification::<AIR, QuadExtension<AIR::BaseElement>, Blake3_192<AIR::BaseElement>>(air, channel, public_coin)
-                }
-                HashFunction::Sha3_256 => {
-                    let public_coin = RandomCoin::new(&public_coin_seed);
-                    let channel = VerifierChannel::new(&air, proof)?;
-                    perform_verification::<AIR, QuadExtension<AIR::BaseElement>, Sha3_256<AIR::BaseElement>>(air, channel, public_coin)
-                }
-            }
-        },
+            let channel = VerifierChannel::new(&air, proof)?;
+            perform_verification::<AIR, QuadExtension<AIR::BaseElement>, C::Hasher, C>(air, channel, public_coin, options)
+        }
         FieldExtension::Cubic => {
             if !<CubeExtension<AIR::BaseElement>>::is_supported() {
                 return Err(VerifierError::UnsupportedFieldExtension(3));
             }
-            match air.options().hash_fn() {
-                HashFunction::Blake3_256 => {
-                    let public_coin = RandomCoin::new(&public_coin_seed);
-                    let channel = VerifierChannel::new(&air, proof)?;
-                    perform_verification::<AIR, CubeExtension<AIR::BaseElement>, Blake3_256<AIR::BaseElement>>(air, channel, public_coin)
-                }
-                HashFunction::Blake3_192 => {
-                    let public_coin = RandomCoin::new(&public_coin_seed);
-                    let channel = VerifierChannel::new(&air, proof)?;
-                    perform_verification::<AIR, CubeExtension<AIR::BaseElement>, Blake3_192<AIR::BaseElement>>(air, channel, public_coin)
-                }
-                HashFunction::Sha3_256 => {
-                    let public_coin = RandomCoin::new(&public_coin_seed);
-                    let channel = VerifierChannel::new(&air, proof)?;
-                    perform_verification::<AIR, CubeExtension<AIR::BaseElement>, Sha3_256<AIR::BaseElement>>(air, channel, public_coin)
-                }
-            }
-        },
+            let channel = VerifierChannel::new(&air, proof)?;
+            perform_verification::<AIR, CubeExtension<AIR::BaseElement>, C::Hasher, C>(air, channel, public_coin, options)
+        }
     }
 }
 
@@ -167,25 +420,53 @@ pub fn verify<AIR: Air>(
 // ================================================================================================
 /// Performs the actual verification by reading the data from the `channel` and making sure it
 /// attests to a correct execution of the computation specified by the provided `air`.
-fn perform_verification<A, E, H>(
+/// Checks skipped by the `options` are not performed, but the channel is still read and the
+/// public coin is still updated in the same way as during the full verification.
+fn perform_verification<A, E, H, R>(
     air: A,
     mut channel: VerifierChannel<A::BaseElement, E, H>,
-    mut public_coin: RandomCoin<A::BaseElement, H>,
-) -> Result<(), VerifierError>
+    mut public_coin: R,
+    options: VerificationOptions,
+) -> Result<Vec<usize>, VerifierError>
 where
     A: Air,
     E: FieldElement<BaseField = A::BaseElement>,
     H: ElementHasher<BaseField = A::BaseElement>,
+    R: PublicCoin<BaseField = A::BaseElement, Hasher = H>,
 {
     // 1 ----- trace commitment -------------------------------------------------------------------
     // read the commitment to evaluations of the trace polynomials over the LDE domain sent by the
     // prover, use it to update the public coin, and draw a set of random coefficients from the
     // coin; in the interactive version of the protocol, the verifier sends these coefficients to
     // the prover, and prover uses them to compute constraint composition polynomial.
+    // the assertions are computed only once and are passed by reference to the places where
+    // they are needed
+    let assertions = air.get_assertions();
     let trace_commitment = channel.read_trace_commitment();
-    public_coin.reseed(trace_commitment);
+    public_coin.reseed_with_tag(&TRACE_COMMITMENT_TAG, trace_commitment);
+
+    // for computations with an auxiliary trace segment, draw random elements for building the
+    // segment from the public coin, and then read the commitment to the extended auxiliary
+    // segment; the elements are drawn from the field in which the protocol is executed, and
+    // thus, values of the auxiliary segment and of its assertions are in this field as well
+    let aux_trace_commitment = channel.read_aux_trace_commitment();
+    let mut aux_rand_elements = Vec::new();
+    let mut aux_assertions = Vec::new();
+    if let Some(aux_trace_commitment) = aux_trace_commitment {
+        for _ in 0..air.num_aux_rand_elements() {
+            let element = public_coin
+                .draw::<E>()
+                .map_err(|_| VerifierError::RandomCoinError)?;
+            aux_rand_elements.push(element);
+        }
+        public_coin.reseed_with_tag(&AUX_TRACE_COMMITMENT_TAG, aux_trace_commitment);
+        aux_assertions = air.get_aux_assertions(&aux_rand_elements);
+    }
+
+    let num_boundary_constraints =
+        assertions.len() + air.get_domain_point_constraints::<E>().len() + aux_assertions.len();
     let constraint_coeffs = air
-        .get_constraint_composition_coefficients(&mut public_coin)
+        .get_constraint_composition_coefficients(&mut public_coin, num_boundary_constraints)
         .map_err(|_| VerifierError::RandomCoinError)?;
 
     // 2 ----- constraint commitment --------------------------------------------------------------
@@ -195,7 +476,7 @@ where
     // to the prover, and the prover evaluates trace and constraint composition polynomials at z,
     // and send the results back to the verifier.
     let constraint_commitment = channel.read_constraint_commitment();
-    public_coin.reseed(constraint_commitment);
+    public_coin.reseed_with_tag(&CONSTRAINT_COMMITMENT_TAG, constraint_commitment);
     let z = public_coin
         .draw::<E>()
         .map_err(|_| VerifierError::RandomCoinError)?;
@@ -204,29 +485,45 @@ where
     // make sure that evaluations obtained by evaluating constraints over the out-of-domain frame
     // are consistent with the evaluations of composition polynomial columns sent by the prover
 
-    // read the out-of-domain evaluation frame sent by the prover and evaluate constraints over it;
-    // also, reseed the public coin with the OOD frame received from the prover
+    // read the out-of-domain evaluation frame sent by the prover and reseed the public coin with
+    // it; then, read evaluations of composition polynomial columns sent by the prover, and reseed
+    // the public coin with these evaluations as well.
     let ood_frame = channel.read_ood_evaluation_frame();
-    let ood_constraint_evaluation_1 = evaluate_constraints(&air, constraint_coeffs, &ood_frame, z);
-    public_coin.reseed(H::hash_elements(ood_frame.current()));
-    public_coin.reseed(H::hash_elements(ood_frame.next()));
-
-    // read evaluations of composition polynomial columns sent by the prover, and reduce them into
-    // a single value by computing sum(z^i * value_i), where value_i is the evaluation of the ith
-    // column polynomial at z^m, where m is the total number of column polynomials; also, reseed
-    // the public coin with the OOD constraint evaluations received from the prover.
+    for i in 0..ood_frame.size() {
+        public_coin.reseed_with_tag(&OOD_FRAME_TAG, H::hash_elements(ood_frame.row(i)));
+    }
     let ood_evaluations = channel.read_ood_evaluations();
-    let ood_constraint_evaluation_2 = ood_evaluations
-        .iter()
-        .enumerate()
-        .fold(E::ZERO, |result, (i, &value)| {
-            result + z.exp((i as u32).into()) * value
-        });
-    public_coin.reseed(H::hash_elements(&ood_evaluations));
+    public_coin.reseed_with_tag(&OOD_EVALUATIONS_TAG, H::hash_elements(&ood_evaluations));
+
+    if !options.skip_ood_check() {
+        // evaluate constraints over the out-of-domain frame
+        let ood_constraint_evaluation_1 = evaluate_constraints(
+            &air,
+            &assertions,
+            &aux_assertions,
+            constraint_coeffs,
+            &ood_frame,
+            &aux_rand_elements,
+            z,
+        )?;
+
+        // reduce evaluations of composition polynomial columns into a single value by computing
+        // sum(z^i * value_i), where value_i is the evaluation of the ith column polynomial at z^m,
+        // where m is the total number of column polynomials.
+        let ood_constraint_evaluation_2 = ood_evaluations
+            .iter()
+            .enumerate()
+            .fold(E::ZERO, |result, (i, &value)| {
+                result + z.exp((i as u32).into()) * value
+            });
 
-    // finally, make sure the values are the same
-    if ood_constraint_evaluation_1 != ood_constraint_evaluation_2 {
-        return Err(VerifierError::InconsistentOodConstraintEvaluations);
+        // finally, make sure the values are the same
+        if ood_constraint_evaluation_1 != ood_constraint_evaluation_2 {
+            return Err(VerifierError::OodConstraintMismatch(
+                ood_constraint_evaluation_1.to_bytes(),
+                ood_constraint_evaluation_2.to_bytes(),
+            ));
+        }
     }
 
     // 4 ----- FRI commitments --------------------------------------------------------------------
@@ -235,7 +532,7 @@ where
     // and the prover uses them to compute the DEEP composition polynomial. the prover, then
     // applies FRI protocol to the evaluations of the DEEP composition polynomial.
     let deep_coefficients = air
-        .get_deep_composition_coefficients::<E, H>(&mut public_coin)
+        .get_deep_composition_coefficients::<E, _>(&mut public_coin)
         .map_err(|_| VerifierError::RandomCoinError)?;
 
     // instantiates a FRI verifier with the FRI layer commitments read from the channel. From the
@@ -246,7 +543,7 @@ where
     let fri_verifier = FriVerifier::new(
         &mut channel,
         &mut public_coin,
-        air.options().to_fri_options(),
+        air.options().to_fri_options::<A::BaseElement>(),
         air.trace_poly_degree(),
     )
     .map_err(VerifierError::FriVerificationFailed)?;
@@ -266,20 +563,34 @@ where
     // interactive version of the protocol, the verifier sends these query positions to the prover,
     // and the prover responds with decommitments against these positions for trace and constraint
     // composition polynomial evaluations.
-    let query_positions = public_coin
-        .draw_integers(air.options().num_queries(), air.lde_domain_size())
-        .map_err(|_| VerifierError::RandomCoinError)?;
+    let query_positions = channel.read_query_positions(
+        &mut public_coin,
+        air.options().num_queries(),
+        air.lde_domain_size(),
+    )?;
 
     // read evaluations of trace and constraint composition polynomials at the queried positions;
     // this also checks that the read values are valid against trace and constraint commitments
     let queried_trace_states = channel.read_trace_states(&query_positions, &trace_commitment)?;
+    let queried_aux_trace_states = match aux_trace_commitment {
+        Some(aux_trace_commitment) => {
+            Some(channel.read_aux_trace_states(&query_positions, &aux_trace_commitment)?)
+        }
+        None => None,
+    };
     let queried_evaluations =
         channel.read_constraint_evaluations(&query_positions, &constraint_commitment)?;
 
+    // the remaining steps only check FRI consistency
+    if options.skip_fri_consistency() {
+        return Ok(query_positions);
+    }
+
     // 6 ----- DEEP composition -------------------------------------------------------------------
     // compute evaluations of the DEEP composition polynomial at the queried positions
     let composer = DeepComposer::new(&air, &query_positions, z, deep_coefficients);
-    let t_composition = composer.compose_registers(queried_trace_states, ood_frame);
+    let t_composition =
+        composer.compose_registers(queried_trace_states, queried_aux_trace_states, ood_frame);
     let c_composition = composer.compose_constraints(queried_evaluations, ood_evaluations);
     let deep_evaluations = composer.combine_compositions(t_composition, c_composition);
 
@@ -288,5 +599,121 @@ where
     // step are in fact evaluations of a polynomial of degree equal to trace polynomial degree
     fri_verifier
         .verify(&mut channel, &deep_evaluations, &query_positions)
-        .map_err(VerifierError::FriVerificationFailed)
+        .map_err(VerifierError::FriVerificationFailed)?;
+
+    Ok(channel.get_query_positions().to_vec())
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Instantiates the `AIR` for the computation specified in the `proof`, and returns it together
+/// with the initial seed of the public coin.
+///
+/// # Errors
+/// Returns an error if the trace specified in the proof is outside of the bounds specified by
+/// `options`, or if the domain offset specified in the proof is not valid in the base field of
+/// the AIR.
+fn instantiate_air<AIR: Air>(
+    proof: &StarkProof,
+    pub_inputs: AIR::PublicInputs,
+    options: &VerificationOptions,
+) -> Result<(AIR, Vec<u8>), VerifierError> {
+    // build a seed for the public coin; the initial seed is the hash of public inputs and proof
+    // context, but as the protocol progresses, the coin will be reseeded with the info received
+    // from the prover
+    let mut public_coin_seed = Vec::new();
+    pub_inputs.write_into(&mut public_coin_seed);
+    proof.write_context_seed_into(&mut public_coin_seed);
+
+    // make sure the trace specified in the proof is within the bounds accepted by the verifier
+    // before anything is derived from it
+    let bounds = options.sanity_bounds();
+    check_bound("trace width", proof.trace_width(), bounds.max_trace_width())?;
+    check_bound(
+        "auxiliary trace width",
+        proof.context.aux_trace_width(),
+        bounds.max_trace_width(),
+    )?;
+    check_bound(
+        "trace length",
+        proof.trace_length(),
+        bounds.max_trace_length(),
+    )?;
+
+    // make sure the domain offset specified in the proof is a valid offset in the base field of
+    // the computation; otherwise, AIR instantiation would fail or the offset would be reduced
+    // modulo the field modulus
+    proof
+        .options()
+        .validate_domain_offset::<AIR::BaseElement>()
+        .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
+
+    // create AIR instance for the computation specified in the proof
+    let air = AIR::new(proof.get_trace_info(), pub_inputs, proof.options().clone());
+    Ok((air, public_coin_seed))
+}
+
+/// Makes sure the `air` is consistent with its declared max constraint degree, is consistent with
+/// the number of padding rows specified in the proof, and can be verified within the bounds
+/// specified by `options`; returns the public coin seed extended with the channel seed of the
+/// `air`.
+fn prepare_coin_seed<AIR: Air>(
+    air: &AIR,
+    mut public_coin_seed: Vec<u8>,
+    options: &VerificationOptions,
+) -> Result<Vec<u8>, VerifierError> {
+    if AIR::MAX_CONSTRAINT_DEGREE != air.context().max_constraint_degree() {
+        return Err(VerifierError::InconsistentConstraintDegree {
+            declared: AIR::MAX_CONSTRAINT_DEGREE,
+            actual: air.context().max_constraint_degree(),
+        });
+    }
+
+    // the number of padding rows determines the steps on which transition constraints are not
+    // enforced; so, it must be defined by the AIR rather than by the prover
+    if air.trace_info().num_padding_rows() != air.context().num_padding_rows() {
+        return Err(VerifierError::InconsistentTracePadding {
+            expected: air.context().num_padding_rows(),
+            actual: air.trace_info().num_padding_rows(),
+        });
+    }
+
+    // absorb the seed of the computation in the same way as the prover does
+    let channel_seed = air.channel_seed();
+    if channel_seed != [0; 32] {
+        public_coin_seed.extend_from_slice(&channel_seed);
+    }
+
+    // make sure the number of transition constraints is within the bounds accepted by the
+    // verifier before the constraints are built
+    check_bound(
+        "number of transition constraints",
+        air.num_transition_constraints(),
+        options.sanity_bounds().max_constraints(),
+    )?;
+    Ok(public_coin_seed)
+}
+
+/// Returns true if hasher `H` computes the hash function specified by `hash_fn`.
+///
+/// The hashers are compared by their digests of the same message; the hashers specified by
+/// [HashFunction] produce distinct digests for this message.
+fn is_hash_fn<B: StarkField, H: Hasher>(hash_fn: HashFunction) -> bool {
+    const MESSAGE: &[u8] = b"winterfell";
+    let expected = match hash_fn {
+        HashFunction::Blake3_256 => Blake3_256::<B>::hash(MESSAGE).as_bytes(),
+        HashFunction::Blake3_192 => Blake3_192::<B>::hash(MESSAGE).as_bytes(),
+        HashFunction::Sha3_256 => Sha3_256::<B>::hash(MESSAGE).as_bytes(),
+        HashFunction::Keccak256 => Keccak256::<B>::hash(MESSAGE).as_bytes(),
+    };
+    H::hash(MESSAGE).as_bytes() == expected
+}
+
+/// Returns an error if `value` of the specified proof parameter is greater than `max`.
+fn check_bound(field: &'static str, value: usize, max: usize) -> Result<(), VerifierError> {
+    if value > max {
+        return Err(VerifierError::SuspiciousLength { field, value, max });
+    }
+    Ok(())
 }
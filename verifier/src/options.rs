@@ -0,0 +1,219 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+// VERIFICATION OPTIONS
+// ================================================================================================
+/// Specifies which checks are performed during proof verification.
+///
+/// By default, all checks are performed, and thus, [verify_with_options()](crate::verify_with_options)
+/// invoked with default options is equivalent to [verify()](crate::verify).
+///
+/// Skipping some of the checks may be useful when a proof is verified as a part of a larger
+/// protocol (e.g., proof composition) in which the skipped checks are performed elsewhere.
+/// A proof verified with any of the checks skipped does not, by itself, attest to a correct
+/// execution of the computation.
+///
+/// Regardless of the options, the verifier reads all components of the proof and updates the
+/// public coin in exactly the same way; Merkle authentication paths for trace and constraint
+/// queries, as well as the proof-of-work, are always checked.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub struct VerificationOptions {
+    skip_ood_check: bool,
+    skip_fri_consistency: bool,
+    sanity_bounds: SanityBounds,
+}
+
+impl VerificationOptions {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+    /// Returns new verification options instantiated with the specified parameters:
+    /// * `skip_ood_check` specifies whether the verifier should skip checking that constraint
+    ///   evaluations at the out-of-domain point are consistent with the out-of-domain evaluations
+    ///   of the constraint composition polynomial.
+    /// * `skip_fri_consistency` specifies whether the verifier should skip computing the DEEP
+    ///   composition polynomial evaluations at the queried positions and verifying the FRI proof
+    ///   against them.
+    pub fn new(skip_ood_check: bool, skip_fri_consistency: bool) -> Self {
+        VerificationOptions {
+            skip_ood_check,
+            skip_fri_consistency,
+            sanity_bounds: SanityBounds::default(),
+        }
+    }
+
+    /// Sets the bounds against which parameters of the computation specified in a proof are
+    /// checked before the proof is verified, and returns the updated options.
+    pub fn with_sanity_bounds(mut self, sanity_bounds: SanityBounds) -> Self {
+        self.sanity_bounds = sanity_bounds;
+        self
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns true if the out-of-domain consistency check should be skipped.
+    pub fn skip_ood_check(&self) -> bool {
+        self.skip_ood_check
+    }
+
+    /// Returns true if verification of the FRI proof should be skipped.
+    pub fn skip_fri_consistency(&self) -> bool {
+        self.skip_fri_consistency
+    }
+
+    /// Returns the bounds against which parameters of the computation specified in a proof are
+    /// checked.
+    pub fn sanity_bounds(&self) -> &SanityBounds {
+        &self.sanity_bounds
+    }
+}
+
+// SANITY BOUNDS
+// ================================================================================================
+/// Upper bounds on the parameters of the computation specified in a proof.
+///
+/// A proof specifies the width and the length of the execution trace of the computation, and
+/// these values are used to instantiate the AIR against which the proof is verified; the AIR
+/// then defines the number of constraints the verifier needs to evaluate. When proofs are
+/// received from untrusted sources, a forged proof could specify values for which instantiating
+/// the AIR or evaluating its constraints would exhaust resources of the verifier. The verifier
+/// checks trace dimensions against these bounds before the AIR is instantiated, and the number
+/// of constraints right after, and rejects proofs which exceed any of the bounds.
+///
+/// By default, trace width is bounded by 255 (the largest width supported by the protocol),
+/// trace length is bounded by $2^{32}$, and the number of transition constraints is bounded by
+/// $2^{16}$.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct SanityBounds {
+    max_trace_width: usize,
+    max_trace_length: usize,
+    max_constraints: usize,
+}
+
+impl SanityBounds {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns new sanity bounds instantiated with the specified parameters:
+    /// * `max_trace_width` is the largest accepted width of the execution trace.
+    /// * `max_trace_length` is the largest accepted length of the execution trace.
+    /// * `max_constraints` is the largest accepted number of transition constraints of the
+    ///   computation.
+    pub fn new(max_trace_width: usize, max_trace_length: usize, max_constraints: usize) -> Self {
+        SanityBounds {
+            max_trace_width,
+            max_trace_length,
+            max_constraints,
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the largest accepted width of the execution trace.
+    pub fn max_trace_width(&self) -> usize {
+        self.max_trace_width
+    }
+
+    /// Returns the largest accepted length of the execution trace.
+    pub fn max_trace_length(&self) -> usize {
+        self.max_trace_length
+    }
+
+    /// Returns the largest accepted number of transition constraints.
+    pub fn max_constraints(&self) -> usize {
+        self.max_constraints
+    }
+}
+
+impl Default for SanityBounds {
+    fn default() -> Self {
+        SanityBounds::new(255, 1 << 32, 1 << 16)
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{SanityBounds, VerificationOptions};
+    use crate::{
+        tests::{build_fib_proof, BrokenFibAir, FibAir},
+        VerifierError,
+    };
+    use math::{fields::f128::BaseElement, FieldElement};
+
+    #[test]
+    fn verify_with_skipped_checks() {
+        let (proof, result) = build_fib_proof(8);
+
+        let full = VerificationOptions::default();
+        let skip_ood = VerificationOptions::new(true, false);
+        let skip_fri = VerificationOptions::new(false, true);
+
+        // a valid proof passes verification with any options
+        for options in [full, skip_ood, skip_fri] {
+            let verification = crate::verify_with_options::<FibAir>(proof.clone(), result, options);
+            assert!(verification.is_ok());
+        }
+
+        // when verified against an AIR with different transition constraints, the proof fails
+        // the OOD check but FRI consistency is not affected
+        let verification = crate::verify_with_options::<BrokenFibAir>(proof.clone(), result, full);
+        assert_ood_mismatch(verification);
+        let verification =
+            crate::verify_with_options::<BrokenFibAir>(proof.clone(), result, skip_fri);
+        assert_ood_mismatch(verification);
+        let verification = crate::verify_with_options::<BrokenFibAir>(proof, result, skip_ood);
+        assert!(verification.is_ok());
+    }
+
+    #[test]
+    fn verify_with_sanity_bounds() {
+        let (proof, result) = build_fib_proof(8);
+
+        // a valid proof passes verification with the default bounds
+        let options = VerificationOptions::default();
+        assert!(crate::verify_with_options::<FibAir>(proof.clone(), result, options).is_ok());
+
+        // tightening any of the bounds below the parameters of the proof makes verification fail
+        let bounds = [
+            (SanityBounds::new(1, 1 << 32, 1 << 16), "trace width"),
+            (SanityBounds::new(255, 4, 1 << 16), "trace length"),
+            (
+                SanityBounds::new(255, 1 << 32, 1),
+                "number of transition constraints",
+            ),
+        ];
+        for (bounds, expected) in bounds {
+            let options = VerificationOptions::default().with_sanity_bounds(bounds);
+            match crate::verify_with_options::<FibAir>(proof.clone(), result, options) {
+                Err(VerifierError::SuspiciousLength { field, .. }) => assert_eq!(expected, field),
+                verification => {
+                    panic!(
+                        "expected suspicious length error, but got {:?}",
+                        verification
+                    )
+                }
+            }
+        }
+    }
+
+    /// Asserts that the verification failed the OOD check, and that the error carries serialized
+    /// values of the mismatched evaluations.
+    fn assert_ood_mismatch(verification: Result<(), VerifierError>) {
+        match verification {
+            Err(VerifierError::OodConstraintMismatch(expected, actual)) => {
+                assert_eq!(BaseElement::ELEMENT_BYTES, expected.len());
+                assert_eq!(BaseElement::ELEMENT_BYTES, actual.len());
+                assert_ne!(expected, actual);
+            }
+            verification => panic!(
+                "expected OOD constraint mismatch, but got {:?}",
+                verification
+            ),
+        }
+    }
+}
@@ -0,0 +1,212 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{verify, Deserializable, StarkProof, VerifierError};
+use air::{proof::TaggedProof, Air, TaggedAir};
+use utils::{
+    collections::BTreeMap,
+    string::{String, ToString},
+    ByteReader, DeserializationError, SliceReader,
+};
+
+// AIR REGISTRY
+// ================================================================================================
+
+/// A function which verifies a proof against public inputs serialized into a sequence of bytes.
+type VerifyFn = fn(StarkProof, &[u8]) -> Result<(), VerifierError>;
+
+/// A collection of [TaggedAir] types which can be used to verify [TaggedProof]s.
+///
+/// When a tagged proof is verified via [AirRegistry::verify()], the AIR against which the proof
+/// is verified is selected based on the tag of the proof. Public inputs are passed in as a
+/// sequence of bytes and are deserialized into public inputs of the selected AIR.
+#[derive(Default)]
+pub struct AirRegistry {
+    verifiers: BTreeMap<String, VerifyFn>,
+}
+
+/// Describes a proof which has been successfully verified via [AirRegistry::verify()].
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct VerificationResult {
+    /// Tag of the AIR against which the proof was verified.
+    pub air_tag: String,
+    /// Conjectured security level (in bits) of the verified proof.
+    pub security_level: u32,
+}
+
+impl AirRegistry {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new registry with no AIRs registered in it.
+    pub fn new() -> Self {
+        AirRegistry {
+            verifiers: BTreeMap::new(),
+        }
+    }
+
+    // REGISTRATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Adds AIR specified by type parameter `A` to this registry.
+    ///
+    /// # Panics
+    /// Panics if an AIR with the same tag has already been added to this registry.
+    pub fn register<A>(&mut self) -> &mut Self
+    where
+        A: TaggedAir,
+        A::PublicInputs: Deserializable,
+    {
+        assert!(
+            !self.verifiers.contains_key(A::AIR_TAG),
+            "AIR with tag '{}' has already been registered",
+            A::AIR_TAG
+        );
+        self.verifiers
+            .insert(A::AIR_TAG.to_string(), verify_serialized::<A>);
+        self
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns true if an AIR with the specified tag has been added to this registry.
+    pub fn contains(&self, air_tag: &str) -> bool {
+        self.verifiers.contains_key(air_tag)
+    }
+
+    // VERIFICATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Verifies the specified tagged proof against the AIR which has the same tag as the proof.
+    ///
+    /// `public_inputs` must contain serialized public inputs of the selected AIR and nothing
+    /// else. If the proof is valid, the returned result describes the AIR against which the
+    /// proof was verified and the security level of the proof.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * No AIR with the tag of the proof has been added to this registry.
+    /// * Public inputs of the selected AIR could not be read from `public_inputs`, or
+    ///   `public_inputs` contains bytes beyond the serialized public inputs.
+    /// * The proof does not attest to a correct execution of the computation described by the
+    ///   selected AIR against the specified public inputs.
+    pub fn verify(
+        &self,
+        tagged_proof: &TaggedProof,
+        public_inputs: &[u8],
+    ) -> Result<VerificationResult, VerifierError> {
+        let verify_fn = self
+            .verifiers
+            .get(&tagged_proof.air_tag)
+            .ok_or_else(|| VerifierError::UnknownAirTag(tagged_proof.air_tag.clone()))?;
+        verify_fn(tagged_proof.proof.clone(), public_inputs)?;
+        Ok(VerificationResult {
+            air_tag: tagged_proof.air_tag.clone(),
+            security_level: tagged_proof.proof.security_level(true),
+        })
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Deserializes public inputs of AIR `A` from the provided bytes and verifies the proof against
+/// them; all of the provided bytes must be consumed by the public inputs.
+fn verify_serialized<A>(proof: StarkProof, public_inputs: &[u8]) -> Result<(), VerifierError>
+where
+    A: Air,
+    A::PublicInputs: Deserializable,
+{
+    let mut source = SliceReader::new(public_inputs);
+    let pub_inputs = A::PublicInputs::read_from(&mut source)
+        .map_err(|err| VerifierError::PublicInputDeserializationError(err.to_string()))?;
+    if source.has_more_bytes() {
+        return Err(VerifierError::PublicInputDeserializationError(
+            DeserializationError::UnconsumedBytes.to_string(),
+        ));
+    }
+    verify::<A>(proof, pub_inputs)
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::AirRegistry;
+    use crate::{
+        tests::{build_fib_proof, BrokenFibAir, FibAir},
+        TaggedProof, VerifierError,
+    };
+    use math::{fields::f128::BaseElement, FieldElement};
+    use utils::{string::ToString, Serializable};
+
+    #[test]
+    fn verify_tagged_proof() {
+        let (proof, result) = build_fib_proof(8);
+        let pub_inputs = result.to_bytes();
+
+        let mut registry = AirRegistry::new();
+        registry.register::<FibAir>().register::<BrokenFibAir>();
+        assert!(registry.contains("fib-v1"));
+        assert!(!registry.contains("fib-v2"));
+
+        // the proof is verified against the AIR selected by its tag, and the proof survives
+        // serialization together with its tag
+        let tagged_proof = TaggedProof::new::<FibAir>(proof.clone());
+        let tagged_proof = TaggedProof::from_bytes(&tagged_proof.to_bytes()).unwrap();
+        assert_eq!("fib-v1", tagged_proof.air_tag);
+        let verification = registry.verify(&tagged_proof, &pub_inputs).unwrap();
+        assert_eq!("fib-v1", verification.air_tag);
+        assert_eq!(proof.security_level(true), verification.security_level);
+
+        let wrong_inputs = (result + BaseElement::ONE).to_bytes();
+        assert!(registry.verify(&tagged_proof, &wrong_inputs).is_err());
+
+        // the same proof tagged with a different AIR is verified against that AIR
+        let tagged_proof = TaggedProof::new::<BrokenFibAir>(proof.clone());
+        assert!(registry.verify(&tagged_proof, &pub_inputs).is_err());
+
+        let tagged_proof = TaggedProof {
+            air_tag: "fib-v2".to_string(),
+            proof,
+        };
+        assert_eq!(
+            Err(VerifierError::UnknownAirTag("fib-v2".to_string())),
+            registry.verify(&tagged_proof, &pub_inputs)
+        );
+    }
+
+    #[test]
+    fn verify_tagged_proof_with_malformed_inputs() {
+        let (proof, result) = build_fib_proof(8);
+        let pub_inputs = result.to_bytes();
+
+        let mut registry = AirRegistry::new();
+        registry.register::<FibAir>();
+        let tagged_proof = TaggedProof::new::<FibAir>(proof);
+
+        // truncated public inputs cannot be deserialized
+        assert!(matches!(
+            registry.verify(&tagged_proof, &pub_inputs[1..]),
+            Err(VerifierError::PublicInputDeserializationError(_))
+        ));
+
+        // public inputs followed by extra bytes are rejected
+        let mut padded_inputs = pub_inputs;
+        padded_inputs.push(0);
+        assert!(matches!(
+            registry.verify(&tagged_proof, &padded_inputs),
+            Err(VerifierError::PublicInputDeserializationError(_))
+        ));
+    }
+
+    #[test]
+    #[should_panic(expected = "AIR with tag 'fib-v1' has already been registered")]
+    fn register_duplicate_tag() {
+        let mut registry = AirRegistry::new();
+        registry.register::<FibAir>().register::<FibAir>();
+    }
+}
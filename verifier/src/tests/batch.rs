@@ -0,0 +1,30 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{build_fib_proof, FibAir};
+use crate::VerifierError;
+use utils::collections::Vec;
+
+// TESTS
+// ================================================================================================
+
+#[test]
+fn verify_batch() {
+    let batch = [16, 32, 64]
+        .iter()
+        .map(|&trace_length| build_fib_proof(trace_length))
+        .collect::<Vec<_>>();
+    crate::verify_batch::<FibAir>(batch.clone()).unwrap();
+
+    // swap public inputs of the last two proofs; the second proof is the first one to fail
+    let mut batch = batch;
+    let result = batch[1].1;
+    batch[1].1 = batch[2].1;
+    batch[2].1 = result;
+    match crate::verify_batch::<FibAir>(batch) {
+        Err(VerifierError::BatchProofInvalid { index, .. }) => assert_eq!(1, index),
+        result => panic!("unexpected verification result: {:?}", result),
+    }
+}
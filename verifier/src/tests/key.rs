@@ -0,0 +1,54 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{build_fib_proof, build_fib_trace, build_options, FibAir};
+use crate::VerifierError;
+use air::{Air, FieldExtension, HashFunction, ProofOptions, VerificationKey};
+use math::{fields::f128::BaseElement, FieldElement};
+use utils::Serializable;
+
+// TESTS
+// ================================================================================================
+
+#[test]
+fn verify_with_key() {
+    let trace = build_fib_trace(16);
+    let (proof, result) = build_fib_proof(16);
+    let air = FibAir::new(trace.get_info(), result, build_options());
+    let key = VerificationKey::new(&air);
+    assert_eq!(2, key.trace_width());
+    assert_eq!(16, key.trace_length());
+    assert_eq!(2, key.num_constraints());
+    assert_eq!(HashFunction::Blake3_256, key.hash_fn());
+
+    // the key survives serialization
+    let key = VerificationKey::from_bytes(&key.to_bytes()).unwrap();
+    assert_eq!(VerificationKey::new(&air), key);
+
+    assert!(crate::verify_with_key::<FibAir>(proof.clone(), result, &key).is_ok());
+    assert!(crate::verify_with_key::<FibAir>(proof, result + BaseElement::ONE, &key).is_err());
+
+    // proofs generated for a different trace length or with different options are rejected
+    let (proof, result) = build_fib_proof(32);
+    assert_eq!(
+        Err(VerifierError::VerificationKeyMismatch),
+        crate::verify_with_key::<FibAir>(proof, result, &key)
+    );
+    let options = ProofOptions::new(
+        36,
+        8,
+        0,
+        HashFunction::Blake3_256,
+        FieldExtension::Quadratic,
+        4,
+        256,
+    );
+    let result = trace.get(1, trace.length() - 1);
+    let proof = prover::prove::<FibAir>(trace, result, options).unwrap();
+    assert_eq!(
+        Err(VerifierError::VerificationKeyMismatch),
+        crate::verify_with_key::<FibAir>(proof, result, &key)
+    );
+}
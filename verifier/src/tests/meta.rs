@@ -0,0 +1,39 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{build_fib_trace, build_options, FibAir};
+use crate::VerifierError;
+use air::proof::StarkProof;
+
+// TESTS
+// ================================================================================================
+
+#[test]
+fn verify_with_trace_metadata() {
+    let trace = build_fib_trace(16);
+    let result = trace.get(1, trace.length() - 1);
+    let prove_with_meta = |meta: &[u8]| {
+        let mut trace = trace.clone();
+        trace.set_meta(meta.to_vec());
+        prover::prove::<FibAir>(trace, result, build_options()).unwrap()
+    };
+
+    let proof = prove_with_meta(b"program 1");
+    assert_eq!(b"program 1", proof.get_trace_info().meta());
+    let bytes = proof.to_bytes();
+    let proof = StarkProof::from_bytes(&bytes).unwrap();
+    assert!(crate::verify_with_meta::<FibAir>(proof.clone(), result, b"program 1").is_ok());
+    assert_eq!(
+        Err(VerifierError::TraceMetadataMismatch),
+        crate::verify_with_meta::<FibAir>(proof, result, b"program 2")
+    );
+
+    // metadata is absorbed into the public coin, and thus, proofs for different metadata differ
+    let other = prove_with_meta(b"program 2");
+    assert_ne!(
+        StarkProof::from_bytes(&bytes).unwrap().commitments,
+        other.commitments
+    );
+}
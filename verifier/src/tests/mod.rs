@@ -0,0 +1,148 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use air::{
+    proof::StarkProof, register_air, Air, AirContext, Assertion, EvaluationFrame, FieldExtension,
+    HashFunction, ProofOptions, TraceInfo, TransitionConstraintDegree,
+};
+use math::{fields::f128::BaseElement, FieldElement};
+use prover::ExecutionTrace;
+use utils::collections::Vec;
+
+mod batch;
+mod key;
+mod meta;
+mod proof_options;
+mod queries;
+mod security;
+mod validation;
+
+// FIBONACCI AIR
+// ================================================================================================
+
+/// Computes Fibonacci sequence with 2 terms per step; public inputs are the last term of the
+/// sequence.
+pub struct FibAir {
+    context: AirContext<BaseElement>,
+    result: BaseElement,
+}
+
+register_air!(FibAir, "fib-v1");
+
+impl Air for FibAir {
+    type BaseElement = BaseElement;
+    type PublicInputs = BaseElement;
+
+    const MAX_CONSTRAINT_DEGREE: usize = 1;
+
+    fn new(trace_info: TraceInfo, pub_inputs: BaseElement, options: ProofOptions) -> Self {
+        let degrees = vec![TransitionConstraintDegree::new(1); 2];
+        FibAir {
+            context: AirContext::new(trace_info, degrees, options),
+            result: pub_inputs,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseElement> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement<BaseField = Self::BaseElement>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+        result[0] = next[0] - (current[0] + current[1]);
+        result[1] = next[1] - (current[1] + next[0]);
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseElement>> {
+        let last_step = self.trace_length() - 1;
+        vec![
+            Assertion::single(0, 0, BaseElement::ONE),
+            Assertion::single(1, 0, BaseElement::ONE),
+            Assertion::single(1, last_step, self.result),
+        ]
+    }
+}
+
+// BROKEN FIBONACCI AIR
+// ================================================================================================
+
+/// Same as Fibonacci AIR but with the first transition constraint offset by one.
+pub struct BrokenFibAir(FibAir);
+
+register_air!(BrokenFibAir, "broken-fib-v1");
+
+impl Air for BrokenFibAir {
+    type BaseElement = BaseElement;
+    type PublicInputs = BaseElement;
+
+    const MAX_CONSTRAINT_DEGREE: usize = FibAir::MAX_CONSTRAINT_DEGREE;
+
+    fn new(trace_info: TraceInfo, pub_inputs: BaseElement, options: ProofOptions) -> Self {
+        BrokenFibAir(FibAir::new(trace_info, pub_inputs, options))
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseElement> {
+        self.0.context()
+    }
+
+    fn evaluate_transition<E: FieldElement<BaseField = Self::BaseElement>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        self.0.evaluate_transition(frame, periodic_values, result);
+        result[0] += E::ONE;
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseElement>> {
+        self.0.get_assertions()
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns a proof of a Fibonacci computation against [FibAir] for an execution trace with the
+/// specified number of steps, together with the result of the computation.
+pub fn build_fib_proof(trace_length: usize) -> (StarkProof, BaseElement) {
+    let trace = build_fib_trace(trace_length);
+    let result = trace.get(1, trace_length - 1);
+    let proof = prover::prove::<FibAir>(trace, result, build_options()).unwrap();
+    (proof, result)
+}
+
+pub fn build_fib_trace(trace_length: usize) -> ExecutionTrace<BaseElement> {
+    let mut trace = ExecutionTrace::new(2, trace_length);
+    trace.fill(
+        |state| {
+            state[0] = BaseElement::ONE;
+            state[1] = BaseElement::ONE;
+        },
+        |_, state| {
+            state[0] += state[1];
+            state[1] += state[0];
+        },
+    );
+    trace
+}
+
+pub fn build_options() -> ProofOptions {
+    ProofOptions::new(
+        36,
+        8,
+        0,
+        HashFunction::Blake3_256,
+        FieldExtension::None,
+        4,
+        256,
+    )
+}
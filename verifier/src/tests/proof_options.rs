@@ -0,0 +1,105 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{build_fib_proof, build_fib_trace, build_options, FibAir};
+use crate::VerifierError;
+use air::{proof::StarkProof, FieldExtension, FriRemainderEncoding, ProofOptions};
+use core::convert::TryInto;
+use math::{fields::f128::BaseElement, FieldElement, StarkField};
+use utils::Serializable;
+
+// TESTS
+// ================================================================================================
+
+#[test]
+fn verify_with_custom_coset_offset() {
+    let offset = BaseElement::new(7);
+    let trace = build_fib_trace(8);
+    let result = trace.get(1, trace.length() - 1);
+    let options = build_options().with_coset_offset(offset);
+    let proof = prover::prove::<FibAir>(trace, result, options).unwrap();
+    assert_eq!(offset, proof.options().domain_offset::<BaseElement>());
+
+    // the offset is carried by the proof and survives serialization
+    let proof = StarkProof::from_bytes(&proof.to_bytes()).unwrap();
+    assert_eq!(offset, proof.options().domain_offset::<BaseElement>());
+    assert!(crate::verify::<FibAir>(proof.clone(), result).is_ok());
+    assert!(crate::verify::<FibAir>(proof.clone(), result + BaseElement::ONE).is_err());
+
+    // a non-canonical encoding of the same offset is rejected by the verifier even though it
+    // reduces to the offset used by the prover
+    let modulus = u128::from_le_bytes(BaseElement::get_modulus_le_bytes().try_into().unwrap());
+    let offset_bytes = 7u128.to_le_bytes();
+    let forged_bytes = (modulus + 7).to_le_bytes();
+    let mut bytes = proof.to_bytes();
+    let options_end = proof.context.to_bytes().len() + 1;
+    assert_eq!(&offset_bytes[..], &bytes[options_end - 16..options_end]);
+    bytes[options_end - 16..options_end].copy_from_slice(&forged_bytes);
+    let forged_proof = StarkProof::from_bytes(&bytes).unwrap();
+    assert_eq!(
+        offset,
+        forged_proof.options().domain_offset::<BaseElement>()
+    );
+    let err = crate::verify::<FibAir>(forged_proof, result).unwrap_err();
+    assert!(
+        matches!(&err, VerifierError::ProofDeserializationError(msg) if msg.contains("is not smaller than the field modulus")),
+        "{}",
+        err
+    );
+}
+
+#[test]
+fn verify_with_fri_remainder_coefficients() {
+    let trace = build_fib_trace(512);
+    let result = trace.get(1, trace.length() - 1);
+    let options = build_options()
+        .with_coset_offset(BaseElement::new(7))
+        .with_fri_remainder_encoding(FriRemainderEncoding::Coefficients);
+    let proof = prover::prove::<FibAir>(trace, result, options).unwrap();
+
+    // the encoding is carried by the proof and survives serialization
+    let proof = StarkProof::from_bytes(&proof.to_bytes()).unwrap();
+    assert_eq!(
+        FriRemainderEncoding::Coefficients,
+        proof.options().fri_remainder_encoding()
+    );
+    assert!(crate::verify::<FibAir>(proof.clone(), result).is_ok());
+    assert!(crate::verify::<FibAir>(proof.clone(), result + BaseElement::ONE).is_err());
+
+    // the remainder is smaller than the one sent as evaluations by the blowup factor
+    let (evaluations_proof, _) = build_fib_proof(512);
+    let blowup_factor = proof.options().blowup_factor();
+    let num_coefficients = proof.fri_proof.num_remainder_elements::<BaseElement>();
+    let num_evaluations = evaluations_proof
+        .fri_proof
+        .num_remainder_elements::<BaseElement>();
+    assert_eq!(num_evaluations / blowup_factor, num_coefficients);
+}
+
+#[test]
+fn verify_with_default_options() {
+    let options = ProofOptions::default_96bit();
+    assert_eq!(96.0, options.security_bits::<BaseElement>(1 << 20));
+    assert_eq!(FieldExtension::None, options.field_extension());
+    verify_fib_proof(options);
+
+    let options = ProofOptions::default_128bit();
+    assert_eq!(128.0, options.security_bits::<BaseElement>(1 << 20));
+    assert_eq!(FieldExtension::Quadratic, options.field_extension());
+    verify_fib_proof(options);
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Generates a proof of a Fibonacci computation with the specified options, and checks that it
+/// is accepted for the correct result only.
+fn verify_fib_proof(options: ProofOptions) {
+    let trace = build_fib_trace(8);
+    let result = trace.get(1, trace.length() - 1);
+    let proof = prover::prove::<FibAir>(trace, result, options).unwrap();
+    assert!(crate::verify::<FibAir>(proof.clone(), result).is_ok());
+    assert!(crate::verify::<FibAir>(proof, result + BaseElement::ONE).is_err());
+}
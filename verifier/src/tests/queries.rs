@@ -0,0 +1,49 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{build_fib_proof, FibAir};
+use crate::VerificationOptions;
+use crypto::{hashers::Blake3_256, MerkleTree};
+use math::fields::f128::BaseElement;
+
+// TESTS
+// ================================================================================================
+
+#[test]
+fn verify_and_get_query_positions() {
+    let (proof, result) = build_fib_proof(8);
+    let lde_domain_size = proof.lde_domain_size();
+    let num_queries = proof.options().num_queries();
+
+    // positions are the same regardless of which checks are performed
+    let positions = crate::verify_and_get_query_positions::<FibAir>(
+        proof.clone(),
+        result,
+        VerificationOptions::default(),
+    )
+    .unwrap();
+    assert_eq!(num_queries, positions.len());
+    assert!(positions.iter().all(|&p| p < lde_domain_size));
+    let skip_all = VerificationOptions::new(true, true);
+    assert_eq!(
+        Ok(positions.clone()),
+        crate::verify_and_get_query_positions::<FibAir>(proof.clone(), result, skip_all)
+    );
+
+    // the prover opened the trace commitment at the same positions
+    let num_fri_layers = proof
+        .options()
+        .to_fri_options::<BaseElement>()
+        .num_fri_layers(lde_domain_size);
+    let (trace_root, _, _) = proof
+        .commitments
+        .parse::<Blake3_256<BaseElement>>(num_fri_layers)
+        .unwrap();
+    let (trace_proof, _) = proof
+        .trace_queries
+        .parse::<Blake3_256<BaseElement>, BaseElement>(lde_domain_size, num_queries, 2)
+        .unwrap();
+    assert!(MerkleTree::verify_batch(&trace_root, &positions, &trace_proof).is_ok());
+}
@@ -0,0 +1,66 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{build_fib_proof, build_fib_trace, FibAir};
+use air::{Air, FieldExtension, HashFunction, ProofOptions};
+use math::{fields::f128::BaseElement, log2};
+
+// TESTS
+// ================================================================================================
+
+#[test]
+fn achieved_security_level() {
+    let (proof, _) = build_fib_proof(512);
+
+    // query positions may repeat, so there can be fewer unique queries than specified
+    let num_queries = proof.options().num_queries();
+    assert!(proof.num_unique_queries() > 0);
+    assert!(proof.num_unique_queries() <= num_queries);
+
+    // when all queries are unique, achieved security is not lower than the conjectured security;
+    // in any case, it cannot exceed the security contributed by the queries (no grinding here)
+    let options = proof.options();
+    let expected_query_security = (log2(options.blowup_factor()) as usize * num_queries) as f64;
+    let security = proof.achieved_security_level();
+    if proof.num_unique_queries() == num_queries {
+        assert!(security >= proof.security_level(true) as f64);
+    }
+    assert!(security <= expected_query_security);
+}
+
+#[test]
+fn proof_size_estimate() {
+    // the estimate should be within 10% of the actual proof size
+    for &(trace_length, extension) in [
+        (512, FieldExtension::None),
+        (2048, FieldExtension::Quadratic),
+        (1 << 13, FieldExtension::None),
+    ]
+    .iter()
+    {
+        let options = ProofOptions::new(36, 8, 0, HashFunction::Blake3_256, extension, 4, 256);
+        let trace = build_fib_trace(trace_length);
+        let result = trace.get(1, trace_length - 1);
+        let proof = prover::prove::<FibAir>(trace, result, options.clone()).unwrap();
+        let proof_size = proof.to_bytes().len();
+        let estimate = options.estimate_proof_size::<BaseElement>(
+            2,
+            trace_length,
+            FibAir::MAX_CONSTRAINT_DEGREE,
+        );
+        assert!(
+            estimate * 10 >= proof_size * 9,
+            "{} vs. {}",
+            estimate,
+            proof_size
+        );
+        assert!(
+            estimate * 10 <= proof_size * 11,
+            "{} vs. {}",
+            estimate,
+            proof_size
+        );
+    }
+}
@@ -0,0 +1,72 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{build_fib_proof, FibAir};
+use crate::VerifierError;
+use air::{Air, AirContext, Assertion, EvaluationFrame, ProofOptions, TraceInfo};
+use crypto::{hashers::Blake3_256, RandomCoin};
+use math::{fields::f128::BaseElement, FieldElement};
+use utils::collections::Vec;
+
+// TESTS
+// ================================================================================================
+
+#[test]
+fn verify_with_inconsistent_constraint_degree() {
+    let (proof, result) = build_fib_proof(8);
+    assert!(crate::verify::<FibAir>(proof.clone(), result).is_ok());
+
+    // the AIR is rejected before any of the proof components are checked
+    let expected = Err(VerifierError::InconsistentConstraintDegree {
+        declared: 2,
+        actual: 1,
+    });
+    assert_eq!(
+        expected,
+        crate::verify::<OverstatedDegreeFibAir>(proof.clone(), result)
+    );
+    assert_eq!(
+        expected,
+        crate::verify_with_coin::<
+            OverstatedDegreeFibAir,
+            RandomCoin<BaseElement, Blake3_256<BaseElement>>,
+        >(proof, result)
+    );
+}
+
+// OVERSTATED DEGREE FIBONACCI AIR
+// ================================================================================================
+
+/// Same as Fibonacci AIR but with the max constraint degree declared to be greater than the
+/// degrees of its transition constraints.
+struct OverstatedDegreeFibAir(FibAir);
+
+impl Air for OverstatedDegreeFibAir {
+    type BaseElement = BaseElement;
+    type PublicInputs = BaseElement;
+
+    const MAX_CONSTRAINT_DEGREE: usize = 2;
+
+    fn new(trace_info: TraceInfo, pub_inputs: BaseElement, options: ProofOptions) -> Self {
+        OverstatedDegreeFibAir(FibAir::new(trace_info, pub_inputs, options))
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseElement> {
+        self.0.context()
+    }
+
+    fn evaluate_transition<E: FieldElement<BaseField = Self::BaseElement>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        self.0.evaluate_transition(frame, periodic_values, result);
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseElement>> {
+        self.0.get_assertions()
+    }
+}
@@ -180,6 +180,10 @@
 //!     type BaseElement = BaseElement;
 //!     type PublicInputs = PublicInputs;
 //!
+//!     // Our only transition constraint involves cubing the register value, and thus, it has
+//!     // degree 3.
+//!     const MAX_CONSTRAINT_DEGREE: usize = 3;
+//!
 //!     // Here, we'll construct a new instance of our computation which is defined by 3
 //!     // parameters: starting value, number of steps, and the end result. Another way to
 //!     // think about it is that an instance of our computation is a specific invocation of
@@ -293,6 +297,7 @@
 //! # impl Air for WorkAir {
 //! #     type BaseElement = BaseElement;
 //! #     type PublicInputs = PublicInputs;
+//! #     const MAX_CONSTRAINT_DEGREE: usize = 3;
 //! #
 //! #     fn new(trace_info: TraceInfo, pub_inputs: PublicInputs, options: ProofOptions) -> Self {
 //! #         assert_eq!(1, trace_info.width());
@@ -341,7 +346,7 @@
 //! let options = ProofOptions::new(
 //!     32, // number of queries
 //!     8,  // blowup factor
-//!     0,  // grinding factor
+//!     16, // grinding factor
 //!     HashFunction::Blake3_256,
 //!     FieldExtension::None,
 //!     8,   // FRI folding factor
@@ -383,12 +388,30 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+pub use air_derive::Air;
+#[cfg(feature = "concurrent")]
+pub use prover::rayon;
 pub use prover::{
-    crypto, iterators, math, prove, Air, AirContext, Assertion, BoundaryConstraint,
-    BoundaryConstraintGroup, ByteReader, ByteWriter, ConstraintCompositionCoefficients,
-    ConstraintDivisor, DeepCompositionCoefficients, Deserializable, DeserializationError,
-    EvaluationFrame, ExecutionTrace, ExecutionTraceFragment, FieldExtension, HashFunction,
-    ProofOptions, ProverError, Serializable, StarkProof, TraceInfo, TransitionConstraintDegree,
-    TransitionConstraintGroup,
+    crypto, eval_constraint_poly_at, fri, iterators, math, prove, prove_instrumented, prove_many,
+    prove_with_aux_segment, prove_with_coin, prove_with_observer, register_air, validate_trace,
+    Air, AirContext, Assertion, AuxSegmentBuilder, BoundaryConstraint, BoundaryConstraintGroup,
+    ByteReader, ByteWriter, CombinationMode, Commitments, CompositeAir, CompositePublicInputs,
+    CompositionCommitment, ConcurrencyConfig, ConstraintCommitment,
+    ConstraintCompositionCoefficients, ConstraintDivisor, ConstraintGroup, ConstraintViolation,
+    Context, CountingWriter, DeepCompositionCoefficients, Deserializable, DeserializationError,
+    EvaluationError, EvaluationFrame, ExecutionTrace, ExecutionTraceFragment, FieldExtension,
+    FriRemainderEncoding, HashFunction, InteractiveProver, LagrangeBoundaryConstraints,
+    MigrationError, OodFrame, PaddingConstraintGroup, ProofOptions, ProverError, ProvingObserver,
+    ProvingStage, Queries, QuerySet, Serializable, StackedAir, StackedPublicInputs, StarkProof,
+    TaggedAir, TaggedProof, TraceInfo, TracePolyTable, TraceValidationReport,
+    TransitionConstraintDegree, TransitionConstraintGroup, VerificationKey,
+    AUX_TRACE_COMMITMENT_TAG, CONSTRAINT_COMMITMENT_TAG, OOD_EVALUATIONS_TAG, OOD_FRAME_TAG,
+    TRACE_COMMITMENT_TAG,
+};
+#[cfg(feature = "std")]
+pub use prover::{prove_with_stats, ProofStats};
+pub use verifier::{
+    verify, verify_and_get_query_positions, verify_batch, verify_many, verify_with_coin,
+    verify_with_key, verify_with_meta, verify_with_options, AirRegistry, SanityBounds,
+    VerificationOptions, VerificationResult, VerifierError,
 };
-pub use verifier::{verify, VerifierError};